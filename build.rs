@@ -0,0 +1,53 @@
+//! Records what a build was made from, for `tbdflow --version`.
+
+use chrono::{DateTime, Utc};
+use std::process::Command;
+
+/// The commit being built: from the `.cargo_vcs_info.json` that
+/// `cargo package` leaves in a published crate, or else from git.
+fn commit_hash() -> Option<String> {
+    let packaged = std::fs::read_to_string(".cargo_vcs_info.json")
+        .ok()
+        .and_then(|info| {
+            let (_, rest) = info.split_once("\"sha1\"")?;
+            let sha = rest.split('"').nth(1)?;
+            Some(sha[..10.min(sha.len())].to_string())
+        });
+    packaged.or_else(|| {
+        Command::new("git")
+            .args(["rev-parse", "--short=10", "HEAD"])
+            .output()
+            .ok()
+            .filter(|out| out.status.success())
+            .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+            .filter(|hash| !hash.is_empty())
+    })
+}
+
+/// Today, or `SOURCE_DATE_EPOCH` for reproducible builds.
+fn build_date() -> String {
+    let date = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .and_then(|secs| DateTime::<Utc>::from_timestamp(secs, 0))
+        .unwrap_or_else(Utc::now);
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn main() {
+    // A path that does not exist would rerun this on every build.
+    if std::path::Path::new(".git/HEAD").exists() {
+        println!("cargo:rerun-if-changed=.git/HEAD");
+        println!("cargo:rerun-if-changed=.git/refs");
+    }
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    println!(
+        "cargo:rustc-env=BUILD_INFO_COMMIT={}",
+        commit_hash().unwrap_or_else(|| "unknown".to_string())
+    );
+    println!("cargo:rustc-env=BUILD_INFO_DATE={}", build_date());
+    println!(
+        "cargo:rustc-env=BUILD_INFO_TARGET={}",
+        std::env::var("TARGET").unwrap_or_default()
+    );
+}
@@ -0,0 +1,233 @@
+use crate::commands::{AuditCommitResponse, AuditResponse, TbdResponse};
+use crate::commit::VERSION_TRAILER_KEY;
+use crate::config::Config;
+use crate::experiment::EXPERIMENT_TRAILER_KEY;
+use crate::git::{self, RunOpts};
+use crate::journal::{self, JournalEvent};
+use crate::review::{FIXES_REVIEW_TRAILER_KEY, SKIP_REVIEW_TRAILER};
+use anyhow::Result;
+use colored::Colorize;
+use git_conventional::Commit;
+use std::path::PathBuf;
+
+/// How a trunk commit appears to have been made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provenance {
+    /// Carries a tbdflow trailer or section, or is named in the journal.
+    Tbdflow,
+    /// Conventional (or a plain `git revert`) but with no trace of tbdflow.
+    Conventional,
+    /// Neither conventional nor traced: made with raw git.
+    Manual,
+}
+
+impl Provenance {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Provenance::Tbdflow => "tbdflow",
+            Provenance::Conventional => "conventional",
+            Provenance::Manual => "manual",
+        }
+    }
+}
+
+/// True when the message carries something only tbdflow writes.
+fn has_tbdflow_marker(message: &str) -> bool {
+    message.lines().map(str::trim).any(|line| {
+        line == "Intent Log:"
+            || line.eq_ignore_ascii_case(SKIP_REVIEW_TRAILER)
+            || line.split_once(':').is_some_and(|(key, _)| {
+                [
+                    VERSION_TRAILER_KEY,
+                    FIXES_REVIEW_TRAILER_KEY,
+                    EXPERIMENT_TRAILER_KEY,
+                ]
+                .iter()
+                .any(|k| key.trim().eq_ignore_ascii_case(k))
+            })
+    })
+}
+
+/// Classifies one commit. `in_journal` is whether the local journal mentions it.
+pub fn classify_commit(message: &str, in_journal: bool) -> Provenance {
+    let subject = message.lines().next().unwrap_or_default().trim();
+    if in_journal || has_tbdflow_marker(message) {
+        Provenance::Tbdflow
+    } else if Commit::parse(subject).is_ok() || message.contains("This reverts commit ") {
+        Provenance::Conventional
+    } else {
+        Provenance::Manual
+    }
+}
+
+fn journal_commits(entries: &[journal::JournalEntry]) -> Vec<String> {
+    entries
+        .iter()
+        .map(|entry| match &entry.event {
+            JournalEvent::ReviewSkipped { commit, .. }
+            | JournalEvent::DodSkipped { commit, .. }
+            | JournalEvent::ReviewApproved { commit } => commit.clone(),
+        })
+        .filter(|commit| !commit.is_empty())
+        .collect()
+}
+
+/// Parses `git::get_log_messages` output into classified commits, newest first.
+pub fn audit_log(log: &str, journal_commits: &[String]) -> Vec<(AuditCommitResponse, Provenance)> {
+    log.split('\x1e')
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(4, '\x1f');
+            let (sha, date, author, message) = (
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+            );
+            if sha.is_empty() {
+                return None;
+            }
+            let in_journal = journal_commits.iter().any(|c| sha.starts_with(c.as_str()));
+            let provenance = classify_commit(message, in_journal);
+            Some((
+                AuditCommitResponse {
+                    sha: git::short_hash(sha).to_string(),
+                    date: date.to_string(),
+                    author: author.to_string(),
+                    subject: message.lines().next().unwrap_or_default().to_string(),
+                    provenance: provenance.as_str().to_string(),
+                },
+                provenance,
+            ))
+        })
+        .collect()
+}
+
+/// Reports trunk commits that bypassed tbdflow, as a measure of adoption.
+pub fn handle_audit(config: &Config, since: &str, json: bool, opts: RunOpts) -> Result<()> {
+    let trunk = format!("origin/{}", config.main_branch_name);
+    let log = git::get_log_messages(&trunk, Some(since), opts)?;
+    let git_dir = PathBuf::from(git::get_git_dir(opts)?);
+    let journal = journal_commits(&journal::read_entries(&git_dir)?);
+    let commits = audit_log(&log, &journal);
+
+    let count = |p: Provenance| commits.iter().filter(|(_, c)| *c == p).count();
+    let (tbdflow, conventional, manual) = (
+        count(Provenance::Tbdflow),
+        count(Provenance::Conventional),
+        count(Provenance::Manual),
+    );
+    let adoption_percent = if commits.is_empty() {
+        0
+    } else {
+        tbdflow * 100 / commits.len()
+    };
+    let flagged: Vec<AuditCommitResponse> = commits
+        .iter()
+        .filter(|(_, p)| *p == Provenance::Manual)
+        .map(|(commit, _)| commit.clone())
+        .collect();
+
+    if json {
+        let response = AuditResponse {
+            trunk,
+            since: since.to_string(),
+            total: commits.len(),
+            tbdflow,
+            conventional,
+            manual,
+            adoption_percent,
+            flagged,
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(response))?
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("--- Trunk audit for '{}' (since {}) ---", trunk, since).blue()
+    );
+    if commits.is_empty() {
+        println!(
+            "{}",
+            "No commits found in the specified time range.".yellow()
+        );
+        return Ok(());
+    }
+    println!(
+        "{} commit(s): {} made with tbdflow, {} conventional without a tbdflow trace, {} bypassed tbdflow",
+        commits.len(),
+        tbdflow,
+        conventional,
+        manual
+    );
+    println!("Adoption: {}%", adoption_percent.to_string().bold());
+    if flagged.is_empty() {
+        println!("{}", "No commits bypassed tbdflow.".green());
+        return Ok(());
+    }
+    println!("\n{}", "Commits that bypassed tbdflow:".yellow().bold());
+    for commit in &flagged {
+        println!(
+            "  {} {} {} {}",
+            commit.sha.yellow(),
+            commit.date,
+            format!("({})", commit.author).dimmed(),
+            commit.subject
+        );
+    }
+    println!(
+        "\n{}",
+        "Merge commits are not audited. Commits made with 'tbdflow commit' keep the conventional format and trailers."
+            .dimmed()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_by_markers_format_and_journal() {
+        assert_eq!(
+            classify_commit("feat: add cache\n\nIntent Log:\n- try lru", false),
+            Provenance::Tbdflow
+        );
+        assert_eq!(
+            classify_commit("fix: flush\n\nFixes-Review: abc1234", false),
+            Provenance::Tbdflow
+        );
+        assert_eq!(
+            classify_commit("quick fix\n\nTbdflow-Version: 0.30.0", false),
+            Provenance::Tbdflow
+        );
+        assert_eq!(
+            classify_commit("feat: add cache", false),
+            Provenance::Conventional
+        );
+        assert_eq!(
+            classify_commit(
+                "Revert \"feat: add cache\"\n\nThis reverts commit abc.",
+                false
+            ),
+            Provenance::Conventional
+        );
+        assert_eq!(classify_commit("quick fix", false), Provenance::Manual);
+        assert_eq!(classify_commit("quick fix", true), Provenance::Tbdflow);
+    }
+
+    #[test]
+    fn parses_log_records_and_matches_journal_prefixes() {
+        let log = "abc1234def\x1f2026-10-17\x1fAda\x1fquick fix\n\x1e\n\
+                   fed4321cba\x1f2026-10-16\x1fBob\x1ffeat: add cache\n\x1e";
+        let commits = audit_log(log, &["fed4321".to_string()]);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].0.sha, "abc1234");
+        assert_eq!(commits[0].0.subject, "quick fix");
+        assert_eq!(commits[0].1, Provenance::Manual);
+        assert_eq!(commits[1].1, Provenance::Tbdflow);
+    }
+}
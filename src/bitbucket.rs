@@ -0,0 +1,112 @@
+//! Bitbucket Cloud's REST API, for the `bitbucket` review strategy.
+//! Requests authenticate with a username and app password.
+
+use crate::config::BitbucketConfig;
+use crate::git::{self, RunOpts};
+use crate::rest::{self, Auth};
+use anyhow::{Result, anyhow, bail};
+use reqwest::Method;
+use serde_json::Value;
+
+const API: &str = "https://api.bitbucket.org/2.0";
+
+pub const USERNAME_VAR: &str = "BITBUCKET_USERNAME";
+pub const APP_PASSWORD_VAR: &str = "BITBUCKET_APP_PASSWORD";
+
+/// A repository on Bitbucket Cloud.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bitbucket {
+    pub workspace: String,
+    pub repo: String,
+    credentials: Option<(String, String)>,
+}
+
+fn setting(var: &str, configured: &Option<String>) -> Option<String> {
+    std::env::var(var)
+        .ok()
+        .or_else(|| configured.clone())
+        .filter(|v| !v.is_empty())
+}
+
+impl Bitbucket {
+    /// The repository behind `origin`. Credentials come from the
+    /// environment first, then `review.bitbucket`.
+    pub fn from_config(config: &BitbucketConfig, opts: RunOpts) -> Result<Self> {
+        let remote = git::get_remote_url(opts.for_reads()).unwrap_or_default();
+        let (_, workspace, repo) = rest::split_remote(&remote).ok_or_else(|| {
+            anyhow!(
+                "Cannot tell the Bitbucket repository from origin ('{}'). Add an 'origin' remote such as https://bitbucket.org/workspace/repo.",
+                remote
+            )
+        })?;
+        let credentials = setting(USERNAME_VAR, &config.username)
+            .zip(setting(APP_PASSWORD_VAR, &config.app_password));
+        Ok(Self {
+            workspace,
+            repo,
+            credentials,
+        })
+    }
+
+    /// The repository's web page, which commit links hang off.
+    pub fn repo_url(&self) -> String {
+        format!("https://bitbucket.org/{}/{}", self.workspace, self.repo)
+    }
+
+    /// `path` under the repository's API, e.g. `/issues`.
+    pub fn repo_path(&self, path: &str) -> String {
+        format!("/repositories/{}/{}{}", self.workspace, self.repo, path)
+    }
+
+    /// Errors unless there are credentials to write with.
+    pub fn require_credentials(&self) -> Result<()> {
+        if self.credentials.is_some() {
+            return Ok(());
+        }
+        bail!(
+            "No Bitbucket credentials. Set {} and {}, or review.bitbucket.username and review.bitbucket.app_password (in .tbdflow.local.yml or your user config).",
+            USERNAME_VAR,
+            APP_PASSWORD_VAR
+        )
+    }
+
+    /// Sends one request to the 2.0 API and returns the JSON it answered
+    /// with (`Null` for an empty body, and for everything under `--dry-run`).
+    pub fn call(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+        opts: RunOpts,
+    ) -> Result<Value> {
+        let auth = self
+            .credentials
+            .as_ref()
+            .map(|(user, password)| Auth::Basic(user, password));
+        rest::call("Bitbucket", API, method, path, auth, body, opts)
+    }
+
+    pub fn get(&self, path: &str, opts: RunOpts) -> Result<Value> {
+        self.call(Method::GET, path, None, opts)
+    }
+
+    pub fn post(&self, path: &str, body: &Value, opts: RunOpts) -> Result<Value> {
+        self.call(Method::POST, path, Some(body), opts)
+    }
+
+    pub fn put(&self, path: &str, body: &Value, opts: RunOpts) -> Result<Value> {
+        self.call(Method::PUT, path, Some(body), opts)
+    }
+
+    /// True when the repository can be read with these credentials. Checked
+    /// under `--dry-run` too.
+    pub fn is_reachable(&self, opts: RunOpts) -> bool {
+        self.credentials.is_some() && self.get(&self.repo_path(""), opts.for_reads()).is_ok()
+    }
+
+    /// The nickname the credentials belong to.
+    pub fn current_user(&self, opts: RunOpts) -> Option<String> {
+        let user = self.get("/user", opts).ok()?;
+        user["nickname"].as_str().map(str::to_string)
+    }
+}
@@ -1,44 +1,278 @@
-use crate::config::Config;
+use crate::commands::{BranchListResponse, TbdResponse};
+use crate::config::{AutoSync, Config};
 use crate::git::{GitError, RunOpts};
-use crate::{commands, config, git, intent};
+use crate::{commands, config, git, intent, journal, prompt, review, version, wizard};
 use anyhow::Result;
+use chrono::Utc;
 use colored::Colorize;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 pub fn get_default_branch_name(config: &Config) -> &str {
     config.main_branch_name.as_str()
 }
 
-pub fn handle_branch(
-    r#type: Option<String>,
+pub struct BranchParams {
+    pub r#type: String,
+    pub name: String,
+    pub issue: Option<String>,
+    pub from_commit: Option<String>,
+    /// Allow `from_commit` to point at a commit that is not on `origin/main`.
+    pub allow_non_main: bool,
+    /// Branch from local main without pulling, regardless of `branch.auto_sync`.
+    pub no_sync: bool,
+    /// What the branch is for; stored as the git branch description.
+    pub description: Option<String>,
+}
+
+/// Context recorded when `tbdflow branch` creates a branch. It lives in the
+/// branch's own `branch.<name>` section of `.git/config`, so git drops it
+/// when the branch is deleted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BranchMetadata {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub branch_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl BranchMetadata {
+    /// Config keys under `branch.<name>`. `description` is git's own key,
+    /// so `git branch --edit-description` shows and edits the same text.
+    const TYPE_KEY: &str = "tbdflow-type";
+    const ISSUE_KEY: &str = "tbdflow-issue";
+    const CREATOR_KEY: &str = "tbdflow-creator";
+    const CREATED_AT_KEY: &str = "tbdflow-created-at";
+    const DESCRIPTION_KEY: &str = "description";
+
+    pub fn save(&self, branch: &str, opts: RunOpts) -> Result<()> {
+        let fields = [
+            (Self::TYPE_KEY, &self.branch_type),
+            (Self::ISSUE_KEY, &self.issue),
+            (Self::CREATOR_KEY, &self.creator),
+            (Self::CREATED_AT_KEY, &self.created_at),
+            (Self::DESCRIPTION_KEY, &self.description),
+        ];
+        for (key, value) in fields {
+            if let Some(value) = value {
+                git::set_config_value(&format!("branch.{}.{}", branch, key), value, opts)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// One line for listings, e.g. `feat, PROJ-1, by Ada <ada@x> on 2026-10-17: "New dashboard"`.
+    pub fn summary(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        parts.extend(self.branch_type.clone());
+        parts.extend(self.issue.clone());
+        let mut line = parts.join(", ");
+        if let Some(creator) = &self.creator {
+            line = format!("{} by {}", line, creator);
+        }
+        if let Some(date) = self.created_at.as_deref().and_then(|d| d.get(..10)) {
+            line = format!("{} on {}", line, date);
+        }
+        let line = line.trim().trim_start_matches(", ").to_string();
+        match &self.description {
+            Some(description) if line.is_empty() => format!("\"{}\"", description),
+            Some(description) => format!("{}: \"{}\"", line, description),
+            None => line,
+        }
+    }
+}
+
+/// Builds the metadata map from `branch.*` config entries, keyed by branch name.
+/// Branches without any tbdflow-relevant keys are left out.
+fn parse_branch_metadata(entries: &[(String, String)]) -> HashMap<String, BranchMetadata> {
+    let mut all: HashMap<String, BranchMetadata> = HashMap::new();
+    for (key, value) in entries {
+        // Branch names may contain dots; the variable name never does.
+        let Some((branch, var)) = key
+            .strip_prefix("branch.")
+            .and_then(|rest| rest.rsplit_once('.'))
+        else {
+            continue;
+        };
+        let value = value.trim_end();
+        if value.is_empty() {
+            continue;
+        }
+        let metadata = all.entry(branch.to_string()).or_default();
+        let value = Some(value.to_string());
+        match var {
+            BranchMetadata::TYPE_KEY => metadata.branch_type = value,
+            BranchMetadata::ISSUE_KEY => metadata.issue = value,
+            BranchMetadata::CREATOR_KEY => metadata.creator = value,
+            BranchMetadata::CREATED_AT_KEY => metadata.created_at = value,
+            BranchMetadata::DESCRIPTION_KEY => metadata.description = value,
+            _ => {}
+        }
+    }
+    all.retain(|_, metadata| *metadata != BranchMetadata::default());
+    all
+}
+
+/// Metadata for every local branch that has some.
+pub fn load_all_branch_metadata(opts: RunOpts) -> HashMap<String, BranchMetadata> {
+    parse_branch_metadata(&git::get_branch_config_entries(opts))
+}
+
+/// The full branch name for a branch of `type` called `name`, following
+/// `issue_handling.strategy`.
+fn branch_name_for(
     config: &Config,
-    name: Option<String>,
-    issue: Option<String>,
-    from_commit: Option<String>,
-    opts: RunOpts,
-) -> Result<()> {
+    r#type: &str,
+    name: &str,
+    issue: Option<&str>,
+) -> Result<String> {
+    let prefix = commands::get_branch_prefix_or_error(&config.branch_types, r#type)?;
+    Ok(match config.issue_handling.strategy {
+        config::IssueHandlingStrategy::BranchName => {
+            let issue_part = issue.map_or("".to_string(), |i| format!("{}-", i));
+            format!("{}{}{}", prefix, issue_part, name)
+        }
+        config::IssueHandlingStrategy::CommitScope => format!("{}{}", prefix, name),
+    })
+}
+
+/// Run before commands that need a named branch. On a detached HEAD, explains
+/// the state and offers to create a short-lived branch right where HEAD is,
+/// keeping any commits and uncommitted changes made there.
+pub fn ensure_on_branch(config: &Config, json: bool, opts: RunOpts) -> Result<()> {
+    let lookup = opts.for_reads();
+    // A rebase detaches HEAD on purpose; the command reports it in its own way.
+    if !git::is_detached_head(opts)? || git::check_git_operation_in_progress(lookup)?.is_some() {
+        return Ok(());
+    }
+    let head = git::get_head_commit_hash(lookup)?;
+    let short = git::short_hash(&head);
+    let message = format!(
+        "HEAD is detached at {}. Commits made here are on no branch and are easy to lose.",
+        short
+    );
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::<()>::err_with_code(
+                &message,
+                commands::ErrorCode::DetachedHead
+            ))?
+        );
+        std::process::exit(1);
+    }
+
+    println!("{}", message.yellow());
+    if let Ok(subject) = git::get_commit_subject(&head, lookup) {
+        println!("{}", format!("  {} {}", short, subject).dimmed());
+    }
+    let hint = format!(
+        "Run 'git switch {}' to go back to trunk, or 'tbdflow branch' to start a branch.",
+        config.main_branch_name
+    );
+    // The branch wizard needs a terminal, so without one there is nothing to offer.
+    if !prompt::is_interactive()
+        || !prompt::confirm("Create a short-lived branch from here?", Some(true), opts)?
+    {
+        return Err(anyhow::anyhow!("HEAD is detached. {}", hint));
+    }
+
+    let wizard = wizard::run_branch_wizard_at_head(config)?;
+    let branch_name = branch_name_for(
+        config,
+        &wizard.branch_type,
+        &wizard.name,
+        wizard.issue.as_deref(),
+    )?;
+    git::create_branch(&branch_name, None, opts)?;
+    BranchMetadata {
+        branch_type: Some(wizard.branch_type),
+        issue: wizard.issue,
+        creator: Some(git::get_user_identity(opts)),
+        created_at: Some(Utc::now().to_rfc3339()),
+        description: wizard.description,
+    }
+    .save(&branch_name, opts)?;
+    git::push_set_upstream(&branch_name, opts)?;
+    println!(
+        "{}",
+        format!("Switched to new branch '{}' at {}.\n", branch_name, short).green()
+    );
+    Ok(())
+}
+
+pub fn handle_branch(config: &Config, params: BranchParams, opts: RunOpts) -> Result<()> {
     println!(
         "{}",
         "--- Creating short-lived branch ---".to_string().blue()
     );
 
     let main_branch_name = get_default_branch_name(config);
-    let prefix = commands::get_branch_prefix_or_error(&config.branch_types, &r#type.unwrap())?;
-
-    let branch_name = match config.issue_handling.strategy {
-        config::IssueHandlingStrategy::BranchName => {
-            let issue_part = issue.map_or("".to_string(), |i| format!("{}-", i));
-            format!("{}{}{}", prefix, issue_part, name.unwrap())
-        }
-        config::IssueHandlingStrategy::CommitScope => {
-            format!("{}{}", prefix, name.unwrap())
-        }
-    };
+    let branch_name = branch_name_for(
+        config,
+        &params.r#type,
+        &params.name,
+        params.issue.as_deref(),
+    )?;
+    let from_commit = params.from_commit;
 
     git::is_working_directory_clean(opts)?;
     git::checkout_main(opts, main_branch_name)?;
-    git::pull_latest_with_rebase(opts)?;
+    let sync_mode = if params.no_sync {
+        AutoSync::Never
+    } else {
+        config.branch.auto_sync
+    };
+    let synced = sync_main(sync_mode, opts)?;
+    if let Some(point) = &from_commit {
+        ensure_from_point_on_main(point, main_branch_name, params.allow_non_main, opts)?;
+    }
+    if let Some(existing) = find_similar_branch(
+        &branch_name,
+        &git::list_branch_names(opts)?,
+        main_branch_name,
+    ) {
+        println!(
+            "{}",
+            format!(
+                "A branch with a similar name already exists: '{}'",
+                existing
+            )
+            .yellow()
+        );
+        if prompt::confirm(
+            &format!(
+                "Switch to '{}' instead of creating '{}'?",
+                existing, branch_name
+            ),
+            Some(false),
+            opts,
+        )? {
+            git::checkout_branch(&existing, opts)?;
+            println!(
+                "\n{}",
+                format!("Success! Switched to existing branch: '{}'", existing).green()
+            );
+            return Ok(());
+        }
+    }
+    print_base_commit(main_branch_name, from_commit.as_deref(), synced, opts)?;
     git::create_branch(&branch_name, from_commit.as_deref(), opts)?;
+    BranchMetadata {
+        branch_type: Some(params.r#type),
+        issue: params.issue,
+        creator: Some(git::get_user_identity(opts)),
+        created_at: Some(Utc::now().to_rfc3339()),
+        description: params.description,
+    }
+    .save(&branch_name, opts)?;
     git::push_set_upstream(&branch_name, opts)?;
     println!(
         "\n{}",
@@ -47,7 +281,426 @@ pub fn handle_branch(
     Ok(())
 }
 
-pub fn handle_complete(r#type: String, name: String, config: &Config, opts: RunOpts) -> Result<()> {
+/// Lists local short-lived branches with their recorded metadata.
+pub fn handle_branch_list(config: &Config, json: bool, opts: RunOpts) -> Result<()> {
+    let mut metadata = load_all_branch_metadata(opts);
+    let branches: Vec<BranchListResponse> = git::list_local_branch_names(opts)?
+        .into_iter()
+        .filter(|branch| branch != get_default_branch_name(config))
+        .map(|branch| BranchListResponse {
+            metadata: metadata.remove(&branch),
+            branch,
+        })
+        .collect();
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(branches))?
+        );
+        return Ok(());
+    }
+
+    println!("{}", "--- Short-lived branches ---".to_string().blue());
+    if branches.is_empty() {
+        println!("{}", "No short-lived branches.".green());
+    }
+    for entry in branches {
+        println!("  - {}", entry.branch);
+        if let Some(meta) = entry.metadata {
+            println!("{}", format!("      {}", meta.summary()).dimmed());
+        }
+    }
+    Ok(())
+}
+
+/// Brings local main up to date according to `mode`. Returns whether main
+/// was pulled from origin.
+fn sync_main(mode: AutoSync, opts: RunOpts) -> Result<bool> {
+    match mode {
+        AutoSync::Always => {
+            git::pull_latest_with_rebase(opts)?;
+            Ok(true)
+        }
+        AutoSync::IfOnline => {
+            if git::fetch_origin(opts).is_err() {
+                println!(
+                    "{}",
+                    "Warning: could not reach origin; branching from local main.".yellow()
+                );
+                return Ok(false);
+            }
+            git::pull_latest_with_rebase(opts)?;
+            Ok(true)
+        }
+        AutoSync::Never => Ok(false),
+    }
+}
+
+/// Tells the user exactly which commit the new branch starts from.
+fn print_base_commit(
+    main_branch: &str,
+    from_commit: Option<&str>,
+    synced: bool,
+    opts: RunOpts,
+) -> Result<()> {
+    let (rev, source) = match from_commit {
+        Some(point) => (point, format!("--from-commit {}", point)),
+        None if synced => (main_branch, format!("{}, synced with origin", main_branch)),
+        None => (main_branch, format!("local {}, not synced", main_branch)),
+    };
+    let hash = git::resolve_commit_hash(rev, opts)?;
+    println!(
+        "{}",
+        format!("Base commit: {} ({})", git::short_hash(&hash), source).dimmed()
+    );
+    Ok(())
+}
+
+/// Short-lived branches should fork from trunk. A from-point that is not on
+/// `origin/main` (a stale or unmerged branch) needs an explicit opt-in.
+fn ensure_from_point_on_main(
+    point: &str,
+    main_branch: &str,
+    allow_non_main: bool,
+    opts: RunOpts,
+) -> Result<()> {
+    if git::is_ancestor_of_main(point, main_branch, opts)? {
+        return Ok(());
+    }
+    if !allow_non_main {
+        return Err(anyhow::anyhow!(
+            "'{}' is not on 'origin/{}'. Short-lived branches should start from trunk; pass --allow-non-main to branch from it anyway.",
+            point,
+            main_branch
+        ));
+    }
+    println!(
+        "{}",
+        format!(
+            "Warning: branching from '{}', which is not on 'origin/{}' (--allow-non-main).",
+            point, main_branch
+        )
+        .yellow()
+    );
+    Ok(())
+}
+
+/// Finds an existing branch whose name is a near-duplicate of `branch_name`,
+/// e.g. a typo like `feat/user-profil-page` for `feat/user-profile-page`.
+/// Names that differ in their digits (versions, issue IDs) are never treated
+/// as duplicates.
+fn find_similar_branch(
+    branch_name: &str,
+    existing: &[String],
+    main_branch: &str,
+) -> Option<String> {
+    let max_distance = (branch_name.len() / 8).min(2);
+    let digits = |s: &str| s.chars().filter(char::is_ascii_digit).collect::<String>();
+
+    existing
+        .iter()
+        .filter(|candidate| candidate.as_str() != main_branch)
+        .filter(|candidate| digits(candidate) == digits(branch_name))
+        .map(|candidate| (edit_distance(branch_name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.clone())
+}
+
+/// Levenshtein distance between two strings, compared case-insensitively.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// The merge commit message for `complete`: git's default subject with the
+/// branch description as the body. `None` (git's default) when there is no description.
+pub fn merge_commit_message(
+    branch_name: &str,
+    metadata: Option<&BranchMetadata>,
+) -> Option<String> {
+    let description = metadata?.description.as_deref()?.trim();
+    if description.is_empty() {
+        return None;
+    }
+    Some(format!("Merge branch '{}'\n\n{}", branch_name, description))
+}
+
+/// Branch types that have a local or remote branch matching `name`, using the
+/// same rule as `complete` (the type's prefix, ending with the name).
+fn types_with_branch_named(name: &str, config: &Config, opts: RunOpts) -> Vec<String> {
+    let branches = git::list_branch_names(opts).unwrap_or_default();
+    let name = name.to_lowercase();
+    let mut types: Vec<String> = config
+        .branch_types
+        .iter()
+        .filter(|(_, prefix)| {
+            let prefix = prefix.to_lowercase();
+            branches.iter().any(|b| {
+                let b = b.to_lowercase();
+                b.starts_with(&prefix) && b.ends_with(&name)
+            })
+        })
+        .map(|(branch_type, _)| branch_type.clone())
+        .collect();
+    types.sort();
+    types
+}
+
+/// Values substituted into the `complete.merge_message` template.
+#[derive(Debug, Default)]
+pub struct MergeTemplateVars {
+    pub branch: String,
+    pub branch_type: String,
+    pub name: String,
+    pub issue: String,
+    pub description: String,
+    /// Subjects of the merged commits, oldest first.
+    pub subjects: Vec<String>,
+    pub dod: String,
+}
+
+/// Renders a merge commit template. `{{subjects}}` becomes one `- subject`
+/// line per merged commit. A line whose placeholders all come out empty is
+/// dropped, so `Refs: {{issue}}` disappears on a branch without an issue.
+pub fn render_merge_template(template: &str, vars: &MergeTemplateVars) -> String {
+    let subjects = vars
+        .subjects
+        .iter()
+        .map(|s| format!("- {}", s))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let commit_count = vars.subjects.len().to_string();
+    let values = [
+        ("{{branch}}", vars.branch.as_str()),
+        ("{{type}}", vars.branch_type.as_str()),
+        ("{{name}}", vars.name.as_str()),
+        ("{{issue}}", vars.issue.as_str()),
+        ("{{description}}", vars.description.as_str()),
+        ("{{subjects}}", subjects.as_str()),
+        ("{{commit_count}}", commit_count.as_str()),
+        ("{{dod}}", vars.dod.as_str()),
+    ];
+
+    let mut lines: Vec<String> = Vec::new();
+    for line in template.lines() {
+        let used: Vec<&(&str, &str)> = values.iter().filter(|(k, _)| line.contains(k)).collect();
+        if !used.is_empty() && used.iter().all(|(_, v)| v.is_empty()) {
+            continue;
+        }
+        let rendered = values
+            .iter()
+            .fold(line.to_string(), |acc, (k, v)| acc.replace(k, v));
+        // Never more than one blank line in a row, e.g. where a line was dropped.
+        if rendered.trim().is_empty() && lines.last().is_some_and(|l| l.trim().is_empty()) {
+            continue;
+        }
+        lines.push(rendered.trim_end().to_string());
+    }
+    lines.join("\n").trim().to_string()
+}
+
+/// A commit on the branch being completed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchCommit {
+    pub sha: String,
+    pub message: String,
+}
+
+impl BranchCommit {
+    pub fn subject(&self) -> &str {
+        self.message.lines().next().unwrap_or_default()
+    }
+}
+
+/// Commits in `range`, oldest first.
+fn branch_commits(range: &str, opts: RunOpts) -> Result<Vec<BranchCommit>> {
+    let log = git::get_range_messages(range, opts)?;
+    Ok(log
+        .split('\x1e')
+        .filter_map(|record| {
+            let (sha, message) = record.trim_start_matches('\n').split_once('\x1f')?;
+            Some(BranchCommit {
+                sha: sha.to_string(),
+                message: message.trim().to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Unticked DoD items in the `TODO:` footer `commit` writes when checklist
+/// items are left open, e.g. `Update docs` for `- [ ] Update docs`.
+pub fn open_todo_items(message: &str) -> Vec<String> {
+    message
+        .lines()
+        .skip_while(|line| line.trim() != "TODO:")
+        .filter_map(|line| line.trim_start().strip_prefix("- [ ]"))
+        .map(|item| item.trim().to_string())
+        .collect()
+}
+
+/// Lists open DoD items on the branch and asks before merging them to trunk.
+/// `--accept-todos` (or `--yes`) merges anyway; without a terminal it stops.
+fn confirm_open_todos(commits: &[BranchCommit], accept_todos: bool, opts: RunOpts) -> Result<()> {
+    let with_todos: Vec<(&BranchCommit, Vec<String>)> = commits
+        .iter()
+        .map(|c| (c, open_todo_items(&c.message)))
+        .filter(|(_, items)| !items.is_empty())
+        .collect();
+    if with_todos.is_empty() {
+        return Ok(());
+    }
+
+    let total: usize = with_todos.iter().map(|(_, items)| items.len()).sum();
+    println!(
+        "{}",
+        format!("This branch has {} open Definition of Done item(s):", total)
+            .yellow()
+            .bold()
+    );
+    for (commit, items) in &with_todos {
+        println!(
+            "  {} {}",
+            git::short_hash(&commit.sha).yellow(),
+            commit.subject()
+        );
+        for item in items {
+            println!("    - [ ] {}", item);
+        }
+    }
+    if accept_todos {
+        println!("{}", "Merging anyway (--accept-todos).".dimmed());
+        return Ok(());
+    }
+    if prompt::confirm("Merge these open items to trunk anyway?", Some(false), opts)? {
+        return Ok(());
+    }
+    Err(anyhow::anyhow!(
+        "Aborted: the branch has open DoD items. Finish them, or pass --accept-todos to merge anyway."
+    ))
+}
+
+/// One-line Definition of Done status for the merged commits: open `TODO:`
+/// items left by `commit`, and commits whose checklist was skipped with
+/// `--no-verify`. Empty when there is no checklist and nothing to report.
+pub fn dod_status(messages: &[String], skipped_commits: usize, has_checklist: bool) -> String {
+    let mut open_items = 0;
+    let mut commits_with_todos = 0;
+    for message in messages {
+        let open = open_todo_items(message).len();
+        if open > 0 {
+            open_items += open;
+            commits_with_todos += 1;
+        }
+    }
+    let mut parts = Vec::new();
+    if open_items > 0 {
+        parts.push(format!(
+            "{} open item(s) in {} commit(s)",
+            open_items, commits_with_todos
+        ));
+    }
+    if skipped_commits > 0 {
+        parts.push(format!(
+            "checklist skipped on {} commit(s)",
+            skipped_commits
+        ));
+    }
+    match (parts.is_empty(), has_checklist) {
+        (false, _) => parts.join(", "),
+        (true, true) => "complete".to_string(),
+        (true, false) => String::new(),
+    }
+}
+
+/// Gathers the template values for completing `branch_name` into `main_branch`.
+fn merge_template_vars(
+    branch_name: &str,
+    r#type: &str,
+    name: &str,
+    metadata: Option<&BranchMetadata>,
+    commits: &[BranchCommit],
+    config: &Config,
+    opts: RunOpts,
+) -> Result<MergeTemplateVars> {
+    let quiet = opts.for_reads();
+    let git_dir = PathBuf::from(git::get_git_dir(quiet)?);
+    let skipped: Vec<String> = journal::read_entries(&git_dir)?
+        .into_iter()
+        .filter_map(|entry| match entry.event {
+            journal::JournalEvent::DodSkipped { commit, .. } => Some(commit),
+            _ => None,
+        })
+        .collect();
+    let skipped_commits = commits
+        .iter()
+        .filter(|commit| {
+            skipped
+                .iter()
+                .any(|c| !c.is_empty() && commit.sha.starts_with(c.as_str()))
+        })
+        .count();
+    let has_checklist = config.commit.dod
+        && config::load_dod_config(quiet).is_ok_and(|dod| !dod.checklist.is_empty());
+    let messages: Vec<String> = commits.iter().map(|c| c.message.clone()).collect();
+
+    Ok(MergeTemplateVars {
+        branch: branch_name.to_string(),
+        branch_type: r#type.to_string(),
+        name: name.to_string(),
+        issue: metadata.and_then(|m| m.issue.clone()).unwrap_or_default(),
+        description: metadata
+            .and_then(|m| m.description.clone())
+            .unwrap_or_default(),
+        subjects: messages
+            .iter()
+            .map(|m| m.lines().next().unwrap_or_default().to_string())
+            .collect(),
+        dod: dod_status(&messages, skipped_commits, has_checklist),
+    })
+}
+
+/// Warns when a release tag would not be the highest version: `changelog`
+/// and `next-version` go by version, so a lower tag is never "the latest".
+fn warn_if_not_newest(tag_name: &str, config: &Config, opts: RunOpts) {
+    let prefix = &config.automatic_tags.release_prefix;
+    let Some(new) = version::Version::from_tag(tag_name, prefix) else {
+        return;
+    };
+    let tags = git::list_merged_tags(opts.for_reads()).unwrap_or_default();
+    if let Some((latest, current)) = version::latest(&tags, prefix, true)
+        && current >= new
+    {
+        println!(
+            "{}",
+            format!(
+                "Warning: '{}' is not newer than '{}', so it will not count as the latest release.",
+                tag_name, latest
+            )
+            .yellow()
+        );
+    }
+}
+
+pub fn handle_complete(
+    r#type: String,
+    name: String,
+    accept_todos: bool,
+    config: &Config,
+    opts: RunOpts,
+) -> Result<()> {
     println!(
         "{}",
         "--- Completing short-lived branch ---".to_string().blue()
@@ -58,24 +711,88 @@ pub fn handle_complete(r#type: String, name: String, config: &Config, opts: RunO
     if name == main_branch_name {
         return Err(GitError::CannotCompleteMainBranch.into());
     }
+    // Check the type before fetching, so a typo fails fast with the valid types.
+    if let Err(e) = commands::get_branch_prefix_or_error(&config.branch_types, &r#type) {
+        let quiet = opts.for_reads();
+        let types = types_with_branch_named(&name, config, quiet);
+        return Err(match types.first() {
+            Some(found) => anyhow::anyhow!(
+                "{}\nA branch named '{}' exists with type {}. Run 'tbdflow complete --type {} --name {}'.",
+                e,
+                name,
+                types.join(", "),
+                found,
+                name
+            ),
+            None => e,
+        });
+    }
 
-    let branch_name = git::find_branch(&name, &r#type, config, opts)?;
+    // A branch that only exists on origin may need a fetch before it shows up.
+    let mut fetched = false;
+    let branch_name = match git::find_branch(&name, &r#type, config, opts) {
+        Ok(found) if git::branch_exists_locally(&found, opts.for_reads()).is_ok() => found,
+        _ => {
+            fetch_or_warn(opts);
+            fetched = true;
+            git::find_branch(&name, &r#type, config, opts)?
+        }
+    };
     println!("{}", format!("Branch to complete: {}", branch_name).blue());
+    // Read before the merge: git drops the branch's config when it is deleted.
+    let metadata = load_all_branch_metadata(opts).remove(&branch_name);
+    if let Some(metadata) = &metadata {
+        println!("{}", format!("  {}", metadata.summary()).dimmed());
+    }
 
-    git::branch_exists_locally(&branch_name, opts)?;
+    if git::branch_exists_locally(&branch_name, opts).is_err() {
+        git::remote_branch_exists(&branch_name, opts)
+            .map_err(|_| GitError::BranchNotFound(branch_name.clone()))?;
+        git::create_tracking_branch(&branch_name, opts)?;
+        println!(
+            "{}",
+            format!(
+                "'{}' only existed on origin; created a local tracking branch.",
+                branch_name
+            )
+            .dimmed()
+        );
+    }
 
     if r#type == "release" {
         let tag_name = format!("{}{}", config.automatic_tags.release_prefix, name);
 
-        if git::tag_exists(&tag_name, opts)? {
-            return Err(GitError::TagAlreadyExists(tag_name).into());
-        }
+        git::ensure_tag_available(&tag_name, opts)?;
+        warn_if_not_newest(&tag_name, config, opts);
     }
 
     git::is_working_directory_clean(opts)?;
+    ensure_branch_is_current(&branch_name, fetched, opts)?;
+    // Measured against origin as of the last fetch.
+    let commits = branch_commits(
+        &format!("origin/{}..{}", main_branch_name, branch_name),
+        opts.for_reads(),
+    )
+    .or_else(|e| if opts.dry_run { Ok(Vec::new()) } else { Err(e) })?;
+    confirm_open_todos(&commits, accept_todos, opts)?;
     git::checkout_main(opts, main_branch_name)?;
     git::pull_latest_with_rebase(opts)?;
-    git::merge_branch(&branch_name, opts)?;
+    let merge_message = match &config.complete.merge_message {
+        Some(template) => {
+            let vars = merge_template_vars(
+                &branch_name,
+                &r#type,
+                &name,
+                metadata.as_ref(),
+                &commits,
+                config,
+                opts,
+            )?;
+            Some(render_merge_template(template, &vars))
+        }
+        None => merge_commit_message(&branch_name, metadata.as_ref()),
+    };
+    git::merge_branch(&branch_name, merge_message.as_deref(), opts)?;
 
     if r#type == "release" {
         let tag_name = format!("{}{}", config.automatic_tags.release_prefix, name);
@@ -92,9 +809,16 @@ pub fn handle_complete(r#type: String, name: String, config: &Config, opts: RunO
         );
     }
 
-    git::push(opts)?;
     if r#type == "release" {
-        git::push_tags(opts)?;
+        let tag_name = format!("{}{}", config.automatic_tags.release_prefix, name);
+        git::push_branch_and_tag_atomic(
+            main_branch_name,
+            &tag_name,
+            config.automatic_tags.push_all_tags,
+            opts,
+        )?;
+    } else {
+        git::push(opts)?;
     }
 
     git::delete_local_branch(&branch_name, opts)?;
@@ -115,5 +839,317 @@ pub fn handle_complete(r#type: String, name: String, config: &Config, opts: RunO
         )
         .green()
     );
+
+    review::trigger_review_on_complete(config, &branch_name, opts)?;
+    Ok(())
+}
+
+/// Fetches origin, warning instead of failing when it cannot be reached.
+fn fetch_or_warn(opts: RunOpts) {
+    if let Err(e) = git::fetch_origin(opts) {
+        println!(
+            "{}",
+            format!(
+                "Warning: could not fetch origin ({}); using the remote branches as last fetched.",
+                e
+            )
+            .yellow()
+        );
+    }
+}
+
+/// Makes sure a teammate's commits on the remote branch are not silently left
+/// out of the merge. Offers to fast-forward when the local branch is behind.
+/// Fetches first unless the caller already did.
+fn ensure_branch_is_current(branch_name: &str, fetched: bool, opts: RunOpts) -> Result<()> {
+    if !fetched {
+        fetch_or_warn(opts);
+    }
+    let (ahead, behind) = git::get_ahead_behind(branch_name, opts)?;
+    if behind == 0 {
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Branch '{}' is {} commit(s) behind its remote (and {} ahead).",
+            branch_name, behind, ahead
+        )
+        .yellow()
+    );
+    if ahead > 0 {
+        return Err(anyhow::anyhow!(
+            "Branch '{}' has diverged from its remote. Rebase it onto its upstream before completing.",
+            branch_name
+        ));
+    }
+    if !prompt::confirm(
+        "Fast-forward the local branch before completing?",
+        Some(false),
+        opts,
+    )? {
+        return Err(anyhow::anyhow!(
+            "Aborted: '{}' is behind its remote. Pull the branch before completing.",
+            branch_name
+        ));
+    }
+    git::fast_forward_to_upstream(branch_name, opts)?;
+    println!(
+        "{}",
+        format!("Fast-forwarded '{}' to its remote.", branch_name).green()
+    );
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::fake::FakeGit;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn entry(key: &str, value: &str) -> (String, String) {
+        (key.to_string(), value.to_string())
+    }
+
+    /// A fake repository holding `branch` locally and on origin.
+    fn fake_repo_with(branch: &str, root: &std::path::Path) -> FakeGit {
+        FakeGit::new()
+            .respond(
+                "for-each-ref --format %(refname) refs/heads/",
+                &format!("refs/heads/main\nrefs/heads/{}", branch),
+            )
+            .respond(&format!("rev-parse --verify --quiet {}", branch), "abc123")
+            .respond("rev-parse --show-toplevel", &root.to_string_lossy())
+            .respond("rev-parse HEAD", "def456")
+    }
+
+    #[test]
+    fn test_complete_pushes_trunk_then_deletes_the_remote_branch() {
+        let root = tempfile::tempdir().unwrap();
+        let (git, opts) = fake_repo_with("feat/login", root.path()).install();
+
+        handle_complete(
+            "feat".to_string(),
+            "login".to_string(),
+            false,
+            &Config::default(),
+            opts,
+        )
+        .unwrap();
+
+        assert_eq!(
+            git.calls_to("merge --no-ff"),
+            vec!["merge --no-ff feat/login"]
+        );
+        assert_eq!(
+            git.calls_to("push"),
+            vec!["push", "push origin --delete feat/login"]
+        );
+        assert_eq!(git.calls_to("branch -d"), vec!["branch -d feat/login"]);
+    }
+
+    #[test]
+    fn test_complete_pushes_nothing_when_the_merge_fails() {
+        let root = tempfile::tempdir().unwrap();
+        let (git, opts) = fake_repo_with("feat/login", root.path())
+            .fail("merge", "CONFLICT (content): Merge conflict in src/lib.rs")
+            .install();
+
+        let err = handle_complete(
+            "feat".to_string(),
+            "login".to_string(),
+            false,
+            &Config::default(),
+            opts,
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("Merge conflict"), "{}", err);
+        assert!(git.calls_to("push").is_empty());
+        assert!(git.calls_to("branch -d").is_empty());
+    }
+
+    #[test]
+    fn test_complete_release_pushes_trunk_and_tag_atomically() {
+        let root = tempfile::tempdir().unwrap();
+        let (git, opts) = fake_repo_with("release_1.2.0", root.path()).install();
+
+        handle_complete(
+            "release".to_string(),
+            "1.2.0".to_string(),
+            false,
+            &Config::default(),
+            opts,
+        )
+        .unwrap();
+
+        assert_eq!(
+            git.calls_to("push"),
+            vec![
+                "push --atomic origin main refs/tags/v1.2.0",
+                "push origin --delete release_1.2.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_branch_metadata() {
+        let entries = vec![
+            entry("branch.main.remote", "origin"),
+            entry("branch.feat/v1.2-ui.remote", "origin"),
+            entry("branch.feat/v1.2-ui.tbdflow-type", "feat"),
+            entry("branch.feat/v1.2-ui.tbdflow-issue", "PROJ-1"),
+            entry(
+                "branch.feat/v1.2-ui.description",
+                "New UI\nspanning lines\n",
+            ),
+        ];
+        let all = parse_branch_metadata(&entries);
+        assert_eq!(all.len(), 1, "branches without metadata are left out");
+        let meta = &all["feat/v1.2-ui"];
+        assert_eq!(meta.branch_type.as_deref(), Some("feat"));
+        assert_eq!(meta.issue.as_deref(), Some("PROJ-1"));
+        assert_eq!(meta.description.as_deref(), Some("New UI\nspanning lines"));
+    }
+
+    #[test]
+    fn test_closest_branch_type_suggests_typos_and_prefixes() {
+        let allowed = ["chore", "docs", "feat", "fix", "release"];
+        assert_eq!(commands::closest_branch_type("doc", &allowed), Some("docs"));
+        assert_eq!(
+            commands::closest_branch_type("feature", &allowed),
+            Some("feat")
+        );
+        assert_eq!(
+            commands::closest_branch_type("relase", &allowed),
+            Some("release")
+        );
+        assert_eq!(commands::closest_branch_type("spike", &allowed), None);
+        assert_eq!(commands::closest_branch_type("", &allowed), None);
+    }
+
+    #[test]
+    fn test_render_merge_template_drops_empty_lines() {
+        let template = "{{type}}: merge {{name}} ({{commit_count}} commits)\n\n\
+                        {{description}}\n\n{{subjects}}\n\nDoD: {{dod}}\nRefs: {{issue}}";
+        let vars = MergeTemplateVars {
+            branch: "feat/dashboard".to_string(),
+            branch_type: "feat".to_string(),
+            name: "dashboard".to_string(),
+            subjects: vec![
+                "feat: add chart".to_string(),
+                "fix: axis labels".to_string(),
+            ],
+            dod: "complete".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            render_merge_template(template, &vars),
+            "feat: merge dashboard (2 commits)\n\n- feat: add chart\n- fix: axis labels\n\nDoD: complete"
+        );
+    }
+
+    #[test]
+    fn test_dod_status_counts_open_items_and_skips() {
+        let messages = vec![
+            "feat: a\n\nTODO:\n- [ ] Tests\n- [ ] Docs".to_string(),
+            "fix: b".to_string(),
+        ];
+        assert_eq!(
+            dod_status(&messages, 1, true),
+            "2 open item(s) in 1 commit(s), checklist skipped on 1 commit(s)"
+        );
+        assert_eq!(dod_status(&messages[1..], 0, true), "complete");
+        assert_eq!(dod_status(&messages[1..], 0, false), "");
+    }
+
+    #[test]
+    fn test_open_todo_items_reads_unticked_footer_items() {
+        let message =
+            "feat: a\n\n- [ ] not a footer\n\nTODO:\n- [ ] Tests\n- [x] Docs\n- [ ]  Changelog";
+        assert_eq!(open_todo_items(message), vec!["Tests", "Changelog"]);
+        assert!(open_todo_items("fix: b\n\n- [ ] Tests").is_empty());
+    }
+
+    #[test]
+    fn test_merge_commit_message_carries_description() {
+        let metadata = BranchMetadata {
+            description: Some("Usage dashboard\nfor admins".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            merge_commit_message("feat/dashboard", Some(&metadata)).unwrap(),
+            "Merge branch 'feat/dashboard'\n\nUsage dashboard\nfor admins"
+        );
+        assert_eq!(
+            merge_commit_message("feat/dashboard", Some(&BranchMetadata::default())),
+            None
+        );
+        assert_eq!(merge_commit_message("feat/dashboard", None), None);
+    }
+
+    #[test]
+    fn test_branch_metadata_summary() {
+        let meta = BranchMetadata {
+            branch_type: Some("feat".to_string()),
+            issue: Some("PROJ-1".to_string()),
+            creator: Some("Ada <ada@example.com>".to_string()),
+            created_at: Some("2026-10-17T09:00:00+00:00".to_string()),
+            description: Some("New dashboard".to_string()),
+        };
+        assert_eq!(
+            meta.summary(),
+            "feat, PROJ-1 by Ada <ada@example.com> on 2026-10-17: \"New dashboard\""
+        );
+
+        let description_only = BranchMetadata {
+            description: Some("Spike".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(description_only.summary(), "\"Spike\"");
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("profile", "profile"), 0);
+        assert_eq!(edit_distance("profil", "profile"), 1);
+        assert_eq!(edit_distance("Profile", "profiel"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_find_similar_branch_detects_typo() {
+        let existing = names(&["main", "feat/user-profile-page", "fix/login"]);
+        assert_eq!(
+            find_similar_branch("feat/user-profil-page", &existing, "main"),
+            Some("feat/user-profile-page".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_similar_branch_ignores_unrelated_names() {
+        let existing = names(&["main", "feat/user-profile-page"]);
+        assert_eq!(
+            find_similar_branch("feat/billing-page", &existing, "main"),
+            None
+        );
+        assert_eq!(find_similar_branch("mains", &existing, "main"), None);
+    }
+
+    #[test]
+    fn test_find_similar_branch_ignores_different_versions_and_issues() {
+        let existing = names(&["release_1.0.0", "feat/PROJ-123-search"]);
+        assert_eq!(
+            find_similar_branch("release_1.0.1", &existing, "main"),
+            None
+        );
+        assert_eq!(
+            find_similar_branch("feat/PROJ-124-search", &existing, "main"),
+            None
+        );
+    }
+}
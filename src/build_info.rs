@@ -0,0 +1,134 @@
+//! What this binary was built from and with, for `tbdflow --version`: the
+//! details a bug report needs beyond the release number.
+
+use crate::commands::{TbdResponse, VersionResponse};
+use crate::config::{GitBackendKind, ReviewStrategy};
+use crate::git_backend;
+use anyhow::Result;
+use serde::Serialize;
+use std::sync::OnceLock;
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Short hash of the commit built from, or "unknown".
+pub const COMMIT: &str = env!("BUILD_INFO_COMMIT");
+pub const BUILD_DATE: &str = env!("BUILD_INFO_DATE");
+pub const TARGET: &str = env!("BUILD_INFO_TARGET");
+
+/// The kebab-case name a config value is written with.
+fn config_name<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Optional cargo features compiled in.
+pub fn features() -> Vec<String> {
+    let mut features = Vec::new();
+    if cfg!(feature = "gix") {
+        features.push("gix".to_string());
+    }
+    features
+}
+
+pub fn git_backends() -> Vec<String> {
+    [GitBackendKind::Subprocess, GitBackendKind::Gix]
+        .into_iter()
+        .filter(|kind| git_backend::is_supported(*kind))
+        .map(|kind| config_name(&kind))
+        .collect()
+}
+
+pub fn review_strategies() -> Vec<String> {
+    ReviewStrategy::ALL.iter().map(config_name).collect()
+}
+
+/// The text after "tbdflow " in `--version`.
+pub fn long_version() -> &'static str {
+    static LONG_VERSION: OnceLock<String> = OnceLock::new();
+    LONG_VERSION.get_or_init(|| {
+        let features = features();
+        let rows = [
+            ("commit", COMMIT.to_string()),
+            ("built", BUILD_DATE.to_string()),
+            ("target", TARGET.to_string()),
+            (
+                "features",
+                if features.is_empty() {
+                    "none".to_string()
+                } else {
+                    features.join(", ")
+                },
+            ),
+            ("git backends", git_backends().join(", ")),
+            ("review strategies", review_strategies().join(", ")),
+        ];
+        let mut text = VERSION.to_string();
+        for (label, value) in rows {
+            text.push_str(&format!("\n{:<18} {}", format!("{}:", label), value));
+        }
+        text
+    })
+}
+
+/// True for `--version --json` (or `-V`), which clap would otherwise answer
+/// as text before `--json` is ever seen.
+pub fn is_json_version_request(args: &[String]) -> bool {
+    let args: Vec<&str> = args
+        .iter()
+        .skip(1)
+        .map(String::as_str)
+        .take_while(|arg| *arg != "--")
+        .collect();
+    args.contains(&"--json") && args.iter().any(|arg| *arg == "--version" || *arg == "-V")
+}
+
+pub fn print_version_json() -> Result<()> {
+    let response = VersionResponse {
+        version: VERSION.to_string(),
+        commit: COMMIT.to_string(),
+        build_date: BUILD_DATE.to_string(),
+        target: TARGET.to_string(),
+        features: features(),
+        git_backends: git_backends(),
+        review_strategies: review_strategies(),
+    };
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&TbdResponse::ok(response))?
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_version_needs_both_flags_before_a_double_dash() {
+        let args = |list: &[&str]| {
+            std::iter::once("tbdflow")
+                .chain(list.iter().copied())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert!(is_json_version_request(&args(&["--version", "--json"])));
+        assert!(is_json_version_request(&args(&["--json", "-V"])));
+        assert!(!is_json_version_request(&args(&["--version"])));
+        assert!(!is_json_version_request(&args(&[
+            "commit",
+            "--json",
+            "-m",
+            "--",
+            "--version"
+        ])));
+    }
+
+    #[test]
+    fn long_version_lists_every_review_strategy() {
+        let text = long_version();
+        assert!(text.starts_with(VERSION));
+        assert!(text.contains("git-notes"));
+        assert!(text.contains("subprocess"));
+    }
+}
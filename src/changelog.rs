@@ -1,30 +1,159 @@
+use crate::commands::{
+    ChangelogEntryResponse, ChangelogResponse, ChangelogSectionResponse, ErrorCode, TbdResponse,
+};
+use crate::config::{ChangelogConfig, Config};
+use crate::git;
 use crate::git::RunOpts;
-use crate::{config::Config, git};
+use crate::history::{self, ParsedCommit};
+use crate::output;
+use crate::shallow;
+use crate::version;
 use anyhow::Result;
 use colored::*;
-use git_conventional::Commit;
 use std::collections::HashMap;
 
-fn get_section_header(commit_type: &str) -> &'static str {
-    match commit_type {
-        "feat" => "### ✨ Features",
-        "fix" => "### 🐛 Bug Fixes",
-        "perf" => "### 🚀 Performance Improvements",
-        "refactor" => "### 🔨 Code Refactoring",
-        "build" | "chore" | "ci" | "docs" | "style" | "test" => "### ⚙️ Maintenance",
-        _ => "### Miscellaneous",
+/// Built-in commit type to section header mapping, in display order.
+const DEFAULT_SECTIONS: [(&str, &str); 10] = [
+    ("feat", "### ✨ Features"),
+    ("fix", "### 🐛 Bug Fixes"),
+    ("perf", "### 🚀 Performance Improvements"),
+    ("refactor", "### 🔨 Code Refactoring"),
+    ("build", "### ⚙️ Maintenance"),
+    ("chore", "### ⚙️ Maintenance"),
+    ("ci", "### ⚙️ Maintenance"),
+    ("docs", "### ⚙️ Maintenance"),
+    ("style", "### ⚙️ Maintenance"),
+    ("test", "### ⚙️ Maintenance"),
+];
+
+/// Merges the configured `changelog.sections` over the built-in mapping.
+pub fn section_headers(changelog: &ChangelogConfig) -> HashMap<String, String> {
+    let mut headers: HashMap<String, String> = DEFAULT_SECTIONS
+        .iter()
+        .map(|(t, h)| (t.to_string(), h.to_string()))
+        .collect();
+    headers.extend(changelog.sections.clone());
+    headers
+}
+
+pub fn get_section_header<'a>(
+    headers: &'a HashMap<String, String>,
+    changelog: &'a ChangelogConfig,
+    commit_type: &str,
+) -> &'a str {
+    headers
+        .get(commit_type)
+        .map_or(changelog.other_header.as_str(), |h| h.as_str())
+}
+
+/// Breaking changes first, then the built-in sections, custom types (sorted by
+/// type), reverted commits and the catch-all. A configured `order` takes precedence.
+pub fn section_order(
+    headers: &HashMap<String, String>,
+    changelog: &ChangelogConfig,
+) -> Vec<String> {
+    let mut custom_types: Vec<&String> = headers
+        .keys()
+        .filter(|t| !DEFAULT_SECTIONS.iter().any(|(d, _)| d == t))
+        .collect();
+    custom_types.sort();
+
+    let mut order: Vec<String> = changelog.order.clone();
+    let defaults = std::iter::once(&changelog.breaking_header)
+        .chain(DEFAULT_SECTIONS.iter().map(|(t, _)| &headers[*t]))
+        .chain(custom_types.into_iter().map(|t| &headers[t]))
+        .chain(std::iter::once(&changelog.reverted_header))
+        .chain(std::iter::once(&changelog.other_header));
+    for header in defaults {
+        if !header.is_empty() && !order.contains(header) {
+            order.push(header.clone());
+        }
     }
+    order
 }
 
-pub fn handle_changelog(
+/// True for commits that undo another: git's `Revert "..."` or a `revert:` type.
+fn is_revert(commit: &ParsedCommit) -> bool {
+    commit.subject.starts_with("Revert \"") || commit.r#type.as_deref() == Some("revert")
+}
+
+/// The commit a revert message names: git's "This reverts commit <hash>."
+/// line, or a `Refs:` or `Reverts:` footer holding a hash.
+fn referenced_hash(message: &str) -> Option<String> {
+    message.lines().find_map(|line| {
+        let line = line.trim();
+        let value = line.strip_prefix("This reverts commit ").or_else(|| {
+            let (key, value) = line.split_once(':')?;
+            matches!(key.trim().to_lowercase().as_str(), "refs" | "reverts").then_some(value)
+        })?;
+        let hash = value
+            .split_whitespace()
+            .next()?
+            .trim_end_matches(['.', ',']);
+        (hash.len() >= 7 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+            .then(|| hash.to_lowercase())
+    })
+}
+
+/// The subject of the commit a revert undoes, for reverts that name no hash.
+fn reverted_subject(revert: &ParsedCommit) -> Option<&str> {
+    match revert.subject.strip_prefix("Revert \"") {
+        Some(rest) => rest.strip_suffix('"'),
+        None => Some(revert.description.as_str()),
+    }
+}
+
+/// Pairs each revert in `commits` (newest first) with the older commit it
+/// undoes, when that commit is in the list too. Reverts are matched by the
+/// hash in their message (read with `message_of`), then by subject. Returns
+/// original hash to revert hash; a commit is paired at most once.
+pub fn find_revert_pairs(
+    commits: &[ParsedCommit],
+    message_of: impl Fn(&str) -> String,
+) -> HashMap<String, String> {
+    let mut pairs: HashMap<String, String> = HashMap::new();
+    let mut paired: Vec<&str> = Vec::new();
+    for (i, revert) in commits.iter().enumerate().rev() {
+        if !is_revert(revert) || paired.contains(&revert.hash.as_str()) {
+            continue;
+        }
+        let older = || {
+            commits[i + 1..]
+                .iter()
+                .filter(|c| !paired.contains(&c.hash.as_str()))
+        };
+        let target = referenced_hash(&message_of(&revert.hash))
+            .and_then(|hash| older().find(|c| c.hash.starts_with(&hash)))
+            .or_else(|| {
+                let subject = reverted_subject(revert)?;
+                older().find(|c| c.subject == subject)
+            });
+        if let Some(original) = target {
+            pairs.insert(original.hash.clone(), revert.hash.clone());
+            paired.push(&original.hash);
+            paired.push(&revert.hash);
+        }
+    }
+    pairs
+}
+
+/// Groups the conventional commits in the range into changelog sections, in
+/// display order. Breaking changes are also listed under the breaking header.
+/// A commit reverted within the range goes under the reverted header instead,
+/// and its revert is left out.
+pub fn build_changelog(
     opts: RunOpts,
     config: &Config,
     from: Option<String>,
     to: Option<String>,
     unreleased: bool,
-) -> Result<String> {
+) -> Result<ChangelogResponse> {
     let range = if unreleased {
-        let latest_tag = git::get_latest_tag(opts)?;
+        let latest_tag = version::latest_tag(config, !config.changelog.skip_prereleases, opts)
+            .map_err(|e| match shallow::missing_tag_hint(opts) {
+                Some(hint) => anyhow::anyhow!("{} {}", e, hint),
+                None => e,
+            })?;
         format!("{}..HEAD", latest_tag)
     } else {
         format!(
@@ -34,48 +163,51 @@ pub fn handle_changelog(
         )
     };
 
-    let history = git::get_commit_history(&range, opts)?;
-    let mut sections: HashMap<&'static str, Vec<String>> = HashMap::new();
-    let mut breaking_changes: Vec<String> = Vec::new();
+    let commits = history::commits_in_range(&range, opts)?;
+    let headers = section_headers(&config.changelog);
+    let mut sections: HashMap<&str, Vec<ChangelogEntryResponse>> = HashMap::new();
+    let mut breaking_changes: Vec<ChangelogEntryResponse> = Vec::new();
     let remote_url = git::get_remote_url(opts).unwrap_or_default();
+    let reverts = find_revert_pairs(&commits, |hash| {
+        git::get_commit_message(hash, opts).unwrap_or_default()
+    });
+    let mut reverted: Vec<ChangelogEntryResponse> = Vec::new();
 
-    // Format: "hash|message"
-    for line in history.lines() {
-        let parts: Vec<&str> = line.split('|').collect();
-        if parts.len() != 2 {
+    for commit in &commits {
+        if reverts.values().any(|revert| *revert == commit.hash) {
             continue;
         }
-        let hash = parts[0];
-        let message = parts[1];
-
-        if let Ok(commit) = Commit::parse(message) {
-            let scope = commit
-                .scope()
-                .map_or("".to_string(), |s| format!("**({}):** ", s));
-            let short_hash = &hash[..7];
-            let commit_link = if !remote_url.is_empty() {
-                format!(" [`{}`]({}/commit/{})", short_hash, remote_url, hash)
-            } else {
-                format!("`{}`", short_hash)
-            };
-
-            let entry = format!("- {}{}{}", scope, commit.description(), commit_link);
+        let Some(commit_type) = &commit.r#type else {
+            continue;
+        };
+        let entry = ChangelogEntryResponse {
+            hash: commit.hash.clone(),
+            r#type: commit_type.clone(),
+            scope: commit.scope.clone(),
+            description: commit.description.clone(),
+            breaking: commit.breaking,
+            url: (!remote_url.is_empty()).then(|| format!("{}/commit/{}", remote_url, commit.hash)),
+            reverted_by: reverts.get(&commit.hash).cloned(),
+        };
 
-            if commit.breaking() {
-                breaking_changes.push(entry.clone());
-            }
+        if entry.reverted_by.is_some() {
+            reverted.push(entry);
+            continue;
+        }
+        if commit.breaking {
+            breaking_changes.push(entry.clone());
+        }
 
-            let section_header = get_section_header(commit.type_().as_str());
+        let section_header = get_section_header(&headers, &config.changelog, commit_type);
+        if !section_header.is_empty() {
             sections.entry(section_header).or_default().push(entry);
         }
     }
 
-    let mut changelog = String::new();
-
-    if unreleased {
-        changelog.push_str("# Unreleased Changes\n");
+    let title = if unreleased {
+        Some("Unreleased Changes".to_string())
     } else {
-        if let Some(tag) = &to {
+        to.as_ref().map(|tag| {
             let version = tag.strip_prefix('v').unwrap_or(tag);
             let date = chrono::Local::now().format("%Y-%m-%d").to_string();
 
@@ -85,36 +217,201 @@ pub fn handle_changelog(
             } else {
                 version.to_string()
             };
-            changelog.push_str(&format!("# {} ({})\n", release_link, date));
-        }
-    }
+            format!("{} ({})", release_link, date)
+        })
+    };
 
-    let section_order = [
-        "### ⚠️ BREAKING CHANGES",
-        "### ✨ Features",
-        "### 🐛 Bug Fixes",
-        "### 🚀 Performance Improvements",
-        "### 🔨 Code Refactoring",
-        "### ⚙️ Maintenance",
-        "### Miscellaneous",
-    ];
-
-    for section in &section_order {
-        let items = if *section == "### ⚠️ BREAKING CHANGES" {
-            Some(&breaking_changes)
+    let mut ordered = Vec::new();
+    for section in section_order(&headers, &config.changelog) {
+        let entries = if section == config.changelog.breaking_header {
+            std::mem::take(&mut breaking_changes)
+        } else if section == config.changelog.reverted_header {
+            std::mem::take(&mut reverted)
         } else {
-            sections.get(section)
+            sections.remove(section.as_str()).unwrap_or_default()
         };
+        if !entries.is_empty() {
+            ordered.push(ChangelogSectionResponse {
+                header: section,
+                entries,
+            });
+        }
+    }
 
-        if let Some(items) = items {
-            if !items.is_empty() {
-                changelog.push_str(&format!("\n{}\n", section.bold()));
-                for item in items {
-                    changelog.push_str(&format!("{}\n", item));
-                }
-            }
+    Ok(ChangelogResponse {
+        range,
+        title,
+        sections: ordered,
+    })
+}
+
+/// The changelog as Markdown. In ASCII mode only the section headings lose
+/// their emojis; commit text is printed as written.
+pub fn render_changelog(changelog: &ChangelogResponse) -> String {
+    let mut out = String::new();
+    if let Some(title) = &changelog.title {
+        out.push_str(&format!("# {}\n", title));
+    }
+    for section in &changelog.sections {
+        out.push_str(&format!("\n{}\n", output::text(&section.header).bold()));
+        for entry in &section.entries {
+            let scope = entry
+                .scope
+                .as_ref()
+                .map_or("".to_string(), |s| format!("**({}):** ", s));
+            let short_hash = git::short_hash(&entry.hash);
+            let commit_link = match &entry.url {
+                Some(url) => format!(" [`{}`]({})", short_hash, url),
+                None => format!("`{}`", short_hash),
+            };
+            let reverted_by = entry.reverted_by.as_ref().map_or("".to_string(), |hash| {
+                format!(" (reverted in `{}`)", git::short_hash(hash))
+            });
+            out.push_str(&format!(
+                "- {}{}{}{}\n",
+                scope, entry.description, commit_link, reverted_by
+            ));
         }
     }
+    out
+}
+
+pub fn handle_changelog(
+    opts: RunOpts,
+    config: &Config,
+    from: Option<String>,
+    to: Option<String>,
+    unreleased: bool,
+) -> Result<String> {
+    shallow::ensure_full_history("the changelog", false, opts)?;
+    Ok(render_changelog(&build_changelog(
+        opts, config, from, to, unreleased,
+    )?))
+}
+
+/// `changelog --json`. The wizard needs a terminal, so a range is required.
+pub fn handle_changelog_json(
+    opts: RunOpts,
+    config: &Config,
+    from: Option<String>,
+    to: Option<String>,
+    unreleased: bool,
+) -> Result<()> {
+    let response = if from.is_none() && to.is_none() && !unreleased {
+        TbdResponse::err_with_code(
+            "Pass --from/--to or --unreleased; the changelog wizard is not available with --json.",
+            ErrorCode::MissingArgs,
+        )
+    } else {
+        shallow::ensure_full_history("the changelog", true, opts)?;
+        TbdResponse::ok(build_changelog(opts, config, from, to, unreleased)?)
+    };
+    println!("{}", serde_json::to_string_pretty(&response)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_mapping_groups_maintenance_types() {
+        let changelog = ChangelogConfig::default();
+        let headers = section_headers(&changelog);
+        assert_eq!(
+            get_section_header(&headers, &changelog, "feat"),
+            "### ✨ Features"
+        );
+        assert_eq!(
+            get_section_header(&headers, &changelog, "ci"),
+            "### ⚙️ Maintenance"
+        );
+        assert_eq!(
+            get_section_header(&headers, &changelog, "wip"),
+            "### Miscellaneous"
+        );
+    }
+
+    #[test]
+    fn configured_sections_override_and_extend_defaults() {
+        let changelog = ChangelogConfig {
+            sections: HashMap::from([
+                ("feat".to_string(), "## Features".to_string()),
+                ("security".to_string(), "## Security".to_string()),
+            ]),
+            ..ChangelogConfig::default()
+        };
+        let headers = section_headers(&changelog);
+        assert_eq!(
+            get_section_header(&headers, &changelog, "feat"),
+            "## Features"
+        );
+        assert_eq!(
+            get_section_header(&headers, &changelog, "security"),
+            "## Security"
+        );
+
+        let order = section_order(&headers, &changelog);
+        assert_eq!(order.first().unwrap(), "### ⚠️ BREAKING CHANGES");
+        assert_eq!(order[1], "## Features");
+        assert_eq!(order.last().unwrap(), "### Miscellaneous");
+        assert!(order.contains(&"## Security".to_string()));
+    }
+
+    #[test]
+    fn configured_order_comes_first() {
+        let changelog = ChangelogConfig {
+            order: vec!["### 🐛 Bug Fixes".to_string()],
+            ..ChangelogConfig::default()
+        };
+        let headers = section_headers(&changelog);
+        let order = section_order(&headers, &changelog);
+        assert_eq!(order[0], "### 🐛 Bug Fixes");
+        assert_eq!(order[1], "### ⚠️ BREAKING CHANGES");
+        assert_eq!(
+            order.iter().filter(|h| *h == "### ⚙️ Maintenance").count(),
+            1
+        );
+    }
+
+    #[test]
+    fn reverts_are_paired_by_hash_then_by_subject() {
+        let log = "\x1eeeee0000\x1fAda\x1frevert: fix: retry uploads\n\
+                   \x1edddd0000\x1fAda\x1fRevert \"feat: add cache\"\n\
+                   \x1ecccc0000\x1fAda\x1ffix: retry uploads\n\
+                   \x1ebbbb0000\x1fAda\x1ffeat: add cache\n\
+                   \x1eaaaa0000\x1fAda\x1ffeat: add cache\n";
+        let commits = history::parse_log(log);
+        let pairs = find_revert_pairs(&commits, |hash| match hash {
+            "dddd0000" => "Revert \"feat: add cache\"\n\nThis reverts commit aaaa0000.".to_string(),
+            _ => String::new(),
+        });
+        assert_eq!(
+            pairs,
+            HashMap::from([
+                ("aaaa0000".to_string(), "dddd0000".to_string()),
+                ("cccc0000".to_string(), "eeee0000".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn revert_footers_name_the_reverted_hash() {
+        assert_eq!(
+            referenced_hash("revert: add cache\n\nRefs: 1A2B3C4D"),
+            Some("1a2b3c4d".to_string())
+        );
+        assert_eq!(referenced_hash("revert: add cache\n\nRefs: #12"), None);
+    }
 
-    Ok(changelog)
+    #[test]
+    fn empty_header_drops_the_section() {
+        let changelog = ChangelogConfig {
+            sections: HashMap::from([("chore".to_string(), String::new())]),
+            ..ChangelogConfig::default()
+        };
+        let headers = section_headers(&changelog);
+        assert_eq!(get_section_header(&headers, &changelog, "chore"), "");
+        assert!(!section_order(&headers, &changelog).contains(&String::new()));
+    }
 }
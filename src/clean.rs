@@ -0,0 +1,309 @@
+//! `tbdflow clean`: removes what tbdflow left behind in this clone and no
+//! longer needs. Everything is listed first; nothing is removed under
+//! `--dry-run` or without confirmation.
+
+use crate::commands::{CleanItemResponse, CleanResponse, TbdResponse};
+use crate::git::{self, RunOpts};
+use crate::{intent, journal, prompt, state};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// A git lock file older than this was left by a process that died: git holds
+/// them for the length of one command.
+const STALE_LOCK_AGE: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Artifact {
+    /// The intent log of a branch that no longer exists, snapshots and all.
+    IntentLog { branch: String },
+    /// A WIP snapshot in the intent log whose commit git has since pruned.
+    Snapshot { hash: String },
+    /// A journal entry about a commit that no longer exists.
+    JournalEntry { event: String, commit: String },
+    /// A state file that could not be read and was moved aside.
+    CorruptState(PathBuf),
+    /// A git lock file nobody holds any more.
+    LockFile(PathBuf),
+}
+
+impl Artifact {
+    fn kind(&self) -> &'static str {
+        match self {
+            Artifact::IntentLog { .. } => "intent-log",
+            Artifact::Snapshot { .. } => "snapshot",
+            Artifact::JournalEntry { .. } => "journal-entry",
+            Artifact::CorruptState(_) => "corrupt-state",
+            Artifact::LockFile(_) => "lock-file",
+        }
+    }
+
+    fn target(&self, git_root: &Path) -> String {
+        let shown = |path: &Path| {
+            path.strip_prefix(git_root)
+                .unwrap_or(path)
+                .display()
+                .to_string()
+        };
+        match self {
+            Artifact::IntentLog { .. } => ".tbdflow-intent.json".to_string(),
+            Artifact::Snapshot { hash } => hash[..10.min(hash.len())].to_string(),
+            Artifact::JournalEntry { event, commit } => format!("{} {}", event, commit),
+            Artifact::CorruptState(path) | Artifact::LockFile(path) => shown(path),
+        }
+    }
+
+    fn reason(&self) -> String {
+        match self {
+            Artifact::IntentLog { branch } => format!("branch '{}' no longer exists", branch),
+            Artifact::Snapshot { .. } => "its commit has been pruned".to_string(),
+            Artifact::JournalEntry { .. } => "the commit no longer exists".to_string(),
+            Artifact::CorruptState(_) => "could not be read and was moved aside".to_string(),
+            Artifact::LockFile(_) => format!(
+                "left behind more than {} minutes ago",
+                STALE_LOCK_AGE.as_secs() / 60
+            ),
+        }
+    }
+}
+
+fn commit_exists(hash: &str, opts: RunOpts) -> bool {
+    git::commit_exists(&format!("{}^{{commit}}", hash), opts).unwrap_or(true)
+}
+
+fn intent_artifacts(git_root: &Path, opts: RunOpts) -> Result<Vec<Artifact>> {
+    let Some(log) = intent::load_intent_log(git_root)? else {
+        return Ok(Vec::new());
+    };
+    if let Some(branch) = &log.branch
+        && git::branch_exists_locally(branch, opts).is_err()
+    {
+        return Ok(vec![Artifact::IntentLog {
+            branch: branch.clone(),
+        }]);
+    }
+    Ok(log
+        .notes
+        .iter()
+        .filter_map(|note| note.snapshot_hash.clone())
+        .filter(|hash| !commit_exists(hash, opts))
+        .map(|hash| Artifact::Snapshot { hash })
+        .collect())
+}
+
+fn journal_artifacts(git_dir: &Path, opts: RunOpts) -> Result<Vec<Artifact>> {
+    let mut checked = HashSet::new();
+    let mut artifacts = Vec::new();
+    for entry in journal::read_entries(git_dir)? {
+        let commit = entry.event.commit().to_string();
+        let gone = !commit_exists(&commit, opts);
+        if gone && checked.insert(commit.clone()) {
+            let event = serde_json::to_value(&entry.event)?;
+            artifacts.push(Artifact::JournalEntry {
+                event: event["event"].as_str().unwrap_or_default().to_string(),
+                commit,
+            });
+        }
+    }
+    Ok(artifacts)
+}
+
+fn corrupt_state(git_dir: &Path) -> Vec<Artifact> {
+    let Ok(entries) = fs::read_dir(git_dir.join(state::STATE_DIR)) else {
+        return Vec::new();
+    };
+    let mut found: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "corrupt"))
+        .collect();
+    found.sort();
+    found.into_iter().map(Artifact::CorruptState).collect()
+}
+
+/// `*.lock` files directly in `dir` older than `STALE_LOCK_AGE`, and with
+/// `recurse`, in its subdirectories.
+fn stale_locks(dir: &Path, recurse: bool, now: SystemTime, found: &mut Vec<Artifact>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if meta.is_dir() {
+            if recurse {
+                stale_locks(&path, true, now, found);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "lock")
+            && meta
+                .modified()
+                .ok()
+                .and_then(|at| now.duration_since(at).ok())
+                .is_some_and(|age| age > STALE_LOCK_AGE)
+        {
+            found.push(Artifact::LockFile(path));
+        }
+    }
+}
+
+/// Everything `clean` would remove.
+pub fn find_artifacts(git_root: &Path, git_dir: &Path, opts: RunOpts) -> Result<Vec<Artifact>> {
+    let mut artifacts = intent_artifacts(git_root, opts)?;
+    artifacts.extend(journal_artifacts(git_dir, opts)?);
+    artifacts.extend(corrupt_state(git_dir));
+    let mut locks = Vec::new();
+    let now = SystemTime::now();
+    stale_locks(git_dir, false, now, &mut locks);
+    stale_locks(&git_dir.join("refs"), true, now, &mut locks);
+    artifacts.extend(locks);
+    Ok(artifacts)
+}
+
+fn remove(git_root: &Path, git_dir: &Path, artifacts: &[Artifact]) -> Result<()> {
+    let pruned: HashSet<&str> = artifacts
+        .iter()
+        .filter_map(|a| match a {
+            Artifact::Snapshot { hash } => Some(hash.as_str()),
+            _ => None,
+        })
+        .collect();
+    if artifacts
+        .iter()
+        .any(|a| matches!(a, Artifact::IntentLog { .. }))
+    {
+        intent::cleanup_intent_log(git_root)?;
+    } else if !pruned.is_empty()
+        && let Some(mut log) = intent::load_intent_log(git_root)?
+    {
+        for note in &mut log.notes {
+            if note
+                .snapshot_hash
+                .as_deref()
+                .is_some_and(|h| pruned.contains(h))
+            {
+                note.snapshot_hash = None;
+            }
+        }
+        intent::save_intent_log(git_root, &log)?;
+    }
+
+    let orphaned: HashSet<&str> = artifacts
+        .iter()
+        .filter_map(|a| match a {
+            Artifact::JournalEntry { commit, .. } => Some(commit.as_str()),
+            _ => None,
+        })
+        .collect();
+    if !orphaned.is_empty() {
+        journal::retain(git_dir, |e| !orphaned.contains(e.event.commit()))?;
+    }
+
+    for artifact in artifacts {
+        if let Artifact::CorruptState(path) | Artifact::LockFile(path) = artifact {
+            fs::remove_file(path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Lists tbdflow's leftovers in this clone and, once confirmed, removes them.
+pub fn handle_clean(json: bool, opts: RunOpts) -> Result<()> {
+    let quiet = opts.for_reads();
+    let git_root = PathBuf::from(git::get_git_root(quiet)?);
+    let git_dir = PathBuf::from(git::get_git_dir(quiet)?);
+    let artifacts = find_artifacts(&git_root, &git_dir, quiet)?;
+
+    if json {
+        let removed = !artifacts.is_empty() && !opts.dry_run && opts.assume_yes;
+        if removed {
+            remove(&git_root, &git_dir, &artifacts)?;
+        }
+        let items = artifacts
+            .iter()
+            .map(|a| CleanItemResponse {
+                kind: a.kind().to_string(),
+                target: a.target(&git_root),
+                reason: a.reason(),
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(CleanResponse { items, removed }))?
+        );
+        return Ok(());
+    }
+
+    if artifacts.is_empty() {
+        println!("{}", "Nothing to clean.".green());
+        return Ok(());
+    }
+    println!("{}", "--- Left behind by tbdflow ---".blue());
+    for artifact in &artifacts {
+        println!(
+            "  {:<14} {} {}",
+            artifact.kind(),
+            artifact.target(&git_root),
+            format!("({})", artifact.reason()).dimmed()
+        );
+    }
+    if opts.dry_run {
+        println!(
+            "{}",
+            format!("[DRY RUN] Would remove {} item(s).", artifacts.len()).yellow()
+        );
+        return Ok(());
+    }
+    if !prompt::confirm(
+        &format!("Remove these {} item(s)?", artifacts.len()),
+        Some(false),
+        opts,
+    )? {
+        println!("{}", "Nothing removed.".yellow());
+        return Ok(());
+    }
+    remove(&git_root, &git_dir, &artifacts)?;
+    println!(
+        "{}",
+        format!("Removed {} item(s).", artifacts.len()).green()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_old_lock_files_are_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = dir.path().join("index.lock");
+        fs::write(&old, "").unwrap();
+        fs::write(dir.path().join("HEAD.lock"), "").unwrap();
+        fs::write(dir.path().join("config"), "").unwrap();
+        fs::create_dir(dir.path().join("heads")).unwrap();
+        fs::write(dir.path().join("heads").join("main.lock"), "").unwrap();
+
+        // Two hours on, every lock file is old; an hour's grace covers the rest.
+        let later = SystemTime::now() + Duration::from_secs(2 * 60 * 60);
+        let mut found = Vec::new();
+        stale_locks(dir.path(), false, later, &mut found);
+        found.sort_by_key(|a| format!("{:?}", a));
+        assert_eq!(
+            found,
+            vec![
+                Artifact::LockFile(dir.path().join("HEAD.lock")),
+                Artifact::LockFile(old.clone()),
+            ]
+        );
+
+        let mut found = Vec::new();
+        stale_locks(dir.path(), true, SystemTime::now(), &mut found);
+        assert!(found.is_empty());
+    }
+}
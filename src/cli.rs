@@ -1,3 +1,5 @@
+use crate::config_import::ImportSource;
+use crate::review::ConcernSeverity;
 use clap::{Parser, Subcommand};
 use clap_complete::Shell;
 
@@ -6,6 +8,7 @@ use clap_complete::Shell;
     name = "tbdflow",
     author = "Claes Adamsson @cladam",
     version,
+    long_version = crate::build_info::long_version(),
     about = "A CLI tool for Trunk-Based Development (TBD) workflows",
     long_about = None)]
 #[command(propagate_version = true)]
@@ -21,6 +24,24 @@ pub struct Cli {
     /// Emit machine-readable JSON output instead of human-readable text.
     #[arg(long, global = true)]
     pub json: bool,
+    /// Automatically confirm every prompt. Prompts are also skipped (using
+    /// their defaults) when stdin/stdout is not a terminal. Can also be set
+    /// with TBDFLOW_ASSUME_YES=1.
+    #[arg(short = 'y', long, visible_alias = "assume-yes", global = true)]
+    pub yes: bool,
+    /// Apply a named profile from `.tbdflow.yml` (the `profiles:` section) over
+    /// the base config. Can also be set with TBDFLOW_PROFILE.
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+    /// Print plain ASCII instead of emojis and box-drawing characters. Can
+    /// also be set with `ascii_output: true` in config or TBDFLOW_ASCII=1.
+    #[arg(long, global = true)]
+    pub ascii: bool,
+    /// Run as if tbdflow was started in this directory, like `git -C`.
+    /// Commands work on the whole repository from any subdirectory, except
+    /// inside a monorepo project.
+    #[arg(long, global = true, value_name = "PATH")]
+    pub cwd: Option<std::path::PathBuf>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -35,13 +56,8 @@ pub enum Commands {
     --main-branch       Set the main branch name (default: main)\n  \
     --remote            Link and push to a remote repository URL")]
     Init {
-        /// Accept defaults and skip all interactive prompts (non-interactive mode).
-        #[arg(
-            short = 'y',
-            long = "yes",
-            alias = "defaults",
-            alias = "non-interactive"
-        )]
+        /// Accept defaults and skip all interactive prompts (same as the global --yes).
+        #[arg(long = "non-interactive", alias = "defaults")]
         non_interactive: bool,
         /// Set the main branch name (default: main).
         #[arg(long)]
@@ -55,6 +71,9 @@ pub enum Commands {
     Info {
         #[arg(short, long, default_value_t = false)]
         edit: bool,
+        /// Re-fetch the shared config named by `extends:` and pin it in `.tbdflow.lock`.
+        #[arg(long)]
+        update_lock: bool,
     },
     /// Checks for a new version of tbdflow and updates it if available.
     Update,
@@ -74,7 +93,12 @@ pub enum Commands {
     tbdflow commit -t fix -m \"fix login bug\" --breaking\n  \
     tbdflow commit -t chore -m \"update dependencies\" --tag \"v0.4.0\"\n  \
     tbdflow commit -t refactor -m \"rename internal API\" --breaking --breaking-description \"The `getUser` function has been renamed to `fetchUser`.\"\n  \
-    tbdflow commit -t fix -s ui -m \"fix button alignment\" --issue \"#123\""
+    tbdflow commit -t fix -s ui -m \"fix button alignment\" --issue \"#123\"\n  \
+    tbdflow commit -t docs -m \"fix typo in readme\" --skip-review\n  \
+    tbdflow commit -t feat -m \"add one-click checkout\" --experiment EXP-42\n  \
+    tbdflow commit -t fix -m \"Fix login bug.\" --fix\n  \
+    tbdflow commit -F msg.txt\n  \
+    git log -1 --format=%B | tbdflow commit --stdin"
     )]
     Commit {
         /// Commit type (e.g. 'feat', 'fix', 'chore', 'docs').
@@ -112,15 +136,99 @@ pub enum Commands {
         /// shell escaping. Conflicts with --body.
         #[arg(long, conflicts_with = "body")]
         body_file: Option<String>,
+        /// Read the complete commit message (header, body and trailers) from a
+        /// file ('-' for stdin). The message is parsed and linted as usual.
+        #[arg(
+            short = 'F',
+            long = "file",
+            conflicts_with_all = ["type", "scope", "message", "message_file", "body", "body_file", "breaking", "breaking_description", "issue", "stdin"]
+        )]
+        file: Option<String>,
+        /// Read the complete commit message from stdin. Same as '-F -'.
+        #[arg(
+            long,
+            conflicts_with_all = ["type", "scope", "message", "message_file", "body", "body_file", "breaking", "breaking_description", "issue"]
+        )]
+        stdin: bool,
+        /// Write the message in $EDITOR, starting from the other flags and the
+        /// type's body template (`commit.templates`).
+        #[arg(short, long, requires = "type")]
+        edit: bool,
+        /// Show a diffstat of what will be committed and ask for confirmation.
+        #[arg(long, conflicts_with = "no_preview")]
+        preview: bool,
+        /// Skip the preview even when `commit.preview` is enabled in .tbdflow.yml.
+        #[arg(long)]
+        no_preview: bool,
+        /// Add a 'Review: skip' trailer so no review is auto-triggered (exempt types only).
+        #[arg(long)]
+        skip_review: bool,
+        /// Tie the commit to an experiment with an 'Experiment: <ID>' trailer.
+        #[arg(long, value_name = "ID")]
+        experiment: Option<String>,
+        /// Mark this commit as the fix-forward for a review concern: adds a
+        /// 'Fixes-Review: <hash>' trailer, comments on the review issue and ticks the concern.
+        #[arg(long, value_name = "HASH")]
+        fixes_review: Option<String>,
+        /// Fix what the lint rules can correct on their own (a trailing period,
+        /// a capitalised subject, an uppercase scope) instead of aborting.
+        #[arg(long)]
+        fix: bool,
         #[arg(long, default_value_t = false, hide = true)]
         /// Internal flag to do a global commit bypassing monorepo safety
         include_projects: bool,
     },
+    /// Parses and lints a commit message without committing. Exits non-zero on violations.
+    #[command(
+        after_help = "Reads the message from the argument, a file (-F), or stdin.\n\
+    For commit-msg hooks, use 'tbdflow verify-commit-msg' instead.\n\n\
+    EXAMPLES:\n  \
+    tbdflow parse \"feat(api): add user endpoint\"\n  \
+    tbdflow parse -F .git/COMMIT_EDITMSG\n  \
+    git log -1 --format=%B | tbdflow parse --json"
+    )]
+    Parse {
+        /// The commit message to check. Reads stdin when omitted.
+        #[arg(conflicts_with = "file")]
+        message: Option<String>,
+        /// Read the commit message from a file ('-' for stdin).
+        #[arg(short = 'F', long = "file")]
+        file: Option<String>,
+    },
+    /// Checks a commit message file from a commit-msg hook. Prints one line per violation.
+    #[command(
+        name = "verify-commit-msg",
+        after_help = "Git passes the message file as the hook's first argument. Comment lines and\n\
+    everything below the scissors line are ignored. Merge, revert, fixup!, squash!\n\
+    and amend! messages made by git are accepted as they are.\n\n\
+    Each violation is printed as 'FILE:LINE:COLUMN: tbdflow: [rule] message', followed\n\
+    by field=, text= and fix= values as JSON strings, for editors and CI to parse.\n\n\
+    EXIT CODES:\n  \
+    0  The message is valid (or was made by git)\n  \
+    1  The message breaks one or more lint rules\n  \
+    2  The message file could not be read\n  \
+    3  .tbdflow.yml could not be loaded or has an invalid rule\n\n\
+    EXAMPLES:\n  \
+    tbdflow verify-commit-msg \"$1\"          # In .git/hooks/commit-msg\n  \
+    tbdflow verify-commit-msg .git/COMMIT_EDITMSG\n  \
+    tbdflow verify-commit-msg --fix .git/COMMIT_EDITMSG"
+    )]
+    VerifyCommitMsg {
+        /// Path to the commit message file.
+        file: String,
+        /// Rewrite the file with the auto-correctable violations fixed, then check it again.
+        #[arg(long)]
+        fix: bool,
+    },
     /// Creates and pushes a new short-lived branch.
     #[command(after_help = "EXAMPLES:\n  \
     tbdflow branch --type feat --name \"user-profile-page\" --issue \"ABC-123\"\n  \
     tbdflow branch -t fix -n \"login-bug\" --issue \"CBA-456\n  \
-    tbdflow branch -t chore -n \"update-dependencies\" -f \"39b68b5\"")]
+    tbdflow branch -t chore -n \"update-dependencies\" -f \"39b68b5\"\n  \
+    tbdflow branch -t fix -n \"backport\" -f release_1.2 --allow-non-main\n  \
+    tbdflow branch -t feat -n \"offline-spike\" --no-sync\n  \
+    tbdflow branch -t feat -n \"dashboard\" --description \"First cut of the usage dashboard\"\n  \
+    tbdflow branch list")]
     Branch {
         /// Type of branch (e.g., feat, fix, chore). See .tbdflow.yml for allowed types.
         #[arg(short, long)]
@@ -134,6 +242,100 @@ pub enum Commands {
         /// Optional commit hash on 'main' to branch from.
         #[arg(short, long)]
         from_commit: Option<String>,
+        /// Allow --from-commit to point at a commit that is not on origin/main.
+        #[arg(long, requires = "from_commit")]
+        allow_non_main: bool,
+        /// Branch from local main without pulling first (overrides branch.auto_sync).
+        #[arg(long)]
+        no_sync: bool,
+        /// What the branch is for; shown by 'branch list', 'check-branches' and 'complete'.
+        #[arg(long)]
+        description: Option<String>,
+        #[command(subcommand)]
+        action: Option<BranchAction>,
+    },
+    /// Lists trunk commits per experiment ('Experiment:' trailer), for cleaning up after A/B tests.
+    #[command(after_help = "EXAMPLES:\n  \
+    tbdflow experiments\n  \
+    tbdflow experiments EXP-42\n  \
+    tbdflow --json experiments")]
+    Experiments {
+        /// Only list commits for this experiment ID.
+        id: Option<String>,
+    },
+    /// Flags trunk commits made with raw git instead of tbdflow, to measure adoption.
+    #[command(
+        after_help = "A commit counts as made with tbdflow when it carries a tbdflow\n\
+    trailer or section (Intent Log, Tbdflow-Version, Fixes-Review, Experiment,\n\
+    Review: skip) or is named in the local journal. Commits that are neither\n\
+    conventional nor traced are flagged. Merge commits are not audited.\n\n\
+    EXAMPLES:\n  \
+    tbdflow audit\n  \
+    tbdflow audit --since \"1 week ago\"\n  \
+    tbdflow --json audit"
+    )]
+    Audit {
+        /// Time range to audit (e.g., "2 weeks ago", "2026-01-01").
+        #[arg(long, default_value = "30 days ago")]
+        since: String,
+    },
+    /// Searches past trunk commit messages and reuses one in the commit wizard.
+    #[command(
+        after_help = "Every word of the query must appear in the message (subject, scope or\n\
+    body), ignoring case. Only conventional commits on origin/main are\n\
+    searched, and repeated messages are listed once. In a terminal you pick\n\
+    one, adapt it in the commit wizard and commit; otherwise the matches are\n\
+    listed.\n\n\
+    EXAMPLES:\n  \
+    tbdflow snippet \"bump deps\"\n  \
+    tbdflow snippet release notes --since \"6 months ago\"\n  \
+    tbdflow snippet ci --list"
+    )]
+    Snippet {
+        /// Words to search for.
+        #[arg(required = true, num_args = 1..)]
+        query: Vec<String>,
+        /// Only search commits since this date (e.g., "6 months ago").
+        #[arg(long)]
+        since: Option<String>,
+        /// Maximum number of matches to show.
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+        /// List the matches without opening the wizard.
+        #[arg(long)]
+        list: bool,
+    },
+    /// Reports trunk-based development metrics from trunk history.
+    #[command(
+        name = "metrics",
+        subcommand,
+        after_help = "INTEGRATION:\n  \
+    Every first-parent commit on origin/main is an integration: a direct\n  \
+    commit, or the merge commit of a completed branch. An author's commits\n  \
+    landing within 10 minutes of each other count once, as one push.\n\n\
+    EXAMPLES:\n  \
+    tbdflow metrics integration\n  \
+    tbdflow metrics integration --since 2w\n  \
+    tbdflow --json metrics integration --since 90d"
+    )]
+    Metrics(MetricsAction),
+    /// Switches between main and short-lived branches, with safety checks.
+    #[command(
+        after_help = "Uncommitted changes are refused by default. With --stash (or\n\
+    switch.uncommitted: stash in .tbdflow.yml) they are parked and restored when you\n\
+    switch back. Warns when the target branch is stale or behind origin/main and offers\n\
+    to rebase it.\n\n\
+    EXAMPLES:\n  \
+    tbdflow switch feat/user-profile-page\n  \
+    tbdflow switch                        # Back to main\n  \
+    tbdflow switch fix/login-bug --stash"
+    )]
+    Switch {
+        /// Branch to switch to. Defaults to the main branch.
+        target: Option<String>,
+        /// Park uncommitted changes instead of refusing (overrides switch.uncommitted).
+        #[arg(long)]
+        stash: bool,
     },
     /// Merges a short-lived branch into 'main' and deletes it.
     #[command(after_help = "EXAMPLES:\n  \
@@ -146,10 +348,75 @@ pub enum Commands {
         /// Name or version of the branch to complete.
         #[arg(short, long)]
         name: Option<String>,
+        /// Merge even when commits on the branch have open DoD 'TODO:' items.
+        #[arg(long)]
+        accept_todos: bool,
+    },
+    /// Diagnoses common environment problems (git setup, credentials, Windows quirks).
+    #[command(
+        after_help = "Checks git, your identity, the origin remote and the GitHub CLI.\n\
+    On Windows it also checks core.autocrlf, long path support, the credential\n\
+    manager and PowerShell completion. Exits non-zero when a check fails.\n\n\
+    EXAMPLES:\n  \
+    tbdflow doctor\n  \
+    tbdflow --json doctor"
+    )]
+    Doctor,
+    /// Shows who you commit and push as: git identity, signing, pair session and GitHub login.
+    #[command(
+        after_help = "The first thing to check when a push, a signed commit or a review fails.\n\
+    Shows the effective git author (and where it is configured), commit signing,\n\
+    the active pair session, the forge host of origin and the GitHub CLI login.\n\
+    Token values are never printed, only which token variables are set.\n\n\
+    EXAMPLES:\n  \
+    tbdflow whoami\n  \
+    tbdflow --json whoami"
+    )]
+    Whoami,
+    /// Removes what tbdflow left behind in this clone and no longer needs.
+    #[command(after_help = "Lists, then removes after confirmation:\n  \
+    - the intent log of a branch that no longer exists\n  \
+    - WIP snapshots whose commits git has pruned\n  \
+    - journal entries about commits that no longer exist\n  \
+    - state files moved aside as unreadable (*.corrupt)\n  \
+    - git lock files more than an hour old, left by a process that died\n\n\
+    EXAMPLES:\n  \
+    tbdflow --dry-run clean     # Only list what would go\n  \
+    tbdflow clean\n  \
+    tbdflow --yes --json clean  # In scripts")]
+    Clean,
+    /// Finishes the last commit that failed part-way, from the step that failed.
+    #[command(
+        after_help = "When a commit fails after its message and DoD checklist are settled,\n\
+    for example because the push or a GitHub call hit a network blip, the\n\
+    remaining steps are kept in .git/tbdflow/retry.json. 'tbdflow retry' runs\n\
+    them from the one that failed: commit, push, review, link-fixes, tag.\n\n\
+    EXAMPLES:\n  \
+    tbdflow retry\n  \
+    tbdflow retry --discard  # Forget it; nothing is undone"
+    )]
+    Retry {
+        /// Forget the failed commit instead of finishing it.
+        #[arg(long)]
+        discard: bool,
     },
     /// Syncs with the remote, shows recent history, and checks for stale branches.
     /// When ci_check is enabled, checks trunk CI status before pulling.
-    Sync,
+    #[command(after_help = "EXAMPLES:\n  \
+    tbdflow sync\n  \
+    tbdflow sync --watch                  # Nudge me when main moves ahead\n  \
+    tbdflow sync --watch --interval 300 --notify")]
+    Sync {
+        /// Keep running, fetching origin periodically and notifying when main moves ahead.
+        #[arg(long)]
+        watch: bool,
+        /// Seconds between fetches in watch mode.
+        #[arg(long, default_value_t = 60, requires = "watch", value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+        /// Also send a desktop notification in watch mode.
+        #[arg(long, requires = "watch")]
+        notify: bool,
+    },
     /// Scans active remote branches for overlapping work that may cause merge conflicts.
     #[command(
         name = "radar",
@@ -181,19 +448,39 @@ pub enum Commands {
     GenerateManPage,
     /// Generates shell completion scripts.
     #[command(name = "generate-completion", hide = true)] // Hidden from help
-    Completion {
+    GenerateCompletion {
         #[arg(value_enum)]
         shell: Shell,
     },
+    /// Prints or installs shell completions.
+    #[command(
+        name = "completion",
+        args_conflicts_with_subcommands = true,
+        after_help = "EXAMPLES:\n  \
+    tbdflow completion install             # Detect the shell from $SHELL and install\n  \
+    tbdflow completion install zsh         # Install for a named shell\n  \
+    tbdflow completion bash                # Print the script instead"
+    )]
+    Completion {
+        /// Print the completion script for this shell.
+        #[arg(value_enum)]
+        shell: Option<Shell>,
+        #[command(subcommand)]
+        action: Option<CompletionAction>,
+    },
     /// Generates a changelog from Conventional Commits.
     #[command(
         name = "changelog",
         after_help = "EXAMPLES:\n  \
     tbdflow changelog --from v1.0.0 --to v2.0.0\n  \
     tbdflow changelog --unreleased\n  \
-    tbdflow changelog --from v1.0.0"
+    tbdflow changelog --from v1.0.0\n  \
+    tbdflow changelog --unreleased --repo ./checkout   # From CI, without cd-ing"
     )]
     Changelog {
+        /// Read the history of the repository at this path (a checkout or a bare repository).
+        #[arg(long, value_name = "PATH", visible_alias = "git-dir")]
+        repo: Option<std::path::PathBuf>,
         /// Generate from this git reference (tag or commit hash).
         #[arg(long)]
         from: Option<String>,
@@ -203,13 +490,44 @@ pub enum Commands {
         /// Generate for all commits since the latest tag.
         #[arg(long, default_value_t = false)]
         unreleased: bool,
+        /// With --unreleased, start from the latest release tag, passing over
+        /// pre-releases such as v2.0.0-rc.1 (changelog.skip_prereleases).
+        #[arg(long, requires = "unreleased")]
+        skip_prereleases: bool,
     },
-    /// Internal commands for configuration.
-    #[command(name = "config", hide = true)]
+    /// Prints the next semantic version, from the Conventional Commits since the last release tag.
+    #[command(
+        name = "next-version",
+        after_help = "A breaking change bumps the major version, a feat the minor and a fix or\n\
+    perf the patch. Pre-release tags (v2.0.0-rc.1) are not releases: the bump is\n\
+    counted from the latest release tag. Build metadata (+build.5) is ignored.\n\
+    Exits non-zero when nothing needs releasing.\n\n\
+    EXAMPLES:\n  \
+    tbdflow next-version                 # v1.5.0\n  \
+    tbdflow next-version --pre rc        # v2.0.0-rc.1, then v2.0.0-rc.2\n  \
+    git tag \"$(tbdflow next-version)\"\n  \
+    tbdflow --json next-version"
+    )]
+    NextVersion {
+        /// Make it a pre-release with this identifier, counting up from the
+        /// existing ones: --pre rc gives -rc.1, then -rc.2.
+        #[arg(long, value_name = "ID")]
+        pre: Option<String>,
+    },
+    /// Reads and edits settings in .tbdflow.yml.
+    #[command(
+        name = "config",
+        after_help = "Keys are dotted paths into .tbdflow.yml, e.g. lint.subject_line_rules.max_length.\n\
+    'get' and 'list' show the effective values, defaults and --profile included.\n\
+    'set' and 'unset' edit the root .tbdflow.yml in place, keeping its comments.\n\
+    'validate' reports typos and wrong types with their line numbers."
+    )]
     Config {
         /// Print the DoD checklist items to stdout.
-        #[arg(long)]
+        #[arg(long, hide = true)]
         get_dod: bool,
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
     },
     /// Prints the short SHA of the current HEAD commit.
     #[command(name = "head-sha", hide = true)]
@@ -233,6 +551,25 @@ pub enum Commands {
         #[arg(long, default_value_t = false)]
         no_push: bool,
     },
+    /// Writes a blame-free incident summary for a revert or fix-forward commit.
+    #[command(
+        name = "postmortem",
+        after_help = "BLAME-FREE POSTMORTEM:\n  \
+    Given the commit that reverted a change (e.g. made by 'tbdflow undo') or\n  \
+    fixed it forward (a 'Fixes-Review:' trailer), assembles a markdown timeline:\n  \
+    the original commit, its CI runs, the review issue and concerns raised on it,\n  \
+    journal events, and the revert or fix. Author names are left out.\n\n\
+    EXAMPLES:\n  \
+    tbdflow postmortem def5678                       # Print to stdout\n  \
+    tbdflow postmortem def5678 -o incident.md       # Write to a file"
+    )]
+    Postmortem {
+        /// The revert or fix-forward commit.
+        sha: String,
+        /// Write the summary to this file instead of stdout.
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<std::path::PathBuf>,
+    },
     /// Logs an intent note (breadcrumb) during development.
     /// Notes are captured in a local .tbdflow-intent.json and included
     /// in the next commit message as an Intent Log.
@@ -271,6 +608,79 @@ pub enum Commands {
     tbdflow task clear"
     )]
     Task(TaskAction),
+    /// Temporarily treats another branch (e.g. a release branch) as trunk.
+    #[command(
+        name = "target",
+        subcommand,
+        after_help = "ALTERNATE TRUNK:\n  \
+    During stabilisation a release branch can be the integration target.\n  \
+    While a target is set, commit, sync, branch and complete treat it as\n  \
+    main. It is kept in .git/tbdflow/target.json until reset.\n\n\
+    EXAMPLES:\n  \
+    tbdflow target set release_2.0\n  \
+    tbdflow target show\n  \
+    tbdflow target reset"
+    )]
+    Target(TargetAction),
+    /// Installs tbdflow's commit-msg hook alongside any hooks already in place.
+    #[command(
+        name = "hooks",
+        subcommand,
+        after_help = "HOOKS:\n  \
+    The commit-msg hook runs 'tbdflow verify-commit-msg' on every message. It\n  \
+    is written to the directory git runs hooks from, so core.hooksPath is\n  \
+    honoured. An existing commit-msg hook is kept as commit-msg.pre-tbdflow\n  \
+    and runs first.\n  \
+    What was changed is recorded in .git/tbdflow/hooks.json, and uninstall\n  \
+    puts every file back as it was.\n\n\
+    EXAMPLES:\n  \
+    tbdflow hooks install\n  \
+    tbdflow hooks status\n  \
+    tbdflow hooks uninstall"
+    )]
+    Hooks(HooksAction),
+    /// Reports on the Definition of Done checklist.
+    #[command(
+        name = "dod",
+        subcommand,
+        after_help = "Commits made with --no-verify while a .dod.yml checklist exists are\n\
+    recorded in the local journal (.git/tbdflow/journal.jsonl).\n\n\
+    EXAMPLES:\n  \
+    tbdflow dod report\n  \
+    tbdflow dod report --days 30\n  \
+    tbdflow --json dod report"
+    )]
+    Dod(DodAction),
+    /// Manages a pair programming session.
+    #[command(
+        name = "pair",
+        subcommand,
+        after_help = "PAIR PROGRAMMING:\n  \
+    While a pair session is active, every 'tbdflow commit' gets a\n  \
+    Co-authored-by trailer for each co-author. The session is stored\n  \
+    locally in .git/ and never committed.\n\n\
+    EXAMPLES:\n  \
+    tbdflow pair start alice                          # Resolve from commit history\n  \
+    tbdflow pair start \"Ada Lovelace <ada@example.com>\"\n  \
+    tbdflow pair show\n  \
+    tbdflow pair stop"
+    )]
+    Pair(PairAction),
+    /// Rotates drivers during a mob programming session on a shared branch.
+    #[command(
+        name = "mob",
+        subcommand,
+        after_help = "MOB ROTATION:\n  \
+    'mob next' commits everything as a wip: commit, pushes the shared\n  \
+    branch and prints what the next driver should run. 'mob done' squashes\n  \
+    the chain of wip: commits into one conventional commit that credits\n  \
+    every driver with a Co-authored-by trailer.\n\n\
+    EXAMPLES:\n  \
+    tbdflow mob next\n  \
+    tbdflow mob done -t feat -s auth -m \"add login throttling\"\n  \
+    tbdflow complete -t feat -n login-throttling"
+    )]
+    Mob(MobAction),
     /// Recovers a WIP snapshot from the safety log.
     /// Snapshots are captured automatically during notes and syncs.
     #[command(
@@ -296,18 +706,34 @@ pub enum Commands {
         after_help = "EXAMPLES:\n  \
         tbdflow review abc1234                      # Create review for a specific commit\n  \
         tbdflow review --trigger                    # Create review for HEAD commit\n  \
+        tbdflow review --trigger --batch main~5..main  # One review for a set of commits\n  \
         tbdflow review --digest                     # Show commits since yesterday\n  \
         tbdflow review --digest --since \"3 days ago\"\n  \
+        tbdflow review --digest --resume --limit 50  # Pick up where the last digest ended\n  \
+        tbdflow review --digest --author ada --path 'src/api/**'\n  \
         tbdflow review --approve abc1234           # Mark commit as reviewed\n  \
         tbdflow review --concern abc1234 -m \"Thread safety issue\"\n  \
-        tbdflow review --dismiss abc1234 -m \"Won't fix, out of scope\"\n\n\
+        tbdflow review --concern abc1234 -m \"Drops writes\" --severity critical\n  \
+        tbdflow review --dismiss abc1234 -m \"Won't fix, out of scope\"\n  \
+        tbdflow review mine --label concern        # My reviews with open concerns\n  \
+        tbdflow review list --authored --older-than 3d\n\n\
         WORKFLOW:\n  \
         1. Commit directly to main with 'tbdflow commit'\n  \
         2. Review is triggered automatically (if enabled) or manually\n  \
         3. Team reviews asynchronously without blocking\n  \
         4. Use --concern to flag issues (keeps issue open)\n  \
         5. Use --approve to mark commits as reviewed\n  \
-        6. Use --dismiss to close without fixing"
+        6. Use --dismiss to close without fixing\n\n\
+        LABELS:\n  \
+        'tbdflow review labels sync' creates or updates the review labels\n  \
+        (names, colors, descriptions) configured under review.labels.\n\n\
+        SWEEP:\n  \
+        'tbdflow review sweep' lists pending reviews older than\n  \
+        review.stale_after_hours and escalates those older than\n  \
+        review.sla_hours, pinging review.fallback_reviewers.\n\n\
+        OFFLINE:\n  \
+        Approvals, concerns and dismissals made while GitHub is unreachable\n  \
+        are queued and sent on the next online run, or with 'tbdflow review flush'."
     )]
     Review {
         /// Commit SHA to trigger a review for. If given without flags, triggers a review.
@@ -316,10 +742,13 @@ pub enum Commands {
         /// Trigger a review request for the current HEAD commit.
         #[arg(long, conflicts_with_all = ["digest", "approve", "concern", "dismiss"])]
         trigger: bool,
+        /// With --trigger, open one review issue for every commit in a range (e.g. main~5..main).
+        #[arg(long, value_name = "RANGE", requires = "trigger")]
+        batch: Option<String>,
         /// Generate a digest of commits needing review.
         #[arg(long, conflicts_with_all = ["trigger", "approve", "concern", "dismiss"])]
         digest: bool,
-        /// Mark a specific commit as approved/reviewed (closes issue with review-accepted label).
+        /// Approve a commit; closes the review (review-accepted label) once `review.required_approvals` is reached.
         #[arg(long, conflicts_with_all = ["trigger", "digest", "concern", "dismiss"])]
         approve: Option<String>,
         /// Raise a concern on a commit (keeps issue open, adds review-concern label).
@@ -331,12 +760,241 @@ pub enum Commands {
         /// Message for concern or dismiss (required with --concern or --dismiss).
         #[arg(short, long)]
         message: Option<String>,
+        /// Severity of a concern; adds a severity label, and 'critical' always fails the commit status.
+        #[arg(long, value_enum, requires = "concern")]
+        severity: Option<ConcernSeverity>,
         /// Time range for digest (e.g., "1 day ago", "2024-01-01").
         #[arg(long, default_value = "1 day ago")]
         since: String,
+        /// Digest only commits whose author matches this pattern (as `git log --author`).
+        #[arg(long)]
+        author: Option<String>,
+        /// Digest only commits touching these paths (repeatable).
+        #[arg(long = "path", value_name = "PATH")]
+        paths: Vec<String>,
+        /// Show at most this many digest commits per page.
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        limit: Option<u64>,
+        /// Digest page to show, newest first (requires --limit).
+        #[arg(long, default_value_t = 1, requires = "limit", value_parser = clap::value_parser!(u64).range(1..))]
+        page: u64,
+        /// Digest commits since the last --resume digest instead of --since.
+        #[arg(long)]
+        resume: bool,
         /// Override default reviewers (comma-separated GitHub usernames).
         #[arg(long, value_delimiter = ',')]
         reviewers: Option<Vec<String>>,
+        #[command(subcommand)]
+        action: Option<ReviewAction>,
+    },
+}
+
+/// Sub-actions for the `tbdflow target` command.
+#[derive(Subcommand, Debug)]
+pub enum TargetAction {
+    /// Treat this branch as trunk until reset.
+    Set {
+        /// The branch to integrate into (e.g. release_2.0).
+        branch: String,
+    },
+    /// Show which branch is trunk.
+    Show,
+    /// Go back to the configured main branch.
+    Reset,
+}
+
+/// Sub-actions for the `tbdflow config` command.
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Print the effective value of a setting.
+    Get {
+        /// Dotted key, e.g. lint.subject_line_rules.max_length.
+        key: String,
+    },
+    /// Write a setting to .tbdflow.yml. The value is read as YAML (50, true, [a, b]).
+    Set {
+        /// Dotted key, e.g. lint.subject_line_rules.max_length.
+        key: String,
+        value: String,
+    },
+    /// Remove a setting from .tbdflow.yml so its default applies.
+    Unset {
+        /// Dotted key, e.g. lint.subject_line_rules.max_length.
+        key: String,
+    },
+    /// Print every effective setting as key = value.
+    List,
+    /// Check .tbdflow.yml for unknown keys and wrong types.
+    Validate {
+        /// Print the JSON Schema instead, e.g. for editor completion.
+        #[arg(long)]
+        schema: bool,
+    },
+    /// Translate a commitlint or commitizen config into the lint section of .tbdflow.yml.
+    Import {
+        /// The tool whose config to read.
+        #[arg(long, value_enum)]
+        from: ImportSource,
+        /// The config file; looked for at the repository root when omitted.
+        #[arg(long)]
+        file: Option<std::path::PathBuf>,
+    },
+}
+
+/// Sub-actions for the `tbdflow hooks` command.
+#[derive(Subcommand, Debug)]
+pub enum HooksAction {
+    /// Install the commit-msg hook, chaining to any existing one.
+    Install,
+    /// Restore the hooks that were there before 'hooks install'.
+    Uninstall,
+    /// Show where hooks live and which ones tbdflow installed.
+    Status,
+}
+
+/// Sub-actions for the `tbdflow metrics` command.
+#[derive(Subcommand, Debug)]
+pub enum MetricsAction {
+    /// Per author: how often they integrate into trunk and their longest gap.
+    Integration {
+        /// Time range, as shorthand (30d, 2w, 12h) or any git date (e.g. "2026-01-01").
+        #[arg(long, default_value = "30d")]
+        since: String,
+    },
+}
+
+/// Sub-actions for the `tbdflow completion` command.
+#[derive(Subcommand, Debug)]
+pub enum CompletionAction {
+    /// Writes the completion script where the shell loads it from, then checks that it loads.
+    Install {
+        /// Shell to install for; detected from $SHELL when omitted.
+        #[arg(value_enum)]
+        shell: Option<Shell>,
+    },
+}
+
+/// Sub-actions for the `tbdflow branch` command.
+#[derive(Subcommand, Debug)]
+pub enum BranchAction {
+    /// Lists local short-lived branches with the context recorded when they were created.
+    List,
+}
+
+/// Sub-actions for the `tbdflow review` command.
+#[derive(Subcommand, Debug)]
+pub enum ReviewAction {
+    /// Manage the review label taxonomy in the repository.
+    #[command(subcommand)]
+    Labels(ReviewLabelsAction),
+    /// Report stale pending reviews and escalate those past the SLA (for scheduled CI jobs).
+    Sweep,
+    /// Send approvals, concerns and dismissals queued while GitHub was unreachable.
+    Flush,
+    /// Show the review of a commit recorded in git notes or the review ledger.
+    Status {
+        /// The commit to show (defaults to HEAD).
+        #[arg(default_value = "HEAD")]
+        commit: String,
+    },
+    /// Show everything recorded about a commit's review: the issue, labels, concerns,
+    /// comments and the commit's diffstat.
+    Show {
+        /// The commit to show (defaults to HEAD).
+        #[arg(default_value = "HEAD")]
+        commit: String,
+    },
+    /// Review throughput on trunk: reviewed vs unreviewed commits, median time to
+    /// approval, concerns per author and top reviewers.
+    Stats {
+        /// Time range, as shorthand (30d, 2w, 12h) or any git date (e.g. "2026-01-01").
+        #[arg(long, default_value = "30d")]
+        since: String,
+    },
+    /// List open reviews (commit, author, age, reviewers, state), optionally filtered.
+    List {
+        /// Only reviews assigned to me (for reviews in git: naming my user.name).
+        #[arg(long)]
+        mine: bool,
+        /// Only reviews of commits I authored.
+        #[arg(long)]
+        authored: bool,
+        /// Only reviews with this label; a role such as 'concern' or 'critical', or a label name.
+        #[arg(long)]
+        label: Option<String>,
+        /// Only reviews at least this old (e.g. 12h, 3d, 2w).
+        #[arg(long, value_name = "AGE")]
+        older_than: Option<String>,
+    },
+    /// List open reviews assigned to me (same as 'review list --mine').
+    Mine {
+        /// Only reviews of commits I authored.
+        #[arg(long)]
+        authored: bool,
+        /// Only reviews with this label; a role such as 'concern' or 'critical', or a label name.
+        #[arg(long)]
+        label: Option<String>,
+        /// Only reviews at least this old (e.g. 12h, 3d, 2w).
+        #[arg(long, value_name = "AGE")]
+        older_than: Option<String>,
+    },
+}
+
+/// Sub-actions for `tbdflow review labels`.
+#[derive(Subcommand, Debug)]
+pub enum ReviewLabelsAction {
+    /// Create or update the repository's review labels to match `review.labels`.
+    Sync,
+}
+
+/// Sub-actions for the `tbdflow pair` command.
+#[derive(Subcommand, Debug)]
+pub enum PairAction {
+    /// Start pairing with one or more co-authors.
+    Start {
+        /// Co-authors as "Name <email>", a name, or an email user found in the history.
+        #[arg(required = true)]
+        coauthors: Vec<String>,
+    },
+    /// Show the active pair session.
+    Show,
+    /// Stop pairing; commits no longer get Co-authored-by trailers.
+    Stop,
+}
+
+/// Sub-actions for the `tbdflow mob` command.
+#[derive(Subcommand, Debug)]
+pub enum MobAction {
+    /// Commit and push the current WIP, then hand over to the next driver.
+    Next,
+    /// Squash the WIP commits into one conventional commit.
+    Done {
+        /// Type of commit (e.g. feat, fix, chore).
+        #[arg(short, long)]
+        r#type: String,
+        /// Optional scope of the commit.
+        #[arg(short, long)]
+        scope: Option<String>,
+        /// The descriptive commit message.
+        #[arg(short, long)]
+        message: String,
+        /// Optional longer body for the commit message.
+        #[arg(long)]
+        body: Option<String>,
+        /// Optional issue reference.
+        #[arg(long)]
+        issue: Option<String>,
+    },
+}
+
+/// Sub-actions for the `tbdflow dod` command.
+#[derive(Subcommand, Debug)]
+pub enum DodAction {
+    /// Summarise how often each author skipped the DoD checklist with --no-verify.
+    Report {
+        /// Only count skips from the last N days.
+        #[arg(long, value_parser = clap::value_parser!(i64).range(1..))]
+        days: Option<i64>,
     },
 }
 
@@ -0,0 +1,204 @@
+//! The `defaults:` section of `.tbdflow.yml`: team-wide default values for
+//! command flags. They are set as clap defaults, so `--help` shows them and a
+//! flag given on the command line still wins.
+
+use crate::cli::Cli;
+use crate::config;
+use crate::git::RunOpts;
+use clap::error::ErrorKind;
+use clap::{Arg, ArgAction, Command, CommandFactory, FromArgMatches, Parser};
+use std::collections::BTreeMap;
+
+/// Flag values keyed by command, then by flag (`no_verify` or `no-verify`).
+pub type Defaults = BTreeMap<String, BTreeMap<String, yaml_serde::Value>>;
+
+/// The long flag of `command` that `flag` names.
+fn find_arg<'a>(command: &'a Command, flag: &str) -> Option<&'a Arg> {
+    let id = flag.replace('-', "_");
+    command
+        .get_arguments()
+        .find(|arg| arg.get_id().as_str() == id && arg.get_long().is_some())
+}
+
+fn is_switch(arg: &Arg) -> bool {
+    matches!(arg.get_action(), ArgAction::SetTrue)
+}
+
+/// The default `value` sets for `arg`, as clap reads it from the command line.
+fn default_text(arg: &Arg, value: &yaml_serde::Value) -> Result<String, String> {
+    match value {
+        yaml_serde::Value::Bool(b) => Ok(b.to_string()),
+        _ if is_switch(arg) => Err("must be true or false".to_string()),
+        yaml_serde::Value::Number(n) => Ok(n.to_string()),
+        yaml_serde::Value::String(s) => Ok(s.clone()),
+        _ => Err("must be true/false, a number or text".to_string()),
+    }
+}
+
+/// A switch needs a way back once it defaults to on: it takes `=false`.
+fn with_switch_default(arg: Arg, value: String) -> Arg {
+    arg.action(ArgAction::Set)
+        .num_args(0..=1)
+        .require_equals(true)
+        .default_missing_value("true")
+        .value_parser(clap::value_parser!(bool))
+        .default_value(value)
+}
+
+/// What is wrong with `defaults`, as dotted key and message: commands and
+/// flags tbdflow does not have, and values a flag cannot take.
+pub fn check(defaults: &Defaults) -> Vec<(String, String)> {
+    let cli = Cli::command();
+    let mut problems = Vec::new();
+    for (name, flags) in defaults {
+        let Some(command) = cli.find_subcommand(name) else {
+            problems.push((
+                format!("defaults.{}", name),
+                "is not a tbdflow command".to_string(),
+            ));
+            continue;
+        };
+        for (flag, value) in flags {
+            let key = format!("defaults.{}.{}", name, flag);
+            match find_arg(command, flag) {
+                None => problems.push((key, format!("is not a flag of 'tbdflow {}'", name))),
+                Some(arg) => {
+                    if let Err(message) = default_text(arg, value) {
+                        problems.push((key, message));
+                    }
+                }
+            }
+        }
+    }
+    problems
+}
+
+/// `command` with `defaults` set on its subcommands' flags. Entries that
+/// [`check`] reports are left out.
+pub fn apply(mut command: Command, defaults: &Defaults) -> Command {
+    for (name, flags) in defaults {
+        let Some(sub_name) = command
+            .find_subcommand(name)
+            .map(|sub| sub.get_name().to_string())
+        else {
+            continue;
+        };
+        for (flag, value) in flags {
+            let Some((id, switch, text)) = command
+                .find_subcommand(&sub_name)
+                .and_then(|sub| find_arg(sub, flag))
+                .and_then(|arg| {
+                    let text = default_text(arg, value).ok()?;
+                    Some((arg.get_id().to_string(), is_switch(arg), text))
+                })
+            else {
+                continue;
+            };
+            command = command.mut_subcommand(&sub_name, |sub| {
+                sub.mut_arg(id, |arg| {
+                    if switch {
+                        with_switch_default(arg, text)
+                    } else {
+                        arg.default_value(text)
+                    }
+                })
+            });
+        }
+    }
+    command
+}
+
+/// Parses the command line again with `defaults` applied. Like the first
+/// parse, exits with clap's message when it does not parse.
+pub fn reparse(defaults: &Defaults) -> Cli {
+    let matches = apply(Cli::command(), defaults).get_matches();
+    Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit())
+}
+
+/// Parses the command line. Help is printed with the configured defaults,
+/// when there is a config to read them from, so `--help` tells the truth.
+pub fn parse() -> Cli {
+    match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) if e.kind() == ErrorKind::DisplayHelp => {
+            let profile = std::env::var("TBDFLOW_PROFILE")
+                .ok()
+                .filter(|p| !p.is_empty());
+            if let Ok(config) =
+                config::load_tbdflow_config(profile.as_deref(), RunOpts::new(false, false))
+                && !config.defaults.is_empty()
+            {
+                reparse(&config.defaults);
+            }
+            e.exit()
+        }
+        Err(e) => e.exit(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Commands;
+
+    fn defaults(yaml: &str) -> Defaults {
+        yaml_serde::from_str(yaml).unwrap()
+    }
+
+    fn parse(defaults: &Defaults, args: &[&str]) -> Cli {
+        let matches = apply(Cli::command(), defaults)
+            .try_get_matches_from(std::iter::once("tbdflow").chain(args.iter().copied()))
+            .unwrap();
+        Cli::from_arg_matches(&matches).unwrap()
+    }
+
+    #[test]
+    fn defaults_apply_unless_the_flag_is_given() {
+        let defaults = defaults("commit:\n  no-verify: true\n  scope: api\n");
+        let Commands::Commit {
+            no_verify, scope, ..
+        } = parse(&defaults, &["commit", "-t", "feat", "-m", "x"]).command
+        else {
+            panic!("not a commit");
+        };
+        assert!(no_verify);
+        assert_eq!(scope.as_deref(), Some("api"));
+
+        let Commands::Commit {
+            no_verify, scope, ..
+        } = parse(
+            &defaults,
+            &["commit", "--no-verify=false", "-s", "ui", "-m", "x"],
+        )
+        .command
+        else {
+            panic!("not a commit");
+        };
+        assert!(!no_verify);
+        assert_eq!(scope.as_deref(), Some("ui"));
+    }
+
+    #[test]
+    fn check_reports_unknown_commands_flags_and_values() {
+        let defaults = defaults(
+            "commit:\n  no_verify: yes please\n  colour: red\ncomplete:\n  accept_todos: true\nfrobnicate:\n  x: 1\n",
+        );
+        assert_eq!(
+            check(&defaults),
+            vec![
+                (
+                    "defaults.commit.colour".to_string(),
+                    "is not a flag of 'tbdflow commit'".to_string()
+                ),
+                (
+                    "defaults.commit.no_verify".to_string(),
+                    "must be true or false".to_string()
+                ),
+                (
+                    "defaults.frobnicate".to_string(),
+                    "is not a tbdflow command".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -1,9 +1,13 @@
+use crate::branch::BranchMetadata;
 use crate::git::RunOpts;
-use crate::{config, git, intent, radar};
+use crate::{
+    branch, config, conventions, git, hooks, intent, output, pair, prompt, radar, review, shallow,
+    toolchain, version,
+};
 use anyhow::Result;
 use clap::Command as Commands;
 use colored::*;
-use dialoguer::{Confirm, Input, theme::ColorfulTheme};
+use dialoguer::{Input, Select};
 use serde::Serialize;
 use std::env;
 use std::fs;
@@ -47,6 +51,10 @@ pub enum ErrorCode {
     CannotCompleteMain,
     /// A git command failed unexpectedly.
     GitFailed,
+    /// Syncing would run into merge conflicts with trunk.
+    ConflictsPredicted,
+    /// HEAD is detached, so there is no branch to work on.
+    DetachedHead,
 }
 
 impl<T: Serialize> TbdResponse<T> {
@@ -82,6 +90,8 @@ impl<T: Serialize> TbdResponse<T> {
 #[derive(Serialize)]
 pub struct InfoResponse {
     pub mode: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
     pub main_branch_name: String,
     pub stale_branch_threshold_days: i64,
     pub log_display_count: usize,
@@ -134,6 +144,12 @@ pub struct StatusResponse {
     pub trunk_ci: String,
     pub changed_files: Vec<String>,
     pub monorepo: MonorepoStatusResponse,
+    /// Co-authors of the active pair session, if any.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pair: Vec<String>,
+    /// Review work waiting on you, when the review system is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reviews: Option<review::ReviewObligations>,
 }
 
 #[derive(Serialize)]
@@ -230,6 +246,15 @@ pub struct SyncResponse {
     pub stale_branches: Vec<StaleBranchResponse>,
 }
 
+/// One JSON line per fetch for `tbdflow sync --watch --json`.
+#[derive(Serialize)]
+pub struct SyncWatchResponse {
+    pub main_branch: String,
+    /// Commits trunk is ahead of the local branch; null when the fetch failed.
+    pub behind: Option<u64>,
+    pub fetch_error: Option<String>,
+}
+
 #[derive(Serialize)]
 pub struct SyncCommitResponse {
     pub hash: String,
@@ -242,6 +267,441 @@ pub struct SyncCommitResponse {
 pub struct StaleBranchResponse {
     pub branch: String,
     pub days_inactive: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<BranchMetadata>,
+}
+
+/// JSON payload for `tbdflow config get --json`, and each entry of `config list --json`.
+#[derive(Serialize)]
+pub struct ConfigValueResponse {
+    pub key: String,
+    pub value: serde_json::Value,
+}
+
+/// JSON payload for `tbdflow config list --json`.
+#[derive(Serialize)]
+pub struct ConfigListResponse {
+    pub settings: Vec<ConfigValueResponse>,
+}
+
+/// JSON payload for `tbdflow config validate --json`.
+#[derive(Serialize)]
+pub struct ConfigValidateResponse {
+    pub file: String,
+    pub valid: bool,
+    pub problems: Vec<ConfigProblemResponse>,
+}
+
+/// One problem found by `tbdflow config validate`.
+#[derive(Serialize)]
+pub struct ConfigProblemResponse {
+    pub line: Option<usize>,
+    pub key: String,
+    pub message: String,
+}
+
+/// JSON payload for `tbdflow current-branch --json`.
+#[derive(Serialize)]
+pub struct CurrentBranchResponse {
+    pub branch: String,
+    pub is_main: bool,
+}
+
+/// JSON payload for `tbdflow check-branches --json`.
+#[derive(Serialize)]
+pub struct CheckBranchesResponse {
+    pub current_branch: String,
+    pub stale_branch_threshold_days: i64,
+    pub stale_branches: Vec<StaleBranchResponse>,
+}
+
+/// JSON payload for `tbdflow changelog --json`.
+#[derive(Serialize)]
+pub struct ChangelogResponse {
+    pub range: String,
+    /// The release heading: "Unreleased Changes", or the version and date for `--to`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub sections: Vec<ChangelogSectionResponse>,
+}
+
+#[derive(Serialize)]
+pub struct ChangelogSectionResponse {
+    pub header: String,
+    pub entries: Vec<ChangelogEntryResponse>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct ChangelogEntryResponse {
+    pub hash: String,
+    pub r#type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    pub description: String,
+    pub breaking: bool,
+    /// Link to the commit, when the remote URL is known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The commit in the same range that reverted this one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverted_by: Option<String>,
+}
+
+/// JSON payload entry for `tbdflow dod report --json`.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct DodSkipReportResponse {
+    pub author: String,
+    pub skips: usize,
+    pub last_skipped: String,
+}
+
+/// JSON payload entry for `tbdflow review list --json`.
+#[derive(Serialize)]
+pub struct ReviewListResponse {
+    /// The review issue; absent for reviews recorded in git.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number: Option<i64>,
+    /// Short hash of the reviewed commit, or `first..last` for a batch.
+    pub commit: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub url: String,
+    pub author: Option<String>,
+    pub reviewers: Vec<String>,
+    /// `pending` or `concern`.
+    pub state: String,
+    pub labels: Vec<String>,
+    pub age_hours: i64,
+}
+
+/// JSON payload for `tbdflow review status --json`.
+#[derive(Serialize)]
+pub struct ReviewStatusResponse {
+    pub commit: String,
+    /// `pending`, `approved`, `concern` or `dismissed`; `None` when no review
+    /// was recorded.
+    pub state: Option<String>,
+    pub approvals: usize,
+    pub required_approvals: usize,
+    pub approvers: Vec<String>,
+    pub reviewers: Vec<String>,
+    pub open_concerns: usize,
+    /// Every review event, oldest first.
+    pub events: Vec<ReviewNoteResponse>,
+}
+
+/// One event in a review note.
+#[derive(Serialize)]
+pub struct ReviewNoteResponse {
+    pub at: String,
+    pub author: String,
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+    /// The fix-forward commit, for `fix` events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+/// JSON payload for `tbdflow review show --json`.
+#[derive(Serialize)]
+pub struct ReviewShowResponse {
+    pub commit: String,
+    pub subject: String,
+    pub author: String,
+    /// The review issue's URL; `None` for reviews recorded in git or when no
+    /// review was found.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<String>,
+    /// The issue's state, or the review state for reviews recorded in git.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    pub labels: Vec<String>,
+    pub concerns: Vec<ReviewConcernResponse>,
+    pub comments: Vec<ReviewCommentResponse>,
+    /// `git show --stat` of the commit.
+    pub diffstat: String,
+}
+
+/// A concern raised in a review.
+#[derive(Serialize)]
+pub struct ReviewConcernResponse {
+    pub text: String,
+    /// Ticked off by a fix-forward.
+    pub resolved: bool,
+}
+
+/// A comment on a review issue, or an event of a review recorded in git.
+#[derive(Serialize)]
+pub struct ReviewCommentResponse {
+    pub author: String,
+    pub at: String,
+    pub body: String,
+}
+
+/// JSON payload for `tbdflow review stats --json`.
+#[derive(Serialize)]
+pub struct ReviewStatsResponse {
+    pub trunk: String,
+    pub since: String,
+    pub commits: usize,
+    /// Commits someone approved, raised a concern on or dismissed.
+    pub reviewed: usize,
+    pub unreviewed: usize,
+    /// Median time from commit to approval, over approved commits.
+    pub median_seconds_to_approval: Option<i64>,
+    /// Concerns raised on each author's commits, most first.
+    pub concerns_by_author: Vec<ReviewCountResponse>,
+    /// Reviews each reviewer approved or raised a concern on, most first.
+    pub top_reviewers: Vec<ReviewCountResponse>,
+}
+
+/// JSON payload for `tbdflow review --digest --json`.
+#[derive(Serialize)]
+pub struct ReviewDigestResponse {
+    /// The `--since` value, or null when resuming from a saved digest.
+    pub since: Option<String>,
+    /// The last digested commit a `--resume` started after.
+    pub resumed_after: Option<String>,
+    /// Commits in the whole digest, across all pages.
+    pub total: usize,
+    pub page: usize,
+    pub pages: usize,
+    pub next_page: Option<usize>,
+    pub sections: Vec<ReviewDigestSectionResponse>,
+    pub default_reviewers: Vec<String>,
+}
+
+/// One changelog section of the digest.
+#[derive(Serialize)]
+pub struct ReviewDigestSectionResponse {
+    pub header: String,
+    pub commits: Vec<ReviewDigestCommitResponse>,
+}
+
+/// A commit in the digest.
+#[derive(Serialize)]
+pub struct ReviewDigestCommitResponse {
+    pub hash: String,
+    pub author: String,
+    pub r#type: Option<String>,
+    pub scope: Option<String>,
+    pub description: String,
+    pub breaking: bool,
+    pub files: usize,
+}
+
+/// A person and how many of something they have in `review stats`.
+#[derive(Serialize)]
+pub struct ReviewCountResponse {
+    pub name: String,
+    pub count: usize,
+}
+
+/// JSON payload for `tbdflow --version --json`.
+#[derive(Serialize)]
+pub struct VersionResponse {
+    pub version: String,
+    /// The commit built from, or "unknown".
+    pub commit: String,
+    pub build_date: String,
+    pub target: String,
+    /// Optional cargo features compiled in.
+    pub features: Vec<String>,
+    /// Values `git.backend` accepts in this build.
+    pub git_backends: Vec<String>,
+    /// Values `review.strategy` accepts in this build.
+    pub review_strategies: Vec<String>,
+}
+
+/// JSON payload for `tbdflow audit --json`.
+#[derive(Serialize)]
+pub struct AuditResponse {
+    pub trunk: String,
+    pub since: String,
+    pub total: usize,
+    pub tbdflow: usize,
+    pub conventional: usize,
+    pub manual: usize,
+    pub adoption_percent: usize,
+    /// Commits that bypassed tbdflow, newest first.
+    pub flagged: Vec<AuditCommitResponse>,
+}
+
+/// JSON payload for `tbdflow snippet --json`.
+#[derive(Serialize)]
+pub struct SnippetResponse {
+    pub query: String,
+    /// Newest first.
+    pub matches: Vec<SnippetMatchResponse>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SnippetMatchResponse {
+    pub sha: String,
+    pub date: String,
+    pub author: String,
+    pub subject: String,
+    pub message: String,
+    /// How many trunk commits used the same header and body.
+    pub uses: usize,
+}
+
+/// JSON payload for `tbdflow metrics integration --json`.
+#[derive(Serialize)]
+pub struct IntegrationMetricsResponse {
+    pub trunk: String,
+    pub since: String,
+    /// Most frequent integrators first.
+    pub authors: Vec<AuthorIntegrationResponse>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuthorIntegrationResponse {
+    pub author: String,
+    pub integrations: usize,
+    /// `None` with fewer than two integrations.
+    pub average_gap_seconds: Option<i64>,
+    pub longest_gap_seconds: Option<i64>,
+    pub last_integration: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditCommitResponse {
+    pub sha: String,
+    pub date: String,
+    pub author: String,
+    pub subject: String,
+    pub provenance: String,
+}
+
+/// JSON payload entry for `tbdflow experiments --json`.
+#[derive(Serialize)]
+pub struct ExperimentResponse {
+    pub experiment: String,
+    pub commits: Vec<ExperimentCommitResponse>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ExperimentCommitResponse {
+    pub sha: String,
+    pub date: String,
+    pub subject: String,
+}
+
+/// JSON payload entry for `tbdflow branch list --json`.
+#[derive(Serialize)]
+pub struct BranchListResponse {
+    pub branch: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<BranchMetadata>,
+}
+
+/// JSON payload for `tbdflow parse --json`.
+#[derive(Serialize)]
+pub struct ParseResponse {
+    pub valid: bool,
+    pub violations: Vec<LintViolationResponse>,
+}
+
+#[derive(Serialize)]
+pub struct LintViolationResponse {
+    pub rule: String,
+    pub message: String,
+    /// `type`, `scope`, `subject`, `body`, `issue` or `message`.
+    pub field: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// The corrected text, when the failure can be fixed automatically.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+    /// 1-based position of `text` in the message.
+    pub line: usize,
+    pub column: usize,
+}
+
+/// JSON payload for `tbdflow next-version --json`.
+#[derive(Serialize)]
+pub struct NextVersionResponse {
+    /// The latest release tag, skipping pre-releases; `None` before the first release.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current: Option<String>,
+    pub next: String,
+    /// `major`, `minor` or `patch`.
+    pub bump: String,
+    /// Commits since `current`.
+    pub commits: usize,
+}
+
+/// JSON payload for `tbdflow clean --json`.
+#[derive(Serialize)]
+pub struct CleanResponse {
+    pub items: Vec<CleanItemResponse>,
+    /// False under `--dry-run`, or when removal was not confirmed.
+    pub removed: bool,
+}
+
+#[derive(Serialize)]
+pub struct CleanItemResponse {
+    /// `snapshot`, `intent-log`, `journal-entry`, `corrupt-state` or `lock-file`.
+    pub kind: String,
+    pub target: String,
+    pub reason: String,
+}
+
+/// JSON payload for `tbdflow doctor --json`.
+#[derive(Serialize)]
+pub struct DoctorResponse {
+    pub healthy: bool,
+    pub checks: Vec<DoctorCheckResponse>,
+}
+
+#[derive(Serialize)]
+pub struct DoctorCheckResponse {
+    pub name: String,
+    /// One of `ok`, `warn` or `fail`.
+    pub status: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// JSON payload for `tbdflow whoami --json`.
+#[derive(Serialize)]
+pub struct WhoamiResponse {
+    /// `Name <email>` git commits as, or null when it is not set.
+    pub author: Option<String>,
+    /// The config file (or variables) the author comes from.
+    pub author_source: Option<String>,
+    /// Only when it differs from the author.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub committer: Option<String>,
+    pub signing: SigningResponse,
+    /// Co-authors of the active pair session.
+    pub coauthors: Vec<String>,
+    pub pairing_since: Option<String>,
+    pub remote_url: Option<String>,
+    /// `GH_HOST`, or the host of the origin remote.
+    pub forge_host: Option<String>,
+    pub gh_installed: bool,
+    /// Null when `gh` is not installed or could not be asked.
+    pub gh_logged_in: Option<bool>,
+    pub gh_account: Option<String>,
+    /// Token variables set in the environment (names only).
+    pub token_vars: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct SigningResponse {
+    pub enabled: bool,
+    /// `gpg.format`: openpgp, ssh or x509.
+    pub format: String,
+    pub key: Option<String>,
+    /// Why signing will fail, when that can be told.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub problem: Option<String>,
 }
 
 pub fn handle_update_command() -> Result<(), anyhow::Error> {
@@ -285,13 +745,14 @@ pub fn handle_init_command(opts: RunOpts, init_opts: InitOptions) -> Result<()>
             println!("{}", "New git repository initialised.".green());
         } else {
             let current_dir = env::current_dir()?.to_string_lossy().to_string();
-            if Confirm::with_theme(&ColorfulTheme::default())
-                .with_prompt(format!(
+            if prompt::confirm(
+                &format!(
                     "Currently not in a git repository ({}). Would you like to initialise one?",
                     current_dir
-                ))
-                .interact()?
-            {
+                ),
+                None,
+                opts,
+            )? {
                 git::init_git_repository(opts)?;
                 println!("{}", "New git repository initialised.".green());
             } else {
@@ -306,13 +767,27 @@ pub fn handle_init_command(opts: RunOpts, init_opts: InitOptions) -> Result<()>
     let tbdflow_path = std::path::Path::new(&git_root).join(".tbdflow.yml");
     let mut files_created = false;
 
+    let target_config_path = if current_dir.as_path() != std::path::Path::new(&git_root) {
+        current_dir.join(".tbdflow.yml")
+    } else {
+        tbdflow_path.clone()
+    };
+    let (conventions, tbdflow_lints) = if target_config_path.exists() {
+        (None, true)
+    } else {
+        (
+            detect_existing_conventions(&init_opts, opts)?,
+            choose_message_linter(std::path::Path::new(&git_root), &init_opts, opts)?,
+        )
+    };
+
     if current_dir.as_path() != std::path::Path::new(&git_root) {
         // We are in a subdirectory, create a project-specific config.
         let project_config_path = current_dir.join(".tbdflow.yml");
         if !project_config_path.exists() {
             let project_config = config::Config {
                 project_root: Some(".".to_string()),
-                ..build_init_config(&init_opts)
+                ..build_init_config(&init_opts, conventions.as_ref(), tbdflow_lints)
             };
             let yaml_string = yaml_serde::to_string(&project_config)?;
             fs::write(&project_config_path, yaml_string)?;
@@ -328,19 +803,51 @@ pub fn handle_init_command(opts: RunOpts, init_opts: InitOptions) -> Result<()>
         }
     } else {
         if !tbdflow_path.exists() {
-            let init_config = build_init_config(&init_opts);
+            let init_config = build_init_config(&init_opts, conventions.as_ref(), tbdflow_lints);
             let yaml_string = yaml_serde::to_string(&init_config)?;
             fs::write(&tbdflow_path, yaml_string)?;
-            println!(
-                "{}",
-                "Created default .tbdflow.yml configuration file.".green()
-            );
+            if conventions.is_some() {
+                println!(
+                    "{}",
+                    "Created .tbdflow.yml matching the repository's conventions.".green()
+                );
+            } else {
+                println!(
+                    "{}",
+                    "Created default .tbdflow.yml configuration file.".green()
+                );
+            }
             files_created = true;
         } else {
             println!("{}", ".tbdflow.yml already exists. Skipping.".yellow());
         }
     }
 
+    // Offer to keep per-developer overrides out of git, at the root only.
+    let root = std::path::Path::new(&git_root);
+    if current_dir.as_path() == root
+        && !intent::gitignore_lists(root, config::LOCAL_CONFIG_FILE)?
+        && (init_opts.non_interactive
+            || prompt::confirm(
+                &format!(
+                    "Add {} to .gitignore, for personal overrides that are never committed?",
+                    config::LOCAL_CONFIG_FILE
+                ),
+                Some(true),
+                opts,
+            )?)
+    {
+        intent::add_to_gitignore(
+            root,
+            config::LOCAL_CONFIG_FILE,
+            "tbdflow per-developer overrides (local-only, never committed)",
+        )?;
+        println!(
+            "{}",
+            format!("Added {} to .gitignore.", config::LOCAL_CONFIG_FILE).green()
+        );
+    }
+
     let dod_path = std::path::Path::new(&git_root).join(".dod.yml");
     if !dod_path.exists() {
         let default_dod = r#"
@@ -374,13 +881,12 @@ checklist:
         } else if init_opts.non_interactive {
             None // No remote linking in non-interactive mode unless explicitly provided.
         } else {
-            if Confirm::with_theme(&ColorfulTheme::default())
-                .with_prompt(
-                    "\nDo you want to link a remote repository and push the initial commit now?",
-                )
-                .interact()?
-            {
-                let url: String = Input::with_theme(&ColorfulTheme::default())
+            if prompt::confirm(
+                "\nDo you want to link a remote repository and push the initial commit now?",
+                Some(false),
+                opts,
+            )? {
+                let url: String = Input::with_theme(&*output::theme())
                     .with_prompt("Please enter the remote repository URL (e.g. from GitHub)")
                     .interact_text()?;
                 if url.is_empty() { None } else { Some(url) }
@@ -390,7 +896,11 @@ checklist:
         };
 
         if let Some(url) = remote_url {
-            let main_branch = init_opts.main_branch.as_deref().unwrap_or("main");
+            let main_branch = init_opts
+                .main_branch
+                .as_deref()
+                .or(conventions.as_ref().and_then(|c| c.main_branch.as_deref()))
+                .unwrap_or("main");
 
             git::add_remote("origin", &url, opts)?;
             git::fetch_origin(opts)?;
@@ -413,10 +923,112 @@ checklist:
     Ok(())
 }
 
-/// Build a Config based on init options, falling back to defaults.
-fn build_init_config(init_opts: &InitOptions) -> config::Config {
+/// Scans an existing history and asks whether to adopt what it shows.
+/// `None` for a fresh repository or when the user prefers the defaults.
+fn detect_existing_conventions(
+    init_opts: &InitOptions,
+    opts: RunOpts,
+) -> Result<Option<conventions::Conventions>> {
+    let Some(detected) = conventions::detect(opts)? else {
+        return Ok(None);
+    };
+
+    println!(
+        "{}",
+        format!(
+            "Detected conventions from the last {} commits:",
+            detected.scanned
+        )
+        .blue()
+    );
+    for line in detected.describe() {
+        println!("  {}", line);
+    }
+    if !detected.is_conventional() {
+        println!(
+            "  {}",
+            "Most commits are not Conventional Commits, so the default types are kept.".yellow()
+        );
+    }
+
+    if init_opts.non_interactive
+        || prompt::confirm("Use these conventions in .tbdflow.yml?", Some(true), opts)?
+    {
+        Ok(Some(detected))
+    } else {
+        Ok(None)
+    }
+}
+
+/// When commitlint already checks commit messages, asks which tool should own
+/// the rules. Returns false when tbdflow's own linting should be turned off.
+fn choose_message_linter(
+    git_root: &std::path::Path,
+    init_opts: &InitOptions,
+    opts: RunOpts,
+) -> Result<bool> {
+    let toolchain = toolchain::CommitToolchain::detect(git_root);
+    if !toolchain.lints_messages() {
+        return Ok(true);
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Found {}. tbdflow also lints commit messages, so every message would be checked twice.",
+            toolchain.describe()
+        )
+        .yellow()
+    );
+    if init_opts.non_interactive || opts.assume_yes || !prompt::is_interactive() {
+        println!("Keeping both linters. Run 'tbdflow doctor' later to see how to pick one.");
+        return Ok(true);
+    }
+
+    let options = &[
+        "Defer to tbdflow: make .husky/commit-msg run 'tbdflow verify-commit-msg'",
+        "Respect the existing toolchain: turn off tbdflow's own linting",
+        "Keep both",
+    ];
+    let selection = Select::with_theme(&*output::theme())
+        .with_prompt("Which tool should own commit message rules?")
+        .items(options)
+        .default(0)
+        .interact()?;
+    match selection {
+        0 => {
+            if opts.dry_run {
+                println!("[DRY RUN] Would point .husky/commit-msg at 'tbdflow verify-commit-msg'.");
+            } else {
+                let hook = toolchain::defer_commit_msg_to_tbdflow(git_root)?;
+                let git_dir = std::path::PathBuf::from(git::get_git_dir(opts)?);
+                hooks::record(&git_dir, hook)?;
+                println!(
+                    "{}",
+                    "Updated .husky/commit-msg to run 'tbdflow verify-commit-msg'.".green()
+                );
+            }
+            Ok(true)
+        }
+        1 => Ok(false),
+        _ => Ok(true),
+    }
+}
+
+/// Build a Config based on init options and detected conventions, falling back to defaults.
+fn build_init_config(
+    init_opts: &InitOptions,
+    conventions: Option<&conventions::Conventions>,
+    tbdflow_lints: bool,
+) -> config::Config {
     let mut cfg = config::Config::default();
 
+    if let Some(conventions) = conventions {
+        conventions.apply(&mut cfg);
+    }
+    if !tbdflow_lints {
+        cfg.lint = None;
+    }
     if let Some(ref branch) = init_opts.main_branch {
         cfg.main_branch_name = branch.clone();
     }
@@ -424,8 +1036,8 @@ fn build_init_config(init_opts: &InitOptions) -> config::Config {
     cfg
 }
 
-pub fn handle_info(opts: RunOpts, edit: bool, json: bool) -> Result<()> {
-    let git_root = git::get_git_root(RunOpts::new(false, false))?;
+pub fn handle_info(opts: RunOpts, profile: Option<&str>, edit: bool, json: bool) -> Result<()> {
+    let git_root = git::get_git_root(opts.for_reads())?;
     let root_config_path = PathBuf::from(&git_root).join(".tbdflow.yml");
 
     if edit {
@@ -437,30 +1049,49 @@ pub fn handle_info(opts: RunOpts, edit: bool, json: bool) -> Result<()> {
         return Ok(());
     }
 
-    let root_config: config::Config = if root_config_path.exists() {
-        let yaml_str = fs::read_to_string(&root_config_path)?;
-        yaml_serde::from_str(&yaml_str)?
-    } else {
-        config::Config::default()
-    };
+    let (root_config, _) =
+        config::load_root_config(std::path::Path::new(&git_root), profile, opts)?;
 
-    let final_config = config::load_tbdflow_config()?;
+    let final_config = config::load_tbdflow_config(profile, opts)?;
 
     if json {
-        return print_info_json(opts, &final_config, &git_root);
+        return print_info_json(opts, &final_config, &git_root, profile);
     }
 
     println!("{}", "--- tbdflow Configuration ---".blue());
-    print_mode_and_settings(&root_config, &root_config_path, &final_config)?;
+    if let Some(profile) = profile {
+        println!("Profile: {}", profile.bold());
+    }
+    if let Some(path) = config::user_config_path().filter(|p| p.is_file()) {
+        println!(
+            "User config: {} {}",
+            path.to_string_lossy(),
+            "(beneath the repository config)".dimmed()
+        );
+    }
+    let local_path = PathBuf::from(&git_root).join(config::LOCAL_CONFIG_FILE);
+    if local_path.is_file() {
+        println!(
+            "Local overrides: {} {}",
+            local_path.to_string_lossy(),
+            "(over the repository config)".dimmed()
+        );
+    }
+    print_mode_and_settings(&root_config, &root_config_path, &final_config, opts)?;
     print_review_config(&final_config.review);
     print_radar_config(&final_config.radar);
     print_ci_config(&final_config.ci_check);
-    print_git_info(opts)?;
+    print_git_info(&final_config, opts)?;
 
     Ok(())
 }
 
-fn print_info_json(opts: RunOpts, config: &config::Config, git_root: &str) -> Result<()> {
+fn print_info_json(
+    opts: RunOpts,
+    config: &config::Config,
+    git_root: &str,
+    profile: Option<&str>,
+) -> Result<()> {
     let mode = if config.monorepo.enabled && !config.monorepo.project_dirs.is_empty() {
         "monorepo".to_string()
     } else if config.project_root.is_some() {
@@ -475,12 +1106,13 @@ fn print_info_json(opts: RunOpts, config: &config::Config, git_root: &str) -> Re
     let dod_path = std::path::Path::new(git_root).join(".dod.yml");
     let dod_configured = dod_path.exists();
 
-    let git_info = build_git_info(opts).ok();
+    let git_info = build_git_info(config, opts).ok();
 
     let issue_strategy = format!("{:?}", config.issue_handling.strategy);
 
     let response = InfoResponse {
         mode,
+        profile: profile.map(str::to_string),
         main_branch_name: config.main_branch_name.clone(),
         stale_branch_threshold_days: config.stale_branch_threshold_days,
         log_display_count: config.log_display_count,
@@ -533,10 +1165,10 @@ fn print_info_json(opts: RunOpts, config: &config::Config, git_root: &str) -> Re
     Ok(())
 }
 
-fn build_git_info(opts: RunOpts) -> Result<GitInfoResponse> {
+fn build_git_info(config: &config::Config, opts: RunOpts) -> Result<GitInfoResponse> {
     let current_branch = git::get_current_branch(opts)?;
     let remote_url = git::get_remote_url(opts).ok();
-    let latest_tag = git::get_latest_tag(opts).ok();
+    let latest_tag = version::latest_tag(config, true, opts).ok();
 
     Ok(GitInfoResponse {
         remote_url,
@@ -547,10 +1179,11 @@ fn build_git_info(opts: RunOpts) -> Result<GitInfoResponse> {
 
 fn print_mode_and_settings(
     root_config: &config::Config,
-    root_config_path: &PathBuf,
+    root_config_path: &std::path::Path,
     final_config: &config::Config,
+    opts: RunOpts,
 ) -> Result<()> {
-    if let Some(project_root) = config::find_project_root()? {
+    if let Some(project_root) = config::find_project_root(opts)? {
         let project_config_path = project_root.join(".tbdflow.yml");
         if project_config_path.exists() {
             println!("Mode: {} (Project)", "Monorepo".to_string().bold());
@@ -699,7 +1332,7 @@ fn print_ci_config(ci_check: &config::CiCheckConfig) {
     }
 }
 
-fn print_git_info(opts: RunOpts) -> Result<()> {
+fn print_git_info(config: &config::Config, opts: RunOpts) -> Result<()> {
     println!("\n{}", "--- Git Info ---".bold());
     if let Ok(remote_url) = git::get_remote_url(opts) {
         println!("Remote 'origin' URL: {}", remote_url.to_string().cyan());
@@ -710,10 +1343,13 @@ fn print_git_info(opts: RunOpts) -> Result<()> {
     let current_branch = git::get_current_branch(opts)?;
     println!("Current branch: {}", current_branch.to_string().cyan());
 
-    if let Ok(latest_tag) = git::get_latest_tag(opts) {
+    if let Ok(latest_tag) = version::latest_tag(config, true, opts) {
         println!("Latest tag: {}", latest_tag.to_string().cyan());
     } else {
-        println!("Latest tag: Not found.");
+        match shallow::missing_tag_hint(opts) {
+            Some(hint) => println!("Latest tag: Not found. {}", hint.yellow()),
+            None => println!("Latest tag: Not found."),
+        }
     }
 
     Ok(())
@@ -733,6 +1369,11 @@ pub fn handle_status(opts: RunOpts, config: &config::Config, json: bool) -> Resu
     } else {
         "disabled".to_string()
     };
+    let git_dir = std::path::PathBuf::from(git::get_git_dir(opts)?);
+    let pair = pair::load_pair_session(&git_dir)?
+        .map(|session| session.coauthors)
+        .unwrap_or_default();
+    let reviews = review::review_obligations(config, &git_dir, opts);
 
     if json {
         let changed_files: Vec<String> = if status_output.is_empty() {
@@ -745,8 +1386,12 @@ pub fn handle_status(opts: RunOpts, config: &config::Config, json: bool) -> Resu
                 .collect()
         };
 
-        let current_project = config::find_project_root()?
-            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()));
+        let current_project = match git::work_scope(config, opts)? {
+            git::WorkScope::Project(dir) => std::path::Path::new(&dir)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string()),
+            _ => None,
+        };
 
         let response = StatusResponse {
             is_main: current_branch == config.main_branch_name,
@@ -760,14 +1405,14 @@ pub fn handle_status(opts: RunOpts, config: &config::Config, json: bool) -> Resu
                 enabled: config.monorepo.enabled,
                 current_project,
             },
+            pair,
+            reviews,
         };
         let json_output = serde_json::to_string_pretty(&TbdResponse::ok(response))?;
         println!("{}", json_output);
     } else {
         println!("--- Checking status ---");
-        let git_root = std::path::PathBuf::from(git::get_git_root(opts)?);
-        let current_dir = env::current_dir()?;
-        if config::is_monorepo_root(config, &current_dir, &git_root) {
+        if git::work_scope(config, opts)? == git::WorkScope::RootOnly {
             println!(
                 "{}",
                 "Monorepo root detected. Showing status for root-level files only.".yellow()
@@ -786,6 +1431,12 @@ pub fn handle_status(opts: RunOpts, config: &config::Config, json: bool) -> Resu
             )
             .dimmed()
         );
+        if !pair.is_empty() {
+            println!("{}", format!("Pairing with: {}", pair.join(", ")).cyan());
+        }
+        if let Some(reviews) = reviews {
+            println!("{}", reviews.summary().cyan());
+        }
     }
     Ok(())
 }
@@ -876,21 +1527,20 @@ pub fn handle_sync(opts: RunOpts, config: &config::Config, json: bool) -> Result
                         .bold()
                         .yellow()
                 );
-                let should_continue = Confirm::with_theme(&ColorfulTheme::default())
-                    .with_prompt("Continue with sync?")
-                    .default(false)
-                    .interact()?;
+                let should_continue = prompt::confirm("Continue with sync?", Some(false), opts)?;
                 if !should_continue {
                     println!("{}", "Sync aborted.".yellow());
                     return Ok(());
                 }
             }
             "pending" => {
-                println!("\n{}", "⏳ Trunk CI is still running.".bold().yellow());
-                let should_continue = Confirm::with_theme(&ColorfulTheme::default())
-                    .with_prompt("Pull anyway?")
-                    .default(false)
-                    .interact()?;
+                println!(
+                    "\n{}",
+                    output::text("⏳ Trunk CI is still running.")
+                        .bold()
+                        .yellow()
+                );
+                let should_continue = prompt::confirm("Pull anyway?", Some(false), opts)?;
                 if !should_continue {
                     println!("{}", "Sync aborted.".yellow());
                     return Ok(());
@@ -904,6 +1554,40 @@ pub fn handle_sync(opts: RunOpts, config: &config::Config, json: bool) -> Result
         }
     }
 
+    git::fetch_origin(opts)?;
+    let conflicts = predict_sync_conflicts(&config.main_branch_name, opts)?;
+    if !conflicts.is_empty() {
+        let msg = format!(
+            "{} file(s) will conflict: {}",
+            conflicts.len(),
+            conflicts.join(", ")
+        );
+        if json {
+            let json_output = serde_json::to_string_pretty(
+                &TbdResponse::<SyncResponse>::err_with_code(&msg, ErrorCode::ConflictsPredicted),
+            )?;
+            println!("{}", json_output);
+            return Ok(());
+        }
+        println!("\n{}", msg.bold().yellow());
+        let range = format!("HEAD..origin/{}", config.main_branch_name);
+        let authors = git::get_authors_touching(&range, &conflicts, opts).unwrap_or_default();
+        if !authors.is_empty() {
+            println!(
+                "{}",
+                format!("Incoming changes to them by: {}", authors.join(", ")).yellow()
+            );
+        }
+        println!(
+            "{}",
+            "Finish your commit first, or coordinate with the author before syncing.".dimmed()
+        );
+        if !prompt::confirm("Continue with sync?", Some(false), opts)? {
+            println!("{}", "Sync aborted.".yellow());
+            return Ok(());
+        }
+    }
+
     if current_branch == config.main_branch_name {
         if !json {
             println!("On main branch, pulling latest changes...");
@@ -916,7 +1600,6 @@ pub fn handle_sync(opts: RunOpts, config: &config::Config, json: bool) -> Result
                 current_branch, config.main_branch_name
             );
         }
-        git::fetch_origin(opts)?;
         git::rebase_onto_main(&config.main_branch_name, opts)?;
     }
 
@@ -960,14 +1643,7 @@ pub fn handle_sync(opts: RunOpts, config: &config::Config, json: bool) -> Result
             None
         };
 
-        let stale_branches =
-            git::get_stale_branches(opts, &current_branch, config.stale_branch_threshold_days)?
-                .into_iter()
-                .map(|(branch, days)| StaleBranchResponse {
-                    branch,
-                    days_inactive: days,
-                })
-                .collect();
+        let stale_branches = stale_branch_responses(opts, &current_branch, config)?;
 
         let response = SyncResponse {
             is_main: current_branch == config.main_branch_name,
@@ -1006,7 +1682,75 @@ pub fn handle_sync(opts: RunOpts, config: &config::Config, json: bool) -> Result
     Ok(())
 }
 
-pub fn handle_check_branches(opts: RunOpts, config: &config::Config) -> Result<()> {
+/// Files that would conflict when HEAD is replayed onto `origin/<main>`.
+/// Empty when nothing conflicts or git cannot tell.
+fn predict_sync_conflicts(main_branch_name: &str, opts: RunOpts) -> Result<Vec<String>> {
+    if opts.dry_run {
+        return Ok(Vec::new());
+    }
+    let upstream = format!("origin/{}", main_branch_name);
+    Ok(git::predict_merge_conflicts("HEAD", &upstream, opts)?.unwrap_or_default())
+}
+
+/// Prints the checked-out branch.
+pub fn handle_current_branch(opts: RunOpts, config: &config::Config, json: bool) -> Result<()> {
+    let branch = git::get_current_branch(opts)?;
+    if json {
+        let response = CurrentBranchResponse {
+            is_main: branch == config.main_branch_name,
+            branch,
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(response))?
+        );
+        return Ok(());
+    }
+    println!("{}", "--- Current branch ---".to_string().blue());
+    println!("{}", format!("Current branch is: {}", branch).green());
+    Ok(())
+}
+
+/// Branches other than `current_branch` with no commits for longer than
+/// `stale_branch_threshold_days`, with their metadata.
+fn stale_branch_responses(
+    opts: RunOpts,
+    current_branch: &str,
+    config: &config::Config,
+) -> Result<Vec<StaleBranchResponse>> {
+    shallow::warn_if_shallow("stale-branch detection", opts);
+    let mut metadata = branch::load_all_branch_metadata(opts);
+    Ok(
+        git::get_stale_branches(opts, current_branch, config.stale_branch_threshold_days)?
+            .into_iter()
+            .map(|(branch, days)| StaleBranchResponse {
+                metadata: metadata.remove(&branch),
+                branch,
+                days_inactive: days,
+            })
+            .collect(),
+    )
+}
+
+pub fn handle_check_branches(opts: RunOpts, config: &config::Config, json: bool) -> Result<()> {
+    if json {
+        let current_branch = git::get_current_branch(opts)?;
+        let response = if current_branch != config.main_branch_name {
+            TbdResponse::err_with_code(
+                git::GitError::NotOnMainBranch(current_branch).to_string(),
+                ErrorCode::NotOnMain,
+            )
+        } else {
+            TbdResponse::ok(CheckBranchesResponse {
+                stale_branches: stale_branch_responses(opts, &current_branch, config)?,
+                stale_branch_threshold_days: config.stale_branch_threshold_days,
+                current_branch,
+            })
+        };
+        println!("{}", serde_json::to_string_pretty(&response)?);
+        return Ok(());
+    }
+
     println!(
         "{}",
         "--- Checking current branch and stale branches ---"
@@ -1027,9 +1771,11 @@ pub fn check_and_warn_for_stale_branches(
     current_branch: &str,
     config: &config::Config,
 ) -> Result<()> {
+    shallow::warn_if_shallow("stale-branch detection", opts);
     let stale_branches =
         git::get_stale_branches(opts, current_branch, config.stale_branch_threshold_days)?;
     if !stale_branches.is_empty() {
+        let metadata = branch::load_all_branch_metadata(opts);
         println!(
             "\n{}",
             "Warning: The following branches may be stale:"
@@ -1041,6 +1787,9 @@ pub fn check_and_warn_for_stale_branches(
                 "{}",
                 format!("  - {} (last commit {} days ago)", branch, days).yellow()
             );
+            if let Some(meta) = metadata.get(&branch) {
+                println!("{}", format!("      {}", meta.summary()).dimmed());
+            }
         }
     }
     Ok(())
@@ -1051,19 +1800,40 @@ pub fn get_branch_prefix_or_error<'a>(
     r#type: &str,
 ) -> Result<&'a String> {
     branch_types.get(r#type).ok_or_else(|| {
-        let allowed_types = branch_types
-            .keys()
-            .map(|s| s.as_str())
-            .collect::<Vec<&str>>()
-            .join(", ");
-        anyhow::anyhow!(
+        let mut allowed_types: Vec<&str> = branch_types.keys().map(|s| s.as_str()).collect();
+        allowed_types.sort();
+        let mut message = format!(
             "Invalid branch type '{}'. Allowed types are: {}",
             r#type,
-            allowed_types
-        )
+            allowed_types.join(", ")
+        );
+        if let Some(closest) = closest_branch_type(r#type, &allowed_types) {
+            message.push_str(&format!("\nDid you mean '{}'?", closest));
+        }
+        anyhow::anyhow!(message)
     })
 }
 
+/// The configured branch type a mistyped `--type` most likely meant: the
+/// nearest by edit distance (at most two edits), or one that starts with it or
+/// that it starts with, e.g. `feature` for `feat`.
+pub fn closest_branch_type<'a>(r#type: &str, allowed_types: &[&'a str]) -> Option<&'a str> {
+    let wanted = r#type.to_lowercase();
+    if wanted.is_empty() {
+        return None;
+    }
+    allowed_types
+        .iter()
+        .map(|candidate| {
+            let distance = branch::edit_distance(&wanted, candidate);
+            let related = candidate.starts_with(wanted.as_str()) || wanted.starts_with(*candidate);
+            (if related { distance.min(1) } else { distance }, *candidate)
+        })
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, candidate)| (*distance, *candidate))
+        .map(|(_, candidate)| candidate)
+}
+
 pub fn handle_undo(sha: &str, no_push: bool, opts: RunOpts, config: &config::Config) -> Result<()> {
     println!(
         "{}",
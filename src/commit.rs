@@ -1,10 +1,17 @@
-use crate::config::{Config, DodConfig};
+use crate::commands::{LintViolationResponse, ParseResponse, TbdResponse};
+use crate::config::{Config, DodConfig, DodItem};
 use crate::git::RunOpts;
-use crate::{config, git, intent, radar, review};
+use crate::i18n::{self, Msg};
+use crate::retry::{CommitStep, PendingCommit};
+use crate::{
+    config, dod, git, intent, journal, output, pair, prompt, radar, retry, review, target,
+};
 use anyhow::Result;
+use chrono::Utc;
 use colored::Colorize;
-use dialoguer::{Confirm, MultiSelect, theme::ColorfulTheme};
-use std::path::PathBuf;
+use dialoguer::{Confirm, Input};
+use std::fs;
+use std::path::{Path, PathBuf};
 
 pub struct CommitParams {
     pub r#type: String,
@@ -17,14 +24,392 @@ pub struct CommitParams {
     pub issue: Option<String>,
     pub include_projects: bool,
     pub no_verify: bool,
+    /// Extra trailers (e.g. `Co-authored-by: ...`) appended verbatim after `Refs`.
+    pub trailers: Vec<String>,
+    /// Show what will be committed and ask for confirmation first.
+    pub preview: bool,
+    /// Apply the suggested fix for auto-correctable lint failures instead of aborting.
+    pub fix: bool,
 }
 
-pub fn run_checklist_interactive(checklist: &[String]) -> Result<Vec<usize>> {
-    let selections = MultiSelect::with_theme(&ColorfulTheme::default())
-        .with_prompt("Please confirm each item before committing:")
-        .items(checklist)
-        .interact()?;
-    Ok(selections)
+/// Trailer key recording the tbdflow version that made a commit.
+pub const VERSION_TRAILER_KEY: &str = "Tbdflow-Version";
+
+/// Appends `Tbdflow-Version: <version>` when `commit.version_trailer` is on
+/// and the trailers do not already carry one.
+pub fn push_version_trailer(config: &Config, trailers: &mut Vec<String>) {
+    let present = trailers.iter().any(|t| {
+        t.split_once(':')
+            .is_some_and(|(key, _)| key.trim().eq_ignore_ascii_case(VERSION_TRAILER_KEY))
+    });
+    if config.commit.version_trailer && !present {
+        trailers.push(format!(
+            "{}: {}",
+            VERSION_TRAILER_KEY,
+            env!("CARGO_PKG_VERSION")
+        ));
+    }
+}
+
+/// Parses a complete commit message (header, body and trailers) into commit
+/// parameters, so messages from files or stdin go through the normal lint pipeline.
+///
+/// Lines starting with `#` are dropped, matching git's default message cleanup.
+/// A `Refs:` trailer becomes the issue reference and a `BREAKING CHANGE:`
+/// trailer becomes the breaking-change description.
+pub fn parse_commit_message(text: &str) -> Result<CommitParams> {
+    let cleaned: String = text
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let cleaned = cleaned.trim();
+
+    let commit = git_conventional::Commit::parse(cleaned).map_err(|e| {
+        anyhow::anyhow!(
+            "Commit message is not a valid Conventional Commit: {}",
+            e.kind()
+        )
+    })?;
+
+    let mut issue = None;
+    let mut breaking_description = None;
+    let mut trailers = Vec::new();
+    for footer in commit.footers() {
+        if footer.breaking() {
+            breaking_description = Some(footer.value().to_string());
+        } else if issue.is_none() && footer.token().as_str().eq_ignore_ascii_case("Refs") {
+            issue = Some(footer.value().to_string());
+        } else {
+            let token = footer.token().as_str();
+            trailers.push(match footer.separator() {
+                git_conventional::FooterSeparator::Ref => format!("{} #{}", token, footer.value()),
+                _ => format!("{}: {}", token, footer.value()),
+            });
+        }
+    }
+
+    Ok(CommitParams {
+        r#type: commit.type_().as_str().to_string(),
+        scope: commit.scope().map(|s| s.as_str().to_string()),
+        message: commit.description().to_string(),
+        body: commit.body().map(|b| b.trim().to_string()),
+        breaking: commit.breaking(),
+        breaking_description,
+        tag: None,
+        issue,
+        include_projects: false,
+        no_verify: false,
+        trailers,
+        preview: false,
+        fix: false,
+    })
+}
+
+/// The `type(scope)!: message` line of a commit.
+fn commit_header(params: &CommitParams) -> String {
+    let scope_part = params
+        .scope
+        .as_deref()
+        .map_or("".to_string(), |s| format!("({})", s));
+    let breaking_part = if params.breaking { "!" } else { "" };
+    format!(
+        "{}{}{}: {}",
+        params.r#type, scope_part, breaking_part, params.message
+    )
+}
+
+/// The full message for `params`: header, body, then `section` (e.g. the
+/// intent log), the `BREAKING CHANGE:` and `Refs:` footers, and `trailers`.
+pub fn build_commit_message(
+    params: &CommitParams,
+    section: Option<&str>,
+    trailers: &[String],
+) -> String {
+    let mut message = commit_header(params);
+    for part in [params.body.as_deref(), section].into_iter().flatten() {
+        message.push_str("\n\n");
+        message.push_str(part);
+    }
+    if let Some(desc) = &params.breaking_description {
+        message.push_str(&format!("\n\nBREAKING CHANGE: {}", desc));
+    }
+    if let Some(issue) = &params.issue {
+        message.push_str(&format!("\n\nRefs: {}", issue));
+    }
+    if !trailers.is_empty() {
+        message.push_str("\n\n");
+        message.push_str(&trailers.join("\n"));
+    }
+    message
+}
+
+/// The body scaffold `commit.templates` sets for `commit_type`, if any.
+pub fn body_template<'a>(config: &'a Config, commit_type: &str) -> Option<&'a str> {
+    config
+        .commit
+        .templates
+        .get(commit_type)
+        .map(|t| t.trim_end())
+        .filter(|t| !t.is_empty())
+}
+
+/// A body from `template` with each line followed by its answer, in order.
+/// Lines left unanswered are dropped; `None` when nothing was answered.
+pub fn fill_body_template(template: &str, answers: &[String]) -> Option<String> {
+    let lines: Vec<String> = template
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .zip(answers)
+        .filter(|(_, answer)| !answer.trim().is_empty())
+        .map(|(line, answer)| format!("{} {}", line.trim_end(), answer.trim()))
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// What `commit --edit` opens: the message the flags give so far, with the
+/// type's template as the body when no body was given.
+fn edit_message_text(params: &CommitParams, template: Option<&str>) -> String {
+    // Without a body, the template lands where the body would be.
+    let template = template.filter(|_| params.body.is_none());
+    let mut text = build_commit_message(params, template, &[]);
+    text.push_str(
+        "\n\n# Write the commit message above. Lines starting with '#' are ignored.\n\
+         # An empty message aborts the commit.\n",
+    );
+    text
+}
+
+/// `text` without the template lines still exactly as the editor got them,
+/// so labels left unanswered don't end up in the body.
+fn strip_unanswered_template(text: &str, template: &str) -> String {
+    let labels: Vec<&str> = template
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let mut kept: Vec<&str> = Vec::new();
+    for line in text.lines() {
+        let blank = line.trim().is_empty();
+        if labels.contains(&line.trim())
+            || (blank && kept.last().is_some_and(|l| l.trim().is_empty()))
+        {
+            continue;
+        }
+        kept.push(line);
+    }
+    kept.join("\n").trim().to_string()
+}
+
+/// Opens the message in `$EDITOR` and reads back what was saved, for
+/// `commit --edit`. Options that are not part of the message carry over.
+pub fn edit_commit_params(
+    config: &Config,
+    params: CommitParams,
+    opts: RunOpts,
+) -> Result<CommitParams> {
+    let path = PathBuf::from(git::get_git_dir(opts.for_reads())?).join("TBDFLOW_EDITMSG");
+    let template = body_template(config, &params.r#type);
+    fs::write(&path, edit_message_text(&params, template))?;
+
+    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
+    let status = std::process::Command::new(&editor)
+        .arg(&path)
+        .status()
+        .map_err(|e| anyhow::anyhow!("Failed to open editor: {}", e))?;
+    let mut text = strip_message_comments(&fs::read_to_string(&path)?);
+    let _ = fs::remove_file(&path);
+    if let (None, Some(template)) = (&params.body, template) {
+        text = strip_unanswered_template(&text, template);
+    }
+    if !status.success() {
+        anyhow::bail!("The editor exited with {}; nothing was committed.", status);
+    }
+    if text.is_empty() {
+        anyhow::bail!("Aborting commit due to an empty commit message.");
+    }
+    Ok(CommitParams {
+        tag: params.tag,
+        include_projects: params.include_projects,
+        no_verify: params.no_verify,
+        preview: params.preview,
+        fix: params.fix,
+        ..parse_commit_message(&text)?
+    })
+}
+
+/// Pushes trunk, turning a non-fast-forward rejection into guidance.
+fn push_to_trunk(main_branch: &str, opts: RunOpts) -> Result<()> {
+    match git::push(opts) {
+        Ok(_) => Ok(()),
+        Err(e) if git::is_non_fast_forward(&e.to_string()) => {
+            println!(
+                "{}",
+                format!(
+                    "'origin/{}' moved while you were committing. Your commit is saved locally but was not pushed.",
+                    main_branch
+                )
+                .red()
+            );
+            print_divergence_guidance(main_branch);
+            Err(anyhow::anyhow!(
+                "Aborted: push to '{}' was rejected (non-fast-forward).",
+                main_branch
+            ))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn parent_dir(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(dir, _)| dir)
+}
+
+/// Changed files left in the working tree that look like they belong in the
+/// commit just made: in a directory it touched, or named after its scope.
+fn forgotten_files(committed: &[String], changed: &[String], scope: Option<&str>) -> Vec<String> {
+    let dirs: std::collections::HashSet<&str> = committed.iter().map(|f| parent_dir(f)).collect();
+    let named_after_scope = |file: &str| {
+        scope.is_some_and(|scope| {
+            file.split('/').any(|segment| {
+                segment == scope
+                    || segment
+                        .split_once('.')
+                        .is_some_and(|(stem, _)| stem == scope)
+            })
+        })
+    };
+    let mut forgotten: Vec<String> = changed
+        .iter()
+        .filter(|f| dirs.contains(parent_dir(f)) || named_after_scope(f))
+        .cloned()
+        .collect();
+    forgotten.sort();
+    forgotten
+}
+
+/// After a push to trunk, points out changed files that look like they
+/// belong in the commit, and offers to amend and push again before anyone
+/// pulls it. Only offered at a terminal: rewriting trunk needs a person.
+fn check_forgotten_files(scope: Option<&str>, opts: RunOpts) -> Result<()> {
+    if opts.dry_run {
+        return Ok(());
+    }
+    let quiet = opts.for_reads();
+    let committed = git::get_changed_files("HEAD", quiet)?;
+    let forgotten = forgotten_files(&committed, &git::get_local_changed_files(quiet)?, scope);
+    if forgotten.is_empty() {
+        return Ok(());
+    }
+    println!(
+        "\n{}",
+        format!("Did you forget to include {}?", forgotten.join(", ")).yellow()
+    );
+    if opts.assume_yes || !prompt::is_interactive() {
+        println!(
+            "{}",
+            "   To add them: git add <files> && git commit --amend --no-edit && git push --force-with-lease"
+                .dimmed()
+        );
+        return Ok(());
+    }
+    if !prompt::confirm(
+        "Amend the commit with them and push again? Only if nobody has pulled it yet.",
+        Some(false),
+        opts,
+    )? {
+        return Ok(());
+    }
+    git::add_root_paths(&forgotten, opts)?;
+    git::amend_no_edit(opts)?;
+    git::push_force_with_lease(opts)?;
+    println!(
+        "{}",
+        format!(
+            "Amended the commit with {} file(s) and pushed it again.",
+            forgotten.len()
+        )
+        .green()
+    );
+    Ok(())
+}
+
+fn print_divergence_guidance(main_branch: &str) {
+    println!("{}", "To recover:".bold());
+    println!("  tbdflow sync    # replay your work onto the latest trunk, then 'git push'");
+    println!(
+        "  git log --oneline --graph {} origin/{}    # inspect first if trunk was force-moved",
+        main_branch, main_branch
+    );
+    println!("  tbdflow recover --list    # restore a safety snapshot if work went missing");
+}
+
+/// Honours a `Review: skip` trailer when the commit type is exempt, recording
+/// the exemption in the journal. Returns true if the review should be skipped.
+fn skip_review_exemption(
+    config: &Config,
+    commit_type: &str,
+    breaking: bool,
+    commit_message: &str,
+    commit_hash: &str,
+    git_dir: &Path,
+    opts: RunOpts,
+) -> Result<bool> {
+    if !review::has_skip_review_trailer(commit_message) {
+        return Ok(false);
+    }
+    if let Some(reason) = review::review_exemption_refusal(config, commit_type, breaking) {
+        println!(
+            "{}",
+            format!("Ignoring '{}': {}.", review::SKIP_REVIEW_TRAILER, reason).yellow()
+        );
+        return Ok(false);
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Review skipped for this '{}' commit ({}).",
+            commit_type,
+            review::SKIP_REVIEW_TRAILER
+        )
+        .dimmed()
+    );
+    if !opts.dry_run {
+        journal::append(
+            git_dir,
+            &journal::JournalEntry::now(
+                git::get_user_identity(opts),
+                journal::JournalEvent::ReviewSkipped {
+                    commit: commit_hash.to_string(),
+                    commit_type: commit_type.to_string(),
+                },
+            ),
+        )?;
+    }
+    Ok(true)
+}
+
+/// Shows a diffstat of the staged changes, optionally the full diff, and
+/// asks whether to go ahead with the commit.
+fn confirm_staged_changes(opts: RunOpts) -> Result<bool> {
+    println!("{}", "--- Changes to be committed ---".blue());
+    println!("{}", git::get_staged_diffstat(opts)?);
+    if prompt::is_interactive()
+        && !opts.assume_yes
+        && prompt::confirm("Show the full diff?", Some(false), opts)?
+    {
+        println!("{}", git::get_staged_diff(opts)?);
+    }
+    prompt::confirm("Commit these changes?", Some(true), opts)
+}
+
+pub fn run_checklist_interactive(checklist: &[String], defaults: &[bool]) -> Result<Vec<usize>> {
+    prompt::checklist(
+        "Please confirm each item before committing:",
+        checklist,
+        defaults,
+    )
 }
 
 pub fn build_todo_footer(checklist: &[String], checked_indices: &[usize]) -> String {
@@ -41,19 +426,61 @@ pub fn build_todo_footer(checklist: &[String], checked_indices: &[usize]) -> Str
     }
 }
 
+/// Footer lines recording the evidence given for ticked DoD items.
+pub fn build_evidence_footer(evidence: &[(String, String)]) -> String {
+    if evidence.is_empty() {
+        return String::new();
+    }
+    let lines: Vec<String> = evidence
+        .iter()
+        .map(|(item, value)| format!("DoD-Evidence: {} ({})", value, item))
+        .collect();
+    format!("\n\n{}", lines.join("\n"))
+}
+
+/// Asks for evidence for every ticked item that declares an `evidence` prompt.
+fn collect_dod_evidence(config: &DodConfig, checked: &[usize]) -> Result<Vec<(String, String)>> {
+    let mut evidence = Vec::new();
+    for &i in checked {
+        let item = &config.checklist[i];
+        let Some(question) = item.evidence_prompt() else {
+            continue;
+        };
+        let value: String = Input::with_theme(&*output::theme())
+            .with_prompt(format!("{} ({})", question, item.text()))
+            .allow_empty(true)
+            .interact_text()?;
+        if value.trim().is_empty() {
+            println!(
+                "{}",
+                format!("No evidence recorded for '{}'.", item.text()).yellow()
+            );
+        } else {
+            evidence.push((item.text().to_string(), value.trim().to_string()));
+        }
+    }
+    Ok(evidence)
+}
+
 pub fn handle_interactive_commit(
     config: &DodConfig,
     base_message: &str,
 ) -> Result<Option<String>, anyhow::Error> {
     let mut commit_message = base_message.to_string();
 
-    let checked = run_checklist_interactive(&config.checklist)?;
-    if checked.len() != config.checklist.len() {
-        if Confirm::with_theme(&ColorfulTheme::default())
+    let items = config.item_texts();
+    let defaults: Vec<bool> = config
+        .checklist
+        .iter()
+        .map(|i| i.checked_by_default())
+        .collect();
+    let checked = run_checklist_interactive(&items, &defaults)?;
+    if checked.len() != items.len() {
+        if Confirm::with_theme(&*output::theme())
             .with_prompt("Warning: Not all DoD items were checked. Proceed by adding a 'TODO' list to the commit message?")
             .interact()?
         {
-            let todo_footer = build_todo_footer(&config.checklist, &checked);
+            let todo_footer = build_todo_footer(&items, &checked);
             commit_message.push_str(&todo_footer);
         } else {
             println!("Commit aborted.");
@@ -64,133 +491,546 @@ pub fn handle_interactive_commit(
     Ok(Some(commit_message))
 }
 
-pub fn handle_interactive_dod(config: &DodConfig) -> Result<Option<String>> {
-    let checked = run_checklist_interactive(&config.checklist)?;
-    if checked.len() != config.checklist.len() {
-        if Confirm::with_theme(&ColorfulTheme::default())
-            .with_prompt("Warning: Not all DoD items were checked. Proceed by adding a 'TODO' list to the commit message?")
-            .interact()?
-        {
-            let todo_footer = build_todo_footer(&config.checklist, &checked);
-            Ok(Some(todo_footer))
+pub fn handle_interactive_dod(config: &DodConfig, opts: RunOpts) -> Result<Option<String>> {
+    let items = config.item_texts();
+
+    // Items with a command are checked off by running it; the rest are manual.
+    let mut checked = Vec::new();
+    let mut manual = Vec::new();
+    for (i, item) in config.checklist.iter().enumerate() {
+        match item.command() {
+            Some(command) => {
+                if dod::run_check(item.text(), command, opts)? {
+                    checked.push(i);
+                }
+            }
+            None => manual.push(i),
+        }
+    }
+
+    let mut evidence = Vec::new();
+    if !manual.is_empty() {
+        // Without a TTY the checklist cannot be ticked; with --yes every item is
+        // recorded as an open TODO rather than silently claimed as done.
+        if prompt::is_interactive() {
+            let manual_items: Vec<String> = manual.iter().map(|&i| items[i].clone()).collect();
+            let manual_config: Vec<DodItem> = manual
+                .iter()
+                .map(|&i| config.checklist[i].clone())
+                .collect();
+            // Pre-tick what this user ticked on their previous commit this session.
+            let quiet = opts.for_reads();
+            let git_dir = git::get_git_dir(quiet).map(PathBuf::from).ok();
+            let user = git::get_user_email(quiet).unwrap_or_default();
+            let remembered = git_dir.as_deref().and_then(dod::load_selection);
+            let defaults =
+                dod::initial_selection(&manual_config, remembered.as_ref(), &user, Utc::now());
+            let ticked = run_checklist_interactive(&manual_items, &defaults)?;
+            if let Some(git_dir) = git_dir.filter(|_| !opts.dry_run) {
+                dod::save_selection(&git_dir, &user, &manual_items, &ticked)?;
+            }
+            let ticked: Vec<usize> = ticked.into_iter().map(|t| manual[t]).collect();
+            evidence = collect_dod_evidence(config, &ticked)?;
+            checked.extend(ticked);
+            checked.sort_unstable();
+        } else if !opts.assume_yes {
+            return Err(anyhow::anyhow!(
+                "The DoD checklist needs an interactive terminal. Re-run with --no-verify to skip it, or --yes to record every item as a TODO."
+            ));
+        }
+    }
+
+    let evidence_footer = build_evidence_footer(&evidence);
+    if checked.len() != items.len() {
+        if prompt::confirm(
+            "Warning: Not all DoD items were checked. Proceed by adding a 'TODO' list to the commit message?",
+            None,
+            opts,
+        )? {
+            let todo_footer = build_todo_footer(&items, &checked);
+            Ok(Some(evidence_footer + &todo_footer))
         } else {
             println!("Commit aborted.");
             Ok(None)
         }
     } else {
-        Ok(Some(String::new())) // All items checked, return an empty footer.
+        Ok(Some(evidence_footer)) // All items checked; only evidence, if any.
     }
 }
 
 pub fn is_valid_commit_type(commit_type: &str, config: &Config) -> bool {
-    if let Some(lint_config) = &config.lint {
-        if let Some(conventional_commit_type) = &lint_config.conventional_commit_type {
-            if let Some(enabled) = conventional_commit_type.enabled {
-                if !enabled {
-                    return true; // If linting is disabled, any type is valid
-                }
-            }
-            if let Some(allowed_types) = &conventional_commit_type.allowed_types {
-                return allowed_types.iter().any(|t| t == commit_type);
-            }
+    if let Some(lint_config) = &config.lint
+        && let Some(conventional_commit_type) = &lint_config.conventional_commit_type
+    {
+        if let Some(enabled) = conventional_commit_type.enabled
+            && !enabled
+        {
+            return true; // If linting is disabled, any type is valid
+        }
+        if let Some(allowed_types) = &conventional_commit_type.allowed_types {
+            return allowed_types.iter().any(|t| t == commit_type);
         }
     }
     true
 }
 
 pub fn is_valid_issue_key(issue_key: &Option<String>, config: &Config) -> Result<bool> {
-    if let Some(lint_config) = &config.lint {
-        if let Some(issue_key_config) = &lint_config.issue_key_missing {
-            if let Some(enabled) = issue_key_config.enabled {
-                if !enabled {
-                    return Ok(true); // If linting is disabled, any issue key is valid
-                }
-            }
-            if let Some(issue_key_pattern) = &issue_key_config.pattern {
-                let re = regex::Regex::new(issue_key_pattern).map_err(|e| {
-                    anyhow::anyhow!("Invalid issue_key pattern '{}': {}", issue_key_pattern, e)
-                })?;
-                return Ok(re.is_match(issue_key.as_ref().unwrap_or(&"".to_string())));
-            }
+    if let Some(lint_config) = &config.lint
+        && let Some(issue_key_config) = &lint_config.issue_key_missing
+    {
+        if let Some(enabled) = issue_key_config.enabled
+            && !enabled
+        {
+            return Ok(true); // If linting is disabled, any issue key is valid
+        }
+        if let Some(issue_key_pattern) = &issue_key_config.pattern {
+            let re = regex::Regex::new(issue_key_pattern).map_err(|e| {
+                anyhow::anyhow!("Invalid issue_key pattern '{}': {}", issue_key_pattern, e)
+            })?;
+            return Ok(re.is_match(issue_key.as_ref().unwrap_or(&"".to_string())));
         }
     }
     Ok(true)
 }
 
 pub fn is_valid_scope(scope: &Option<String>, config: &Config) -> bool {
-    if let Some(lint_config) = &config.lint {
-        if let Some(scope_config) = &lint_config.scope {
-            if let Some(enabled) = scope_config.enabled {
-                if !enabled {
-                    return true; // If linting is disabled, any scope is valid
-                }
-            }
-            if let Some(enforce_lowercase) = scope_config.enforce_lowercase {
-                if enforce_lowercase {
-                    if let Some(s) = scope {
-                        return s.chars().all(|c| c.is_lowercase());
-                    }
-                }
-            }
+    if let Some(lint_config) = &config.lint
+        && let Some(scope_config) = &lint_config.scope
+    {
+        if let Some(enabled) = scope_config.enabled
+            && !enabled
+        {
+            return true; // If linting is disabled, any scope is valid
+        }
+        if let Some(enforce_lowercase) = scope_config.enforce_lowercase
+            && enforce_lowercase
+            && let Some(s) = scope
+        {
+            return s.chars().all(|c| c.is_lowercase());
         }
     }
     true
 }
 
 pub fn is_valid_subject_line(subject: &str, config: &Config) -> Result<(), String> {
-    if let Some(lint) = &config.lint {
-        if let Some(rules) = &lint.subject_line_rules {
-            if let Some(max_len) = rules.max_length {
-                if subject.len() > max_len {
-                    return Err(format!(
-                        "Subject line exceeds maximum length of {} characters.",
-                        max_len
-                    ));
-                }
-            }
-            if let Some(enforce_lowercase) = rules.enforce_lowercase {
-                if enforce_lowercase {
-                    if let Some(first) = subject.chars().next() {
-                        if first.is_uppercase() {
-                            return Err(
-                                "Subject line must not start with a capital letter.".to_string()
-                            );
-                        }
-                    }
-                }
-            }
-            if let Some(no_period) = rules.no_period {
-                if no_period && subject.trim_end().ends_with('.') {
-                    return Err("Subject line should not end with a period.".to_string());
-                }
-            }
+    if let Some(lint) = &config.lint
+        && let Some(rules) = &lint.subject_line_rules
+    {
+        if let Some(max_len) = rules.max_length
+            && subject.len() > max_len
+        {
+            return Err(format!(
+                "Subject line exceeds maximum length of {} characters.",
+                max_len
+            ));
+        }
+        if let Some(enforce_lowercase) = rules.enforce_lowercase
+            && enforce_lowercase
+            && let Some(first) = subject.chars().next()
+            && first.is_uppercase()
+        {
+            return Err("Subject line must not start with a capital letter.".to_string());
+        }
+        if let Some(no_period) = rules.no_period
+            && no_period
+            && subject.trim_end().ends_with('.')
+        {
+            return Err("Subject line should not end with a period.".to_string());
         }
     }
     Ok(())
 }
 
 pub fn is_valid_body_lines(body: &str, config: &Config) -> bool {
-    if let Some(lint) = &config.lint {
-        if let Some(rules) = &lint.body_line_rules {
-            if let Some(max_len) = rules.max_line_length {
-                for line in body.lines() {
-                    if line.len() > max_len {
-                        return false;
-                    }
-                }
+    if let Some(lint) = &config.lint
+        && let Some(rules) = &lint.body_line_rules
+        && let Some(max_len) = rules.max_line_length
+    {
+        for line in body.lines() {
+            if line.len() > max_len {
+                return false;
             }
-            // Enforced in code already, but can be uncommented later on
-            // if let Some(leading_blank) = rules.leading_blank {
-            //     if leading_blank && !body.starts_with("\n\n") {
-            //         return false; // Body must start with a leading blank line
-            //     }
-            // }
         }
     }
+    // Enforced in code already, but can be uncommented later on
+    // if let Some(leading_blank) = rules.leading_blank {
+    //     if leading_blank && !body.starts_with("\n\n") {
+    //         return false; // Body must start with a leading blank line
+    //     }
+    // }
     true
 }
 
-pub fn handle_commit(opts: RunOpts, config: &Config, params: CommitParams) -> Result<()> {
+/// A single lint failure, named after the `.tbdflow.yml` rule that produced it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintViolation {
+    pub rule: &'static str,
+    /// The part of the message at fault: `type`, `scope`, `subject`, `body`,
+    /// `issue`, or `message` when it is not a Conventional Commit at all.
+    pub field: &'static str,
+    pub message: String,
+    /// The offending text, when the rule points at some.
+    pub text: Option<String>,
+    /// What `text` should be, for failures `--fix` can correct on its own.
+    pub fix: Option<String>,
+}
+
+/// The subject with the auto-correctable rules applied: a trailing period
+/// stripped and the first letter lowercased, unless the first word is an
+/// acronym such as "API". `None` when that changes nothing.
+pub fn fix_subject(subject: &str, config: &Config) -> Option<String> {
+    let rules = config.lint.as_ref()?.subject_line_rules.as_ref()?;
+    let mut fixed = subject.trim_end().to_string();
+    if rules.no_period == Some(true) {
+        fixed = fixed.trim_end_matches('.').trim_end().to_string();
+    }
+    let first_word = fixed.split_whitespace().next().unwrap_or_default();
+    let acronym = first_word.chars().filter(|c| c.is_alphabetic()).count() > 1
+        && !first_word.chars().any(char::is_lowercase);
+    if rules.enforce_lowercase == Some(true) && !acronym {
+        let mut chars = fixed.chars();
+        if let Some(first) = chars.next() {
+            fixed = first.to_lowercase().chain(chars).collect();
+        }
+    }
+    (!fixed.is_empty() && fixed != subject).then_some(fixed)
+}
+
+/// The first body line longer than `body_line_rules.max_line_length`.
+fn first_long_body_line<'a>(body: &'a str, config: &Config) -> Option<&'a str> {
+    let max_len = config
+        .lint
+        .as_ref()?
+        .body_line_rules
+        .as_ref()?
+        .max_line_length?;
+    body.lines().find(|line| line.len() > max_len)
+}
+
+/// Runs every configured lint rule and collects all violations instead of
+/// stopping at the first one.
+pub fn lint_commit_params(params: &CommitParams, config: &Config) -> Result<Vec<LintViolation>> {
+    let mut violations = Vec::new();
+
+    if !is_valid_commit_type(&params.r#type, config) {
+        violations.push(LintViolation {
+            rule: "conventional_commit_type",
+            field: "type",
+            message: format!(
+                "'{}' is not a valid Conventional Commit type.",
+                params.r#type
+            ),
+            text: Some(params.r#type.clone()),
+            fix: None,
+        });
+    }
+    if !is_valid_issue_key(&params.issue, config)? {
+        violations.push(LintViolation {
+            rule: "issue_key_missing",
+            field: "issue",
+            message: "Issue reference is required by your .tbdflow.yml config.".to_string(),
+            text: params.issue.clone(),
+            fix: None,
+        });
+    }
+    if !is_valid_scope(&params.scope, config) {
+        let fix = params
+            .scope
+            .as_ref()
+            .map(|s| s.to_lowercase())
+            .filter(|lower| is_valid_scope(&Some(lower.clone()), config));
+        violations.push(LintViolation {
+            rule: "scope",
+            field: "scope",
+            message: "Scope must be lowercase.".to_string(),
+            text: params.scope.clone(),
+            fix,
+        });
+    }
+    if let Err(e) = is_valid_subject_line(&params.message, config) {
+        violations.push(LintViolation {
+            rule: "subject_line_rules",
+            field: "subject",
+            message: e,
+            text: Some(params.message.clone()),
+            fix: fix_subject(&params.message, config),
+        });
+    }
+    if let Some(body_text) = &params.body
+        && !is_valid_body_lines(body_text, config)
+    {
+        violations.push(LintViolation {
+            rule: "body_line_rules",
+            field: "body",
+            message: "Commit message body contains lines that exceed the maximum length."
+                .to_string(),
+            text: first_long_body_line(body_text, config).map(str::to_string),
+            fix: None,
+        });
+    }
+
+    Ok(violations)
+}
+
+/// Applies every suggested fix to `params` and returns the violations that
+/// were fixed. Whatever cannot be fixed is left for the normal lint checks.
+pub fn apply_fixes(params: &mut CommitParams, config: &Config) -> Result<Vec<LintViolation>> {
+    let fixed: Vec<LintViolation> = lint_commit_params(params, config)?
+        .into_iter()
+        .filter(|v| v.fix.is_some())
+        .collect();
+    for v in &fixed {
+        let value = v.fix.clone().unwrap_or_default();
+        match v.field {
+            "scope" => params.scope = Some(value),
+            "subject" => params.message = value,
+            _ => {}
+        }
+    }
+    Ok(fixed)
+}
+
+/// Where a violation's offending text sits in the raw message: the line index
+/// and byte offset within that line. Comment lines are skipped, and the type,
+/// scope and subject are only looked for in the header.
+fn span(text: &str, v: &LintViolation) -> Option<(usize, usize)> {
+    let needle = v.text.as_deref().filter(|t| !t.is_empty())?;
+    let mut lines = text
+        .split('\n')
+        .enumerate()
+        .filter(|(_, line)| !line.starts_with('#'));
+    match v.field {
+        "type" | "scope" | "subject" => {
+            let (i, header) = lines.find(|(_, line)| !line.trim().is_empty())?;
+            let at = match v.field {
+                "type" => header.find(needle),
+                "scope" => header.find(&format!("({})", needle)).map(|at| at + 1),
+                _ => header.rfind(needle),
+            }?;
+            Some((i, at))
+        }
+        _ => lines.find_map(|(i, line)| line.find(needle).map(|at| (i, at))),
+    }
+}
+
+/// The 1-based line and column (in characters) a violation points at in the
+/// raw message `text`; the start of the message when it has no offending text.
+pub fn locate(text: &str, v: &LintViolation) -> (usize, usize) {
+    match span(text, v) {
+        Some((i, at)) => {
+            let line = text.split('\n').nth(i).unwrap_or_default();
+            (i + 1, line[..at].chars().count() + 1)
+        }
+        None => (1, 1),
+    }
+}
+
+/// The raw message `text` with every suggested fix written in place; comments
+/// and the rest of the message are left as they were.
+pub fn fix_message_text(text: &str, violations: &[LintViolation]) -> String {
+    let mut lines: Vec<String> = text.split('\n').map(str::to_string).collect();
+    let mut edits: Vec<(usize, usize, &LintViolation)> = violations
+        .iter()
+        .filter(|v| v.fix.is_some())
+        .filter_map(|v| span(text, v).map(|(i, at)| (i, at, v)))
+        .collect();
+    // Right to left, so an edit never shifts the offsets of the next one.
+    edits.sort_by_key(|&(i, at, _)| std::cmp::Reverse((i, at)));
+    for (i, at, v) in edits {
+        let len = v.text.as_deref().unwrap_or_default().len();
+        lines[i].replace_range(at..at + len, v.fix.as_deref().unwrap_or_default());
+    }
+    lines.join("\n")
+}
+
+/// The field, offending text and fix of a violation as `key="value"` pairs,
+/// with JSON string quoting so the values can be parsed back unambiguously.
+pub fn violation_details(v: &LintViolation) -> String {
+    let quote = |s: &str| serde_json::to_string(s).unwrap_or_default();
+    let mut details = format!("field={}", v.field);
+    if let Some(text) = &v.text {
+        details.push_str(&format!(" text={}", quote(text)));
+    }
+    if let Some(fix) = &v.fix {
+        details.push_str(&format!(" fix={}", quote(fix)));
+    }
+    details
+}
+
+/// Parses and lints a raw commit message without committing anything.
+/// Returns an error (non-zero exit) when the message has any violations.
+pub fn handle_parse(config: &Config, text: &str, json: bool) -> Result<()> {
+    let violations = match parse_commit_message(text) {
+        Ok(params) => lint_commit_params(&params, config)?,
+        Err(e) => vec![not_conventional(e)],
+    };
+
+    if json {
+        let response = ParseResponse {
+            valid: violations.is_empty(),
+            violations: violations
+                .iter()
+                .map(|v| {
+                    let (line, column) = locate(text, v);
+                    LintViolationResponse {
+                        rule: v.rule.to_string(),
+                        message: v.message.clone(),
+                        field: v.field.to_string(),
+                        text: v.text.clone(),
+                        fix: v.fix.clone(),
+                        line,
+                        column,
+                    }
+                })
+                .collect(),
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(response))?
+        );
+    } else if violations.is_empty() {
+        println!("{}", "Commit message is valid.".green());
+    } else {
+        println!("{}", "Commit message has lint violations:".red().bold());
+        for v in &violations {
+            let (line, column) = locate(text, v);
+            println!("  - [{}] {}", v.rule.yellow(), v.message);
+            println!(
+                "    {}",
+                format!("{}:{}: {}", line, column, violation_details(v)).dimmed()
+            );
+        }
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Commit message failed {} lint rule(s).",
+            violations.len()
+        ))
+    }
+}
+
+/// Exit codes of `tbdflow verify-commit-msg`, as documented in its help.
+pub const VERIFY_EXIT_VALID: i32 = 0;
+pub const VERIFY_EXIT_INVALID: i32 = 1;
+pub const VERIFY_EXIT_UNREADABLE: i32 = 2;
+pub const VERIFY_EXIT_CONFIG: i32 = 3;
+
+/// Line git puts above the diff in `commit --verbose`; nothing below it is part of the message.
+const SCISSORS_MARKER: &str = "------------------------ >8 ------------------------";
+
+/// The message as git will store it: comment lines and the scissors section removed.
+pub fn strip_message_comments(text: &str) -> String {
+    text.lines()
+        .take_while(|line| !(line.starts_with('#') && line.contains(SCISSORS_MARKER)))
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// True for subjects git writes itself, which are not Conventional Commits.
+fn is_generated_by_git(subject: &str) -> bool {
+    ["Merge ", "Revert \"", "fixup! ", "squash! ", "amend! "]
+        .iter()
+        .any(|prefix| subject.starts_with(prefix))
+}
+
+/// Checks a commit message file with the full rule set, including any lint
+/// override for `branch`. Returns the violations; empty when the message passes.
+pub fn verify_commit_msg(text: &str, config: &Config, branch: &str) -> Result<Vec<LintViolation>> {
+    let message = strip_message_comments(text);
+    let subject = message.lines().next().unwrap_or_default();
+    // An empty message is left for git to reject with its own error.
+    if message.is_empty() || is_generated_by_git(subject) {
+        return Ok(Vec::new());
+    }
+    let lint_config = Config {
+        lint: config.lint_for_branch(branch)?,
+        ..config.clone()
+    };
+    match parse_commit_message(&message) {
+        Ok(params) => lint_commit_params(&params, &lint_config),
+        Err(e) => Ok(vec![not_conventional(e)]),
+    }
+}
+
+/// The violation for a message that does not parse as a Conventional Commit.
+fn not_conventional(e: anyhow::Error) -> LintViolation {
+    LintViolation {
+        rule: "conventional_format",
+        field: "message",
+        message: e.to_string(),
+        text: None,
+        fix: None,
+    }
+}
+
+/// Runs `verify-commit-msg` and returns the exit code. Output goes to stderr,
+/// one line per problem, so git shows it under the failed commit. With `fix`,
+/// the file is rewritten with the suggested fixes and checked again.
+pub fn handle_verify_commit_msg(config: &Config, path: &str, fix: bool, opts: RunOpts) -> i32 {
+    let mut text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("tbdflow: cannot read commit message file '{}': {}", path, e);
+            return VERIFY_EXIT_UNREADABLE;
+        }
+    };
+    let branch = git::get_current_branch(opts.for_reads()).unwrap_or_default();
+    let mut result = verify_commit_msg(&text, config, &branch);
+    if fix
+        && let Ok(violations) = &result
+        && violations.iter().any(|v| v.fix.is_some())
+    {
+        let fixed = fix_message_text(&text, violations);
+        if let Err(e) = fs::write(path, &fixed) {
+            eprintln!(
+                "tbdflow: cannot write commit message file '{}': {}",
+                path, e
+            );
+            return VERIFY_EXIT_UNREADABLE;
+        }
+        for v in violations.iter().filter(|v| v.fix.is_some()) {
+            eprintln!("tbdflow: fixed [{}] {}", v.rule, violation_details(v));
+        }
+        text = fixed;
+        result = verify_commit_msg(&text, config, &branch);
+    }
+    match result {
+        Ok(violations) if violations.is_empty() => VERIFY_EXIT_VALID,
+        Ok(violations) => {
+            for v in &violations {
+                let (line, column) = locate(&text, v);
+                eprintln!(
+                    "{}:{}:{}: tbdflow: [{}] {} {}",
+                    path,
+                    line,
+                    column,
+                    v.rule,
+                    v.message,
+                    violation_details(v)
+                );
+            }
+            if !fix && violations.iter().any(|v| v.fix.is_some()) {
+                eprintln!(
+                    "tbdflow: run 'tbdflow verify-commit-msg --fix {}' to apply the fixes.",
+                    path
+                );
+            }
+            VERIFY_EXIT_INVALID
+        }
+        Err(e) => {
+            eprintln!("tbdflow: invalid .tbdflow.yml: {:#}", e);
+            VERIFY_EXIT_CONFIG
+        }
+    }
+}
+
+pub fn handle_commit(opts: RunOpts, config: &Config, mut params: CommitParams) -> Result<()> {
     println!("{}", "--- Committing changes ---".blue());
 
     // Check for conflicting flags based on issue handling strategy
@@ -212,7 +1052,30 @@ pub fn handle_commit(opts: RunOpts, config: &Config, params: CommitParams) -> Re
         ));
     }
 
-    // Linting based on the provided configuration
+    // Linting based on the provided configuration, with any override for
+    // the current branch type applied.
+    let branch_for_lint = git::get_current_branch(opts.for_reads()).unwrap_or_default();
+    let lint_config = Config {
+        lint: config.lint_for_branch(&branch_for_lint)?,
+        ..config.clone()
+    };
+    let config = &lint_config;
+
+    if params.fix {
+        for v in apply_fixes(&mut params, config)? {
+            println!(
+                "{}",
+                format!(
+                    "Fixed {}: '{}' -> '{}'",
+                    v.field,
+                    v.text.unwrap_or_default(),
+                    v.fix.unwrap_or_default()
+                )
+                .yellow()
+            );
+        }
+    }
+
     if !is_valid_commit_type(&params.r#type, config) {
         println!(
             "{}",
@@ -235,38 +1098,56 @@ pub fn handle_commit(opts: RunOpts, config: &Config, params: CommitParams) -> Re
 
     if let Err(e) = is_valid_subject_line(&params.message, config) {
         println!("{}", format!("Commit message subject error: {}", e).red());
+        if let Some(fixed) = fix_subject(&params.message, config) {
+            println!(
+                "{}",
+                format!("Hint: Run again with --fix to commit it as '{}'.", fixed).yellow()
+            );
+        }
         return Err(anyhow::anyhow!("Aborted: Invalid commit message subject."));
     }
 
-    if let Some(body_text) = &params.body {
-        if !is_valid_body_lines(body_text, config) {
+    if let Some(body_text) = &params.body
+        && !is_valid_body_lines(body_text, config)
+    {
+        println!(
+            "{}",
+            "Commit message body contains lines that exceed the maximum length.".red()
+        );
+        return Err(anyhow::anyhow!("Aborted: Invalid commit message body."));
+    }
+
+    if let Some(s) = &params.scope
+        && !is_valid_scope(&Some(s.clone()), config)
+    {
+        println!("{}", "Scope must be lowercase.".red());
+        if is_valid_scope(&Some(s.to_lowercase()), config) {
             println!(
                 "{}",
-                "Commit message body contains lines that exceed the maximum length.".red()
+                format!("Hint: Run again with --fix to use '{}'.", s.to_lowercase()).yellow()
             );
-            return Err(anyhow::anyhow!("Aborted: Invalid commit message body."));
         }
+        return Err(anyhow::anyhow!("Aborted: Invalid commit scope."));
     }
 
-    if let Some(s) = &params.scope {
-        if !is_valid_scope(&Some(s.clone()), config) {
-            println!("{}", "Scope must be lowercase.".red());
-            return Err(anyhow::anyhow!("Aborted: Invalid commit scope."));
-        }
+    if let Some(tag_name) = &params.tag {
+        git::ensure_tag_available(tag_name, opts)?;
     }
 
-    let scope_part = params.scope.map_or("".to_string(), |s| format!("({})", s));
-    let breaking_part = if params.breaking { "!" } else { "" };
-    let header = format!(
-        "{}{}{}: {}",
-        params.r#type, scope_part, breaking_part, params.message
-    );
+    let release_target = check_release_stabilization(config, &params.r#type, opts)?;
+
+    let header = commit_header(&params);
 
-    let dod_config = config::load_dod_config().unwrap_or_default();
+    let dod_config = if config.commit.dod {
+        config::load_dod_config(opts).unwrap_or_default()
+    } else {
+        config::DodConfig::default()
+    };
+    let dod_skipped = params.no_verify && !dod_config.checklist.is_empty();
     let todo_footer_result = if params.no_verify || dod_config.checklist.is_empty() {
         Ok(Some(String::new()))
     } else {
-        handle_interactive_dod(&dod_config)
+        handle_interactive_dod(&dod_config, opts)
     };
 
     if let Some(todo_footer) = todo_footer_result? {
@@ -274,31 +1155,32 @@ pub fn handle_commit(opts: RunOpts, config: &Config, params: CommitParams) -> Re
 
         // Read the intent log (if any) for inclusion in the commit body.
         let intent_log = intent::load_intent_log(&git_root)?;
-        let intent_section = intent_log
-            .as_ref()
-            .and_then(|log| intent::format_for_commit(log));
-
-        let mut commit_message = header;
-        if let Some(body_text) = params.body {
-            commit_message.push_str("\n\n");
-            commit_message.push_str(&body_text);
-        }
-        // Append the Intent Log section (before breaking change / refs / TODO)
-        if let Some(intent_text) = &intent_section {
-            commit_message.push_str("\n\n");
-            commit_message.push_str(intent_text);
-        }
-        if let Some(desc) = params.breaking_description {
-            commit_message.push_str(&format!("\n\nBREAKING CHANGE: {}", desc));
-        }
-        if let Some(issue_ref) = &params.issue {
-            commit_message.push_str(&format!("\n\nRefs: {}", issue_ref));
+        let intent_section = intent_log.as_ref().and_then(intent::format_for_commit);
+
+        // Credit the active pair, unless the message already names them
+        let mut trailers = params.trailers.clone();
+        let git_dir = PathBuf::from(git::get_git_dir(opts)?);
+        if let Some(session) = pair::load_pair_session(&git_dir)? {
+            for trailer in session.trailers() {
+                if !trailers.iter().any(|t| t.eq_ignore_ascii_case(&trailer)) {
+                    trailers.push(trailer);
+                }
+            }
         }
+        push_version_trailer(config, &mut trailers);
+        // The Intent Log section goes after the body, before the footers.
+        let mut commit_message =
+            build_commit_message(&params, intent_section.as_deref(), &trailers);
         commit_message.push_str(&todo_footer);
 
         println!(
             "{}",
-            format!("Commit message will be:\n---\n{}\n---", commit_message).blue()
+            format!(
+                "{}\n---\n{}\n---",
+                i18n::t(Msg::CommitMessageWillBe),
+                commit_message
+            )
+            .blue()
         );
 
         if opts.verbose {
@@ -310,76 +1192,305 @@ pub fn handle_commit(opts: RunOpts, config: &Config, params: CommitParams) -> Re
         git::stage_scoped_changes(config, params.include_projects, opts)?;
 
         if !git::has_staged_changes(opts)? {
-            println!("{}", "No changes added to commit.".yellow());
+            println!("{}", i18n::t(Msg::NoChangesToCommit).yellow());
+            return Ok(());
+        }
+
+        if params.preview && !confirm_staged_changes(opts)? {
+            println!("{}", i18n::t(Msg::CommitAborted).yellow());
             return Ok(());
         }
 
         // Radar: check for overlapping work before committing
         if !radar::check_before_commit(config, opts)? {
-            println!("{}", "Commit aborted by user.".yellow());
+            println!("{}", i18n::t(Msg::CommitAborted).yellow());
             return Ok(());
         }
 
-        let current_branch = git::get_current_branch(opts)?;
-        if current_branch == config.main_branch_name {
-            println!("--- Committing directly to main branch ---");
-            git::pull_latest_with_rebase(opts)?;
-            git::commit(&commit_message, opts)?;
-            git::push(opts)?;
+        if let Some(release) = &release_target {
+            git::checkout_branch(release, opts).map_err(|e| {
+                anyhow::anyhow!("Could not switch to '{}' with your changes: {}", release, e)
+            })?;
+        }
+        let pending = PendingCommit {
+            branch: git::get_current_branch(opts)?,
+            step: CommitStep::Commit,
+            fixes: review::fixed_reviews(&trailers),
+            message: commit_message,
+            header,
+            r#type: params.r#type,
+            breaking: params.breaking,
+            scope: params.scope,
+            include_projects: params.include_projects,
+            tag: params.tag,
+            dod_skipped,
+            clear_intent: intent_section.is_some(),
+            commit: None,
+            error: String::new(),
+        };
+        finish_commit(config, pending, opts)?;
+        if let Some(release) = &release_target {
+            git::checkout_main(opts, &config.main_branch_name)?;
             println!(
-                "\n{}",
-                "Successfully committed and pushed changes to main.".green()
+                "{}",
+                format!(
+                    "Back on '{}'. The commit reaches it when '{}' is completed.",
+                    config.main_branch_name, release
+                )
+                .dimmed()
             );
+        }
+    }
+    Ok(())
+}
 
-            // Clean-up the intent log after successful push to trunk
-            if intent_section.is_some() {
-                // Report snapshot consumption before clearing
-                if let Ok(Some(log)) = intent::load_intent_log(&git_root) {
-                    let snapshot_count = log
-                        .notes
-                        .iter()
-                        .filter(|n| n.snapshot_hash.is_some())
-                        .count();
-                    if snapshot_count > 0 {
-                        println!(
-                            "{}",
-                            format!(
-                                "Releasing {} WIP snapshot(s), your work is now in git history.",
-                                snapshot_count
-                            )
-                            .dimmed()
-                        );
-                    }
-                }
-                intent::cleanup_intent_log(&git_root)?;
-                println!("{}", "Intent log consumed and cleared.".dimmed());
-            }
+/// Release branches, local or on origin, oldest version first.
+fn release_branches(names: &[String], prefix: &str, trunk: &str) -> Vec<String> {
+    let version = |name: &str| -> Vec<u64> {
+        name.split(|c: char| !c.is_ascii_digit())
+            .filter_map(|part| part.parse().ok())
+            .collect()
+    };
+    let mut releases: Vec<String> = names
+        .iter()
+        .filter(|name| !prefix.is_empty() && name.starts_with(prefix) && *name != trunk)
+        .cloned()
+        .collect();
+    releases.sort_by_key(|name| version(name));
+    releases
+}
+
+/// Warns about a commit to main while a release branch exists. Commits of
+/// `commit.release_stabilization.types` stay on main, out of the release.
+/// With the `offer` policy, a commit of one of its `fix_types` may go to the
+/// release branch instead; returns that branch if so.
+fn check_release_stabilization(
+    config: &Config,
+    commit_type: &str,
+    opts: RunOpts,
+) -> Result<Option<String>> {
+    let stabilization = &config.commit.release_stabilization;
+    let kept_out = stabilization.types.iter().any(|t| t == commit_type);
+    let is_fix = stabilization.fix_types.iter().any(|t| t == commit_type);
+    if stabilization.policy == config::StabilizationPolicy::Off || !(kept_out || is_fix) {
+        return Ok(None);
+    }
+    let quiet = opts.for_reads();
+    // With a trunk target set, the team is already committing to the release.
+    if git::get_current_branch(quiet)? != config.main_branch_name
+        || target::active_target(opts).is_some()
+    {
+        return Ok(None);
+    }
+    let Some(prefix) = config.branch_types.get("release") else {
+        return Ok(None);
+    };
+    let releases = release_branches(
+        &git::list_branch_names(quiet)?,
+        prefix,
+        &config.main_branch_name,
+    );
+    let Some(release) = releases.last() else {
+        return Ok(None);
+    };
 
-            // Auto-trigger review if rules match the changed files
-            let commit_hash = git::get_head_commit_hash(opts)?;
-            if review::should_auto_trigger_review(config, &commit_hash, opts)? {
-                let author = git::get_user_name(opts)?;
-                review::trigger_review(config, None, &commit_hash, &commit_message, &author, opts)?;
+    let outcome = if kept_out {
+        "stays out of that release"
+    } else {
+        "will not be in that release"
+    };
+    println!(
+        "{}",
+        format!(
+            "'{}' is being stabilised. This '{}' commit goes to '{}' and {}.",
+            release, commit_type, config.main_branch_name, outcome
+        )
+        .yellow()
+    );
+    if releases.len() > 1 {
+        println!(
+            "{}",
+            format!(
+                "   Other release branches: {}",
+                releases[..releases.len() - 1].join(", ")
+            )
+            .dimmed()
+        );
+    }
+    // New work never goes onto a release that is being stabilised.
+    if kept_out || stabilization.policy == config::StabilizationPolicy::Warn {
+        return Ok(None);
+    }
+    if prompt::confirm(
+        &format!("Commit to '{}' instead?", release),
+        Some(false),
+        opts,
+    )? {
+        Ok(Some(release.clone()))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Runs the steps of a commit from `pending.step` on. When one fails, the
+/// rest is saved for `tbdflow retry`; once all are done, it is forgotten.
+pub fn finish_commit(config: &Config, mut pending: PendingCommit, opts: RunOpts) -> Result<()> {
+    let git_dir = PathBuf::from(git::get_git_dir(opts.for_reads())?);
+    match run_commit_steps(config, &mut pending, &git_dir, opts) {
+        Ok(()) => {
+            if !opts.dry_run {
+                retry::clear(&git_dir)?;
+            }
+            Ok(())
+        }
+        Err(e) => {
+            if !opts.dry_run {
+                pending.error = e.to_string();
+                retry::save(&git_dir, &pending)?;
+                println!(
+                    "{}",
+                    format!(
+                        "Run 'tbdflow retry' to continue from the {} step, without the checklist and message again.",
+                        pending.step.as_str()
+                    )
+                    .yellow()
+                );
             }
+            Err(e)
+        }
+    }
+}
+
+/// Clears the intent log once its notes are in a commit on trunk.
+fn release_intent_log(opts: RunOpts) -> Result<()> {
+    let git_root = PathBuf::from(git::get_git_root(opts.for_reads())?);
+    let Some(log) = intent::load_intent_log(&git_root)? else {
+        return Ok(());
+    };
+    // Report snapshot consumption before clearing
+    let snapshot_count = log
+        .notes
+        .iter()
+        .filter(|n| n.snapshot_hash.is_some())
+        .count();
+    if snapshot_count > 0 {
+        println!(
+            "{}",
+            format!(
+                "Releasing {} WIP snapshot(s), your work is now in git history.",
+                snapshot_count
+            )
+            .dimmed()
+        );
+    }
+    intent::cleanup_intent_log(&git_root)?;
+    println!("{}", i18n::t(Msg::IntentLogCleared).dimmed());
+    Ok(())
+}
+
+/// Each step moves `pending.step` on once it has done its work, so a
+/// failure leaves it at the step to retry.
+fn run_commit_steps(
+    config: &Config,
+    pending: &mut PendingCommit,
+    git_dir: &Path,
+    opts: RunOpts,
+) -> Result<()> {
+    let on_trunk = pending.branch == config.main_branch_name;
+
+    if pending.step == CommitStep::Commit {
+        if on_trunk {
+            println!("{}", i18n::t(Msg::CommittingToMain));
+            git::pull_latest_with_rebase(opts)?;
+        } else {
+            println!(
+                "{}",
+                i18n::tf(Msg::CommittingToBranch, &[("branch", &pending.branch)])
+            );
+        }
+        git::commit(&pending.message, opts)?;
+        pending.commit = Some(git::get_head_commit_hash(opts)?);
+        pending.step = CommitStep::Push;
+    }
+
+    if pending.step == CommitStep::Push {
+        if on_trunk {
+            push_to_trunk(&pending.branch, opts)?;
+            println!("\n{}", i18n::t(Msg::CommittedToMain).green());
         } else {
-            println!("--- Committing to feature branch '{}' ---", current_branch);
-            git::commit(&commit_message, opts)?;
             git::push(opts)?;
             println!(
                 "\n{}",
-                format!("Successfully pushed changes to '{}'.", current_branch).green()
+                i18n::tf(Msg::PushedToBranch, &[("branch", &pending.branch)]).green()
             );
         }
+        pending.step = CommitStep::Review;
+        if on_trunk {
+            check_forgotten_files(pending.scope.as_deref(), opts)?;
+            // Amending in a forgotten file moves HEAD.
+            pending.commit = Some(git::get_head_commit_hash(opts)?);
+            if pending.clear_intent && !opts.dry_run {
+                release_intent_log(opts)?;
+            }
+        }
+        if pending.dod_skipped && !opts.dry_run {
+            journal::append(
+                git_dir,
+                &journal::JournalEntry::now(
+                    git::get_user_identity(opts),
+                    journal::JournalEvent::DodSkipped {
+                        commit: git::get_head_commit_hash(opts)?,
+                        branch: pending.branch.clone(),
+                    },
+                ),
+            )?;
+        }
+    }
+
+    if pending.step == CommitStep::Review {
+        // Auto-trigger review if rules match the changed files
+        let commit_hash = git::get_head_commit_hash(opts)?;
+        if on_trunk
+            && review::should_auto_trigger_review(config, &commit_hash, opts)?
+            && !skip_review_exemption(
+                config,
+                &pending.r#type,
+                pending.breaking,
+                &pending.message,
+                &commit_hash,
+                git_dir,
+                opts,
+            )?
+        {
+            let author = git::get_user_name(opts)?;
+            review::trigger_review(config, None, &commit_hash, &pending.message, &author, opts)?;
+        }
+        pending.step = CommitStep::LinkFixes;
+    }
+
+    if pending.step == CommitStep::LinkFixes {
+        for review_hash in &pending.fixes {
+            let fix_hash = git::get_head_commit_hash(opts)?;
+            review::link_fix_to_review(config, review_hash, &fix_hash, &pending.header, opts)?;
+        }
+        pending.step = CommitStep::Tag;
+    }
 
-        if let Some(tag_name) = params.tag {
-            let commit_hash = git::get_head_commit_hash(opts)?;
-            git::create_tag(&tag_name, &commit_message, &commit_hash, opts)?;
+    if let Some(tag_name) = &pending.tag {
+        let commit_hash = git::get_head_commit_hash(opts)?;
+        // A retry may find the tag made by the attempt that failed to push it.
+        if !git::tag_exists(tag_name, opts.for_reads())? {
+            git::create_tag(tag_name, &pending.message, &commit_hash, opts)?;
+        }
+        if config.automatic_tags.push_all_tags {
             git::push_tags(opts)?;
-            println!(
-                "{}",
-                format!("Success! Created and pushed tag '{}'", tag_name).green()
-            );
+        } else {
+            git::push_tag(tag_name, opts)?;
         }
+        println!(
+            "{}",
+            format!("Success! Created and pushed tag '{}'", tag_name).green()
+        );
     }
     Ok(())
 }
@@ -387,6 +1498,158 @@ pub fn handle_commit(opts: RunOpts, config: &Config, params: CommitParams) -> Re
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn body_templates_fill_answered_lines_and_seed_the_editor() {
+        let mut config = Config::default();
+        config
+            .commit
+            .templates
+            .insert("fix".to_string(), "Root cause:\nImpact:\n".to_string());
+        let template = body_template(&config, "fix").unwrap();
+        assert_eq!(template, "Root cause:\nImpact:");
+        assert!(body_template(&config, "feat").is_none());
+
+        let answers = [
+            "Retry loop reused a closed socket".to_string(),
+            "".to_string(),
+        ];
+        assert_eq!(
+            fill_body_template(template, &answers).as_deref(),
+            Some("Root cause: Retry loop reused a closed socket")
+        );
+        assert!(fill_body_template(template, &["".to_string(), " ".to_string()]).is_none());
+
+        let mut params = parse_commit_message("fix(api): handle timeouts").unwrap();
+        params.issue = Some("PROJ-7".to_string());
+        let text = edit_message_text(&params, Some(template));
+        assert!(
+            text.starts_with("fix(api): handle timeouts\n\nRoot cause:\nImpact:\n\nRefs: PROJ-7")
+        );
+        let saved = parse_commit_message(&strip_unanswered_template(
+            &strip_message_comments(&text),
+            template,
+        ))
+        .unwrap();
+        assert_eq!(saved.body, None);
+        assert_eq!(saved.issue.as_deref(), Some("PROJ-7"));
+
+        let answered = text.replace("Root cause:\n", "Root cause: stale socket\n");
+        let saved = parse_commit_message(&strip_unanswered_template(
+            &strip_message_comments(&answered),
+            template,
+        ))
+        .unwrap();
+        assert_eq!(saved.body.as_deref(), Some("Root cause: stale socket"));
+    }
+
+    #[test]
+    fn commit_message_puts_section_between_body_and_footers() {
+        let mut params = parse_commit_message("feat(api)!: drop v1").unwrap();
+        params.body = Some("Clients moved to v2.".to_string());
+        params.breaking_description = Some("v1 endpoints are gone".to_string());
+        params.issue = Some("PROJ-9".to_string());
+        let message = build_commit_message(
+            &params,
+            Some("Intent Log:\n- checked clients"),
+            &["Co-authored-by: Ada <ada@x.io>".to_string()],
+        );
+        assert_eq!(
+            message,
+            "feat(api)!: drop v1\n\nClients moved to v2.\n\nIntent Log:\n- checked clients\n\n\
+             BREAKING CHANGE: v1 endpoints are gone\n\nRefs: PROJ-9\n\nCo-authored-by: Ada <ada@x.io>"
+        );
+    }
+
+    #[test]
+    fn release_branches_are_ordered_by_version() {
+        let names: Vec<String> = [
+            "main",
+            "feat/x",
+            "release_1.10.0",
+            "release_1.9.2",
+            "release_main",
+        ]
+        .iter()
+        .map(|n| n.to_string())
+        .collect();
+        assert_eq!(
+            release_branches(&names, "release_", "release_main"),
+            vec!["release_1.9.2", "release_1.10.0"]
+        );
+        assert!(release_branches(&names, "", "main").is_empty());
+    }
+
+    #[test]
+    fn forgotten_files_share_a_directory_or_the_scope() {
+        let files = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        let committed = files(&["src/auth/login.rs"]);
+        let changed = files(&[
+            "src/auth/session.rs",
+            "src/api/auth.rs",
+            "docs/auth/overview.md",
+            "src/api/users.rs",
+            "README.md",
+        ]);
+        assert_eq!(
+            forgotten_files(&committed, &changed, Some("auth")),
+            files(&[
+                "docs/auth/overview.md",
+                "src/api/auth.rs",
+                "src/auth/session.rs"
+            ])
+        );
+        assert_eq!(
+            forgotten_files(&committed, &changed, None),
+            files(&["src/auth/session.rs"])
+        );
+    }
+
+    #[test]
+    fn version_trailer_is_added_once_when_enabled() {
+        let mut config = Config::default();
+        let mut trailers = Vec::new();
+        push_version_trailer(&config, &mut trailers);
+        assert!(trailers.is_empty());
+
+        config.commit.version_trailer = true;
+        push_version_trailer(&config, &mut trailers);
+        push_version_trailer(&config, &mut trailers);
+        assert_eq!(
+            trailers,
+            vec![format!("Tbdflow-Version: {}", env!("CARGO_PKG_VERSION"))]
+        );
+    }
+
+    #[test]
+    fn verify_commit_msg_ignores_comments_scissors_and_git_made_messages() {
+        let config = Config::default();
+        let edited = "feat: add cache\n\n# Please enter the commit message\n\
+                      # ------------------------ >8 ------------------------\n\
+                      diff --git a/x b/x\nNot A Conventional Line\n";
+        assert_eq!(strip_message_comments(edited), "feat: add cache");
+        assert!(
+            verify_commit_msg(edited, &config, "main")
+                .unwrap()
+                .is_empty()
+        );
+
+        for message in [
+            "Merge branch 'main' into feat/x",
+            "Revert \"feat: add cache\"",
+            "fixup! feat: add cache",
+            "# only a comment\n",
+        ] {
+            assert!(
+                verify_commit_msg(message, &config, "main")
+                    .unwrap()
+                    .is_empty()
+            );
+        }
+
+        let violations = verify_commit_msg("quick fix\n", &config, "main").unwrap();
+        assert_eq!(violations[0].rule, "conventional_format");
+    }
     use crate::config::*;
 
     fn config_with_defaults() -> Config {
@@ -625,6 +1888,106 @@ mod tests {
         assert!(is_valid_issue_key(&Some("PROJ-1".to_string()), &config).is_err());
     }
 
+    #[test]
+    fn parse_commit_message_reads_header_body_and_trailers() {
+        let text = "feat(api)!: add user endpoint\n\nExposes users over REST.\n\nBREAKING CHANGE: drops v1 routes\nRefs: PROJ-42\nCo-authored-by: Ada <ada@example.com>";
+        let params = parse_commit_message(text).unwrap();
+        assert_eq!(params.r#type, "feat");
+        assert_eq!(params.scope.as_deref(), Some("api"));
+        assert_eq!(params.message, "add user endpoint");
+        assert_eq!(params.body.as_deref(), Some("Exposes users over REST."));
+        assert!(params.breaking);
+        assert_eq!(
+            params.breaking_description.as_deref(),
+            Some("drops v1 routes")
+        );
+        assert_eq!(params.issue.as_deref(), Some("PROJ-42"));
+        assert_eq!(
+            params.trailers,
+            vec!["Co-authored-by: Ada <ada@example.com>"]
+        );
+    }
+
+    #[test]
+    fn parse_commit_message_strips_comment_lines() {
+        let params = parse_commit_message("# comment\nfix: handle empty input\n# another").unwrap();
+        assert_eq!(params.r#type, "fix");
+        assert_eq!(params.message, "handle empty input");
+        assert!(params.body.is_none());
+    }
+
+    #[test]
+    fn parse_commit_message_rejects_non_conventional_text() {
+        assert!(parse_commit_message("Update stuff").is_err());
+    }
+
+    #[test]
+    fn lint_collects_every_violation() {
+        let config = config_with_defaults();
+        let params = parse_commit_message("yolo(API): Add thing.").unwrap();
+        let rules: Vec<&str> = lint_commit_params(&params, &config)
+            .unwrap()
+            .iter()
+            .map(|v| v.rule)
+            .collect();
+        assert_eq!(
+            rules,
+            vec!["conventional_commit_type", "scope", "subject_line_rules"]
+        );
+    }
+
+    #[test]
+    fn fix_subject_strips_the_period_and_lowercases_but_keeps_acronyms() {
+        let config = config_with_defaults();
+        assert_eq!(
+            fix_subject("Add user endpoint.", &config).as_deref(),
+            Some("add user endpoint")
+        );
+        assert_eq!(
+            fix_subject("API keys rotate daily.", &config).as_deref(),
+            Some("API keys rotate daily")
+        );
+        assert_eq!(fix_subject("add user endpoint", &config), None);
+        assert_eq!(
+            fix_subject("Add user endpoint.", &config_without_lint()),
+            None
+        );
+    }
+
+    #[test]
+    fn violations_are_located_and_fixed_in_the_raw_message() {
+        let config = config_with_defaults();
+        let text = "# Please enter the commit message\nfeat(UI): Align button.\n\nRefs: #12\n";
+        let params = parse_commit_message(text).unwrap();
+        let violations = lint_commit_params(&params, &config).unwrap();
+        let located: Vec<(&str, (usize, usize))> = violations
+            .iter()
+            .map(|v| (v.field, locate(text, v)))
+            .collect();
+        assert_eq!(located, vec![("scope", (2, 6)), ("subject", (2, 11))]);
+        assert_eq!(
+            violation_details(&violations[1]),
+            "field=subject text=\"Align button.\" fix=\"align button\""
+        );
+        assert_eq!(
+            fix_message_text(text, &violations),
+            "# Please enter the commit message\nfeat(ui): align button\n\nRefs: #12\n"
+        );
+
+        let mut params = params;
+        assert_eq!(apply_fixes(&mut params, &config).unwrap().len(), 2);
+        assert_eq!(params.scope.as_deref(), Some("ui"));
+        assert_eq!(params.message, "align button");
+        assert!(lint_commit_params(&params, &config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn lint_passes_valid_message() {
+        let config = config_with_defaults();
+        let params = parse_commit_message("feat(api): add user endpoint").unwrap();
+        assert!(lint_commit_params(&params, &config).unwrap().is_empty());
+    }
+
     #[test]
     fn todo_footer_empty_when_all_checked() {
         let checklist = vec!["item1".to_string(), "item2".to_string()];
@@ -652,4 +2015,20 @@ mod tests {
         assert!(footer.contains("- [ ] b"));
         assert!(footer.starts_with("\n\nTODO:\n"));
     }
+
+    #[test]
+    fn evidence_footer_records_each_item() {
+        assert_eq!(build_evidence_footer(&[]), "");
+        let footer = build_evidence_footer(&[
+            (
+                "Tests pass".to_string(),
+                "https://ci.example.com/run/42".to_string(),
+            ),
+            ("Ticket updated".to_string(), "PROJ-7".to_string()),
+        ]);
+        assert_eq!(
+            footer,
+            "\n\nDoD-Evidence: https://ci.example.com/run/42 (Tests pass)\nDoD-Evidence: PROJ-7 (Ticket updated)"
+        );
+    }
 }
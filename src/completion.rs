@@ -0,0 +1,290 @@
+use crate::cli::Cli;
+use crate::git::RunOpts;
+use crate::prompt;
+use anyhow::{Context, Result};
+use clap::CommandFactory;
+use clap_complete::Shell;
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Where `completion install` puts things for one shell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstallPlan {
+    pub shell: Shell,
+    /// File the completion script is written to, for shells that load completions from files.
+    pub script_path: Option<PathBuf>,
+    /// A line that loads the completions, and the rc file it belongs in.
+    pub rc_line: Option<(PathBuf, String)>,
+}
+
+/// Prints the completion script for `shell` to stdout.
+pub fn print_completion(shell: Shell) {
+    let mut cmd = Cli::command();
+    let bin_name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, bin_name, &mut std::io::stdout());
+}
+
+fn completion_script(shell: Shell) -> Vec<u8> {
+    let mut cmd = Cli::command();
+    let bin_name = cmd.get_name().to_string();
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut cmd, bin_name, &mut script);
+    script
+}
+
+/// Works out where the completions for `shell` go, given the home directory
+/// and a lookup for environment variables such as `XDG_DATA_HOME`.
+pub fn install_plan(
+    shell: Shell,
+    home: &Path,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<InstallPlan> {
+    let xdg = |var: &str, fallback: &str| {
+        env(var)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join(fallback))
+    };
+    let (script_path, rc_line) = match shell {
+        Shell::Bash => (
+            Some(xdg("XDG_DATA_HOME", ".local/share").join("bash-completion/completions/tbdflow")),
+            None,
+        ),
+        Shell::Zsh => {
+            let zdotdir = env("ZDOTDIR")
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from)
+                .unwrap_or_else(|| home.to_path_buf());
+            (
+                Some(home.join(".zfunc/_tbdflow")),
+                Some((
+                    zdotdir.join(".zshrc"),
+                    "fpath=(~/.zfunc $fpath); autoload -Uz compinit && compinit".to_string(),
+                )),
+            )
+        }
+        Shell::Fish => (
+            Some(xdg("XDG_CONFIG_HOME", ".config").join("fish/completions/tbdflow.fish")),
+            None,
+        ),
+        Shell::PowerShell => {
+            let profile = if cfg!(windows) {
+                home.join("Documents/PowerShell/Microsoft.PowerShell_profile.ps1")
+            } else {
+                xdg("XDG_CONFIG_HOME", ".config")
+                    .join("powershell/Microsoft.PowerShell_profile.ps1")
+            };
+            (
+                None,
+                Some((
+                    profile,
+                    "tbdflow completion powershell | Out-String | Invoke-Expression".to_string(),
+                )),
+            )
+        }
+        other => anyhow::bail!(
+            "'completion install' does not support {}; load 'tbdflow completion {}' from your shell's config instead.",
+            other,
+            other
+        ),
+    };
+    Ok(InstallPlan {
+        shell,
+        script_path,
+        rc_line,
+    })
+}
+
+/// Asks the shell itself to load the installed script. `None` when the shell
+/// cannot be run here, so nothing could be checked.
+fn verify(shell: Shell, script: &Path) -> Option<Result<(), String>> {
+    let script = script.to_string_lossy().to_string();
+    let (program, args): (&str, Vec<String>) = match shell {
+        Shell::Bash => (
+            "bash",
+            vec![
+                "-c".to_string(),
+                "source \"$1\" && complete -p tbdflow".to_string(),
+                "verify".to_string(),
+                script,
+            ],
+        ),
+        Shell::Zsh => ("zsh", vec!["-n".to_string(), script]),
+        Shell::Fish => ("fish", vec!["--no-execute".to_string(), script]),
+        _ => return None,
+    };
+    let output = Command::new(program)
+        .args(&args)
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+    Some(if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    })
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// Installs completions for `shell` (detected from `$SHELL` when omitted).
+pub fn handle_completion_install(shell: Option<Shell>, opts: RunOpts) -> Result<()> {
+    let shell = shell.or_else(Shell::from_env).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Could not detect your shell from $SHELL. Name it, e.g. 'tbdflow completion install zsh'."
+        )
+    })?;
+    let home = home_dir().context("Could not find your home directory (HOME is not set)")?;
+    let plan = install_plan(shell, &home, |var| std::env::var(var).ok())?;
+
+    println!(
+        "{}",
+        format!("--- Installing {} completions ---", shell).blue()
+    );
+
+    if let Some(path) = &plan.script_path {
+        if opts.dry_run {
+            println!(
+                "{}",
+                format!("[DRY RUN] Would write completions to {}", path.display()).yellow()
+            );
+        } else {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)
+                    .with_context(|| format!("Failed to create {}", dir.display()))?;
+            }
+            fs::write(path, completion_script(shell))
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!(
+                "{}",
+                format!("Wrote completions to {}", path.display()).green()
+            );
+        }
+    }
+
+    if let Some((rc, line)) = &plan.rc_line {
+        let existing = fs::read_to_string(rc).unwrap_or_default();
+        if existing.lines().any(|l| l.trim() == line) {
+            println!(
+                "{}",
+                format!("{} already loads them.", rc.display()).dimmed()
+            );
+        } else if opts.dry_run {
+            println!(
+                "{}",
+                format!(
+                    "[DRY RUN] Would offer to add '{}' to {}",
+                    line,
+                    rc.display()
+                )
+                .yellow()
+            );
+        } else if prompt::confirm(
+            &format!("Add '{}' to {}?", line, rc.display()),
+            Some(false),
+            opts,
+        )? {
+            if let Some(dir) = rc.parent() {
+                fs::create_dir_all(dir)
+                    .with_context(|| format!("Failed to create {}", dir.display()))?;
+            }
+            let separator = if existing.is_empty() || existing.ends_with('\n') {
+                ""
+            } else {
+                "\n"
+            };
+            fs::write(
+                rc,
+                format!(
+                    "{}{}\n# tbdflow completions\n{}\n",
+                    existing, separator, line
+                ),
+            )
+            .with_context(|| format!("Failed to write {}", rc.display()))?;
+            println!("{}", format!("Added to {}", rc.display()).green());
+        } else {
+            println!(
+                "{}",
+                format!(
+                    "Left {} alone. To load completions, add:\n  {}",
+                    rc.display(),
+                    line
+                )
+                .yellow()
+            );
+        }
+    }
+
+    if let Some(path) = plan.script_path.as_deref().filter(|_| !opts.dry_run) {
+        match verify(shell, path) {
+            Some(Ok(())) => println!(
+                "{}",
+                format!("Verified: {} loads the completions.", shell).green()
+            ),
+            Some(Err(error)) => println!(
+                "{}",
+                format!(
+                    "Warning: {} could not load {}: {}",
+                    shell,
+                    path.display(),
+                    error
+                )
+                .yellow()
+            ),
+            None => println!(
+                "{}",
+                format!("Could not verify: {} is not available here.", shell).dimmed()
+            ),
+        }
+    }
+    println!("{}", "Open a new shell to start using them.".dimmed());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plans_follow_each_shells_conventions() {
+        let home = Path::new("/home/ada");
+        let no_env = |_: &str| None;
+
+        let bash = install_plan(Shell::Bash, home, no_env).unwrap();
+        assert_eq!(
+            bash.script_path.unwrap(),
+            home.join(".local/share/bash-completion/completions/tbdflow")
+        );
+        assert!(bash.rc_line.is_none());
+
+        let zsh = install_plan(Shell::Zsh, home, no_env).unwrap();
+        assert_eq!(zsh.script_path.unwrap(), home.join(".zfunc/_tbdflow"));
+        assert_eq!(zsh.rc_line.unwrap().0, home.join(".zshrc"));
+
+        let fish = install_plan(Shell::Fish, home, |var| {
+            (var == "XDG_CONFIG_HOME").then(|| "/cfg".to_string())
+        })
+        .unwrap();
+        assert_eq!(
+            fish.script_path.unwrap(),
+            PathBuf::from("/cfg/fish/completions/tbdflow.fish")
+        );
+
+        let pwsh = install_plan(Shell::PowerShell, home, no_env).unwrap();
+        assert!(pwsh.script_path.is_none());
+        assert!(
+            pwsh.rc_line
+                .unwrap()
+                .1
+                .starts_with("tbdflow completion powershell")
+        );
+
+        assert!(install_plan(Shell::Elvish, home, no_env).is_err());
+    }
+}
@@ -1,17 +1,83 @@
+use crate::command_defaults;
+use crate::extends;
 use crate::git::{self, RunOpts};
 use anyhow::{Context, anyhow};
+use colored::Colorize;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Deserialize, Default)]
 pub struct DodConfig {
     #[serde(default)]
-    pub checklist: Vec<String>,
+    pub checklist: Vec<DodItem>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+/// A `.dod.yml` checklist entry: plain text, or a map that can start the
+/// item ticked (`checked`), ask for evidence when it is ticked (`evidence`),
+/// or be checked by running a command (`command`).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum DodItem {
+    Text(String),
+    Detailed {
+        item: String,
+        #[serde(default)]
+        checked: bool,
+        /// Prompt for the evidence to record, e.g. "Link to the CI run".
+        #[serde(default)]
+        evidence: Option<String>,
+        /// Shell command that checks the item off automatically when it exits 0.
+        #[serde(default)]
+        command: Option<String>,
+    },
+}
+
+impl DodItem {
+    pub fn text(&self) -> &str {
+        match self {
+            DodItem::Text(text) => text,
+            DodItem::Detailed { item, .. } => item,
+        }
+    }
+
+    pub fn checked_by_default(&self) -> bool {
+        matches!(self, DodItem::Detailed { checked: true, .. })
+    }
+
+    pub fn command(&self) -> Option<&str> {
+        match self {
+            DodItem::Detailed {
+                command: Some(command),
+                ..
+            } => Some(command),
+            _ => None,
+        }
+    }
+
+    pub fn evidence_prompt(&self) -> Option<&str> {
+        match self {
+            DodItem::Detailed {
+                evidence: Some(prompt),
+                ..
+            } => Some(prompt),
+            _ => None,
+        }
+    }
+}
+
+impl DodConfig {
+    pub fn item_texts(&self) -> Vec<String> {
+        self.checklist
+            .iter()
+            .map(|i| i.text().to_string())
+            .collect()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, JsonSchema)]
 pub struct MonorepoConfig {
     #[serde(default)]
     pub enabled: bool,
@@ -19,7 +85,7 @@ pub struct MonorepoConfig {
     pub project_dirs: Vec<String>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum ReviewStrategy {
     /// Create GitHub issues for review tracking (requires `gh` CLI).
@@ -29,10 +95,68 @@ pub enum ReviewStrategy {
     GithubWorkflow,
     /// Log reviews locally without external integration.
     LogOnly,
+    /// Create issues and commit statuses on a Gitea or Forgejo server.
+    Gitea,
+    /// Create issues and build statuses on Bitbucket Cloud.
+    Bitbucket,
+    /// Keep review state in git notes pushed alongside the repository.
+    GitNotes,
+    /// Commit review records to trunk under `.tbdflow/reviews/`.
+    InRepo,
+}
+
+impl ReviewStrategy {
+    /// Every strategy this build supports.
+    pub const ALL: [ReviewStrategy; 7] = [
+        ReviewStrategy::GithubIssue,
+        ReviewStrategy::GithubWorkflow,
+        ReviewStrategy::LogOnly,
+        ReviewStrategy::Gitea,
+        ReviewStrategy::Bitbucket,
+        ReviewStrategy::GitNotes,
+        ReviewStrategy::InRepo,
+    ];
+}
+
+/// Where the `gitea` review strategy finds the Gitea or Forgejo server.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct GiteaConfig {
+    /// Base URL, e.g. "https://git.example.com"; defaults to the host of `origin`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// API token, used when GITEA_TOKEN and FORGEJO_TOKEN are unset. Keep it
+    /// out of the shared `.tbdflow.yml`: put it in `.tbdflow.local.yml` or the user config.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+/// App-password credentials for the `bitbucket` review strategy.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct BitbucketConfig {
+    /// Bitbucket username, used when BITBUCKET_USERNAME is unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// App password with issue and repository write access, used when
+    /// BITBUCKET_APP_PASSWORD is unset. Keep it out of the shared `.tbdflow.yml`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_password: Option<String>,
+}
+
+/// What `tbdflow complete` puts up for review after merging a branch.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompleteReview {
+    /// No review; only direct commits to main are reviewed.
+    #[default]
+    Off,
+    /// Review the merge commit as a whole.
+    MergeCommit,
+    /// One batch review listing each commit from the branch.
+    Batch,
 }
 
 /// Maps file glob patterns to specific reviewers.
-#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, JsonSchema)]
 pub struct ReviewRule {
     /// e.g. "src/auth/**", "infra/*.tf"
     pub pattern: String,
@@ -41,7 +165,7 @@ pub struct ReviewRule {
     pub reviewers: Option<Vec<String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct ReviewLabelsConfig {
     #[serde(default = "ReviewLabelsConfig::default_pending")]
     pub pending: String,
@@ -51,6 +175,22 @@ pub struct ReviewLabelsConfig {
     pub accepted: String,
     #[serde(default = "ReviewLabelsConfig::default_dismissed")]
     pub dismissed: String,
+    /// Added alongside `concern` for `review --concern --severity minor`.
+    #[serde(default = "ReviewLabelsConfig::default_minor")]
+    pub minor: String,
+    #[serde(default = "ReviewLabelsConfig::default_major")]
+    pub major: String,
+    #[serde(default = "ReviewLabelsConfig::default_critical")]
+    pub critical: String,
+    /// Added by `review sweep` to reviews pending past `sla_hours`.
+    #[serde(default = "ReviewLabelsConfig::default_escalated")]
+    pub escalated: String,
+    /// Hex colors keyed by label role (`pending`, `concern`, ...), e.g. "FBCA04".
+    #[serde(default)]
+    pub colors: HashMap<String, String>,
+    /// Descriptions keyed by label role; roles not listed keep the built-in text.
+    #[serde(default)]
+    pub descriptions: HashMap<String, String>,
 }
 
 impl Default for ReviewLabelsConfig {
@@ -60,6 +200,12 @@ impl Default for ReviewLabelsConfig {
             concern: Self::default_concern(),
             accepted: Self::default_accepted(),
             dismissed: Self::default_dismissed(),
+            minor: Self::default_minor(),
+            major: Self::default_major(),
+            critical: Self::default_critical(),
+            escalated: Self::default_escalated(),
+            colors: HashMap::new(),
+            descriptions: HashMap::new(),
         }
     }
 }
@@ -77,9 +223,68 @@ impl ReviewLabelsConfig {
     fn default_dismissed() -> String {
         "review-dismissed".to_string()
     }
+    fn default_minor() -> String {
+        "concern-minor".to_string()
+    }
+    fn default_major() -> String {
+        "concern-major".to_string()
+    }
+    fn default_critical() -> String {
+        "concern-critical".to_string()
+    }
+    fn default_escalated() -> String {
+        "review-escalated".to_string()
+    }
+}
+
+/// The commit status set when a concern is raised. Description templates and
+/// `target_url` support `{{message}}`, `{{severity}}`, `{{sha}}`, `{{short_sha}}`
+/// and `{{issue_url}}`.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct ReviewStatusConfig {
+    /// Status check name, as referenced by branch protection rules.
+    #[serde(default = "ReviewStatusConfig::default_context")]
+    pub context: String,
+    #[serde(default = "ReviewStatusConfig::default_pending_description")]
+    pub pending_description: String,
+    /// Used when `concern_blocks_status` is true.
+    #[serde(default = "ReviewStatusConfig::default_failure_description")]
+    pub failure_description: String,
+    #[serde(default = "ReviewStatusConfig::default_critical_description")]
+    pub critical_description: String,
+    /// Link shown next to the status; defaults to the review issue.
+    #[serde(default)]
+    pub target_url: Option<String>,
+}
+
+impl Default for ReviewStatusConfig {
+    fn default() -> Self {
+        Self {
+            context: Self::default_context(),
+            pending_description: Self::default_pending_description(),
+            failure_description: Self::default_failure_description(),
+            critical_description: Self::default_critical_description(),
+            target_url: None,
+        }
+    }
+}
+
+impl ReviewStatusConfig {
+    fn default_context() -> String {
+        "peer-review".to_string()
+    }
+    fn default_pending_description() -> String {
+        "Awaiting fix-forward for concern: {{message}}".to_string()
+    }
+    fn default_failure_description() -> String {
+        "Audit Concern: {{message}}".to_string()
+    }
+    fn default_critical_description() -> String {
+        "Critical Concern: {{message}}".to_string()
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum RadarLevel {
     #[default]
@@ -87,7 +292,7 @@ pub enum RadarLevel {
     Line,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum RadarOnCommit {
     #[default]
@@ -96,7 +301,7 @@ pub enum RadarOnCommit {
     Confirm,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct RadarConfig {
     #[serde(default)]
     pub enabled: bool,
@@ -133,23 +338,239 @@ impl RadarConfig {
 }
 
 /// Pre-flight CI status check via `gh` CLI during `tbdflow sync`.
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
 pub struct CiCheckConfig {
     #[serde(default)]
     pub enabled: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+/// Section headers used by `tbdflow changelog`.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct ChangelogConfig {
+    /// Commit type to section header, merged over the built-in mapping.
+    /// An empty header leaves that type out of the changelog.
+    #[serde(default)]
+    pub sections: HashMap<String, String>,
+    /// Section headers in the order they should appear. Unlisted sections follow.
+    #[serde(default)]
+    pub order: Vec<String>,
+    #[serde(default = "ChangelogConfig::default_breaking_header")]
+    pub breaking_header: String,
+    /// Header for commit types with no section of their own.
+    #[serde(default = "ChangelogConfig::default_other_header")]
+    pub other_header: String,
+    /// Header listing commits reverted within the same range. Empty leaves
+    /// the commit and its revert out of the changelog.
+    #[serde(default = "ChangelogConfig::default_reverted_header")]
+    pub reverted_header: String,
+    /// Let `--unreleased` start from the latest release tag, passing over
+    /// pre-release tags such as `v2.0.0-rc.1`.
+    #[serde(default)]
+    pub skip_prereleases: bool,
+}
+
+impl Default for ChangelogConfig {
+    fn default() -> Self {
+        Self {
+            sections: HashMap::new(),
+            order: Vec::new(),
+            breaking_header: Self::default_breaking_header(),
+            other_header: Self::default_other_header(),
+            reverted_header: Self::default_reverted_header(),
+            skip_prereleases: false,
+        }
+    }
+}
+
+impl ChangelogConfig {
+    fn default_breaking_header() -> String {
+        "### ⚠️ BREAKING CHANGES".to_string()
+    }
+
+    fn default_other_header() -> String {
+        "### Miscellaneous".to_string()
+    }
+
+    fn default_reverted_header() -> String {
+        "### ⏪ Reverted".to_string()
+    }
+}
+
+/// Settings for the git subprocesses tbdflow runs.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct GitConfig {
+    /// Fail any git command that runs longer than this. No limit when unset.
+    #[serde(default)]
+    pub timeout_seconds: Option<u64>,
+    /// Where branch, HEAD and status queries run. See `git_backend`.
+    #[serde(default)]
+    pub backend: GitBackendKind,
+}
+
+/// `git.backend`: the `git` binary, or gitoxide in-process (needs the `gix` feature).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum GitBackendKind {
+    #[default]
+    Subprocess,
+    Gix,
+}
+
+/// Defaults for `tbdflow commit`.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct CommitConfig {
+    /// Show a diffstat and ask for confirmation before every commit.
+    #[serde(default)]
+    pub preview: bool,
+    /// Run the `.dod.yml` checklist before committing. Profiles can turn it off.
+    #[serde(default = "CommitConfig::default_dod")]
+    pub dod: bool,
+    /// Add a `Tbdflow-Version:` trailer, so `audit` can tell tool-made commits apart.
+    #[serde(default)]
+    pub version_trailer: bool,
+    /// What to do about commits to main while a release branch exists.
+    #[serde(default)]
+    pub release_stabilization: ReleaseStabilizationConfig,
+    /// Body scaffolds by commit type, e.g. `fix: "Root cause:\nImpact:"`. The
+    /// wizard asks for each line and `commit --edit` starts from it.
+    #[serde(default)]
+    pub templates: BTreeMap<String, String>,
+}
+
+impl CommitConfig {
+    fn default_dod() -> bool {
+        true
+    }
+}
+
+impl Default for CommitConfig {
+    fn default() -> Self {
+        CommitConfig {
+            preview: false,
+            dod: Self::default_dod(),
+            version_trailer: false,
+            release_stabilization: ReleaseStabilizationConfig::default(),
+            templates: BTreeMap::new(),
+        }
+    }
+}
+
+/// How `tbdflow commit` treats a commit to main while a release branch is
+/// being stabilised.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum StabilizationPolicy {
+    #[default]
+    Off,
+    /// Warn, then commit to main as usual.
+    Warn,
+    /// Warn, and offer to commit fixes to the release branch instead.
+    Offer,
+}
+
+/// Commits to main that a release branch's stabilisation window should hear about.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct ReleaseStabilizationConfig {
+    #[serde(default)]
+    pub policy: StabilizationPolicy,
+    /// Commit types kept out of the release: warned about, committed to main.
+    #[serde(default = "ReleaseStabilizationConfig::default_types")]
+    pub types: Vec<String>,
+    /// Commit types the release may need: `offer` asks to commit them to the
+    /// release branch instead.
+    #[serde(default = "ReleaseStabilizationConfig::default_fix_types")]
+    pub fix_types: Vec<String>,
+}
+
+impl ReleaseStabilizationConfig {
+    fn default_types() -> Vec<String> {
+        vec!["feat".to_string()]
+    }
+
+    fn default_fix_types() -> Vec<String> {
+        vec!["fix".to_string()]
+    }
+}
+
+impl Default for ReleaseStabilizationConfig {
+    fn default() -> Self {
+        ReleaseStabilizationConfig {
+            policy: StabilizationPolicy::Off,
+            types: Self::default_types(),
+            fix_types: Self::default_fix_types(),
+        }
+    }
+}
+
+/// When `tbdflow branch` pulls main before creating the branch.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum AutoSync {
+    #[default]
+    Always,
+    /// Pull only if origin can be fetched; otherwise branch from local main.
+    IfOnline,
+    Never,
+}
+
+/// Defaults for `tbdflow branch`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct BranchConfig {
+    #[serde(default)]
+    pub auto_sync: AutoSync,
+}
+
+/// Settings for `tbdflow complete`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct CompleteConfig {
+    /// Template for the merge commit message. Supports `{{branch}}`, `{{type}}`,
+    /// `{{name}}`, `{{issue}}`, `{{description}}`, `{{subjects}}`,
+    /// `{{commit_count}}` and `{{dod}}`. Unset keeps git's default subject.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge_message: Option<String>,
+}
+
+/// What `tbdflow switch` does with uncommitted changes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum UncommittedPolicy {
+    /// Stop and ask the user to commit or stash first.
+    #[default]
+    Refuse,
+    /// Park the changes in a stash and restore them on switching back.
+    Stash,
+}
+
+/// Defaults for `tbdflow switch`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct SwitchConfig {
+    #[serde(default)]
+    pub uncommitted: UncommittedPolicy,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct ReviewConfig {
     #[serde(default)]
     pub enabled: bool,
     #[serde(default)]
     pub default_reviewers: Vec<String>,
+    /// @-mention the assigned reviewers in the review issue body as well.
+    #[serde(default)]
+    pub mention_reviewers: bool,
+    /// Team slugs (e.g. "my-org/backend") @-mentioned in every review issue.
+    #[serde(default)]
+    pub mention_teams: Vec<String>,
     #[serde(default)]
     pub strategy: ReviewStrategy,
     /// Workflow filename for `github-workflow` strategy (e.g. "nbr-review.yml").
     #[serde(default)]
     pub workflow: Option<String>,
+    /// Server and token for the `gitea` strategy.
+    #[serde(default)]
+    pub gitea: GiteaConfig,
+    /// Credentials for the `bitbucket` strategy.
+    #[serde(default)]
+    pub bitbucket: BitbucketConfig,
     #[serde(default)]
     pub rules: Vec<ReviewRule>,
     #[serde(default)]
@@ -157,16 +578,87 @@ pub struct ReviewConfig {
     /// If true, a concern sets commit status to 'failure' instead of 'pending'.
     #[serde(default)]
     pub concern_blocks_status: bool,
+    #[serde(default)]
+    pub status: ReviewStatusConfig,
+    /// Commits changing more lines than this always trigger a review. Off (0) unless set.
+    #[serde(default)]
+    pub large_commit_lines: usize,
+    /// Commit types that may opt out of auto-triggered review with `Review: skip`.
+    #[serde(default = "ReviewConfig::default_exempt_types")]
+    pub exempt_types: Vec<String>,
+    /// `review sweep` reports pending reviews older than this as stale.
+    #[serde(default = "ReviewConfig::default_stale_after_hours")]
+    pub stale_after_hours: u64,
+    /// `review sweep` escalates pending reviews older than this.
+    #[serde(default = "ReviewConfig::default_sla_hours")]
+    pub sla_hours: u64,
+    /// Pinged when a review is escalated (users or teams, e.g. "my-org/leads").
+    #[serde(default)]
+    pub fallback_reviewers: Vec<String>,
+    /// How long `tbdflow status` reuses the review counts it fetched from GitHub.
+    #[serde(default = "ReviewConfig::default_status_cache_minutes")]
+    pub status_cache_minutes: u64,
+    /// Review to trigger when `tbdflow complete` merges a branch into main.
+    #[serde(default)]
+    pub on_complete: CompleteReview,
+    /// Distinct approvals needed before `review --approve` closes the review.
+    #[serde(default = "ReviewConfig::default_required_approvals")]
+    pub required_approvals: usize,
+}
+
+impl Default for ReviewConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            default_reviewers: Vec::new(),
+            mention_reviewers: false,
+            mention_teams: Vec::new(),
+            strategy: ReviewStrategy::default(),
+            workflow: None,
+            gitea: GiteaConfig::default(),
+            bitbucket: BitbucketConfig::default(),
+            rules: Vec::new(),
+            labels: ReviewLabelsConfig::default(),
+            concern_blocks_status: false,
+            status: ReviewStatusConfig::default(),
+            large_commit_lines: 0,
+            exempt_types: Self::default_exempt_types(),
+            stale_after_hours: Self::default_stale_after_hours(),
+            sla_hours: Self::default_sla_hours(),
+            fallback_reviewers: Vec::new(),
+            status_cache_minutes: Self::default_status_cache_minutes(),
+            on_complete: CompleteReview::default(),
+            required_approvals: Self::default_required_approvals(),
+        }
+    }
+}
+
+impl ReviewConfig {
+    fn default_exempt_types() -> Vec<String> {
+        vec!["docs".to_string(), "style".to_string(), "chore".to_string()]
+    }
+    fn default_stale_after_hours() -> u64 {
+        24
+    }
+    fn default_sla_hours() -> u64 {
+        72
+    }
+    fn default_status_cache_minutes() -> u64 {
+        10
+    }
+    fn default_required_approvals() -> usize {
+        1
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum IssueHandlingStrategy {
     BranchName,
     CommitScope,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct IssueHandling {
     pub strategy: IssueHandlingStrategy,
 }
@@ -179,53 +671,74 @@ impl Default for IssueHandling {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// How the latest tag is picked for `changelog --unreleased` and `info`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum TagSort {
+    /// The highest semver tag with the release prefix reachable from HEAD.
+    #[default]
+    Version,
+    /// The tag nearest to HEAD in history (`git describe`), whatever its name.
+    Nearest,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct AutomaticTags {
     pub release_prefix: String,
+    /// Push every local tag (`git push --tags`) instead of only the tag just created.
+    #[serde(default)]
+    pub push_all_tags: bool,
+    #[serde(default)]
+    pub tag_sort: TagSort,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct ConventionalCommitTypeConfig {
     pub enabled: Option<bool>,
     pub allowed_types: Option<Vec<String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct IssueKeyConfig {
     pub enabled: Option<bool>,
     pub pattern: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct ScopeConfig {
     pub enabled: Option<bool>,
     pub enforce_lowercase: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct SubjectLineRules {
     pub max_length: Option<usize>,
     pub enforce_lowercase: Option<bool>,
     pub no_period: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct BodyLineRules {
     pub max_line_length: Option<usize>,
     pub leading_blank: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct LintConfig {
     pub conventional_commit_type: Option<ConventionalCommitTypeConfig>,
     pub issue_key_missing: Option<IssueKeyConfig>,
     pub scope: Option<ScopeConfig>,
     pub subject_line_rules: Option<SubjectLineRules>,
     pub body_line_rules: Option<BodyLineRules>,
+    /// Partial `lint` overrides keyed by branch type (or `main` for the trunk),
+    /// merged over the rules above for commits made on that kind of branch.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[schemars(with = "HashMap<String, serde_json::Value>")]
+    pub branches: HashMap<String, yaml_serde::Value>,
 }
 
 /// Loaded from `.tbdflow.yml` at the git root, with optional per-project overrides.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Config {
     pub main_branch_name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -244,15 +757,75 @@ pub struct Config {
     pub radar: RadarConfig,
     #[serde(default)]
     pub ci_check: CiCheckConfig,
+    #[serde(default)]
+    pub commit: CommitConfig,
+    #[serde(default)]
+    pub branch: BranchConfig,
+    #[serde(default)]
+    pub complete: CompleteConfig,
+    #[serde(default)]
+    pub switch: SwitchConfig,
+    #[serde(default)]
+    pub changelog: ChangelogConfig,
+    #[serde(default)]
+    pub git: GitConfig,
+    /// Print plain text instead of emojis and box-drawing characters.
+    #[serde(default)]
+    pub ascii_output: bool,
+    /// Language for user-facing messages (e.g. "sv", or "system" for the
+    /// system locale); TBDFLOW_LANG overrides it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
     pub branch_types: HashMap<String, String>,
     pub automatic_tags: AutomaticTags,
     pub lint: Option<LintConfig>,
+    /// Default flag values per command, e.g. `commit: { no_verify: true }`.
+    /// A flag given on the command line wins.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(with = "BTreeMap<String, BTreeMap<String, serde_json::Value>>")]
+    pub defaults: command_defaults::Defaults,
 }
 
 fn default_log_display_count() -> usize {
     15
 }
 
+impl Config {
+    /// The branch type `branch` belongs to: `main` on the trunk, otherwise the
+    /// `branch_types` key with the longest matching prefix.
+    pub fn branch_type_for(&self, branch: &str) -> Option<String> {
+        if branch == self.main_branch_name {
+            return Some("main".to_string());
+        }
+        self.branch_types
+            .iter()
+            .filter(|(_, prefix)| !prefix.is_empty() && branch.starts_with(prefix.as_str()))
+            .max_by_key(|(_, prefix)| prefix.len())
+            .map(|(name, _)| name.clone())
+    }
+
+    /// The lint rules for commits on `branch`, with the matching
+    /// `lint.branches` override merged over the base rules.
+    pub fn lint_for_branch(&self, branch: &str) -> Result<Option<LintConfig>, anyhow::Error> {
+        let Some(lint) = &self.lint else {
+            return Ok(None);
+        };
+        let Some(branch_type) = self.branch_type_for(branch) else {
+            return Ok(Some(lint.clone()));
+        };
+        let Some(overlay) = lint.branches.get(&branch_type) else {
+            return Ok(Some(lint.clone()));
+        };
+
+        let mut value = yaml_serde::to_value(lint)?;
+        merge_yaml(&mut value, overlay.clone());
+        let mut merged: LintConfig = yaml_serde::from_value(value)
+            .map_err(|e| anyhow!("Invalid lint.branches.{} override: {}", branch_type, e))?;
+        merged.branches = lint.branches.clone();
+        Ok(Some(merged))
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         let mut branch_types = HashMap::new();
@@ -278,9 +851,20 @@ impl Default for Config {
             review: ReviewConfig::default(),
             radar: RadarConfig::default(),
             ci_check: CiCheckConfig::default(),
+            commit: CommitConfig::default(),
+            branch: BranchConfig::default(),
+            complete: CompleteConfig::default(),
+            switch: SwitchConfig::default(),
+            changelog: ChangelogConfig::default(),
+            git: GitConfig::default(),
+            ascii_output: false,
+            locale: None,
+            defaults: BTreeMap::new(),
             branch_types,
             automatic_tags: AutomaticTags {
                 release_prefix: "v".to_string(),
+                push_all_tags: false,
+                tag_sort: TagSort::Version,
             },
             // Add default lint configuration
             lint: Some(LintConfig {
@@ -317,6 +901,7 @@ impl Default for Config {
                     max_line_length: Some(80),
                     leading_blank: Option::from(true),
                 }),
+                branches: HashMap::new(),
             }),
         }
     }
@@ -342,42 +927,347 @@ fn merge_configs(parent: &mut Config, child: Config) {
     // monorepo, automatic_tags
 }
 
-pub fn load_tbdflow_config() -> Result<Config, anyhow::Error> {
-    let git_root = match git::get_git_root(RunOpts::new(false, false)) {
-        Ok(path) => path,
-        Err(_) => {
-            // Not in a git repo, so we can't find the config.
-            // Return default config silently as before.
-            return Ok(Config::default());
+/// Top-level key holding named profiles in `.tbdflow.yml`.
+pub(crate) const PROFILES_KEY: &str = "profiles";
+
+/// Deep-merges `overlay` into `base`: mappings are merged key by key, any
+/// other value (including sequences) replaces the base value.
+fn merge_yaml(base: &mut yaml_serde::Value, overlay: yaml_serde::Value) {
+    match (base, overlay) {
+        (yaml_serde::Value::Mapping(base_map), yaml_serde::Value::Mapping(overlay_map)) => {
+            for (key, value) in overlay_map {
+                match base_map.get_mut(&key) {
+                    Some(existing) => merge_yaml(existing, value),
+                    None => {
+                        base_map.insert(key, value);
+                    }
+                }
+            }
         }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Parses a `.tbdflow.yml`, merging `profiles.<profile>` over the base
+/// settings. Returns the config and the names of the profiles it defines.
+pub fn parse_config_with_profile(
+    content: &str,
+    profile: Option<&str>,
+) -> Result<(Config, Vec<String>), anyhow::Error> {
+    config_from_value(yaml_serde::from_str(content)?, profile)
+}
+
+fn config_from_value(
+    mut value: yaml_serde::Value,
+    profile: Option<&str>,
+) -> Result<(Config, Vec<String>), anyhow::Error> {
+    let profiles = match value.as_mapping_mut() {
+        Some(map) => map.remove(PROFILES_KEY),
+        None => None,
     };
-    // Load base config from git root, or use default.
-    let root_config_path = Path::new(&git_root).join(".tbdflow.yml");
-    let mut base_config = if root_config_path.exists() {
+    let mut profiles = match profiles {
+        Some(yaml_serde::Value::Mapping(map)) => map,
+        Some(yaml_serde::Value::Null) | None => yaml_serde::Mapping::new(),
+        Some(_) => return Err(anyhow!("'{}' must be a map of profile names", PROFILES_KEY)),
+    };
+    let names: Vec<String> = profiles
+        .keys()
+        .filter_map(|k| k.as_str().map(str::to_string))
+        .collect();
+
+    if let Some(overlay) = profile.and_then(|name| profiles.remove(name)) {
+        merge_yaml(&mut value, overlay);
+    }
+
+    Ok((yaml_serde::from_value(value)?, names))
+}
+
+/// Parses the root `.tbdflow.yml` and, when it has `extends:`, merges it over
+/// the shared base config it names.
+fn apply_extends(
+    content: &str,
+    git_root: &Path,
+    opts: RunOpts,
+) -> Result<yaml_serde::Value, anyhow::Error> {
+    let mut value: yaml_serde::Value = yaml_serde::from_str(content)
+        .map_err(|e| anyhow!("Failed to parse root .tbdflow.yml: {}", e))?;
+    let source = match value
+        .as_mapping_mut()
+        .and_then(|map| map.remove(extends::EXTENDS_KEY))
+    {
+        Some(yaml_serde::Value::String(source)) => source,
+        Some(_) => return Err(anyhow!("'{}' must be a path or URL", extends::EXTENDS_KEY)),
+        None => return Ok(value),
+    };
+
+    let opts = opts.for_reads();
+    let git_dir = git::get_git_dir(opts)?;
+    let opts = opts.with_timeout(extends::root_timeout(&value).or(opts.timeout_secs));
+    let base_str = extends::resolve(&source, git_root, Path::new(&git_dir), opts)?;
+    let mut base: yaml_serde::Value = yaml_serde::from_str(&base_str)
+        .map_err(|e| anyhow!("Failed to parse shared config {}: {}", source, e))?;
+    if let Some(map) = base.as_mapping_mut() {
+        // Only one level of `extends` is followed.
+        map.remove(extends::EXTENDS_KEY);
+    }
+    merge_yaml(&mut base, value);
+    Ok(base)
+}
+
+/// The user's own config: `$XDG_CONFIG_HOME/tbdflow/config.yml`, by default
+/// `~/.config/tbdflow/config.yml`.
+pub fn user_config_path() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config_home.join("tbdflow").join("config.yml"))
+}
+
+/// The user's config file as a partial config, or `None` when there is
+/// none. It holds preferences, so `extends:` is not followed.
+fn user_layer() -> Result<Option<yaml_serde::Value>, anyhow::Error> {
+    let Some(path) = user_config_path().filter(|path| path.is_file()) else {
+        return Ok(None);
+    };
+    let mut value: yaml_serde::Value = yaml_serde::from_str(&fs::read_to_string(&path)?)
+        .map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))?;
+    if let Some(map) = value.as_mapping_mut() {
+        map.remove(extends::EXTENDS_KEY);
+    }
+    Ok((!value.is_null()).then_some(value))
+}
+
+/// Puts the user's settings beneath `repo`: keys the repository sets win,
+/// and without a repository config the defaults fill in the rest.
+fn with_user_layer(repo: Option<yaml_serde::Value>) -> Result<yaml_serde::Value, anyhow::Error> {
+    Ok(match (user_layer()?, repo) {
+        (Some(mut user), Some(repo)) => {
+            merge_yaml(&mut user, repo);
+            user
+        }
+        (user, None) => {
+            let mut defaults = yaml_serde::to_value(Config::default())?;
+            if let Some(user) = user {
+                merge_yaml(&mut defaults, user);
+            }
+            defaults
+        }
+        (None, Some(repo)) => repo,
+    })
+}
+
+/// Untracked, per-developer overrides at the git root, merged over everything
+/// in the committed config files.
+pub const LOCAL_CONFIG_FILE: &str = ".tbdflow.local.yml";
+
+/// Merges `.tbdflow.local.yml` from `git_root` over `config`, when it exists.
+/// Like the user config, it cannot use `extends:` or define profiles.
+fn apply_local_overrides(config: Config, git_root: &Path) -> Result<Config, anyhow::Error> {
+    let path = git_root.join(LOCAL_CONFIG_FILE);
+    if !path.is_file() {
+        return Ok(config);
+    }
+    let mut overrides: yaml_serde::Value = yaml_serde::from_str(&fs::read_to_string(&path)?)
+        .map_err(|e| anyhow!("Failed to parse {}: {}", LOCAL_CONFIG_FILE, e))?;
+    if let Some(map) = overrides.as_mapping_mut() {
+        map.remove(extends::EXTENDS_KEY);
+        map.remove(PROFILES_KEY);
+    }
+    if overrides.is_null() {
+        return Ok(config);
+    }
+    let mut merged = yaml_serde::to_value(&config)?;
+    merge_yaml(&mut merged, overrides);
+    yaml_serde::from_value(merged).map_err(|e| anyhow!("Invalid {}: {}", LOCAL_CONFIG_FILE, e))
+}
+
+/// Loads the root `.tbdflow.yml` (with its `extends:` base and `profile`
+/// applied) over the user config, or the defaults when the repository has
+/// none. Also returns the profile names it defines.
+pub fn load_root_config(
+    git_root: &Path,
+    profile: Option<&str>,
+    opts: RunOpts,
+) -> Result<(Config, Vec<String>), anyhow::Error> {
+    let root_config_path = git_root.join(".tbdflow.yml");
+    let repo = if root_config_path.exists() {
         let config_str = fs::read_to_string(root_config_path)?;
-        yaml_serde::from_str(&config_str)
-            .map_err(|e| anyhow!("Failed to parse root .tbdflow.yml: {}", e))?
+        Some(apply_extends(&config_str, git_root, opts)?)
     } else {
-        Config::default()
+        None
     };
+    let value = with_user_layer(repo)?;
+    config_from_value(value, profile)
+        .map_err(|e| anyhow!("Failed to parse root .tbdflow.yml: {}", e))
+}
+
+/// Parses `content` as the root `.tbdflow.yml` of `git_root`, following its
+/// `extends:` and applying `profile`.
+pub fn parse_root_config(
+    content: &str,
+    git_root: &Path,
+    profile: Option<&str>,
+    opts: RunOpts,
+) -> Result<(Config, Vec<String>), anyhow::Error> {
+    let value = apply_extends(content, git_root, opts)?;
+    config_from_value(value, profile)
+        .map_err(|e| anyhow!("Failed to parse root .tbdflow.yml: {}", e))
+}
+
+/// Loads the effective config: the user config, the root and any
+/// project-level `.tbdflow.yml` with `profile` (from `--profile` or
+/// `TBDFLOW_PROFILE`) applied to each, `.tbdflow.local.yml`, then the
+/// `TBDFLOW_*` overrides from the environment.
+pub fn load_tbdflow_config(profile: Option<&str>, opts: RunOpts) -> Result<Config, anyhow::Error> {
+    apply_env_overrides(load_file_config(profile, opts)?, std::env::vars())
+}
+
+/// `TBDFLOW_*` variables that are not settings.
+const NON_SETTING_VARS: &[&str] = &[
+    "TBDFLOW_PROFILE",
+    "TBDFLOW_ASSUME_YES",
+    "TBDFLOW_ASCII",
+    "TBDFLOW_LANG",
+];
+
+/// The setting a `TBDFLOW_*` variable overrides: `__` separates sections, so
+/// `TBDFLOW_LINT__SUBJECT_LINE_RULES__MAX_LENGTH` is
+/// `lint.subject_line_rules.max_length`.
+fn env_override_path(name: &str) -> Option<Vec<String>> {
+    if NON_SETTING_VARS.contains(&name) {
+        return None;
+    }
+    let path: Vec<String> = name
+        .strip_prefix("TBDFLOW_")?
+        .split("__")
+        .map(str::to_lowercase)
+        .collect();
+    (!path.iter().any(String::is_empty)).then_some(path)
+}
+
+/// Layers `TBDFLOW_*` overrides from `vars` over `config`. Values are read as
+/// YAML, so `50`, `true` and `[a, b]` keep their types; one the setting cannot
+/// take is used as a string instead. A variable that names no setting is
+/// skipped with a warning.
+pub fn apply_env_overrides(
+    config: Config,
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> Result<Config, anyhow::Error> {
+    let mut overrides: Vec<(String, Vec<String>, String)> = vars
+        .into_iter()
+        .filter_map(|(name, raw)| env_override_path(&name).map(|path| (name, path, raw)))
+        .collect();
+    if overrides.is_empty() {
+        return Ok(config);
+    }
+    overrides.sort();
+
+    let mut merged = yaml_serde::to_value(&config)?;
+    for (name, path, raw) in overrides {
+        let nest = |value: yaml_serde::Value| {
+            path.iter().rev().fold(value, |value, key| {
+                let mut map = yaml_serde::Mapping::new();
+                map.insert(yaml_serde::Value::from(key.as_str()), value);
+                yaml_serde::Value::Mapping(map)
+            })
+        };
+        let parsed: yaml_serde::Value =
+            yaml_serde::from_str(&raw).unwrap_or_else(|_| yaml_serde::Value::String(raw.clone()));
+        let unknown: Vec<String> = crate::config_schema::check_overrides(&nest(parsed.clone()))
+            .into_iter()
+            .filter(|(_, message)| message.starts_with("is not a known setting"))
+            .map(|(key, message)| format!("'{}' {}", key, message))
+            .collect();
+        if !unknown.is_empty() {
+            eprintln!(
+                "{}",
+                format!("Warning: ignoring {}: {}", name, unknown.join("; ")).yellow()
+            );
+            continue;
+        }
+
+        let mut candidate = merged.clone();
+        merge_yaml(&mut candidate, nest(parsed.clone()));
+        if yaml_serde::from_value::<Config>(candidate.clone()).is_err() && !parsed.is_string() {
+            candidate = merged.clone();
+            merge_yaml(&mut candidate, nest(yaml_serde::Value::String(raw)));
+        }
+        yaml_serde::from_value::<Config>(candidate.clone())
+            .map_err(|e| anyhow!("Invalid value in {}: {}", name, e))?;
+        merged = candidate;
+    }
+    Ok(yaml_serde::from_value(merged)?)
+}
 
-    // Check if we are in a subdirectory and if a local config exists.
-    let current_dir = std::env::current_dir()?;
-    if current_dir != Path::new(&git_root) {
-        let local_config_path = current_dir.join(".tbdflow.yml");
+fn load_file_config(profile: Option<&str>, opts: RunOpts) -> Result<Config, anyhow::Error> {
+    let quiet = opts.for_reads();
+    let git_root = match git::get_git_root(quiet) {
+        Ok(path) => path,
+        Err(_) => {
+            // A bare repository has no work tree, so read the committed config.
+            if let Ok(content) = git::run_git_command("show", &["HEAD:.tbdflow.yml"], quiet) {
+                let repo = yaml_serde::from_str(&content)
+                    .map_err(|e| anyhow!("Failed to parse committed .tbdflow.yml: {}", e))?;
+                let value = with_user_layer(Some(repo))?;
+                return config_from_value(value, profile)
+                    .map(|(config, _)| config)
+                    .map_err(|e| anyhow!("Failed to parse committed .tbdflow.yml: {}", e));
+            }
+            // Not in a git repo: only the user's own config applies.
+            let value = with_user_layer(None)?;
+            return config_from_value(value, profile).map(|(config, _)| config);
+        }
+    };
+    let mut known_profiles: Vec<String> = Vec::new();
+
+    // Load base config from git root, or use default.
+    let (mut base_config, names) = load_root_config(Path::new(&git_root), profile, opts)?;
+    known_profiles.extend(names);
+
+    // A project's config applies anywhere inside the project; otherwise a
+    // config in the current subdirectory does.
+    let project_dir = match find_project_root(opts)? {
+        Some(dir) => dir,
+        None => std::env::current_dir()?,
+    };
+    if project_dir != Path::new(&git_root) {
+        let local_config_path = project_dir.join(".tbdflow.yml");
         if local_config_path.exists() {
             let local_config_str = fs::read_to_string(local_config_path)?;
-            let local_config: Config = yaml_serde::from_str(&local_config_str)
+            let (local_config, names) = parse_config_with_profile(&local_config_str, profile)
                 .map_err(|e| anyhow!("Failed to parse local .tbdflow.yml: {}", e))?;
+            known_profiles.extend(names);
             merge_configs(&mut base_config, local_config);
         }
     }
 
-    Ok(base_config)
+    if let Some(name) = profile
+        && !known_profiles.iter().any(|p| p == name)
+    {
+        known_profiles.sort();
+        known_profiles.dedup();
+        let available = if known_profiles.is_empty() {
+            "none defined".to_string()
+        } else {
+            known_profiles.join(", ")
+        };
+        return Err(anyhow!(
+            "Unknown profile '{}' (available: {}).",
+            name,
+            available
+        ));
+    }
+
+    apply_local_overrides(base_config, Path::new(&git_root))
 }
 
-pub fn load_dod_config() -> anyhow::Result<DodConfig> {
-    let content = fs::read_to_string(".dod.yml").context("Failed to read .dod.yml")?;
+/// Reads `.dod.yml` from the git root, so the checklist is found from any
+/// subdirectory.
+pub fn load_dod_config(opts: RunOpts) -> anyhow::Result<DodConfig> {
+    let root = git::get_git_root(opts.for_reads()).unwrap_or_else(|_| ".".to_string());
+    let content =
+        fs::read_to_string(Path::new(&root).join(".dod.yml")).context("Failed to read .dod.yml")?;
     let config: DodConfig = yaml_serde::from_str(&content).context("Failed to parse .dod.yml")?;
     Ok(config)
 }
@@ -386,16 +1276,16 @@ pub fn is_monorepo_root(config: &Config, current_dir: &Path, git_root: &Path) ->
     current_dir == git_root && config.monorepo.enabled && !config.monorepo.project_dirs.is_empty()
 }
 
-pub fn find_project_root() -> Result<Option<PathBuf>, anyhow::Error> {
+pub fn find_project_root(opts: RunOpts) -> Result<Option<PathBuf>, anyhow::Error> {
     let mut current_dir = std::env::current_dir()?;
-    let git_root = PathBuf::from(git::get_git_root(RunOpts::new(false, false))?);
+    let git_root = PathBuf::from(git::get_git_root(opts.for_reads())?);
 
     loop {
         let config_path = current_dir.join(".tbdflow.yml");
         if config_path.exists() {
             let content = fs::read_to_string(&config_path)?;
-            let config: Config = yaml_serde::from_str(&content)?;
-            if config.project_root.is_some() {
+            let value: yaml_serde::Value = yaml_serde::from_str(&content)?;
+            if value.get("project_root").is_some_and(|v| !v.is_null()) {
                 return Ok(Some(current_dir));
             }
         }
@@ -413,3 +1303,138 @@ pub fn find_project_root() -> Result<Option<PathBuf>, anyhow::Error> {
 
     Ok(None)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+review:
+  enabled: true
+  default_reviewers: [ "alice" ]
+profiles:
+  solo:
+    commit:
+      dod: false
+    review:
+      enabled: false
+"#;
+
+    #[test]
+    fn profile_is_merged_over_base_config() {
+        let (config, names) = parse_config_with_profile(CONFIG, Some("solo")).unwrap();
+        assert_eq!(names, vec!["solo".to_string()]);
+        assert!(!config.review.enabled);
+        assert!(!config.commit.dod);
+        // Keys the profile does not mention keep their base values.
+        assert_eq!(config.review.default_reviewers, vec!["alice".to_string()]);
+        assert_eq!(config.main_branch_name, "main");
+    }
+
+    #[test]
+    fn branch_type_uses_longest_matching_prefix() {
+        let mut config = Config::default();
+        config
+            .branch_types
+            .insert("feat-ui".to_string(), "feat/ui-".to_string());
+        assert_eq!(config.branch_type_for("main").as_deref(), Some("main"));
+        assert_eq!(
+            config.branch_type_for("feat/login").as_deref(),
+            Some("feat")
+        );
+        assert_eq!(
+            config.branch_type_for("feat/ui-header").as_deref(),
+            Some("feat-ui")
+        );
+        assert_eq!(config.branch_type_for("spike"), None);
+    }
+
+    #[test]
+    fn branch_lint_override_is_merged_over_base_rules() {
+        let mut config = Config::default();
+        let overlay: yaml_serde::Value = yaml_serde::from_str(
+            "issue_key_missing:\n  enabled: false\nsubject_line_rules:\n  max_length: 100\n",
+        )
+        .unwrap();
+        let lint = config.lint.as_mut().unwrap();
+        lint.issue_key_missing.as_mut().unwrap().enabled = Some(true);
+        lint.branches.insert("feat".to_string(), overlay);
+
+        let feat = config.lint_for_branch("feat/login").unwrap().unwrap();
+        assert_eq!(feat.issue_key_missing.unwrap().enabled, Some(false));
+        let subject = feat.subject_line_rules.unwrap();
+        assert_eq!(subject.max_length, Some(100));
+        // Keys the override leaves out keep their base values.
+        assert_eq!(subject.no_period, Some(true));
+
+        let main = config.lint_for_branch("main").unwrap().unwrap();
+        assert_eq!(main.issue_key_missing.unwrap().enabled, Some(true));
+    }
+
+    #[test]
+    fn base_config_is_used_without_a_profile() {
+        let (config, _) = parse_config_with_profile(CONFIG, None).unwrap();
+        assert!(config.review.enabled);
+        assert!(config.commit.dod);
+    }
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn env_overrides_are_layered_over_the_file() {
+        let (config, _) = parse_config_with_profile(CONFIG, None).unwrap();
+        let config = apply_env_overrides(
+            config,
+            vars(&[
+                ("TBDFLOW_LINT__SUBJECT_LINE_RULES__MAX_LENGTH", "50"),
+                ("TBDFLOW_REVIEW__ENABLED", "false"),
+                // Read as YAML this is a number, but the setting takes a string.
+                ("TBDFLOW_MAIN_BRANCH_NAME", "2024"),
+                ("TBDFLOW_PROFILE", "solo"),
+                ("PATH", "/usr/bin"),
+            ]),
+        )
+        .unwrap();
+        let rules = config.lint.unwrap().subject_line_rules.unwrap();
+        assert_eq!(rules.max_length, Some(50));
+        assert!(!config.review.enabled);
+        assert_eq!(config.review.default_reviewers, vec!["alice"]);
+        assert_eq!(config.main_branch_name, "2024");
+    }
+
+    #[test]
+    fn env_overrides_that_name_no_setting_are_ignored() {
+        let config = apply_env_overrides(
+            Config::default(),
+            vars(&[("TBDFLOW_REVIEW__ENABLD", "true"), ("TBDFLOW__", "x")]),
+        )
+        .unwrap();
+        assert!(!config.review.enabled);
+    }
+
+    #[test]
+    fn env_override_of_the_wrong_type_is_an_error() {
+        let err = apply_env_overrides(
+            Config::default(),
+            vars(&[("TBDFLOW_STALE_BRANCH_THRESHOLD_DAYS", "soon")]),
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Invalid value in TBDFLOW_STALE_BRANCH_THRESHOLD_DAYS"),
+            "Error was: {}",
+            err
+        );
+    }
+}
@@ -0,0 +1,779 @@
+//! `tbdflow config`: reads the effective settings and edits the root
+//! `.tbdflow.yml`. Edits are made to the lines of the file, so its comments
+//! and layout survive. A file laid out in a way that cannot be edited like
+//! that (flow mappings, tabs) is rewritten from its parsed form instead.
+
+use crate::commands::{ConfigListResponse, ConfigValueResponse, TbdResponse};
+use crate::config::{self, Config, PROFILES_KEY};
+use crate::extends::EXTENDS_KEY;
+use crate::git::RunOpts;
+use anyhow::{Context, Result, anyhow, bail};
+use colored::Colorize;
+use std::fs;
+use std::path::Path;
+use yaml_serde::{Mapping, Value};
+
+const CONFIG_FILE: &str = ".tbdflow.yml";
+
+/// Splits a dotted key such as `lint.subject_line_rules.max_length`.
+fn parse_key(key: &str) -> Result<Vec<&str>> {
+    let path: Vec<&str> = key.split('.').collect();
+    if path.iter().any(|segment| segment.trim().is_empty()) {
+        bail!(
+            "Invalid key '{}'. Use a dotted path such as lint.subject_line_rules.max_length.",
+            key
+        );
+    }
+    Ok(path)
+}
+
+fn unknown_setting(key: &str) -> anyhow::Error {
+    anyhow!(
+        "Unknown setting '{}'. Run 'tbdflow config list' to see them all.",
+        key
+    )
+}
+
+/// Top-level keys that only exist in the file; they are folded away while loading.
+fn is_file_only(path: &[&str]) -> bool {
+    path[0] == PROFILES_KEY || path[0] == EXTENDS_KEY
+}
+
+fn settings(config: &Config) -> Result<Value> {
+    yaml_serde::to_value(config).context("Failed to read the effective settings")
+}
+
+/// Keys holding credentials. Their values are never printed.
+const SECRET_KEYS: &[&str] = &["token", "app_password"];
+
+const REDACTED: &str = "***";
+
+fn is_secret(key: &str) -> bool {
+    SECRET_KEYS.contains(&key)
+}
+
+/// `value` with every credential that is set replaced by `***`, for output
+/// that ends up in bug reports and CI logs.
+fn redacted(value: Value) -> Value {
+    match value {
+        Value::Mapping(map) => Value::Mapping(
+            map.into_iter()
+                .map(|(k, v)| {
+                    let v = match k.as_str() {
+                        Some(key) if is_secret(key) && !v.is_null() => {
+                            Value::String(REDACTED.to_string())
+                        }
+                        _ => redacted(v),
+                    };
+                    (k, v)
+                })
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Looks `path` up in the effective settings. Anything under an optional
+/// section that is not set reads as null; a key no section has is `None`.
+fn effective(root: &Value, path: &[&str]) -> Option<Value> {
+    let mut current = root;
+    for segment in path {
+        match current {
+            Value::Null => return Some(Value::Null),
+            Value::Mapping(map) => current = map.get(*segment)?,
+            _ => return None,
+        }
+    }
+    Some(current.clone())
+}
+
+/// `value` on one line, in YAML flow style.
+fn flow(value: &Value) -> String {
+    match value {
+        Value::Sequence(items) => {
+            let items: Vec<String> = items.iter().map(flow).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Mapping(map) => {
+            let entries: Vec<String> = map
+                .iter()
+                .map(|(k, v)| format!("{}: {}", flow(k), flow(v)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        scalar => yaml_serde::to_string(scalar)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Every setting under `prefix` as `(dotted key, value)`, skipping unset ones.
+fn flatten(prefix: &str, value: &Value, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Null => {}
+        Value::Mapping(map) if !map.is_empty() => {
+            for (k, v) in map {
+                let key = k.as_str().map_or_else(|| flow(k), str::to_string);
+                let key = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&key, v, out);
+            }
+        }
+        _ => out.push((prefix.to_string(), value.clone())),
+    }
+}
+
+/// Prints the effective value of one setting: strings as-is, sections as YAML.
+/// Credentials are shown as `***`.
+pub fn handle_config_get(config: &Config, key: &str, json: bool) -> Result<()> {
+    let path = parse_key(key)?;
+    let value =
+        effective(&redacted(settings(config)?), &path).ok_or_else(|| unknown_setting(key))?;
+    if json {
+        let response = ConfigValueResponse {
+            key: key.to_string(),
+            value: serde_json::to_value(&value)?,
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(response))?
+        );
+        return Ok(());
+    }
+    match &value {
+        Value::String(s) => println!("{}", s),
+        Value::Mapping(map) if !map.is_empty() => {
+            print!("{}", yaml_serde::to_string(&value)?)
+        }
+        Value::Sequence(items) if !items.is_empty() => {
+            print!("{}", yaml_serde::to_string(&value)?)
+        }
+        _ => println!("{}", flow(&value)),
+    }
+    Ok(())
+}
+
+/// Prints every effective setting as `key = value`, sorted by key.
+/// Credentials are shown as `***`.
+pub fn handle_config_list(config: &Config, json: bool) -> Result<()> {
+    let mut entries = Vec::new();
+    flatten("", &redacted(settings(config)?), &mut entries);
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    if json {
+        let settings = entries
+            .into_iter()
+            .map(|(key, value)| {
+                Ok(ConfigValueResponse {
+                    key,
+                    value: serde_json::to_value(&value)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(ConfigListResponse { settings }))?
+        );
+        return Ok(());
+    }
+    for (key, value) in entries {
+        println!("{} = {}", key, flow(&value));
+    }
+    Ok(())
+}
+
+/// Writes one setting to the root `.tbdflow.yml`. `raw` is read as YAML, so
+/// `50`, `true` and `[a, b]` keep their types; anything that does not parse
+/// is stored as a string.
+pub fn handle_config_set(git_root: &Path, key: &str, raw: &str, opts: RunOpts) -> Result<()> {
+    let path = parse_key(key)?;
+    let value: Value = yaml_serde::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()));
+    let file = git_root.join(CONFIG_FILE);
+    let edit = edit(&read_config_file(&file)?, &path, Some(&value))?;
+
+    let (config, _) = config::parse_root_config(&edit.text, git_root, None, opts)
+        .with_context(|| format!("Invalid value for '{}'", key))?;
+    if !is_file_only(&path) && effective(&settings(&config)?, &path).as_ref() != Some(&value) {
+        return Err(unknown_setting(key));
+    }
+    let shown = match path.last() {
+        Some(last) if is_secret(last) => REDACTED.to_string(),
+        _ => flow(&value),
+    };
+    save(&file, &edit, &format!("Set {} = {}", key, shown), opts)
+}
+
+/// Removes one setting from the root `.tbdflow.yml`, along with any section
+/// left empty, so the default applies again.
+pub fn handle_config_unset(git_root: &Path, key: &str, opts: RunOpts) -> Result<()> {
+    let path = parse_key(key)?;
+    let file = git_root.join(CONFIG_FILE);
+    let edit = edit(&read_config_file(&file)?, &path, None)?;
+    config::parse_root_config(&edit.text, git_root, None, opts)
+        .with_context(|| format!("'{}' is required, so it cannot be removed", key))?;
+    save(&file, &edit, &format!("Removed {}", key), opts)
+}
+
+/// Writes several settings to the root `.tbdflow.yml` in one go, keeping its
+/// comments where it can, and prints each one. `done` ends the message.
+pub(crate) fn set_all(
+    git_root: &Path,
+    settings: &[(String, Value)],
+    done: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    let file = git_root.join(CONFIG_FILE);
+    let original = read_config_file(&file)?;
+    let mut combined = Edit {
+        text: original.clone(),
+        rewritten: false,
+        had_comments: false,
+    };
+    for (key, value) in settings {
+        let next = edit(&combined.text, &parse_key(key)?, Some(value))?;
+        combined.rewritten |= next.rewritten;
+        combined.text = next.text;
+    }
+    combined.had_comments = combined.rewritten && original.lines().any(|l| l.contains('#'));
+    config::parse_root_config(&combined.text, git_root, None, opts)
+        .with_context(|| format!("The result is not a valid {}", CONFIG_FILE))?;
+    for (key, value) in settings {
+        println!("  {} = {}", key, flow(value));
+    }
+    save(&file, &combined, done, opts)
+}
+
+fn read_config_file(file: &Path) -> Result<String> {
+    if !file.exists() {
+        bail!(
+            "There is no {} at the repository root. Run 'tbdflow init' to create one.",
+            CONFIG_FILE
+        );
+    }
+    fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))
+}
+
+fn save(file: &Path, edit: &Edit, done: &str, opts: RunOpts) -> Result<()> {
+    if opts.dry_run {
+        println!(
+            "{}",
+            format!("[DRY RUN] {} would be written as:", CONFIG_FILE).yellow()
+        );
+        print!("{}", edit.text);
+        return Ok(());
+    }
+    fs::write(file, &edit.text).with_context(|| format!("Failed to write {}", file.display()))?;
+    if edit.rewritten && edit.had_comments {
+        println!(
+            "{}",
+            format!(
+                "{} could not be edited in place, so it was rewritten and its comments were lost.",
+                CONFIG_FILE
+            )
+            .yellow()
+        );
+    }
+    println!("{}", format!("{} in {}.", done, CONFIG_FILE).green());
+    Ok(())
+}
+
+#[derive(Debug)]
+struct Edit {
+    text: String,
+    /// Serialized from the parsed document rather than edited line by line.
+    rewritten: bool,
+    had_comments: bool,
+}
+
+/// Sets `path` to `change`, or removes it when `change` is `None`. The lines
+/// of `original` are edited when the result parses to exactly the intended
+/// document; otherwise the intended document is serialized.
+fn edit(original: &str, path: &[&str], change: Option<&Value>) -> Result<Edit> {
+    let mut expected: Value = yaml_serde::from_str(original)
+        .with_context(|| format!("Failed to parse {}", CONFIG_FILE))?;
+    match change {
+        Some(value) => set_path(&mut expected, path, value.clone())?,
+        None => {
+            if !remove_path(&mut expected, path) {
+                bail!("'{}' is not set in {}.", path.join("."), CONFIG_FILE);
+            }
+        }
+    }
+
+    let in_place = match change {
+        Some(value) => lines::set(original, path, &flow(value)),
+        None => lines::remove(original, path),
+    };
+    if let Some(text) = in_place
+        && yaml_serde::from_str::<Value>(&text).ok().as_ref() == Some(&expected)
+    {
+        return Ok(Edit {
+            text,
+            rewritten: false,
+            had_comments: false,
+        });
+    }
+    Ok(Edit {
+        text: yaml_serde::to_string(&expected)?,
+        rewritten: true,
+        had_comments: original.lines().any(|l| l.contains('#')),
+    })
+}
+
+/// The 1-based line of `text` that sets `path`, or of the nearest section
+/// above it that is set. `None` when not even the first key is there.
+pub(crate) fn line_of(text: &str, path: &[&str]) -> Option<usize> {
+    (1..=path.len())
+        .rev()
+        .find_map(|depth| lines::line_of(text, &path[..depth]))
+}
+
+fn set_path(doc: &mut Value, path: &[&str], value: Value) -> Result<()> {
+    let mut current = doc;
+    for (depth, segment) in path.iter().enumerate() {
+        if current.is_null() {
+            *current = Value::Mapping(Mapping::new());
+        }
+        let map = current.as_mapping_mut().ok_or_else(|| {
+            anyhow!(
+                "'{}' is not a section, so it has no '{}'.",
+                path[..depth].join("."),
+                segment
+            )
+        })?;
+        if depth + 1 == path.len() {
+            map.insert(Value::from(*segment), value);
+            return Ok(());
+        }
+        current = map.entry(Value::from(*segment)).or_insert(Value::Null);
+    }
+    Ok(())
+}
+
+/// Removes `path` and any section that leaves empty. False if it was not set.
+fn remove_path(doc: &mut Value, path: &[&str]) -> bool {
+    let Some(map) = doc.as_mapping_mut() else {
+        return false;
+    };
+    match path {
+        [] => false,
+        [last] => map.remove(*last).is_some(),
+        [first, rest @ ..] => {
+            let Some(child) = map.get_mut(*first) else {
+                return false;
+            };
+            if !remove_path(child, rest) {
+                return false;
+            }
+            if child.as_mapping().is_some_and(Mapping::is_empty) {
+                map.remove(*first);
+            }
+            true
+        }
+    }
+}
+
+/// Line-based edits to a block-style YAML document. Each returns `None` for
+/// layouts it does not handle; callers check that the result parses to what
+/// was intended either way.
+mod lines {
+    use super::flow;
+    use yaml_serde::Value;
+
+    struct Line<'a> {
+        text: &'a str,
+        indent: usize,
+        /// Holds YAML, rather than being blank or a comment.
+        content: bool,
+        /// A `- ` sequence entry.
+        item: bool,
+        key: Option<String>,
+        /// Byte offset just past the `:` that ends the key.
+        value_at: usize,
+    }
+
+    impl Line<'_> {
+        /// The inline value after the key, without any comment.
+        fn value(&self) -> &str {
+            if self.key.is_none() {
+                return "";
+            }
+            let rest = &self.text[self.value_at..];
+            rest[..comment_start(rest)].trim()
+        }
+    }
+
+    /// Where the trailing comment of `rest` starts, including the spaces
+    /// before its `#`; the length of `rest` if there is none.
+    fn comment_start(rest: &str) -> usize {
+        let value = rest.trim_start();
+        let offset = rest.len() - value.len();
+        if value.starts_with('#') {
+            return 0;
+        }
+        let from = match value.chars().next() {
+            Some(q @ ('"' | '\'')) => value[1..].find(q).map_or(value.len(), |i| i + 2),
+            _ => 0,
+        };
+        match value[from..].find(" #") {
+            Some(i) => {
+                let before = &rest[..offset + from + i];
+                before.trim_end().len()
+            }
+            None => rest.len(),
+        }
+    }
+
+    /// The key a line starts with and the offset just past its `:`.
+    fn split_key(body: &str) -> Option<(String, usize)> {
+        let is_end = |rest: &str| rest.is_empty() || rest.starts_with(' ');
+        if let Some(q @ ('"' | '\'')) = body.chars().next() {
+            let close = body[1..].find(q)? + 1;
+            let rest = &body[close + 1..];
+            return (rest.starts_with(':') && is_end(&rest[1..]))
+                .then(|| (body[1..close].to_string(), close + 2));
+        }
+        if body.starts_with(['{', '[', '&', '*', '!', '|', '>', '?', '#']) {
+            return None;
+        }
+        body.match_indices(':')
+            .map(|(i, _)| i)
+            .find(|&i| is_end(&body[i + 1..]))
+            .filter(|&i| !body[..i].contains(" #"))
+            .map(|i| (body[..i].trim_end().to_string(), i + 1))
+    }
+
+    fn scan(text: &str) -> Option<Line<'_>> {
+        let body = text.trim_start_matches(' ');
+        let indent = text.len() - body.len();
+        let mut line = Line {
+            text,
+            indent,
+            content: false,
+            item: false,
+            key: None,
+            value_at: text.len(),
+        };
+        let trimmed = body.trim_end();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed == "---" {
+            return Some(line);
+        }
+        if body.starts_with('\t') {
+            return None;
+        }
+        line.content = true;
+        if trimmed == "-" || body.starts_with("- ") {
+            line.item = true;
+        } else if let Some((key, after)) = split_key(body) {
+            line.key = Some(key);
+            line.value_at = indent + after;
+        }
+        Some(line)
+    }
+
+    fn parse(text: &str) -> Option<Vec<Line<'_>>> {
+        text.lines().map(scan).collect()
+    }
+
+    /// One past the last line of the block starting at `i`: its nested lines,
+    /// but not the comments and blank lines that precede the next key.
+    fn block_end(lines: &[Line], i: usize) -> usize {
+        let indent = lines[i].indent;
+        let mut end = lines[i + 1..]
+            .iter()
+            .position(|l| l.content && (l.indent < indent || (l.indent == indent && !l.item)))
+            .map_or(lines.len(), |p| i + 1 + p);
+        while end > i + 1 && !lines[end - 1].content {
+            end -= 1;
+        }
+        end
+    }
+
+    enum Lookup {
+        Found(usize),
+        /// `path[..depth]` exists, ending at line `parent` (the document for
+        /// `None`); its entries are indented by `indent`.
+        Missing {
+            depth: usize,
+            parent: Option<usize>,
+            indent: usize,
+        },
+    }
+
+    fn find(lines: &[Line], path: &[&str]) -> Option<Lookup> {
+        let (mut start, mut end) = (0, lines.len());
+        let mut parent: Option<usize> = None;
+        for (depth, segment) in path.iter().enumerate() {
+            let indent = match (start..end).find(|&i| lines[i].content) {
+                Some(i) => lines[i].indent,
+                None => parent.map_or(0, |p| lines[p].indent + 2),
+            };
+            let mut entries: Vec<usize> = Vec::new();
+            for i in (start..end).filter(|&i| lines[i].content && lines[i].indent == indent) {
+                match (&lines[i].key, lines[i].item) {
+                    (Some(_), _) => entries.push(i),
+                    // An indentless sequence, as yaml_serde writes it: the
+                    // items belong to the key before them.
+                    (None, true) if !entries.is_empty() => {}
+                    _ => return None,
+                }
+            }
+            let hit = entries
+                .into_iter()
+                .rfind(|&i| lines[i].key.as_deref() == Some(*segment));
+            match hit {
+                Some(i) if depth + 1 == path.len() => return Some(Lookup::Found(i)),
+                // A section with an inline value, such as a flow mapping.
+                Some(i) if !lines[i].value().is_empty() => return None,
+                Some(i) => {
+                    parent = Some(i);
+                    start = i + 1;
+                    end = block_end(lines, i);
+                }
+                None => {
+                    return Some(Lookup::Missing {
+                        depth,
+                        parent,
+                        indent,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn join(lines: Vec<String>) -> String {
+        let mut text = lines.join("\n");
+        text.push('\n');
+        text
+    }
+
+    /// The 1-based line `path` is set on.
+    pub(super) fn line_of(text: &str, path: &[&str]) -> Option<usize> {
+        match find(&parse(text)?, path)? {
+            Lookup::Found(i) => Some(i + 1),
+            Lookup::Missing { .. } => None,
+        }
+    }
+
+    /// Sets `path` to the flow-style `value`, keeping a trailing comment on
+    /// its line. Missing sections are added at the end of their parent.
+    pub(super) fn set(text: &str, path: &[&str], value: &str) -> Option<String> {
+        let lines = parse(text)?;
+        let mut out: Vec<String> = text.lines().map(str::to_string).collect();
+        match find(&lines, path)? {
+            Lookup::Found(i) => {
+                let line = &lines[i];
+                let rest = &line.text[line.value_at..];
+                let comment = &rest[comment_start(rest)..];
+                let updated = format!("{} {}{}", &line.text[..line.value_at], value, comment);
+                out.splice(i..block_end(&lines, i), [updated]);
+            }
+            Lookup::Missing {
+                depth,
+                parent,
+                indent,
+            } => {
+                let at = parent.map_or(lines.len(), |p| block_end(&lines, p));
+                let added = path[depth..].iter().enumerate().map(|(n, segment)| {
+                    let pad = " ".repeat(indent + 2 * n);
+                    let key = flow(&Value::from(*segment));
+                    if depth + n + 1 == path.len() {
+                        format!("{}{}: {}", pad, key, value)
+                    } else {
+                        format!("{}{}:", pad, key)
+                    }
+                });
+                out.splice(at..at, added);
+            }
+        }
+        Some(join(out))
+    }
+
+    /// Removes `path` with everything nested under it, then any section
+    /// that leaves empty.
+    pub(super) fn remove(text: &str, path: &[&str]) -> Option<String> {
+        let lines = parse(text)?;
+        let Lookup::Found(i) = find(&lines, path)? else {
+            return None;
+        };
+        let mut range = i..block_end(&lines, i);
+        for depth in (1..path.len()).rev() {
+            let Lookup::Found(p) = find(&lines, &path[..depth])? else {
+                return None;
+            };
+            let end = block_end(&lines, p);
+            let others = (p + 1..end).any(|j| lines[j].content && !range.contains(&j));
+            if others || !lines[p].value().is_empty() {
+                break;
+            }
+            range = p..end;
+        }
+        let mut out: Vec<String> = text.lines().map(str::to_string).collect();
+        out.drain(range);
+        Some(join(out))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMENTED: &str = "\
+# Team settings
+main_branch_name: main
+lint:
+  # Keep subjects short.
+  subject_line_rules:
+    max_length: 72 # hard limit
+    no_period: true
+
+  body_line_rules:
+    max_line_length: 80
+branch_types:
+  feat: \"feat/\"
+";
+
+    fn set(text: &str, key: &str, raw: &str) -> Edit {
+        let value: Value = yaml_serde::from_str(raw).unwrap();
+        edit(text, &parse_key(key).unwrap(), Some(&value)).unwrap()
+    }
+
+    #[test]
+    fn set_replaces_a_value_and_keeps_comments() {
+        let edit = set(COMMENTED, "lint.subject_line_rules.max_length", "50");
+        assert!(!edit.rewritten);
+        assert_eq!(
+            edit.text,
+            COMMENTED.replace("max_length: 72 # hard limit", "max_length: 50 # hard limit")
+        );
+    }
+
+    #[test]
+    fn set_adds_missing_keys_to_their_section() {
+        let edit = set(
+            COMMENTED,
+            "lint.subject_line_rules.enforce_lowercase",
+            "true",
+        );
+        assert!(!edit.rewritten);
+        assert!(
+            edit.text
+                .contains("    no_period: true\n    enforce_lowercase: true\n\n  body_line_rules:")
+        );
+
+        let edit = set(COMMENTED, "review.enabled", "true");
+        assert!(edit.text.starts_with(COMMENTED));
+        assert!(edit.text.ends_with("review:\n  enabled: true\n"));
+    }
+
+    #[test]
+    fn keys_after_an_indentless_sequence_are_edited_in_place() {
+        // The file `tbdflow init` writes, with a comment of the team's own.
+        let init = format!(
+            "# Team settings\n{}",
+            yaml_serde::to_string(&crate::config::Config::default()).unwrap()
+        );
+        assert!(init.contains("  exempt_types:\n  - "));
+        let edit = set(&init, "review.sla_hours", "10");
+        assert!(!edit.rewritten);
+        assert!(edit.text.starts_with("# Team settings\n"));
+        assert!(edit.text.contains("  sla_hours: 10\n"));
+        assert_eq!(edit.text.lines().count(), init.lines().count());
+
+        let edit = set(&init, "review.exempt_types", "[docs]");
+        assert!(!edit.rewritten);
+        assert!(edit.text.contains("  exempt_types: [docs]\n"));
+    }
+
+    #[test]
+    fn set_replaces_a_section_with_a_flow_value() {
+        let edit = set(COMMENTED, "branch_types", "{feat: f/, fix: x/}");
+        assert!(!edit.rewritten);
+        assert!(edit.text.ends_with("branch_types: {feat: f/, fix: x/}\n"));
+    }
+
+    #[test]
+    fn unset_removes_the_key_and_emptied_sections() {
+        let path = parse_key("lint.body_line_rules.max_line_length").unwrap();
+        let edit = edit(COMMENTED, &path, None).unwrap();
+        assert!(!edit.rewritten);
+        assert!(!edit.text.contains("body_line_rules"));
+        assert!(edit.text.contains("# Keep subjects short."));
+        assert!(edit.text.contains("max_length: 72 # hard limit"));
+
+        let path = parse_key("lint.scope").unwrap();
+        let err = super::edit(COMMENTED, &path, None).unwrap_err();
+        assert_eq!(err.to_string(), "'lint.scope' is not set in .tbdflow.yml.");
+    }
+
+    #[test]
+    fn flow_sections_are_rewritten() {
+        let text = "# comment\nlint: {subject_line_rules: {max_length: 72}}\n";
+        let edit = set(text, "lint.subject_line_rules.max_length", "50");
+        assert!(edit.rewritten && edit.had_comments);
+        let parsed: Value = yaml_serde::from_str(&edit.text).unwrap();
+        assert_eq!(
+            effective(&parsed, &["lint", "subject_line_rules", "max_length"]),
+            Some(Value::from(50))
+        );
+    }
+
+    #[test]
+    fn set_refuses_to_descend_into_a_value() {
+        let path = parse_key("main_branch_name.x").unwrap();
+        let err = edit(COMMENTED, &path, Some(&Value::from(1))).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "'main_branch_name' is not a section, so it has no 'x'."
+        );
+    }
+
+    #[test]
+    fn keys_are_dotted_paths() {
+        assert_eq!(parse_key("a.b").unwrap(), vec!["a", "b"]);
+        assert!(parse_key("a..b").is_err());
+        assert!(parse_key("").is_err());
+    }
+
+    #[test]
+    fn credentials_are_redacted() {
+        let root: Value = yaml_serde::from_str(
+            "review:\n  gitea:\n    server: https://git.example.com\n    token: s3cret\n  github: {token: null}\n  bitbucket: {app_password: hunter2}\n",
+        )
+        .unwrap();
+        let mut entries = Vec::new();
+        flatten("", &redacted(root), &mut entries);
+        assert_eq!(
+            entries,
+            vec![
+                (
+                    "review.gitea.server".to_string(),
+                    Value::from("https://git.example.com")
+                ),
+                ("review.gitea.token".to_string(), Value::from("***")),
+                (
+                    "review.bitbucket.app_password".to_string(),
+                    Value::from("***")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn effective_values_read_through_unset_sections() {
+        let root: Value = yaml_serde::from_str("lint: null\nreview:\n  enabled: true\n").unwrap();
+        assert_eq!(
+            effective(&root, &["review", "enabled"]),
+            Some(Value::from(true))
+        );
+        assert_eq!(
+            effective(&root, &["lint", "scope", "enabled"]),
+            Some(Value::Null)
+        );
+        assert_eq!(effective(&root, &["review", "nope"]), None);
+        assert_eq!(effective(&root, &["review", "enabled", "x"]), None);
+    }
+}
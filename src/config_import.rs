@@ -0,0 +1,449 @@
+//! `tbdflow config import`: translates an existing commitlint or commitizen
+//! setup into the `lint` section of `.tbdflow.yml`, for teams moving over.
+//! Only rules tbdflow can enforce are carried across; the rest are listed.
+
+use crate::config_edit;
+use crate::git::RunOpts;
+use anyhow::{Context, Result, anyhow, bail};
+use colored::Colorize;
+use serde_json::Value as Json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use yaml_serde::Value;
+
+/// The tool whose configuration is imported.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ImportSource {
+    Commitlint,
+    Commitizen,
+}
+
+impl ImportSource {
+    /// Config files the tool reads, in the order it looks for them.
+    fn candidates(self) -> &'static [&'static str] {
+        match self {
+            ImportSource::Commitlint => &[
+                ".commitlintrc",
+                ".commitlintrc.json",
+                ".commitlintrc.yaml",
+                ".commitlintrc.yml",
+                "package.json",
+            ],
+            ImportSource::Commitizen => &[
+                "pyproject.toml",
+                ".cz.toml",
+                ".cz.json",
+                "cz.json",
+                ".cz.yaml",
+                "cz.yaml",
+                "cz.toml",
+            ],
+        }
+    }
+}
+
+/// commitlint configs written in JavaScript, which cannot be read here.
+const COMMITLINT_SCRIPTS: &[&str] = &[
+    "commitlint.config.js",
+    "commitlint.config.cjs",
+    "commitlint.config.mjs",
+    "commitlint.config.ts",
+    ".commitlintrc.js",
+    ".commitlintrc.cjs",
+    ".commitlintrc.ts",
+];
+
+/// The types `@commitlint/config-conventional` allows.
+const COMMITLINT_CONVENTIONAL_TYPES: &[&str] = &[
+    "build", "chore", "ci", "docs", "feat", "fix", "perf", "refactor", "revert", "style", "test",
+];
+
+/// The types `cz_conventional_commits` offers.
+const COMMITIZEN_CONVENTIONAL_TYPES: &[&str] = &[
+    "fix", "feat", "docs", "style", "refactor", "perf", "test", "build", "ci",
+];
+
+/// The translated settings, and what had no tbdflow equivalent.
+#[derive(Debug, Default, PartialEq)]
+pub struct Import {
+    /// Dotted keys under `lint`, each set once, in the order they were found.
+    pub settings: Vec<(String, Value)>,
+    /// Rules that were not carried across, with the reason.
+    pub skipped: Vec<String>,
+}
+
+impl Import {
+    fn set(&mut self, key: &str, value: impl Into<Value>) {
+        let key = format!("lint.{}", key);
+        let value = value.into();
+        match self.settings.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = value,
+            None => self.settings.push((key, value)),
+        }
+    }
+
+    fn allow_types(&mut self, types: Vec<String>) {
+        self.set("conventional_commit_type.enabled", true);
+        self.set(
+            "conventional_commit_type.allowed_types",
+            Value::Sequence(types.into_iter().map(Value::from).collect()),
+        );
+    }
+
+    fn skip(&mut self, what: String) {
+        self.skipped.push(what);
+    }
+}
+
+/// Reads the tool's config from `file`, or from the first file it would use
+/// at the repository root, as JSON. Returns the file read too.
+fn read_source(
+    git_root: &Path,
+    from: ImportSource,
+    file: Option<&Path>,
+) -> Result<(PathBuf, Json)> {
+    if let Some(file) = file {
+        let doc = parse_file(file, from)?.ok_or_else(|| {
+            anyhow!(
+                "{} has no {} settings.",
+                file.display(),
+                format!("{:?}", from).to_lowercase()
+            )
+        })?;
+        return Ok((file.to_path_buf(), doc));
+    }
+    for name in from.candidates() {
+        let path = git_root.join(name);
+        if path.is_file()
+            && let Some(doc) = parse_file(&path, from)?
+        {
+            return Ok((path, doc));
+        }
+    }
+    if from == ImportSource::Commitlint
+        && let Some(script) = COMMITLINT_SCRIPTS
+            .iter()
+            .find(|name| git_root.join(name).is_file())
+    {
+        bail!(
+            "{} is JavaScript, which tbdflow cannot read. Export its rules to a .commitlintrc.json and pass it with --file.",
+            script
+        );
+    }
+    bail!(
+        "No {} config found at the repository root (looked for {}).",
+        format!("{:?}", from).to_lowercase(),
+        from.candidates().join(", ")
+    )
+}
+
+/// The tool's settings in `path`, or `None` for a shared file such as
+/// `package.json` or `pyproject.toml` that has none.
+fn parse_file(path: &Path, from: ImportSource) -> Result<Option<Json>> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let doc: Json = if name.ends_with(".toml") {
+        let table: toml::Table =
+            toml::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))?;
+        serde_json::to_value(table)?
+    } else {
+        // YAML is a superset of JSON, so this reads both.
+        let value: Value = yaml_serde::from_str(&text)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        serde_json::to_value(value)?
+    };
+    let section = match (from, name.as_str()) {
+        (ImportSource::Commitlint, "package.json") => doc.get("commitlint").cloned(),
+        (ImportSource::Commitlint, _) => Some(doc),
+        (ImportSource::Commitizen, n) if n.ends_with(".toml") => doc
+            .get("tool")
+            .and_then(|tool| tool.get("commitizen"))
+            .cloned(),
+        (ImportSource::Commitizen, _) => doc.get("commitizen").cloned(),
+    };
+    Ok(section)
+}
+
+fn strings(value: &Json) -> Option<Vec<String>> {
+    match value {
+        Json::String(s) => Some(vec![s.clone()]),
+        Json::Array(items) => items
+            .iter()
+            .map(|v| v.as_str().map(str::to_string))
+            .collect(),
+        _ => None,
+    }
+}
+
+/// The rules `@commitlint/config-conventional` sets that tbdflow can enforce.
+fn conventional_preset() -> serde_json::Map<String, Json> {
+    let rules = serde_json::json!({
+        "type-enum": [2, "always", COMMITLINT_CONVENTIONAL_TYPES],
+        "header-max-length": [2, "always", 100],
+        "subject-case": [2, "never", ["sentence-case", "start-case", "pascal-case", "upper-case"]],
+        "subject-full-stop": [2, "never", "."],
+        "body-leading-blank": [1, "always"],
+        "body-max-line-length": [2, "always", 100],
+    });
+    match rules {
+        Json::Object(map) => map,
+        _ => unreachable!(),
+    }
+}
+
+/// Translates a commitlint config: its `rules`, over those of
+/// `@commitlint/config-conventional` when it extends that.
+pub fn from_commitlint(doc: &Json) -> Import {
+    let mut import = Import::default();
+    let mut rules = serde_json::Map::new();
+    for preset in doc.get("extends").and_then(strings).unwrap_or_default() {
+        if preset.ends_with("config-conventional") {
+            rules.extend(conventional_preset());
+        } else {
+            import.skip(format!(
+                "extends '{}' (only config-conventional is known)",
+                preset
+            ));
+        }
+    }
+    if let Some(Json::Object(own)) = doc.get("rules") {
+        rules.extend(own.clone());
+    }
+    // The header limit stands in for a subject limit, unless there is one.
+    let mut names: Vec<&String> = rules.keys().collect();
+    names.sort_by_key(|name| *name != "header-max-length");
+
+    for name in names {
+        let Some(rule) = rules[name].as_array() else {
+            import.skip(format!("{} (not in [level, when, value] form)", name));
+            continue;
+        };
+        if rule.first().and_then(Json::as_u64).unwrap_or(0) == 0 {
+            continue;
+        }
+        let always = rule.get(1).and_then(Json::as_str).unwrap_or("always") == "always";
+        let value = rule.get(2);
+        let number = value.and_then(Json::as_u64);
+        let listed = value.and_then(strings).unwrap_or_default();
+        let has = |case: &str| listed.iter().any(|c| c == case);
+        match name.as_str() {
+            "type-enum" if always && !listed.is_empty() => import.allow_types(listed),
+            "header-max-length" | "subject-max-length" if always && number.is_some() => {
+                import.set("subject_line_rules.max_length", number.unwrap())
+            }
+            "subject-full-stop" if !always && value.and_then(Json::as_str) == Some(".") => {
+                import.set("subject_line_rules.no_period", true)
+            }
+            "subject-case"
+                if (always && listed == ["lower-case"])
+                    || (!always && (has("sentence-case") || has("upper-case"))) =>
+            {
+                import.set("subject_line_rules.enforce_lowercase", true)
+            }
+            "scope-case" if always && listed == ["lower-case"] => {
+                import.set("scope.enabled", true);
+                import.set("scope.enforce_lowercase", true);
+            }
+            "body-max-line-length" if always && number.is_some() => {
+                import.set("body_line_rules.max_line_length", number.unwrap())
+            }
+            "body-leading-blank" if always => import.set("body_line_rules.leading_blank", true),
+            // Implied by conventional commits, which tbdflow always parses.
+            "type-empty" | "subject-empty" if !always => {}
+            "type-case" if always && listed == ["lower-case"] => {}
+            _ => import.skip(format!("{} (no tbdflow equivalent)", name)),
+        }
+    }
+    import
+}
+
+/// Translates commitizen settings: the commit types of the configured rule
+/// set and `message_length_limit`.
+pub fn from_commitizen(doc: &Json) -> Import {
+    let mut import = Import::default();
+    match doc
+        .get("name")
+        .and_then(Json::as_str)
+        .unwrap_or("cz_conventional_commits")
+    {
+        "cz_conventional_commits" => import.allow_types(
+            COMMITIZEN_CONVENTIONAL_TYPES
+                .iter()
+                .map(|t| t.to_string())
+                .collect(),
+        ),
+        "cz_customize" => {
+            let choices = doc
+                .pointer("/customize/questions")
+                .and_then(Json::as_array)
+                .and_then(|questions| {
+                    questions
+                        .iter()
+                        .find(|q| q.get("name").and_then(Json::as_str) == Some("change_type"))
+                })
+                .and_then(|q| q.get("choices"))
+                .and_then(Json::as_array);
+            let types: Vec<String> = choices
+                .into_iter()
+                .flatten()
+                .filter_map(|c| c.get("value").and_then(Json::as_str).map(str::to_string))
+                .collect();
+            if types.is_empty() {
+                import.skip("customize (no 'change_type' question with choices)".to_string());
+            } else {
+                import.allow_types(types);
+            }
+        }
+        other => import.skip(format!("name '{}' (a commitizen plugin)", other)),
+    }
+    if let Some(limit) = doc.get("message_length_limit").and_then(Json::as_u64)
+        && limit > 0
+    {
+        import.set("subject_line_rules.max_length", limit);
+    }
+    import
+}
+
+/// Reads the commitlint or commitizen config and writes what translates into
+/// the `lint` section of the root `.tbdflow.yml`.
+pub fn handle_config_import(
+    git_root: &Path,
+    from: ImportSource,
+    file: Option<&Path>,
+    opts: RunOpts,
+) -> Result<()> {
+    let (path, doc) = read_source(git_root, from, file)?;
+    let import = match from {
+        ImportSource::Commitlint => from_commitlint(&doc),
+        ImportSource::Commitizen => from_commitizen(&doc),
+    };
+    let source = path.strip_prefix(git_root).unwrap_or(&path).display();
+    if import.settings.is_empty() {
+        println!(
+            "{}",
+            format!("Nothing in {} translates to tbdflow lint rules.", source).yellow()
+        );
+    } else {
+        config_edit::set_all(
+            git_root,
+            &import.settings,
+            &format!(
+                "Imported {} lint settings from {}",
+                import.settings.len(),
+                source
+            ),
+            opts,
+        )?;
+    }
+    for skipped in &import.skipped {
+        println!("{}", format!("Not imported: {}", skipped).yellow());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(import: &Import) -> Vec<(&str, String)> {
+        import
+            .settings
+            .iter()
+            .map(|(k, v)| {
+                let v = serde_json::to_string(v).unwrap();
+                (k.as_str(), v)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn commitlint_rules_override_the_conventional_preset() {
+        let doc = serde_json::json!({
+            "extends": ["@commitlint/config-conventional", "./local"],
+            "rules": {
+                "type-enum": [2, "always", ["feat", "fix"]],
+                "subject-max-length": [2, "always", 50],
+                "scope-case": [2, "always", "lower-case"],
+                "body-leading-blank": [0, "always"],
+                "footer-leading-blank": [1, "always"]
+            }
+        });
+        let import = from_commitlint(&doc);
+        let settings = keys(&import);
+        assert!(settings.contains(&(
+            "lint.conventional_commit_type.allowed_types",
+            r#"["feat","fix"]"#.to_string()
+        )));
+        assert!(settings.contains(&("lint.subject_line_rules.max_length", "50".to_string())));
+        assert!(settings.contains(&("lint.subject_line_rules.no_period", "true".to_string())));
+        assert!(settings.contains(&(
+            "lint.subject_line_rules.enforce_lowercase",
+            "true".to_string()
+        )));
+        assert!(settings.contains(&("lint.scope.enforce_lowercase", "true".to_string())));
+        assert!(
+            !settings
+                .iter()
+                .any(|(k, _)| *k == "lint.body_line_rules.leading_blank")
+        );
+        assert_eq!(
+            import.skipped,
+            vec![
+                "extends './local' (only config-conventional is known)",
+                "footer-leading-blank (no tbdflow equivalent)"
+            ]
+        );
+    }
+
+    #[test]
+    fn commitizen_types_come_from_the_rule_set() {
+        let import = from_commitizen(&serde_json::json!({"message_length_limit": 72}));
+        let settings = keys(&import);
+        assert!(settings.contains(&(
+            "lint.conventional_commit_type.allowed_types",
+            r#"["fix","feat","docs","style","refactor","perf","test","build","ci"]"#.to_string()
+        )));
+        assert!(settings.contains(&("lint.subject_line_rules.max_length", "72".to_string())));
+
+        let custom = serde_json::json!({
+            "name": "cz_customize",
+            "customize": {"questions": [
+                {"type": "list", "name": "change_type", "choices": [
+                    {"value": "feature", "name": "feature: A new feature."},
+                    {"value": "bug fix", "name": "bug fix: A bug fix."}
+                ]}
+            ]}
+        });
+        let custom = from_commitizen(&custom);
+        let settings = keys(&custom);
+        assert!(settings.contains(&(
+            "lint.conventional_commit_type.allowed_types",
+            r#"["feature","bug fix"]"#.to_string()
+        )));
+
+        let plugin = from_commitizen(&serde_json::json!({"name": "cz_jira"}));
+        assert!(plugin.settings.is_empty());
+        assert_eq!(plugin.skipped, vec!["name 'cz_jira' (a commitizen plugin)"]);
+    }
+
+    #[test]
+    fn commitizen_settings_are_read_from_pyproject() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pyproject.toml");
+        fs::write(&path, "[project]\nname = \"app\"\n").unwrap();
+        assert_eq!(parse_file(&path, ImportSource::Commitizen).unwrap(), None);
+        fs::write(
+            &path,
+            "[tool.commitizen]\nname = \"cz_conventional_commits\"\nmessage_length_limit = 60\n",
+        )
+        .unwrap();
+        let doc = parse_file(&path, ImportSource::Commitizen)
+            .unwrap()
+            .unwrap();
+        assert_eq!(doc["message_length_limit"], 60);
+    }
+}
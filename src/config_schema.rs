@@ -0,0 +1,594 @@
+//! The JSON Schema for `.tbdflow.yml`, generated from [`Config`], and
+//! `tbdflow config validate`, which checks the file against it. serde ignores
+//! keys it does not know, so a typo would otherwise fall back to the default
+//! without a word.
+
+use crate::branch;
+use crate::command_defaults;
+use crate::commands::{ConfigProblemResponse, ConfigValidateResponse, TbdResponse};
+use crate::config::{self, Config, PROFILES_KEY};
+use crate::config_edit;
+use crate::extends::EXTENDS_KEY;
+use crate::git::RunOpts;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::{Map, Value, json};
+use std::fs;
+use std::path::Path;
+
+const CONFIG_FILE: &str = ".tbdflow.yml";
+
+/// The JSON Schema for `.tbdflow.yml`. Unknown keys are not allowed, so
+/// editors flag typos as they are made.
+pub fn schema() -> Value {
+    let mut schema =
+        serde_json::to_value(schemars::schema_for!(Config)).expect("a schema is valid JSON");
+    close_objects(&mut schema);
+    schema["title"] = json!(CONFIG_FILE);
+
+    let properties = schema["properties"].clone();
+    schema["$defs"]["Profile"] = json!({
+        "description": "Overrides merged over the base settings when this profile is selected.",
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    });
+    schema["properties"][PROFILES_KEY] = json!({
+        "description": "Named sets of overrides, selected with --profile or TBDFLOW_PROFILE.",
+        "type": "object",
+        "additionalProperties": { "$ref": "#/$defs/Profile" },
+    });
+    schema["properties"][EXTENDS_KEY] = json!({
+        "description": "Path or URL of a shared base config this file is merged over.",
+        "type": "string",
+    });
+    schema
+}
+
+/// Marks every object with known properties as closed to other keys.
+fn close_objects(schema: &mut Value) {
+    match schema {
+        Value::Object(map) => {
+            if map.contains_key("properties") && !map.contains_key("additionalProperties") {
+                map.insert("additionalProperties".to_string(), Value::Bool(false));
+            }
+            map.values_mut().for_each(close_objects);
+        }
+        Value::Array(items) => items.iter_mut().for_each(close_objects),
+        _ => {}
+    }
+}
+
+/// Something wrong with one key of the file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    /// Where in the file, when it can be told.
+    pub line: Option<usize>,
+    /// Dotted path to the key, e.g. `lint.scope.enabled` or `review.rules[0]`.
+    pub key: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+fn dotted(path: &[Segment]) -> String {
+    let mut key = String::new();
+    for segment in path {
+        match segment {
+            Segment::Key(k) if key.is_empty() => key.push_str(k),
+            Segment::Key(k) => {
+                key.push('.');
+                key.push_str(k);
+            }
+            Segment::Index(i) => key.push_str(&format!("[{}]", i)),
+        }
+    }
+    key
+}
+
+struct Validator<'a> {
+    defs: &'a Map<String, Value>,
+    /// A profile or a file with `extends:`, which may leave required keys to its base.
+    partial: bool,
+    problems: Vec<(Vec<Segment>, String)>,
+}
+
+impl Validator<'_> {
+    fn report(&mut self, path: &[Segment], message: String) {
+        self.problems.push((path.to_vec(), message));
+    }
+
+    /// Whether `value` matches `schema`, without reporting anything.
+    fn accepts(&self, schema: &Value, value: &Value) -> bool {
+        let mut trial = Validator {
+            defs: self.defs,
+            partial: self.partial,
+            problems: Vec::new(),
+        };
+        trial.check(schema, value, &mut Vec::new());
+        trial.problems.is_empty()
+    }
+
+    fn check(&mut self, schema: &Value, value: &Value, path: &mut Vec<Segment>) {
+        let schema = resolve(self.defs, schema);
+        if let Some(alternatives) = schema["anyOf"]
+            .as_array()
+            .or_else(|| schema["oneOf"].as_array())
+        {
+            if alternatives.iter().any(|alt| self.accepts(alt, value)) {
+                return;
+            }
+            // `Option<T>` is `T` or null: report against `T` for a precise message.
+            let not_null: Vec<&Value> = alternatives
+                .iter()
+                .filter(|alt| alt["type"] != "null")
+                .collect();
+            if let [only] = not_null[..] {
+                return self.check(only, value, path);
+            }
+            let allowed: Vec<String> = alternatives.iter().flat_map(allowed_values).collect();
+            let message = if allowed.is_empty() {
+                format!("must be {}", describe_types(alternatives))
+            } else {
+                format!("must be one of: {}", allowed.join(", "))
+            };
+            return self.report(path, message);
+        }
+        if let Some(allowed) = schema["enum"].as_array()
+            && !allowed.contains(value)
+        {
+            let allowed: Vec<String> = allowed.iter().map(show).collect();
+            return self.report(path, format!("must be one of: {}", allowed.join(", ")));
+        }
+        if let Some(expected) = schema.get("const")
+            && expected != value
+        {
+            return self.report(path, format!("must be {}", show(expected)));
+        }
+        if let Some(types) = schema.get("type")
+            && !type_matches(types, value)
+        {
+            let message = format!(
+                "must be {}, not {}",
+                describe_types(std::slice::from_ref(schema)),
+                type_name(value)
+            );
+            return self.report(path, message);
+        }
+        if let (Some(minimum), Some(n)) = (schema["minimum"].as_f64(), value.as_f64())
+            && n < minimum
+        {
+            return self.report(path, format!("must be at least {}", minimum));
+        }
+
+        match value {
+            Value::Object(map) => self.check_object(schema, map, path),
+            Value::Array(items) => {
+                if let Some(item_schema) = schema.get("items") {
+                    for (i, item) in items.iter().enumerate() {
+                        path.push(Segment::Index(i));
+                        self.check(item_schema, item, path);
+                        path.pop();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_object(&mut self, schema: &Value, map: &Map<String, Value>, path: &mut Vec<Segment>) {
+        let properties = schema["properties"].as_object();
+        for (key, value) in map {
+            path.push(Segment::Key(key.clone()));
+            match (
+                properties.and_then(|p| p.get(key)),
+                &schema["additionalProperties"],
+            ) {
+                (Some(property), _) => self.check(property, value, path),
+                (None, Value::Bool(false)) => {
+                    let known: Vec<&str> = properties
+                        .map(|p| p.keys().map(String::as_str).collect())
+                        .unwrap_or_default();
+                    let mut message = "is not a known setting".to_string();
+                    if let Some(closest) = closest_key(key, &known) {
+                        message.push_str(&format!(" (did you mean '{}'?)", closest));
+                    }
+                    self.report(path, message);
+                }
+                (None, additional) if additional.is_object() => self.check(additional, value, path),
+                (None, _) => {}
+            }
+            path.pop();
+        }
+        if self.partial {
+            return;
+        }
+        for required in schema["required"].as_array().into_iter().flatten() {
+            if let Some(key) = required.as_str()
+                && !map.contains_key(key)
+            {
+                path.push(Segment::Key(key.to_string()));
+                self.report(path, "is required".to_string());
+                path.pop();
+            }
+        }
+    }
+}
+
+/// Follows a `$ref` into `defs`.
+fn resolve<'s>(defs: &'s Map<String, Value>, schema: &'s Value) -> &'s Value {
+    match schema["$ref"]
+        .as_str()
+        .and_then(|r| r.strip_prefix("#/$defs/"))
+    {
+        Some(name) => defs.get(name).unwrap_or(&Value::Null),
+        None => schema,
+    }
+}
+
+fn closest_key<'a>(key: &str, known: &[&'a str]) -> Option<&'a str> {
+    known
+        .iter()
+        .map(|candidate| (branch::edit_distance(key, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, candidate)| (*distance, *candidate))
+        .map(|(_, candidate)| candidate)
+}
+
+fn show(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// The values an enum-like alternative allows, e.g. from `{"const": "gix"}`.
+fn allowed_values(schema: &Value) -> Vec<String> {
+    if let Some(value) = schema.get("const") {
+        return vec![show(value)];
+    }
+    schema["enum"]
+        .as_array()
+        .map(|values| values.iter().map(show).collect())
+        .unwrap_or_default()
+}
+
+fn type_matches(types: &Value, value: &Value) -> bool {
+    let matches = |t: &str| match t {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    };
+    match types {
+        Value::String(t) => matches(t),
+        Value::Array(ts) => ts.iter().filter_map(Value::as_str).any(matches),
+        _ => true,
+    }
+}
+
+fn type_phrase(t: &str) -> &'static str {
+    match t {
+        "null" => "empty",
+        "boolean" => "true or false",
+        "integer" => "a whole number",
+        "number" => "a number",
+        "string" => "text",
+        "array" => "a list",
+        "object" => "a map of keys",
+        _ => "something else",
+    }
+}
+
+fn describe_types(schemas: &[Value]) -> String {
+    let mut phrases: Vec<&str> = Vec::new();
+    for schema in schemas {
+        let types = match &schema["type"] {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        for t in types.into_iter().filter(|t| *t != "null") {
+            let phrase = type_phrase(t);
+            if !phrases.contains(&phrase) {
+                phrases.push(phrase);
+            }
+        }
+    }
+    if phrases.is_empty() {
+        "a valid value".to_string()
+    } else {
+        phrases.join(" or ")
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "empty",
+        Value::Bool(_) => "true/false",
+        Value::Number(n) if n.is_f64() => "a decimal number",
+        Value::Number(_) => "a number",
+        Value::String(_) => "text",
+        Value::Array(_) => "a list",
+        Value::Object(_) => "a map",
+    }
+}
+
+/// Checks the YAML in `content` against [`schema`]. A file with `extends:`
+/// may leave required keys to its base.
+pub fn validate(content: &str) -> Result<Vec<Problem>> {
+    let yaml: yaml_serde::Value = match yaml_serde::from_str(content) {
+        Ok(yaml) => yaml,
+        Err(e) => {
+            return Ok(vec![Problem {
+                line: e.location().map(|l| l.line()),
+                key: String::new(),
+                message: format!("is not valid YAML: {}", e),
+            }]);
+        }
+    };
+    let value = match serde_json::to_value(&yaml) {
+        Ok(value) => value,
+        Err(e) => {
+            return Ok(vec![Problem {
+                line: None,
+                key: String::new(),
+                message: format!("cannot be read: {}", e),
+            }]);
+        }
+    };
+    let schema = schema();
+    let defs = schema["$defs"].as_object().cloned().unwrap_or_default();
+    let mut validator = Validator {
+        defs: &defs,
+        partial: value.get(EXTENDS_KEY).is_some(),
+        problems: Vec::new(),
+    };
+    if value.is_null() {
+        validator.check(&schema, &json!({}), &mut Vec::new());
+    } else {
+        validator.check(&schema, &value, &mut Vec::new());
+    }
+    // Profiles only override, so nothing is required of them.
+    if let Some(Value::Object(profiles)) = value.get(PROFILES_KEY) {
+        validator.problems.retain(
+            |(path, _)| !matches!(path.first(), Some(Segment::Key(k)) if k == PROFILES_KEY),
+        );
+        validator.partial = true;
+        for (name, profile) in profiles {
+            let mut path = vec![
+                Segment::Key(PROFILES_KEY.to_string()),
+                Segment::Key(name.clone()),
+            ];
+            validator.check(&defs["Profile"], profile, &mut path);
+        }
+    }
+
+    Ok(validator
+        .problems
+        .into_iter()
+        .map(|(path, message)| {
+            let keys: Vec<&str> = path
+                .iter()
+                .map_while(|segment| match segment {
+                    Segment::Key(k) => Some(k.as_str()),
+                    Segment::Index(_) => None,
+                })
+                .collect();
+            Problem {
+                line: config_edit::line_of(content, &keys),
+                key: dotted(&path),
+                message,
+            }
+        })
+        .collect())
+}
+
+/// Checks a partial config, such as the overrides built from one `TBDFLOW_*`
+/// variable, like a profile: every key must be known, none is required.
+/// Returns the dotted key and message of each problem.
+pub fn check_overrides(overrides: &yaml_serde::Value) -> Vec<(String, String)> {
+    let value = match serde_json::to_value(overrides) {
+        Ok(value) => value,
+        Err(e) => return vec![(String::new(), format!("cannot be read: {}", e))],
+    };
+    let schema = schema();
+    let defs = schema["$defs"].as_object().cloned().unwrap_or_default();
+    let mut validator = Validator {
+        defs: &defs,
+        partial: true,
+        problems: Vec::new(),
+    };
+    validator.check(&defs["Profile"], &value, &mut Vec::new());
+    validator
+        .problems
+        .into_iter()
+        .map(|(path, message)| (dotted(&path), message))
+        .collect()
+}
+
+/// `tbdflow config validate`: checks the root `.tbdflow.yml` against the
+/// schema, then loads it as tbdflow would. Exits non-zero on any problem.
+pub fn handle_config_validate(git_root: &Path, json: bool, opts: RunOpts) -> Result<()> {
+    let file = git_root.join(CONFIG_FILE);
+    if !file.exists() {
+        anyhow::bail!(
+            "There is no {} at the repository root. Run 'tbdflow init' to create one.",
+            CONFIG_FILE
+        );
+    }
+    let content =
+        fs::read_to_string(&file).with_context(|| format!("Failed to read {}", file.display()))?;
+    let mut problems = validate(&content)?;
+    if problems.is_empty() {
+        match config::parse_root_config(&content, git_root, None, opts) {
+            Ok((config, _)) => {
+                for (key, message) in command_defaults::check(&config.defaults) {
+                    let keys: Vec<&str> = key.split('.').collect();
+                    problems.push(Problem {
+                        line: config_edit::line_of(&content, &keys),
+                        key,
+                        message,
+                    });
+                }
+            }
+            Err(e) => problems.push(Problem {
+                line: None,
+                key: String::new(),
+                message: format!("cannot be loaded: {:#}", e),
+            }),
+        }
+    }
+
+    if json {
+        let response = ConfigValidateResponse {
+            file: CONFIG_FILE.to_string(),
+            valid: problems.is_empty(),
+            problems: problems
+                .iter()
+                .map(|p| ConfigProblemResponse {
+                    line: p.line,
+                    key: p.key.clone(),
+                    message: p.message.clone(),
+                })
+                .collect(),
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(response))?
+        );
+    } else if problems.is_empty() {
+        println!("{}", format!("{} is valid.", CONFIG_FILE).green());
+    } else {
+        for problem in &problems {
+            let location = match problem.line {
+                Some(line) => format!("{}:{}", CONFIG_FILE, line),
+                None => CONFIG_FILE.to_string(),
+            };
+            let subject = if problem.key.is_empty() {
+                "The file".to_string()
+            } else {
+                format!("'{}'", problem.key)
+            };
+            println!("{}: {} {}", location.bold(), subject, problem.message);
+        }
+        println!(
+            "{}",
+            format!("{} problem(s) found in {}.", problems.len(), CONFIG_FILE).red()
+        );
+    }
+    if !problems.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "\
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: \"feat/\"
+automatic_tags:
+  release_prefix: \"v\"
+";
+
+    fn problems(extra: &str) -> Vec<Problem> {
+        validate(&format!("{}{}", BASE, extra)).unwrap()
+    }
+
+    #[test]
+    fn a_valid_file_has_no_problems() {
+        assert_eq!(problems(""), vec![]);
+        assert_eq!(
+            problems("lint:\n  subject_line_rules:\n    max_length: 50\n"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn unknown_keys_are_reported_with_a_suggestion() {
+        let found = problems("lint:\n  subject_line_rules:\n    max_lenght: 50\n");
+        assert_eq!(
+            found,
+            vec![Problem {
+                line: Some(9),
+                key: "lint.subject_line_rules.max_lenght".to_string(),
+                message: "is not a known setting (did you mean 'max_length'?)".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn type_mismatches_are_reported() {
+        let found = problems("review:\n  enabled: yes please\n  strategy: github\n");
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].key, "review.enabled");
+        assert_eq!(found[0].line, Some(8));
+        assert_eq!(found[0].message, "must be true or false, not text");
+        assert_eq!(found[1].key, "review.strategy");
+        assert!(
+            found[1]
+                .message
+                .starts_with("must be one of: github-issue, github-workflow"),
+            "Message was: {}",
+            found[1].message
+        );
+    }
+
+    #[test]
+    fn keys_after_an_indentless_sequence_are_reported_on_their_own_line() {
+        let found = problems(
+            "review:\n  exempt_types:\n  - docs\n  enabld: true\n  large_commit_lines: lots\n",
+        );
+        let lines: Vec<(&str, Option<usize>)> =
+            found.iter().map(|p| (p.key.as_str(), p.line)).collect();
+        assert_eq!(
+            lines,
+            vec![
+                ("review.enabld", Some(10)),
+                ("review.large_commit_lines", Some(11))
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_required_keys_are_reported_unless_extended() {
+        let found = validate("main_branch_name: main\n").unwrap();
+        let keys: Vec<&str> = found.iter().map(|p| p.key.as_str()).collect();
+        assert!(keys.contains(&"stale_branch_threshold_days"));
+        assert!(found.iter().all(|p| p.message == "is required"));
+
+        assert_eq!(
+            validate("extends: ../base.yml\nmain_branch_name: main\n").unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn profiles_are_checked_as_overrides() {
+        let found = problems("profiles:\n  solo:\n    commit:\n      dod: nope\n");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].key, "profiles.solo.commit.dod");
+        assert_eq!(found[0].line, Some(10));
+    }
+
+    #[test]
+    fn schema_closes_known_sections() {
+        let schema = schema();
+        assert_eq!(schema["additionalProperties"], json!(false));
+        assert_eq!(schema["properties"]["extends"]["type"], json!("string"));
+        assert!(schema["$defs"]["LintConfig"]["properties"]["subject_line_rules"].is_object());
+    }
+}
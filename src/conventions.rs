@@ -0,0 +1,307 @@
+//! Infers the conventions a repository already follows, so `tbdflow init`
+//! can propose a `.tbdflow.yml` that matches current practice.
+
+use crate::config::Config;
+use crate::git::{self, RunOpts};
+use anyhow::Result;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// How many recent commits are scanned.
+pub const HISTORY_LIMIT: usize = 200;
+/// A commit type must appear at least this often to be adopted.
+const MIN_TYPE_USES: usize = 2;
+/// The subject length lint rule is only relaxed above this.
+const DEFAULT_SUBJECT_MAX: usize = 72;
+const WELL_KNOWN_MAIN_BRANCHES: &[&str] = &["main", "master", "trunk", "develop"];
+
+/// What the history says about how this repository is used.
+#[derive(Debug, Default, PartialEq)]
+pub struct Conventions {
+    pub main_branch: Option<String>,
+    /// Commit types by number of uses, most used first.
+    pub commit_types: Vec<(String, usize)>,
+    /// Scopes by number of uses, most used first.
+    pub scopes: Vec<(String, usize)>,
+    pub scanned: usize,
+    pub conventional: usize,
+    /// Conventional subjects whose description starts with a capital letter.
+    pub capitalised: usize,
+    /// 95th percentile subject length.
+    pub subject_length_p95: usize,
+    pub tag_prefix: Option<String>,
+}
+
+fn count_sorted(counts: HashMap<String, usize>) -> Vec<(String, usize)> {
+    let mut sorted: Vec<(String, usize)> = counts.into_iter().collect();
+    sorted.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    sorted
+}
+
+/// The trunk: what `origin/HEAD` points at, else a well-known name that
+/// exists locally, else the current branch.
+pub fn infer_main_branch(
+    origin_head: Option<String>,
+    local_branches: &[String],
+    current: &str,
+) -> Option<String> {
+    origin_head
+        .or_else(|| {
+            WELL_KNOWN_MAIN_BRANCHES
+                .iter()
+                .find(|name| local_branches.iter().any(|b| b == *name))
+                .map(|name| name.to_string())
+        })
+        .or_else(|| (!current.is_empty()).then(|| current.to_string()))
+}
+
+/// The most common text in front of the version number in release tags
+/// (`v` for `v1.2.0`, empty for `1.2.0`). `None` when no tag looks like a release.
+pub fn infer_tag_prefix(tags: &[String]) -> Option<String> {
+    let version = Regex::new(r"^(\D*?)\d+(\.\d+)+").expect("valid regex");
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for tag in tags {
+        if let Some(caps) = version.captures(tag) {
+            *counts.entry(caps[1].to_string()).or_default() += 1;
+        }
+    }
+    count_sorted(counts)
+        .into_iter()
+        .next()
+        .map(|(prefix, _)| prefix)
+}
+
+/// Tallies commit types, scopes, capitalisation and length across `subjects`.
+pub fn analyse_subjects(subjects: &[String]) -> Conventions {
+    let conventional = Regex::new(r"^([a-zA-Z]+)(?:\(([^)]+)\))?!?: (.+)$").expect("valid regex");
+    let mut types: HashMap<String, usize> = HashMap::new();
+    let mut scopes: HashMap<String, usize> = HashMap::new();
+    let mut stats = Conventions {
+        scanned: subjects.len(),
+        ..Default::default()
+    };
+
+    for subject in subjects {
+        let Some(caps) = conventional.captures(subject) else {
+            continue;
+        };
+        stats.conventional += 1;
+        *types.entry(caps[1].to_lowercase()).or_default() += 1;
+        if let Some(scope) = caps.get(2) {
+            *scopes.entry(scope.as_str().to_string()).or_default() += 1;
+        }
+        if caps[3].chars().next().is_some_and(char::is_uppercase) {
+            stats.capitalised += 1;
+        }
+    }
+
+    let mut lengths: Vec<usize> = subjects.iter().map(|s| s.chars().count()).collect();
+    lengths.sort_unstable();
+    if !lengths.is_empty() {
+        let index = (lengths.len() * 95).div_ceil(100).saturating_sub(1);
+        stats.subject_length_p95 = lengths[index];
+    }
+
+    stats.commit_types = count_sorted(types);
+    stats.scopes = count_sorted(scopes);
+    stats
+}
+
+/// Scans the current repository. `None` when there is no history to learn from.
+pub fn detect(opts: RunOpts) -> Result<Option<Conventions>> {
+    let subjects = git::get_recent_subjects(HISTORY_LIMIT, opts).unwrap_or_default();
+    if subjects.is_empty() {
+        return Ok(None);
+    }
+    let mut conventions = analyse_subjects(&subjects);
+    conventions.main_branch = infer_main_branch(
+        git::get_origin_head_branch(opts),
+        &git::list_local_branch_names(opts).unwrap_or_default(),
+        &git::get_current_branch(opts).unwrap_or_default(),
+    );
+    conventions.tag_prefix = infer_tag_prefix(&git::list_tags(opts).unwrap_or_default());
+    Ok(Some(conventions))
+}
+
+impl Conventions {
+    /// True when at least half of the scanned commits are Conventional Commits.
+    pub fn is_conventional(&self) -> bool {
+        self.scanned > 0 && self.conventional * 2 >= self.scanned
+    }
+
+    fn adopted_types(&self) -> Vec<String> {
+        let mut types: Vec<String> = self
+            .commit_types
+            .iter()
+            .filter(|(_, uses)| *uses >= MIN_TYPE_USES)
+            .map(|(name, _)| name.clone())
+            .collect();
+        types.sort();
+        types
+    }
+
+    /// Adjusts `config` to match what the history shows.
+    pub fn apply(&self, config: &mut Config) {
+        if let Some(main) = &self.main_branch {
+            config.main_branch_name = main.clone();
+        }
+        if let Some(prefix) = &self.tag_prefix {
+            config.automatic_tags.release_prefix = prefix.clone();
+        }
+        let Some(lint) = config.lint.as_mut() else {
+            return;
+        };
+
+        if self.is_conventional() {
+            let types = self.adopted_types();
+            if let Some(cct) = lint.conventional_commit_type.as_mut()
+                && !types.is_empty()
+            {
+                cct.allowed_types = Some(types);
+            }
+            if let Some(scope) = lint.scope.as_mut()
+                && self
+                    .scopes
+                    .iter()
+                    .any(|(s, _)| s.chars().any(char::is_uppercase))
+            {
+                scope.enforce_lowercase = Some(false);
+            }
+            if let Some(rules) = lint.subject_line_rules.as_mut()
+                && self.capitalised * 2 > self.conventional
+            {
+                rules.enforce_lowercase = Some(false);
+            }
+        }
+        if let Some(rules) = lint.subject_line_rules.as_mut()
+            && self.subject_length_p95 > DEFAULT_SUBJECT_MAX
+        {
+            rules.max_length = Some(self.subject_length_p95.div_ceil(10) * 10);
+        }
+    }
+
+    /// Human-readable lines describing what was detected.
+    pub fn describe(&self) -> Vec<String> {
+        let list = |items: &[(String, usize)]| {
+            items
+                .iter()
+                .take(8)
+                .map(|(name, uses)| format!("{} ({})", name, uses))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut lines = Vec::new();
+        if let Some(main) = &self.main_branch {
+            lines.push(format!("Main branch: {}", main));
+        }
+        lines.push(format!(
+            "Conventional Commits: {} of {} recent commits",
+            self.conventional, self.scanned
+        ));
+        if self.is_conventional() {
+            if !self.commit_types.is_empty() {
+                lines.push(format!("Commit types: {}", list(&self.commit_types)));
+            }
+            if !self.scopes.is_empty() {
+                lines.push(format!("Scopes: {}", list(&self.scopes)));
+            }
+        }
+        if self.subject_length_p95 > DEFAULT_SUBJECT_MAX {
+            lines.push(format!(
+                "Subject length: 95% of subjects fit in {} characters",
+                self.subject_length_p95
+            ));
+        }
+        if let Some(prefix) = &self.tag_prefix {
+            let shown = if prefix.is_empty() { "(none)" } else { prefix };
+            lines.push(format!("Tag prefix: {}", shown));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn main_branch_prefers_origin_head_then_well_known_names() {
+        let branches = strings(&["feature-x", "master"]);
+        assert_eq!(
+            infer_main_branch(Some("trunk".to_string()), &branches, "feature-x").as_deref(),
+            Some("trunk")
+        );
+        assert_eq!(
+            infer_main_branch(None, &branches, "feature-x").as_deref(),
+            Some("master")
+        );
+        assert_eq!(
+            infer_main_branch(None, &strings(&["dev"]), "dev").as_deref(),
+            Some("dev")
+        );
+    }
+
+    #[test]
+    fn tag_prefix_is_the_most_common_one() {
+        let tags = strings(&["release-1.0.0", "release-1.1.0", "v0.9.0", "nightly"]);
+        assert_eq!(infer_tag_prefix(&tags).as_deref(), Some("release-"));
+        assert_eq!(infer_tag_prefix(&strings(&["1.2.0"])).as_deref(), Some(""));
+        assert_eq!(infer_tag_prefix(&strings(&["nightly"])), None);
+    }
+
+    #[test]
+    fn conventional_history_narrows_types_and_relaxes_casing() {
+        let subjects = strings(&[
+            "feat(API): Add users endpoint",
+            "feat: Add login",
+            "fix(API): Handle timeouts",
+            "fix: Typo",
+            "build: bump deps",
+            "Merge branch 'x'",
+        ]);
+        let conventions = analyse_subjects(&subjects);
+        assert!(conventions.is_conventional());
+        assert_eq!(conventions.commit_types[0], ("feat".to_string(), 2));
+
+        let mut config = Config::default();
+        conventions.apply(&mut config);
+        let lint = config.lint.unwrap();
+        assert_eq!(
+            lint.conventional_commit_type.unwrap().allowed_types,
+            Some(strings(&["feat", "fix"]))
+        );
+        assert_eq!(lint.scope.unwrap().enforce_lowercase, Some(false));
+        assert_eq!(
+            lint.subject_line_rules.unwrap().enforce_lowercase,
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn free_form_history_keeps_default_types() {
+        let subjects = strings(&["Add login", "Fix typo", "feat: search"]);
+        let conventions = analyse_subjects(&subjects);
+        assert!(!conventions.is_conventional());
+
+        let mut config = Config::default();
+        let default_types = config
+            .lint
+            .as_ref()
+            .and_then(|l| l.conventional_commit_type.as_ref())
+            .and_then(|c| c.allowed_types.clone());
+        conventions.apply(&mut config);
+        assert_eq!(
+            config
+                .lint
+                .unwrap()
+                .conventional_commit_type
+                .unwrap()
+                .allowed_types,
+            default_types
+        );
+    }
+}
@@ -0,0 +1,432 @@
+use crate::commands::{DoctorCheckResponse, DoctorResponse, TbdResponse};
+use crate::config::Config;
+use crate::gh;
+use crate::git;
+use crate::git::RunOpts;
+use crate::toolchain::CommitToolchain;
+use anyhow::Result;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warn => "warn",
+            CheckStatus::Fail => "fail",
+        }
+    }
+}
+
+/// The outcome of a single diagnostic.
+#[derive(Debug, Clone)]
+pub struct DoctorCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+    /// How to fix it, shown for warnings and failures.
+    pub hint: Option<String>,
+}
+
+impl DoctorCheck {
+    fn ok(name: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Ok,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: &'static str, message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(name: &'static str, message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+fn check_git_installed(opts: RunOpts) -> DoctorCheck {
+    match git::get_git_version(opts) {
+        Ok(version) => DoctorCheck::ok("git", version),
+        Err(_) => DoctorCheck::fail(
+            "git",
+            "git was not found on PATH.",
+            "Install git from https://git-scm.com/downloads.",
+        ),
+    }
+}
+
+fn check_repository(opts: RunOpts) -> DoctorCheck {
+    if git::is_git_repository(opts).is_err() {
+        return DoctorCheck::warn(
+            "repository",
+            "Not inside a git repository.",
+            "Run 'tbdflow init' to set one up.",
+        );
+    }
+    match git::get_remote_url(opts) {
+        Ok(url) if !url.is_empty() => DoctorCheck::ok("repository", format!("origin is {}", url)),
+        _ => DoctorCheck::warn(
+            "repository",
+            "No 'origin' remote is configured, so pushes will fail.",
+            "git remote add origin <url>",
+        ),
+    }
+}
+
+fn check_identity(opts: RunOpts) -> DoctorCheck {
+    let name = git::get_config_value("user.name", opts);
+    let email = git::get_config_value("user.email", opts);
+    match (name, email) {
+        (Some(name), Some(email)) => DoctorCheck::ok("identity", format!("{} <{}>", name, email)),
+        _ => DoctorCheck::fail(
+            "identity",
+            "user.name and user.email must both be set to commit.",
+            "git config --global user.name \"Your Name\" && git config --global user.email you@example.com",
+        ),
+    }
+}
+
+fn check_gh_cli(config: &Config) -> DoctorCheck {
+    let needed = config.review.enabled || config.ci_check.enabled;
+    match (gh::is_available(), needed) {
+        (true, _) => DoctorCheck::ok("gh", "GitHub CLI is installed."),
+        (false, true) => DoctorCheck::warn(
+            "gh",
+            "GitHub CLI is not installed, but review or ci_check is enabled.",
+            gh::GH.install_hint,
+        ),
+        (false, false) => DoctorCheck::ok("gh", "GitHub CLI not installed (optional)."),
+    }
+}
+
+/// Two linters with different rules make commits fail in confusing ways.
+pub fn evaluate_commit_toolchain(toolchain: &CommitToolchain, tbdflow_lints: bool) -> DoctorCheck {
+    if toolchain.is_empty() {
+        return DoctorCheck::ok("commit hooks", "No husky or commitlint setup found.");
+    }
+    if toolchain.commit_msg_runs_tbdflow() {
+        return DoctorCheck::ok(
+            "commit hooks",
+            format!("{}; commit-msg runs tbdflow.", toolchain.describe()),
+        );
+    }
+    match (toolchain.lints_messages(), tbdflow_lints) {
+        (true, true) => DoctorCheck::warn(
+            "commit hooks",
+            format!(
+                "{} and tbdflow both lint commit messages; their rules can disagree.",
+                toolchain.describe()
+            ),
+            "Let one tool own the rules: make .husky/commit-msg run 'tbdflow verify-commit-msg \"$1\"', or remove 'lint:' from .tbdflow.yml.",
+        ),
+        (true, false) => DoctorCheck::ok(
+            "commit hooks",
+            format!(
+                "{} owns commit message rules; tbdflow linting is off.",
+                toolchain.describe()
+            ),
+        ),
+        (false, _) => DoctorCheck::ok("commit hooks", toolchain.describe()),
+    }
+}
+
+fn check_commit_toolchain(config: &Config, opts: RunOpts) -> DoctorCheck {
+    let toolchain = git::get_git_root(opts)
+        .map(|root| CommitToolchain::detect(Path::new(&root)))
+        .unwrap_or_default();
+    evaluate_commit_toolchain(&toolchain, config.lint.is_some())
+}
+
+/// Without line-ending normalisation, Windows checkouts commit CRLF files.
+pub fn evaluate_autocrlf(value: Option<&str>, normalised_by_gitattributes: bool) -> DoctorCheck {
+    match value {
+        _ if normalised_by_gitattributes => DoctorCheck::ok(
+            "core.autocrlf",
+            "Line endings are normalised by .gitattributes.",
+        ),
+        Some("true") => DoctorCheck::ok("core.autocrlf", "true"),
+        Some("input") => DoctorCheck::ok("core.autocrlf", "input (LF in the working tree)"),
+        other => DoctorCheck::warn(
+            "core.autocrlf",
+            format!(
+                "core.autocrlf is {}; CRLF line endings may be committed and show up as whole-file diffs.",
+                other.unwrap_or("unset")
+            ),
+            "git config --global core.autocrlf true, or add '* text=auto' to .gitattributes.",
+        ),
+    }
+}
+
+/// Paths over 260 characters need both git and Windows to opt in.
+pub fn evaluate_long_paths(core_longpaths: Option<&str>, os_enabled: Option<bool>) -> DoctorCheck {
+    match (core_longpaths == Some("true"), os_enabled) {
+        (true, Some(true)) => DoctorCheck::ok("long paths", "Enabled in git and Windows."),
+        (true, None) => DoctorCheck::ok(
+            "long paths",
+            "Enabled in git (could not read the Windows setting).",
+        ),
+        (false, _) => DoctorCheck::warn(
+            "long paths",
+            "core.longpaths is not enabled; deep paths fail with 'Filename too long'.",
+            "git config --global core.longpaths true",
+        ),
+        (true, Some(false)) => DoctorCheck::warn(
+            "long paths",
+            "core.longpaths is set, but Windows long path support is disabled.",
+            "Set HKLM\\SYSTEM\\CurrentControlSet\\Control\\FileSystem\\LongPathsEnabled to 1 (as administrator).",
+        ),
+    }
+}
+
+/// tbdflow pushes on every commit; without a helper git prompts each time.
+pub fn evaluate_credential_helper(helper: Option<&str>) -> DoctorCheck {
+    match helper {
+        Some(h) if h.contains("manager") => DoctorCheck::ok(
+            "credential helper",
+            format!("{} (Git Credential Manager)", h),
+        ),
+        Some(h) => DoctorCheck::ok("credential helper", h.to_string()),
+        None => DoctorCheck::warn(
+            "credential helper",
+            "No credential helper is configured; pushes may hang waiting for a password.",
+            "Install Git Credential Manager and run 'git config --global credential.helper manager'.",
+        ),
+    }
+}
+
+pub fn evaluate_powershell_completion(profile: Option<&str>) -> DoctorCheck {
+    match profile {
+        Some(content) if content.contains("tbdflow completion powershell") => DoctorCheck::ok(
+            "PowerShell completion",
+            "Installed in your PowerShell profile.",
+        ),
+        _ => DoctorCheck::warn(
+            "PowerShell completion",
+            "tbdflow completions are not loaded by your PowerShell profile.",
+            "Add-Content $PROFILE 'tbdflow completion powershell | Out-String | Invoke-Expression'",
+        ),
+    }
+}
+
+fn has_text_auto_gitattributes(opts: RunOpts) -> bool {
+    git::get_git_root(opts)
+        .ok()
+        .map(|root| Path::new(&root).join(".gitattributes"))
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .is_some_and(|content| content.lines().any(|l| l.contains("text=auto")))
+}
+
+/// Reads `LongPathsEnabled` from the registry; `None` if it cannot be queried.
+fn windows_long_paths_enabled() -> Option<bool> {
+    let output = Command::new("reg")
+        .args([
+            "query",
+            r"HKLM\SYSTEM\CurrentControlSet\Control\FileSystem",
+            "/v",
+            "LongPathsEnabled",
+        ])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).contains("0x1"))
+}
+
+/// Concatenates the Windows PowerShell and PowerShell 7 profiles, if any exist.
+fn read_powershell_profiles() -> Option<String> {
+    let documents = PathBuf::from(std::env::var_os("USERPROFILE")?).join("Documents");
+    let profiles: Vec<String> = ["PowerShell", "WindowsPowerShell"]
+        .iter()
+        .map(|dir| documents.join(dir).join("Microsoft.PowerShell_profile.ps1"))
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .collect();
+    if profiles.is_empty() {
+        None
+    } else {
+        Some(profiles.join("\n"))
+    }
+}
+
+fn windows_checks(opts: RunOpts) -> Vec<DoctorCheck> {
+    vec![
+        evaluate_autocrlf(
+            git::get_config_value("core.autocrlf", opts).as_deref(),
+            has_text_auto_gitattributes(opts),
+        ),
+        evaluate_long_paths(
+            git::get_config_value("core.longpaths", opts).as_deref(),
+            windows_long_paths_enabled(),
+        ),
+        evaluate_credential_helper(git::get_config_value("credential.helper", opts).as_deref()),
+        evaluate_powershell_completion(read_powershell_profiles().as_deref()),
+    ]
+}
+
+pub fn run_checks(config: &Config, opts: RunOpts) -> Vec<DoctorCheck> {
+    let mut checks = vec![
+        check_git_installed(opts),
+        check_repository(opts),
+        check_identity(opts),
+        check_gh_cli(config),
+        check_commit_toolchain(config, opts),
+    ];
+    if cfg!(windows) {
+        checks.extend(windows_checks(opts));
+    }
+    checks
+}
+
+/// Diagnoses common environment problems. Fails when any check fails;
+/// warnings are reported but do not change the exit code.
+pub fn handle_doctor(config: &Config, opts: RunOpts, json: bool) -> Result<()> {
+    let checks = run_checks(config, opts);
+    let failures = checks
+        .iter()
+        .filter(|c| c.status == CheckStatus::Fail)
+        .count();
+
+    if json {
+        let response = DoctorResponse {
+            healthy: failures == 0,
+            checks: checks
+                .iter()
+                .map(|c| DoctorCheckResponse {
+                    name: c.name.to_string(),
+                    status: c.status.as_str().to_string(),
+                    message: c.message.clone(),
+                    hint: c.hint.clone(),
+                })
+                .collect(),
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(response))?
+        );
+        if failures > 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    println!("{}", "--- Checking your environment ---".blue());
+    for check in &checks {
+        let line = format!("{}: {}", check.name, check.message);
+        match check.status {
+            CheckStatus::Ok => println!("{} {}", "[ok]".green(), line),
+            CheckStatus::Warn => println!("{} {}", "[warn]".yellow(), line),
+            CheckStatus::Fail => println!("{} {}", "[fail]".red(), line),
+        }
+        if let Some(hint) = &check.hint {
+            println!("       {}", hint.dimmed());
+        }
+    }
+
+    if failures > 0 {
+        return Err(anyhow::anyhow!("doctor found {} problem(s).", failures));
+    }
+    println!("\n{}", "No blocking problems found.".green());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn double_linting_is_a_warning() {
+        let toolchain = CommitToolchain {
+            husky_hooks: vec!["commit-msg".to_string()],
+            commitlint_config: Some("commitlint.config.js".to_string()),
+            commit_msg_hook: Some("npx commitlint --edit $1".to_string()),
+        };
+        assert_eq!(
+            evaluate_commit_toolchain(&toolchain, true).status,
+            CheckStatus::Warn
+        );
+        assert_eq!(
+            evaluate_commit_toolchain(&toolchain, false).status,
+            CheckStatus::Ok
+        );
+
+        let deferred = CommitToolchain {
+            commit_msg_hook: Some("tbdflow parse -F \"$1\"".to_string()),
+            ..toolchain
+        };
+        assert_eq!(
+            evaluate_commit_toolchain(&deferred, true).status,
+            CheckStatus::Ok
+        );
+    }
+
+    #[test]
+    fn autocrlf_is_fine_when_set_or_normalised() {
+        assert_eq!(
+            evaluate_autocrlf(Some("true"), false).status,
+            CheckStatus::Ok
+        );
+        assert_eq!(evaluate_autocrlf(None, true).status, CheckStatus::Ok);
+        let check = evaluate_autocrlf(Some("false"), false);
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert!(check.message.contains("core.autocrlf is false"));
+    }
+
+    #[test]
+    fn long_paths_need_git_and_windows() {
+        assert_eq!(
+            evaluate_long_paths(Some("true"), Some(true)).status,
+            CheckStatus::Ok
+        );
+        assert_eq!(
+            evaluate_long_paths(Some("true"), Some(false)).status,
+            CheckStatus::Warn
+        );
+        assert_eq!(
+            evaluate_long_paths(None, Some(true)).status,
+            CheckStatus::Warn
+        );
+    }
+
+    #[test]
+    fn credential_helper_warns_when_missing() {
+        let check = evaluate_credential_helper(Some("manager"));
+        assert_eq!(check.status, CheckStatus::Ok);
+        assert!(check.message.contains("Git Credential Manager"));
+        assert_eq!(evaluate_credential_helper(None).status, CheckStatus::Warn);
+    }
+
+    #[test]
+    fn powershell_completion_is_detected_in_profile() {
+        let profile = "Import-Module posh-git\ntbdflow completion powershell | Out-String | Invoke-Expression";
+        assert_eq!(
+            evaluate_powershell_completion(Some(profile)).status,
+            CheckStatus::Ok
+        );
+        assert_eq!(
+            evaluate_powershell_completion(None).status,
+            CheckStatus::Warn
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use crate::commands::{DodSkipReportResponse, TbdResponse};
+use crate::config::DodItem;
+use crate::git;
+use crate::git::RunOpts;
+use crate::journal::{self, JournalEntry, JournalEvent};
+use crate::state::{self, State};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// How long a remembered checklist selection is reused. Commits further
+/// apart than this start again from the `.dod.yml` defaults.
+const SESSION_HOURS: i64 = 8;
+
+/// The DoD items a user ticked (or left unticked) on their last commit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RememberedSelection {
+    /// `user.email` of whoever made the selection.
+    pub user: String,
+    pub saved_at: String,
+    /// Item text to whether it was ticked.
+    pub items: BTreeMap<String, bool>,
+}
+
+impl State for RememberedSelection {
+    const FILE: &'static str = "dod-selection.json";
+    const LEGACY_FILE: Option<&'static str> = Some("tbdflow-dod-selection.json");
+}
+
+pub fn load_selection(git_dir: &Path) -> Option<RememberedSelection> {
+    state::load(git_dir).ok().flatten()
+}
+
+pub fn save_selection(
+    git_dir: &Path,
+    user: &str,
+    items: &[String],
+    checked_indices: &[usize],
+) -> Result<()> {
+    let selection = RememberedSelection {
+        user: user.to_string(),
+        saved_at: Utc::now().to_rfc3339(),
+        items: items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (item.clone(), checked_indices.contains(&i)))
+            .collect(),
+    };
+    state::save(git_dir, &selection)
+}
+
+/// Which items start ticked: the user's own selection from earlier in the
+/// session where it covers an item, otherwise the item's `.dod.yml` default.
+pub fn initial_selection(
+    items: &[DodItem],
+    remembered: Option<&RememberedSelection>,
+    user: &str,
+    now: DateTime<Utc>,
+) -> Vec<bool> {
+    let in_session = remembered.filter(|r| {
+        r.user == user
+            && DateTime::parse_from_rfc3339(&r.saved_at).is_ok_and(|saved| {
+                now.signed_duration_since(saved) < Duration::hours(SESSION_HOURS)
+            })
+    });
+    items
+        .iter()
+        .map(|item| {
+            in_session
+                .and_then(|r| r.items.get(item.text()).copied())
+                .unwrap_or_else(|| item.checked_by_default())
+        })
+        .collect()
+}
+
+/// Runs an item's check command through the platform shell. Returns whether
+/// it succeeded; the command's output is shown only when it fails.
+pub fn run_check(item: &str, command: &str, opts: RunOpts) -> Result<bool> {
+    if opts.dry_run {
+        println!(
+            "{}",
+            format!("[DRY RUN] Would run DoD check: {}", command).yellow()
+        );
+        return Ok(true);
+    }
+    print!(
+        "{}",
+        format!("Checking '{}' ({})... ", item, command).blue()
+    );
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let output = std::process::Command::new(shell)
+        .args([flag, command])
+        .output()
+        .with_context(|| format!("Failed to run DoD check '{}'", command))?;
+    if output.status.success() {
+        println!("{}", "passed".green());
+        return Ok(true);
+    }
+    println!("{}", "failed".red());
+    let combined = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let lines: Vec<&str> = combined.lines().collect();
+    for line in &lines[lines.len().saturating_sub(20)..] {
+        println!("  {}", line.dimmed());
+    }
+    Ok(false)
+}
+
+/// Counts `--no-verify` skips per author, most frequent first. Only entries
+/// at or after `since` are counted when it is given.
+fn summarize_dod_skips(
+    entries: &[JournalEntry],
+    since: Option<DateTime<Utc>>,
+) -> Vec<DodSkipReportResponse> {
+    let mut per_author: BTreeMap<&str, DodSkipReportResponse> = BTreeMap::new();
+    for entry in entries {
+        if !matches!(entry.event, JournalEvent::DodSkipped { .. }) {
+            continue;
+        }
+        let Ok(at) = DateTime::parse_from_rfc3339(&entry.timestamp) else {
+            continue;
+        };
+        if since.is_some_and(|since| at < since) {
+            continue;
+        }
+        let row = per_author
+            .entry(&entry.author)
+            .or_insert_with(|| DodSkipReportResponse {
+                author: entry.author.clone(),
+                skips: 0,
+                last_skipped: entry.timestamp.clone(),
+            });
+        row.skips += 1;
+        if entry.timestamp > row.last_skipped {
+            row.last_skipped = entry.timestamp.clone();
+        }
+    }
+    let mut rows: Vec<DodSkipReportResponse> = per_author.into_values().collect();
+    rows.sort_by(|a, b| b.skips.cmp(&a.skips).then_with(|| a.author.cmp(&b.author)));
+    rows
+}
+
+/// Summarises how often each author bypassed the DoD checklist, from the
+/// local journal.
+pub fn handle_dod_report(days: Option<i64>, json: bool, opts: RunOpts) -> Result<()> {
+    let git_dir = PathBuf::from(git::get_git_dir(opts)?);
+    let entries = journal::read_entries(&git_dir)?;
+    let since = days.map(|d| Utc::now() - Duration::days(d));
+    let rows = summarize_dod_skips(&entries, since);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&TbdResponse::ok(rows))?);
+        return Ok(());
+    }
+
+    let period = days.map_or("all time".to_string(), |d| format!("last {} days", d));
+    println!("{}", format!("--- DoD skips ({}) ---", period).blue());
+    if rows.is_empty() {
+        println!("{}", "Nobody skipped the DoD checklist.".green());
+        return Ok(());
+    }
+    for row in rows {
+        println!(
+            "  {:>3}  {} {}",
+            row.skips.to_string().yellow(),
+            row.author,
+            format!(
+                "(last {})",
+                row.last_skipped.get(..10).unwrap_or(&row.last_skipped)
+            )
+            .dimmed()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<DodItem> {
+        vec![
+            DodItem::Text("Tests pass".to_string()),
+            DodItem::Detailed {
+                item: "No secrets committed".to_string(),
+                checked: true,
+                evidence: None,
+                command: None,
+            },
+            DodItem::Text("Docs updated".to_string()),
+        ]
+    }
+
+    fn remembered(user: &str, saved_at: DateTime<Utc>) -> RememberedSelection {
+        RememberedSelection {
+            user: user.to_string(),
+            saved_at: saved_at.to_rfc3339(),
+            items: BTreeMap::from([
+                ("Tests pass".to_string(), true),
+                ("No secrets committed".to_string(), false),
+            ]),
+        }
+    }
+
+    #[test]
+    fn dod_yml_accepts_plain_and_detailed_items() {
+        let config: crate::config::DodConfig = yaml_serde::from_str(
+            "checklist:\n  - \"Tests pass\"\n  - item: \"No secrets committed\"\n    checked: true\n  - \"Docs updated\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.checklist, items());
+
+        let with_evidence: crate::config::DodConfig = yaml_serde::from_str(
+            "checklist:\n  - item: \"Tests pass\"\n    evidence: \"Link to the CI run\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            with_evidence.checklist[0].evidence_prompt(),
+            Some("Link to the CI run")
+        );
+    }
+
+    #[test]
+    fn check_commands_report_success_and_failure() {
+        let opts = RunOpts::new(false, false);
+        assert!(run_check("ok", "exit 0", opts).unwrap());
+        assert!(!run_check("broken", "exit 3", opts).unwrap());
+    }
+
+    fn skip(author: &str, timestamp: &str) -> JournalEntry {
+        JournalEntry {
+            timestamp: timestamp.to_string(),
+            author: author.to_string(),
+            event: JournalEvent::DodSkipped {
+                commit: "abc1234".to_string(),
+                branch: "main".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn skip_report_counts_per_author() {
+        let entries = vec![
+            skip("Ada <ada@example.com>", "2026-10-01T09:00:00+00:00"),
+            skip("Bob <bob@example.com>", "2026-10-02T09:00:00+00:00"),
+            skip("Bob <bob@example.com>", "2026-10-05T09:00:00+00:00"),
+            JournalEntry {
+                timestamp: "2026-10-06T09:00:00+00:00".to_string(),
+                author: "Ada <ada@example.com>".to_string(),
+                event: JournalEvent::ReviewSkipped {
+                    commit: "def5678".to_string(),
+                    commit_type: "docs".to_string(),
+                },
+            },
+        ];
+        let rows = summarize_dod_skips(&entries, None);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].author, "Bob <bob@example.com>");
+        assert_eq!(rows[0].skips, 2);
+        assert_eq!(rows[0].last_skipped, "2026-10-05T09:00:00+00:00");
+        assert_eq!(rows[1].skips, 1);
+
+        let since = DateTime::parse_from_rfc3339("2026-10-03T00:00:00+00:00")
+            .unwrap()
+            .with_timezone(&Utc);
+        let recent = summarize_dod_skips(&entries, Some(since));
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].skips, 1);
+    }
+
+    #[test]
+    fn defaults_come_from_dod_yml() {
+        assert_eq!(
+            initial_selection(&items(), None, "ada@example.com", Utc::now()),
+            vec![false, true, false]
+        );
+    }
+
+    #[test]
+    fn same_user_in_session_reuses_last_selection() {
+        let now = Utc::now();
+        let last = remembered("ada@example.com", now - Duration::hours(1));
+        assert_eq!(
+            initial_selection(&items(), Some(&last), "ada@example.com", now),
+            vec![true, false, false]
+        );
+    }
+
+    #[test]
+    fn other_user_or_old_selection_falls_back_to_defaults() {
+        let now = Utc::now();
+        let other = remembered("bob@example.com", now);
+        assert_eq!(
+            initial_selection(&items(), Some(&other), "ada@example.com", now),
+            vec![false, true, false]
+        );
+        let stale = remembered("ada@example.com", now - Duration::hours(SESSION_HOURS + 1));
+        assert_eq!(
+            initial_selection(&items(), Some(&stale), "ada@example.com", now),
+            vec![false, true, false]
+        );
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let texts = vec!["Tests pass".to_string(), "Docs updated".to_string()];
+        save_selection(dir.path(), "ada@example.com", &texts, &[1]).unwrap();
+        let loaded = load_selection(dir.path()).unwrap();
+        assert_eq!(loaded.user, "ada@example.com");
+        assert!(!loaded.items["Tests pass"]);
+        assert!(loaded.items["Docs updated"]);
+    }
+}
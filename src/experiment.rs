@@ -0,0 +1,125 @@
+use crate::commands::{ExperimentCommitResponse, ExperimentResponse, TbdResponse};
+use crate::config::Config;
+use crate::git;
+use crate::git::RunOpts;
+use anyhow::Result;
+use colored::Colorize;
+use std::collections::BTreeMap;
+
+/// Trailer key that ties a commit to an experiment (A/B test, feature flag).
+pub const EXPERIMENT_TRAILER_KEY: &str = "Experiment";
+
+pub fn experiment_trailer(id: &str) -> String {
+    format!("{}: {}", EXPERIMENT_TRAILER_KEY, id)
+}
+
+/// Groups `git::get_log_with_trailer` output by experiment ID. Commits
+/// without the trailer are dropped; a commit with several IDs is listed
+/// under each of them.
+fn group_by_experiment(log: &str) -> BTreeMap<String, Vec<ExperimentCommitResponse>> {
+    let mut experiments: BTreeMap<String, Vec<ExperimentCommitResponse>> = BTreeMap::new();
+    for line in log.lines() {
+        let fields: Vec<&str> = line.split('\x1f').collect();
+        let [sha, date, subject, ids] = fields[..] else {
+            continue;
+        };
+        for id in ids.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+            experiments
+                .entry(id.to_string())
+                .or_default()
+                .push(ExperimentCommitResponse {
+                    sha: sha.to_string(),
+                    date: date.to_string(),
+                    subject: subject.to_string(),
+                });
+        }
+    }
+    experiments
+}
+
+/// Lists trunk commits per experiment, so the flag and its code paths can be
+/// cleaned up once the experiment is over.
+pub fn handle_experiments(
+    config: &Config,
+    id: Option<String>,
+    json: bool,
+    opts: RunOpts,
+) -> Result<()> {
+    let trunk = format!("origin/{}", config.main_branch_name);
+    let log = git::get_log_with_trailer(&trunk, EXPERIMENT_TRAILER_KEY, opts)?;
+    let mut experiments = group_by_experiment(&log);
+    if let Some(id) = &id {
+        experiments.retain(|experiment, _| experiment == id);
+    }
+
+    if json {
+        let response: Vec<ExperimentResponse> = experiments
+            .into_iter()
+            .map(|(experiment, commits)| ExperimentResponse {
+                experiment,
+                commits,
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(response))?
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("--- Experiments on '{}' ---", trunk)
+            .to_string()
+            .blue()
+    );
+    if experiments.is_empty() {
+        let message = match &id {
+            Some(id) => format!("No commits on '{}' for experiment '{}'.", trunk, id),
+            None => format!(
+                "No commits on '{}' have an '{}:' trailer.",
+                trunk, EXPERIMENT_TRAILER_KEY
+            ),
+        };
+        println!("{}", message.green());
+        return Ok(());
+    }
+    for (experiment, commits) in experiments {
+        println!(
+            "\n{} {}",
+            experiment.bold(),
+            format!("({} commit(s))", commits.len()).dimmed()
+        );
+        for commit in commits {
+            println!(
+                "  {} {} {}",
+                commit.sha.yellow(),
+                commit.date,
+                commit.subject
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_commits_by_experiment_id() {
+        let log = "a1\x1f2026-10-02\x1ffeat: new checkout\x1fEXP-42\n\
+                   b2\x1f2026-10-01\x1ffix: typo\x1f\n\
+                   c3\x1f2026-09-30\x1ffeat: both\x1fEXP-7,EXP-42";
+        let grouped = group_by_experiment(log);
+        assert_eq!(grouped.keys().collect::<Vec<_>>(), vec!["EXP-42", "EXP-7"]);
+        assert_eq!(grouped["EXP-42"].len(), 2);
+        assert_eq!(grouped["EXP-42"][0].sha, "a1");
+        assert_eq!(grouped["EXP-7"][0].subject, "feat: both");
+    }
+
+    #[test]
+    fn builds_the_trailer() {
+        assert_eq!(experiment_trailer("EXP-42"), "Experiment: EXP-42");
+    }
+}
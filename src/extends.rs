@@ -0,0 +1,285 @@
+//! Shared team configuration pulled in with `extends:` in `.tbdflow.yml`.
+//!
+//! The base config is read from a path (relative to the git root) or fetched
+//! from an http(s) URL. `tbdflow info --update-lock` pins its git blob hash
+//! in `.tbdflow.lock` so an upstream change never lands silently: it is only
+//! adopted after the next `--update-lock`. URL sources are cached in `.git/`
+//! and refreshed once the cache is older than a day.
+
+use crate::git::{self, RunOpts};
+use crate::rest;
+use crate::state::{self, State};
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Top-level key naming the shared base config.
+pub const EXTENDS_KEY: &str = "extends";
+/// Lock file, committed next to `.tbdflow.yml`.
+pub const LOCK_FILE: &str = ".tbdflow.lock";
+const CACHE_HOURS: i64 = 24;
+
+/// The pinned version of the shared config.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtendsLock {
+    pub source: String,
+    pub hash: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LockFile {
+    extends: ExtendsLock,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedBase {
+    source: String,
+    hash: String,
+    fetched_at: String,
+    content: String,
+}
+
+fn is_url(source: &str) -> bool {
+    source.starts_with("https://") || source.starts_with("http://")
+}
+
+fn read_lock(git_root: &Path) -> Option<ExtendsLock> {
+    let content = fs::read_to_string(git_root.join(LOCK_FILE)).ok()?;
+    yaml_serde::from_str::<LockFile>(&content)
+        .ok()
+        .map(|l| l.extends)
+}
+
+fn write_lock(git_root: &Path, lock: &ExtendsLock) -> Result<()> {
+    let content = format!(
+        "# Generated by tbdflow. Pins the shared config named by `extends:`.\n{}",
+        yaml_serde::to_string(&LockFile {
+            extends: lock.clone()
+        })?
+    );
+    fs::write(git_root.join(LOCK_FILE), content)
+        .with_context(|| format!("Failed to write {}", LOCK_FILE))
+}
+
+impl State for CachedBase {
+    const FILE: &'static str = "extends-cache.json";
+    const LEGACY_FILE: Option<&'static str> = Some("tbdflow-extends-cache.json");
+}
+
+fn read_cache(git_dir: &Path) -> Option<CachedBase> {
+    state::load(git_dir).ok().flatten()
+}
+
+fn write_cache(git_dir: &Path, cache: &CachedBase) {
+    let _ = state::save(git_dir, cache);
+}
+
+/// `git.timeout_seconds` as the root `.tbdflow.yml` sets it. The shared
+/// config is fetched before the merged config exists, so this bounds the fetch.
+pub fn root_timeout(root: &yaml_serde::Value) -> Option<u64> {
+    root.get("git")?.get("timeout_seconds")?.as_u64()
+}
+
+/// Reads the shared config from disk or the network, bypassing the cache.
+fn read_source(source: &str, git_root: &Path, opts: RunOpts) -> Result<String> {
+    if is_url(source) {
+        rest::get_text(source, opts.for_reads())
+    } else {
+        let path = git_root.join(source);
+        fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read shared config {}", path.display()))
+    }
+}
+
+/// Whether a cached copy can be used without refetching.
+fn is_cache_fresh(
+    cache: &CachedBase,
+    source: &str,
+    lock: &ExtendsLock,
+    now: DateTime<Utc>,
+) -> bool {
+    cache.source == source
+        && cache.hash == lock.hash
+        && DateTime::parse_from_rfc3339(&cache.fetched_at)
+            .is_ok_and(|at| now.signed_duration_since(at).num_hours() < CACHE_HOURS)
+}
+
+/// Returns the shared base config named by `source`, pinned to `.tbdflow.lock`.
+/// Without a lock it is used as it is, with a hint to pin it; with one, an
+/// upstream change is refused (or the cached, locked copy is used).
+pub fn resolve(source: &str, git_root: &Path, git_dir: &Path, opts: RunOpts) -> Result<String> {
+    let lock = read_lock(git_root).filter(|l| l.source == source);
+    let cache = if is_url(source) {
+        read_cache(git_dir)
+    } else {
+        None
+    };
+
+    if let (Some(lock), Some(cache)) = (&lock, &cache)
+        && is_cache_fresh(cache, source, lock, Utc::now())
+    {
+        return Ok(cache.content.clone());
+    }
+
+    let locked_cache = cache
+        .as_ref()
+        .zip(lock.as_ref())
+        .filter(|(c, l)| c.source == source && c.hash == l.hash)
+        .map(|(c, _)| c.content.clone());
+
+    let content = match read_source(source, git_root, opts) {
+        Ok(content) => content,
+        Err(e) => {
+            return match locked_cache {
+                Some(content) => {
+                    eprintln!(
+                        "{} {}. Using the cached copy.",
+                        "Warning:".yellow(),
+                        e.to_string().lines().next().unwrap_or_default()
+                    );
+                    Ok(content)
+                }
+                None => Err(e),
+            };
+        }
+    };
+    let hash = git::hash_blob(&content, opts.for_reads())?;
+
+    match lock {
+        None => {
+            eprintln!(
+                "{} shared config {} is not pinned. Run 'tbdflow info --update-lock' to pin it in {}.",
+                "Warning:".yellow(),
+                source,
+                LOCK_FILE
+            );
+        }
+        Some(lock) if lock.hash != hash => {
+            let hint = format!(
+                "Shared config {} has changed upstream ({} -> {}). Review it and run 'tbdflow info --update-lock' to adopt it.",
+                source,
+                &lock.hash[..lock.hash.len().min(7)],
+                &hash[..hash.len().min(7)]
+            );
+            return match locked_cache {
+                Some(content) => {
+                    eprintln!("{} {}", "Warning:".yellow(), hint);
+                    Ok(content)
+                }
+                None => Err(anyhow!(hint)),
+            };
+        }
+        Some(_) => {}
+    }
+
+    if is_url(source) {
+        write_cache(
+            git_dir,
+            &CachedBase {
+                source: source.to_string(),
+                hash,
+                fetched_at: Utc::now().to_rfc3339(),
+                content: content.clone(),
+            },
+        );
+    }
+    Ok(content)
+}
+
+/// Fetches the shared config again and pins its current content in `.tbdflow.lock`.
+pub fn update_lock(opts: RunOpts) -> Result<()> {
+    let quiet = opts.for_reads();
+    let git_root = std::path::PathBuf::from(git::get_git_root(quiet)?);
+    let git_dir = std::path::PathBuf::from(git::get_git_dir(quiet)?);
+    let config_str =
+        fs::read_to_string(git_root.join(".tbdflow.yml")).context("Failed to read .tbdflow.yml")?;
+    let value: yaml_serde::Value = yaml_serde::from_str(&config_str)?;
+    let source = value
+        .get(EXTENDS_KEY)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("'.tbdflow.yml' has no '{}:' entry to lock.", EXTENDS_KEY))?
+        .to_string();
+
+    let opts = opts.with_timeout(root_timeout(&value).or(opts.timeout_secs));
+    let content = read_source(&source, &git_root, opts)?;
+    let hash = git::hash_blob(&content, opts.for_reads())?;
+    let lock = ExtendsLock {
+        source: source.clone(),
+        hash: hash.clone(),
+    };
+    if read_lock(&git_root).as_ref() == Some(&lock) {
+        println!("{} is already up to date.", LOCK_FILE);
+        return Ok(());
+    }
+    if opts.dry_run {
+        println!("[DRY RUN] Would lock {} at {}", source, hash);
+        return Ok(());
+    }
+    write_lock(&git_root, &lock)?;
+    if is_url(&source) {
+        write_cache(
+            &git_dir,
+            &CachedBase {
+                source: source.clone(),
+                hash: hash.clone(),
+                fetched_at: Utc::now().to_rfc3339(),
+                content,
+            },
+        );
+    }
+    println!(
+        "{} {} to {} at {}. Commit it so the team picks up the change.",
+        "Updated".green(),
+        LOCK_FILE,
+        source,
+        &hash[..hash.len().min(7)]
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(hash: &str, fetched_at: DateTime<Utc>) -> CachedBase {
+        CachedBase {
+            source: "https://example.com/base.yml".to_string(),
+            hash: hash.to_string(),
+            fetched_at: fetched_at.to_rfc3339(),
+            content: String::new(),
+        }
+    }
+
+    #[test]
+    fn cache_is_fresh_only_for_the_locked_hash_within_a_day() {
+        let now = Utc::now();
+        let lock = ExtendsLock {
+            source: "https://example.com/base.yml".to_string(),
+            hash: "abc".to_string(),
+        };
+        let source = lock.source.as_str();
+        assert!(is_cache_fresh(&cache("abc", now), source, &lock, now));
+        assert!(!is_cache_fresh(&cache("def", now), source, &lock, now));
+        assert!(!is_cache_fresh(
+            &cache("abc", now - chrono::Duration::hours(25)),
+            source,
+            &lock,
+            now
+        ));
+        assert!(!is_cache_fresh(
+            &cache("abc", now),
+            "https://example.com/other.yml",
+            &lock,
+            now
+        ));
+    }
+
+    #[test]
+    fn urls_are_told_apart_from_paths() {
+        assert!(is_url("https://example.com/base.yml"));
+        assert!(!is_url("../platform/base.yml"));
+    }
+}
@@ -0,0 +1,279 @@
+//! Runs external CLIs (`gh` today, `glab` or `jira` tomorrow) the way
+//! `git::run_git_command` runs git: `--verbose` prints each call, `--dry-run`
+//! prints it instead, `git.timeout_seconds` bounds it, and a missing binary
+//! is reported with a hint on how to install it.
+
+use crate::git::RunOpts;
+use anyhow::Result;
+use colored::Colorize;
+use std::io::{ErrorKind, Read, Write};
+use std::process::{Command, Output, Stdio};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ExternalError {
+    #[error("'{program}' is not installed. {install_hint}")]
+    NotInstalled {
+        program: &'static str,
+        install_hint: &'static str,
+    },
+    #[error("Failed to execute '{program} {command}': {source}")]
+    Spawn {
+        program: &'static str,
+        command: String,
+        source: std::io::Error,
+    },
+    #[error(
+        "'{program} {command}' timed out after {secs}s. Check your network, or raise git.timeout_seconds in .tbdflow.yml."
+    )]
+    Timeout {
+        program: &'static str,
+        command: String,
+        secs: u64,
+    },
+}
+
+/// What an invocation printed, with surrounding whitespace trimmed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ToolOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// An external CLI tbdflow shells out to. Declare one as a `static` so the
+/// availability probe runs at most once per process.
+pub struct ExternalTool {
+    pub program: &'static str,
+    /// Shown when the program is missing.
+    pub install_hint: &'static str,
+    available: OnceLock<bool>,
+}
+
+impl ExternalTool {
+    pub const fn new(program: &'static str, install_hint: &'static str) -> Self {
+        Self {
+            program,
+            install_hint,
+            available: OnceLock::new(),
+        }
+    }
+
+    /// Runs the tool with `args`. Under `--dry-run` nothing runs, not even
+    /// searches: the invocation is printed and an empty, successful output is
+    /// returned. A non-zero exit is not an error; check [`ToolOutput::success`].
+    pub fn run(&self, args: &[&str], opts: RunOpts) -> Result<ToolOutput> {
+        if opts.dry_run {
+            println!(
+                "{}",
+                "[DRY RUN] Command would execute but no changes made".yellow()
+            );
+            println!("{} {}", self.program, args.join(" "));
+            println!();
+            return Ok(ToolOutput {
+                success: true,
+                ..Default::default()
+            });
+        }
+        if opts.verbose {
+            println!(
+                "{} {} {}",
+                "[RUNNING] ".cyan(),
+                self.program,
+                args.join(" ")
+            );
+        }
+
+        let mut command = Command::new(self.program);
+        command
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        let output = match opts.timeout_secs {
+            None => command.output().map(Some),
+            Some(secs) => output_with_timeout(&mut command, secs),
+        };
+        let subcommand = args.first().copied().unwrap_or_default().to_string();
+        let output = match output {
+            Ok(Some(output)) => output,
+            Ok(None) => {
+                return Err(ExternalError::Timeout {
+                    program: self.program,
+                    command: subcommand,
+                    secs: opts.timeout_secs.unwrap_or_default(),
+                }
+                .into());
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => return Err(self.not_installed().into()),
+            Err(source) => {
+                return Err(ExternalError::Spawn {
+                    program: self.program,
+                    command: subcommand,
+                    source,
+                }
+                .into());
+            }
+        };
+        Ok(ToolOutput {
+            success: output.status.success(),
+            stdout: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        })
+    }
+
+    /// Returns true if the tool is installed and executable. Only probes
+    /// `<program> --version`, once per process, so it runs under `--dry-run` too.
+    pub fn is_available(&self) -> bool {
+        *self.available.get_or_init(|| {
+            Command::new(self.program)
+                .arg("--version")
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .map(|s| s.success())
+                .unwrap_or(false)
+        })
+    }
+
+    /// Errors with the install hint unless the tool [is available](Self::is_available).
+    pub fn require(&self) -> Result<()> {
+        if self.is_available() {
+            Ok(())
+        } else {
+            Err(self.not_installed().into())
+        }
+    }
+
+    fn not_installed(&self) -> ExternalError {
+        ExternalError::NotInstalled {
+            program: self.program,
+            install_hint: self.install_hint,
+        }
+    }
+}
+
+/// Runs `command` to completion, killing it if it has not finished within
+/// `secs` seconds, in which case `None` is returned. Output is drained on
+/// separate threads so a chatty command cannot block on a full pipe.
+pub(crate) fn output_with_timeout(
+    command: &mut Command,
+    secs: u64,
+) -> std::io::Result<Option<Output>> {
+    output_with_input(command, None, Some(secs))
+}
+
+/// Like [`output_with_timeout`], with `input` written to the command's stdin
+/// (when it is piped) and no time limit when `secs` is `None`.
+pub(crate) fn output_with_input(
+    command: &mut Command,
+    input: Option<&str>,
+    secs: Option<u64>,
+) -> std::io::Result<Option<Output>> {
+    let mut child = command.spawn()?;
+
+    let feed = child.stdin.take().map(|mut stdin| {
+        let input = input.unwrap_or_default().to_string();
+        std::thread::spawn(move || {
+            let _ = stdin.write_all(input.as_bytes());
+        })
+    });
+    let drain = |pipe: Option<Box<dyn Read + Send>>| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            buf
+        })
+    };
+    let stdout = drain(
+        child
+            .stdout
+            .take()
+            .map(|p| Box::new(p) as Box<dyn Read + Send>),
+    );
+    let stderr = drain(
+        child
+            .stderr
+            .take()
+            .map(|p| Box::new(p) as Box<dyn Read + Send>),
+    );
+
+    let status = match secs {
+        None => child.wait()?,
+        Some(secs) => {
+            let deadline = Instant::now() + Duration::from_secs(secs);
+            loop {
+                if let Some(status) = child.try_wait()? {
+                    break status;
+                }
+                if Instant::now() >= deadline {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Ok(None);
+                }
+                std::thread::sleep(Duration::from_millis(20));
+            }
+        }
+    };
+    if let Some(feed) = feed {
+        let _ = feed.join();
+    }
+
+    Ok(Some(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static MISSING: ExternalTool = ExternalTool::new(
+        "tbdflow-no-such-tool",
+        "Install it from https://example.com.",
+    );
+
+    #[test]
+    fn missing_tool_is_reported_with_its_install_hint() {
+        assert!(!MISSING.is_available());
+        let err = MISSING
+            .run(&["issue", "list"], RunOpts::new(false, false))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "'tbdflow-no-such-tool' is not installed. Install it from https://example.com."
+        );
+        assert!(MISSING.require().is_err());
+    }
+
+    #[test]
+    fn dry_run_runs_nothing() {
+        // The tool does not even exist; under --dry-run it is only printed.
+        let output = MISSING
+            .run(&["label", "create", "x"], RunOpts::new(false, true))
+            .unwrap();
+        assert!(output.success);
+        assert!(output.stdout.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn hung_tool_times_out() {
+        static SLEEP: ExternalTool = ExternalTool::new("sleep", "");
+        let started = Instant::now();
+        let err = SLEEP
+            .run(&["5"], RunOpts::new(false, false).with_timeout(Some(1)))
+            .unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert!(
+            err.to_string().contains("'sleep 5' timed out after 1s"),
+            "Error was: {}",
+            err
+        );
+    }
+}
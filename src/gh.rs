@@ -0,0 +1,22 @@
+//! The GitHub CLI (`gh`). Every `gh` call goes through [`run`], so
+//! `--verbose`, `--dry-run` and `git.timeout_seconds` behave exactly as they
+//! do for git commands.
+
+use crate::external::{ExternalTool, ToolOutput};
+use crate::git::RunOpts;
+use anyhow::Result;
+
+pub static GH: ExternalTool = ExternalTool::new(
+    "gh",
+    "Install it from https://cli.github.com and run 'gh auth login'.",
+);
+
+/// Runs `gh` with `args`; see [`ExternalTool::run`].
+pub fn run(args: &[&str], opts: RunOpts) -> Result<ToolOutput> {
+    GH.run(args, opts)
+}
+
+/// Returns true if the GitHub CLI (`gh`) is installed and executable.
+pub fn is_available() -> bool {
+    GH.is_available()
+}
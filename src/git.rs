@@ -1,5 +1,8 @@
 use crate::commands;
-use crate::config::Config;
+use crate::config::{Config, GitBackendKind};
+use crate::external;
+use crate::gh;
+use crate::git_backend;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use colored::Colorize;
@@ -11,11 +14,179 @@ use thiserror::Error;
 pub struct RunOpts {
     pub verbose: bool,
     pub dry_run: bool,
+    /// Answer "yes" to every confirmation prompt (`--yes`).
+    pub assume_yes: bool,
+    /// Kill git and other external tool subprocesses that run longer than this (`git.timeout_seconds`).
+    pub timeout_secs: Option<u64>,
+    /// Where read-only repository queries run (`git.backend`).
+    pub backend: GitBackendKind,
+    /// Executes the commands; tests swap in a fake.
+    pub runner: &'static dyn GitRunner,
 }
 
 impl RunOpts {
     pub fn new(verbose: bool, dry_run: bool) -> Self {
-        Self { verbose, dry_run }
+        Self {
+            verbose,
+            dry_run,
+            assume_yes: false,
+            timeout_secs: None,
+            backend: GitBackendKind::Subprocess,
+            runner: &SubprocessRunner,
+        }
+    }
+
+    /// The same options without `--dry-run`, for queries that must run for
+    /// real even when changes are only being previewed.
+    pub fn for_reads(self) -> Self {
+        Self {
+            dry_run: false,
+            ..self
+        }
+    }
+
+    pub fn with_assume_yes(mut self, assume_yes: bool) -> Self {
+        self.assume_yes = assume_yes;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout_secs: Option<u64>) -> Self {
+        self.timeout_secs = timeout_secs;
+        self
+    }
+
+    pub fn with_backend(mut self, backend: GitBackendKind) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    pub fn with_runner(mut self, runner: &'static dyn GitRunner) -> Self {
+        self.runner = runner;
+        self
+    }
+}
+
+/// Executes the git commands behind every helper in this module. Handlers
+/// reach it through [`RunOpts::runner`], so unit tests can answer git from a
+/// script and assert on the exact commands a handler runs.
+pub trait GitRunner: std::fmt::Debug + Sync {
+    /// Runs `git <command> <args>` and returns its trimmed stdout, or its
+    /// stderr as a [`GitError::Git`] when it exits non-zero.
+    fn run(&self, command: &str, args: &[&str], opts: RunOpts) -> Result<String>;
+
+    /// Runs `git <command> <args>` with its output discarded and returns the
+    /// exit code.
+    fn status(&self, command: &str, args: &[&str], opts: RunOpts) -> Result<Option<i32>>;
+
+    /// Runs `git <command> <args>` with `input` and returns its exit code and
+    /// output, for commands whose non-zero exits carry an answer.
+    fn output(
+        &self,
+        command: &str,
+        args: &[&str],
+        input: GitInput<'_>,
+        opts: RunOpts,
+    ) -> Result<GitOutput>;
+}
+
+/// What [`GitRunner::output`] gives git besides its arguments.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GitInput<'a> {
+    /// Written to git's standard input.
+    pub stdin: Option<&'a str>,
+    /// An index to use instead of the repository's (`GIT_INDEX_FILE`).
+    pub index_file: Option<&'a std::path::Path>,
+}
+
+/// How a git command exited and what it printed, from [`GitRunner::output`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitOutput {
+    pub code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Runs the `git` binary.
+#[derive(Debug)]
+pub struct SubprocessRunner;
+
+impl GitRunner for SubprocessRunner {
+    fn run(&self, command: &str, args: &[&str], opts: RunOpts) -> Result<String> {
+        let mut git = Command::new("git");
+        git.arg(command)
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let (success, stdout, stderr) = match opts.timeout_secs {
+            None => {
+                let output = git
+                    .output()
+                    .with_context(|| format!("Failed to execute 'git {}'", command))?;
+                (output.status.success(), output.stdout, output.stderr)
+            }
+            Some(secs) => run_with_timeout(&mut git, command, secs)?,
+        };
+
+        if success {
+            Ok(String::from_utf8_lossy(&stdout).trim().to_string())
+        } else {
+            Err(GitError::Git(String::from_utf8_lossy(&stderr).trim().to_string()).into())
+        }
+    }
+
+    fn status(&self, command: &str, args: &[&str], opts: RunOpts) -> Result<Option<i32>> {
+        let mut git = Command::new("git");
+        git.arg(command)
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+
+        let status = match opts.timeout_secs {
+            None => git
+                .status()
+                .with_context(|| format!("Failed to execute 'git {}'", command))?,
+            Some(secs) => {
+                external::output_with_timeout(&mut git, secs)
+                    .with_context(|| format!("Failed to execute 'git {}'", command))?
+                    .ok_or_else(|| GitError::Timeout(command.to_string(), secs))?
+                    .status
+            }
+        };
+        Ok(status.code())
+    }
+
+    fn output(
+        &self,
+        command: &str,
+        args: &[&str],
+        input: GitInput<'_>,
+        opts: RunOpts,
+    ) -> Result<GitOutput> {
+        let mut git = Command::new("git");
+        git.arg(command)
+            .args(args)
+            .stdin(if input.stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(index) = input.index_file {
+            git.env("GIT_INDEX_FILE", index);
+        }
+
+        let output = external::output_with_input(&mut git, input.stdin, opts.timeout_secs)
+            .with_context(|| format!("Failed to execute 'git {}'", command))?
+            .ok_or_else(|| {
+                GitError::Timeout(command.to_string(), opts.timeout_secs.unwrap_or(0))
+            })?;
+        Ok(GitOutput {
+            code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        })
     }
 }
 
@@ -31,16 +202,22 @@ pub enum GitError {
     BranchNotFound(String),
     #[error("Tag '{0}' already exists.")]
     TagAlreadyExists(String),
+    #[error("Tag '{0}' already exists on origin. Run 'git fetch --tags' and pick another version.")]
+    TagExistsOnRemote(String),
     #[error("Cannot complete the main branch. This is a protected branch.")]
     CannotCompleteMainBranch,
     #[error("Not on main branch: {0}")]
     NotOnMainBranch(String),
     #[error("Not a Git repository: {0}")]
     NotAGitRepository(String),
+    #[error(
+        "'git {0}' timed out after {1}s. Check your network and credential helper, or raise git.timeout_seconds in .tbdflow.yml."
+    )]
+    Timeout(String, u64),
 }
 
 /// Runs a Git command with the specified subcommand and arguments.
-fn run_git_command(command: &str, args: &[&str], opts: RunOpts) -> Result<String> {
+pub(crate) fn run_git_command(command: &str, args: &[&str], opts: RunOpts) -> Result<String> {
     if opts.verbose || opts.dry_run {
         if opts.dry_run {
             println!(
@@ -55,25 +232,24 @@ fn run_git_command(command: &str, args: &[&str], opts: RunOpts) -> Result<String
         }
     }
 
-    let output = Command::new("git")
-        .arg(command)
-        .args(args)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .with_context(|| format!("Failed to execute 'git {}'", command))?;
+    opts.runner.run(command, args, opts)
+}
 
-    if output.status.success() {
-        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
-    } else {
-        Err(GitError::Git(String::from_utf8_lossy(&output.stderr).trim().to_string()).into())
-    }
+/// Runs `git`, killing it if it has not finished within `secs` seconds.
+fn run_with_timeout(
+    git: &mut Command,
+    command: &str,
+    secs: u64,
+) -> Result<(bool, Vec<u8>, Vec<u8>)> {
+    let output = external::output_with_timeout(git, secs)
+        .with_context(|| format!("Failed to execute 'git {}'", command))?
+        .ok_or_else(|| GitError::Timeout(command.to_string(), secs))?;
+    Ok((output.status.success(), output.stdout, output.stderr))
 }
 
 /// Checks if the git working directory is clean.
 pub fn is_working_directory_clean(opts: RunOpts) -> Result<()> {
-    let output = run_git_command("status", &["--porcelain"], opts)?;
-    if output.is_empty() {
+    if !git_backend::for_opts(opts).is_dirty()? {
         Ok(())
     } else {
         Err(GitError::DirectoryNotClean(
@@ -83,12 +259,8 @@ pub fn is_working_directory_clean(opts: RunOpts) -> Result<()> {
     }
 }
 
-/// Runs a git command, suppressing stdout/stderr. Returns the exit status.
-fn run_git_status_check(
-    command: &str,
-    args: &[&str],
-    opts: RunOpts,
-) -> Result<std::process::ExitStatus> {
+/// Runs a git command, suppressing stdout/stderr. Returns the exit code.
+fn run_git_status_check(command: &str, args: &[&str], opts: RunOpts) -> Result<Option<i32>> {
     if opts.verbose {
         println!(
             "{} git {} {}",
@@ -97,20 +269,26 @@ fn run_git_status_check(
             args.join(" ")
         );
     }
-    Command::new("git")
-        .arg(command)
-        .args(args)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status()
-        .with_context(|| format!("Failed to execute 'git {}'", command))
+    opts.runner.status(command, args, opts)
+}
+
+pub fn get_staged_diffstat(opts: RunOpts) -> Result<String> {
+    run_git_command("diff", &["--cached", "--stat"], opts)
+}
+
+pub fn get_commit_diffstat(commit_hash: &str, opts: RunOpts) -> Result<String> {
+    run_git_command("show", &["--stat", "--format=", commit_hash], opts)
+}
+
+pub fn get_staged_diff(opts: RunOpts) -> Result<String> {
+    run_git_command("diff", &["--cached"], opts)
 }
 
 /// Checks if there are any changes in the staging area.
 pub fn has_staged_changes(opts: RunOpts) -> Result<bool> {
-    let status = run_git_status_check("diff", &["--staged", "--quiet"], opts)?;
+    let code = run_git_status_check("diff", &["--staged", "--quiet"], opts)?;
     // git diff --quiet exits 1 if there are changes, 0 if clean.
-    Ok(status.code() == Some(1))
+    Ok(code == Some(1))
 }
 
 pub fn add_remote(remote_name: &str, remote_url: &str, opts: RunOpts) -> Result<String> {
@@ -121,6 +299,10 @@ pub fn checkout_main(opts: RunOpts, main_branch: &str) -> Result<String> {
     run_git_command("checkout", &[main_branch], opts)
 }
 
+pub fn checkout_branch(branch_name: &str, opts: RunOpts) -> Result<String> {
+    run_git_command("checkout", &[branch_name], opts)
+}
+
 pub fn pull_latest_with_rebase(opts: RunOpts) -> Result<String> {
     run_git_command("pull", &["--rebase", "--autostash"], opts)
 }
@@ -135,6 +317,13 @@ pub fn fetch_origin(opts: RunOpts) -> Result<String> {
     run_git_command("fetch", &["origin"], opts)
 }
 
+/// Checks out `branch_name` and fast-forwards it to its upstream.
+/// Fails if the local branch has diverged from the remote.
+pub fn fast_forward_to_upstream(branch_name: &str, opts: RunOpts) -> Result<String> {
+    run_git_command("checkout", &[branch_name], opts)?;
+    run_git_command("merge", &["--ff-only", "@{u}"], opts)
+}
+
 pub fn remote_branch_exists(branch_name: &str, opts: RunOpts) -> Result<()> {
     let output = run_git_command(
         "ls-remote",
@@ -155,8 +344,20 @@ pub fn rebase_onto_main(main_branch_name: &str, opts: RunOpts) -> Result<String>
     )
 }
 
+/// Stages every change in the repository, wherever tbdflow runs from.
 pub fn add_all(opts: RunOpts) -> Result<String> {
-    run_git_command("add", &["."], opts)
+    run_git_command("add", &["--", ":/"], opts)
+}
+
+/// Stages `paths`, given relative to the git root.
+pub fn add_root_paths(paths: &[String], opts: RunOpts) -> Result<String> {
+    let specs: Vec<String> = paths
+        .iter()
+        .map(|p| format!(":(top,literal){}", p))
+        .collect();
+    let mut args = vec!["--"];
+    args.extend(specs.iter().map(String::as_str));
+    run_git_command("add", &args, opts)
 }
 
 /// Stages everything except the given project directories using `:(exclude)` pathspec.
@@ -183,14 +384,134 @@ pub fn commit(message: &str, opts: RunOpts) -> Result<String> {
     run_git_command("commit", &["-m", message], opts)
 }
 
+/// Commits without running hooks, for work-in-progress handoffs.
+pub fn commit_no_verify(message: &str, opts: RunOpts) -> Result<String> {
+    run_git_command("commit", &["--no-verify", "-m", message], opts)
+}
+
+/// Folds the staged changes into HEAD, keeping its message.
+pub fn amend_no_edit(opts: RunOpts) -> Result<String> {
+    run_git_command("commit", &["--amend", "--no-edit"], opts)
+}
+
+/// Moves the branch back to `target`, keeping all changes staged.
+pub fn reset_soft(target: &str, opts: RunOpts) -> Result<String> {
+    run_git_command("reset", &["--soft", target], opts)
+}
+
+/// Pushes rewritten history, refusing if the remote moved since the last fetch.
+pub fn push_force_with_lease(opts: RunOpts) -> Result<String> {
+    run_git_command("push", &["--force-with-lease"], opts)
+}
+
 pub fn push(opts: RunOpts) -> Result<String> {
     run_git_command("push", &[], opts)
 }
 
+/// True if a failed push was rejected because the remote has commits we do not.
+pub fn is_non_fast_forward(error: &str) -> bool {
+    error.contains("non-fast-forward") || error.contains("fetch first")
+}
+
 pub fn push_tags(opts: RunOpts) -> Result<String> {
     run_git_command("push", &["--tags"], opts)
 }
 
+/// Pushes a single tag, leaving any other local tags unpublished.
+pub fn push_tag(tag_name: &str, opts: RunOpts) -> Result<String> {
+    let tag_ref = format!("refs/tags/{}", tag_name);
+    run_git_command("push", &["origin", &tag_ref], opts)
+}
+
+/// True when `name` resolves to an object.
+pub fn ref_exists(name: &str, opts: RunOpts) -> bool {
+    run_git_command("rev-parse", &["--verify", "--quiet", name], opts).is_ok()
+}
+
+/// The note `commit` carries under `notes_ref`, or `None` when it has none.
+pub fn read_note(notes_ref: &str, commit: &str, opts: RunOpts) -> Option<String> {
+    run_git_command("notes", &["--ref", notes_ref, "show", commit], opts).ok()
+}
+
+/// Commits that carry a note under `notes_ref`.
+pub fn list_noted_commits(notes_ref: &str, opts: RunOpts) -> Vec<String> {
+    run_git_command("notes", &["--ref", notes_ref, "list"], opts)
+        .map(|out| {
+            out.lines()
+                .filter_map(|line| line.split_whitespace().nth(1))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Replaces the note `commit` carries under `notes_ref`.
+pub fn write_note(notes_ref: &str, commit: &str, content: &str, opts: RunOpts) -> Result<String> {
+    run_git_command(
+        "notes",
+        &["--ref", notes_ref, "add", "-f", "-m", content, commit],
+        opts,
+    )
+}
+
+/// Fetches origin's `notes_ref` into the local ref `into`, overwriting it.
+pub fn fetch_notes(notes_ref: &str, into: &str, opts: RunOpts) -> Result<String> {
+    let refspec = format!("+{}:{}", notes_ref, into);
+    run_git_command("fetch", &["origin", &refspec], opts)
+}
+
+/// Merges the notes in `other` into `notes_ref`, keeping every line of both
+/// when the two annotate the same commit.
+pub fn merge_notes(notes_ref: &str, other: &str, opts: RunOpts) -> Result<String> {
+    run_git_command(
+        "notes",
+        &["--ref", notes_ref, "merge", "-s", "cat_sort_uniq", other],
+        opts,
+    )
+}
+
+/// Pushes a single ref to origin under the same name.
+pub fn push_ref(refname: &str, opts: RunOpts) -> Result<String> {
+    run_git_command("push", &["origin", refname], opts)
+}
+
+/// Pushes `commit` to origin's `branch`; refused unless it fast-forwards.
+pub fn push_commit_to_branch(commit: &str, branch: &str, opts: RunOpts) -> Result<String> {
+    let refspec = format!("{}:refs/heads/{}", commit, branch);
+    run_git_command("push", &["origin", &refspec], opts)
+}
+
+/// Fetches origin's `branch`, updating `origin/<branch>`.
+pub fn fetch_branch(branch: &str, opts: RunOpts) -> Result<String> {
+    run_git_command("fetch", &["origin", branch], opts)
+}
+
+/// Pushes a branch and a tag in a single `--atomic` transaction, so observers
+/// never see the tagless commit or a tag pointing at an unpushed commit.
+/// With `all_tags`, every local tag is included instead of just `tag_name`.
+pub fn push_branch_and_tag_atomic(
+    branch_name: &str,
+    tag_name: &str,
+    all_tags: bool,
+    opts: RunOpts,
+) -> Result<String> {
+    let tag_ref = format!("refs/tags/{}", tag_name);
+    if all_tags {
+        run_git_command("push", &["--atomic", "--tags", "origin", branch_name], opts)
+    } else {
+        run_git_command("push", &["--atomic", "origin", branch_name, &tag_ref], opts)
+    }
+}
+
+/// Creates a local branch tracking `origin/<branch_name>` without checking it out.
+pub fn create_tracking_branch(branch_name: &str, opts: RunOpts) -> Result<String> {
+    run_git_command(
+        "branch",
+        &["--track", branch_name, &format!("origin/{}", branch_name)],
+        opts,
+    )
+}
+
 pub fn branch_exists_locally(branch_name: &str, opts: RunOpts) -> Result<()> {
     let output = run_git_command("rev-parse", &["--verify", "--quiet", branch_name], opts)?;
     match output {
@@ -204,11 +525,10 @@ pub fn branch_exists_locally(branch_name: &str, opts: RunOpts) -> Result<()> {
 pub fn find_branch(name: &str, r#type: &str, config: &Config, opts: RunOpts) -> Result<String> {
     let prefix = commands::get_branch_prefix_or_error(&config.branch_types, r#type)?;
 
-    let all_branches = run_git_command("branch", &["--list"], opts)?;
     let mut found_branches: Vec<String> = Vec::new();
 
-    for branch in all_branches.lines() {
-        let trimmed_branch = branch.trim().trim_start_matches('*').trim();
+    // Includes branches that only exist on origin, e.g. a teammate's.
+    for trimmed_branch in list_branch_names(opts)? {
         let lower_branch = trimmed_branch.to_lowercase();
         let lower_name = name.to_lowercase();
         let lower_prefix = prefix.to_lowercase();
@@ -216,7 +536,7 @@ pub fn find_branch(name: &str, r#type: &str, config: &Config, opts: RunOpts) ->
         // Check if the branch starts with the correct prefix and ends with the name.
         // This correctly handles branches with or without issue IDs in the middle.
         if lower_branch.starts_with(&lower_prefix) && lower_branch.ends_with(&lower_name) {
-            found_branches.push(trimmed_branch.to_string());
+            found_branches.push(trimmed_branch);
         }
     }
 
@@ -232,13 +552,80 @@ pub fn find_branch(name: &str, r#type: &str, config: &Config, opts: RunOpts) ->
     }
 }
 
+/// Names of all local branches.
+pub fn list_local_branch_names(opts: RunOpts) -> Result<Vec<String>> {
+    git_backend::for_opts(opts).local_branches()
+}
+
+/// Lists local branches and branches on `origin`, without the `origin/` prefix.
+pub fn list_branch_names(opts: RunOpts) -> Result<Vec<String>> {
+    let output = run_git_command(
+        "for-each-ref",
+        &[
+            "--format",
+            "%(refname)",
+            "refs/heads/",
+            "refs/remotes/origin/",
+        ],
+        opts,
+    )?;
+    let mut names: Vec<String> = output
+        .lines()
+        .filter_map(|line| {
+            line.strip_prefix("refs/heads/")
+                .or_else(|| line.strip_prefix("refs/remotes/origin/"))
+        })
+        .filter(|name| *name != "HEAD")
+        .map(|name| name.to_string())
+        .collect();
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
 pub fn tag_exists(tag_name: &str, opts: RunOpts) -> Result<bool> {
     let output = run_git_command("tag", &["-l", tag_name], opts)?;
     Ok(!output.is_empty())
 }
 
-pub fn merge_branch(branch_name: &str, opts: RunOpts) -> Result<String> {
-    run_git_command("merge", &["--no-ff", branch_name], opts)
+/// Returns `None` when origin cannot be reached.
+pub fn remote_tag_exists(tag_name: &str, opts: RunOpts) -> Option<bool> {
+    let tag_ref = format!("refs/tags/{}", tag_name);
+    run_git_command("ls-remote", &["--tags", "origin", &tag_ref], opts)
+        .ok()
+        .map(|output| !output.is_empty())
+}
+
+/// Fails if the tag exists locally or on origin, so a release does not get
+/// as far as a rejected push. Offline, only local tags are checked.
+pub fn ensure_tag_available(tag_name: &str, opts: RunOpts) -> Result<()> {
+    if tag_exists(tag_name, opts)? {
+        return Err(GitError::TagAlreadyExists(tag_name.to_string()).into());
+    }
+    match remote_tag_exists(tag_name, opts) {
+        Some(true) => Err(GitError::TagExistsOnRemote(tag_name.to_string()).into()),
+        Some(false) => Ok(()),
+        None => {
+            println!(
+                "{}",
+                format!(
+                    "Warning: could not reach origin to check for tag '{}'; only local tags were checked.",
+                    tag_name
+                )
+                .yellow()
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Merges with `--no-ff`, using `message` for the merge commit instead of
+/// git's default when one is given.
+pub fn merge_branch(branch_name: &str, message: Option<&str>, opts: RunOpts) -> Result<String> {
+    match message {
+        Some(message) => run_git_command("merge", &["--no-ff", "-m", message, branch_name], opts),
+        None => run_git_command("merge", &["--no-ff", branch_name], opts),
+    }
 }
 
 pub fn delete_local_branch(branch_name: &str, opts: RunOpts) -> Result<String> {
@@ -250,7 +637,21 @@ pub fn delete_remote_branch(branch_name: &str, opts: RunOpts) -> Result<String>
 }
 
 pub fn get_current_branch(opts: RunOpts) -> Result<String> {
-    run_git_command("branch", &["--show-current"], opts)
+    git_backend::for_opts(opts).current_branch()
+}
+
+/// True if `commit` is reachable from `origin/<main_branch>`. Fails if
+/// `commit` cannot be resolved.
+pub fn is_ancestor_of_main(commit: &str, main_branch: &str, opts: RunOpts) -> Result<bool> {
+    let main_ref = format!("origin/{}", main_branch);
+    match run_git_command("merge-base", &["--is-ancestor", commit, &main_ref], opts) {
+        Ok(_) => Ok(true),
+        // Exit status 1 without a message means "not an ancestor".
+        Err(e) if matches!(e.downcast_ref::<GitError>(), Some(GitError::Git(msg)) if msg.is_empty()) => {
+            Ok(false)
+        }
+        Err(e) => Err(e),
+    }
 }
 
 pub fn create_branch(branch_name: &str, from_point: Option<&str>, opts: RunOpts) -> Result<String> {
@@ -261,16 +662,73 @@ pub fn create_branch(branch_name: &str, from_point: Option<&str>, opts: RunOpts)
     run_git_command("checkout", &args, opts)
 }
 
+/// True when HEAD points at a commit rather than a branch. Runs under `--dry-run` too.
+pub fn is_detached_head(opts: RunOpts) -> Result<bool> {
+    // Exits 1 when HEAD is not a symbolic ref; an unborn branch still is one.
+    let code = run_git_status_check("symbolic-ref", &["--quiet", "HEAD"], opts)?;
+    Ok(code == Some(1))
+}
+
+/// The first seven characters of a commit hash, as `git log --oneline` shows it.
+pub fn short_hash(hash: &str) -> &str {
+    &hash[..7.min(hash.len())]
+}
+
 pub fn get_head_commit_hash(opts: RunOpts) -> Result<String> {
-    run_git_command("rev-parse", &["HEAD"], opts)
+    git_backend::for_opts(opts).head_commit()
 }
 
 pub fn get_latest_tag(opts: RunOpts) -> Result<String> {
     run_git_command("describe", &["--tags", "--abbrev=0"], opts)
 }
 
-pub fn get_commit_history(range: &str, opts: RunOpts) -> Result<String> {
-    run_git_command("log", &[range, "--pretty=format:%H|%s"], opts)
+/// True when the repository is a shallow clone.
+pub fn is_shallow_repository(opts: RunOpts) -> Result<bool> {
+    Ok(run_git_command("rev-parse", &["--is-shallow-repository"], opts)? == "true")
+}
+
+/// Turns a shallow clone into a full one, fetching all tags too.
+pub fn unshallow(opts: RunOpts) -> Result<String> {
+    run_git_command("fetch", &["--unshallow", "--tags"], opts)
+}
+
+/// Tags on commits reachable from HEAD.
+pub fn list_merged_tags(opts: RunOpts) -> Result<Vec<String>> {
+    let output = run_git_command("tag", &["--merged", "HEAD"], opts)?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// Every tag in the repository.
+pub fn list_tags(opts: RunOpts) -> Result<Vec<String>> {
+    let output = run_git_command("tag", &["-l"], opts)?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// Subjects of the last `limit` commits reachable from HEAD, newest first.
+pub fn get_recent_subjects(limit: usize, opts: RunOpts) -> Result<Vec<String>> {
+    let count = format!("-{}", limit);
+    let output = run_git_command("log", &[count.as_str(), "--format=%s"], opts)?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// The branch `origin/HEAD` points at, when the remote advertises one.
+pub fn get_origin_head_branch(opts: RunOpts) -> Option<String> {
+    run_git_command(
+        "symbolic-ref",
+        &["--quiet", "--short", "refs/remotes/origin/HEAD"],
+        opts,
+    )
+    .ok()
+    .and_then(|r| r.strip_prefix("origin/").map(str::to_string))
+}
+
+/// `git log <args>` with one record per commit: `<RS>hash<US>author<US>subject`
+/// followed by the touched paths, one per line (`<RS>` is `\x1e`, `<US>` is `\x1f`).
+/// Parse it with [`crate::history::parse_log`].
+pub fn get_log_records(args: &[&str], opts: RunOpts) -> Result<String> {
+    let mut log_args = vec!["--name-only", "--pretty=format:%x1e%H%x1f%an%x1f%s"];
+    log_args.extend_from_slice(args);
+    run_git_command("log", &log_args, opts)
 }
 
 pub fn get_remote_url(opts: RunOpts) -> Result<String> {
@@ -299,13 +757,15 @@ pub fn get_status_full(opts: RunOpts) -> Result<String> {
     run_git_command("status", &[], opts)
 }
 
-pub fn status_for_path(relative_path: &str, opts: RunOpts) -> Result<String> {
-    run_git_command("status", &["--short", "--", relative_path], opts)
+/// Short status for `pathspec`. Paths are relative to the git root, from
+/// whichever directory tbdflow runs.
+pub fn status_for_path(pathspec: &str, opts: RunOpts) -> Result<String> {
+    run_git_command("status", &["--porcelain", "--", pathspec], opts)
 }
 
 /// Status excluding the given project directories (monorepo root use).
 pub fn status_excluding_projects(project_dirs: &[String], opts: RunOpts) -> Result<String> {
-    let mut args = vec!["--short", "--"];
+    let mut args = vec!["--porcelain", "--"];
     let exclude_args: Vec<String> = project_dirs
         .iter()
         .map(|dir| format!(":(exclude){}/", dir))
@@ -318,56 +778,87 @@ pub fn status_excluding_projects(project_dirs: &[String], opts: RunOpts) -> Resu
     run_git_command("status", &args, opts)
 }
 
-/// Monorepo-aware status: scoped to sub-project, root-only, or full.
-pub fn get_scoped_status(config: &Config, opts: RunOpts) -> Result<String> {
-    let git_root = std::path::PathBuf::from(get_git_root(opts)?);
-    let current_dir = std::env::current_dir()?;
-    let project_root = crate::config::find_project_root()?;
+/// What a command run from the current directory works on. Outside a
+/// monorepo project this is the whole repository, wherever tbdflow runs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorkScope {
+    Repo,
+    /// Root-level files only, at the root of a monorepo.
+    RootOnly,
+    /// A monorepo project, as a path relative to the git root.
+    Project(String),
+}
 
-    if let Some(proj_root) = project_root {
-        if current_dir == proj_root {
-            status_for_path(".", opts)
-        } else {
-            let relative_path = proj_root.strip_prefix(&git_root).unwrap_or(&proj_root);
-            let path_str = relative_path.to_str().ok_or_else(|| {
-                anyhow::anyhow!(
-                    "Project path contains non-UTF-8 characters: {:?}",
-                    relative_path
-                )
-            })?;
-            status_for_path(path_str, opts)
+impl WorkScope {
+    /// The git pathspec covering the scope, valid from any directory.
+    fn pathspec(&self) -> String {
+        match self {
+            WorkScope::Project(dir) => format!(":/{}", dir),
+            _ => ":/".to_string(),
         }
-    } else if crate::config::is_monorepo_root(config, &current_dir, &git_root) {
-        status_excluding_projects(&config.monorepo.project_dirs, opts)
+    }
+}
+
+/// The scope of the current directory: the project it is in (one with
+/// `project_root` set, or a `monorepo.project_dirs` entry), the monorepo
+/// root, or else the repository.
+pub fn work_scope(config: &Config, opts: RunOpts) -> Result<WorkScope> {
+    let git_root = std::path::PathBuf::from(get_git_root(opts.for_reads())?);
+    let current_dir = std::env::current_dir()?;
+    let project = match crate::config::find_project_root(opts)? {
+        Some(dir) => Some(dir),
+        None if config.monorepo.enabled => config
+            .monorepo
+            .project_dirs
+            .iter()
+            .map(|dir| git_root.join(dir))
+            .find(|dir| current_dir.starts_with(dir)),
+        None => None,
+    };
+    if let Some(dir) = project {
+        let relative = dir.strip_prefix(&git_root).unwrap_or(&dir);
+        let path = relative.to_str().ok_or_else(|| {
+            anyhow::anyhow!("Project path contains non-UTF-8 characters: {:?}", relative)
+        })?;
+        if !path.is_empty() {
+            return Ok(WorkScope::Project(path.to_string()));
+        }
+    }
+    if crate::config::is_monorepo_root(config, &current_dir, &git_root) {
+        Ok(WorkScope::RootOnly)
     } else {
-        get_status_short(opts)
+        Ok(WorkScope::Repo)
+    }
+}
+
+/// Monorepo-aware status: scoped to sub-project, root-only, or full.
+pub fn get_scoped_status(config: &Config, opts: RunOpts) -> Result<String> {
+    match work_scope(config, opts)? {
+        WorkScope::RootOnly => status_excluding_projects(&config.monorepo.project_dirs, opts),
+        scope => status_for_path(&scope.pathspec(), opts),
     }
 }
 
 /// Monorepo-aware staging. At the repo root, excludes project dirs unless `include_projects` is set.
 pub fn stage_scoped_changes(config: &Config, include_projects: bool, opts: RunOpts) -> Result<()> {
-    let git_root = std::path::PathBuf::from(get_git_root(opts)?);
-    let current_dir = std::env::current_dir()?;
-
-    if current_dir == git_root
-        && config.monorepo.enabled
-        && !config.monorepo.project_dirs.is_empty()
-    {
-        if include_projects {
+    match work_scope(config, opts)? {
+        WorkScope::RootOnly if include_projects => {
             println!(
                 "{}",
                 "Including all project directories in commit.".yellow()
             );
             add_all(opts)?;
-        } else {
+        }
+        WorkScope::RootOnly => {
             println!(
                 "{}",
                 "Monorepo root detected. Staging root-level files only.".yellow()
             );
             add_excluding_projects(&config.monorepo.project_dirs, opts)?;
         }
-    } else {
-        add_all(opts)?;
+        scope => {
+            run_git_command("add", &["--", &scope.pathspec()], opts)?;
+        }
     }
 
     Ok(())
@@ -406,6 +897,22 @@ pub fn get_commit_count_ahead(branch: &str, main_branch: &str, opts: RunOpts) ->
     run_git_command("rev-list", &["--count", &range], opts)
 }
 
+/// Number of commits on `origin/<main_branch>` that `HEAD` does not have yet.
+pub fn get_commit_count_behind_main(main_branch: &str, opts: RunOpts) -> Result<String> {
+    let range = format!("HEAD..origin/{}", main_branch);
+    run_git_command("rev-list", &["--count", &range], opts)
+}
+
+/// Number of commits on `origin/<main_branch>` that `branch` does not have yet.
+pub fn get_branch_count_behind_main(
+    branch: &str,
+    main_branch: &str,
+    opts: RunOpts,
+) -> Result<String> {
+    let range = format!("{}..origin/{}", branch, main_branch);
+    run_git_command("rev-list", &["--count", &range], opts)
+}
+
 pub fn get_branch_log(branch: &str, main_branch: &str, opts: RunOpts) -> Result<String> {
     let range = format!("origin/{}..{}", main_branch, branch);
     run_git_command("log", &["--oneline", "-n", "10", &range], opts)
@@ -463,23 +970,185 @@ pub fn get_stale_branches(
     Ok(stale_branches)
 }
 
+/// Returns every distinct `Name <email>` that has authored a commit in the repository.
+pub fn get_known_authors(opts: RunOpts) -> Result<Vec<String>> {
+    let output = run_git_command("log", &["--all", "--format=%an <%ae>"], opts)?;
+    let mut authors: Vec<String> = Vec::new();
+    for line in output.lines() {
+        if !authors.iter().any(|a| a == line) {
+            authors.push(line.to_string());
+        }
+    }
+    Ok(authors)
+}
+
+pub fn get_git_dir(opts: RunOpts) -> Result<String> {
+    run_git_command("rev-parse", &["--absolute-git-dir"], opts)
+}
+
+/// Hashes `content` the way git hashes a blob, without writing it to the object store.
+pub fn hash_blob(content: &str, opts: RunOpts) -> Result<String> {
+    let input = GitInput {
+        stdin: Some(content),
+        ..GitInput::default()
+    };
+    let output = opts
+        .runner
+        .output("hash-object", &["--stdin"], input, opts)?;
+    if output.code == Some(0) {
+        Ok(output.stdout)
+    } else {
+        Err(GitError::Git(output.stderr).into())
+    }
+}
+
 pub fn get_user_name(opts: RunOpts) -> Result<String> {
     run_git_command("config", &["user.name"], opts)
 }
 
+/// Reads a git config value, or `None` when the key is unset.
+pub fn get_config_value(key: &str, opts: RunOpts) -> Option<String> {
+    run_git_command("config", &["--get", key], opts)
+        .ok()
+        .filter(|v| !v.is_empty())
+}
+
+pub fn set_config_value(key: &str, value: &str, opts: RunOpts) -> Result<String> {
+    run_git_command("config", &[key, value], opts)
+}
+
+/// Every `branch.*` config entry as `(key, value)` pairs. Values may span
+/// several lines, so entries are read NUL-separated.
+pub fn get_branch_config_entries(opts: RunOpts) -> Vec<(String, String)> {
+    // Exits non-zero when nothing matches.
+    run_git_command("config", &["--null", "--get-regexp", r"^branch\."], opts)
+        .map(|output| {
+            output
+                .split('\0')
+                .filter_map(|entry| {
+                    let (key, value) = entry.split_once('\n').unwrap_or((entry, ""));
+                    (!key.is_empty()).then(|| (key.to_string(), value.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn get_git_version(opts: RunOpts) -> Result<String> {
+    run_git_command("--version", &[], opts)
+}
+
+pub fn get_user_email(opts: RunOpts) -> Result<String> {
+    run_git_command("config", &["user.email"], opts)
+}
+
+/// The current user as `Name <email>`, with empty parts when unset.
+pub fn get_user_identity(opts: RunOpts) -> String {
+    format!(
+        "{} <{}>",
+        get_user_name(opts).unwrap_or_default(),
+        get_user_email(opts).unwrap_or_default()
+    )
+}
+
 pub fn get_commit_message(commit_hash: &str, opts: RunOpts) -> Result<String> {
     run_git_command("log", &["-1", "--format=%s", commit_hash], opts)
 }
 
-/// Returns format: `hash|author|subject`
-pub fn get_log_since(since: &str, opts: RunOpts) -> Result<String> {
+/// Full commit message (subject, body and trailers).
+pub fn get_commit_full_message(commit_hash: &str, opts: RunOpts) -> Result<String> {
+    run_git_command("log", &["-1", "--format=%B", commit_hash], opts)
+}
+
+/// Total lines added plus deleted in a commit; binary files count as zero.
+pub fn get_commit_line_count(commit_hash: &str, opts: RunOpts) -> Result<usize> {
+    let output = run_git_command(
+        "diff-tree",
+        &["--no-commit-id", "--numstat", "-r", commit_hash],
+        opts,
+    )?;
+    Ok(output
+        .lines()
+        .flat_map(|line| line.split('\t').take(2))
+        .filter_map(|n| n.parse::<usize>().ok())
+        .sum())
+}
+
+/// Returns format: `hash|author <email>|subject`, newest first.
+pub fn get_log_range(range: &str, opts: RunOpts) -> Result<String> {
+    run_git_command("log", &["--pretty=format:%H|%an <%ae>|%s", range], opts)
+}
+
+/// Full messages of the non-merge commits in `range`, oldest first, each as
+/// `sha<US>message<RS>`, where `<US>` is `\x1f` and `<RS>` is `\x1e`.
+pub fn get_range_messages(range: &str, opts: RunOpts) -> Result<String> {
     run_git_command(
         "log",
-        &["--since", since, "--pretty=format:%H|%an|%s"],
+        &[
+            "--no-merges",
+            "--reverse",
+            "--pretty=format:%H%x1f%B%x1e",
+            range,
+        ],
+        opts,
+    )
+}
+
+/// Full messages of the non-merge commits in `range` (since `since`, when
+/// given), newest first, each as `sha<US>date<US>author<US>message<RS>`,
+/// where `<US>` is `\x1f` and `<RS>` is `\x1e`.
+pub fn get_log_messages(range: &str, since: Option<&str>, opts: RunOpts) -> Result<String> {
+    let mut args = vec!["--no-merges"];
+    if let Some(since) = since {
+        args.extend(["--since", since]);
+    }
+    args.extend(["--pretty=format:%H%x1f%as%x1f%an%x1f%B%x1e", range]);
+    run_git_command("log", &args, opts)
+}
+
+/// Commits that landed on `trunk` since `since`, newest first, one per line as
+/// `committer_timestamp<US>author`. Only first-parent history is followed, so
+/// a merged branch counts once, as its merge commit.
+pub fn get_trunk_integrations(trunk: &str, since: &str, opts: RunOpts) -> Result<String> {
+    run_git_command(
+        "log",
+        &[
+            "--first-parent",
+            "--since",
+            since,
+            "--pretty=format:%ct%x1f%an",
+            trunk,
+        ],
+        opts,
+    )
+}
+
+/// First-parent commits on `trunk` since `since`, one per line as
+/// `hash<US>author<US>unix_time<US>subject`, where `<US>` is `\x1f`.
+pub fn get_trunk_commits(trunk: &str, since: &str, opts: RunOpts) -> Result<String> {
+    run_git_command(
+        "log",
+        &[
+            "--first-parent",
+            "--since",
+            since,
+            "--pretty=format:%H%x1f%an%x1f%ct%x1f%s",
+            trunk,
+        ],
         opts,
     )
 }
 
+/// Commits in `range` with the values of their `key` trailer, one per line as
+/// `short_sha<US>date<US>subject<US>value[,value]`, where `<US>` is `\x1f`.
+pub fn get_log_with_trailer(range: &str, key: &str, opts: RunOpts) -> Result<String> {
+    let format = format!(
+        "--pretty=format:%h%x1f%as%x1f%s%x1f%(trailers:key={},valueonly,separator=%x2C)",
+        key
+    );
+    run_git_command("log", &[&format, range], opts)
+}
+
 pub fn get_latest_commit_time(branch: &str, opts: RunOpts) -> Result<Option<DateTime<Utc>>> {
     let ref_name = format!("origin/{}", branch);
     let output = run_git_command("log", &["-1", "--format=%cI", &ref_name], opts)?;
@@ -492,6 +1161,14 @@ pub fn get_latest_commit_time(branch: &str, opts: RunOpts) -> Result<Option<Date
     }
 }
 
+/// When `commit_hash` was committed.
+pub fn get_commit_time(commit_hash: &str, opts: RunOpts) -> Result<DateTime<Utc>> {
+    let output = run_git_command("log", &["-1", "--format=%cI", commit_hash], opts)?;
+    DateTime::parse_from_rfc3339(output.trim())
+        .map(|dt| dt.with_timezone(&Utc))
+        .with_context(|| format!("Could not read the commit time of '{}'", commit_hash))
+}
+
 pub fn get_file_churn(
     branch: &str,
     hours: u64,
@@ -521,7 +1198,7 @@ pub fn get_file_churn(
     }
 
     let mut sorted: Vec<(String, usize)> = counts.into_iter().collect();
-    sorted.sort_by(|a, b| b.1.cmp(&a.1));
+    sorted.sort_by_key(|b| std::cmp::Reverse(b.1));
     sorted.truncate(limit);
     Ok(sorted)
 }
@@ -529,7 +1206,15 @@ pub fn get_file_churn(
 pub fn get_changed_files(commit_hash: &str, opts: RunOpts) -> Result<Vec<String>> {
     let output = run_git_command(
         "diff-tree",
-        &["--no-commit-id", "--name-only", "-r", commit_hash],
+        // Merge commits are compared with their first parent.
+        &[
+            "-m",
+            "--first-parent",
+            "--no-commit-id",
+            "--name-only",
+            "-r",
+            commit_hash,
+        ],
         opts,
     )?;
 
@@ -634,6 +1319,162 @@ pub fn get_diff_hunks_between_refs(
     Ok(parse_hunk_headers(&output, DiffSide::New))
 }
 
+/// Files that would conflict when merging `theirs` into `ours`, found with
+/// `git merge-tree --write-tree` without touching the index or worktree.
+/// `None` when git cannot tell (git older than 2.38, or an unknown ref).
+pub fn predict_merge_conflicts(
+    ours: &str,
+    theirs: &str,
+    opts: RunOpts,
+) -> Result<Option<Vec<String>>> {
+    let args = ["--write-tree", "--name-only", "--no-messages", ours, theirs];
+    if opts.verbose {
+        println!(
+            "{} git merge-tree {}",
+            "[CHECKING] ".dimmed(),
+            args.join(" ")
+        );
+    }
+    let output = opts
+        .runner
+        .output("merge-tree", &args, GitInput::default(), opts)?;
+    // Exit 0 is a clean merge, 1 a conflicted one; anything else is an error.
+    match output.code {
+        Some(0) => Ok(Some(Vec::new())),
+        Some(1) => {
+            let mut files: Vec<String> = output
+                .stdout
+                .lines()
+                .skip(1) // the tree id
+                .take_while(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect();
+            files.dedup();
+            Ok(Some(files))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Runs git with its index at `index` and `input` on stdin, for the plumbing
+/// behind [`commit_file_onto`].
+fn run_git_plumbing(
+    command: &str,
+    args: &[&str],
+    index: &std::path::Path,
+    stdin: Option<&str>,
+    opts: RunOpts,
+) -> Result<String> {
+    let input = GitInput {
+        stdin,
+        index_file: Some(index),
+    };
+    let output = opts.runner.output(command, args, input, opts)?;
+    if output.code == Some(0) {
+        Ok(output.stdout)
+    } else {
+        Err(GitError::Git(output.stderr).into())
+    }
+}
+
+/// Makes a commit on top of `parent` that sets `path` to `content`, without
+/// touching the index, the worktree or any branch, and returns its hash.
+pub fn commit_file_onto(
+    parent: &str,
+    path: &str,
+    content: &str,
+    message: &str,
+    opts: RunOpts,
+) -> Result<String> {
+    if opts.dry_run {
+        println!(
+            "{}",
+            format!("[DRY RUN] Would commit {} onto {}", path, parent).yellow()
+        );
+        return Ok(String::new());
+    }
+    if opts.verbose {
+        println!("{} commit {} onto {}", "[RUNNING] ".cyan(), path, parent);
+    }
+    let index = std::path::PathBuf::from(get_git_dir(opts)?).join("tbdflow-commit-file.index");
+    let result = (|| {
+        run_git_plumbing("read-tree", &[parent], &index, None, opts)?;
+        let blob = run_git_plumbing(
+            "hash-object",
+            &["-w", "--stdin"],
+            &index,
+            Some(content),
+            opts,
+        )?;
+        let entry = format!("100644,{},{}", blob, path);
+        run_git_plumbing(
+            "update-index",
+            &["--add", "--cacheinfo", &entry],
+            &index,
+            None,
+            opts,
+        )?;
+        let tree = run_git_plumbing("write-tree", &[], &index, None, opts)?;
+        run_git_plumbing(
+            "commit-tree",
+            &[&tree, "-p", parent, "-m", message],
+            &index,
+            None,
+            opts,
+        )
+    })();
+    let _ = std::fs::remove_file(&index);
+    result
+}
+
+/// Moves `refname` from `old` to `new`, failing if it no longer points at `old`.
+pub fn update_ref(refname: &str, new: &str, old: &str, opts: RunOpts) -> Result<String> {
+    run_git_command("update-ref", &[refname, new, old], opts)
+}
+
+/// The content of `path` at `rev`, or `None` when it has no such file.
+pub fn show_file(rev: &str, path: &str, opts: RunOpts) -> Option<String> {
+    run_git_command("show", &[&format!("{}:{}", rev, path)], opts).ok()
+}
+
+/// Paths of the files directly in `dir` at `rev`; empty when there is none.
+pub fn list_files_at(rev: &str, dir: &str, opts: RunOpts) -> Vec<String> {
+    run_git_command(
+        "ls-tree",
+        &[
+            "--name-only",
+            rev,
+            &format!("{}/", dir.trim_end_matches('/')),
+        ],
+        opts,
+    )
+    .map(|out| out.lines().map(str::to_string).collect())
+    .unwrap_or_default()
+}
+
+/// Restores `path` in the index and worktree to what HEAD has.
+pub fn checkout_path_from_head(path: &str, opts: RunOpts) -> Result<String> {
+    run_git_command("checkout", &["HEAD", "--", path], opts)
+}
+
+/// Distinct authors of the commits in `range` that touch any of `paths`.
+pub fn get_authors_touching(range: &str, paths: &[String], opts: RunOpts) -> Result<Vec<String>> {
+    let mut args = vec!["--format=%an", range, "--"];
+    args.extend(paths.iter().map(String::as_str));
+    let output = run_git_command("log", &args, opts)?;
+    let mut authors: Vec<String> = Vec::new();
+    for author in output.lines().filter(|l| !l.is_empty()) {
+        if !authors.iter().any(|a| a == author) {
+            authors.push(author.to_string());
+        }
+    }
+    Ok(authors)
+}
+
+pub fn get_commit_author(commit_hash: &str, opts: RunOpts) -> Result<String> {
+    run_git_command("log", &["-1", "--format=%an", commit_hash], opts)
+}
+
 pub fn get_branch_author(branch: &str, opts: RunOpts) -> Result<String> {
     let ref_name = format!("origin/{}", branch);
     run_git_command("log", &["-1", "--format=%an", &ref_name], opts)
@@ -694,12 +1535,12 @@ pub fn is_ancestor_of(commit_hash: &str, branch: &str, opts: RunOpts) -> Result<
     // Use refs/heads/ to unambiguously refer to the local branch,
     // avoiding conflicts with tags or other refs that share the same name.
     let qualified_branch = format!("refs/heads/{}", branch);
-    let status = run_git_status_check(
+    let code = run_git_status_check(
         "merge-base",
         &["--is-ancestor", &full_hash, &qualified_branch],
         opts,
     )?;
-    Ok(status.code() == Some(0))
+    Ok(code == Some(0))
 }
 
 pub fn get_commit_subject(commit_hash: &str, opts: RunOpts) -> Result<String> {
@@ -733,17 +1574,6 @@ pub enum CiStatus {
     Unknown(String),
 }
 
-/// Returns true if the GitHub CLI (`gh`) is installed and executable.
-pub fn is_gh_cli_available() -> bool {
-    Command::new("gh")
-        .arg("--version")
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status()
-        .map(|s| s.success())
-        .unwrap_or(false)
-}
-
 /// Check the CI status of the latest commit on the given branch using the `gh` CLI.
 ///
 /// Uses `gh api` to query the combined commit status and check-runs for the
@@ -758,7 +1588,7 @@ pub fn check_ci_status(branch: &str, opts: RunOpts) -> CiStatus {
     }
 
     // First, check if `gh` CLI is available
-    if !is_gh_cli_available() {
+    if !gh::is_available() {
         return CiStatus::Unknown("gh CLI is not installed".to_string());
     }
 
@@ -772,8 +1602,8 @@ pub fn check_ci_status(branch: &str, opts: RunOpts) -> CiStatus {
 
     // Use `gh run list` to query the status of the latest workflow run on the branch.
     // This gives us the overall conclusion of the most recent CI run.
-    let output = Command::new("gh")
-        .args([
+    let output = match gh::run(
+        &[
             "run",
             "list",
             "--branch",
@@ -784,25 +1614,21 @@ pub fn check_ci_status(branch: &str, opts: RunOpts) -> CiStatus {
             "status,conclusion",
             "--jq",
             ".[0] | .status + \"/\" + .conclusion",
-        ])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output();
-
-    let output = match output {
+        ],
+        opts,
+    ) {
         Ok(o) => o,
         Err(e) => {
             return CiStatus::Unknown(format!("Failed to run gh CLI: {}", e));
         }
     };
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if !output.success {
         // If the command failed because there are no workflow runs, treat as unknown
-        return CiStatus::Unknown(format!("gh run list failed: {}", stderr));
+        return CiStatus::Unknown(format!("gh run list failed: {}", output.stderr));
     }
 
-    let result = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let result = output.stdout;
 
     if opts.verbose {
         println!("{} gh run status: {}", "[PRE-FLIGHT]".cyan(), result);
@@ -842,9 +1668,32 @@ pub fn stash_apply(hash: &str, opts: RunOpts) -> Result<String> {
     run_git_command("stash", &["apply", hash], opts)
 }
 
+/// Stashes tracked and untracked changes under `message`.
+pub fn stash_push(message: &str, opts: RunOpts) -> Result<String> {
+    run_git_command(
+        "stash",
+        &["push", "--include-untracked", "-m", message],
+        opts,
+    )
+}
+
+/// Finds the newest stash whose message is exactly `message`, e.g. `stash@{2}`.
+pub fn find_stash(message: &str, opts: RunOpts) -> Result<Option<String>> {
+    let output = run_git_command("stash", &["list", "--format=%gd%x09%gs"], opts)?;
+    // The subject reads "On <branch>: <message>".
+    let suffix = format!(": {}", message);
+    Ok(output.lines().find_map(|line| {
+        let (reference, subject) = line.split_once('\t')?;
+        subject.ends_with(&suffix).then(|| reference.to_string())
+    }))
+}
+
+pub fn stash_pop(reference: &str, opts: RunOpts) -> Result<String> {
+    run_git_command("stash", &["pop", reference], opts)
+}
+
 pub fn is_working_directory_dirty(opts: RunOpts) -> Result<bool> {
-    let output = run_git_command("status", &["--porcelain"], opts)?;
-    Ok(!output.is_empty())
+    git_backend::for_opts(opts).is_dirty()
 }
 
 /// Returns (ahead, behind) commit counts relative to the upstream tracking branch.
@@ -852,7 +1701,8 @@ pub fn is_working_directory_dirty(opts: RunOpts) -> Result<bool> {
 pub fn get_ahead_behind(branch: &str, opts: RunOpts) -> Result<(u64, u64)> {
     // Use full ref path to avoid ambiguity with tags sharing the branch name.
     let local_ref = format!("refs/heads/{}", branch);
-    let upstream = format!("{}@{{u}}", local_ref);
+    // `@{u}` only resolves against a short branch name, not a full ref path.
+    let upstream = format!("{}@{{u}}", branch);
     let range = format!("{}...{}", local_ref, upstream);
     let output = run_git_command("rev-list", &["--left-right", "--count", &range], opts);
     match output {
@@ -889,9 +1739,134 @@ pub fn check_git_operation_in_progress(opts: RunOpts) -> Result<Option<String>>
     Ok(None)
 }
 
+/// A scripted [`GitRunner`] for unit-testing handlers without a repository.
+#[cfg(test)]
+pub(crate) mod fake {
+    use super::{GitError, GitInput, GitOutput, GitRunner, RunOpts};
+    use anyhow::Result;
+    use std::sync::Mutex;
+
+    /// Records every command it is asked to run and answers from a script.
+    /// Commands nothing in the script matches succeed with no output.
+    #[derive(Debug, Default)]
+    pub struct FakeGit {
+        script: Vec<(String, Result<String, String>)>,
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl FakeGit {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Answers commands whose command line starts with `prefix` with `stdout`.
+        pub fn respond(mut self, prefix: &str, stdout: &str) -> Self {
+            self.script
+                .push((prefix.to_string(), Ok(stdout.to_string())));
+            self
+        }
+
+        /// Fails commands whose command line starts with `prefix` with `stderr`.
+        pub fn fail(mut self, prefix: &str, stderr: &str) -> Self {
+            self.script
+                .push((prefix.to_string(), Err(stderr.to_string())));
+            self
+        }
+
+        /// Leaks the fake, as [`RunOpts`] only holds `'static` runners, and
+        /// returns it with options that run git through it.
+        pub fn install(self) -> (&'static FakeGit, RunOpts) {
+            let fake: &'static FakeGit = Box::leak(Box::new(self));
+            (fake, RunOpts::new(false, false).with_runner(fake))
+        }
+
+        /// Every command line run so far, without the leading `git`.
+        pub fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+
+        /// The calls starting with `prefix`.
+        pub fn calls_to(&self, prefix: &str) -> Vec<String> {
+            self.calls()
+                .into_iter()
+                .filter(|call| call.starts_with(prefix))
+                .collect()
+        }
+
+        fn answer(&self, command: &str, args: &[&str]) -> Result<String, String> {
+            let line = std::iter::once(command)
+                .chain(args.iter().copied())
+                .collect::<Vec<_>>()
+                .join(" ");
+            self.calls.lock().unwrap().push(line.clone());
+            self.script
+                .iter()
+                .find(|(prefix, _)| line.starts_with(prefix.as_str()))
+                .map_or(Ok(String::new()), |(_, answer)| answer.clone())
+        }
+    }
+
+    impl GitRunner for FakeGit {
+        fn run(&self, command: &str, args: &[&str], _opts: RunOpts) -> Result<String> {
+            self.answer(command, args)
+                .map_err(|stderr| GitError::Git(stderr).into())
+        }
+
+        fn status(&self, command: &str, args: &[&str], _opts: RunOpts) -> Result<Option<i32>> {
+            Ok(Some(if self.answer(command, args).is_ok() {
+                0
+            } else {
+                1
+            }))
+        }
+
+        fn output(
+            &self,
+            command: &str,
+            args: &[&str],
+            _input: GitInput<'_>,
+            _opts: RunOpts,
+        ) -> Result<GitOutput> {
+            Ok(match self.answer(command, args) {
+                Ok(stdout) => GitOutput {
+                    code: Some(0),
+                    stdout,
+                    stderr: String::new(),
+                },
+                Err(stderr) => GitOutput {
+                    code: Some(1),
+                    stdout: String::new(),
+                    stderr,
+                },
+            })
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_short_hash_returns_first_seven_chars() {
+        assert_eq!(short_hash("abc1234567890"), "abc1234");
+    }
+
+    #[test]
+    fn test_short_hash_handles_exact_seven_chars() {
+        assert_eq!(short_hash("abc1234"), "abc1234");
+    }
+
+    #[test]
+    fn test_short_hash_handles_short_input() {
+        assert_eq!(short_hash("abc"), "abc");
+    }
+
+    #[test]
+    fn test_short_hash_handles_empty_input() {
+        assert_eq!(short_hash(""), "");
+    }
 
     #[test]
     fn test_git_is_installed() {
@@ -915,6 +1890,85 @@ mod tests {
         assert!(output.contains("git version"), "Output was: {}", output);
     }
 
+    #[test]
+    fn test_run_git_command_with_timeout_returns_output() {
+        let opts = RunOpts::new(false, false).with_timeout(Some(30));
+        let output = run_git_command("--version", &[], opts).unwrap();
+        assert!(output.contains("git version"), "Output was: {}", output);
+    }
+
+    #[test]
+    fn test_hash_blob_feeds_content_on_stdin() {
+        let opts = RunOpts::new(false, false).with_timeout(Some(30));
+        assert_eq!(
+            hash_blob("hello\n", opts).unwrap(),
+            "ce013625030ba8dba906f756967f9e9ca394464a"
+        );
+    }
+
+    #[test]
+    fn test_predict_merge_conflicts_runs_through_the_runner() {
+        let (fake, opts) = super::fake::FakeGit::new()
+            .respond("merge-tree", "4b825dc642cb6eb9a060e54bf8d69288fbee4904")
+            .install();
+        assert_eq!(
+            predict_merge_conflicts("HEAD", "origin/main", opts).unwrap(),
+            Some(Vec::new())
+        );
+        assert_eq!(
+            fake.calls(),
+            vec!["merge-tree --write-tree --name-only --no-messages HEAD origin/main"]
+        );
+    }
+
+    #[test]
+    fn test_commit_file_onto_runs_plumbing_through_the_runner() {
+        let (fake, opts) = super::fake::FakeGit::new()
+            .respond("rev-parse --absolute-git-dir", "/nonexistent/.git")
+            .respond("hash-object", "b10b")
+            .respond("write-tree", "7ree")
+            .respond("commit-tree", "c0ffee")
+            .install();
+        assert_eq!(
+            commit_file_onto("base", "reviews/a.yml", "a: 1\n", "record", opts).unwrap(),
+            "c0ffee"
+        );
+        assert_eq!(
+            fake.calls_to("commit-tree"),
+            vec!["commit-tree 7ree -p base -m record"]
+        );
+        assert_eq!(
+            fake.calls_to("update-index"),
+            vec!["update-index --add --cacheinfo 100644,b10b,reviews/a.yml"]
+        );
+    }
+
+    #[test]
+    fn test_run_git_status_check_with_timeout_returns_exit_code() {
+        let opts = RunOpts::new(false, false).with_timeout(Some(30));
+        assert_eq!(
+            run_git_status_check("--version", &[], opts).unwrap(),
+            Some(0)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_with_timeout_kills_hung_command() {
+        let mut hung = Command::new("sh");
+        hung.args(["-c", "sleep 5"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        let started = Instant::now();
+        let err = run_with_timeout(&mut hung, "fetch", 1).unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert!(
+            err.to_string().contains("'git fetch' timed out after 1s"),
+            "Error was: {}",
+            err
+        );
+    }
+
     #[test]
     fn test_status() {
         let opts = RunOpts::new(true, false);
@@ -947,4 +2001,15 @@ mod tests {
         assert_ne!(CiStatus::Green, CiStatus::Pending);
         assert_ne!(CiStatus::Failed, CiStatus::Pending);
     }
+
+    #[test]
+    fn is_non_fast_forward_matches_push_rejections() {
+        assert!(is_non_fast_forward(
+            " ! [rejected]        main -> main (fetch first)"
+        ));
+        assert!(is_non_fast_forward(
+            " ! [rejected]        main -> main (non-fast-forward)"
+        ));
+        assert!(!is_non_fast_forward("fatal: could not read Username"));
+    }
 }
@@ -0,0 +1,191 @@
+//! Backends for the repository queries tbdflow makes on almost every command.
+//!
+//! The default runs the `git` binary, like the rest of the `git` module. Built
+//! with the `gix` feature and `git.backend: gix` in `.tbdflow.yml`, the same
+//! queries run in-process through gitoxide instead. Anything that changes the
+//! repository (commits included) keeps going through `git`, so hooks, signing
+//! and the user's git config apply exactly as they would on the command line.
+
+use crate::config::GitBackendKind;
+use crate::git::{self, RunOpts};
+use anyhow::Result;
+
+/// Read-only repository queries with more than one implementation.
+pub trait GitBackend {
+    /// The checked-out branch, or an empty string on a detached HEAD.
+    fn current_branch(&self) -> Result<String>;
+    /// Full hash of the commit HEAD points at.
+    fn head_commit(&self) -> Result<String>;
+    /// Names of all local branches, sorted.
+    fn local_branches(&self) -> Result<Vec<String>>;
+    /// True when `git status --porcelain` would print anything, untracked files included.
+    fn is_dirty(&self) -> Result<bool>;
+}
+
+/// Runs the `git` binary.
+pub struct Subprocess {
+    opts: RunOpts,
+}
+
+impl GitBackend for Subprocess {
+    fn current_branch(&self) -> Result<String> {
+        git::run_git_command("branch", &["--show-current"], self.opts)
+    }
+
+    fn head_commit(&self) -> Result<String> {
+        git::run_git_command("rev-parse", &["HEAD"], self.opts)
+    }
+
+    fn local_branches(&self) -> Result<Vec<String>> {
+        let output = git::run_git_command(
+            "for-each-ref",
+            &["--format", "%(refname:short)", "refs/heads/"],
+            self.opts,
+        )?;
+        Ok(output.lines().map(str::to_string).collect())
+    }
+
+    fn is_dirty(&self) -> Result<bool> {
+        let output = git::run_git_command("status", &["--porcelain"], self.opts)?;
+        Ok(!output.is_empty())
+    }
+}
+
+/// The backend `opts` asks for. Dry runs always use [`Subprocess`], so they
+/// print the commands as before, and so does a repository gix cannot open,
+/// so the error comes from git.
+pub fn for_opts(opts: RunOpts) -> Box<dyn GitBackend> {
+    #[cfg(feature = "gix")]
+    if opts.backend == GitBackendKind::Gix
+        && !opts.dry_run
+        && let Ok(backend) = gix_backend::Gix::discover(opts)
+    {
+        return Box::new(backend);
+    }
+    Box::new(Subprocess { opts })
+}
+
+/// Whether this build can honour `kind`.
+pub fn is_supported(kind: GitBackendKind) -> bool {
+    kind == GitBackendKind::Subprocess || cfg!(feature = "gix")
+}
+
+#[cfg(feature = "gix")]
+mod gix_backend {
+    use super::GitBackend;
+    use crate::git::RunOpts;
+    use anyhow::{Context, Result};
+    use colored::Colorize;
+    use std::path::Path;
+
+    /// Answers queries in-process with gitoxide.
+    pub struct Gix {
+        repo: gix::Repository,
+        verbose: bool,
+    }
+
+    impl Gix {
+        pub fn discover(opts: RunOpts) -> Result<Self> {
+            Self::open(Path::new("."), opts)
+        }
+
+        /// The repository containing `dir`.
+        pub fn open(dir: &Path, opts: RunOpts) -> Result<Self> {
+            Ok(Self {
+                repo: gix::discover(dir).context("Failed to open the repository with gix")?,
+                verbose: opts.verbose,
+            })
+        }
+
+        fn trace(&self, query: &str) {
+            if self.verbose {
+                println!("{} {}", "[IN-PROCESS] ".cyan(), query);
+            }
+        }
+    }
+
+    impl GitBackend for Gix {
+        fn current_branch(&self) -> Result<String> {
+            self.trace("current branch");
+            Ok(self
+                .repo
+                .head_name()?
+                .map(|name| name.shorten().to_string())
+                .unwrap_or_default())
+        }
+
+        fn head_commit(&self) -> Result<String> {
+            self.trace("HEAD commit");
+            Ok(self.repo.head_id()?.to_string())
+        }
+
+        fn local_branches(&self) -> Result<Vec<String>> {
+            self.trace("local branches");
+            let mut names = Vec::new();
+            for reference in self
+                .repo
+                .references()?
+                .local_branches()
+                .map_err(|e| anyhow::anyhow!(e))?
+            {
+                let reference = reference.map_err(|e| anyhow::anyhow!(e))?;
+                names.push(reference.name().shorten().to_string());
+            }
+            names.sort();
+            Ok(names)
+        }
+
+        fn is_dirty(&self) -> Result<bool> {
+            self.trace("status");
+            let mut changes = self
+                .repo
+                .status(gix::progress::Discard)?
+                .untracked_files(gix::status::UntrackedFiles::Collapsed)
+                .into_iter(Vec::new())?;
+            match changes.next() {
+                Some(change) => change.map(|_| true).map_err(Into::into),
+                None => Ok(false),
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "gix"))]
+mod tests {
+    use super::gix_backend::Gix;
+    use super::*;
+    use std::path::Path;
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn gix_answers_like_git() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path();
+        git(path, &["init", "-q", "-b", "main"]);
+        git(path, &["config", "user.email", "test@example.com"]);
+        git(path, &["config", "user.name", "Test"]);
+        std::fs::write(path.join("README.md"), "test").unwrap();
+        git(path, &["add", "."]);
+        git(path, &["commit", "-q", "-m", "init"]);
+        git(path, &["branch", "feat/x"]);
+
+        let backend = Gix::open(path, RunOpts::new(false, false)).unwrap();
+        assert_eq!(backend.current_branch().unwrap(), "main");
+        assert_eq!(backend.head_commit().unwrap().len(), 40);
+        assert_eq!(backend.local_branches().unwrap(), vec!["feat/x", "main"]);
+        assert!(!backend.is_dirty().unwrap());
+
+        // Untracked files count, as they do for `git status --porcelain`.
+        std::fs::write(path.join("new.txt"), "new").unwrap();
+        assert!(backend.is_dirty().unwrap());
+    }
+}
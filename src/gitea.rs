@@ -0,0 +1,115 @@
+//! Gitea's REST API (Forgejo serves the same one), for the `gitea` review
+//! strategy.
+
+use crate::config::GiteaConfig;
+use crate::git::{self, RunOpts};
+use crate::rest::{self, Auth};
+use crate::whoami;
+use anyhow::{Result, anyhow, bail};
+use reqwest::Method;
+use serde_json::Value;
+
+/// Token variables read before `review.gitea.token`, in order.
+pub const TOKEN_VARS: &[&str] = &["GITEA_TOKEN", "FORGEJO_TOKEN"];
+
+/// A repository on a Gitea or Forgejo server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gitea {
+    /// Web root of the server, e.g. `https://git.example.com`.
+    pub base: String,
+    pub owner: String,
+    pub repo: String,
+    token: Option<String>,
+}
+
+impl Gitea {
+    /// The repository behind `origin`, on `review.gitea.url` or else the
+    /// server `origin` is on. The token comes from the environment first.
+    pub fn from_config(config: &GiteaConfig, opts: RunOpts) -> Result<Self> {
+        let remote = git::get_remote_url(opts.for_reads()).unwrap_or_default();
+        let (web, owner, repo) = rest::split_remote(&remote).ok_or_else(|| {
+            anyhow!(
+                "Cannot tell the Gitea repository from origin ('{}'). Add an 'origin' remote such as https://git.example.com/owner/repo.",
+                remote
+            )
+        })?;
+        let base = config
+            .url
+            .clone()
+            .or(web)
+            .or_else(|| whoami::remote_host(&remote).map(|host| format!("https://{}", host)))
+            .ok_or_else(|| anyhow!("Set review.gitea.url to the address of the Gitea server."))?;
+        let token = TOKEN_VARS
+            .iter()
+            .find_map(|var| std::env::var(var).ok().filter(|t| !t.is_empty()))
+            .or_else(|| config.token.clone().filter(|t| !t.is_empty()));
+        Ok(Self {
+            base: base.trim_end_matches('/').to_string(),
+            owner,
+            repo,
+            token,
+        })
+    }
+
+    /// The repository's web page, which commit and issue links hang off.
+    pub fn repo_url(&self) -> String {
+        format!("{}/{}/{}", self.base, self.owner, self.repo)
+    }
+
+    /// `path` under the repository's API, e.g. `/issues`.
+    pub fn repo_path(&self, path: &str) -> String {
+        format!("/repos/{}/{}{}", self.owner, self.repo, path)
+    }
+
+    /// Errors unless there is a token to write with.
+    pub fn require_token(&self) -> Result<()> {
+        if self.token.is_some() {
+            return Ok(());
+        }
+        bail!(
+            "No Gitea token. Set {} or review.gitea.token (in .tbdflow.local.yml or your user config).",
+            TOKEN_VARS.join(" or ")
+        )
+    }
+
+    /// Sends one request to `/api/v1{path}` and returns the JSON it answered
+    /// with (`Null` for an empty body, and for everything under `--dry-run`).
+    pub fn call(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+        opts: RunOpts,
+    ) -> Result<Value> {
+        let api = format!("{}/api/v1", self.base);
+        let auth = self.token.as_deref().map(Auth::Token);
+        rest::call("Gitea", &api, method, path, auth, body, opts)
+    }
+
+    pub fn get(&self, path: &str, opts: RunOpts) -> Result<Value> {
+        self.call(Method::GET, path, None, opts)
+    }
+
+    pub fn post(&self, path: &str, body: &Value, opts: RunOpts) -> Result<Value> {
+        self.call(Method::POST, path, Some(body), opts)
+    }
+
+    pub fn patch(&self, path: &str, body: &Value, opts: RunOpts) -> Result<Value> {
+        self.call(Method::PATCH, path, Some(body), opts)
+    }
+
+    pub fn delete(&self, path: &str, opts: RunOpts) -> Result<Value> {
+        self.call(Method::DELETE, path, None, opts)
+    }
+
+    /// True when the server answers. Checked under `--dry-run` too.
+    pub fn is_reachable(&self, opts: RunOpts) -> bool {
+        self.get("/version", opts.for_reads()).is_ok()
+    }
+
+    /// The login the token belongs to.
+    pub fn current_user(&self, opts: RunOpts) -> Option<String> {
+        let user = self.get("/user", opts).ok()?;
+        user["login"].as_str().map(str::to_string)
+    }
+}
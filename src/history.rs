@@ -0,0 +1,113 @@
+//! A parsed view of `git log` shared by the changelog and the review digest.
+
+use crate::git::{self, RunOpts};
+use anyhow::Result;
+use git_conventional::Commit;
+
+const RECORD_SEPARATOR: char = '\x1e';
+const FIELD_SEPARATOR: char = '\x1f';
+
+/// One commit from the log, with its Conventional Commit fields when the
+/// subject follows the spec.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedCommit {
+    pub hash: String,
+    pub author: String,
+    pub subject: String,
+    pub r#type: Option<String>,
+    pub scope: Option<String>,
+    /// The description after `type(scope):`, or the whole subject.
+    pub description: String,
+    pub breaking: bool,
+    /// Paths touched by the commit.
+    pub files: Vec<String>,
+}
+
+impl ParsedCommit {
+    fn new(hash: &str, author: &str, subject: &str, files: Vec<String>) -> Self {
+        let mut commit = ParsedCommit {
+            hash: hash.to_string(),
+            author: author.to_string(),
+            subject: subject.to_string(),
+            r#type: None,
+            scope: None,
+            description: subject.to_string(),
+            breaking: false,
+            files,
+        };
+        if let Ok(parsed) = Commit::parse(subject) {
+            commit.r#type = Some(parsed.type_().as_str().to_string());
+            commit.scope = parsed.scope().map(|s| s.as_str().to_string());
+            commit.description = parsed.description().to_string();
+            commit.breaking = parsed.breaking();
+        }
+        commit
+    }
+
+    pub fn short_hash(&self) -> &str {
+        git::short_hash(&self.hash)
+    }
+
+    pub fn is_conventional(&self) -> bool {
+        self.r#type.is_some()
+    }
+}
+
+/// Parses `git log` output written by [`git::get_log_records`].
+pub fn parse_log(output: &str) -> Vec<ParsedCommit> {
+    output
+        .split(RECORD_SEPARATOR)
+        .filter_map(|record| {
+            let mut lines = record.lines().filter(|l| !l.trim().is_empty());
+            let header = lines.next()?;
+            let mut fields = header.splitn(3, FIELD_SEPARATOR);
+            let hash = fields.next()?;
+            let author = fields.next()?;
+            let subject = fields.next().unwrap_or_default();
+            let files = lines.map(str::to_string).collect();
+            Some(ParsedCommit::new(hash, author, subject, files))
+        })
+        .collect()
+}
+
+/// Commits in `range` (e.g. `v1.0.0..HEAD`), newest first.
+pub fn commits_in_range(range: &str, opts: RunOpts) -> Result<Vec<ParsedCommit>> {
+    Ok(parse_log(&git::get_log_records(&[range], opts)?))
+}
+
+/// Commits on HEAD since `since` (any `git log --since` value), newest first.
+pub fn commits_since(since: &str, opts: RunOpts) -> Result<Vec<ParsedCommit>> {
+    Ok(parse_log(&git::get_log_records(&["--since", since], opts)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_conventional_fields_and_files() {
+        let log = "\x1eabc1234567\x1fAda\x1ffeat(api)!: add users endpoint\n\nsrc/api.rs\nsrc/lib.rs\n\
+                   \x1edef7654321\x1fBob\x1fTidy up | things\n\nREADME.md\n";
+        let commits = parse_log(log);
+        assert_eq!(commits.len(), 2);
+
+        let feat = &commits[0];
+        assert_eq!(feat.short_hash(), "abc1234");
+        assert_eq!(feat.author, "Ada");
+        assert_eq!(feat.r#type.as_deref(), Some("feat"));
+        assert_eq!(feat.scope.as_deref(), Some("api"));
+        assert_eq!(feat.description, "add users endpoint");
+        assert!(feat.breaking);
+        assert_eq!(feat.files, vec!["src/api.rs", "src/lib.rs"]);
+
+        let other = &commits[1];
+        assert!(!other.is_conventional());
+        assert_eq!(other.description, "Tidy up | things");
+        assert_eq!(other.files, vec!["README.md"]);
+    }
+
+    #[test]
+    fn empty_log_has_no_commits() {
+        assert!(parse_log("").is_empty());
+    }
+}
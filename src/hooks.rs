@@ -0,0 +1,337 @@
+//! Installs tbdflow's commit-msg hook (`tbdflow verify-commit-msg`) without
+//! clobbering what is already there.
+//!
+//! The hook goes wherever git runs hooks from, so `core.hooksPath` is honoured.
+//! A hook that already exists is moved to `<hook>.pre-tbdflow` and called first;
+//! what was changed is recorded in `.git/tbdflow/hooks.json` so that
+//! `tbdflow hooks uninstall` can put every file back exactly as it was.
+
+use crate::git::{self, RunOpts};
+use crate::state::{self, State};
+use crate::toolchain::{TBDFLOW_COMMIT_MSG_LINE, hook_runs_tbdflow};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const COMMIT_MSG_HOOK: &str = "commit-msg";
+const BACKUP_SUFFIX: &str = ".pre-tbdflow";
+/// First line after the shebang of every hook tbdflow writes.
+const HOOK_MARKER: &str =
+    "# Installed by tbdflow; 'tbdflow hooks uninstall' restores the previous hook.";
+
+/// One hook file tbdflow has changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstalledHook {
+    pub path: String,
+    /// Where the hook that was there before now lives; tbdflow's hook calls it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backup: Option<String>,
+    /// Contents before tbdflow edited the hook in place (as `init` does for husky).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstalledHooks {
+    pub hooks: Vec<InstalledHook>,
+}
+
+impl State for InstalledHooks {
+    const FILE: &'static str = "hooks.json";
+}
+
+/// The directory git runs hooks from, and whether `core.hooksPath` chose it.
+pub fn hooks_dir(git_root: &Path, git_dir: &Path, opts: RunOpts) -> (PathBuf, bool) {
+    match git::get_config_value("core.hooksPath", opts).filter(|p| !p.is_empty()) {
+        Some(configured) => {
+            let path = match configured.strip_prefix("~/") {
+                Some(rest) => std::env::var_os("HOME")
+                    .map(|home| PathBuf::from(home).join(rest))
+                    .unwrap_or_else(|| PathBuf::from(&configured)),
+                None => git_root.join(&configured),
+            };
+            // husky v9 points hooksPath at generated wrappers in `.husky/_`
+            // that run the hooks kept in `.husky/`.
+            let path = if path.ends_with(".husky/_") {
+                path.parent().map(Path::to_path_buf).unwrap_or(path)
+            } else {
+                path
+            };
+            (path, true)
+        }
+        None => (git_dir.join("hooks"), false),
+    }
+}
+
+/// The commit-msg hook tbdflow writes, calling `backup` first when there is one.
+pub fn render_hook(backup: Option<&str>) -> String {
+    let mut hook = format!("#!/bin/sh\n{}\n", HOOK_MARKER);
+    if let Some(backup) = backup {
+        hook.push_str(&format!(
+            "previous=\"$(dirname -- \"$0\")/{}\"\n\
+             if [ -x \"$previous\" ]; then\n  \"$previous\" \"$@\" || exit $?\n\
+             elif [ -f \"$previous\" ]; then\n  sh \"$previous\" \"$@\" || exit $?\nfi\n",
+            backup
+        ));
+    }
+    hook.push_str(TBDFLOW_COMMIT_MSG_LINE);
+    hook.push('\n');
+    hook
+}
+
+fn is_tbdflow_hook(content: &str) -> bool {
+    content.contains(HOOK_MARKER)
+}
+
+fn make_executable(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("Failed to make {} executable", path.display()))?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+/// Adds `hook` to the record, keeping the earliest entry for a path so the
+/// state before tbdflow first touched it is what uninstall restores.
+pub fn record(git_dir: &Path, hook: InstalledHook) -> Result<()> {
+    let mut installed: InstalledHooks = state::load(git_dir)?.unwrap_or_default();
+    if installed.hooks.iter().any(|h| h.path == hook.path) {
+        return Ok(());
+    }
+    installed.hooks.push(hook);
+    state::save(git_dir, &installed)
+}
+
+/// Installs the commit-msg hook into `dir`. Returns what was changed, or
+/// `None` when a tbdflow hook is already there.
+pub fn install_commit_msg_hook(dir: &Path) -> Result<Option<InstalledHook>> {
+    let path = dir.join(COMMIT_MSG_HOOK);
+    let existing = fs::read_to_string(&path).ok();
+    if existing.as_deref().is_some_and(hook_runs_tbdflow) {
+        return Ok(None);
+    }
+
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let backup_name = format!("{}{}", COMMIT_MSG_HOOK, BACKUP_SUFFIX);
+    let backup = if path.exists() {
+        let backup_path = dir.join(&backup_name);
+        if backup_path.exists() {
+            anyhow::bail!(
+                "{} already exists; move it away before installing.",
+                backup_path.display()
+            );
+        }
+        fs::rename(&path, &backup_path)
+            .with_context(|| format!("Failed to move aside {}", path.display()))?;
+        Some(backup_path)
+    } else {
+        None
+    };
+
+    fs::write(
+        &path,
+        render_hook(backup.as_ref().map(|_| backup_name.as_str())),
+    )
+    .with_context(|| format!("Failed to write {}", path.display()))?;
+    make_executable(&path)?;
+    Ok(Some(InstalledHook {
+        path: path.to_string_lossy().to_string(),
+        backup: backup.map(|b| b.to_string_lossy().to_string()),
+        original: None,
+    }))
+}
+
+/// Puts one hook back the way it was. Returns false when the hook was changed
+/// since tbdflow wrote it, in which case it is left alone.
+pub fn restore(hook: &InstalledHook) -> Result<bool> {
+    let path = Path::new(&hook.path);
+    if let Some(original) = &hook.original {
+        // Edited in place: write the old contents back, keeping the file's mode.
+        fs::write(path, original).with_context(|| format!("Failed to restore {}", hook.path))?;
+        return Ok(true);
+    }
+    let current = fs::read_to_string(path).unwrap_or_default();
+    if path.exists() && !is_tbdflow_hook(&current) {
+        return Ok(false);
+    }
+    if path.exists() {
+        fs::remove_file(path).with_context(|| format!("Failed to remove {}", hook.path))?;
+    }
+    if let Some(backup) = &hook.backup {
+        fs::rename(backup, path)
+            .with_context(|| format!("Failed to move {} back to {}", backup, hook.path))?;
+    }
+    Ok(true)
+}
+
+/// `tbdflow hooks install`.
+pub fn handle_hooks_install(git_root: &Path, git_dir: &Path, opts: RunOpts) -> Result<()> {
+    let quiet = opts.for_reads();
+    let (dir, from_config) = hooks_dir(git_root, git_dir, quiet);
+    if from_config {
+        println!(
+            "{}",
+            format!("core.hooksPath is set; installing into {}", dir.display()).dimmed()
+        );
+    }
+    let path = dir.join(COMMIT_MSG_HOOK);
+    if opts.dry_run {
+        let action = if path.exists() {
+            format!(
+                "move {} to {}{} and chain to it from a new commit-msg hook",
+                path.display(),
+                COMMIT_MSG_HOOK,
+                BACKUP_SUFFIX
+            )
+        } else {
+            format!("write {}", path.display())
+        };
+        println!("{}", format!("[DRY RUN] Would {}", action).yellow());
+        return Ok(());
+    }
+
+    match install_commit_msg_hook(&dir)? {
+        None => println!(
+            "{}",
+            format!("{} already runs tbdflow.", path.display()).green()
+        ),
+        Some(hook) => {
+            if let Some(backup) = &hook.backup {
+                println!(
+                    "{}",
+                    format!("Kept the existing hook as {}; it still runs first.", backup).dimmed()
+                );
+            }
+            record(git_dir, hook)?;
+            println!(
+                "{}",
+                format!("Installed commit-msg hook at {}", path.display()).green()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// `tbdflow hooks uninstall`.
+pub fn handle_hooks_uninstall(git_dir: &Path, opts: RunOpts) -> Result<()> {
+    let installed: InstalledHooks = state::load(git_dir)?.unwrap_or_default();
+    if installed.hooks.is_empty() {
+        println!("{}", "No hooks installed by tbdflow.".yellow());
+        return Ok(());
+    }
+    if opts.dry_run {
+        for hook in &installed.hooks {
+            println!(
+                "{}",
+                format!("[DRY RUN] Would restore {}", hook.path).yellow()
+            );
+        }
+        return Ok(());
+    }
+
+    let mut kept = Vec::new();
+    for hook in installed.hooks {
+        if restore(&hook)? {
+            println!("{}", format!("Restored {}", hook.path).green());
+        } else {
+            println!(
+                "{}",
+                format!(
+                    "Left {} alone: it was changed after tbdflow installed it.",
+                    hook.path
+                )
+                .yellow()
+            );
+            kept.push(hook);
+        }
+    }
+    if kept.is_empty() {
+        state::remove::<InstalledHooks>(git_dir)?;
+    } else {
+        state::save(git_dir, &InstalledHooks { hooks: kept })?;
+    }
+    Ok(())
+}
+
+/// `tbdflow hooks status`.
+pub fn handle_hooks_status(git_root: &Path, git_dir: &Path, opts: RunOpts) -> Result<()> {
+    let (dir, from_config) = hooks_dir(git_root, git_dir, opts);
+    let source = if from_config {
+        "core.hooksPath"
+    } else {
+        "default"
+    };
+    println!("Hooks directory: {} ({})", dir.display(), source);
+    let installed: InstalledHooks = state::load(git_dir)?.unwrap_or_default();
+    if installed.hooks.is_empty() {
+        println!(
+            "{}",
+            "No hooks installed by tbdflow. Run 'tbdflow hooks install'.".yellow()
+        );
+    }
+    for hook in &installed.hooks {
+        let detail = match (&hook.backup, &hook.original) {
+            (Some(backup), _) => format!("chains to {}", backup),
+            (None, Some(_)) => "edited in place".to_string(),
+            (None, None) => "no previous hook".to_string(),
+        };
+        println!("  {} {}", hook.path, format!("({})", detail).dimmed());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    fn mode(path: &Path) -> u32 {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(path).unwrap().permissions().mode() & 0o777
+    }
+
+    #[test]
+    fn install_chains_to_existing_hook_and_restore_puts_it_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(COMMIT_MSG_HOOK);
+        fs::write(&path, "#!/bin/sh\nexit 0\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o750)).unwrap();
+        }
+
+        let hook = install_commit_msg_hook(dir.path()).unwrap().unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        assert!(written.contains("commit-msg.pre-tbdflow"));
+        assert!(written.contains(TBDFLOW_COMMIT_MSG_LINE));
+        assert!(install_commit_msg_hook(dir.path()).unwrap().is_none());
+
+        assert!(restore(&hook).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "#!/bin/sh\nexit 0\n");
+        #[cfg(unix)]
+        assert_eq!(mode(&path), 0o750);
+        assert!(!dir.path().join("commit-msg.pre-tbdflow").exists());
+    }
+
+    #[test]
+    fn restore_removes_fresh_hooks_and_skips_edited_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let hook = install_commit_msg_hook(dir.path()).unwrap().unwrap();
+        assert!(hook.backup.is_none());
+        assert!(!render_hook(None).contains(BACKUP_SUFFIX));
+
+        fs::write(&hook.path, "#!/bin/sh\necho mine\n").unwrap();
+        assert!(!restore(&hook).unwrap());
+
+        fs::write(&hook.path, render_hook(None)).unwrap();
+        assert!(restore(&hook).unwrap());
+        assert!(!Path::new(&hook.path).exists());
+    }
+}
@@ -0,0 +1,189 @@
+//! Message catalogs for user-facing text. English is built in and is the
+//! fallback for any message a catalog does not translate.
+//!
+//! The locale comes from `TBDFLOW_LANG`, then `locale:` in `.tbdflow.yml`.
+//! Either can be `system` to follow the usual `LC_ALL` / `LC_MESSAGES` /
+//! `LANG` variables; without either, messages are in English.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// A language with a message catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Locale {
+    En = 0,
+    Sv = 1,
+}
+
+impl Locale {
+    /// Every supported locale code.
+    pub const CODES: &'static [&'static str] = &["en", "sv"];
+
+    /// The code that follows the system locale instead of naming a language.
+    pub const SYSTEM: &'static str = "system";
+
+    /// Reads a locale code such as `sv`, `sv_SE.UTF-8` or `en-GB`.
+    /// `None` for languages without a catalog.
+    pub fn parse(code: &str) -> Option<Locale> {
+        let language = code
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        match language.as_str() {
+            "en" | "c" | "posix" => Some(Locale::En),
+            "sv" => Some(Locale::Sv),
+            _ => None,
+        }
+    }
+}
+
+static LOCALE: AtomicU8 = AtomicU8::new(Locale::En as u8);
+
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+pub fn locale() -> Locale {
+    match LOCALE.load(Ordering::Relaxed) {
+        1 => Locale::Sv,
+        _ => Locale::En,
+    }
+}
+
+/// Picks the locale from `TBDFLOW_LANG` or, failing that, the configured
+/// `locale:`. The system locale is only used when one of them asks for it.
+pub fn resolve_locale(configured: Option<&str>) -> Locale {
+    let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+    let code = match var("TBDFLOW_LANG").or_else(|| configured.map(str::to_string)) {
+        Some(code) if code == Locale::SYSTEM => var("LC_ALL")
+            .or_else(|| var("LC_MESSAGES"))
+            .or_else(|| var("LANG")),
+        code => code,
+    };
+    code.and_then(|code| Locale::parse(&code))
+        .unwrap_or(Locale::En)
+}
+
+/// User-facing messages. Placeholders are written `{name}` and filled in by [`tf`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    NotAGitRepo,
+    InitHint,
+    NoConventionalCommits,
+    CommitMessageWillBe,
+    NoChangesToCommit,
+    CommitAborted,
+    CommittingToMain,
+    CommittedToMain,
+    CommittingToBranch,
+    PushedToBranch,
+    IntentLogCleared,
+    TriggeringReview,
+    TriggeringBatchReview,
+    ReviewRequestedFor,
+    ReviewLogged,
+    ApprovingCommit,
+    CommitApproved,
+    ApprovalProgress,
+    RaisingConcern,
+    DismissingReview,
+}
+
+fn en(msg: Msg) -> &'static str {
+    match msg {
+        Msg::NotAGitRepo => "Error: Not a git repository (or any of the parent directories).",
+        Msg::InitHint => "Hint: Run 'tbdflow init' to initialise a new repository here.",
+        Msg::NoConventionalCommits => "No conventional commits found in the specified range.",
+        Msg::CommitMessageWillBe => "Commit message will be:",
+        Msg::NoChangesToCommit => "No changes added to commit.",
+        Msg::CommitAborted => "Commit aborted by user.",
+        Msg::CommittingToMain => "--- Committing directly to main branch ---",
+        Msg::CommittedToMain => "Successfully committed and pushed changes to main.",
+        Msg::CommittingToBranch => "--- Committing to feature branch '{branch}' ---",
+        Msg::PushedToBranch => "Successfully pushed changes to '{branch}'.",
+        Msg::IntentLogCleared => "Intent log consumed and cleared.",
+        Msg::TriggeringReview => "--- Triggering Non-blocking Review ---",
+        Msg::TriggeringBatchReview => "--- Triggering Non-blocking Batch Review ---",
+        Msg::ReviewRequestedFor => "Review requested for:",
+        Msg::ReviewLogged => "Review logged (no external system integration)",
+        Msg::ApprovingCommit => "--- Approving Commit {commit} ---",
+        Msg::CommitApproved => "Commit {commit} marked as approved",
+        Msg::ApprovalProgress => {
+            "Approval recorded for commit {commit} ({count}/{required}). Waiting for {remaining} more."
+        }
+        Msg::RaisingConcern => "--- Raising Concern on Commit {commit} ---",
+        Msg::DismissingReview => "--- Dismissing Review for Commit {commit} ---",
+    }
+}
+
+fn sv(msg: Msg) -> Option<&'static str> {
+    Some(match msg {
+        Msg::NotAGitRepo => "Fel: Inte ett git-repo (eller någon av föräldrakatalogerna).",
+        Msg::InitHint => "Tips: Kör 'tbdflow init' för att initiera ett nytt repo här.",
+        Msg::NoConventionalCommits => {
+            "Inga conventional commits hittades i det angivna intervallet."
+        }
+        Msg::CommitMessageWillBe => "Commit-meddelandet blir:",
+        Msg::NoChangesToCommit => "Inga ändringar att committa.",
+        Msg::CommitAborted => "Commit avbruten av användaren.",
+        Msg::CommittingToMain => "--- Committar direkt till main-grenen ---",
+        Msg::CommittedToMain => "Ändringarna är committade och pushade till main.",
+        Msg::CommittingToBranch => "--- Committar till feature-grenen '{branch}' ---",
+        Msg::PushedToBranch => "Ändringarna är pushade till '{branch}'.",
+        Msg::IntentLogCleared => "Intent-loggen är använd och rensad.",
+        Msg::TriggeringReview => "--- Startar icke-blockerande granskning ---",
+        Msg::TriggeringBatchReview => "--- Startar icke-blockerande samlad granskning ---",
+        Msg::ReviewRequestedFor => "Granskning begärd för:",
+        Msg::ReviewLogged => "Granskningen loggad (ingen extern integration)",
+        Msg::ApprovingCommit => "--- Godkänner commit {commit} ---",
+        Msg::CommitApproved => "Commit {commit} markerad som godkänd",
+        Msg::ApprovalProgress => {
+            "Godkännande registrerat för commit {commit} ({count}/{required}). Väntar på {remaining} till."
+        }
+        Msg::RaisingConcern => "--- Flaggar synpunkt på commit {commit} ---",
+        Msg::DismissingReview => "--- Avfärdar granskning av commit {commit} ---",
+    })
+}
+
+/// `msg` in the current locale.
+pub fn t(msg: Msg) -> &'static str {
+    match locale() {
+        Locale::En => en(msg),
+        Locale::Sv => sv(msg).unwrap_or_else(|| en(msg)),
+    }
+}
+
+/// `msg` in the current locale with each `{name}` placeholder filled in.
+pub fn tf(msg: Msg, args: &[(&str, &str)]) -> String {
+    let mut text = t(msg).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), value);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locale_codes_are_parsed() {
+        assert_eq!(Locale::parse("sv_SE.UTF-8"), Some(Locale::Sv));
+        assert_eq!(Locale::parse("en-GB"), Some(Locale::En));
+        assert_eq!(Locale::parse("C.UTF-8"), Some(Locale::En));
+        assert_eq!(Locale::parse("de_DE"), None);
+    }
+
+    #[test]
+    fn placeholders_are_filled_in_english_by_default() {
+        assert_eq!(
+            tf(Msg::PushedToBranch, &[("branch", "feat/login")]),
+            "Successfully pushed changes to 'feat/login'."
+        );
+        assert_eq!(
+            sv(Msg::PushedToBranch).map(|s| s.replace("{branch}", "feat/login")),
+            Some("Ändringarna är pushade till 'feat/login'.".to_string())
+        );
+    }
+}
@@ -10,28 +10,44 @@ const INTENT_FILE: &str = ".tbdflow-intent.json";
 
 /// Appends `.tbdflow-intent.json` to `.gitignore` if not already present.
 fn ensure_gitignored(git_root: &Path) -> Result<()> {
+    add_to_gitignore(
+        git_root,
+        INTENT_FILE,
+        "tbdflow intent log (local-only, never committed)",
+    )?;
+    Ok(())
+}
+
+/// True when `.gitignore` at `git_root` has a line for `entry`.
+pub(crate) fn gitignore_lists(git_root: &Path, entry: &str) -> Result<bool> {
     let gitignore_path = git_root.join(".gitignore");
-    let entry = INTENT_FILE;
-
-    if gitignore_path.exists() {
-        let content = fs::read_to_string(&gitignore_path)
-            .with_context(|| format!("Failed to read {}", gitignore_path.display()))?;
-        // Already present — nothing to do.
-        if content.lines().any(|line| line.trim() == entry) {
-            return Ok(());
-        }
+    if !gitignore_path.exists() {
+        return Ok(false);
+    }
+    let content = fs::read_to_string(&gitignore_path)
+        .with_context(|| format!("Failed to read {}", gitignore_path.display()))?;
+    Ok(content.lines().any(|line| line.trim() == entry))
+}
+
+/// Appends `entry`, under a `# comment` line, to `.gitignore` at `git_root`.
+/// Returns false when it was already there.
+pub(crate) fn add_to_gitignore(git_root: &Path, entry: &str, comment: &str) -> Result<bool> {
+    // Already present — nothing to do.
+    if gitignore_lists(git_root, entry)? {
+        return Ok(false);
     }
 
     // Append the entry (with a leading newline to avoid joining with the last line).
+    let gitignore_path = git_root.join(".gitignore");
     use std::io::Write;
     let mut file = fs::OpenOptions::new()
         .create(true)
         .append(true)
         .open(&gitignore_path)
         .with_context(|| format!("Failed to open {}", gitignore_path.display()))?;
-    writeln!(file, "\n# tbdflow intent log (local-only, never committed)")?;
+    writeln!(file, "\n# {}", comment)?;
     writeln!(file, "{}", entry)?;
-    Ok(())
+    Ok(true)
 }
 
 /// A single intent note captured during development.
@@ -104,7 +120,7 @@ pub fn load_intent_log(git_root: &Path) -> Result<Option<IntentLog>> {
 }
 
 /// Saves the intent log to disk.
-fn save_intent_log(git_root: &Path, log: &IntentLog) -> Result<()> {
+pub(crate) fn save_intent_log(git_root: &Path, log: &IntentLog) -> Result<()> {
     // Guard: make sure the intent file won't be picked up by `git add .`.
     ensure_gitignored(git_root)?;
 
@@ -185,25 +201,25 @@ pub fn add_note(git_root: &Path, message: &str, current_branch: &str) -> Result<
 /// Starts a new task, creating a fresh intent log (or updating the task name on an existing one).
 pub fn start_task(git_root: &Path, description: &str, current_branch: &str) -> Result<()> {
     let existing = load_intent_log(git_root)?;
-    if let Some(existing_log) = &existing {
-        if !existing_log.notes.is_empty() {
-            // Check for stale log from a different branch
-            if let BranchCheck::Stale {
-                log_branch,
-                current_branch: cur,
-            } = check_branch(existing_log, current_branch)
-            {
-                warn_stale(&log_branch, &cur);
-            }
-            println!(
-                "{}",
-                format!(
-                    "Warning: Existing intent log has {} note(s). They will be preserved.",
-                    existing_log.notes.len()
-                )
-                .yellow()
-            );
+    if let Some(existing_log) = &existing
+        && !existing_log.notes.is_empty()
+    {
+        // Check for stale log from a different branch
+        if let BranchCheck::Stale {
+            log_branch,
+            current_branch: cur,
+        } = check_branch(existing_log, current_branch)
+        {
+            warn_stale(&log_branch, &cur);
         }
+        println!(
+            "{}",
+            format!(
+                "Warning: Existing intent log has {} note(s). They will be preserved.",
+                existing_log.notes.len()
+            )
+            .yellow()
+        );
     }
 
     let mut log =
@@ -251,14 +267,13 @@ pub fn show_intent_log(git_root: &Path, current_branch: Option<&str>) -> Result<
     match load_intent_log(git_root)? {
         Some(log) => {
             // Stale-branch warning
-            if let Some(branch) = current_branch {
-                if let BranchCheck::Stale {
+            if let Some(branch) = current_branch
+                && let BranchCheck::Stale {
                     log_branch,
                     current_branch: cur,
                 } = check_branch(&log, branch)
-                {
-                    warn_stale(&log_branch, &cur);
-                }
+            {
+                warn_stale(&log_branch, &cur);
             }
             if let Some(task) = &log.task {
                 println!("{} {}", "Task:".blue().bold(), task);
@@ -0,0 +1,176 @@
+use crate::state;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Stored in the state directory, `.git/tbdflow/`.
+const JOURNAL_FILE: &str = "journal.jsonl";
+/// Where releases before the state directory kept the journal.
+const LEGACY_JOURNAL_FILE: &str = "tbdflow-journal.jsonl";
+
+/// Something tbdflow did that the team may want to audit later.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum JournalEvent {
+    /// A commit opted out of the automatic review.
+    ReviewSkipped { commit: String, commit_type: String },
+    /// A commit bypassed the DoD checklist with `--no-verify`.
+    DodSkipped { commit: String, branch: String },
+    /// A reviewer approved a commit (recorded for the `log-only` strategy).
+    ReviewApproved { commit: String },
+}
+
+impl JournalEvent {
+    /// The commit the event is about.
+    pub fn commit(&self) -> &str {
+        match self {
+            JournalEvent::ReviewSkipped { commit, .. }
+            | JournalEvent::DodSkipped { commit, .. }
+            | JournalEvent::ReviewApproved { commit } => commit,
+        }
+    }
+}
+
+/// One line of the journal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub timestamp: String,
+    /// `Name <email>` of whoever ran the command.
+    pub author: String,
+    #[serde(flatten)]
+    pub event: JournalEvent,
+}
+
+impl JournalEntry {
+    pub fn now(author: impl Into<String>, event: JournalEvent) -> Self {
+        Self {
+            timestamp: Utc::now().to_rfc3339(),
+            author: author.into(),
+            event,
+        }
+    }
+}
+
+fn journal_path(git_dir: &Path) -> Result<PathBuf> {
+    state::file_path(git_dir, JOURNAL_FILE, LEGACY_JOURNAL_FILE)
+}
+
+/// Appends an entry to the journal, one JSON object per line.
+pub fn append(git_dir: &Path, entry: &JournalEntry) -> Result<()> {
+    let path = journal_path(git_dir)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Reads every entry, oldest first. Lines that cannot be parsed (for example
+/// events written by a newer tbdflow) are skipped.
+pub fn read_entries(git_dir: &Path) -> Result<Vec<JournalEntry>> {
+    let path = journal_path(git_dir)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Drops the entries `keep` rejects and returns how many went. Lines that
+/// cannot be parsed are kept as they are.
+pub fn retain(git_dir: &Path, mut keep: impl FnMut(&JournalEntry) -> bool) -> Result<usize> {
+    let path = journal_path(git_dir)?;
+    if !path.exists() {
+        return Ok(0);
+    }
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut removed = 0;
+    let mut kept = String::new();
+    for line in content.lines() {
+        match serde_json::from_str::<JournalEntry>(line) {
+            Ok(entry) if !keep(&entry) => removed += 1,
+            _ => {
+                kept.push_str(line);
+                kept.push('\n');
+            }
+        }
+    }
+    if removed > 0 {
+        fs::write(&path, kept).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn append_and_read_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let entry = JournalEntry::now(
+            "Ada <ada@example.com>",
+            JournalEvent::ReviewSkipped {
+                commit: "abc1234".to_string(),
+                commit_type: "docs".to_string(),
+            },
+        );
+        append(dir.path(), &entry).unwrap();
+        append(dir.path(), &entry).unwrap();
+
+        let entries = read_entries(dir.path()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0], entry);
+    }
+
+    #[test]
+    fn unreadable_lines_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            journal_path(dir.path()).unwrap(),
+            "not json\n{\"timestamp\":\"t\",\"author\":\"a\",\"event\":\"from-the-future\"}\n\
+             {\"timestamp\":\"t\",\"author\":\"a\",\"event\":\"review-skipped\",\"commit\":\"c\",\"commit_type\":\"docs\"}\n",
+        )
+        .unwrap();
+        let entries = read_entries(dir.path()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(matches!(
+            entries[0].event,
+            JournalEvent::ReviewSkipped { .. }
+        ));
+    }
+
+    #[test]
+    fn retain_keeps_unreadable_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = journal_path(dir.path()).unwrap();
+        fs::write(
+            &path,
+            "not json\n\
+             {\"timestamp\":\"t\",\"author\":\"a\",\"event\":\"review-approved\",\"commit\":\"gone\"}\n\
+             {\"timestamp\":\"t\",\"author\":\"a\",\"event\":\"review-approved\",\"commit\":\"c\"}\n",
+        )
+        .unwrap();
+        let removed = retain(dir.path(), |e| e.event.commit() != "gone").unwrap();
+        assert_eq!(removed, 1);
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("not json\n"));
+        assert_eq!(read_entries(dir.path()).unwrap()[0].event.commit(), "c");
+    }
+
+    #[test]
+    fn missing_journal_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_entries(dir.path()).unwrap().is_empty());
+    }
+}
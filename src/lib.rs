@@ -1,12 +1,54 @@
+pub mod audit;
+pub mod bitbucket;
 pub mod branch;
+pub mod build_info;
 pub mod changelog;
+pub mod clean;
 pub mod cli;
+pub mod command_defaults;
 pub mod commands;
 pub mod commit;
+pub mod completion;
 pub mod config;
+pub mod config_edit;
+pub mod config_import;
+pub mod config_schema;
+pub mod conventions;
+pub mod doctor;
+pub mod dod;
+pub mod experiment;
+pub mod extends;
+pub mod external;
+pub mod gh;
 pub mod git;
+pub mod git_backend;
+pub mod gitea;
+pub mod history;
+pub mod hooks;
+pub mod i18n;
 pub mod intent;
+pub mod journal;
+pub mod metrics;
+pub mod mob;
+pub mod output;
+pub mod pair;
+pub mod postmortem;
+pub mod prompt;
 pub mod radar;
 pub mod recover;
+pub mod rest;
+pub mod retry;
 pub mod review;
+pub mod review_ledger;
+pub mod review_notes;
+pub mod review_queue;
+pub mod shallow;
+pub mod snippet;
+pub mod state;
+pub mod switch;
+pub mod target;
+pub mod toolchain;
+pub mod version;
+pub mod watch;
+pub mod whoami;
 pub mod wizard;
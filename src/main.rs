@@ -2,13 +2,28 @@ use clap::{CommandFactory, Parser};
 use colored::Colorize;
 use std::io;
 use std::io::Write;
+use tbdflow::cli::BranchAction;
 use tbdflow::cli::Commands;
+use tbdflow::cli::CompletionAction;
+use tbdflow::cli::ConfigAction;
+use tbdflow::cli::DodAction;
+use tbdflow::cli::HooksAction;
+use tbdflow::cli::MetricsAction;
+use tbdflow::cli::MobAction;
+use tbdflow::cli::PairAction;
+use tbdflow::cli::ReviewAction;
+use tbdflow::cli::ReviewLabelsAction;
+use tbdflow::cli::TargetAction;
 use tbdflow::cli::TaskAction;
 use tbdflow::commit::CommitParams;
 use tbdflow::git::RunOpts;
 use tbdflow::git::get_current_branch;
+use tbdflow::i18n::Msg;
 use tbdflow::{
-    branch, changelog, cli, commands, commit, config, git, intent, radar, recover, review, wizard,
+    audit, branch, build_info, changelog, clean, cli, command_defaults, commands, commit,
+    completion, config, config_edit, config_import, config_schema, doctor, dod, experiment,
+    extends, git, git_backend, hooks, i18n, intent, metrics, mob, output, pair, postmortem, prompt,
+    radar, recover, retry, review, snippet, switch, target, version, watch, whoami, wizard,
 };
 
 /// Read content from a file path, or from stdin if the path is "-".
@@ -25,29 +40,151 @@ fn read_file_or_stdin(path: &str) -> anyhow::Result<String> {
 }
 
 fn main() -> anyhow::Result<()> {
-    let cli = cli::Cli::parse();
+    if build_info::is_json_version_request(&std::env::args().collect::<Vec<_>>()) {
+        return build_info::print_version_json();
+    }
+    let mut cli = command_defaults::parse();
     let verbose = cli.verbose;
     let dry_run = cli.dry_run;
     let json = cli.json;
-    let opts = RunOpts::new(verbose, dry_run);
+    let assume_yes = cli.yes || prompt::assume_yes_from_env();
+    let opts = RunOpts::new(verbose, dry_run).with_assume_yes(assume_yes);
+    output::set_ascii(cli.ascii || output::ascii_from_env());
+    i18n::set_locale(i18n::resolve_locale(None));
+
+    if let Some(path) = &cli.cwd {
+        std::env::set_current_dir(path).map_err(|e| {
+            anyhow::anyhow!("Cannot change to directory '{}': {}", path.display(), e)
+        })?;
+    }
+
+    // `changelog --repo` reads another checkout or a bare repository.
+    if let Commands::Changelog {
+        repo: Some(path), ..
+    } = &cli.command
+    {
+        std::env::set_current_dir(path).map_err(|e| {
+            anyhow::anyhow!("Cannot open repository at '{}': {}", path.display(), e)
+        })?;
+    }
 
     if !matches!(
         cli.command,
         Commands::Init { .. }
             | Commands::Update
             | Commands::Completion { .. }
+            | Commands::GenerateCompletion { .. }
             | Commands::GenerateManPage
+            | Commands::Parse { .. }
+            | Commands::VerifyCommitMsg { .. }
+            | Commands::Doctor
+            | Commands::Whoami
     ) && git::is_git_repository(opts).is_err()
     {
-        println!(
-            "{}",
-            "Error: Not a git repository (or any of the parent directories).".red()
-        );
-        println!("Hint: Run 'tbdflow init' to initialise a new repository here.");
+        println!("{}", i18n::t(Msg::NotAGitRepo).red());
+        println!("{}", i18n::t(Msg::InitHint));
         std::process::exit(1);
     }
 
-    let config = config::load_tbdflow_config()?;
+    let profile = cli
+        .profile
+        .clone()
+        .or_else(|| std::env::var("TBDFLOW_PROFILE").ok())
+        .filter(|p| !p.is_empty());
+    if let Commands::Info {
+        update_lock: true, ..
+    } = cli.command
+    {
+        extends::update_lock(opts)?;
+    }
+    let mut config = match config::load_tbdflow_config(profile.as_deref(), opts) {
+        Ok(config) => config,
+        Err(e) if matches!(cli.command, Commands::VerifyCommitMsg { .. }) => {
+            eprintln!("tbdflow: invalid .tbdflow.yml: {:#}", e);
+            std::process::exit(commit::VERIFY_EXIT_CONFIG);
+        }
+        // Let `config` check and repair the file that failed to load.
+        Err(e)
+            if matches!(
+                cli.command,
+                Commands::Config {
+                    action: Some(
+                        ConfigAction::Set { .. }
+                            | ConfigAction::Unset { .. }
+                            | ConfigAction::Validate { .. }
+                    ),
+                    ..
+                }
+            ) =>
+        {
+            eprintln!(
+                "{}",
+                format!("Warning: invalid .tbdflow.yml: {:#}", e).yellow()
+            );
+            config::Config::default()
+        }
+        Err(e) => return Err(e),
+    };
+    let opts = opts
+        .with_timeout(config.git.timeout_seconds)
+        .with_backend(config.git.backend);
+    let configured_main = config.main_branch_name.clone();
+    // A trunk target only redirects the commands that integrate work.
+    if matches!(
+        cli.command,
+        Commands::Commit { .. } | Commands::Sync { .. } | Commands::Branch { .. }
+    ) && let Some(target) = target::active_target(opts)
+    {
+        if !json {
+            println!(
+                "{}",
+                format!(
+                    "Trunk target: '{}' (instead of '{}'; 'tbdflow target reset' to undo)",
+                    target.branch, configured_main
+                )
+                .yellow()
+            );
+        }
+        config.main_branch_name = target.branch;
+    }
+    if config.ascii_output {
+        output::set_ascii(true);
+    }
+    if let Some(code) = config.locale.as_deref()
+        && code != i18n::Locale::SYSTEM
+        && i18n::Locale::parse(code).is_none()
+    {
+        eprintln!(
+            "{}",
+            format!(
+                "Warning: unknown locale '{}' (available: {}, or {}); using English.",
+                code,
+                i18n::Locale::CODES.join(", "),
+                i18n::Locale::SYSTEM
+            )
+            .yellow()
+        );
+    }
+    i18n::set_locale(i18n::resolve_locale(config.locale.as_deref()));
+    if !git_backend::is_supported(config.git.backend) {
+        eprintln!(
+            "{}",
+            "Warning: git.backend 'gix' needs tbdflow built with '--features gix'; using the git binary."
+                .yellow()
+        );
+    }
+    if !config.defaults.is_empty() {
+        for (key, message) in command_defaults::check(&config.defaults) {
+            eprintln!(
+                "{}",
+                format!("Warning: '{}' {}; ignoring it.", key, message).yellow()
+            );
+        }
+        cli = command_defaults::reparse(&config.defaults);
+    }
+    if matches!(cli.command, Commands::Commit { .. } | Commands::Sync { .. }) {
+        branch::ensure_on_branch(&config, json, opts)?;
+    }
 
     match cli.command {
         Commands::Init {
@@ -56,22 +193,51 @@ fn main() -> anyhow::Result<()> {
             remote,
         } => {
             let init_opts = commands::InitOptions {
-                non_interactive,
+                non_interactive: non_interactive || assume_yes || !prompt::is_interactive(),
                 main_branch,
                 remote,
             };
             commands::handle_init_command(opts, init_opts)?;
         }
-        Commands::Info { edit } => {
-            commands::handle_info(opts, edit, json)?;
+        Commands::Info { edit, .. } => {
+            commands::handle_info(opts, profile.as_deref(), edit, json)?;
         }
-        Commands::Config { get_dod } => {
-            if get_dod {
-                if let Ok(dod_config) = config::load_dod_config() {
-                    for item in dod_config.checklist {
-                        println!("{}", item);
+        Commands::Config { get_dod, action } => {
+            if get_dod && let Ok(dod_config) = config::load_dod_config(opts) {
+                for item in dod_config.item_texts() {
+                    println!("{}", item);
+                }
+            }
+            let git_root = || git::get_git_root(opts.for_reads()).map(std::path::PathBuf::from);
+            match action {
+                Some(ConfigAction::Get { key }) => {
+                    config_edit::handle_config_get(&config, &key, json)?
+                }
+                Some(ConfigAction::List) => config_edit::handle_config_list(&config, json)?,
+                Some(ConfigAction::Set { key, value }) => {
+                    config_edit::handle_config_set(&git_root()?, &key, &value, opts)?
+                }
+                Some(ConfigAction::Unset { key }) => {
+                    config_edit::handle_config_unset(&git_root()?, &key, opts)?
+                }
+                Some(ConfigAction::Validate { schema: true }) => {
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&config_schema::schema())?
+                    );
+                }
+                Some(ConfigAction::Validate { schema: false }) => {
+                    config_schema::handle_config_validate(&git_root()?, json, opts)?
+                }
+                Some(ConfigAction::Import { from, file }) => {
+                    config_import::handle_config_import(&git_root()?, from, file.as_deref(), opts)?
+                }
+                None if !get_dod => {
+                    if let Err(help) = cli::Cli::try_parse_from(["tbdflow", "config", "--help"]) {
+                        help.print()?;
                     }
                 }
+                None => {}
             }
         }
         Commands::HeadSha => {
@@ -88,13 +254,24 @@ fn main() -> anyhow::Result<()> {
             body,
             message_file,
             body_file,
+            file,
+            stdin,
+            edit,
             breaking,
             breaking_description,
             tag,
             no_verify,
             issue,
+            preview,
+            no_preview,
+            skip_review,
+            experiment,
+            fixes_review,
+            fix,
             include_projects,
         } => {
+            let preview = preview || (config.commit.preview && !no_preview);
+
             // Resolve message from --message or --message-file
             let resolved_message = match (message, message_file) {
                 (Some(m), _) => Some(m),
@@ -109,8 +286,42 @@ fn main() -> anyhow::Result<()> {
                 (None, None) => None,
             };
 
-            let params = match (r#type, resolved_message) {
-                (Some(t), Some(m)) => CommitParams {
+            // A complete message from -F/--stdin replaces the individual flags
+            let full_message = match (file, stdin) {
+                (Some(path), _) => Some(read_file_or_stdin(&path)?),
+                (None, true) => Some(read_file_or_stdin("-")?),
+                (None, false) => None,
+            };
+
+            let mut params = match (r#type, resolved_message, full_message) {
+                (_, _, Some(text)) => CommitParams {
+                    tag,
+                    include_projects,
+                    no_verify,
+                    preview,
+                    fix,
+                    ..commit::parse_commit_message(&text)?
+                },
+                (Some(t), m, None) if edit => commit::edit_commit_params(
+                    &config,
+                    CommitParams {
+                        r#type: t,
+                        scope,
+                        message: m.unwrap_or_default(),
+                        body: resolved_body,
+                        breaking,
+                        breaking_description,
+                        tag,
+                        issue,
+                        include_projects,
+                        no_verify,
+                        trailers: Vec::new(),
+                        preview,
+                        fix,
+                    },
+                    opts,
+                )?,
+                (Some(t), Some(m), None) => CommitParams {
                     r#type: t,
                     scope,
                     message: m,
@@ -121,6 +332,9 @@ fn main() -> anyhow::Result<()> {
                     issue,
                     include_projects,
                     no_verify,
+                    trailers: Vec::new(),
+                    preview,
+                    fix,
                 },
                 _ => {
                     let w = wizard::run_commit_wizard(&config)?;
@@ -135,49 +349,158 @@ fn main() -> anyhow::Result<()> {
                         issue: w.issue,
                         include_projects,
                         no_verify,
+                        trailers: Vec::new(),
+                        preview,
+                        fix,
                     }
                 }
             };
+            if skip_review && !review::has_skip_review_trailer(&params.trailers.join("\n")) {
+                params
+                    .trailers
+                    .push(review::SKIP_REVIEW_TRAILER.to_string());
+            }
+            if let Some(id) = experiment {
+                let trailer = experiment::experiment_trailer(&id);
+                if !params.trailers.contains(&trailer) {
+                    params.trailers.push(trailer);
+                }
+            }
+            if let Some(hash) = fixes_review {
+                if !git::commit_exists(&hash, opts)? {
+                    return Err(anyhow::anyhow!(
+                        "--fixes-review: commit '{}' not found.",
+                        hash
+                    ));
+                }
+                let trailer = review::fixes_review_trailer(&hash);
+                if !params.trailers.contains(&trailer) {
+                    params.trailers.push(trailer);
+                }
+            }
 
             commit::handle_commit(opts, &config, params)?;
         }
+        Commands::Parse { message, file } => {
+            let text = match (message, file) {
+                (Some(m), _) => m,
+                (None, Some(path)) => read_file_or_stdin(&path)?,
+                (None, None) => read_file_or_stdin("-")?,
+            };
+            commit::handle_parse(&config, &text, json)?;
+        }
+        Commands::VerifyCommitMsg { file, fix } => {
+            std::process::exit(commit::handle_verify_commit_msg(&config, &file, fix, opts));
+        }
         Commands::Branch {
             r#type,
             name,
             issue,
             from_commit,
+            allow_non_main,
+            no_sync,
+            description,
+            action,
         } => {
-            if r#type.is_none() || name.is_none() {
-                // Enter interactive wizard mode
-                let wizard_result = wizard::run_branch_wizard(&config)?;
-                branch::handle_branch(
-                    Some(wizard_result.branch_type),
-                    &config,
-                    Some(wizard_result.name),
-                    wizard_result.issue,
-                    wizard_result.from_commit,
-                    opts,
-                )?;
+            if let Some(BranchAction::List) = action {
+                branch::handle_branch_list(&config, json, opts)?;
             } else {
-                branch::handle_branch(r#type, &config, name, issue, from_commit, opts)?;
+                let params = match (r#type, name) {
+                    (Some(r#type), Some(name)) => branch::BranchParams {
+                        r#type,
+                        name,
+                        issue,
+                        from_commit,
+                        allow_non_main,
+                        no_sync,
+                        description,
+                    },
+                    _ => {
+                        // Enter interactive wizard mode
+                        let wizard_result = wizard::run_branch_wizard(&config)?;
+                        branch::BranchParams {
+                            r#type: wizard_result.branch_type,
+                            name: wizard_result.name,
+                            issue: wizard_result.issue,
+                            from_commit: wizard_result.from_commit,
+                            allow_non_main: false,
+                            no_sync,
+                            description: wizard_result.description,
+                        }
+                    }
+                };
+                branch::handle_branch(&config, params, opts)?;
             }
         }
-        Commands::Complete { r#type, name } => match (r#type, name) {
+        Commands::Experiments { id } => {
+            experiment::handle_experiments(&config, id, json, opts)?;
+        }
+        Commands::Audit { since } => {
+            audit::handle_audit(&config, &since, json, opts)?;
+        }
+        Commands::Snippet {
+            query,
+            since,
+            limit,
+            list,
+        } => {
+            snippet::handle_snippet(
+                &config,
+                &query.join(" "),
+                since.as_deref(),
+                limit,
+                list,
+                json,
+                opts,
+            )?;
+        }
+        Commands::Metrics(MetricsAction::Integration { since }) => {
+            metrics::handle_integration_metrics(&config, &since, json, opts)?;
+        }
+        Commands::Switch { target, stash } => {
+            switch::handle_switch(&config, target, stash, opts)?;
+        }
+        Commands::Complete {
+            r#type,
+            name,
+            accept_todos,
+        } => match (r#type, name) {
             (Some(t), Some(n)) => {
-                branch::handle_complete(t, n, &config, opts)?;
+                branch::handle_complete(t, n, accept_todos, &config, opts)?;
             }
             _ => {
                 let wizard_result = wizard::run_complete_wizard(&config)?;
                 branch::handle_complete(
                     wizard_result.branch_type,
                     wizard_result.name,
+                    accept_todos,
                     &config,
                     opts,
                 )?;
             }
         },
-        Commands::Sync => {
-            commands::handle_sync(opts, &config, json)?;
+        Commands::Sync {
+            watch,
+            interval,
+            notify,
+        } => {
+            if watch {
+                watch::handle_sync_watch(opts, &config, interval, notify, json)?;
+            } else {
+                commands::handle_sync(opts, &config, json)?;
+            }
+        }
+        Commands::Doctor => {
+            doctor::handle_doctor(&config, opts, json)?;
+        }
+        Commands::Whoami => {
+            whoami::handle_whoami(opts, json)?;
+        }
+        Commands::Clean => {
+            clean::handle_clean(json, opts)?;
+        }
+        Commands::Retry { discard } => {
+            retry::handle_retry(&config, discard, opts)?;
         }
         Commands::Radar => {
             radar::handle_radar(opts, &config, json)?;
@@ -186,12 +509,10 @@ fn main() -> anyhow::Result<()> {
             commands::handle_status(opts, &config, json)?;
         }
         Commands::CurrentBranch => {
-            println!("{}", "--- Current branch ---".to_string().blue());
-            let branch_name = get_current_branch(opts)?;
-            println!("{}", format!("Current branch is: {}", branch_name).green());
+            commands::handle_current_branch(opts, &config, json)?;
         }
         Commands::CheckBranches => {
-            commands::handle_check_branches(opts, &config)?;
+            commands::handle_check_branches(opts, &config, json)?;
         }
         Commands::GenerateManPage => {
             println!("{}", "--- Generating a man page ---".to_string().blue());
@@ -211,17 +532,34 @@ fn main() -> anyhow::Result<()> {
             }
             io::stdout().write_all(&buffer)?;
         }
-        Commands::Completion { shell } => {
-            let mut cmd = cli::Cli::command();
-            let bin_name = cmd.get_name().to_string();
-            clap_complete::generate(shell, &mut cmd, bin_name, &mut io::stdout());
+        Commands::GenerateCompletion { shell } => completion::print_completion(shell),
+        Commands::Completion { shell, action } => match action {
+            Some(CompletionAction::Install { shell }) => {
+                completion::handle_completion_install(shell, opts)?
+            }
+            None => match shell {
+                Some(shell) => completion::print_completion(shell),
+                None => anyhow::bail!(
+                    "Name a shell to print its completions (e.g. 'tbdflow completion zsh'), or run 'tbdflow completion install'."
+                ),
+            },
+        },
+        Commands::NextVersion { pre } => {
+            version::handle_next_version(&config, pre.as_deref(), json, opts)?;
         }
         Commands::Changelog {
             from,
             to,
             unreleased,
+            skip_prereleases,
+            ..
         } => {
-            if from.is_none() && to.is_none() && !unreleased {
+            if skip_prereleases {
+                config.changelog.skip_prereleases = true;
+            }
+            if json {
+                changelog::handle_changelog_json(opts, &config, from, to, unreleased)?;
+            } else if from.is_none() && to.is_none() && !unreleased {
                 // Enter interactive wizard mode
                 let wizard_result = wizard::run_changelog_wizard()?;
                 let changelog = changelog::handle_changelog(
@@ -232,20 +570,14 @@ fn main() -> anyhow::Result<()> {
                     wizard_result.unreleased,
                 )?;
                 if changelog.is_empty() {
-                    println!(
-                        "{}",
-                        "No conventional commits found in the specified range.".yellow()
-                    );
+                    println!("{}", i18n::t(Msg::NoConventionalCommits).yellow());
                 } else {
                     println!("{}", changelog);
                 }
             } else {
                 let changelog = changelog::handle_changelog(opts, &config, from, to, unreleased)?;
                 if changelog.is_empty() {
-                    println!(
-                        "{}",
-                        "No conventional commits found in the specified range.".yellow()
-                    );
+                    println!("{}", i18n::t(Msg::NoConventionalCommits).yellow());
                 } else {
                     println!("{}", changelog);
                 }
@@ -254,6 +586,9 @@ fn main() -> anyhow::Result<()> {
         Commands::Undo { sha, no_push } => {
             commands::handle_undo(&sha, no_push, opts, &config)?;
         }
+        Commands::Postmortem { sha, output } => {
+            postmortem::handle_postmortem(&sha, output.as_deref(), opts)?;
+        }
         Commands::Note { message, show } => {
             let git_root = std::path::PathBuf::from(git::get_git_root(opts)?);
             let current_branch = get_current_branch(opts)?;
@@ -288,6 +623,9 @@ fn main() -> anyhow::Result<()> {
                 }
             }
         }
+        Commands::Dod(DodAction::Report { days }) => {
+            dod::handle_dod_report(days, json, opts)?;
+        }
         Commands::Task(action) => {
             let git_root = std::path::PathBuf::from(git::get_git_root(opts)?);
             let current_branch = get_current_branch(opts)?;
@@ -314,6 +652,70 @@ fn main() -> anyhow::Result<()> {
                 }
             }
         }
+        Commands::Target(action) => {
+            let git_dir = std::path::PathBuf::from(git::get_git_dir(opts)?);
+            match action {
+                TargetAction::Set { branch } => {
+                    target::handle_target_set(&git_dir, &branch, &configured_main, opts)?
+                }
+                TargetAction::Show => target::handle_target_show(&git_dir, &configured_main)?,
+                TargetAction::Reset => {
+                    target::handle_target_reset(&git_dir, &configured_main, opts)?
+                }
+            }
+        }
+        Commands::Hooks(action) => {
+            let quiet = opts.for_reads();
+            let git_root = std::path::PathBuf::from(git::get_git_root(quiet)?);
+            let git_dir = std::path::PathBuf::from(git::get_git_dir(quiet)?);
+            match action {
+                HooksAction::Install => hooks::handle_hooks_install(&git_root, &git_dir, opts)?,
+                HooksAction::Uninstall => hooks::handle_hooks_uninstall(&git_dir, opts)?,
+                HooksAction::Status => hooks::handle_hooks_status(&git_root, &git_dir, quiet)?,
+            }
+        }
+        Commands::Pair(action) => {
+            let git_dir = std::path::PathBuf::from(git::get_git_dir(opts)?);
+            match action {
+                PairAction::Start { coauthors } => {
+                    let known_authors = git::get_known_authors(opts)?;
+                    let resolved = coauthors
+                        .iter()
+                        .map(|c| pair::resolve_coauthor(c, &known_authors))
+                        .collect::<anyhow::Result<Vec<_>>>()?;
+                    pair::handle_pair_start(&git_dir, resolved)?;
+                }
+                PairAction::Show => pair::handle_pair_show(&git_dir)?,
+                PairAction::Stop => pair::handle_pair_stop(&git_dir)?,
+            }
+        }
+        Commands::Mob(action) => match action {
+            MobAction::Next => mob::handle_mob_next(&config, opts)?,
+            MobAction::Done {
+                r#type,
+                scope,
+                message,
+                body,
+                issue,
+            } => {
+                let params = CommitParams {
+                    r#type,
+                    scope,
+                    message,
+                    body,
+                    breaking: false,
+                    breaking_description: None,
+                    tag: None,
+                    issue,
+                    include_projects: true,
+                    no_verify: true,
+                    trailers: Vec::new(),
+                    preview: false,
+                    fix: false,
+                };
+                mob::handle_mob_done(&config, params, opts)?;
+            }
+        },
         Commands::Recover { selector, list } => {
             let git_root = std::path::PathBuf::from(git::get_git_root(opts)?);
             let current_branch = get_current_branch(opts)?;
@@ -330,34 +732,111 @@ fn main() -> anyhow::Result<()> {
         Commands::Review {
             sha,
             trigger,
+            batch,
             digest,
             approve,
             concern,
             dismiss,
             message,
+            severity,
             since,
+            author,
+            paths,
+            limit,
+            page,
+            resume,
             reviewers,
+            action,
         } => {
-            if let Some(commit_hash) = approve {
+            let digest_filter = review::DigestFilter {
+                since,
+                author,
+                paths,
+                limit: limit.map(|l| l as usize),
+                page: page as usize,
+                resume,
+            };
+            let writes = approve.is_some()
+                || concern.is_some()
+                || dismiss.is_some()
+                || sha.is_some()
+                || batch.is_some()
+                || trigger
+                || matches!(
+                    action,
+                    Some(ReviewAction::Flush | ReviewAction::Sweep | ReviewAction::Labels(_))
+                );
+            if json && writes {
+                anyhow::bail!(
+                    "--json is only supported for the review digest, list, mine, status, show and stats."
+                );
+            }
+            if let Some(ReviewAction::Flush) = action {
+                review::flush_review_queue(&config, true, opts)?;
+                return Ok(());
+            }
+            if !json {
+                review::flush_review_queue(&config, false, opts)?;
+            }
+            if let Some(ReviewAction::Labels(ReviewLabelsAction::Sync)) = action {
+                review::handle_review_labels_sync(&config, opts)?;
+            } else if let Some(ReviewAction::List {
+                mine,
+                authored,
+                label,
+                older_than,
+            }) = action
+            {
+                let filter = review::ReviewListFilter {
+                    mine,
+                    authored,
+                    label,
+                    older_than,
+                };
+                review::handle_review_list(&config, &filter, json, opts)?;
+            } else if let Some(ReviewAction::Mine {
+                authored,
+                label,
+                older_than,
+            }) = action
+            {
+                let filter = review::ReviewListFilter {
+                    mine: true,
+                    authored,
+                    label,
+                    older_than,
+                };
+                review::handle_review_list(&config, &filter, json, opts)?;
+            } else if let Some(ReviewAction::Status { commit }) = action {
+                review::handle_review_status(&config, &commit, json, opts)?;
+            } else if let Some(ReviewAction::Show { commit }) = action {
+                review::handle_review_show(&config, &commit, json, opts)?;
+            } else if let Some(ReviewAction::Stats { since }) = action {
+                review::handle_review_stats(&config, &since, json, opts)?;
+            } else if let Some(ReviewAction::Sweep) = action {
+                review::handle_review_sweep(&config, opts)?;
+            } else if let Some(commit_hash) = approve {
                 review::handle_review_approve(&config, &commit_hash, opts)?;
             } else if let Some(commit_hash) = concern {
                 let msg = message.ok_or_else(|| {
                     anyhow::anyhow!("--message is required when raising a concern")
                 })?;
-                review::handle_review_concern(&config, &commit_hash, &msg, opts)?;
+                review::handle_review_concern(&config, &commit_hash, &msg, severity, opts)?;
             } else if let Some(commit_hash) = dismiss {
                 let msg = message.ok_or_else(|| {
                     anyhow::anyhow!("--message is required when dismissing a review")
                 })?;
                 review::handle_review_dismiss(&config, &commit_hash, &msg, opts)?;
             } else if digest {
-                review::handle_review_digest(&config, &since, opts)?;
+                review::handle_review_digest(&config, &digest_filter, json, opts)?;
             } else if let Some(commit_sha) = sha {
                 review::handle_review_trigger(&config, reviewers, Some(commit_sha.as_str()), opts)?;
+            } else if let Some(range) = batch {
+                review::handle_review_batch(&config, reviewers, &range, opts)?;
             } else if trigger {
                 review::handle_review_trigger(&config, reviewers, None, opts)?;
             } else {
-                review::handle_review_digest(&config, &since, opts)?;
+                review::handle_review_digest(&config, &digest_filter, json, opts)?;
             }
         }
     }
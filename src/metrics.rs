@@ -0,0 +1,216 @@
+//! Trunk-based development metrics read from trunk history.
+
+use crate::commands::{AuthorIntegrationResponse, IntegrationMetricsResponse, TbdResponse};
+use crate::config::Config;
+use crate::git::{self, RunOpts};
+use crate::postmortem::format_duration;
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use colored::Colorize;
+use std::collections::BTreeMap;
+
+/// Trunk commits by one author landing within this many seconds of each other
+/// were pushed together, so they count as a single integration.
+pub const BATCH_WINDOW_SECS: i64 = 10 * 60;
+
+/// Gaps longer than this are flagged: trunk-based development expects every
+/// developer to integrate at least daily.
+const DAILY_SECS: i64 = 24 * 60 * 60;
+
+/// Turns shorthand such as `30d`, `2w` or `12h` into a `git log --since`
+/// value. Anything else is passed to git unchanged.
+pub fn git_since(since: &str) -> String {
+    let since = since.trim();
+    let unit = match since.chars().last() {
+        Some('h') => "hours",
+        Some('d') => "days",
+        Some('w') => "weeks",
+        _ => return since.to_string(),
+    };
+    match since[..since.len() - 1].parse::<u32>() {
+        Ok(n) => format!("{} {} ago", n, unit),
+        Err(_) => since.to_string(),
+    }
+}
+
+/// Per-author integration stats from `git::get_trunk_integrations` output,
+/// most frequent integrators first.
+pub fn integration_stats(log: &str) -> Vec<AuthorIntegrationResponse> {
+    let mut by_author: BTreeMap<&str, Vec<i64>> = BTreeMap::new();
+    for line in log.lines() {
+        let Some((timestamp, author)) = line.split_once('\x1f') else {
+            continue;
+        };
+        if let Ok(timestamp) = timestamp.trim().parse::<i64>() {
+            by_author.entry(author).or_default().push(timestamp);
+        }
+    }
+
+    let mut rows: Vec<AuthorIntegrationResponse> = by_author
+        .into_iter()
+        .map(|(author, mut timestamps)| {
+            timestamps.sort_unstable();
+            let mut integrations: Vec<i64> = Vec::new();
+            for timestamp in timestamps {
+                match integrations.last_mut() {
+                    Some(last) if timestamp - *last <= BATCH_WINDOW_SECS => *last = timestamp,
+                    _ => integrations.push(timestamp),
+                }
+            }
+            let gaps: Vec<i64> = integrations.windows(2).map(|w| w[1] - w[0]).collect();
+            let last = *integrations.last().unwrap_or(&0);
+            AuthorIntegrationResponse {
+                author: author.to_string(),
+                integrations: integrations.len(),
+                average_gap_seconds: (!gaps.is_empty())
+                    .then(|| gaps.iter().sum::<i64>() / gaps.len() as i64),
+                longest_gap_seconds: gaps.iter().copied().max(),
+                last_integration: DateTime::from_timestamp(last, 0)
+                    .unwrap_or_default()
+                    .to_rfc3339(),
+            }
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        b.integrations
+            .cmp(&a.integrations)
+            .then_with(|| a.author.cmp(&b.author))
+    });
+    rows
+}
+
+fn format_gap(seconds: Option<i64>) -> String {
+    seconds.map_or("-".to_string(), |s| format_duration(Duration::seconds(s)))
+}
+
+/// Shows how often each author integrates into trunk, and their longest gap.
+pub fn handle_integration_metrics(
+    config: &Config,
+    since: &str,
+    json: bool,
+    opts: RunOpts,
+) -> Result<()> {
+    let trunk = format!("origin/{}", config.main_branch_name);
+    let log = git::get_trunk_integrations(&trunk, &git_since(since), opts)?;
+    let authors = integration_stats(&log);
+
+    if json {
+        let response = IntegrationMetricsResponse {
+            trunk,
+            since: since.to_string(),
+            authors,
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(response))?
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "--- Integration frequency on '{}' (since {}) ---",
+            trunk, since
+        )
+        .blue()
+    );
+    if authors.is_empty() {
+        println!(
+            "{}",
+            "No integrations found in the specified time range.".yellow()
+        );
+        return Ok(());
+    }
+
+    let width = authors
+        .iter()
+        .map(|a| a.author.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Author".len());
+    println!(
+        "  {:<width$}  {:>12}  {:>8}  {:>11}  Last",
+        "Author",
+        "Integrations",
+        "Avg gap",
+        "Longest gap",
+        width = width
+    );
+    let now = Utc::now();
+    for row in &authors {
+        let flag = |seconds: Option<i64>, text: String| {
+            if seconds.is_some_and(|s| s > DAILY_SECS) {
+                text.yellow()
+            } else {
+                text.normal()
+            }
+        };
+        let last = DateTime::parse_from_rfc3339(&row.last_integration)
+            .map(|t| format!("{} ago", format_duration(now - t.with_timezone(&Utc))))
+            .unwrap_or_default();
+        println!(
+            "  {:<width$}  {:>12}  {}  {}  {}",
+            row.author,
+            row.integrations,
+            flag(
+                row.average_gap_seconds,
+                format!("{:>8}", format_gap(row.average_gap_seconds))
+            ),
+            flag(
+                row.longest_gap_seconds,
+                format!("{:>11}", format_gap(row.longest_gap_seconds))
+            ),
+            last.dimmed(),
+            width = width
+        );
+    }
+    println!(
+        "\n{}",
+        "Commits pushed within 10 minutes of each other count as one integration. Gaps over a day are highlighted."
+            .dimmed()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_since_shorthand() {
+        assert_eq!(git_since("30d"), "30 days ago");
+        assert_eq!(git_since("2w"), "2 weeks ago");
+        assert_eq!(git_since("12h"), "12 hours ago");
+        assert_eq!(git_since("2026-01-01"), "2026-01-01");
+        assert_eq!(git_since("1 week ago"), "1 week ago");
+    }
+
+    #[test]
+    fn batches_pushes_and_measures_gaps_per_author() {
+        let day = DAILY_SECS;
+        // Newest first, as git log prints it.
+        let log = [
+            format!("{}\x1fAda", 4 * day),
+            format!("{}\x1fBob", 3 * day),
+            format!("{}\x1fAda", day + 60),
+            format!("{}\x1fAda", day),
+            format!("{}\x1fAda", 0),
+        ]
+        .join("\n");
+        let rows = integration_stats(&log);
+        assert_eq!(rows.len(), 2);
+
+        let ada = &rows[0];
+        assert_eq!(ada.author, "Ada");
+        assert_eq!(ada.integrations, 3);
+        // Gaps of one day (plus the batched minute) and three days.
+        assert_eq!(ada.longest_gap_seconds, Some(3 * day - 60));
+        assert_eq!(ada.average_gap_seconds, Some(2 * day));
+
+        let bob = &rows[1];
+        assert_eq!(bob.integrations, 1);
+        assert_eq!(bob.average_gap_seconds, None);
+        assert_eq!(bob.longest_gap_seconds, None);
+    }
+}
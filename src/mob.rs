@@ -0,0 +1,184 @@
+use crate::commit::{self, CommitParams};
+use crate::config::Config;
+use crate::git::RunOpts;
+use crate::{git, pair};
+use anyhow::Result;
+use colored::Colorize;
+use std::path::PathBuf;
+
+const WIP_PREFIX: &str = "wip:";
+
+/// A commit in the WIP chain at the tip of the mob branch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WipCommit {
+    pub hash: String,
+    pub author: String,
+}
+
+/// Returns the unbroken run of `wip:` commits at the tip of a
+/// `hash|author <email>|subject` log, newest first.
+pub fn collect_wip_chain(log: &str) -> Vec<WipCommit> {
+    log.lines()
+        .map(|line| line.splitn(3, '|').collect::<Vec<_>>())
+        .take_while(|parts| parts.len() == 3 && parts[2].starts_with(WIP_PREFIX))
+        .map(|parts| WipCommit {
+            hash: parts[0].to_string(),
+            author: parts[1].to_string(),
+        })
+        .collect()
+}
+
+/// Mob work never happens on trunk: WIP commits would land on main.
+fn current_mob_branch(config: &Config, opts: RunOpts) -> Result<String> {
+    let branch = git::get_current_branch(opts)?;
+    if branch == config.main_branch_name {
+        return Err(anyhow::anyhow!(
+            "Mob sessions run on a shared branch, not '{}'. Start one with 'tbdflow branch'.",
+            branch
+        ));
+    }
+    Ok(branch)
+}
+
+/// Commits the current WIP, pushes it to the shared branch and prints
+/// handoff instructions for the next driver.
+pub fn handle_mob_next(config: &Config, opts: RunOpts) -> Result<()> {
+    println!("{}", "--- Handing over to the next driver ---".blue());
+    let branch = current_mob_branch(config, opts)?;
+
+    git::add_all(opts)?;
+    if git::has_staged_changes(opts)? {
+        let driver = git::get_user_name(opts).unwrap_or_default();
+        let message = if driver.is_empty() {
+            format!("{} mob handoff", WIP_PREFIX)
+        } else {
+            format!("{} mob handoff from {}", WIP_PREFIX, driver)
+        };
+        git::commit_no_verify(&message, opts)?;
+        println!("{}", format!("Committed WIP: {}", message).green());
+    } else {
+        println!(
+            "{}",
+            "No uncommitted changes; pushing the branch as-is.".yellow()
+        );
+    }
+
+    if git::remote_branch_exists(&branch, opts).is_ok() {
+        git::push(opts)?;
+    } else {
+        git::push_set_upstream(&branch, opts)?;
+    }
+
+    println!("\n{}", "Next driver, pick up with:".bold());
+    println!("  git fetch origin");
+    println!("  git checkout {}", branch);
+    println!("  git pull --ff-only");
+    println!(
+        "{}",
+        "When the mob is finished, run 'tbdflow mob done' to squash the WIP commits.".dimmed()
+    );
+    Ok(())
+}
+
+/// Squashes the WIP chain (and any uncommitted changes) into one
+/// conventional commit crediting every driver, then force-pushes the branch.
+pub fn handle_mob_done(config: &Config, params: CommitParams, opts: RunOpts) -> Result<()> {
+    println!("{}", "--- Squashing mob WIP commits ---".blue());
+    let branch = current_mob_branch(config, opts)?;
+
+    let violations = commit::lint_commit_params(&params, config)?;
+    if !violations.is_empty() {
+        for violation in &violations {
+            println!(
+                "{}",
+                format!("[{}] {}", violation.rule, violation.message).red()
+            );
+        }
+        return Err(anyhow::anyhow!("Aborted: Invalid commit message."));
+    }
+
+    let range = format!("origin/{}..HEAD", config.main_branch_name);
+    let log = git::get_log_range(&range, opts)?;
+    let chain = collect_wip_chain(&log);
+    let oldest = chain
+        .last()
+        .ok_or_else(|| anyhow::anyhow!("No '{}' commits to squash on '{}'.", WIP_PREFIX, branch))?;
+
+    // Credit every other driver, plus the active pair session.
+    let me = format!(
+        "{} <{}>",
+        git::get_user_name(opts).unwrap_or_default(),
+        git::get_user_email(opts).unwrap_or_default()
+    );
+    let mut coauthors: Vec<String> = Vec::new();
+    for author in chain.iter().rev().map(|c| &c.author) {
+        if *author != me && !coauthors.contains(author) {
+            coauthors.push(author.clone());
+        }
+    }
+    let git_dir = PathBuf::from(git::get_git_dir(opts)?);
+    if let Some(session) = pair::load_pair_session(&git_dir)? {
+        for coauthor in session.coauthors {
+            if !coauthors.contains(&coauthor) {
+                coauthors.push(coauthor);
+            }
+        }
+    }
+
+    let mut trailers = params.trailers.clone();
+    for coauthor in &coauthors {
+        let trailer = format!("Co-authored-by: {}", coauthor);
+        if !trailers.iter().any(|t| t.eq_ignore_ascii_case(&trailer)) {
+            trailers.push(trailer);
+        }
+    }
+    commit::push_version_trailer(config, &mut trailers);
+    let commit_message = commit::build_commit_message(&params, None, &trailers);
+    println!(
+        "{}",
+        format!("Commit message will be:\n---\n{}\n---", commit_message).blue()
+    );
+
+    git::add_all(opts)?;
+    git::reset_soft(&format!("{}^", oldest.hash), opts)?;
+    git::commit(&commit_message, opts)?;
+    git::push_force_with_lease(opts)?;
+
+    println!(
+        "\n{}",
+        format!(
+            "Success! Squashed {} WIP commit(s) on '{}'.",
+            chain.len(),
+            branch
+        )
+        .green()
+    );
+    println!(
+        "{}",
+        "Run 'tbdflow complete' to merge the branch into main.".dimmed()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_wip_chain_stops_at_first_real_commit() {
+        let log = "c3|Bob <bob@x.io>|wip: mob handoff from Bob\n\
+                   c2|Ada <ada@x.io>|wip: mob handoff from Ada\n\
+                   c1|Ada <ada@x.io>|feat: scaffold\n\
+                   c0|Bob <bob@x.io>|wip: older handoff";
+        let chain = collect_wip_chain(log);
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain[1].hash, "c2");
+        assert_eq!(chain[0].author, "Bob <bob@x.io>");
+    }
+
+    #[test]
+    fn collect_wip_chain_is_empty_without_wip_tip() {
+        assert!(collect_wip_chain("c1|Ada <ada@x.io>|feat: scaffold").is_empty());
+        assert!(collect_wip_chain("").is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+//! Terminal output settings shared by every command. In ASCII mode emojis and
+//! box-drawing characters are swapped for plain text, for terminals and CI
+//! logs that mangle them.
+
+use dialoguer::theme::{ColorfulTheme, SimpleTheme, Theme};
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+/// Turns ASCII mode on or off for the rest of the process.
+pub fn set_ascii(enabled: bool) {
+    ASCII.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_ascii() -> bool {
+    ASCII.load(Ordering::Relaxed)
+}
+
+/// True when `TBDFLOW_ASCII` is set to anything but empty or `0`.
+pub fn ascii_from_env() -> bool {
+    std::env::var("TBDFLOW_ASCII").is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+/// `fancy` normally, `plain` in ASCII mode.
+pub fn glyph<'a>(fancy: &'a str, plain: &'a str) -> &'a str {
+    if is_ascii() { plain } else { fancy }
+}
+
+/// `text` unchanged normally; in ASCII mode with every glyph swapped for plain text.
+pub fn text(text: &str) -> Cow<'_, str> {
+    if is_ascii() && !text.is_ascii() {
+        Cow::Owned(to_ascii(text))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Replaces dashes, bullets and box-drawing characters with ASCII look-alikes
+/// and drops emojis (with the space that followed them).
+pub fn to_ascii(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut dropped = false;
+    for c in text.chars() {
+        let replacement = match c {
+            '—' | '–' | '─' => Some("-"),
+            '•' | '·' => Some("*"),
+            '├' | '│' => Some("|"),
+            '└' => Some("`"),
+            '→' => Some("->"),
+            '✔' | '✓' => Some("[ok]"),
+            '✘' | '✗' => Some("[x]"),
+            '❯' => Some(">"),
+            c if c.is_ascii() => {
+                if !(dropped && c == ' ') {
+                    out.push(c);
+                }
+                dropped = false;
+                continue;
+            }
+            _ => None,
+        };
+        match replacement {
+            Some(plain) => {
+                out.push_str(plain);
+                dropped = false;
+            }
+            None => dropped = true,
+        }
+    }
+    out
+}
+
+/// The theme for interactive prompts: colourful normally, plain in ASCII mode.
+pub fn theme() -> Box<dyn Theme> {
+    if is_ascii() {
+        Box::new(SimpleTheme)
+    } else {
+        Box::new(ColorfulTheme::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emojis_are_dropped_and_glyphs_replaced() {
+        assert_eq!(to_ascii("### ✨ Features"), "### Features");
+        assert_eq!(to_ascii("### ⚙️ Maintenance"), "### Maintenance");
+        assert_eq!(
+            to_ascii("⏳ Trunk CI is still running."),
+            "Trunk CI is still running."
+        );
+        assert_eq!(to_ascii("  └── src/a.rs — @ada"), "  `-- src/a.rs - @ada");
+        assert_eq!(to_ascii("   • Review"), "   * Review");
+        assert_eq!(to_ascii("plain"), "plain");
+    }
+}
@@ -0,0 +1,167 @@
+use crate::state::{self, State};
+use anyhow::Result;
+use chrono::Utc;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The active pair programming session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairSession {
+    /// Co-authors in `Name <email>` form.
+    pub coauthors: Vec<String>,
+    /// Timestamp when the session was started.
+    pub started_at: String,
+}
+
+impl PairSession {
+    /// Returns the `Co-authored-by` trailers for every co-author.
+    pub fn trailers(&self) -> Vec<String> {
+        self.coauthors
+            .iter()
+            .map(|c| format!("Co-authored-by: {}", c))
+            .collect()
+    }
+}
+
+impl State for PairSession {
+    const FILE: &'static str = "pair.json";
+    const LEGACY_FILE: Option<&'static str> = Some("tbdflow-pair.json");
+}
+
+/// Loads the active pair session, or returns `None` when nobody is pairing.
+pub fn load_pair_session(git_dir: &Path) -> Result<Option<PairSession>> {
+    state::load(git_dir)
+}
+
+/// Resolves a co-author given as `Name <email>`, a name, or the user part of an
+/// email address against the authors already present in the history.
+pub fn resolve_coauthor(handle: &str, known_authors: &[String]) -> Result<String> {
+    let handle = handle.trim();
+    if handle.contains('<') && handle.ends_with('>') {
+        return Ok(handle.to_string());
+    }
+
+    let needle = handle.to_lowercase();
+    let matches: Vec<&String> = known_authors
+        .iter()
+        .filter(|author| {
+            let lower = author.to_lowercase();
+            let (name, email) = lower.split_once(" <").unwrap_or((lower.as_str(), ""));
+            let email_user = email.split('@').next().unwrap_or("");
+            name == needle
+                || name.split_whitespace().next() == Some(&needle)
+                || email_user == needle
+        })
+        .collect();
+
+    match matches.as_slice() {
+        [author] => Ok(author.to_string()),
+        [] => Err(anyhow::anyhow!(
+            "Could not find an author matching '{}' in the history. Use \"Name <email>\" instead.",
+            handle
+        )),
+        _ => Err(anyhow::anyhow!(
+            "'{}' matches several authors:\n{}\nUse \"Name <email>\" to pick one.",
+            handle,
+            matches
+                .iter()
+                .map(|m| m.as_str())
+                .collect::<Vec<_>>()
+                .join("\n")
+        )),
+    }
+}
+
+/// Starts a pair session with the given co-authors, replacing any active one.
+pub fn handle_pair_start(git_dir: &Path, coauthors: Vec<String>) -> Result<()> {
+    let session = PairSession {
+        coauthors,
+        started_at: Utc::now().to_rfc3339(),
+    };
+    state::save(git_dir, &session)?;
+
+    println!(
+        "{}",
+        format!("Pairing with: {}", session.coauthors.join(", ")).green()
+    );
+    println!(
+        "{}",
+        "Every commit will carry a Co-authored-by trailer until 'tbdflow pair stop'.".dimmed()
+    );
+    Ok(())
+}
+
+/// Ends the active pair session.
+pub fn handle_pair_stop(git_dir: &Path) -> Result<()> {
+    if !state::remove::<PairSession>(git_dir)? {
+        println!("{}", "No active pair session.".yellow());
+        return Ok(());
+    }
+    println!("{}", "Pair session ended.".green());
+    Ok(())
+}
+
+/// Prints the active pair session.
+pub fn handle_pair_show(git_dir: &Path) -> Result<()> {
+    match load_pair_session(git_dir)? {
+        Some(session) => {
+            println!(
+                "{}",
+                format!("Pairing with: {}", session.coauthors.join(", ")).green()
+            );
+            println!("{}", format!("Since: {}", session.started_at).dimmed());
+        }
+        None => println!("{}", "No active pair session.".yellow()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn known() -> Vec<String> {
+        vec![
+            "Alice Andersson <alice@example.com>".to_string(),
+            "Bob Berg <bob.berg@example.com>".to_string(),
+            "Alice Cooper <acooper@example.com>".to_string(),
+        ]
+    }
+
+    #[test]
+    fn resolve_coauthor_accepts_explicit_identity() {
+        let resolved = resolve_coauthor("Ada Lovelace <ada@example.com>", &known()).unwrap();
+        assert_eq!(resolved, "Ada Lovelace <ada@example.com>");
+    }
+
+    #[test]
+    fn resolve_coauthor_matches_email_user_and_full_name() {
+        assert_eq!(
+            resolve_coauthor("bob.berg", &known()).unwrap(),
+            "Bob Berg <bob.berg@example.com>"
+        );
+        assert_eq!(
+            resolve_coauthor("alice cooper", &known()).unwrap(),
+            "Alice Cooper <acooper@example.com>"
+        );
+    }
+
+    #[test]
+    fn resolve_coauthor_rejects_ambiguous_and_unknown() {
+        assert!(resolve_coauthor("alice", &known()).is_err());
+        assert!(resolve_coauthor("carol", &known()).is_err());
+    }
+
+    #[test]
+    fn trailers_are_formatted_per_coauthor() {
+        let session = PairSession {
+            coauthors: vec!["Ada <ada@example.com>".to_string()],
+            started_at: String::new(),
+        };
+        assert_eq!(
+            session.trailers(),
+            vec!["Co-authored-by: Ada <ada@example.com>".to_string()]
+        );
+    }
+}
@@ -0,0 +1,486 @@
+//! Blame-free incident summaries. Given a revert or fix-forward commit,
+//! `tbdflow postmortem` lines up what tbdflow already knows about the change
+//! it undid — CI runs, the review issue, concerns and journal events — as a
+//! markdown timeline. People are deliberately left out: the summary is about
+//! what happened, not who did it.
+
+use crate::gh;
+use crate::git::{self, RunOpts, short_hash};
+use crate::journal::{self, JournalEvent};
+use crate::review;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use colored::Colorize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// How the incident commit dealt with the original change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// `git revert`, as done by `tbdflow undo`.
+    Revert,
+    /// A fix linked with a `Fixes-Review:` trailer.
+    FixForward,
+}
+
+impl Resolution {
+    fn as_str(self) -> &'static str {
+        match self {
+            Resolution::Revert => "revert",
+            Resolution::FixForward => "fix-forward",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitInfo {
+    pub hash: String,
+    pub subject: String,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineEvent {
+    pub at: DateTime<Utc>,
+    pub event: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewIssue {
+    pub number: i64,
+    pub url: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Postmortem {
+    pub original: CommitInfo,
+    pub resolution: CommitInfo,
+    pub kind: Resolution,
+    pub review_issue: Option<ReviewIssue>,
+    /// Concern messages raised on the review issue, oldest first.
+    pub concerns: Vec<TimelineEvent>,
+    /// False when GitHub could not be asked, so CI and review data are missing.
+    pub github_available: bool,
+    pub timeline: Vec<TimelineEvent>,
+}
+
+/// The commit that `message` reverts or fixes forward, if any.
+pub fn original_commit(message: &str) -> Option<(String, Resolution)> {
+    let reverted = message.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("This reverts commit ")
+            .map(|rest| rest.trim_end_matches('.').trim().to_string())
+    });
+    if let Some(hash) = reverted.filter(|h| !h.is_empty()) {
+        return Some((hash, Resolution::Revert));
+    }
+    let trailers: Vec<String> = message.lines().map(str::to_string).collect();
+    review::fixed_reviews(&trailers)
+        .into_iter()
+        .next()
+        .map(|hash| (hash, Resolution::FixForward))
+}
+
+fn parse_time(value: &Value) -> Option<DateTime<Utc>> {
+    value
+        .as_str()
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// CI runs from `gh run list --json name,status,conclusion,createdAt`.
+pub fn parse_ci_runs(json: &str) -> Vec<TimelineEvent> {
+    let runs: Vec<Value> = serde_json::from_str(json).unwrap_or_default();
+    runs.iter()
+        .filter_map(|run| {
+            let at = parse_time(&run["createdAt"])?;
+            let name = run["name"].as_str().unwrap_or("CI");
+            let result = match run["conclusion"].as_str().filter(|c| !c.is_empty()) {
+                Some(conclusion) => conclusion,
+                None => run["status"].as_str().unwrap_or("unknown"),
+            };
+            Some(TimelineEvent {
+                at,
+                event: format!("CI run \"{}\": {}", name, result),
+            })
+        })
+        .collect()
+}
+
+/// Concerns among the comments of `gh issue view --json comments`.
+pub fn parse_concern_comments(json: &str) -> Vec<TimelineEvent> {
+    let parsed: Value = serde_json::from_str(json).unwrap_or_default();
+    parsed["comments"]
+        .as_array()
+        .map(|comments| {
+            comments
+                .iter()
+                .filter_map(|comment| {
+                    let body = comment["body"].as_str()?;
+                    let rest = body.trim_start().strip_prefix("**Concern Raised**")?;
+                    let (severity, message) = match rest.split_once('\n') {
+                        Some((first, message)) => (first.trim(), message.trim()),
+                        None => (rest.trim(), ""),
+                    };
+                    let message = message.lines().next().unwrap_or_default();
+                    let event = if severity.is_empty() {
+                        format!("Concern raised: {}", message)
+                    } else {
+                        format!("Concern raised {}: {}", severity, message)
+                    };
+                    Some(TimelineEvent {
+                        at: parse_time(&comment["createdAt"])?,
+                        event,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `2h 15m`, `3d 4h` or `45m`.
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    let (days, hours, mins) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    match (days, hours) {
+        (0, 0) => format!("{}m", mins),
+        (0, _) => format!("{}h {}m", hours, mins),
+        _ => format!("{}d {}h", days, hours),
+    }
+}
+
+/// Journal events recorded for the original commit.
+fn journal_events(git_dir: &Path, original: &str) -> Result<Vec<TimelineEvent>> {
+    let matches = |commit: &str| !commit.is_empty() && original.starts_with(commit);
+    Ok(journal::read_entries(git_dir)?
+        .into_iter()
+        .filter_map(|entry| {
+            let event = match &entry.event {
+                JournalEvent::ReviewSkipped {
+                    commit,
+                    commit_type,
+                } if matches(commit) => {
+                    format!("Automatic review skipped for a `{}` commit", commit_type)
+                }
+                JournalEvent::DodSkipped { commit, .. } if matches(commit) => {
+                    "Definition of Done checklist skipped (`--no-verify`)".to_string()
+                }
+                JournalEvent::ReviewApproved { commit } if matches(commit) => {
+                    "Approval recorded".to_string()
+                }
+                _ => return None,
+            };
+            let at = DateTime::parse_from_rfc3339(&entry.timestamp).ok()?;
+            Some(TimelineEvent {
+                at: at.with_timezone(&Utc),
+                event,
+            })
+        })
+        .collect())
+}
+
+fn gh_json(args: &[&str], opts: RunOpts) -> Option<String> {
+    gh::run(args, opts)
+        .ok()
+        .filter(|o| o.success)
+        .map(|o| o.stdout)
+}
+
+fn find_review_issue(short: &str, opts: RunOpts) -> Option<ReviewIssue> {
+    let search_query = format!("[Review] in:title {} in:title", short);
+    let json = gh_json(
+        &[
+            "issue",
+            "list",
+            "--search",
+            &search_query,
+            "--state",
+            "all",
+            "--json",
+            "number,url,createdAt,closedAt",
+            "--limit",
+            "1",
+        ],
+        opts,
+    )?;
+    let issues: Vec<Value> = serde_json::from_str(&json).ok()?;
+    let issue = issues.first()?;
+    Some(ReviewIssue {
+        number: issue["number"].as_i64()?,
+        url: issue["url"].as_str().unwrap_or_default().to_string(),
+        created_at: parse_time(&issue["createdAt"]),
+        closed_at: parse_time(&issue["closedAt"]),
+    })
+}
+
+fn commit_info(hash: &str, opts: RunOpts) -> Result<CommitInfo> {
+    let full = git::resolve_commit_hash(hash, opts)?;
+    Ok(CommitInfo {
+        subject: git::get_commit_message(&full, opts)?,
+        at: git::get_commit_time(&full, opts)?,
+        hash: full,
+    })
+}
+
+/// Gathers everything known about the incident resolved by `incident_hash`.
+pub fn build_postmortem(incident_hash: &str, opts: RunOpts) -> Result<Postmortem> {
+    let resolution = commit_info(incident_hash, opts)?;
+    let message = git::get_commit_full_message(&resolution.hash, opts)?;
+    let (original_hash, kind) = original_commit(&message).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Commit {} is neither a revert nor a fix-forward: it has no 'This reverts commit' line or Fixes-Review trailer.",
+            short_hash(&resolution.hash)
+        )
+    })?;
+    let original = commit_info(&original_hash, opts)
+        .with_context(|| format!("Original commit '{}' not found", original_hash))?;
+    let short = short_hash(&original.hash).to_string();
+
+    let mut timeline = vec![TimelineEvent {
+        at: original.at,
+        event: format!("Original change `{}` landed: {}", short, original.subject),
+    }];
+    let git_dir = PathBuf::from(git::get_git_dir(opts)?);
+    timeline.extend(journal_events(&git_dir, &original.hash)?);
+
+    let github_available = gh::is_available();
+    let mut review_issue = None;
+    let mut concerns = Vec::new();
+    if github_available {
+        if let Some(runs) = gh_json(
+            &[
+                "run",
+                "list",
+                "--commit",
+                &original.hash,
+                "--json",
+                "name,status,conclusion,createdAt",
+            ],
+            opts,
+        ) {
+            timeline.extend(parse_ci_runs(&runs));
+        }
+        review_issue = find_review_issue(&short, opts);
+        if let Some(issue) = &review_issue {
+            if let Some(at) = issue.created_at {
+                timeline.push(TimelineEvent {
+                    at,
+                    event: format!("Review issue #{} opened", issue.number),
+                });
+            }
+            let number = issue.number.to_string();
+            if let Some(comments) = gh_json(&["issue", "view", &number, "--json", "comments"], opts)
+            {
+                concerns = parse_concern_comments(&comments);
+                timeline.extend(concerns.iter().cloned());
+            }
+            if let Some(at) = issue.closed_at {
+                timeline.push(TimelineEvent {
+                    at,
+                    event: format!("Review issue #{} closed", issue.number),
+                });
+            }
+        }
+    }
+
+    timeline.push(TimelineEvent {
+        at: resolution.at,
+        event: format!(
+            "Resolved by {} `{}`: {}",
+            kind.as_str(),
+            short_hash(&resolution.hash),
+            resolution.subject
+        ),
+    });
+    timeline.sort_by_key(|e| e.at);
+
+    Ok(Postmortem {
+        original,
+        resolution,
+        kind,
+        review_issue,
+        concerns,
+        github_available,
+        timeline,
+    })
+}
+
+/// The incident summary as markdown.
+pub fn render_markdown(postmortem: &Postmortem, generated_at: DateTime<Utc>) -> String {
+    let original = &postmortem.original;
+    let resolution = &postmortem.resolution;
+    let mut md = format!("# Incident summary: {}\n\n", original.subject);
+    md.push_str(&format!(
+        "_Blame-free postmortem generated by tbdflow on {}. It describes what happened, not who did it._\n\n",
+        generated_at.format("%Y-%m-%d %H:%M UTC")
+    ));
+
+    md.push_str("## Summary\n\n| | |\n|---|---|\n");
+    md.push_str(&format!(
+        "| Original change | `{}` {} |\n",
+        short_hash(&original.hash),
+        original.subject
+    ));
+    md.push_str(&format!(
+        "| Resolved by | `{}` {} ({}) |\n",
+        short_hash(&resolution.hash),
+        resolution.subject,
+        postmortem.kind.as_str()
+    ));
+    md.push_str(&format!(
+        "| Time to recovery | {} |\n",
+        format_duration(resolution.at - original.at)
+    ));
+    let review = match &postmortem.review_issue {
+        Some(issue) if !issue.url.is_empty() => format!("[#{}]({})", issue.number, issue.url),
+        Some(issue) => format!("#{}", issue.number),
+        None if postmortem.github_available => "No review issue found".to_string(),
+        None => "Unknown (GitHub not available)".to_string(),
+    };
+    md.push_str(&format!("| Review | {} |\n\n", review));
+
+    md.push_str("## Timeline\n\n| Time (UTC) | Event |\n|---|---|\n");
+    for event in &postmortem.timeline {
+        md.push_str(&format!(
+            "| {} | {} |\n",
+            event.at.format("%Y-%m-%d %H:%M"),
+            event.event.replace('|', "\\|")
+        ));
+    }
+    if !postmortem.github_available {
+        md.push_str(
+            "\n_CI runs and review activity are missing: the GitHub CLI (gh) was not available._\n",
+        );
+    }
+
+    md.push_str("\n## Concerns raised during review\n\n");
+    if postmortem.concerns.is_empty() {
+        md.push_str("None recorded.\n");
+    } else {
+        for concern in &postmortem.concerns {
+            md.push_str(&format!("- {}\n", concern.event));
+        }
+    }
+
+    md.push_str(
+        "\n## Questions for the team\n\n\
+         - Which signal could have caught this before it reached trunk?\n\
+         - Did CI and review give the information needed, in time?\n\
+         - What would make recovery faster next time?\n",
+    );
+    md
+}
+
+pub fn handle_postmortem(commit: &str, output_path: Option<&Path>, opts: RunOpts) -> Result<()> {
+    let lookup = opts.for_reads();
+    let postmortem = build_postmortem(commit, lookup)?;
+    let markdown = render_markdown(&postmortem, Utc::now());
+
+    match output_path {
+        Some(path) if opts.dry_run => {
+            println!(
+                "{}",
+                format!("[DRY RUN] Would write the postmortem to {}", path.display()).yellow()
+            );
+        }
+        Some(path) => {
+            std::fs::write(path, &markdown)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!(
+                "{}",
+                format!("Postmortem written to {}", path.display()).green()
+            );
+        }
+        None => print!("{}", markdown),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 17, hour, min, 0).unwrap()
+    }
+
+    #[test]
+    fn finds_reverted_and_fixed_commits() {
+        let revert = "Revert \"feat: add cache\"\n\nThis reverts commit abc1234def5678.\n";
+        assert_eq!(
+            original_commit(revert),
+            Some(("abc1234def5678".to_string(), Resolution::Revert))
+        );
+        let fix = "fix: flush cache on write\n\nFixes-Review: abc1234\n";
+        assert_eq!(
+            original_commit(fix),
+            Some(("abc1234".to_string(), Resolution::FixForward))
+        );
+        assert_eq!(original_commit("feat: add cache"), None);
+    }
+
+    #[test]
+    fn parses_ci_runs_and_concerns() {
+        let runs = r#"[{"name":"CI","status":"completed","conclusion":"failure","createdAt":"2026-10-17T10:05:00Z"},
+                       {"name":"Lint","status":"in_progress","conclusion":"","createdAt":"2026-10-17T10:06:00Z"}]"#;
+        let events = parse_ci_runs(runs);
+        assert_eq!(events[0].event, "CI run \"CI\": failure");
+        assert_eq!(events[1].event, "CI run \"Lint\": in_progress");
+
+        let comments = r#"{"comments":[
+            {"body":"**Concern Raised** (critical)\n\nDrops writes on retry","createdAt":"2026-10-17T11:00:00Z"},
+            {"body":"LGTM","createdAt":"2026-10-17T11:30:00Z"}]}"#;
+        let concerns = parse_concern_comments(comments);
+        assert_eq!(concerns.len(), 1);
+        assert_eq!(
+            concerns[0].event,
+            "Concern raised (critical): Drops writes on retry"
+        );
+    }
+
+    #[test]
+    fn durations_are_human_readable() {
+        assert_eq!(format_duration(Duration::minutes(45)), "45m");
+        assert_eq!(format_duration(Duration::minutes(135)), "2h 15m");
+        assert_eq!(format_duration(Duration::hours(76)), "3d 4h");
+    }
+
+    #[test]
+    fn markdown_has_summary_and_ordered_timeline() {
+        let postmortem = Postmortem {
+            original: CommitInfo {
+                hash: "abc1234def".to_string(),
+                subject: "feat: add cache".to_string(),
+                at: at(10, 0),
+            },
+            resolution: CommitInfo {
+                hash: "fed4321cba".to_string(),
+                subject: "Revert \"feat: add cache\"".to_string(),
+                at: at(12, 30),
+            },
+            kind: Resolution::Revert,
+            review_issue: None,
+            concerns: Vec::new(),
+            github_available: false,
+            timeline: vec![
+                TimelineEvent {
+                    at: at(10, 0),
+                    event: "Original change `abc1234` landed: feat: add cache".to_string(),
+                },
+                TimelineEvent {
+                    at: at(12, 30),
+                    event: "Resolved by revert `fed4321`".to_string(),
+                },
+            ],
+        };
+        let md = render_markdown(&postmortem, at(13, 0));
+        assert!(md.starts_with("# Incident summary: feat: add cache\n"));
+        assert!(md.contains("| Time to recovery | 2h 30m |"));
+        assert!(md.contains("| Review | Unknown (GitHub not available) |"));
+        assert!(md.contains("| 2026-10-17 10:00 | Original change"));
+        assert!(md.contains("None recorded."));
+    }
+}
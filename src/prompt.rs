@@ -0,0 +1,188 @@
+use crate::git::RunOpts;
+use crate::output;
+use anyhow::{Result, anyhow};
+use colored::Colorize;
+use console::{Key, Term};
+use dialoguer::Confirm;
+use std::io::IsTerminal;
+
+/// Returns true when both stdin and stdout are attached to a terminal.
+/// CI runners, git hooks and piped invocations all fail this check.
+pub fn is_interactive() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
+/// Environment variable that turns on `--yes` for every invocation, e.g. in CI jobs.
+pub const ASSUME_YES_ENV: &str = "TBDFLOW_ASSUME_YES";
+
+/// True when `TBDFLOW_ASSUME_YES` is set to `1`, `true` or `yes`.
+pub fn assume_yes_from_env() -> bool {
+    std::env::var(ASSUME_YES_ENV).is_ok_and(|v| is_truthy(&v))
+}
+
+fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes"
+    )
+}
+
+/// Fails fast when a prompt is unavoidable but nobody is there to answer it.
+pub fn require_interactive(hint: &str) -> Result<()> {
+    if is_interactive() {
+        return Ok(());
+    }
+    Err(anyhow!(
+        "Cannot prompt for input in a non-interactive session (no TTY). {}",
+        hint
+    ))
+}
+
+/// Asks a yes/no question, honouring `--yes` and non-interactive sessions.
+///
+/// Without a TTY the `default` answer is used; if there is no default the
+/// call fails instead of hanging.
+pub fn confirm(prompt: &str, default: Option<bool>, opts: RunOpts) -> Result<bool> {
+    if opts.assume_yes {
+        println!("{} {}", prompt, "yes (--yes)".dimmed());
+        return Ok(true);
+    }
+
+    if !is_interactive() {
+        return match default {
+            Some(answer) => {
+                let label = if answer { "yes" } else { "no" };
+                println!(
+                    "{} {}",
+                    prompt,
+                    format!("{} (non-interactive default)", label).dimmed()
+                );
+                Ok(answer)
+            }
+            None => Err(anyhow!(
+                "Cannot answer '{}' in a non-interactive session. Re-run with --yes to confirm automatically.",
+                prompt
+            )),
+        };
+    }
+
+    let theme = output::theme();
+    let mut question = Confirm::with_theme(&*theme).with_prompt(prompt);
+    if let Some(answer) = default {
+        question = question.default(answer);
+    }
+    Ok(question.interact()?)
+}
+
+/// Bulk shortcuts offered by [`checklist`] on top of toggling single items.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChecklistShortcut {
+    /// Tick everything, or untick everything when all items are ticked.
+    All,
+    /// Flip every item.
+    Invert,
+}
+
+pub fn apply_checklist_shortcut(checked: &mut [bool], shortcut: ChecklistShortcut) {
+    match shortcut {
+        ChecklistShortcut::All => {
+            let all = checked.iter().all(|c| *c);
+            checked.fill(!all);
+        }
+        ChecklistShortcut::Invert => checked.iter_mut().for_each(|c| *c = !*c),
+    }
+}
+
+/// Multi-select prompt with `a` (select all/none) and `i` (invert)
+/// shortcuts. `defaults` sets which items start ticked. Returns the indices
+/// of the ticked items.
+pub fn checklist(prompt: &str, items: &[String], defaults: &[bool]) -> Result<Vec<usize>> {
+    if items.is_empty() {
+        return Ok(Vec::new());
+    }
+    let theme = output::theme();
+    let term = Term::stderr();
+    let mut checked: Vec<bool> = (0..items.len())
+        .map(|i| defaults.get(i).copied().unwrap_or(false))
+        .collect();
+    let mut active = 0;
+    let mut drawn = 0;
+
+    term.hide_cursor()?;
+    let result = loop {
+        term.clear_last_lines(drawn)?;
+        let mut header = String::new();
+        theme.format_multi_select_prompt(&mut header, prompt)?;
+        term.write_line(&format!(
+            "{} {}",
+            header,
+            "[space] toggle, [a] all/none, [i] invert, [enter] done".dimmed()
+        ))?;
+        for (i, item) in items.iter().enumerate() {
+            let mut line = String::new();
+            theme.format_multi_select_prompt_item(&mut line, item, checked[i], i == active)?;
+            term.write_line(&line)?;
+        }
+        drawn = items.len() + 1;
+
+        match term.read_key() {
+            Ok(Key::ArrowDown | Key::Tab | Key::Char('j')) => active = (active + 1) % items.len(),
+            Ok(Key::ArrowUp | Key::BackTab | Key::Char('k')) => {
+                active = (active + items.len() - 1) % items.len()
+            }
+            Ok(Key::Char(' ')) => checked[active] = !checked[active],
+            Ok(Key::Char('a')) => apply_checklist_shortcut(&mut checked, ChecklistShortcut::All),
+            Ok(Key::Char('i')) => apply_checklist_shortcut(&mut checked, ChecklistShortcut::Invert),
+            Ok(Key::Enter) => break Ok(()),
+            Ok(_) => {}
+            Err(e) => break Err(e),
+        }
+    };
+    term.clear_last_lines(drawn)?;
+    term.show_cursor()?;
+    result?;
+
+    let selected: Vec<&str> = items
+        .iter()
+        .zip(&checked)
+        .filter(|(_, c)| **c)
+        .map(|(item, _)| item.as_str())
+        .collect();
+    let mut summary = String::new();
+    theme.format_multi_select_prompt_selection(&mut summary, prompt, &selected)?;
+    term.write_line(&summary)?;
+
+    Ok((0..items.len()).filter(|i| checked[*i]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirm_returns_true_with_assume_yes() {
+        let opts = RunOpts::new(false, false).with_assume_yes(true);
+        assert!(confirm("Proceed?", Some(false), opts).unwrap());
+        assert!(confirm("Proceed?", None, opts).unwrap());
+    }
+
+    #[test]
+    fn env_values_are_truthy_case_insensitively() {
+        assert!(is_truthy("1"));
+        assert!(is_truthy("TRUE"));
+        assert!(is_truthy(" yes "));
+        assert!(!is_truthy("0"));
+        assert!(!is_truthy(""));
+    }
+
+    #[test]
+    fn checklist_shortcuts() {
+        let mut checked = vec![true, false, true];
+        apply_checklist_shortcut(&mut checked, ChecklistShortcut::Invert);
+        assert_eq!(checked, vec![false, true, false]);
+        apply_checklist_shortcut(&mut checked, ChecklistShortcut::All);
+        assert_eq!(checked, vec![true, true, true]);
+        apply_checklist_shortcut(&mut checked, ChecklistShortcut::All);
+        assert_eq!(checked, vec![false, false, false]);
+    }
+}
@@ -4,7 +4,7 @@ use crate::commands::{
 };
 use crate::config::{Config, RadarLevel, RadarOnCommit};
 use crate::git::RunOpts;
-use crate::{git, intent};
+use crate::{git, intent, output, prompt};
 use anyhow::Result;
 use chrono::Utc;
 use colored::*;
@@ -186,7 +186,7 @@ pub fn scan(config: &Config, opts: RunOpts) -> Result<RadarResult> {
         for file in &overlapping_files {
             let overlap_kind = match level {
                 RadarLevel::Line => detect_line_overlap(file, &main_ref, &branch_ref, opts)
-                    .unwrap_or_else(|| OverlapKind::SameFile),
+                    .unwrap_or(OverlapKind::SameFile),
                 RadarLevel::File => OverlapKind::SameFile,
             };
 
@@ -242,10 +242,10 @@ fn detect_line_overlap(
 
 fn should_ignore(file: &str, patterns: &[String]) -> bool {
     for pattern in patterns {
-        if let Ok(glob_pattern) = glob::Pattern::new(pattern) {
-            if glob_pattern.matches(file) {
-                return true;
-            }
+        if let Ok(glob_pattern) = glob::Pattern::new(pattern)
+            && glob_pattern.matches(file)
+        {
+            return true;
         }
     }
     false
@@ -433,12 +433,12 @@ fn radar_snapshot(opts: RunOpts) {
     // Check if last *snapshot* (not just any note) is recent enough (< 30 min)
     if let Ok(Some(log)) = intent::load_intent_log(&git_root) {
         let last_snapshot = log.notes.iter().rev().find(|n| n.snapshot_hash.is_some());
-        if let Some(note) = last_snapshot {
-            if let Ok(ts) = chrono::DateTime::parse_from_rfc3339(&note.timestamp) {
-                let age = Utc::now().signed_duration_since(ts);
-                if age.num_minutes() < 30 {
-                    return; // Recent snapshot exists, skip
-                }
+        if let Some(note) = last_snapshot
+            && let Ok(ts) = chrono::DateTime::parse_from_rfc3339(&note.timestamp)
+        {
+            let age = Utc::now().signed_duration_since(ts);
+            if age.num_minutes() < 30 {
+                return; // Recent snapshot exists, skip
             }
         }
     }
@@ -483,9 +483,9 @@ fn print_branch_overlap(overlap: &BranchOverlap) {
     let file_count = overlap.overlapping_files.len();
     for (i, file_overlap) in overlap.overlapping_files.iter().enumerate() {
         let connector = if i == file_count - 1 {
-            "└──"
+            output::glyph("└──", "`--")
         } else {
-            "├──"
+            output::glyph("├──", "|--")
         };
         let indicator = match &file_overlap.overlap_kind {
             OverlapKind::LineOverlap { .. } => "[!!] LINE OVERLAP".red().bold().to_string(),
@@ -581,8 +581,12 @@ pub fn check_before_commit(config: &Config, opts: RunOpts) -> Result<bool> {
                 OverlapKind::SameFile => "[!]",
             };
             println!(
-                "  {} {} — @{} on {}",
-                indicator, file_overlap.file_path, overlap.author, overlap.branch_name
+                "  {} {} {} @{} on {}",
+                indicator,
+                file_overlap.file_path,
+                output::glyph("—", "-"),
+                overlap.author,
+                overlap.branch_name
             );
         }
     }
@@ -592,14 +596,11 @@ pub fn check_before_commit(config: &Config, opts: RunOpts) -> Result<bool> {
             println!("{}", "  Consider coordinating before pushing.\n".dimmed());
             Ok(true)
         }
-        RadarOnCommit::Confirm => {
-            let proceed =
-                dialoguer::Confirm::with_theme(&dialoguer::theme::ColorfulTheme::default())
-                    .with_prompt("Overlapping work detected. Continue with commit?")
-                    .default(true)
-                    .interact()?;
-            Ok(proceed)
-        }
+        RadarOnCommit::Confirm => prompt::confirm(
+            "Overlapping work detected. Continue with commit?",
+            Some(true),
+            opts,
+        ),
         RadarOnCommit::Off => Ok(true),
     }
 }
@@ -1,8 +1,7 @@
 use crate::git::RunOpts;
-use crate::{commands, git, intent};
+use crate::{commands, git, intent, prompt};
 use anyhow::{Context, Result};
 use colored::Colorize;
-use dialoguer::{Confirm, theme::ColorfulTheme};
 use std::path::Path;
 
 /// A single recoverable snapshot entry.
@@ -60,15 +59,14 @@ pub fn handle_recover_list(git_root: &Path, current_branch: &str) -> Result<()>
         branch: Some(ref log_branch),
         ..
     }) = log
+        && log_branch != current_branch
     {
-        if log_branch != current_branch {
-            intent::warn_stale(log_branch, current_branch);
-            println!();
-        }
+        intent::warn_stale(log_branch, current_branch);
+        println!();
     }
 
     println!("{}", "Available WIP snapshots:".blue().bold());
-    println!("  {:<5} {:<22} {:<42} {}", "#", "Timestamp", "Note", "Hash");
+    println!("  {:<5} {:<22} {:<42} Hash", "#", "Timestamp", "Note");
     println!("  {}", "-".repeat(85));
 
     for entry in &entries {
@@ -143,10 +141,7 @@ pub fn handle_recover_apply(git_root: &Path, selector: &str, opts: RunOpts) -> R
             format!("[DRY RUN] Would run: git stash apply {}", hash).yellow()
         );
     } else {
-        let confirmed = Confirm::with_theme(&ColorfulTheme::default())
-            .with_prompt("Apply snapshot?")
-            .default(true)
-            .interact()?;
+        let confirmed = prompt::confirm("Apply snapshot?", Some(true), opts)?;
 
         if !confirmed {
             println!("{}", "Recover aborted.".yellow());
@@ -0,0 +1,204 @@
+//! JSON over HTTP, for the forges tbdflow talks to without a CLI (Gitea,
+//! Forgejo and Bitbucket Cloud). Requests behave like `gh` calls:
+//! `--verbose` prints each one, `--dry-run` prints it instead of sending it,
+//! and `git.timeout_seconds` bounds it.
+
+use crate::git::RunOpts;
+use anyhow::{Context, Result, anyhow, bail};
+use colored::Colorize;
+use reqwest::Method;
+use reqwest::blocking::Client;
+use serde_json::Value;
+use std::time::Duration;
+
+/// How a request proves who sends it.
+#[derive(Debug, Clone, Copy)]
+pub enum Auth<'a> {
+    /// `Authorization: token <token>`, as Gitea takes it.
+    Token(&'a str),
+    /// HTTP basic auth, e.g. a Bitbucket username and app password.
+    Basic(&'a str, &'a str),
+}
+
+/// The web root (for http(s) remotes), owner and name of the repository
+/// `url` points at. Servers installed under a path keep it in the root:
+/// `https://example.com/git/team/app` is `team/app` on `https://example.com/git`.
+pub fn split_remote(url: &str) -> Option<(Option<String>, String, String)> {
+    let url = url.trim_end_matches('/').trim_end_matches(".git");
+    let (web, path) = match url.split_once("://") {
+        Some((scheme, rest)) => {
+            let (authority, path) = rest.split_once('/')?;
+            let host = authority.rsplit('@').next()?;
+            let web = matches!(scheme, "http" | "https").then(|| format!("{}://{}", scheme, host));
+            (web, path)
+        }
+        None => {
+            let (_, path) = url.split_once(':')?;
+            (None, path)
+        }
+    };
+    let mut segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let repo = segments.pop()?.to_string();
+    let owner = segments.pop()?.to_string();
+    let web = web.map(|root| match segments.is_empty() {
+        true => root,
+        false => format!("{}/{}", root, segments.join("/")),
+    });
+    Some((web, owner, repo))
+}
+
+/// Sends one request to `{api}{path}` and returns the JSON it answered with
+/// (`Null` for an empty body, and for everything under `--dry-run`).
+/// `service` names the forge in errors.
+pub fn call(
+    service: &str,
+    api: &str,
+    method: Method,
+    path: &str,
+    auth: Option<Auth>,
+    body: Option<&Value>,
+    opts: RunOpts,
+) -> Result<Value> {
+    let url = format!("{}{}", api, path);
+    if opts.dry_run {
+        println!(
+            "{}",
+            "[DRY RUN] Request would be sent but no changes made".yellow()
+        );
+        println!("{} {}", method, url);
+        println!();
+        return Ok(Value::Null);
+    }
+    if opts.verbose {
+        println!("{} {} {}", "[RUNNING] ".cyan(), method, url);
+    }
+
+    let mut request = client(opts)?
+        .request(method.clone(), &url)
+        .header("Accept", "application/json");
+    request = match auth {
+        Some(Auth::Token(token)) => request.header("Authorization", format!("token {}", token)),
+        Some(Auth::Basic(user, password)) => request.basic_auth(user, Some(password)),
+        None => request,
+    };
+    if let Some(body) = body {
+        request = request.json(body);
+    }
+    let response = request
+        .send()
+        .map_err(|e| send_error(e, &format!("{} {}", method, path), api, opts))?;
+    let status = response.status();
+    let text = response.text().unwrap_or_default();
+    if !status.is_success() {
+        bail!(
+            "{} '{} {}' failed ({}): {}",
+            service,
+            method,
+            path,
+            status.as_u16(),
+            error_message(&text)
+        );
+    }
+    if text.trim().is_empty() {
+        return Ok(Value::Null);
+    }
+    serde_json::from_str(&text).with_context(|| format!("Unexpected answer to {} {}", method, path))
+}
+
+/// A client bounded by `git.timeout_seconds`.
+fn client(opts: RunOpts) -> Result<Client> {
+    let mut client = Client::builder();
+    if let Some(secs) = opts.timeout_secs {
+        client = client.timeout(Duration::from_secs(secs));
+    }
+    Ok(client.build()?)
+}
+
+/// Why `what` could not be sent to `server`, pointing at the timeout when it ran out.
+fn send_error(e: reqwest::Error, what: &str, server: &str, opts: RunOpts) -> anyhow::Error {
+    match opts.timeout_secs {
+        Some(secs) if e.is_timeout() => anyhow!(
+            "'{}' timed out after {}s. Check your network, or raise git.timeout_seconds in .tbdflow.yml.",
+            what,
+            secs
+        ),
+        _ => anyhow!(e).context(format!("Failed to reach {}", server)),
+    }
+}
+
+/// Downloads `url` as text, failing on HTTP errors. It only reads, so it
+/// runs under `--dry-run` too.
+pub fn get_text(url: &str, opts: RunOpts) -> Result<String> {
+    if opts.verbose {
+        println!("{} GET {}", "[RUNNING] ".cyan(), url);
+    }
+    let response = client(opts)?
+        .get(url)
+        .send()
+        .map_err(|e| send_error(e, &format!("GET {}", url), url, opts))?;
+    let status = response.status();
+    if !status.is_success() {
+        bail!("Failed to fetch {}: HTTP {}", url, status.as_u16());
+    }
+    response
+        .text()
+        .with_context(|| format!("Failed to read {}", url))
+}
+
+/// The message in an error answer: Gitea's `message`, Bitbucket's
+/// `error.message`, or else the answer itself.
+fn error_message(text: &str) -> String {
+    let parsed: Value = serde_json::from_str(text).unwrap_or(Value::Null);
+    parsed["message"]
+        .as_str()
+        .or_else(|| parsed["error"]["message"].as_str())
+        .map_or_else(|| text.to_string(), str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remotes_split_into_server_owner_and_repo() {
+        let split = |url: &str| split_remote(url).unwrap();
+        assert_eq!(
+            split("https://git.example.com/team/app.git"),
+            (
+                Some("https://git.example.com".to_string()),
+                "team".to_string(),
+                "app".to_string()
+            )
+        );
+        assert_eq!(
+            split("http://user@example.com:3000/git/team/app"),
+            (
+                Some("http://example.com:3000/git".to_string()),
+                "team".to_string(),
+                "app".to_string()
+            )
+        );
+        assert_eq!(
+            split("git@git.example.com:team/app.git"),
+            (None, "team".to_string(), "app".to_string())
+        );
+        assert_eq!(
+            split("ssh://git@git.example.com:2222/team/app"),
+            (None, "team".to_string(), "app".to_string())
+        );
+        assert_eq!(split_remote("https://git.example.com/app"), None);
+    }
+
+    #[test]
+    fn error_messages_are_read_from_either_forge() {
+        assert_eq!(
+            error_message(r#"{"message": "token is required"}"#),
+            "token is required"
+        );
+        assert_eq!(
+            error_message(r#"{"type": "error", "error": {"message": "Repository not found"}}"#),
+            "Repository not found"
+        );
+        assert_eq!(error_message("Bad Gateway"), "Bad Gateway");
+    }
+}
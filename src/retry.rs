@@ -0,0 +1,160 @@
+//! `tbdflow retry`: picks a commit up again at the step that failed, so a
+//! network blip during the push or a `gh` call does not mean going through
+//! the DoD checklist and the message again.
+
+use crate::commit;
+use crate::config::Config;
+use crate::git::{self, RunOpts};
+use crate::state::{self, State};
+use anyhow::{Result, bail};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The steps of `tbdflow commit` after the message is settled, in order.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommitStep {
+    /// Pull trunk (on trunk) and create the commit.
+    Commit,
+    /// Push the commit.
+    Push,
+    /// Request a review if the review rules match.
+    Review,
+    /// Link the commit to the reviews it fixes forward.
+    LinkFixes,
+    /// Create and push the tag.
+    Tag,
+}
+
+impl CommitStep {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommitStep::Commit => "commit",
+            CommitStep::Push => "push",
+            CommitStep::Review => "review",
+            CommitStep::LinkFixes => "link-fixes",
+            CommitStep::Tag => "tag",
+        }
+    }
+}
+
+/// A commit that stopped part-way, with what it needs to finish.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingCommit {
+    pub branch: String,
+    /// The first step that has not completed.
+    pub step: CommitStep,
+    /// The full commit message, DoD footer included.
+    pub message: String,
+    /// Its first line.
+    pub header: String,
+    pub r#type: String,
+    pub breaking: bool,
+    pub scope: Option<String>,
+    pub include_projects: bool,
+    /// Reviews named in `Fixes-Review` trailers.
+    pub fixes: Vec<String>,
+    pub tag: Option<String>,
+    pub dod_skipped: bool,
+    /// Clear the intent log once the commit is on trunk.
+    pub clear_intent: bool,
+    /// The commit, once made.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Why the last attempt stopped.
+    #[serde(default)]
+    pub error: String,
+}
+
+impl State for PendingCommit {
+    const FILE: &'static str = "retry.json";
+}
+
+pub fn load(git_dir: &Path) -> Result<Option<PendingCommit>> {
+    state::load(git_dir)
+}
+
+pub fn save(git_dir: &Path, pending: &PendingCommit) -> Result<()> {
+    state::save(git_dir, pending)
+}
+
+pub fn clear(git_dir: &Path) -> Result<()> {
+    state::remove::<PendingCommit>(git_dir)?;
+    Ok(())
+}
+
+/// Resumes the commit that failed last, or with `discard`, forgets it.
+pub fn handle_retry(config: &Config, discard: bool, opts: RunOpts) -> Result<()> {
+    let quiet = opts.for_reads();
+    let git_dir = PathBuf::from(git::get_git_dir(quiet)?);
+    let Some(pending) = load(&git_dir)? else {
+        println!("{}", "Nothing to retry.".green());
+        return Ok(());
+    };
+
+    if discard {
+        if opts.dry_run {
+            println!(
+                "{}",
+                format!("[DRY RUN] Would forget '{}'", pending.header).yellow()
+            );
+        } else {
+            clear(&git_dir)?;
+            println!(
+                "{}",
+                format!("Forgot '{}'. Nothing was undone.", pending.header).yellow()
+            );
+        }
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "--- Retrying '{}' from the {} step ---",
+            pending.header,
+            pending.step.as_str()
+        )
+        .blue()
+    );
+    if !pending.error.is_empty() {
+        println!("{}", format!("Last attempt: {}", pending.error).dimmed());
+    }
+
+    let branch = git::get_current_branch(quiet)?;
+    if branch != pending.branch {
+        bail!(
+            "The commit was on '{}', but you are on '{}'. Switch back to retry it, or run 'tbdflow retry --discard'.",
+            pending.branch,
+            branch
+        );
+    }
+    if let Some(commit) = &pending.commit
+        && git::get_head_commit_hash(quiet)? != *commit
+    {
+        bail!(
+            "HEAD has moved since {} was committed, so it cannot be finished. Run 'tbdflow retry --discard'.",
+            git::short_hash(commit)
+        );
+    }
+    if opts.dry_run {
+        println!(
+            "{}",
+            format!(
+                "[DRY RUN] Would continue from the {} step",
+                pending.step.as_str()
+            )
+            .yellow()
+        );
+        return Ok(());
+    }
+    if pending.step == CommitStep::Commit {
+        git::stage_scoped_changes(config, pending.include_projects, opts)?;
+        if !git::has_staged_changes(opts)? {
+            clear(&git_dir)?;
+            bail!("There is nothing left to commit, so there is nothing to retry.");
+        }
+    }
+    commit::finish_commit(config, pending, opts)
+}
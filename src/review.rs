@@ -1,68 +1,243 @@
-use crate::config::{Config, ReviewLabelsConfig, ReviewStrategy};
-use crate::git::{self, RunOpts};
+use crate::bitbucket::Bitbucket;
+use crate::changelog;
+use crate::commands::{
+    ReviewCommentResponse, ReviewConcernResponse, ReviewCountResponse, ReviewDigestCommitResponse,
+    ReviewDigestResponse, ReviewDigestSectionResponse, ReviewListResponse, ReviewNoteResponse,
+    ReviewShowResponse, ReviewStatsResponse, ReviewStatusResponse, TbdResponse,
+};
+use crate::config::{
+    ChangelogConfig, CompleteReview, Config, ReviewConfig, ReviewLabelsConfig, ReviewStrategy,
+};
+use crate::gh;
+use crate::git::{self, RunOpts, short_hash};
+use crate::gitea::Gitea;
+use crate::history::{self, ParsedCommit};
+use crate::i18n::{self, Msg};
+use crate::journal;
+use crate::metrics;
+use crate::output;
+use crate::postmortem::format_duration;
+use crate::review_ledger;
+use crate::review_notes::{self, NoteAction, NoteEvent, ReviewState};
+use crate::review_queue::{self, Action};
+use crate::state::{self, State};
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use colored::Colorize;
+use git_conventional::Commit;
 use glob::Pattern;
-use serde_json::Value;
-use std::process::Command;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Every approval comment starts with this, so approvals can be counted.
+const APPROVAL_COMMENT: &str = "Approved via `tbdflow review --approve`";
+
+/// How urgently a concern needs to be fixed forward.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ConcernSeverity {
+    Minor,
+    Major,
+    Critical,
+}
+
+impl ConcernSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConcernSeverity::Minor => "minor",
+            ConcernSeverity::Major => "major",
+            ConcernSeverity::Critical => "critical",
+        }
+    }
+
+    pub fn label<'a>(&self, labels: &'a ReviewLabelsConfig) -> &'a str {
+        match self {
+            ConcernSeverity::Minor => &labels.minor,
+            ConcernSeverity::Major => &labels.major,
+            ConcernSeverity::Critical => &labels.critical,
+        }
+    }
+}
+
+/// Commit status state and description template for a concern. Critical
+/// concerns always fail the status; otherwise `concern_blocks_status` decides.
+fn concern_commit_status(
+    severity: Option<ConcernSeverity>,
+    review: &ReviewConfig,
+) -> (&'static str, &str) {
+    match severity {
+        Some(ConcernSeverity::Critical) => ("failure", &review.status.critical_description),
+        _ if review.concern_blocks_status => ("failure", &review.status.failure_description),
+        _ => ("pending", &review.status.pending_description),
+    }
+}
+
+/// GitHub rejects status descriptions longer than this.
+const MAX_STATUS_DESCRIPTION: usize = 140;
+
+/// Values substituted into `review.status` templates.
+struct StatusVars<'a> {
+    message: &'a str,
+    severity: Option<ConcernSeverity>,
+    sha: &'a str,
+    issue_url: &'a str,
+}
+
+fn render_status_template(template: &str, vars: &StatusVars) -> String {
+    template
+        .replace("{{message}}", vars.message)
+        .replace("{{severity}}", vars.severity.map_or("", |s| s.as_str()))
+        .replace("{{sha}}", vars.sha)
+        .replace("{{short_sha}}", short_hash(vars.sha))
+        .replace("{{issue_url}}", vars.issue_url)
+}
+
+fn truncate_description(description: String) -> String {
+    if description.chars().count() <= MAX_STATUS_DESCRIPTION {
+        return description;
+    }
+    let mut truncated: String = description
+        .chars()
+        .take(MAX_STATUS_DESCRIPTION - 3)
+        .collect();
+    truncated.push_str("...");
+    truncated
+}
+
+/// Trailer that asks for a commit to be left out of auto-triggered review.
+pub const SKIP_REVIEW_TRAILER: &str = "Review: skip";
+
+pub fn has_skip_review_trailer(message: &str) -> bool {
+    message
+        .lines()
+        .any(|l| l.trim().eq_ignore_ascii_case(SKIP_REVIEW_TRAILER))
+}
+
+/// Trailer key linking a fix-forward commit to the review it addresses.
+pub const FIXES_REVIEW_TRAILER_KEY: &str = "Fixes-Review";
+
+pub fn fixes_review_trailer(review_hash: &str) -> String {
+    format!("{}: {}", FIXES_REVIEW_TRAILER_KEY, short_hash(review_hash))
+}
+
+/// Review hashes named by `Fixes-Review:` trailers.
+pub fn fixed_reviews(trailers: &[String]) -> Vec<String> {
+    trailers
+        .iter()
+        .filter_map(|t| t.split_once(':'))
+        .filter(|(key, _)| key.trim().eq_ignore_ascii_case(FIXES_REVIEW_TRAILER_KEY))
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// Returns why a `Review: skip` request cannot be honoured, or `None` if the
+/// commit may skip review. Breaking changes are never exempt.
+pub fn review_exemption_refusal(
+    config: &Config,
+    commit_type: &str,
+    breaking: bool,
+) -> Option<String> {
+    if breaking {
+        return Some("breaking changes are always reviewed".to_string());
+    }
+    if !config.review.exempt_types.iter().any(|t| t == commit_type) {
+        return Some(format!(
+            "'{}' is not in review.exempt_types ({})",
+            commit_type,
+            config.review.exempt_types.join(", ")
+        ));
+    }
+    None
+}
 
-fn short_hash(hash: &str) -> &str {
-    &hash[..7.min(hash.len())]
+/// True for `type!:` subjects and `BREAKING CHANGE:` footers.
+pub fn is_breaking_message(message: &str) -> bool {
+    match Commit::parse(message.trim()) {
+        Ok(commit) => commit.breaking(),
+        Err(_) => message
+            .lines()
+            .any(|l| l.starts_with("BREAKING CHANGE:") || l.starts_with("BREAKING-CHANGE:")),
+    }
 }
 
-/// Returns true if any review rule patterns match the files changed in this commit.
+/// Returns true if the commit is breaking, larger than `large_commit_lines`,
+/// or touches files matching a review rule pattern.
 pub fn should_auto_trigger_review(
     config: &Config,
     commit_hash: &str,
     opts: RunOpts,
 ) -> Result<bool> {
-    if !config.review.enabled || config.review.rules.is_empty() {
+    if !config.review.enabled {
+        return Ok(false);
+    }
+
+    // Breaking and oversized commits are the ones most in need of course correction.
+    let message = git::get_commit_full_message(commit_hash, opts)?;
+    if is_breaking_message(&message) {
+        if opts.verbose {
+            println!("{} Auto-trigger: breaking change", "[REVIEW]".magenta());
+        }
+        return Ok(true);
+    }
+
+    let threshold = config.review.large_commit_lines;
+    if threshold > 0 {
+        let lines = git::get_commit_line_count(commit_hash, opts)?;
+        if lines > threshold {
+            if opts.verbose {
+                println!(
+                    "{} Auto-trigger: {} lines changed (threshold {})",
+                    "[REVIEW]".magenta(),
+                    lines,
+                    threshold
+                );
+            }
+            return Ok(true);
+        }
+    }
+
+    if config.review.rules.is_empty() {
         return Ok(false);
     }
 
     let touched_files = git::get_changed_files(commit_hash, opts)?;
 
     for rule in &config.review.rules {
-        if let Ok(pattern) = Pattern::new(&rule.pattern) {
-            if touched_files.iter().any(|f| pattern.matches(f)) {
-                if opts.verbose {
-                    println!(
-                        "{} Auto-trigger: files match rule pattern '{}'",
-                        "[REVIEW]".magenta(),
-                        rule.pattern
-                    );
-                }
-                return Ok(true);
+        if let Ok(pattern) = Pattern::new(&rule.pattern)
+            && touched_files.iter().any(|f| pattern.matches(f))
+        {
+            if opts.verbose {
+                println!(
+                    "{} Auto-trigger: files match rule pattern '{}'",
+                    "[REVIEW]".magenta(),
+                    rule.pattern
+                );
             }
+            return Ok(true);
         }
     }
 
     Ok(false)
 }
 
-pub fn trigger_review(
+/// Reviewers for a change touching `files`: the override if given, else the
+/// reviewers of every matching rule, else the defaults. The flag is true when
+/// a targeted rule matched.
+fn reviewers_for_files(
     config: &Config,
+    files: &[String],
     reviewers_override: Option<&[String]>,
-    commit_hash: &str,
-    message: &str,
-    author: &str,
     opts: RunOpts,
-) -> Result<()> {
-    if !config.review.enabled {
-        if opts.verbose {
-            println!("{}", "Review system is disabled in config.".dimmed());
-        }
-        return Ok(());
-    }
-
-    // Identify which rules apply based on touched files
-    let touched_files = git::get_changed_files(commit_hash, opts)?;
+) -> (Vec<String>, bool) {
     let mut applicable_reviewers: Vec<String> = Vec::new();
     let mut is_targeted = false;
 
     for rule in &config.review.rules {
         if let Ok(pattern) = Pattern::new(&rule.pattern) {
-            let matched = touched_files.iter().any(|f| pattern.matches(f));
+            let matched = files.iter().any(|f| pattern.matches(f));
             if matched {
                 if opts.verbose {
                     println!(
@@ -89,8 +264,29 @@ pub fn trigger_review(
 
     final_reviewers.sort();
     final_reviewers.dedup();
+    (final_reviewers, is_targeted)
+}
+
+pub fn trigger_review(
+    config: &Config,
+    reviewers_override: Option<&[String]>,
+    commit_hash: &str,
+    message: &str,
+    author: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    if !config.review.enabled {
+        if opts.verbose {
+            println!("{}", "Review system is disabled in config.".dimmed());
+        }
+        return Ok(());
+    }
+
+    let touched_files = git::get_changed_files(commit_hash, opts)?;
+    let (final_reviewers, is_targeted) =
+        reviewers_for_files(config, &touched_files, reviewers_override, opts);
 
-    println!("{}", "--- Triggering Non-blocking Review ---".blue());
+    println!("{}", i18n::t(Msg::TriggeringReview).blue());
     if is_targeted {
         println!("{} Review triggered by targeted file rules.", ">>".yellow());
     }
@@ -98,7 +294,7 @@ pub fn trigger_review(
     let short = short_hash(commit_hash);
     println!(
         "{} {} ({})",
-        "Review requested for:".green(),
+        i18n::t(Msg::ReviewRequestedFor).green(),
         message.bold(),
         short.dimmed()
     );
@@ -115,7 +311,7 @@ pub fn trigger_review(
     match &config.review.strategy {
         ReviewStrategy::GithubIssue => {
             create_github_issue(
-                &config.review.labels,
+                &config.review,
                 &final_reviewers,
                 commit_hash,
                 message,
@@ -126,11 +322,42 @@ pub fn trigger_review(
         ReviewStrategy::GithubWorkflow => {
             trigger_github_workflow(config, commit_hash, message, author, &final_reviewers, opts)?;
         }
-        ReviewStrategy::LogOnly => {
-            println!(
-                "{}",
-                "Review logged (no external system integration)".dimmed()
+        ReviewStrategy::Gitea => {
+            let gitea = Gitea::from_config(&config.review.gitea, opts)?;
+            let (title, body) = review_issue(
+                &config.review,
+                &final_reviewers,
+                commit_hash,
+                message,
+                author,
+                &gitea.repo_url(),
             );
+            open_gitea_review_issue(
+                &gitea,
+                &config.review,
+                &final_reviewers,
+                &title,
+                &body,
+                opts,
+            );
+        }
+        ReviewStrategy::Bitbucket => {
+            let bitbucket = Bitbucket::from_config(&config.review.bitbucket, opts)?;
+            let (title, body) = review_issue(
+                &config.review,
+                &final_reviewers,
+                commit_hash,
+                message,
+                author,
+                &bitbucket.repo_url(),
+            );
+            open_bitbucket_review(&bitbucket, config, &[commit_hash], &title, &body, opts);
+        }
+        ReviewStrategy::GitNotes | ReviewStrategy::InRepo => {
+            request_recorded_review(config, &[commit_hash], &final_reviewers, opts)?;
+        }
+        ReviewStrategy::LogOnly => {
+            println!("{}", i18n::t(Msg::ReviewLogged).dimmed());
         }
     }
 
@@ -145,7 +372,7 @@ fn trigger_github_workflow(
     reviewers: &[String],
     opts: RunOpts,
 ) -> Result<()> {
-    if !is_gh_cli_available() {
+    if !gh::is_available() {
         println!(
             "{}",
             "Warning: GitHub CLI (gh) not found. Install it to trigger workflows.".yellow()
@@ -177,8 +404,8 @@ fn trigger_github_workflow(
     // Build workflow inputs as JSON
     let reviewers_json = reviewers.join(",");
 
-    let output = Command::new("gh")
-        .args([
+    let output = gh::run(
+        &[
             "workflow",
             "run",
             workflow_name,
@@ -190,11 +417,12 @@ fn trigger_github_workflow(
             &format!("author={}", author),
             "-f",
             &format!("reviewers={}", reviewers_json),
-        ])
-        .output()
-        .context("Failed to trigger GitHub workflow")?;
+        ],
+        opts,
+    )
+    .context("Failed to trigger GitHub workflow")?;
 
-    if output.status.success() {
+    if output.success {
         println!(
             "{}",
             format!(
@@ -212,7 +440,7 @@ fn trigger_github_workflow(
             "   Check GitHub Actions for issue creation and status updates.".dimmed()
         );
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stderr = &output.stderr;
         if stderr.contains("could not find any workflows") {
             println!(
                 "{}",
@@ -233,7 +461,7 @@ fn trigger_github_workflow(
             );
             // Fallback to client-side issue creation
             create_github_issue(
-                &config.review.labels,
+                &config.review,
                 reviewers,
                 commit_hash,
                 message,
@@ -251,61 +479,170 @@ fn trigger_github_workflow(
     Ok(())
 }
 
+/// The "**Reviewers:**" line for a review issue, or `None` when nobody is
+/// to be mentioned. Assignment alone does not notify everyone in every org.
+fn review_mentions(review: &ReviewConfig, reviewers: &[String]) -> Option<String> {
+    let mut handles: Vec<String> = Vec::new();
+    let people = if review.mention_reviewers {
+        reviewers
+    } else {
+        &[]
+    };
+    for handle in people.iter().chain(&review.mention_teams) {
+        let handle = mention(handle);
+        if !handles.contains(&handle) {
+            handles.push(handle);
+        }
+    }
+    (!handles.is_empty()).then(|| format!("**Reviewers:** {}\n", handles.join(" ")))
+}
+
+/// Reviewer guidance shared by every review issue body.
+const REVIEW_GUIDANCE: &str = "\
+> In Trunk-Based Development, this code is already in the trunk.\n\
+> Your goal is **Course Correction** and **Knowledge Sharing**, not gatekeeping.\n\n\
+### What to Look For\n\n\
+| Focus | Question |\n\
+|-------|----------|\n\
+| **Design & Intent** | Does the implementation align with our architectural patterns? |\n\
+| **Logic & Edge Cases** | Are there logical flaws or unhappy paths that tests might miss? |\n\
+| **Readability** | Are names descriptive? (Code as Documentation) |\n\
+| **Simplification** | Can this be done with less code or lower complexity? |\n\n\
+### How to Comment\n\n\
+- **Questions > Commands**: _\"Could we use the existing helper here?\"_ instead of _\"Change this.\"_\n\
+- **Praise**: If you see something clever or clean, say so! NBR boosts team morale.\n\
+- **Nitpicking**: Label minor style issues as `(nit)` so the author knows they're optional.\n\n";
+
+/// A commit reference for an issue body, linked when the remote is known.
+/// The web page of a commit. Bitbucket Cloud spells the path `commits`.
+fn commit_url(repo_url: &str, commit_hash: &str) -> String {
+    let segment = match repo_url.starts_with("https://bitbucket.org/") {
+        true => "commits",
+        false => "commit",
+    };
+    format!("{}/{}/{}", repo_url, segment, commit_hash)
+}
+
+fn commit_link(repo_url: &str, commit_hash: &str) -> String {
+    if repo_url.is_empty() {
+        format!("`{}`", commit_hash)
+    } else {
+        format!(
+            "[`{}`]({})",
+            short_hash(commit_hash),
+            commit_url(repo_url, commit_hash)
+        )
+    }
+}
+
 fn create_github_issue(
-    labels: &ReviewLabelsConfig,
+    review: &ReviewConfig,
     reviewers: &[String],
     commit_hash: &str,
     message: &str,
     author: &str,
     opts: RunOpts,
 ) -> Result<()> {
-    let short = short_hash(commit_hash);
-
-    // Check if gh CLI is available
-    if !is_gh_cli_available() {
-        println!(
-            "{}",
-            "Warning: GitHub CLI (gh) not found. Install it to enable GitHub issue creation."
-                .yellow()
-        );
-        println!(
-            "{}",
-            "Install: https://cli.github.com/ or 'brew install gh'".dimmed()
-        );
-        return Ok(());
-    }
-
-    // Ensure all review labels exist (create if missing)
-    ensure_review_labels_exist(labels, opts);
-
     // Get the repository URL for commit links
     let repo_url = git::get_remote_url(opts).unwrap_or_default();
-    let commit_url = if repo_url.is_empty() {
-        format!("`{}`", commit_hash)
-    } else {
-        format!("[`{}`]({}/commit/{})", short, repo_url, commit_hash)
-    };
+    let (title, body) = review_issue(review, reviewers, commit_hash, message, author, &repo_url);
+    open_review_issue(review, reviewers, &title, &body, opts)
+}
+
+/// Title and body of the review issue for one commit.
+fn review_issue(
+    review: &ReviewConfig,
+    reviewers: &[String],
+    commit_hash: &str,
+    message: &str,
+    author: &str,
+    repo_url: &str,
+) -> (String, String) {
+    let short = short_hash(commit_hash);
+    let commit_url = commit_link(repo_url, commit_hash);
 
     let title = format!("[Review] {} ({})", message, short);
     let body = format!(
         "## Non-blocking Review Request\n\n\
         **Commit:** {}\n\
         **Author:** {}\n\
-        **Message:** {}\n\n\
+        **Message:** {}\n\
+        {}\n\
+        ---\n\n\
+        {}\
+        ### Concerns\n\n\
+        _No concerns raised yet._\n\n\
+        ---\n\n\
+        To approve via CLI:\n\
+        ```\n\
+        tbdflow review --approve {}\n\
+        ```\n\n\
+        To raise a concern:\n\
+        ```\n\
+        tbdflow review --concern {} -m \"Your concern here\"\n\
+        ```",
+        commit_url,
+        author,
+        message,
+        review_mentions(review, reviewers).unwrap_or_default(),
+        REVIEW_GUIDANCE,
+        short,
+        short
+    );
+    (title, body)
+}
+
+/// Title and body of a review issue covering several commits, oldest first.
+/// Each commit gets its own checklist section. The title ends with the first
+/// and last short hashes, so `--approve` and `--concern` find the issue by either.
+pub fn batch_issue(
+    range: &str,
+    commits: &[ParsedCommit],
+    author: &str,
+    repo_url: &str,
+    mentions: &str,
+) -> (String, String) {
+    let first = commits.first().map_or("", |c| c.short_hash());
+    let last = commits.last().map_or("", |c| c.short_hash());
+    let title = format!(
+        "[Review] {} ({} commits, {}..{})",
+        range,
+        commits.len(),
+        first,
+        last
+    );
+
+    let mut sections = String::new();
+    for commit in commits {
+        sections.push_str(&format!(
+            "#### {} {}\n\n- [ ] Reviewed\n",
+            commit_link(repo_url, &commit.hash),
+            commit.subject
+        ));
+        if !commit.files.is_empty() {
+            sections.push_str(&format!(
+                "\n<details><summary>{} file(s)</summary>\n\n{}\n</details>\n",
+                commit.files.len(),
+                commit
+                    .files
+                    .iter()
+                    .map(|f| format!("- `{}`", f))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ));
+        }
+        sections.push('\n');
+    }
+
+    let body = format!(
+        "## Non-blocking Batch Review Request\n\n\
+        **Range:** `{}` ({} commits)\n\
+        **Author:** {}\n\
+        {}\n\
         ---\n\n\
-        > In Trunk-Based Development, this code is already in the trunk.\n\
-        > Your goal is **Course Correction** and **Knowledge Sharing**, not gatekeeping.\n\n\
-        ### What to Look For\n\n\
-        | Focus | Question |\n\
-        |-------|----------|\n\
-        | **Design & Intent** | Does the implementation align with our architectural patterns? |\n\
-        | **Logic & Edge Cases** | Are there logical flaws or unhappy paths that tests might miss? |\n\
-        | **Readability** | Are names descriptive? (Code as Documentation) |\n\
-        | **Simplification** | Can this be done with less code or lower complexity? |\n\n\
-        ### How to Comment\n\n\
-        - **Questions > Commands**: _\"Could we use the existing helper here?\"_ instead of _\"Change this.\"_\n\
-        - **Praise**: If you see something clever or clean, say so! NBR boosts team morale.\n\
-        - **Nitpicking**: Label minor style issues as `(nit)` so the author knows they're optional.\n\n\
+        {}\
+        ### Commits\n\n\
+        {}\
         ### Concerns\n\n\
         _No concerns raised yet._\n\n\
         ---\n\n\
@@ -317,13 +654,49 @@ fn create_github_issue(
         ```\n\
         tbdflow review --concern {} -m \"Your concern here\"\n\
         ```",
-        commit_url, author, message, short, short
+        range,
+        commits.len(),
+        author,
+        mentions,
+        REVIEW_GUIDANCE,
+        sections,
+        last,
+        last
     );
+    (title, body)
+}
+
+/// Creates a review issue with the pending label, assigned to `reviewers`.
+fn open_review_issue(
+    review: &ReviewConfig,
+    reviewers: &[String],
+    title: &str,
+    body: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    let labels = &review.labels;
+
+    // Check if gh CLI is available
+    if !gh::is_available() {
+        println!(
+            "{}",
+            "Warning: GitHub CLI (gh) not found. Install it to enable GitHub issue creation."
+                .yellow()
+        );
+        println!(
+            "{}",
+            "Install: https://cli.github.com/ or 'brew install gh'".dimmed()
+        );
+        return Ok(());
+    }
+
+    // Ensure all review labels exist (create if missing)
+    ensure_review_labels_exist(labels, opts);
 
-    let mut args = vec!["issue", "create", "--title", &title, "--body", &body];
+    let mut args = vec!["issue", "create", "--title", title, "--body", body];
 
     // Add the pending label
-    if label_exists(&labels.pending) {
+    if label_exists(&labels.pending, opts) {
         args.push("--label");
         args.push(&labels.pending);
     }
@@ -336,20 +709,13 @@ fn create_github_issue(
         args.push(&assignees_str);
     }
 
-    if opts.verbose {
-        println!("{} gh {}", "[RUNNING]".cyan(), args.join(" "));
-    }
-
-    let output = Command::new("gh")
-        .args(&args)
-        .output()
-        .context("Failed to execute 'gh' CLI")?;
+    let output = gh::run(&args, opts).context("Failed to execute 'gh' CLI")?;
 
-    if output.status.success() {
-        let issue_url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.success {
+        let issue_url = output.stdout;
         println!("{} {}", "Review issue created:".green(), issue_url);
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stderr = &output.stderr;
         println!(
             "{}",
             format!("Warning: Failed to create GitHub issue: {}", stderr).yellow()
@@ -359,20 +725,22 @@ fn create_github_issue(
     Ok(())
 }
 
-fn label_exists(label_name: &str) -> bool {
-    Command::new("gh")
-        .args(["label", "list", "--search", label_name, "--json", "name"])
-        .output()
-        .map(|o| {
-            o.status.success()
-                && String::from_utf8_lossy(&o.stdout)
-                    .contains(&format!("\"name\":\"{}\"", label_name))
-        })
-        .unwrap_or(false)
+fn label_exists(label_name: &str, opts: RunOpts) -> bool {
+    gh::run(
+        &["label", "list", "--search", label_name, "--json", "name"],
+        opts,
+    )
+    .map(|o| {
+        o.success
+            && o.stdout
+                .as_str()
+                .contains(&format!("\"name\":\"{}\"", label_name))
+    })
+    .unwrap_or(false)
 }
 
 fn ensure_label_exists(label_name: &str, description: &str, color: &str, opts: RunOpts) {
-    if label_exists(label_name) {
+    if label_exists(label_name, opts) {
         return;
     }
 
@@ -380,8 +748,8 @@ fn ensure_label_exists(label_name: &str, description: &str, color: &str, opts: R
         println!("{} Creating '{}' label...", "[INFO]".cyan(), label_name);
     }
 
-    let result = Command::new("gh")
-        .args([
+    let result = gh::run(
+        &[
             "label",
             "create",
             label_name,
@@ -389,14 +757,13 @@ fn ensure_label_exists(label_name: &str, description: &str, color: &str, opts: R
             description,
             "--color",
             color,
-        ])
-        .output();
+        ],
+        opts,
+    );
 
     match result {
-        Ok(output) if output.status.success() => {
-            if opts.verbose {
-                println!("{} Created '{}' label", "[INFO]".cyan(), label_name);
-            }
+        Ok(output) if output.success && opts.verbose => {
+            println!("{} Created '{}' label", "[INFO]".cyan(), label_name);
         }
         _ => {
             // Silently continue - label creation may fail due to permissions
@@ -405,787 +772,5309 @@ fn ensure_label_exists(label_name: &str, description: &str, color: &str, opts: R
     }
 }
 
-fn ensure_review_labels_exist(labels: &ReviewLabelsConfig, opts: RunOpts) {
-    ensure_label_exists(
-        &labels.pending,
-        "Review pending - awaiting attention",
-        "FBCA04", // Yellow
-        opts,
-    );
-    ensure_label_exists(
-        &labels.concern,
+/// Built-in review labels as (role, color, description).
+const DEFAULT_LABEL_STYLES: [(&str, &str, &str); 8] = [
+    ("pending", "FBCA04", "Review pending - awaiting attention"),
+    (
+        "concern",
+        "D93F0B",
         "Review concern raised - needs attention",
-        "D93F0B", // Red-orange
-        opts,
-    );
-    ensure_label_exists(
-        &labels.accepted,
-        "Review accepted/approved",
-        "0E8A16", // Green
-        opts,
-    );
-    ensure_label_exists(
-        &labels.dismissed,
-        "Review dismissed - won't fix",
-        "6A737D", // Gray
-        opts,
-    );
+    ),
+    ("accepted", "0E8A16", "Review accepted/approved"),
+    ("dismissed", "6A737D", "Review dismissed - won't fix"),
+    (
+        "minor",
+        "C5DEF5",
+        "Minor concern - fix forward when convenient",
+    ),
+    ("major", "E99695", "Major concern - fix forward soon"),
+    ("critical", "B60205", "Critical concern - fix forward now"),
+    ("escalated", "5319E7", "Review pending past its SLA"),
+];
+
+/// A review label as it should exist in the repository.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelSpec {
+    pub name: String,
+    /// Six hex digits without a leading '#', as `gh label` expects.
+    pub color: String,
+    pub description: String,
 }
 
-fn is_gh_cli_available() -> bool {
-    git::is_gh_cli_available()
+/// Accepts GitHub label colors: six hex digits, optionally prefixed with '#'.
+pub fn is_valid_hex_color(color: &str) -> bool {
+    let hex = color.strip_prefix('#').unwrap_or(color);
+    hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit())
 }
 
-pub fn handle_review_trigger(
-    config: &Config,
-    reviewers_override: Option<Vec<String>>,
-    commit_sha: Option<&str>,
-    opts: RunOpts,
-) -> Result<()> {
-    if !config.review.enabled {
-        println!(
-            "{}",
-            "Review system is not enabled. Add the following to your .tbdflow.yml:".yellow()
-        );
-        println!("\n  review:");
-        println!("    enabled: true");
-        println!("    strategy: github-issue");
-        println!("    default_reviewers:");
-        println!("      - teammate-username\n");
-        return Ok(());
+fn label_name_for_role<'a>(labels: &'a ReviewLabelsConfig, role: &str) -> Option<&'a str> {
+    match role {
+        "pending" => Some(&labels.pending),
+        "concern" => Some(&labels.concern),
+        "accepted" => Some(&labels.accepted),
+        "dismissed" => Some(&labels.dismissed),
+        "minor" => Some(&labels.minor),
+        "major" => Some(&labels.major),
+        "critical" => Some(&labels.critical),
+        "escalated" => Some(&labels.escalated),
+        _ => None,
     }
+}
 
-    let commit_hash = match commit_sha {
-        Some(sha) if !sha.is_empty() => {
-            // Resolve the provided SHA to a full hash
-            let full = git::resolve_commit_hash(sha, opts)?;
-            if opts.verbose {
-                println!(
-                    "{} Triggering review for commit {}",
-                    "[REVIEW]".magenta(),
-                    short_hash(&full)
-                );
-            }
-            full
+/// Resolves the configured review labels, validating colors and role names.
+pub fn label_taxonomy(labels: &ReviewLabelsConfig) -> Result<Vec<LabelSpec>> {
+    for (field, map) in [
+        ("colors", &labels.colors),
+        ("descriptions", &labels.descriptions),
+    ] {
+        if let Some(role) = map
+            .keys()
+            .find(|r| label_name_for_role(labels, r).is_none())
+        {
+            let roles: Vec<&str> = DEFAULT_LABEL_STYLES.iter().map(|(r, _, _)| *r).collect();
+            return Err(anyhow::anyhow!(
+                "Unknown review label role '{}' in review.labels.{}. Expected one of: {}.",
+                role,
+                field,
+                roles.join(", ")
+            ));
         }
-        _ => git::get_head_commit_hash(opts)?,
-    };
-    let message = git::get_commit_message(&commit_hash, opts)?;
-    let author = git::get_user_name(opts)?;
+    }
 
-    trigger_review(
-        config,
-        reviewers_override.as_deref(),
-        &commit_hash,
-        &message,
-        &author,
-        opts,
-    )
+    let mut specs = Vec::new();
+    for (role, default_color, default_description) in DEFAULT_LABEL_STYLES {
+        let color = labels
+            .colors
+            .get(role)
+            .map_or(default_color, String::as_str);
+        if !is_valid_hex_color(color) {
+            return Err(anyhow::anyhow!(
+                "Invalid color '{}' for review label '{}'. Use six hex digits, e.g. 'FBCA04'.",
+                color,
+                role
+            ));
+        }
+        specs.push(LabelSpec {
+            name: label_name_for_role(labels, role)
+                .unwrap_or(role)
+                .to_string(),
+            color: color.trim_start_matches('#').to_uppercase(),
+            description: labels
+                .descriptions
+                .get(role)
+                .map_or(default_description, String::as_str)
+                .to_string(),
+        });
+    }
+    Ok(specs)
 }
 
-pub fn handle_review_digest(config: &Config, since: &str, opts: RunOpts) -> Result<()> {
-    println!(
-        "{}",
-        format!("--- Trunk Evolution Digest (Since {}) ---", since).blue()
-    );
+/// What `review labels sync` needs to do for one label.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LabelChange {
+    Create(LabelSpec),
+    Update(LabelSpec),
+    Unchanged(String),
+}
+
+/// Compares the configured taxonomy with the labels that exist in the repository.
+/// Labels outside the taxonomy are left alone.
+pub fn plan_label_sync(wanted: &[LabelSpec], existing: &[LabelSpec]) -> Vec<LabelChange> {
+    wanted
+        .iter()
+        .map(|spec| match existing.iter().find(|e| e.name == spec.name) {
+            None => LabelChange::Create(spec.clone()),
+            Some(e)
+                if !e.color.eq_ignore_ascii_case(&spec.color)
+                    || e.description != spec.description =>
+            {
+                LabelChange::Update(spec.clone())
+            }
+            Some(_) => LabelChange::Unchanged(spec.name.clone()),
+        })
+        .collect()
+}
+
+fn parse_label_list(json: &str) -> Vec<LabelSpec> {
+    let parsed: Value = serde_json::from_str(json).unwrap_or(Value::Null);
+    parsed
+        .as_array()
+        .map(|labels| {
+            labels
+                .iter()
+                .filter_map(|l| {
+                    Some(LabelSpec {
+                        name: l["name"].as_str()?.to_string(),
+                        color: l["color"].as_str().unwrap_or_default().to_string(),
+                        description: l["description"].as_str().unwrap_or_default().to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn ensure_review_labels_exist(labels: &ReviewLabelsConfig, opts: RunOpts) {
+    match label_taxonomy(labels) {
+        Ok(specs) => {
+            for spec in specs {
+                ensure_label_exists(&spec.name, &spec.description, &spec.color, opts);
+            }
+        }
+        Err(e) => println!("{}", format!("Warning: {}", e).yellow()),
+    }
+}
+
+/// Creates or updates the repository's review labels to match `review.labels`.
+pub fn handle_review_labels_sync(config: &Config, opts: RunOpts) -> Result<()> {
+    println!("{}", "--- Syncing review labels ---".blue());
+    let wanted = label_taxonomy(&config.review.labels)?;
 
-    let log = git::get_log_since(since, opts)?;
+    gh::GH
+        .require()
+        .context("Syncing review labels needs the GitHub CLI")?;
 
-    if log.is_empty() {
+    let output = gh::run(
+        &[
+            "label",
+            "list",
+            "--json",
+            "name,color,description",
+            "--limit",
+            "500",
+        ],
+        opts,
+    )
+    .context("Failed to list labels with 'gh'")?;
+    if !output.success {
+        return Err(anyhow::anyhow!("Failed to list labels: {}", output.stderr));
+    }
+    let existing = parse_label_list(&output.stdout);
+    if opts.dry_run {
         println!(
             "{}",
-            "No new commits found in the specified time range.".yellow()
+            "[DRY RUN] Existing labels were not fetched, so every label is listed.".yellow()
         );
-        return Ok(());
     }
 
-    println!("\n{}", "COMMITS FOR REVIEW".cyan().bold());
-    println!("{}", "─".repeat(50).cyan());
-
-    for line in log.lines() {
-        if line.is_empty() {
-            continue;
-        }
-        let parts: Vec<&str> = line.splitn(3, '|').collect();
-        if parts.len() >= 2 {
-            let hash = short_hash(parts[0]);
-            let author = parts.get(1).unwrap_or(&"unknown");
-            let message = parts.get(2).unwrap_or(&"");
+    let mut changed = 0;
+    for change in plan_label_sync(&wanted, &existing) {
+        let (verb, spec) = match change {
+            LabelChange::Unchanged(name) => {
+                println!("  {} {}", "unchanged".dimmed(), name);
+                continue;
+            }
+            LabelChange::Create(spec) => ("create", spec),
+            LabelChange::Update(spec) => ("edit", spec),
+        };
+        changed += 1;
+        let action = if verb == "create" {
+            "created"
+        } else {
+            "updated"
+        };
+        if opts.dry_run {
             println!(
-                "  {} {} {}",
-                hash.yellow(),
-                format!("({})", author).dimmed(),
-                message
+                "  {} {} (#{}, \"{}\")",
+                format!("[DRY RUN] would be {}", action).yellow(),
+                spec.name,
+                spec.color,
+                spec.description
             );
+            continue;
         }
+        let result = gh::run(
+            &[
+                "label",
+                verb,
+                &spec.name,
+                "--color",
+                &spec.color,
+                "--description",
+                &spec.description,
+            ],
+            opts,
+        )
+        .context("Failed to execute 'gh label'")?;
+        if !result.success {
+            return Err(anyhow::anyhow!(
+                "Failed to {} label '{}': {}",
+                verb,
+                spec.name,
+                result.stderr
+            ));
+        }
+        println!("  {} {}", action.green(), spec.name);
     }
 
-    println!("{}", "─".repeat(50).cyan());
-
-    if !config.review.default_reviewers.is_empty() {
+    if changed == 0 {
         println!(
-            "\n{}",
-            format!(
-                "Default reviewers: {}",
-                config.review.default_reviewers.join(", ")
-            )
-            .dimmed()
+            "{}",
+            "Review labels already match the configuration.".green()
         );
     }
+    Ok(())
+}
 
-    println!("\n{}", "Next steps:".bold());
-    println!("   • Review commits above and discuss with the team");
-    println!("   • Run 'tbdflow review --approve <hash>' to mark as reviewed");
-    println!("   • Run 'tbdflow review --trigger' to create review issues\n");
+/// True when `gh` is installed and can reach GitHub.
+fn is_github_reachable(opts: RunOpts) -> bool {
+    gh::is_available() && gh::run(&["api", "rate_limit", "--silent"], opts).is_ok_and(|o| o.success)
+}
 
-    Ok(())
+/// The server reviews go to, for messages.
+fn forge_name(strategy: &ReviewStrategy) -> &'static str {
+    match strategy {
+        ReviewStrategy::Gitea => "Gitea",
+        ReviewStrategy::Bitbucket => "Bitbucket",
+        ReviewStrategy::InRepo => "origin",
+        _ => "GitHub",
+    }
 }
 
-pub fn handle_review_approve(config: &Config, commit_hash: &str, opts: RunOpts) -> Result<()> {
-    let short = short_hash(commit_hash);
+/// True when the server the review strategy talks to can be reached.
+fn is_forge_reachable(config: &Config, opts: RunOpts) -> bool {
+    match config.review.strategy {
+        ReviewStrategy::Gitea => Gitea::from_config(&config.review.gitea, opts)
+            .is_ok_and(|gitea| gitea.is_reachable(opts)),
+        ReviewStrategy::Bitbucket => Bitbucket::from_config(&config.review.bitbucket, opts)
+            .is_ok_and(|bitbucket| bitbucket.is_reachable(opts)),
+        // Notes are written locally and pushed when origin can be reached.
+        ReviewStrategy::GitNotes => true,
+        ReviewStrategy::InRepo => review_ledger::is_reachable(config, opts),
+        _ => is_github_reachable(opts),
+    }
+}
+
+/// Keeps `action` for later when the forge cannot be reached right now.
+fn queue_review_action(config: &Config, action: Action, opts: RunOpts) -> Result<()> {
+    let git_dir = std::path::PathBuf::from(git::get_git_dir(opts)?);
+    let description = action.describe();
+    let waiting = review_queue::push(&git_dir, action)?;
+    println!(
+        "{}",
+        format!(
+            "{} is unreachable. Queued '{}' ({} action(s) waiting).",
+            forge_name(&config.review.strategy),
+            description,
+            waiting
+        )
+        .yellow()
+    );
+    println!(
+        "{}",
+        "   It will be sent on the next online 'tbdflow review' run, or with 'tbdflow review flush'."
+            .dimmed()
+    );
+    Ok(())
+}
 
-    println!("{}", format!("--- Approving Commit {} ---", short).blue());
+fn send_review_action(config: &Config, action: &Action, opts: RunOpts) -> Result<()> {
+    match config.review.strategy {
+        ReviewStrategy::Gitea => {
+            return match action {
+                Action::Approve { commit } => approve_gitea_review(config, commit, opts),
+                Action::Concern {
+                    commit,
+                    message,
+                    severity,
+                } => raise_gitea_concern(config, commit, message, *severity, opts),
+                Action::Dismiss { commit, message } => {
+                    dismiss_gitea_review(config, commit, message, opts)
+                }
+            };
+        }
+        ReviewStrategy::Bitbucket => {
+            return match action {
+                Action::Approve { commit } => approve_bitbucket_review(config, commit, opts),
+                Action::Concern {
+                    commit,
+                    message,
+                    severity,
+                } => raise_bitbucket_concern(config, commit, message, *severity, opts),
+                Action::Dismiss { commit, message } => {
+                    dismiss_bitbucket_review(config, commit, message, opts)
+                }
+            };
+        }
+        ReviewStrategy::GitNotes | ReviewStrategy::InRepo => {
+            return match action {
+                Action::Approve { commit } => approve_recorded_review(config, commit, opts),
+                Action::Concern {
+                    commit,
+                    message,
+                    severity,
+                } => raise_recorded_concern(config, commit, message, *severity, opts),
+                Action::Dismiss { commit, message } => {
+                    dismiss_recorded_review(config, commit, message, opts)
+                }
+            };
+        }
+        _ => {}
+    }
+    match action {
+        Action::Approve { commit } => close_github_review_issue(
+            &config.review.labels,
+            short_hash(commit),
+            config.review.required_approvals,
+            opts,
+        ),
+        Action::Concern {
+            commit,
+            message,
+            severity,
+        } => raise_github_concern(config, commit, message, *severity, opts),
+        Action::Dismiss { commit, message } => {
+            dismiss_github_review_issue(&config.review.labels, short_hash(commit), message, opts)
+        }
+    }
+}
 
+/// Sends review actions queued while offline. `explicit` is set for
+/// `tbdflow review flush`; otherwise nothing is printed while still offline.
+pub fn flush_review_queue(config: &Config, explicit: bool, opts: RunOpts) -> Result<()> {
+    let git_dir = std::path::PathBuf::from(git::get_git_dir(opts)?);
+    let queued = review_queue::load(&git_dir)?;
+    if queued.is_empty() {
+        if explicit {
+            println!("{}", "No queued review actions.".green());
+        }
+        return Ok(());
+    }
     if opts.dry_run {
-        println!("{}", "[DRY RUN] Would mark commit as approved".yellow());
+        println!(
+            "{}",
+            format!(
+                "[DRY RUN] Would send {} queued review action(s)",
+                queued.len()
+            )
+            .yellow()
+        );
         return Ok(());
     }
-
-    match &config.review.strategy {
-        ReviewStrategy::GithubIssue => {
-            close_github_review_issue(&config.review.labels, short, opts)?;
-        }
-        ReviewStrategy::GithubWorkflow => {
-            // For workflow strategy, close the issue which will trigger
-            // the server-side Action to update commit status
-            close_github_review_issue(&config.review.labels, short, opts)?;
+    if !is_forge_reachable(config, opts) {
+        if explicit {
             println!(
                 "{}",
-                "   Server-side workflow will update commit status.".dimmed()
+                format!(
+                    "{} is still unreachable; {} review action(s) remain queued.",
+                    forge_name(&config.review.strategy),
+                    queued.len()
+                )
+                .yellow()
             );
         }
-        ReviewStrategy::LogOnly => {
-            println!("{}", format!("Commit {} marked as approved", short).green());
-        }
+        return Ok(());
     }
 
-    Ok(())
+    println!(
+        "{}",
+        format!("--- Sending {} queued review action(s) ---", queued.len()).blue()
+    );
+    let mut remaining = Vec::new();
+    for entry in queued {
+        if let Err(e) = send_review_action(config, &entry.action, opts) {
+            println!(
+                "{}",
+                format!(
+                    "Warning: could not send '{}': {}",
+                    entry.action.describe(),
+                    e
+                )
+                .yellow()
+            );
+            remaining.push(entry);
+        }
+    }
+    review_queue::save(&git_dir, &remaining)
 }
 
-pub fn handle_review_concern(
-    config: &Config,
-    commit_hash: &str,
-    message: &str,
-    opts: RunOpts,
-) -> Result<()> {
-    let short = short_hash(commit_hash);
-
+fn print_review_not_enabled() {
     println!(
         "{}",
-        format!("--- Raising Concern on Commit {} ---", short).blue()
+        "Review system is not enabled. Add the following to your .tbdflow.yml:".yellow()
     );
+    println!("\n  review:");
+    println!("    enabled: true");
+    println!("    strategy: github-issue");
+    println!("    default_reviewers:");
+    println!("      - teammate-username\n");
+}
 
-    if opts.dry_run {
-        println!("{}", "[DRY RUN] Would raise concern on commit".yellow());
+pub fn handle_review_trigger(
+    config: &Config,
+    reviewers_override: Option<Vec<String>>,
+    commit_sha: Option<&str>,
+    opts: RunOpts,
+) -> Result<()> {
+    if !config.review.enabled {
+        print_review_not_enabled();
         return Ok(());
     }
 
-    match &config.review.strategy {
-        ReviewStrategy::GithubIssue | ReviewStrategy::GithubWorkflow => {
-            raise_github_concern(config, commit_hash, message, opts)?;
-        }
-        ReviewStrategy::LogOnly => {
-            println!("{}", format!("CONCERN on {}: {}", short, message).yellow());
+    let commit_hash = match commit_sha {
+        Some(sha) if !sha.is_empty() => {
+            // Resolve the provided SHA to a full hash
+            let full = git::resolve_commit_hash(sha, opts)?;
+            if opts.verbose {
+                println!(
+                    "{} Triggering review for commit {}",
+                    "[REVIEW]".magenta(),
+                    short_hash(&full)
+                );
+            }
+            full
         }
-    }
+        _ => git::get_head_commit_hash(opts)?,
+    };
+    let message = git::get_commit_message(&commit_hash, opts)?;
+    let author = git::get_user_name(opts)?;
 
-    Ok(())
+    trigger_review(
+        config,
+        reviewers_override.as_deref(),
+        &commit_hash,
+        &message,
+        &author,
+        opts,
+    )
 }
 
-pub fn handle_review_dismiss(
+/// Opens a single review issue covering every commit in `range`
+/// (e.g. `main~5..main`), for changes too big to review commit by commit.
+pub fn handle_review_batch(
     config: &Config,
-    commit_hash: &str,
-    message: &str,
+    reviewers_override: Option<Vec<String>>,
+    range: &str,
     opts: RunOpts,
 ) -> Result<()> {
-    let short = short_hash(commit_hash);
+    if !config.review.enabled {
+        print_review_not_enabled();
+        return Ok(());
+    }
+
+    // Reading the log is safe in dry-run.
+    let mut commits = history::commits_in_range(range, opts.for_reads())?;
+    if commits.is_empty() {
+        return Err(anyhow::anyhow!("No commits in range '{}'.", range));
+    }
+    commits.reverse();
+
+    let mut files: Vec<String> = commits.iter().flat_map(|c| c.files.clone()).collect();
+    files.sort();
+    files.dedup();
+    let (reviewers, is_targeted) =
+        reviewers_for_files(config, &files, reviewers_override.as_deref(), opts);
+    let author = git::get_user_name(opts)?;
 
+    println!("{}", i18n::t(Msg::TriggeringBatchReview).blue());
+    if is_targeted {
+        println!("{} Review triggered by targeted file rules.", ">>".yellow());
+    }
     println!(
-        "{}",
-        format!("--- Dismissing Review for Commit {} ---", short).blue()
+        "{} {} ({} commits)",
+        i18n::t(Msg::ReviewRequestedFor).green(),
+        range.bold(),
+        commits.len()
     );
+    for commit in &commits {
+        println!("   {} {}", commit.short_hash().dimmed(), commit.subject);
+    }
+    println!("   Author: {}", author);
+    if !reviewers.is_empty() {
+        println!("   Reviewers: {}", reviewers.join(", "));
+    }
 
     if opts.dry_run {
-        println!("{}", "[DRY RUN] Would dismiss review".yellow());
+        println!("{}", "[DRY RUN] Would create batch review request".yellow());
         return Ok(());
     }
 
     match &config.review.strategy {
         ReviewStrategy::GithubIssue | ReviewStrategy::GithubWorkflow => {
-            dismiss_github_review_issue(&config.review.labels, short, message, opts)?;
+            if config.review.strategy == ReviewStrategy::GithubWorkflow {
+                println!(
+                    "{}",
+                    "   The review workflow handles single commits; creating the batch issue client-side."
+                        .dimmed()
+                );
+            }
+            let repo_url = git::get_remote_url(opts).unwrap_or_default();
+            let mentions = review_mentions(&config.review, &reviewers).unwrap_or_default();
+            let (title, body) = batch_issue(range, &commits, &author, &repo_url, &mentions);
+            open_review_issue(&config.review, &reviewers, &title, &body, opts)?;
+        }
+        ReviewStrategy::Gitea => {
+            let gitea = Gitea::from_config(&config.review.gitea, opts)?;
+            let mentions = review_mentions(&config.review, &reviewers).unwrap_or_default();
+            let (title, body) = batch_issue(range, &commits, &author, &gitea.repo_url(), &mentions);
+            open_gitea_review_issue(&gitea, &config.review, &reviewers, &title, &body, opts);
+        }
+        ReviewStrategy::Bitbucket => {
+            let bitbucket = Bitbucket::from_config(&config.review.bitbucket, opts)?;
+            let mentions = review_mentions(&config.review, &reviewers).unwrap_or_default();
+            let (title, body) =
+                batch_issue(range, &commits, &author, &bitbucket.repo_url(), &mentions);
+            let hashes: Vec<&str> = commits.iter().map(|c| c.hash.as_str()).collect();
+            open_bitbucket_review(&bitbucket, config, &hashes, &title, &body, opts);
+        }
+        ReviewStrategy::GitNotes | ReviewStrategy::InRepo => {
+            let hashes: Vec<&str> = commits.iter().map(|c| c.hash.as_str()).collect();
+            request_recorded_review(config, &hashes, &reviewers, opts)?;
         }
         ReviewStrategy::LogOnly => {
-            println!(
-                "{}",
-                format!("Review for {} dismissed: {}", short, message).dimmed()
-            );
+            println!("{}", i18n::t(Msg::ReviewLogged).dimmed());
         }
     }
 
     Ok(())
 }
 
-fn raise_github_concern(
-    config: &Config,
-    commit_hash: &str,
-    message: &str,
-    opts: RunOpts,
-) -> Result<()> {
-    let short = short_hash(commit_hash);
-    let labels = &config.review.labels;
-
-    if !is_gh_cli_available() {
+/// Triggers the review set by `review.on_complete` once `branch_name` has
+/// been merged into trunk with a merge commit at HEAD.
+pub fn trigger_review_on_complete(config: &Config, branch_name: &str, opts: RunOpts) -> Result<()> {
+    if !config.review.enabled || config.review.on_complete == CompleteReview::Off {
+        return Ok(());
+    }
+    if opts.dry_run {
         println!(
             "{}",
-            "Warning: GitHub CLI (gh) not found. Cannot raise concern.".yellow()
+            format!(
+                "[DRY RUN] Would request a review of the work merged from '{}'",
+                branch_name
+            )
+            .yellow()
         );
         return Ok(());
     }
 
-    // Search for the review issue
-    let search_query = format!("[Review] in:title {} in:title is:open", short);
+    match config.review.on_complete {
+        CompleteReview::MergeCommit => {
+            let merge_commit = git::get_head_commit_hash(opts)?;
+            let message = git::get_commit_message(&merge_commit, opts)?;
+            let author = git::get_user_name(opts)?;
+            trigger_review(config, None, &merge_commit, &message, &author, opts)
+        }
+        CompleteReview::Batch => {
+            // HEAD^1 is trunk before the merge and HEAD^2 the branch tip.
+            let trunk = git::resolve_commit_hash("HEAD^1", opts)?;
+            let tip = git::resolve_commit_hash("HEAD^2", opts)?;
+            let range = format!("{}..{}", short_hash(&trunk), short_hash(&tip));
+            handle_review_batch(config, None, &range, opts)
+        }
+        CompleteReview::Off => Ok(()),
+    }
+}
 
-    if opts.verbose {
-        println!("{} Searching for review issue...", "[INFO]".cyan());
+/// Groups digest commits under the changelog's section headers, in changelog
+/// order. Breaking changes get their own section; nothing is dropped.
+pub fn group_digest<'a>(
+    commits: &'a [ParsedCommit],
+    changelog_config: &ChangelogConfig,
+) -> Vec<(String, Vec<&'a ParsedCommit>)> {
+    let headers = changelog::section_headers(changelog_config);
+    let mut sections: HashMap<String, Vec<&ParsedCommit>> = HashMap::new();
+    for commit in commits {
+        let header = if commit.breaking {
+            changelog_config.breaking_header.as_str()
+        } else {
+            commit
+                .r#type
+                .as_deref()
+                .map_or(changelog_config.other_header.as_str(), |t| {
+                    changelog::get_section_header(&headers, changelog_config, t)
+                })
+        };
+        let header = if header.is_empty() {
+            changelog_config.other_header.as_str()
+        } else {
+            header
+        };
+        sections.entry(header.to_string()).or_default().push(commit);
     }
 
-    let output = Command::new("gh")
-        .args([
-            "issue",
-            "list",
-            "--search",
-            &search_query,
-            "--json",
-            "number,body",
-            "--limit",
-            "1",
-        ])
-        .output()
-        .context("Failed to search for GitHub issues")?;
+    changelog::section_order(&headers, changelog_config)
+        .into_iter()
+        .filter_map(|header| sections.remove(&header).map(|c| (header, c)))
+        .collect()
+}
 
-    if !output.status.success() {
-        println!(
-            "{}",
-            format!("Warning: Could not find review issue for {}", short).yellow()
-        );
-        return Ok(());
-    }
+/// Where the last `--resume` digest ended.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DigestState {
+    pub last_commit: String,
+    pub digested_at: String,
+}
 
-    let json_output = String::from_utf8_lossy(&output.stdout);
+impl State for DigestState {
+    const FILE: &'static str = "digest.json";
+    const LEGACY_FILE: Option<&'static str> = Some("tbdflow-digest-state.json");
+}
 
-    if let Some(issue_num) = extract_issue_number(&json_output) {
-        let issue_num_str = issue_num.to_string();
+pub fn load_digest_state(git_dir: &Path) -> Result<Option<DigestState>> {
+    state::load(git_dir)
+}
 
-        // Update labels: remove pending, add concern
-        if opts.verbose {
-            println!(
-                "{} Updating labels on issue #{}",
-                "[INFO]".cyan(),
-                issue_num
-            );
-        }
+pub fn save_digest_state(git_dir: &Path, digest: &DigestState) -> Result<()> {
+    state::save(git_dir, digest)
+}
 
-        let _ = Command::new("gh")
-            .args([
-                "issue",
-                "edit",
-                &issue_num_str,
-                "--remove-label",
-                &labels.pending,
-            ])
-            .output();
+/// Which commits `review --digest` shows.
+#[derive(Debug, Clone, Default)]
+pub struct DigestFilter {
+    /// Any `git log --since` value; ignored when resuming from saved state.
+    pub since: String,
+    /// Only commits whose author matches, as `git log --author`.
+    pub author: Option<String>,
+    /// Only commits touching these paths.
+    pub paths: Vec<String>,
+    /// Commits per page; `None` shows everything.
+    pub limit: Option<usize>,
+    /// 1-based page number, newest commits first.
+    pub page: usize,
+    /// Start from where the last `--resume` digest ended.
+    pub resume: bool,
+}
 
-        let _ = Command::new("gh")
-            .args([
-                "issue",
+/// The `page`th slice of `items` of at most `limit` items, and the number of pages.
+pub fn digest_page<T>(items: &[T], limit: Option<usize>, page: usize) -> (&[T], usize) {
+    let Some(limit) = limit.filter(|l| *l > 0) else {
+        return (items, 1);
+    };
+    let pages = items.len().div_ceil(limit).max(1);
+    let start = (page.max(1) - 1).saturating_mul(limit).min(items.len());
+    let end = (start + limit).min(items.len());
+    (&items[start..end], pages)
+}
+
+pub fn handle_review_digest(
+    config: &Config,
+    filter: &DigestFilter,
+    json: bool,
+    opts: RunOpts,
+) -> Result<()> {
+    let git_dir = std::path::PathBuf::from(git::get_git_dir(opts)?);
+    let resume_from = if filter.resume {
+        match load_digest_state(&git_dir)? {
+            Some(state) if git::commit_exists(&state.last_commit, opts)? => Some(state),
+            Some(state) => {
+                let warning = format!(
+                    "Last digested commit {} no longer exists; falling back to --since.",
+                    short_hash(&state.last_commit)
+                );
+                if json {
+                    eprintln!("{}", warning);
+                } else {
+                    println!("{}", warning.yellow());
+                }
+                None
+            }
+            None => None,
+        }
+    } else {
+        None
+    };
+
+    let range = resume_from
+        .as_ref()
+        .map(|state| format!("{}..HEAD", state.last_commit));
+    let mut log_args: Vec<&str> = match &range {
+        Some(range) => vec![range.as_str()],
+        None => vec!["--since", filter.since.as_str()],
+    };
+    if let Some(author) = &filter.author {
+        log_args.extend(["--author", author.as_str()]);
+    }
+    if !filter.paths.is_empty() {
+        log_args.push("--");
+        log_args.extend(filter.paths.iter().map(String::as_str));
+    }
+
+    let commits = history::parse_log(&git::get_log_records(&log_args, opts)?);
+    let (shown, pages) = digest_page(&commits, filter.limit, filter.page);
+    if !commits.is_empty() && filter.page > pages {
+        anyhow::bail!(
+            "Page {} is past the end of the digest ({} commits, {} page(s)).",
+            filter.page,
+            commits.len(),
+            pages
+        );
+    }
+
+    if json {
+        if !commits.is_empty() && filter.page == pages {
+            save_digest_position(&git_dir, filter, opts)?;
+        }
+        let response = ReviewDigestResponse {
+            since: resume_from.is_none().then(|| filter.since.clone()),
+            resumed_after: resume_from.map(|state| state.last_commit),
+            total: commits.len(),
+            page: filter.page,
+            pages,
+            next_page: (filter.page < pages).then_some(filter.page + 1),
+            sections: group_digest(shown, &config.changelog)
+                .into_iter()
+                .map(|(header, section)| ReviewDigestSectionResponse {
+                    header: header.trim_start_matches('#').trim().to_string(),
+                    commits: section
+                        .into_iter()
+                        .map(|commit| ReviewDigestCommitResponse {
+                            hash: commit.hash.clone(),
+                            author: commit.author.clone(),
+                            r#type: commit.r#type.clone(),
+                            scope: commit.scope.clone(),
+                            description: commit.description.clone(),
+                            breaking: commit.breaking,
+                            files: commit.files.len(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            default_reviewers: config.review.default_reviewers.clone(),
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(response))?
+        );
+        return Ok(());
+    }
+
+    let heading = match &resume_from {
+        Some(state) => format!(
+            "--- Trunk Evolution Digest (Since last digest at {}) ---",
+            short_hash(&state.last_commit)
+        ),
+        None => format!("--- Trunk Evolution Digest (Since {}) ---", filter.since),
+    };
+    println!("{}", heading.blue());
+
+    if commits.is_empty() {
+        println!(
+            "{}",
+            "No new commits found in the specified time range.".yellow()
+        );
+        return Ok(());
+    }
+
+    println!("\n{}", "COMMITS FOR REVIEW".cyan().bold());
+    if pages > 1 {
+        println!(
+            "{}",
+            format!(
+                "Page {}/{} ({} of {} commits)",
+                filter.page,
+                pages,
+                shown.len(),
+                commits.len()
+            )
+            .dimmed()
+        );
+    }
+    println!("{}", output::glyph("─", "-").repeat(50).cyan());
+
+    for (header, section) in group_digest(shown, &config.changelog) {
+        println!(
+            "{}",
+            output::text(header.trim_start_matches('#').trim()).bold()
+        );
+        for commit in section {
+            let scope = commit
+                .scope
+                .as_ref()
+                .map_or(String::new(), |s| format!("{}: ", s));
+            let files = match commit.files.len() {
+                1 => " (1 file)".to_string(),
+                n => format!(" ({} files)", n),
+            };
+            println!(
+                "  {} {} {}{}{}",
+                commit.short_hash().yellow(),
+                format!("({})", commit.author).dimmed(),
+                scope,
+                commit.description,
+                files.dimmed()
+            );
+        }
+    }
+
+    println!("{}", output::glyph("─", "-").repeat(50).cyan());
+
+    if filter.page < pages {
+        println!(
+            "{}",
+            format!("More commits: run again with --page {}", filter.page + 1).dimmed()
+        );
+    } else if save_digest_position(&git_dir, filter, opts)? {
+        println!(
+            "{}",
+            "Digest position saved; the next --resume starts after this commit range.".dimmed()
+        );
+    }
+
+    if !config.review.default_reviewers.is_empty() {
+        println!(
+            "\n{}",
+            format!(
+                "Default reviewers: {}",
+                config.review.default_reviewers.join(", ")
+            )
+            .dimmed()
+        );
+    }
+
+    println!("\n{}", "Next steps:".bold());
+    let bullet = output::glyph("•", "*");
+    println!(
+        "   {} Review commits above and discuss with the team",
+        bullet
+    );
+    println!(
+        "   {} Run 'tbdflow review --approve <hash>' to mark as reviewed",
+        bullet
+    );
+    println!(
+        "   {} Run 'tbdflow review --trigger' to create review issues\n",
+        bullet
+    );
+
+    Ok(())
+}
+
+/// Records HEAD as the end of the digest for the next `--resume`, when this
+/// digest was resumable. Returns whether anything was saved.
+fn save_digest_position(git_dir: &Path, filter: &DigestFilter, opts: RunOpts) -> Result<bool> {
+    if !filter.resume || opts.dry_run {
+        return Ok(false);
+    }
+    let head = git::resolve_commit_hash("HEAD", opts)?;
+    save_digest_state(
+        git_dir,
+        &DigestState {
+            last_commit: head,
+            digested_at: Utc::now().to_rfc3339(),
+        },
+    )?;
+    Ok(true)
+}
+
+pub fn handle_review_approve(config: &Config, commit_hash: &str, opts: RunOpts) -> Result<()> {
+    let short = short_hash(commit_hash);
+
+    println!(
+        "{}",
+        i18n::tf(Msg::ApprovingCommit, &[("commit", short)]).blue()
+    );
+
+    if opts.dry_run {
+        println!("{}", "[DRY RUN] Would mark commit as approved".yellow());
+        return Ok(());
+    }
+
+    let required = config.review.required_approvals;
+    if config.review.strategy != ReviewStrategy::LogOnly && !is_forge_reachable(config, opts) {
+        return queue_review_action(
+            config,
+            Action::Approve {
+                commit: commit_hash.to_string(),
+            },
+            opts,
+        );
+    }
+    match &config.review.strategy {
+        ReviewStrategy::GithubIssue => {
+            close_github_review_issue(&config.review.labels, short, required, opts)?;
+        }
+        ReviewStrategy::GithubWorkflow => {
+            // For workflow strategy, close the issue which will trigger
+            // the server-side Action to update commit status
+            close_github_review_issue(&config.review.labels, short, required, opts)?;
+            println!(
+                "{}",
+                "   Server-side workflow will update commit status.".dimmed()
+            );
+        }
+        ReviewStrategy::Gitea => {
+            approve_gitea_review(config, commit_hash, opts)?;
+        }
+        ReviewStrategy::Bitbucket => {
+            approve_bitbucket_review(config, commit_hash, opts)?;
+        }
+        ReviewStrategy::GitNotes | ReviewStrategy::InRepo => {
+            approve_recorded_review(config, commit_hash, opts)?;
+        }
+        ReviewStrategy::LogOnly => {
+            if required > 1 {
+                let approvals = record_local_approval(commit_hash, opts)?;
+                if approvals < required {
+                    print_approval_progress(short, approvals, required);
+                    return Ok(());
+                }
+            }
+            println!(
+                "{}",
+                i18n::tf(Msg::CommitApproved, &[("commit", short)]).green()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Records an approval in the journal and returns how many distinct people
+/// have approved `commit_hash` so far.
+fn record_local_approval(commit_hash: &str, opts: RunOpts) -> Result<usize> {
+    let git_dir = std::path::PathBuf::from(git::get_git_dir(opts)?);
+    let commit =
+        git::resolve_commit_hash(commit_hash, opts).unwrap_or_else(|_| commit_hash.to_string());
+    journal::append(
+        &git_dir,
+        &journal::JournalEntry::now(
+            git::get_user_identity(opts),
+            journal::JournalEvent::ReviewApproved {
+                commit: commit.clone(),
+            },
+        ),
+    )?;
+    Ok(local_approvers(&journal::read_entries(&git_dir)?, &commit).len())
+}
+
+/// Distinct authors of journal approvals for `commit`.
+fn local_approvers(entries: &[journal::JournalEntry], commit: &str) -> Vec<String> {
+    let mut approvers: Vec<String> = entries
+        .iter()
+        .filter(|e| matches!(&e.event, journal::JournalEvent::ReviewApproved { commit: c } if c == commit))
+        .map(|e| e.author.clone())
+        .collect();
+    approvers.sort();
+    approvers.dedup();
+    approvers
+}
+
+fn print_approval_progress(short_hash: &str, approvals: usize, required: usize) {
+    println!(
+        "{}",
+        i18n::tf(
+            Msg::ApprovalProgress,
+            &[
+                ("commit", short_hash),
+                ("count", &approvals.to_string()),
+                ("required", &required.to_string()),
+                ("remaining", &(required - approvals).to_string()),
+            ],
+        )
+        .green()
+    );
+}
+
+/// Distinct logins that left an approval comment. `body` and `login` are
+/// JSON pointers into each comment, as every forge names them differently.
+fn approvers_in(comments: &Value, body: &str, login: &str) -> Vec<String> {
+    let mut approvers: Vec<String> = comments
+        .as_array()
+        .map(|comments| {
+            comments
+                .iter()
+                .filter(|c| {
+                    c.pointer(body)
+                        .and_then(Value::as_str)
+                        .is_some_and(|b| b.starts_with(APPROVAL_COMMENT))
+                })
+                .filter_map(|c| c.pointer(login).and_then(Value::as_str))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    approvers.sort();
+    approvers.dedup();
+    approvers
+}
+
+/// Distinct GitHub logins that left an approval comment, from
+/// `gh issue view --json comments` output.
+fn approvers_from_comments(json: &str) -> Vec<String> {
+    let parsed: Value = serde_json::from_str(json).unwrap_or(Value::Null);
+    approvers_in(&parsed["comments"], "/body", "/author/login")
+}
+
+/// Comments an approval on the issue and returns the number of distinct
+/// approvers, including the current `gh` user.
+fn record_github_approval(issue: &str, required: usize, opts: RunOpts) -> Result<usize> {
+    let view = gh::run(&["issue", "view", issue, "--json", "comments"], opts)
+        .context("Failed to read review issue comments")?;
+    let approvers = approvers_from_comments(&view.stdout);
+
+    let me = gh::run(&["api", "user", "--jq", ".login"], opts)
+        .ok()
+        .filter(|o| o.success)
+        .map(|o| o.stdout)
+        .unwrap_or_default();
+    if !me.is_empty() && approvers.contains(&me) {
+        if opts.verbose {
+            println!("{} {} has already approved", "[INFO]".cyan(), me);
+        }
+        return Ok(approvers.len());
+    }
+
+    let count = approvers.len() + 1;
+    let comment = format!("{} ({}/{})", APPROVAL_COMMENT, count, required);
+    gh::run(&["issue", "comment", issue, "--body", &comment], opts)
+        .context("Failed to comment on review issue")?;
+    let _ = gh::run(
+        &[
+            "api",
+            "--method",
+            "POST",
+            &format!("repos/{{owner}}/{{repo}}/issues/{}/reactions", issue),
+            "-f",
+            "content=+1",
+        ],
+        opts,
+    );
+    Ok(count)
+}
+
+pub fn handle_review_concern(
+    config: &Config,
+    commit_hash: &str,
+    message: &str,
+    severity: Option<ConcernSeverity>,
+    opts: RunOpts,
+) -> Result<()> {
+    let short = short_hash(commit_hash);
+
+    println!(
+        "{}",
+        i18n::tf(Msg::RaisingConcern, &[("commit", short)]).blue()
+    );
+
+    if opts.dry_run {
+        println!("{}", "[DRY RUN] Would raise concern on commit".yellow());
+        return Ok(());
+    }
+
+    match &config.review.strategy {
+        ReviewStrategy::GithubIssue
+        | ReviewStrategy::GithubWorkflow
+        | ReviewStrategy::Gitea
+        | ReviewStrategy::Bitbucket
+        | ReviewStrategy::InRepo
+            if !is_forge_reachable(config, opts) =>
+        {
+            queue_review_action(
+                config,
+                Action::Concern {
+                    commit: commit_hash.to_string(),
+                    message: message.to_string(),
+                    severity,
+                },
+                opts,
+            )?;
+        }
+        ReviewStrategy::GithubIssue | ReviewStrategy::GithubWorkflow => {
+            raise_github_concern(config, commit_hash, message, severity, opts)?;
+        }
+        ReviewStrategy::Gitea => {
+            raise_gitea_concern(config, commit_hash, message, severity, opts)?;
+        }
+        ReviewStrategy::Bitbucket => {
+            raise_bitbucket_concern(config, commit_hash, message, severity, opts)?;
+        }
+        ReviewStrategy::GitNotes | ReviewStrategy::InRepo => {
+            raise_recorded_concern(config, commit_hash, message, severity, opts)?;
+        }
+        ReviewStrategy::LogOnly => {
+            let level = severity.map_or(String::new(), |s| format!(" ({})", s.as_str()));
+            println!(
+                "{}",
+                format!("CONCERN{} on {}: {}", level, short, message).yellow()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+pub fn handle_review_dismiss(
+    config: &Config,
+    commit_hash: &str,
+    message: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    let short = short_hash(commit_hash);
+
+    println!(
+        "{}",
+        i18n::tf(Msg::DismissingReview, &[("commit", short)]).blue()
+    );
+
+    if opts.dry_run {
+        println!("{}", "[DRY RUN] Would dismiss review".yellow());
+        return Ok(());
+    }
+
+    match &config.review.strategy {
+        ReviewStrategy::GithubIssue
+        | ReviewStrategy::GithubWorkflow
+        | ReviewStrategy::Gitea
+        | ReviewStrategy::Bitbucket
+        | ReviewStrategy::InRepo
+            if !is_forge_reachable(config, opts) =>
+        {
+            queue_review_action(
+                config,
+                Action::Dismiss {
+                    commit: commit_hash.to_string(),
+                    message: message.to_string(),
+                },
+                opts,
+            )?;
+        }
+        ReviewStrategy::GithubIssue | ReviewStrategy::GithubWorkflow => {
+            dismiss_github_review_issue(&config.review.labels, short, message, opts)?;
+        }
+        ReviewStrategy::Gitea => {
+            dismiss_gitea_review(config, commit_hash, message, opts)?;
+        }
+        ReviewStrategy::Bitbucket => {
+            dismiss_bitbucket_review(config, commit_hash, message, opts)?;
+        }
+        ReviewStrategy::GitNotes | ReviewStrategy::InRepo => {
+            dismiss_recorded_review(config, commit_hash, message, opts)?;
+        }
+        ReviewStrategy::LogOnly => {
+            println!(
+                "{}",
+                format!("Review for {} dismissed: {}", short, message).dimmed()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn raise_github_concern(
+    config: &Config,
+    commit_hash: &str,
+    message: &str,
+    severity: Option<ConcernSeverity>,
+    opts: RunOpts,
+) -> Result<()> {
+    let short = short_hash(commit_hash);
+    let labels = &config.review.labels;
+
+    if !gh::is_available() {
+        println!(
+            "{}",
+            "Warning: GitHub CLI (gh) not found. Cannot raise concern.".yellow()
+        );
+        return Ok(());
+    }
+
+    // Search for the review issue
+    let search_query = format!("[Review] in:title {} in:title is:open", short);
+
+    if opts.verbose {
+        println!("{} Searching for review issue...", "[INFO]".cyan());
+    }
+
+    let output = gh::run(
+        &[
+            "issue",
+            "list",
+            "--search",
+            &search_query,
+            "--json",
+            "number,body,url",
+            "--limit",
+            "1",
+        ],
+        opts,
+    )
+    .context("Failed to search for GitHub issues")?;
+
+    if !output.success {
+        println!(
+            "{}",
+            format!("Warning: Could not find review issue for {}", short).yellow()
+        );
+        return Ok(());
+    }
+
+    if let Some(issue) = first_issue(&output.stdout)? {
+        let issue_num = issue.number;
+        let issue_num_str = issue_num.to_string();
+
+        // Update labels: remove pending, add concern
+        if opts.verbose {
+            println!(
+                "{} Updating labels on issue #{}",
+                "[INFO]".cyan(),
+                issue_num
+            );
+        }
+
+        let _ = gh::run(
+            &[
+                "issue",
+                "edit",
+                &issue_num_str,
+                "--remove-label",
+                &labels.pending,
+            ],
+            opts,
+        );
+
+        let _ = gh::run(
+            &[
+                "issue",
                 "edit",
                 &issue_num_str,
                 "--add-label",
                 &labels.concern,
-            ])
-            .output();
+            ],
+            opts,
+        );
+
+        // A re-raised concern replaces the previous severity
+        if let Some(severity) = severity {
+            let others: Vec<&str> = [
+                ConcernSeverity::Minor,
+                ConcernSeverity::Major,
+                ConcernSeverity::Critical,
+            ]
+            .iter()
+            .filter(|s| **s != severity)
+            .map(|s| s.label(labels))
+            .collect();
+            let _ = gh::run(
+                &[
+                    "issue",
+                    "edit",
+                    &issue_num_str,
+                    "--remove-label",
+                    &others.join(","),
+                    "--add-label",
+                    severity.label(labels),
+                ],
+                opts,
+            );
+        }
+
+        // Add a comment with the concern
+        let comment = match severity {
+            Some(s) => format!("**Concern Raised** ({})\n\n{}", s.as_str(), message),
+            None => format!("**Concern Raised**\n\n{}", message),
+        };
+
+        let _ = gh::run(
+            &["issue", "comment", &issue_num_str, "--body", &comment],
+            opts,
+        );
+
+        // Append checklist item to the issue body
+        append_concern_checklist_item(&issue_num_str, message, opts)?;
+
+        // Set commit status based on severity and config
+        set_commit_status(config, commit_hash, message, severity, &issue.url, opts)?;
+
+        let applied = match severity {
+            Some(s) => format!("{}, {}", labels.concern, s.label(labels)),
+            None => labels.concern.clone(),
+        };
+        println!(
+            "{}",
+            format!(
+                "Concern raised on issue #{} for commit {} (label: {})",
+                issue_num, short, applied
+            )
+            .yellow()
+        );
+    } else {
+        println!(
+            "{}",
+            format!("Warning: No open review issue found for commit {}", short).yellow()
+        );
+        println!("   Run 'tbdflow review --trigger' first to create the review issue.");
+    }
+
+    Ok(())
+}
+
+fn append_concern_checklist_item(
+    issue_num: &str,
+    concern_message: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    // Get current issue body
+    let output = gh::run(&["issue", "view", issue_num, "--json", "number,body"], opts)
+        .context("Failed to get issue body")?;
+
+    if !output.success {
+        return Ok(());
+    }
+
+    let current_body = parse_gh_json::<GhIssue>(&output.stdout)?
+        .map(|issue| issue.body)
+        .unwrap_or_default();
+    let new_body = with_concern_item(current_body, concern_message);
+
+    if opts.verbose {
+        println!(
+            "{} Updating issue body with concern checklist item",
+            "[INFO]".cyan()
+        );
+    }
+
+    let _ = gh::run(&["issue", "edit", issue_num, "--body", &new_body], opts);
+
+    Ok(())
+}
+
+/// The review issue body with `concern_message` added as an open checklist item.
+fn with_concern_item(current_body: String, concern_message: &str) -> String {
+    // Replace the "No concerns raised yet" placeholder or append to concerns section
+    if current_body.contains("_No concerns raised yet._") {
+        current_body.replace(
+            "_No concerns raised yet._",
+            &format!("- [ ] {}", concern_message),
+        )
+    } else if current_body.contains("### Concerns") {
+        // Find the concerns section and append the new item
+        let concerns_marker = "### Concerns\n\n";
+        if let Some(pos) = current_body.find(concerns_marker) {
+            let insert_pos = pos + concerns_marker.len();
+            let (before, after) = current_body.split_at(insert_pos);
+            format!("{}- [ ] {}\n{}", before, concern_message, after)
+        } else {
+            current_body
+        }
+    } else {
+        current_body
+    }
+}
+
+fn significant_words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() >= 3)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Ticks the open concern item that `fix_subject` addresses: the only open
+/// item, or the one sharing the most words with the subject. Returns the new
+/// body and the item's text, or `None` when no item clearly matches.
+pub fn check_off_concern(body: &str, fix_subject: &str) -> Option<(String, String)> {
+    let open: Vec<(usize, &str)> = body
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            line.trim_start()
+                .strip_prefix("- [ ] ")
+                .map(|item| (i, item))
+        })
+        .collect();
+
+    let (index, item) = if open.len() == 1 {
+        open[0]
+    } else {
+        let subject_words = significant_words(fix_subject);
+        let scored: Vec<(usize, (usize, &str))> = open
+            .iter()
+            .map(|&(i, item)| {
+                let overlap = significant_words(item)
+                    .iter()
+                    .filter(|w| subject_words.contains(w))
+                    .count();
+                (overlap, (i, item))
+            })
+            .filter(|(overlap, _)| *overlap > 0)
+            .collect();
+        let best = scored.iter().map(|(overlap, _)| *overlap).max()?;
+        let mut top = scored.iter().filter(|(overlap, _)| *overlap == best);
+        let candidate = top.next()?.1;
+        if top.next().is_some() {
+            return None; // A tie is not a clear match.
+        }
+        candidate
+    };
+
+    let new_body: Vec<String> = body
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == index {
+                line.replacen("- [ ] ", "- [x] ", 1)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    Some((new_body.join("\n"), item.to_string()))
+}
+
+/// Comments on the review issue for `review_hash` with a link to the
+/// fix-forward commit and ticks the concern it addresses.
+pub fn link_fix_to_review(
+    config: &Config,
+    review_hash: &str,
+    fix_hash: &str,
+    fix_subject: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    let review_short = short_hash(review_hash);
+
+    if opts.dry_run {
+        println!(
+            "{}",
+            format!(
+                "[DRY RUN] Would link this commit to the review of {}",
+                review_short
+            )
+            .yellow()
+        );
+        return Ok(());
+    }
+    if config.review.enabled {
+        match config.review.strategy {
+            ReviewStrategy::Gitea => {
+                return link_fix_to_gitea_review(config, review_short, fix_hash, fix_subject, opts);
+            }
+            ReviewStrategy::Bitbucket => {
+                return link_fix_to_bitbucket_review(
+                    config,
+                    review_short,
+                    fix_hash,
+                    fix_subject,
+                    opts,
+                );
+            }
+            ReviewStrategy::GitNotes | ReviewStrategy::InRepo => {
+                return link_fix_to_recorded_review(
+                    config,
+                    review_short,
+                    fix_hash,
+                    fix_subject,
+                    opts,
+                );
+            }
+            _ => {}
+        }
+    }
+    if !config.review.enabled
+        || config.review.strategy == ReviewStrategy::LogOnly
+        || !gh::is_available()
+    {
+        println!(
+            "{}",
+            format!(
+                "Fix-forward for review of {} recorded in the commit trailer.",
+                review_short
+            )
+            .dimmed()
+        );
+        return Ok(());
+    }
+
+    let search_query = format!("[Review] in:title {} in:title is:open", review_short);
+    let output = gh::run(
+        &[
+            "issue",
+            "list",
+            "--search",
+            &search_query,
+            "--json",
+            "number,body",
+            "--limit",
+            "1",
+        ],
+        opts,
+    )
+    .context("Failed to search for GitHub issues")?;
+    let issue = match output.success {
+        true => first_issue(&output.stdout)?,
+        false => None,
+    };
+    let Some(issue) = issue else {
+        println!(
+            "{}",
+            format!(
+                "Warning: No open review issue found for commit {}",
+                review_short
+            )
+            .yellow()
+        );
+        return Ok(());
+    };
+    let issue_num = issue.number;
+    let issue_num_str = issue_num.to_string();
+
+    let remote_url = git::get_remote_url(opts).unwrap_or_default();
+    let checked = check_off_concern(&issue.body, fix_subject);
+    let comment = fix_forward_comment(&remote_url, fix_hash, fix_subject, &checked);
+    let _ = gh::run(
+        &["issue", "comment", &issue_num_str, "--body", &comment],
+        opts,
+    );
+    if let Some((new_body, _)) = &checked {
+        let _ = gh::run(&["issue", "edit", &issue_num_str, "--body", new_body], opts);
+    }
+    print_fix_link(issue_num, review_short, checked);
+    Ok(())
+}
+
+/// The comment a fix-forward leaves on the review issue.
+fn fix_forward_comment(
+    repo_url: &str,
+    fix_hash: &str,
+    fix_subject: &str,
+    checked: &Option<(String, String)>,
+) -> String {
+    let fix_short = short_hash(fix_hash);
+    let link = if repo_url.is_empty() {
+        format!("`{}`", fix_short)
+    } else {
+        format!("[`{}`]({})", fix_short, commit_url(repo_url, fix_hash))
+    };
+    let mut comment = format!("**Fix-forward** in {}: {}", link, fix_subject);
+    if let Some((_, item)) = checked {
+        comment.push_str(&format!("\n\nAddresses concern: {}", item));
+    }
+    comment
+}
+
+fn print_fix_link(issue_num: u64, review_short: &str, checked: Option<(String, String)>) {
+    match checked {
+        Some((new_body, item)) => {
+            println!(
+                "{}",
+                format!(
+                    "Linked to review issue #{} and checked off: {}",
+                    issue_num, item
+                )
+                .green()
+            );
+            if !new_body.contains("- [ ] ") {
+                println!(
+                    "   All concerns addressed. Run 'tbdflow review --approve {}' to close the review.",
+                    review_short
+                );
+            }
+        }
+        None => println!(
+            "{}",
+            format!(
+                "Linked to review issue #{}. No concern item clearly matched, so none was checked off.",
+                issue_num
+            )
+            .yellow()
+        ),
+    }
+}
+
+fn link_fix_to_gitea_review(
+    config: &Config,
+    review_short: &str,
+    fix_hash: &str,
+    fix_subject: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    let gitea = Gitea::from_config(&config.review.gitea, opts)?;
+    gitea.require_token()?;
+    let Some(issue) = find_gitea_review_issue(&gitea, review_short, opts)? else {
+        println!(
+            "{}",
+            format!(
+                "Warning: No open review issue found for commit {}",
+                review_short
+            )
+            .yellow()
+        );
+        return Ok(());
+    };
+    let number = issue["number"].as_u64().unwrap_or_default();
+    let checked = check_off_concern(issue["body"].as_str().unwrap_or_default(), fix_subject);
+    let comment = fix_forward_comment(&gitea.repo_url(), fix_hash, fix_subject, &checked);
+    comment_on_gitea_issue(&gitea, number, &comment, opts)?;
+    if let Some((new_body, _)) = &checked {
+        let path = format!("/issues/{}", number);
+        gitea.patch(&gitea.repo_path(&path), &json!({ "body": new_body }), opts)?;
+    }
+    print_fix_link(number, review_short, checked);
+    Ok(())
+}
+
+fn link_fix_to_bitbucket_review(
+    config: &Config,
+    review_short: &str,
+    fix_hash: &str,
+    fix_subject: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    let bitbucket = Bitbucket::from_config(&config.review.bitbucket, opts)?;
+    bitbucket.require_credentials()?;
+    let Some(issue) = find_bitbucket_review_issue(&bitbucket, review_short, opts) else {
+        println!(
+            "{}",
+            format!(
+                "Warning: No open review issue found for commit {}",
+                review_short
+            )
+            .yellow()
+        );
+        return Ok(());
+    };
+    let id = issue["id"].as_u64().unwrap_or_default();
+    let checked = check_off_concern(
+        issue["content"]["raw"].as_str().unwrap_or_default(),
+        fix_subject,
+    );
+    let comment = fix_forward_comment(&bitbucket.repo_url(), fix_hash, fix_subject, &checked);
+    comment_on_bitbucket_issue(&bitbucket, id, &comment, opts)?;
+    if let Some((new_body, _)) = &checked {
+        update_bitbucket_issue(
+            &bitbucket,
+            id,
+            &json!({ "content": { "raw": new_body } }),
+            opts,
+        )?;
+    }
+    print_fix_link(id, review_short, checked);
+    Ok(())
+}
+
+fn set_commit_status(
+    config: &Config,
+    commit_hash: &str,
+    message: &str,
+    severity: Option<ConcernSeverity>,
+    issue_url: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    if !gh::is_available() {
+        return Ok(());
+    }
+
+    let vars = StatusVars {
+        message,
+        severity,
+        sha: commit_hash,
+        issue_url,
+    };
+    let (state, template) = concern_commit_status(severity, &config.review);
+    let description = truncate_description(render_status_template(template, &vars));
+    let target_url = match &config.review.status.target_url {
+        Some(template) => render_status_template(template, &vars),
+        None => issue_url.to_string(),
+    };
+
+    // Get repo owner/name
+    let repo_info = gh::run(&["repo", "view", "--json", "owner,name"], opts);
+
+    let repo = match repo_info {
+        Ok(output) if output.success => parse_gh_json::<GhRepo>(&output.stdout)?,
+        _ => return Ok(()),
+    };
+
+    let Some(GhRepo {
+        name,
+        owner: GhOwner { login: owner },
+    }) = repo
+    else {
+        return Ok(());
+    };
+
+    if opts.verbose {
+        println!(
+            "{} Setting commit status '{}' to '{}' for {}",
+            "[INFO]".cyan(),
+            config.review.status.context,
+            state,
+            short_hash(commit_hash)
+        );
+    }
+
+    let api_path = format!("repos/{}/{}/statuses/{}", owner, name, commit_hash);
+    let state_field = format!("state={}", state);
+    let context_field = format!("context={}", config.review.status.context);
+    let description_field = format!("description={}", description);
+    let target_field = format!("target_url={}", target_url);
+
+    let mut args = vec![
+        "api",
+        &api_path,
+        "-f",
+        &state_field,
+        "-f",
+        &context_field,
+        "-f",
+        &description_field,
+    ];
+    if !target_url.is_empty() {
+        args.push("-f");
+        args.push(&target_field);
+    }
+
+    let _ = gh::run(&args, opts);
+
+    Ok(())
+}
+
+fn dismiss_github_review_issue(
+    labels: &ReviewLabelsConfig,
+    short_hash: &str,
+    message: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    if !gh::is_available() {
+        println!(
+            "{}",
+            "Warning: GitHub CLI (gh) not found. Cannot dismiss review.".yellow()
+        );
+        return Ok(());
+    }
+
+    // Search for the review issue
+    let search_query = format!("[Review] in:title {} in:title is:open", short_hash);
+
+    if opts.verbose {
+        println!("{} Searching for review issue...", "[INFO]".cyan());
+    }
+
+    let output = gh::run(
+        &[
+            "issue",
+            "list",
+            "--search",
+            &search_query,
+            "--json",
+            "number",
+            "--limit",
+            "1",
+        ],
+        opts,
+    )
+    .context("Failed to search for GitHub issues")?;
+
+    if output.success {
+        if let Some(issue) = first_issue(&output.stdout)? {
+            let issue_num = issue.number;
+            let issue_num_str = issue_num.to_string();
+
+            // Update labels: remove pending/concern, add dismissed
+            if opts.verbose {
+                println!(
+                    "{} Updating labels on issue #{}",
+                    "[INFO]".cyan(),
+                    issue_num
+                );
+            }
+
+            let _ = gh::run(
+                &[
+                    "issue",
+                    "edit",
+                    &issue_num_str,
+                    "--remove-label",
+                    &labels.pending,
+                ],
+                opts,
+            );
+
+            let _ = gh::run(
+                &[
+                    "issue",
+                    "edit",
+                    &issue_num_str,
+                    "--remove-label",
+                    &labels.concern,
+                ],
+                opts,
+            );
+
+            let _ = gh::run(
+                &[
+                    "issue",
+                    "edit",
+                    &issue_num_str,
+                    "--add-label",
+                    &labels.dismissed,
+                ],
+                opts,
+            );
+
+            // Close with a comment
+            let comment = format!(
+                "**Dismissed** via `tbdflow review --dismiss`\n\nReason: {}",
+                message
+            );
+
+            let close_output = gh::run(
+                &["issue", "close", &issue_num_str, "--comment", &comment],
+                opts,
+            )
+            .context("Failed to close GitHub issue")?;
+
+            if close_output.success {
+                println!(
+                    "{}",
+                    format!(
+                        "Review for commit {} dismissed and issue #{} closed (label: {})",
+                        short_hash, issue_num, labels.dismissed
+                    )
+                    .dimmed()
+                );
+            } else {
+                println!(
+                    "{}",
+                    "Review dismissed (issue close failed)".to_string().yellow()
+                );
+            }
+        } else {
+            println!(
+                "{}",
+                format!(
+                    "Review for {} dismissed (no open review issue found)",
+                    short_hash
+                )
+                .dimmed()
+            );
+        }
+    } else {
+        println!(
+            "{}",
+            format!("Review for {} dismissed", short_hash).dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+fn close_github_review_issue(
+    labels: &ReviewLabelsConfig,
+    short_hash: &str,
+    required_approvals: usize,
+    opts: RunOpts,
+) -> Result<()> {
+    if !gh::is_available() {
+        println!(
+            "{}",
+            "Warning: GitHub CLI (gh) not found. Marking as approved locally only.".yellow()
+        );
+        println!("{}", format!("Commit {} approved", short_hash).green());
+        return Ok(());
+    }
+
+    // Search for the review issue
+    let search_query = format!("[Review] in:title {} in:title is:open", short_hash);
+
+    if opts.verbose {
+        println!("{} Searching for review issue...", "[INFO]".cyan());
+    }
+
+    let output = gh::run(
+        &[
+            "issue",
+            "list",
+            "--search",
+            &search_query,
+            "--json",
+            "number",
+            "--limit",
+            "1",
+        ],
+        opts,
+    )
+    .context("Failed to search for GitHub issues")?;
+
+    if output.success {
+        if let Some(issue) = first_issue(&output.stdout)? {
+            let issue_num = issue.number;
+            let issue_num_str = issue_num.to_string();
+
+            let mut close_comment = APPROVAL_COMMENT.to_string();
+            if required_approvals > 1 {
+                let approvals = record_github_approval(&issue_num_str, required_approvals, opts)?;
+                if approvals < required_approvals {
+                    print_approval_progress(short_hash, approvals, required_approvals);
+                    return Ok(());
+                }
+                close_comment = format!("Approved by {} reviewers, closing review.", approvals);
+            }
+
+            // Remove pending/concern labels and add accepted label
+            if opts.verbose {
+                println!(
+                    "{} Updating labels on issue #{}",
+                    "[INFO]".cyan(),
+                    issue_num
+                );
+            }
+
+            let _ = gh::run(
+                &[
+                    "issue",
+                    "edit",
+                    &issue_num_str,
+                    "--remove-label",
+                    &labels.pending,
+                ],
+                opts,
+            );
+
+            let _ = gh::run(
+                &[
+                    "issue",
+                    "edit",
+                    &issue_num_str,
+                    "--remove-label",
+                    &labels.concern,
+                ],
+                opts,
+            );
+
+            let _ = gh::run(
+                &[
+                    "issue",
+                    "edit",
+                    &issue_num_str,
+                    "--add-label",
+                    &labels.accepted,
+                ],
+                opts,
+            );
+
+            if opts.verbose {
+                println!("{} Closing issue #{}", "[INFO]".cyan(), issue_num);
+            }
+
+            let close_output = gh::run(
+                &[
+                    "issue",
+                    "close",
+                    &issue_num_str,
+                    "--comment",
+                    &close_comment,
+                ],
+                opts,
+            )
+            .context("Failed to close GitHub issue")?;
+
+            if close_output.success {
+                println!(
+                    "{}",
+                    format!(
+                        "Commit {} approved and review issue #{} closed (label: {})",
+                        short_hash, issue_num, labels.accepted
+                    )
+                    .green()
+                );
+            } else {
+                println!(
+                    "{}",
+                    format!("Commit {} approved (issue close failed)", short_hash).yellow()
+                );
+            }
+        } else {
+            println!(
+                "{}",
+                format!(
+                    "Commit {} approved (no open review issue found)",
+                    short_hash
+                )
+                .green()
+            );
+        }
+    } else {
+        println!("{}", format!("Commit {} approved", short_hash).green());
+    }
+
+    Ok(())
+}
+
+/// Label ids by name on the Gitea server, creating any review label that
+/// does not exist yet.
+fn gitea_review_labels(
+    gitea: &Gitea,
+    labels: &ReviewLabelsConfig,
+    opts: RunOpts,
+) -> HashMap<String, i64> {
+    let mut ids: HashMap<String, i64> = gitea
+        .get(&gitea.repo_path("/labels?limit=100"), opts.for_reads())
+        .ok()
+        .and_then(|v| v.as_array().cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|l| Some((l["name"].as_str()?.to_string(), l["id"].as_i64()?)))
+        .collect();
+    let specs = match label_taxonomy(labels) {
+        Ok(specs) => specs,
+        Err(e) => {
+            println!("{}", format!("Warning: {}", e).yellow());
+            return ids;
+        }
+    };
+    for spec in specs {
+        if ids.contains_key(&spec.name) {
+            continue;
+        }
+        let body = json!({
+            "name": spec.name,
+            "color": format!("#{}", spec.color),
+            "description": spec.description,
+        });
+        if let Ok(created) = gitea.post(&gitea.repo_path("/labels"), &body, opts)
+            && let Some(id) = created["id"].as_i64()
+        {
+            ids.insert(spec.name, id);
+        }
+    }
+    ids
+}
+
+/// Swaps the `remove` labels on issue `number` for the `add` ones. Labels
+/// the issue does not carry are skipped.
+fn gitea_relabel(
+    gitea: &Gitea,
+    ids: &HashMap<String, i64>,
+    number: u64,
+    remove: &[&str],
+    add: &[&str],
+    opts: RunOpts,
+) {
+    if opts.verbose {
+        println!("{} Updating labels on issue #{}", "[INFO]".cyan(), number);
+    }
+    for id in remove.iter().filter_map(|name| ids.get(*name)) {
+        let path = format!("/issues/{}/labels/{}", number, id);
+        let _ = gitea.delete(&gitea.repo_path(&path), opts);
+    }
+    let add: Vec<i64> = add
+        .iter()
+        .filter_map(|name| ids.get(*name).copied())
+        .collect();
+    if !add.is_empty() {
+        let path = format!("/issues/{}/labels", number);
+        let _ = gitea.post(&gitea.repo_path(&path), &json!({ "labels": add }), opts);
+    }
+}
+
+/// True when `issue`, as a forge returns it, is the review issue for `short`.
+fn is_review_issue_for(issue: &Value, short: &str) -> bool {
+    issue["title"]
+        .as_str()
+        .is_some_and(|t| t.starts_with("[Review]") && t.contains(short))
+}
+
+/// The open review issue for `short` among Gitea's `/issues` results.
+fn gitea_review_issue<'a>(issues: &'a Value, short: &str) -> Option<&'a Value> {
+    issues
+        .as_array()?
+        .iter()
+        .find(|issue| is_review_issue_for(issue, short))
+}
+
+fn find_gitea_review_issue(gitea: &Gitea, short: &str, opts: RunOpts) -> Result<Option<Value>> {
+    if opts.verbose {
+        println!("{} Searching for review issue...", "[INFO]".cyan());
+    }
+    let path = format!("/issues?state=open&type=issues&limit=50&q={}", short);
+    let issues = gitea
+        .get(&gitea.repo_path(&path), opts.for_reads())
+        .context("Failed to search for Gitea issues")?;
+    Ok(gitea_review_issue(&issues, short).cloned())
+}
+
+fn comment_on_gitea_issue(gitea: &Gitea, number: u64, body: &str, opts: RunOpts) -> Result<()> {
+    let path = format!("/issues/{}/comments", number);
+    gitea.post(&gitea.repo_path(&path), &json!({ "body": body }), opts)?;
+    Ok(())
+}
+
+fn close_gitea_issue(gitea: &Gitea, number: u64, opts: RunOpts) -> Result<()> {
+    let path = format!("/issues/{}", number);
+    gitea.patch(&gitea.repo_path(&path), &json!({ "state": "closed" }), opts)?;
+    Ok(())
+}
+
+/// Sets the review status on `commit_hash`. Gitea has no server-side
+/// workflow to do it, so approvals and dismissals set it too.
+fn set_gitea_status(
+    gitea: &Gitea,
+    config: &Config,
+    commit_hash: &str,
+    state: &str,
+    description: String,
+    target_url: &str,
+    opts: RunOpts,
+) {
+    let sha = git::resolve_commit_hash(commit_hash, opts.for_reads())
+        .unwrap_or_else(|_| commit_hash.to_string());
+    if opts.verbose {
+        println!(
+            "{} Setting commit status '{}' to '{}' for {}",
+            "[INFO]".cyan(),
+            config.review.status.context,
+            state,
+            short_hash(&sha)
+        );
+    }
+    let body = json!({
+        "state": state,
+        "context": config.review.status.context,
+        "description": truncate_description(description),
+        "target_url": target_url,
+    });
+    let path = format!("/statuses/{}", sha);
+    if let Err(e) = gitea.post(&gitea.repo_path(&path), &body, opts) {
+        println!(
+            "{}",
+            format!("Warning: Failed to set commit status: {}", e).yellow()
+        );
+    }
+}
+
+fn open_gitea_review_issue(
+    gitea: &Gitea,
+    review: &ReviewConfig,
+    reviewers: &[String],
+    title: &str,
+    body: &str,
+    opts: RunOpts,
+) {
+    if let Err(e) = gitea.require_token() {
+        println!("{}", format!("Warning: {}", e).yellow());
+        return;
+    }
+    let ids = gitea_review_labels(gitea, &review.labels, opts);
+    let labels: Vec<i64> = ids
+        .get(&review.labels.pending)
+        .into_iter()
+        .copied()
+        .collect();
+    let issue = json!({
+        "title": title,
+        "body": body,
+        "assignees": reviewers,
+        "labels": labels,
+    });
+    match gitea.post(&gitea.repo_path("/issues"), &issue, opts) {
+        Ok(created) => {
+            if let Some(url) = created["html_url"].as_str() {
+                println!("{} {}", "Review issue created:".green(), url);
+            }
+        }
+        Err(e) => println!(
+            "{}",
+            format!("Warning: Failed to create Gitea issue: {}", e).yellow()
+        ),
+    }
+}
+
+/// Comments an approval on the issue and returns the number of distinct
+/// approvers, including the token's owner.
+fn record_gitea_approval(
+    gitea: &Gitea,
+    number: u64,
+    required: usize,
+    opts: RunOpts,
+) -> Result<usize> {
+    let path = format!("/issues/{}/comments", number);
+    let comments = gitea
+        .get(&gitea.repo_path(&path), opts.for_reads())
+        .context("Failed to read review issue comments")?;
+    let approvers = approvers_in(&comments, "/body", "/user/login");
+
+    if let Some(me) = gitea.current_user(opts.for_reads())
+        && approvers.contains(&me)
+    {
+        if opts.verbose {
+            println!("{} {} has already approved", "[INFO]".cyan(), me);
+        }
+        return Ok(approvers.len());
+    }
+
+    let count = approvers.len() + 1;
+    let comment = format!("{} ({}/{})", APPROVAL_COMMENT, count, required);
+    comment_on_gitea_issue(gitea, number, &comment, opts)
+        .context("Failed to comment on review issue")?;
+    Ok(count)
+}
+
+fn approve_gitea_review(config: &Config, commit_hash: &str, opts: RunOpts) -> Result<()> {
+    let short = short_hash(commit_hash);
+    let labels = &config.review.labels;
+    let required = config.review.required_approvals;
+    let gitea = Gitea::from_config(&config.review.gitea, opts)?;
+    gitea.require_token()?;
+
+    let Some(issue) = find_gitea_review_issue(&gitea, short, opts)? else {
+        println!(
+            "{}",
+            format!("Commit {} approved (no open review issue found)", short).green()
+        );
+        return Ok(());
+    };
+    let number = issue["number"].as_u64().unwrap_or_default();
+    let issue_url = issue["html_url"].as_str().unwrap_or_default();
+
+    let mut close_comment = APPROVAL_COMMENT.to_string();
+    if required > 1 {
+        let approvals = record_gitea_approval(&gitea, number, required, opts)?;
+        if approvals < required {
+            print_approval_progress(short, approvals, required);
+            return Ok(());
+        }
+        close_comment = format!("Approved by {} reviewers, closing review.", approvals);
+    }
+
+    let ids = gitea_review_labels(&gitea, labels, opts);
+    gitea_relabel(
+        &gitea,
+        &ids,
+        number,
+        &[&labels.pending, &labels.concern],
+        &[&labels.accepted],
+        opts,
+    );
+    comment_on_gitea_issue(&gitea, number, &close_comment, opts)?;
+    if opts.verbose {
+        println!("{} Closing issue #{}", "[INFO]".cyan(), number);
+    }
+    close_gitea_issue(&gitea, number, opts).context("Failed to close Gitea issue")?;
+    set_gitea_status(
+        &gitea,
+        config,
+        commit_hash,
+        "success",
+        "Review approved".to_string(),
+        issue_url,
+        opts,
+    );
+    println!(
+        "{}",
+        format!(
+            "Commit {} approved and review issue #{} closed (label: {})",
+            short, number, labels.accepted
+        )
+        .green()
+    );
+    Ok(())
+}
+
+fn raise_gitea_concern(
+    config: &Config,
+    commit_hash: &str,
+    message: &str,
+    severity: Option<ConcernSeverity>,
+    opts: RunOpts,
+) -> Result<()> {
+    let short = short_hash(commit_hash);
+    let labels = &config.review.labels;
+    let gitea = Gitea::from_config(&config.review.gitea, opts)?;
+    gitea.require_token()?;
+
+    let Some(issue) = find_gitea_review_issue(&gitea, short, opts)? else {
+        println!(
+            "{}",
+            format!("Warning: No open review issue found for commit {}", short).yellow()
+        );
+        println!("   Run 'tbdflow review --trigger' first to create the review issue.");
+        return Ok(());
+    };
+    let number = issue["number"].as_u64().unwrap_or_default();
+    let issue_url = issue["html_url"].as_str().unwrap_or_default();
+
+    // A re-raised concern replaces the previous severity
+    let mut remove = vec![labels.pending.as_str()];
+    let mut add = vec![labels.concern.as_str()];
+    if let Some(severity) = severity {
+        remove.extend(
+            [
+                ConcernSeverity::Minor,
+                ConcernSeverity::Major,
+                ConcernSeverity::Critical,
+            ]
+            .iter()
+            .filter(|s| **s != severity)
+            .map(|s| s.label(labels)),
+        );
+        add.push(severity.label(labels));
+    }
+    let ids = gitea_review_labels(&gitea, labels, opts);
+    gitea_relabel(&gitea, &ids, number, &remove, &add, opts);
+
+    let comment = match severity {
+        Some(s) => format!("**Concern Raised** ({})\n\n{}", s.as_str(), message),
+        None => format!("**Concern Raised**\n\n{}", message),
+    };
+    comment_on_gitea_issue(&gitea, number, &comment, opts)?;
+
+    if opts.verbose {
+        println!(
+            "{} Updating issue body with concern checklist item",
+            "[INFO]".cyan()
+        );
+    }
+    let body = with_concern_item(
+        issue["body"].as_str().unwrap_or_default().to_string(),
+        message,
+    );
+    let path = format!("/issues/{}", number);
+    gitea.patch(&gitea.repo_path(&path), &json!({ "body": body }), opts)?;
+
+    let vars = StatusVars {
+        message,
+        severity,
+        sha: commit_hash,
+        issue_url,
+    };
+    let (state, template) = concern_commit_status(severity, &config.review);
+    let target_url = match &config.review.status.target_url {
+        Some(template) => render_status_template(template, &vars),
+        None => issue_url.to_string(),
+    };
+    set_gitea_status(
+        &gitea,
+        config,
+        commit_hash,
+        state,
+        render_status_template(template, &vars),
+        &target_url,
+        opts,
+    );
+
+    let applied = match severity {
+        Some(s) => format!("{}, {}", labels.concern, s.label(labels)),
+        None => labels.concern.clone(),
+    };
+    println!(
+        "{}",
+        format!(
+            "Concern raised on issue #{} for commit {} (label: {})",
+            number, short, applied
+        )
+        .yellow()
+    );
+    Ok(())
+}
+
+fn dismiss_gitea_review(
+    config: &Config,
+    commit_hash: &str,
+    message: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    let short = short_hash(commit_hash);
+    let labels = &config.review.labels;
+    let gitea = Gitea::from_config(&config.review.gitea, opts)?;
+    gitea.require_token()?;
+
+    let Some(issue) = find_gitea_review_issue(&gitea, short, opts)? else {
+        println!(
+            "{}",
+            format!(
+                "Review for {} dismissed (no open review issue found)",
+                short
+            )
+            .dimmed()
+        );
+        return Ok(());
+    };
+    let number = issue["number"].as_u64().unwrap_or_default();
+    let issue_url = issue["html_url"].as_str().unwrap_or_default();
+
+    let ids = gitea_review_labels(&gitea, labels, opts);
+    gitea_relabel(
+        &gitea,
+        &ids,
+        number,
+        &[&labels.pending, &labels.concern],
+        &[&labels.dismissed],
+        opts,
+    );
+    let comment = format!(
+        "**Dismissed** via `tbdflow review --dismiss`\n\nReason: {}",
+        message
+    );
+    comment_on_gitea_issue(&gitea, number, &comment, opts)?;
+    close_gitea_issue(&gitea, number, opts).context("Failed to close Gitea issue")?;
+    set_gitea_status(
+        &gitea,
+        config,
+        commit_hash,
+        "success",
+        format!("Review dismissed: {}", message),
+        issue_url,
+        opts,
+    );
+    println!(
+        "{}",
+        format!(
+            "Review for commit {} dismissed and issue #{} closed (label: {})",
+            short, number, labels.dismissed
+        )
+        .dimmed()
+    );
+    Ok(())
+}
+
+/// Bitbucket's build status state for a commit status state.
+fn bitbucket_state(state: &str) -> &'static str {
+    match state {
+        "success" => "SUCCESSFUL",
+        "failure" => "FAILED",
+        _ => "INPROGRESS",
+    }
+}
+
+/// Sets the review build status on `commit_hash`, linking to `url` (the
+/// review issue) or else the commit.
+fn set_bitbucket_status(
+    bitbucket: &Bitbucket,
+    config: &Config,
+    commit_hash: &str,
+    state: &str,
+    description: String,
+    url: Option<&str>,
+    opts: RunOpts,
+) {
+    let sha = git::resolve_commit_hash(commit_hash, opts.for_reads())
+        .unwrap_or_else(|_| commit_hash.to_string());
+    let context = &config.review.status.context;
+    if opts.verbose {
+        println!(
+            "{} Setting build status '{}' to '{}' for {}",
+            "[INFO]".cyan(),
+            context,
+            state,
+            short_hash(&sha)
+        );
+    }
+    let url = url
+        .filter(|u| !u.is_empty())
+        .map_or_else(|| commit_url(&bitbucket.repo_url(), &sha), str::to_string);
+    let body = json!({
+        "key": context,
+        "name": context,
+        "state": bitbucket_state(state),
+        "description": truncate_description(description),
+        "url": url,
+    });
+    let path = format!("/commit/{}/statuses/build", sha);
+    if let Err(e) = bitbucket.post(&bitbucket.repo_path(&path), &body, opts) {
+        println!(
+            "{}",
+            format!("Warning: Failed to set build status: {}", e).yellow()
+        );
+    }
+}
+
+/// The open review issue for `short` among a page of Bitbucket issues.
+fn bitbucket_review_issue<'a>(page: &'a Value, short: &str) -> Option<&'a Value> {
+    page["values"].as_array()?.iter().find(|issue| {
+        let open = matches!(issue["state"].as_str(), Some("new" | "open" | "on hold"));
+        open && is_review_issue_for(issue, short)
+    })
+}
+
+/// The open review issue for `short`, or `None` when there is none or the
+/// repository's issue tracker is turned off.
+fn find_bitbucket_review_issue(bitbucket: &Bitbucket, short: &str, opts: RunOpts) -> Option<Value> {
+    if opts.verbose {
+        println!("{} Searching for review issue...", "[INFO]".cyan());
+    }
+    let path = bitbucket.repo_path("/issues?pagelen=50&sort=-created_on");
+    let page = bitbucket.get(&path, opts.for_reads()).ok()?;
+    bitbucket_review_issue(&page, short).cloned()
+}
+
+fn bitbucket_issue_link(issue: &Value) -> Option<&str> {
+    issue["links"]["html"]["href"].as_str()
+}
+
+fn comment_on_bitbucket_issue(
+    bitbucket: &Bitbucket,
+    id: u64,
+    body: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    let path = format!("/issues/{}/comments", id);
+    bitbucket.post(
+        &bitbucket.repo_path(&path),
+        &json!({ "content": { "raw": body } }),
+        opts,
+    )?;
+    Ok(())
+}
+
+fn update_bitbucket_issue(
+    bitbucket: &Bitbucket,
+    id: u64,
+    fields: &Value,
+    opts: RunOpts,
+) -> Result<()> {
+    let path = format!("/issues/{}", id);
+    bitbucket.put(&bitbucket.repo_path(&path), fields, opts)?;
+    Ok(())
+}
+
+/// Opens the review issue and marks `commits` pending. Without an issue
+/// tracker, the build status alone tracks the review.
+fn open_bitbucket_review(
+    bitbucket: &Bitbucket,
+    config: &Config,
+    commits: &[&str],
+    title: &str,
+    body: &str,
+    opts: RunOpts,
+) {
+    if let Err(e) = bitbucket.require_credentials() {
+        println!("{}", format!("Warning: {}", e).yellow());
+        return;
+    }
+    let issue = json!({
+        "title": title,
+        "content": { "raw": body },
+        "kind": "task",
+    });
+    let link = match bitbucket.post(&bitbucket.repo_path("/issues"), &issue, opts) {
+        Ok(created) => {
+            let link = bitbucket_issue_link(&created).map(str::to_string);
+            if let Some(link) = &link {
+                println!("{} {}", "Review issue created:".green(), link);
+            }
+            link
+        }
+        Err(e) => {
+            println!(
+                "{}",
+                format!(
+                    "Warning: Failed to create Bitbucket issue ({}). Is the issue tracker enabled? Tracking the review with build statuses only.",
+                    e
+                )
+                .yellow()
+            );
+            None
+        }
+    };
+    for commit in commits {
+        set_bitbucket_status(
+            bitbucket,
+            config,
+            commit,
+            "pending",
+            "Review pending".to_string(),
+            link.as_deref(),
+            opts,
+        );
+    }
+}
+
+/// Comments an approval on the issue and returns the number of distinct
+/// approvers, including the owner of the app password.
+fn record_bitbucket_approval(
+    bitbucket: &Bitbucket,
+    id: u64,
+    required: usize,
+    opts: RunOpts,
+) -> Result<usize> {
+    let path = format!("/issues/{}/comments?pagelen=100", id);
+    let page = bitbucket
+        .get(&bitbucket.repo_path(&path), opts.for_reads())
+        .context("Failed to read review issue comments")?;
+    let approvers = approvers_in(&page["values"], "/content/raw", "/user/nickname");
+
+    if let Some(me) = bitbucket.current_user(opts.for_reads())
+        && approvers.contains(&me)
+    {
+        if opts.verbose {
+            println!("{} {} has already approved", "[INFO]".cyan(), me);
+        }
+        return Ok(approvers.len());
+    }
+
+    let count = approvers.len() + 1;
+    let comment = format!("{} ({}/{})", APPROVAL_COMMENT, count, required);
+    comment_on_bitbucket_issue(bitbucket, id, &comment, opts)
+        .context("Failed to comment on review issue")?;
+    Ok(count)
+}
+
+fn approve_bitbucket_review(config: &Config, commit_hash: &str, opts: RunOpts) -> Result<()> {
+    let short = short_hash(commit_hash);
+    let required = config.review.required_approvals;
+    let bitbucket = Bitbucket::from_config(&config.review.bitbucket, opts)?;
+    bitbucket.require_credentials()?;
+
+    let issue = find_bitbucket_review_issue(&bitbucket, short, opts);
+    if let Some(issue) = &issue {
+        let id = issue["id"].as_u64().unwrap_or_default();
+        let mut close_comment = APPROVAL_COMMENT.to_string();
+        if required > 1 {
+            let approvals = record_bitbucket_approval(&bitbucket, id, required, opts)?;
+            if approvals < required {
+                print_approval_progress(short, approvals, required);
+                return Ok(());
+            }
+            close_comment = format!("Approved by {} reviewers, closing review.", approvals);
+        }
+        comment_on_bitbucket_issue(&bitbucket, id, &close_comment, opts)?;
+        if opts.verbose {
+            println!("{} Resolving issue #{}", "[INFO]".cyan(), id);
+        }
+        update_bitbucket_issue(&bitbucket, id, &json!({ "state": "resolved" }), opts)
+            .context("Failed to resolve Bitbucket issue")?;
+    }
+    let link = issue.as_ref().and_then(bitbucket_issue_link);
+    set_bitbucket_status(
+        &bitbucket,
+        config,
+        commit_hash,
+        "success",
+        "Review approved".to_string(),
+        link,
+        opts,
+    );
+    match &issue {
+        Some(issue) => println!(
+            "{}",
+            format!(
+                "Commit {} approved and review issue #{} resolved",
+                short, issue["id"]
+            )
+            .green()
+        ),
+        None => println!(
+            "{}",
+            format!("Commit {} approved (build status only)", short).green()
+        ),
+    }
+    Ok(())
+}
+
+fn raise_bitbucket_concern(
+    config: &Config,
+    commit_hash: &str,
+    message: &str,
+    severity: Option<ConcernSeverity>,
+    opts: RunOpts,
+) -> Result<()> {
+    let short = short_hash(commit_hash);
+    let bitbucket = Bitbucket::from_config(&config.review.bitbucket, opts)?;
+    bitbucket.require_credentials()?;
+
+    let issue = find_bitbucket_review_issue(&bitbucket, short, opts);
+    if let Some(issue) = &issue {
+        let id = issue["id"].as_u64().unwrap_or_default();
+        let comment = match severity {
+            Some(s) => format!("**Concern Raised** ({})\n\n{}", s.as_str(), message),
+            None => format!("**Concern Raised**\n\n{}", message),
+        };
+        comment_on_bitbucket_issue(&bitbucket, id, &comment, opts)?;
+
+        // Bitbucket issues have no labels: the state says a concern is open
+        // and the priority carries its severity.
+        let body = with_concern_item(
+            issue["content"]["raw"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            message,
+        );
+        let mut fields = json!({ "state": "open", "content": { "raw": body } });
+        if let Some(severity) = severity {
+            fields["priority"] = json!(severity.as_str());
+        }
+        if opts.verbose {
+            println!("{} Updating issue #{}", "[INFO]".cyan(), id);
+        }
+        update_bitbucket_issue(&bitbucket, id, &fields, opts)?;
+    }
+
+    let link = issue
+        .as_ref()
+        .and_then(bitbucket_issue_link)
+        .unwrap_or_default();
+    let vars = StatusVars {
+        message,
+        severity,
+        sha: commit_hash,
+        issue_url: link,
+    };
+    let (state, template) = concern_commit_status(severity, &config.review);
+    let target_url = config
+        .review
+        .status
+        .target_url
+        .as_ref()
+        .map(|template| render_status_template(template, &vars));
+    set_bitbucket_status(
+        &bitbucket,
+        config,
+        commit_hash,
+        state,
+        render_status_template(template, &vars),
+        target_url.as_deref().or(Some(link)),
+        opts,
+    );
+
+    let applied = severity.map_or(String::new(), |s| format!(" (priority: {})", s.as_str()));
+    match &issue {
+        Some(issue) => println!(
+            "{}",
+            format!(
+                "Concern raised on issue #{} for commit {}{}",
+                issue["id"], short, applied
+            )
+            .yellow()
+        ),
+        None => println!(
+            "{}",
+            format!(
+                "Concern raised on commit {}{} (build status only)",
+                short, applied
+            )
+            .yellow()
+        ),
+    }
+    Ok(())
+}
+
+fn dismiss_bitbucket_review(
+    config: &Config,
+    commit_hash: &str,
+    message: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    let short = short_hash(commit_hash);
+    let bitbucket = Bitbucket::from_config(&config.review.bitbucket, opts)?;
+    bitbucket.require_credentials()?;
+
+    let issue = find_bitbucket_review_issue(&bitbucket, short, opts);
+    if let Some(issue) = &issue {
+        let id = issue["id"].as_u64().unwrap_or_default();
+        let comment = format!(
+            "**Dismissed** via `tbdflow review --dismiss`\n\nReason: {}",
+            message
+        );
+        comment_on_bitbucket_issue(&bitbucket, id, &comment, opts)?;
+        update_bitbucket_issue(&bitbucket, id, &json!({ "state": "wontfix" }), opts)
+            .context("Failed to close Bitbucket issue")?;
+    }
+    set_bitbucket_status(
+        &bitbucket,
+        config,
+        commit_hash,
+        "success",
+        format!("Review dismissed: {}", message),
+        issue.as_ref().and_then(bitbucket_issue_link),
+        opts,
+    );
+    match &issue {
+        Some(issue) => println!(
+            "{}",
+            format!(
+                "Review for commit {} dismissed and issue #{} closed (wontfix)",
+                short, issue["id"]
+            )
+            .dimmed()
+        ),
+        None => println!(
+            "{}",
+            format!("Review for {} dismissed (build status only)", short).dimmed()
+        ),
+    }
+    Ok(())
+}
+
+/// The full hash reviews are recorded under, for a hash as the user typed it.
+fn noted_commit(commit_hash: &str, opts: RunOpts) -> Result<String> {
+    git::resolve_commit_hash(&format!("{}^{{commit}}", commit_hash), opts.for_reads())
+}
+
+/// Where the `git-notes` and `in-repo` strategies record review events.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ReviewStore {
+    /// Notes under `refs/notes/tbdflow-reviews`.
+    Notes,
+    /// YAML files under `.tbdflow/reviews/` committed to trunk.
+    Ledger,
+}
+
+impl ReviewStore {
+    fn for_config(config: &Config) -> Self {
+        if config.review.strategy == ReviewStrategy::InRepo {
+            ReviewStore::Ledger
+        } else {
+            ReviewStore::Notes
+        }
+    }
+
+    /// Where the events are, for messages.
+    fn describe(&self) -> String {
+        match self {
+            ReviewStore::Notes => format!("git notes ({})", review_notes::NOTES_REF),
+            ReviewStore::Ledger => review_ledger::LEDGER_DIR.to_string(),
+        }
+    }
+
+    /// Brings in what origin has recorded.
+    fn sync(&self, config: &Config, opts: RunOpts) -> Result<()> {
+        match self {
+            ReviewStore::Notes => review_notes::pull(opts),
+            ReviewStore::Ledger => review_ledger::fetch(config, opts),
+        }
+    }
+
+    fn read(
+        &self,
+        config: &Config,
+        commit: &str,
+        opts: RunOpts,
+    ) -> Result<Vec<review_notes::NoteEvent>> {
+        match self {
+            ReviewStore::Notes => Ok(review_notes::read(commit, opts.for_reads())),
+            ReviewStore::Ledger => Ok(review_ledger::read(config, commit, opts)?
+                .map(|ledger| ledger.events)
+                .unwrap_or_default()),
+        }
+    }
+
+    /// Every reviewed commit with its subject and events.
+    fn read_all(
+        &self,
+        config: &Config,
+        opts: RunOpts,
+    ) -> Result<Vec<(String, String, Vec<review_notes::NoteEvent>)>> {
+        match self {
+            ReviewStore::Notes => Ok(review_notes::read_all(opts.for_reads())
+                .into_iter()
+                .map(|(commit, events)| {
+                    let subject =
+                        git::get_commit_subject(&commit, opts.for_reads()).unwrap_or_default();
+                    (commit, subject, events)
+                })
+                .collect()),
+            ReviewStore::Ledger => Ok(review_ledger::read_all(config, opts)?
+                .into_iter()
+                .map(|ledger| (ledger.commit, ledger.subject, ledger.events))
+                .collect()),
+        }
+    }
+
+    /// Adds `event` to `commit`'s record and shares it with origin. Returns
+    /// every event on the commit.
+    fn write(
+        &self,
+        config: &Config,
+        commit: &str,
+        event: review_notes::NoteEvent,
+        opts: RunOpts,
+    ) -> Result<Vec<review_notes::NoteEvent>> {
+        match self {
+            ReviewStore::Notes => write_review_note(commit, event, opts),
+            ReviewStore::Ledger => {
+                let subject = git::get_commit_subject(commit, opts.for_reads()).unwrap_or_default();
+                Ok(review_ledger::record(config, commit, &subject, event, opts)?.events)
+            }
+        }
+    }
+}
+
+/// Pulls origin's review notes, adds `event` to `commit`'s note and pushes
+/// them back. Returns every event on the commit. A failed push only warns:
+/// the note stays local and goes out with the next review action.
+fn write_review_note(
+    commit: &str,
+    event: review_notes::NoteEvent,
+    opts: RunOpts,
+) -> Result<Vec<review_notes::NoteEvent>> {
+    if let Err(e) = review_notes::pull(opts) {
+        println!(
+            "{}",
+            format!("Warning: could not merge review notes from origin: {}", e).yellow()
+        );
+    }
+    let events = review_notes::append(commit, event, opts)?;
+    if let Err(e) = review_notes::push(opts) {
+        println!(
+            "{}",
+            format!(
+                "Warning: could not push review notes: {}. They are kept locally and go out with the next review action.",
+                e
+            )
+            .yellow()
+        );
+    }
+    Ok(events)
+}
+
+fn note_event(action: review_notes::NoteAction, opts: RunOpts) -> review_notes::NoteEvent {
+    review_notes::NoteEvent::now(git::get_user_identity(opts.for_reads()), action)
+}
+
+fn request_recorded_review(
+    config: &Config,
+    hashes: &[&str],
+    reviewers: &[String],
+    opts: RunOpts,
+) -> Result<()> {
+    let store = ReviewStore::for_config(config);
+    for hash in hashes {
+        let mut event = note_event(review_notes::NoteAction::Requested, opts);
+        event.reviewers = reviewers.to_vec();
+        store.write(config, hash, event, opts)?;
+    }
+    println!(
+        "{}",
+        format!("Review recorded in {}.", store.describe()).dimmed()
+    );
+    Ok(())
+}
+
+fn approve_recorded_review(config: &Config, commit_hash: &str, opts: RunOpts) -> Result<()> {
+    let store = ReviewStore::for_config(config);
+    let commit = noted_commit(commit_hash, opts)?;
+    let short = short_hash(&commit);
+    let required = config.review.required_approvals;
+    let event = note_event(review_notes::NoteAction::Approved, opts);
+    let me = event.author.clone();
+    let mut events = store.read(config, &commit, opts)?;
+    if !events
+        .iter()
+        .any(|e| e.action == review_notes::NoteAction::Approved && e.author == me)
+    {
+        events = store.write(config, &commit, event, opts)?;
+    } else if opts.verbose {
+        println!("{} {} has already approved", "[INFO]".cyan(), me);
+    }
+    let approvals = review_notes::fold(&events, required).map_or(0, |s| s.approvers.len());
+    if approvals < required {
+        print_approval_progress(short, approvals, required);
+    } else {
+        println!(
+            "{}",
+            i18n::tf(Msg::CommitApproved, &[("commit", short)]).green()
+        );
+    }
+    Ok(())
+}
+
+fn raise_recorded_concern(
+    config: &Config,
+    commit_hash: &str,
+    message: &str,
+    severity: Option<ConcernSeverity>,
+    opts: RunOpts,
+) -> Result<()> {
+    let commit = noted_commit(commit_hash, opts)?;
+    let mut event = note_event(review_notes::NoteAction::Concern, opts);
+    event.message = Some(message.to_string());
+    event.severity = severity;
+    ReviewStore::for_config(config).write(config, &commit, event, opts)?;
+    let level = severity.map_or(String::new(), |s| format!(" ({})", s.as_str()));
+    println!(
+        "{}",
+        format!("CONCERN{} on {}: {}", level, short_hash(&commit), message).yellow()
+    );
+    Ok(())
+}
+
+fn dismiss_recorded_review(
+    config: &Config,
+    commit_hash: &str,
+    message: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    let commit = noted_commit(commit_hash, opts)?;
+    let mut event = note_event(review_notes::NoteAction::Dismissed, opts);
+    event.message = Some(message.to_string());
+    ReviewStore::for_config(config).write(config, &commit, event, opts)?;
+    println!(
+        "{}",
+        format!("Review for {} dismissed: {}", short_hash(&commit), message).dimmed()
+    );
+    Ok(())
+}
+
+fn link_fix_to_recorded_review(
+    config: &Config,
+    review_short: &str,
+    fix_hash: &str,
+    fix_subject: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    let store = ReviewStore::for_config(config);
+    let Ok(commit) = noted_commit(review_short, opts) else {
+        println!(
+            "{}",
+            format!("Warning: Cannot find the reviewed commit {}", review_short).yellow()
+        );
+        return Ok(());
+    };
+    let _ = store.sync(config, opts);
+    if store.read(config, &commit, opts)?.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "Warning: No review recorded in {} for commit {}",
+                store.describe(),
+                review_short
+            )
+            .yellow()
+        );
+        return Ok(());
+    }
+    let mut event = note_event(review_notes::NoteAction::Fix, opts);
+    event.message = Some(fix_subject.to_string());
+    event.commit = Some(fix_hash.to_string());
+    store.write(config, &commit, event, opts)?;
+    println!(
+        "{}",
+        format!(
+            "Linked fix-forward {} to the review of {} in {}.",
+            short_hash(fix_hash),
+            review_short,
+            store.describe()
+        )
+        .green()
+    );
+    Ok(())
+}
+
+/// `tbdflow review status <commit>`: the review recorded in git notes, or in
+/// the ledger on trunk for the `in-repo` strategy.
+pub fn handle_review_status(
+    config: &Config,
+    commit_hash: &str,
+    json: bool,
+    opts: RunOpts,
+) -> Result<()> {
+    let store = ReviewStore::for_config(config);
+    let commit = noted_commit(commit_hash, opts)?;
+    let short = short_hash(&commit);
+    if !opts.dry_run
+        && let Err(e) = store.sync(config, opts)
+        && !json
+    {
+        println!(
+            "{}",
+            format!("Warning: could not fetch reviews from origin: {}", e).yellow()
+        );
+    }
+    let events = store.read(config, &commit, opts)?;
+    let required = config.review.required_approvals;
+    let status = review_notes::fold(&events, required);
+
+    if json {
+        let response = ReviewStatusResponse {
+            commit: commit.clone(),
+            state: status.as_ref().map(|s| s.state.as_str().to_string()),
+            approvals: status.as_ref().map_or(0, |s| s.approvers.len()),
+            required_approvals: required,
+            approvers: status
+                .as_ref()
+                .map(|s| s.approvers.clone())
+                .unwrap_or_default(),
+            reviewers: status
+                .as_ref()
+                .map(|s| s.reviewers.clone())
+                .unwrap_or_default(),
+            open_concerns: status.as_ref().map_or(0, |s| s.open_concerns),
+            events: events
+                .iter()
+                .map(|e| ReviewNoteResponse {
+                    at: e.at.clone(),
+                    author: e.author.clone(),
+                    action: e.action.as_str().to_string(),
+                    message: e.message.clone(),
+                    severity: e.severity.map(|s| s.as_str().to_string()),
+                    commit: e.commit.clone(),
+                })
+                .collect(),
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(response))?
+        );
+        return Ok(());
+    }
+
+    let Some(status) = status else {
+        println!(
+            "{}",
+            format!("No review recorded in {} for {}.", store.describe(), short).yellow()
+        );
+        if !matches!(
+            config.review.strategy,
+            ReviewStrategy::GitNotes | ReviewStrategy::InRepo
+        ) {
+            println!(
+                "{}",
+                "   Only the git-notes and in-repo review strategies record reviews there."
+                    .dimmed()
+            );
+        }
+        return Ok(());
+    };
+    println!("{}", format!("--- Review of {} ---", short).blue());
+    let state = match status.state {
+        ReviewState::Approved => status.state.as_str().green(),
+        ReviewState::Concern => status.state.as_str().yellow(),
+        ReviewState::Pending | ReviewState::Dismissed => status.state.as_str().normal(),
+    };
+    println!(
+        "State:     {} ({}/{} approvals)",
+        state,
+        status.approvers.len(),
+        required
+    );
+    if !status.reviewers.is_empty() {
+        println!("Reviewers: {}", status.reviewers.join(", "));
+    }
+    if !status.approvers.is_empty() {
+        println!("Approved:  {}", status.approvers.join(", "));
+    }
+    if status.open_concerns > 0 {
+        println!("Concerns:  {} without a fix-forward", status.open_concerns);
+    }
+    println!();
+    for event in &events {
+        let mut detail = event.message.clone().unwrap_or_default();
+        if let Some(severity) = event.severity {
+            detail = format!("({}) {}", severity.as_str(), detail);
+        }
+        if let Some(fix) = &event.commit {
+            detail = format!("{} {}", short_hash(fix), detail);
+        }
+        println!(
+            "  {} {:<9} {} {}",
+            event.at.dimmed(),
+            event.action.as_str(),
+            event.author,
+            detail
+        );
+    }
+    Ok(())
+}
+
+/// Where a forge keeps the parts of a review issue and its comments, as JSON
+/// pointers.
+struct IssueFields {
+    url: &'static str,
+    body: &'static str,
+    comment_author: &'static str,
+    comment_body: &'static str,
+    comment_at: &'static str,
+}
+
+const GITHUB_ISSUE: IssueFields = IssueFields {
+    url: "/url",
+    body: "/body",
+    comment_author: "/author/login",
+    comment_body: "/body",
+    comment_at: "/createdAt",
+};
+
+const GITEA_ISSUE: IssueFields = IssueFields {
+    url: "/html_url",
+    body: "/body",
+    comment_author: "/user/login",
+    comment_body: "/body",
+    comment_at: "/created_at",
+};
+
+const BITBUCKET_ISSUE: IssueFields = IssueFields {
+    url: "/links/html/href",
+    body: "/content/raw",
+    comment_author: "/user/nickname",
+    comment_body: "/content/raw",
+    comment_at: "/created_on",
+};
+
+/// What the configured strategy has on record about a commit's review.
+#[derive(Default)]
+struct ReviewRecord {
+    issue: Option<String>,
+    state: Option<String>,
+    labels: Vec<String>,
+    concerns: Vec<ReviewConcernResponse>,
+    comments: Vec<ReviewCommentResponse>,
+}
+
+fn pointer_str<'a>(value: &'a Value, pointer: &str) -> &'a str {
+    value
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+}
+
+/// The checklist items under the review issue's `### Concerns` heading.
+/// Ticked items were resolved by a fix-forward.
+fn concern_items(body: &str) -> Vec<ReviewConcernResponse> {
+    body.lines()
+        .skip_while(|line| line.trim() != "### Concerns")
+        .skip(1)
+        .take_while(|line| !line.starts_with('#') && line.trim() != "---")
+        .filter_map(|line| {
+            let line = line.trim_start();
+            match line.strip_prefix("- [ ] ") {
+                Some(text) => Some((text, false)),
+                None => line
+                    .strip_prefix("- [x] ")
+                    .or_else(|| line.strip_prefix("- [X] "))
+                    .map(|text| (text, true)),
+            }
+        })
+        .map(|(text, resolved)| ReviewConcernResponse {
+            text: text.trim().to_string(),
+            resolved,
+        })
+        .collect()
+}
+
+/// The record of a review issue, as `fields` finds it in the forge's JSON.
+fn issue_record(issue: &Value, comments: &Value, fields: &IssueFields) -> ReviewRecord {
+    let url = pointer_str(issue, fields.url);
+    ReviewRecord {
+        issue: (!url.is_empty()).then(|| url.to_string()),
+        state: issue["state"].as_str().map(str::to_lowercase),
+        labels: issue["labels"]
+            .as_array()
+            .map(|labels| {
+                labels
+                    .iter()
+                    .filter_map(|label| label["name"].as_str())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        concerns: concern_items(pointer_str(issue, fields.body)),
+        comments: comments
+            .as_array()
+            .map(|comments| {
+                comments
+                    .iter()
+                    .map(|c| ReviewCommentResponse {
+                        author: pointer_str(c, fields.comment_author).to_string(),
+                        at: pointer_str(c, fields.comment_at).to_string(),
+                        body: pointer_str(c, fields.comment_body).trim().to_string(),
+                    })
+                    .filter(|c| !c.body.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// The review issue for `short`, open or closed, with its comments.
+fn github_review_record(short: &str, opts: RunOpts) -> Result<Option<ReviewRecord>> {
+    if !gh::is_available() {
+        anyhow::bail!("GitHub CLI (gh) not found. Cannot read the review issue.");
+    }
+    let search_query = format!("[Review] in:title {} in:title", short);
+    let output = gh::run(
+        &[
+            "issue",
+            "list",
+            "--search",
+            &search_query,
+            "--state",
+            "all",
+            "--json",
+            "number,body,url",
+            "--limit",
+            "1",
+        ],
+        opts.for_reads(),
+    )
+    .context("Failed to search for GitHub issues")?;
+    if !output.success {
+        anyhow::bail!(
+            "Could not search for the review issue: {}",
+            output.stderr.trim()
+        );
+    }
+    let Some(issue) = first_issue(&output.stdout)? else {
+        return Ok(None);
+    };
+    let view = gh::run(
+        &[
+            "issue",
+            "view",
+            &issue.number.to_string(),
+            "--json",
+            "url,state,labels,body,comments",
+        ],
+        opts.for_reads(),
+    )
+    .context("Failed to read the review issue")?;
+    if !view.success {
+        anyhow::bail!(
+            "Could not read issue #{}: {}",
+            issue.number,
+            view.stderr.trim()
+        );
+    }
+    Ok(parse_gh_json::<Value>(&view.stdout)?
+        .map(|issue| issue_record(&issue, &issue["comments"], &GITHUB_ISSUE)))
+}
+
+fn gitea_review_record(
+    config: &Config,
+    short: &str,
+    opts: RunOpts,
+) -> Result<Option<ReviewRecord>> {
+    let gitea = Gitea::from_config(&config.review.gitea, opts)?;
+    let path = format!("/issues?state=all&type=issues&limit=50&q={}", short);
+    let issues = gitea
+        .get(&gitea.repo_path(&path), opts.for_reads())
+        .context("Failed to search for Gitea issues")?;
+    let Some(issue) = gitea_review_issue(&issues, short) else {
+        return Ok(None);
+    };
+    let path = format!(
+        "/issues/{}/comments",
+        issue["number"].as_u64().unwrap_or_default()
+    );
+    let comments = gitea
+        .get(&gitea.repo_path(&path), opts.for_reads())
+        .context("Failed to read review issue comments")?;
+    Ok(Some(issue_record(issue, &comments, &GITEA_ISSUE)))
+}
+
+fn bitbucket_review_record(
+    config: &Config,
+    short: &str,
+    opts: RunOpts,
+) -> Result<Option<ReviewRecord>> {
+    let bitbucket = Bitbucket::from_config(&config.review.bitbucket, opts)?;
+    let path = bitbucket.repo_path("/issues?pagelen=50&sort=-created_on");
+    let page = bitbucket
+        .get(&path, opts.for_reads())
+        .context("Failed to search for Bitbucket issues")?;
+    let Some(issue) = page["values"]
+        .as_array()
+        .and_then(|issues| issues.iter().find(|i| is_review_issue_for(i, short)))
+    else {
+        return Ok(None);
+    };
+    let path = format!(
+        "/issues/{}/comments?pagelen=100",
+        issue["id"].as_u64().unwrap_or_default()
+    );
+    let comments = bitbucket
+        .get(&bitbucket.repo_path(&path), opts.for_reads())
+        .context("Failed to read review issue comments")?;
+    Ok(Some(issue_record(
+        issue,
+        &comments["values"],
+        &BITBUCKET_ISSUE,
+    )))
+}
+
+/// The record of a review in git notes or the ledger. Each fix-forward
+/// resolves the oldest concern still open; every event with a message is a
+/// comment.
+fn recorded_review_record(events: &[NoteEvent], required: usize) -> Option<ReviewRecord> {
+    let status = review_notes::fold(events, required)?;
+    let mut concerns: Vec<ReviewConcernResponse> = Vec::new();
+    for event in events {
+        match event.action {
+            NoteAction::Concern => {
+                let message = event.message.clone().unwrap_or_default();
+                concerns.push(ReviewConcernResponse {
+                    text: match event.severity {
+                        Some(s) => format!("({}) {}", s.as_str(), message),
+                        None => message,
+                    },
+                    resolved: false,
+                });
+            }
+            NoteAction::Fix => {
+                if let Some(concern) = concerns.iter_mut().find(|c| !c.resolved) {
+                    concern.resolved = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(ReviewRecord {
+        issue: None,
+        state: Some(status.state.as_str().to_string()),
+        labels: Vec::new(),
+        concerns,
+        comments: events
+            .iter()
+            .filter_map(|e| {
+                e.message.as_ref().map(|message| ReviewCommentResponse {
+                    author: e.author.clone(),
+                    at: e.at.clone(),
+                    body: match &e.commit {
+                        Some(fix) => format!("fix-forward {}: {}", short_hash(fix), message),
+                        None => format!("{}: {}", e.action.as_str(), message),
+                    },
+                })
+            })
+            .collect(),
+    })
+}
+
+/// Handles `tbdflow review show`: the review issue or recorded review of a
+/// commit, its concerns and comments, and what the commit changed.
+pub fn handle_review_show(
+    config: &Config,
+    commit_hash: &str,
+    json: bool,
+    opts: RunOpts,
+) -> Result<()> {
+    let commit = noted_commit(commit_hash, opts)?;
+    let short = short_hash(&commit);
+    let record = match config.review.strategy {
+        ReviewStrategy::GithubIssue | ReviewStrategy::GithubWorkflow => {
+            github_review_record(short, opts)?
+        }
+        ReviewStrategy::Gitea => gitea_review_record(config, short, opts)?,
+        ReviewStrategy::Bitbucket => bitbucket_review_record(config, short, opts)?,
+        ReviewStrategy::GitNotes | ReviewStrategy::InRepo => {
+            let store = ReviewStore::for_config(config);
+            if !opts.dry_run
+                && let Err(e) = store.sync(config, opts)
+                && !json
+            {
+                println!(
+                    "{}",
+                    format!("Warning: could not fetch reviews from origin: {}", e).yellow()
+                );
+            }
+            let events = store.read(config, &commit, opts)?;
+            recorded_review_record(&events, config.review.required_approvals)
+        }
+        ReviewStrategy::LogOnly => None,
+    };
+    let subject = git::get_commit_subject(&commit, opts.for_reads())?;
+    let author = git::get_commit_author(&commit, opts.for_reads())?;
+    let diffstat = git::get_commit_diffstat(&commit, opts.for_reads())?;
+
+    if json {
+        let record = record.unwrap_or_default();
+        let response = ReviewShowResponse {
+            commit: commit.clone(),
+            subject,
+            author,
+            issue: record.issue,
+            state: record.state,
+            labels: record.labels,
+            concerns: record.concerns,
+            comments: record.comments,
+            diffstat,
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(response))?
+        );
+        return Ok(());
+    }
+
+    println!("{}", format!("--- Review of {} ---", short).blue());
+    println!("Commit:   {} {}", short, subject);
+    println!("Author:   {}", author);
+    match &record {
+        None => {
+            println!("{}", format!("No review found for {}.", short).yellow());
+            if config.review.strategy == ReviewStrategy::LogOnly {
+                println!(
+                    "{}",
+                    "   The log-only strategy keeps no review record.".dimmed()
+                );
+            }
+        }
+        Some(record) => {
+            if let Some(issue) = &record.issue {
+                println!("Issue:    {}", issue);
+            }
+            if let Some(state) = &record.state {
+                println!("State:    {}", state);
+            }
+            if !record.labels.is_empty() {
+                println!("Labels:   {}", record.labels.join(", "));
+            }
+            if !record.concerns.is_empty() {
+                println!("\nConcerns:");
+                for concern in &record.concerns {
+                    if concern.resolved {
+                        println!("  {} {}", "[x]".green(), concern.text);
+                    } else {
+                        println!("  {} {}", "[ ]".yellow(), concern.text);
+                    }
+                }
+            }
+            if !record.comments.is_empty() {
+                println!("\nComments:");
+                for comment in &record.comments {
+                    println!("  {} {}", comment.at.dimmed(), comment.author);
+                    for line in comment.body.lines() {
+                        println!("    {}", line);
+                    }
+                }
+            }
+        }
+    }
+    println!("\nChanges:");
+    for line in diffstat.lines() {
+        println!("  {}", line.trim_start());
+    }
+    Ok(())
+}
+
+/// A trunk commit counted by `review stats`.
+struct TrunkCommit {
+    hash: String,
+    author: String,
+    committed_at: DateTime<Utc>,
+}
+
+/// Parses `git::get_trunk_commits` output, leaving out the commits that
+/// record reviews in the ledger.
+fn parse_trunk_commits(log: &str) -> Vec<TrunkCommit> {
+    log.lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\x1f');
+            let hash = fields.next()?;
+            let author = fields.next()?;
+            let time = fields.next()?.trim().parse::<i64>().ok()?;
+            let subject = fields.next().unwrap_or_default();
+            (!subject.starts_with("chore(review): ")).then(|| TrunkCommit {
+                hash: hash.to_string(),
+                author: author.to_string(),
+                committed_at: DateTime::from_timestamp(time, 0).unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// What happened in one commit's review, as `review stats` counts it.
+#[derive(Debug, Default, PartialEq)]
+struct CommitReview {
+    /// Someone approved it, raised a concern on it or dismissed it.
+    reviewed: bool,
+    approved_at: Option<DateTime<Utc>>,
+    concerns: usize,
+    /// Who approved or raised a concern, once each.
+    reviewers: Vec<String>,
+}
+
+/// The review recorded in git notes or the ledger. It counts as approved
+/// when the `required`-th person approves.
+fn recorded_commit_review(events: &[NoteEvent], required: usize) -> CommitReview {
+    let mut review = CommitReview::default();
+    let mut approvers: Vec<&str> = Vec::new();
+    for event in events {
+        if matches!(event.action, NoteAction::Approved | NoteAction::Concern)
+            && !review.reviewers.contains(&event.author)
+        {
+            review.reviewers.push(event.author.clone());
+        }
+        match event.action {
+            NoteAction::Approved => {
+                review.reviewed = true;
+                if !approvers.contains(&event.author.as_str()) {
+                    approvers.push(&event.author);
+                }
+                if approvers.len() >= required.max(1) && review.approved_at.is_none() {
+                    review.approved_at = DateTime::parse_from_rfc3339(&event.at)
+                        .ok()
+                        .map(|at| at.with_timezone(&Utc));
+                }
+            }
+            NoteAction::Concern => {
+                review.reviewed = true;
+                review.concerns += 1;
+            }
+            NoteAction::Dismissed => review.reviewed = true,
+            NoteAction::Requested | NoteAction::Fix => {}
+        }
+    }
+    review
+}
+
+/// Reviews by short hash from `gh issue list --json title,labels,closedAt,comments`
+/// output. An issue carrying the accepted label was approved when it closed.
+fn github_commit_reviews(json: &str, labels: &ReviewLabelsConfig) -> HashMap<String, CommitReview> {
+    let issues: Value = serde_json::from_str(json).unwrap_or(Value::Null);
+    let mut reviews = HashMap::new();
+    for issue in issues.as_array().into_iter().flatten() {
+        let commit = review_title_commit(issue["title"].as_str().unwrap_or_default());
+        if commit.is_empty() {
+            continue;
+        }
+        let has_label = |name: &str| {
+            issue["labels"]
+                .as_array()
+                .is_some_and(|l| l.iter().any(|l| l["name"].as_str() == Some(name)))
+        };
+        let mut reviewers = approvers_in(&issue["comments"], "/body", "/author/login");
+        let concern_authors: Vec<&str> = issue["comments"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|c| pointer_str(c, "/body").starts_with("**Concern Raised**"))
+            .map(|c| pointer_str(c, "/author/login"))
+            .collect();
+        for author in &concern_authors {
+            if !author.is_empty() && !reviewers.iter().any(|r| r == author) {
+                reviewers.push(author.to_string());
+            }
+        }
+        let approved = has_label(&labels.accepted);
+        reviews.insert(
+            short_hash(&commit).to_string(),
+            CommitReview {
+                reviewed: approved || !concern_authors.is_empty() || has_label(&labels.dismissed),
+                approved_at: issue["closedAt"]
+                    .as_str()
+                    .filter(|_| approved)
+                    .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+                    .map(|at| at.with_timezone(&Utc)),
+                concerns: concern_authors.len(),
+                reviewers,
+            },
+        );
+    }
+    reviews
+}
+
+/// Counts by name, most first, then by name.
+fn ranked_counts(counts: HashMap<String, usize>) -> Vec<ReviewCountResponse> {
+    let mut ranked: Vec<ReviewCountResponse> = counts
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(name, count)| ReviewCountResponse { name, count })
+        .collect();
+    ranked.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    ranked
+}
+
+/// Review throughput over `commits`, with `reviews` keyed by short hash.
+fn review_stats(
+    trunk: &str,
+    since: &str,
+    commits: &[TrunkCommit],
+    reviews: &HashMap<String, CommitReview>,
+) -> ReviewStatsResponse {
+    let mut reviewed = 0;
+    let mut to_approval: Vec<i64> = Vec::new();
+    let mut concerns: HashMap<String, usize> = HashMap::new();
+    let mut reviewers: HashMap<String, usize> = HashMap::new();
+    for commit in commits {
+        let Some(review) = reviews.get(short_hash(&commit.hash)) else {
+            continue;
+        };
+        if review.reviewed {
+            reviewed += 1;
+        }
+        if let Some(approved_at) = review.approved_at {
+            to_approval.push((approved_at - commit.committed_at).num_seconds().max(0));
+        }
+        *concerns.entry(commit.author.clone()).or_default() += review.concerns;
+        for reviewer in &review.reviewers {
+            *reviewers.entry(reviewer.clone()).or_default() += 1;
+        }
+    }
+    to_approval.sort_unstable();
+    let middle = to_approval.len() / 2;
+    let median = match to_approval.len() {
+        0 => None,
+        n if n % 2 == 0 => Some((to_approval[middle - 1] + to_approval[middle]) / 2),
+        _ => Some(to_approval[middle]),
+    };
+    ReviewStatsResponse {
+        trunk: trunk.to_string(),
+        since: since.to_string(),
+        commits: commits.len(),
+        reviewed,
+        unreviewed: commits.len() - reviewed,
+        median_seconds_to_approval: median,
+        concerns_by_author: ranked_counts(concerns),
+        top_reviewers: ranked_counts(reviewers),
+    }
+}
 
-        // Add a comment with the concern
-        let comment = format!("**Concern Raised**\n\n{}", message);
+/// The review issues created since `since`, as `review stats` reads them.
+fn github_reviews_since(
+    config: &Config,
+    since: &str,
+    opts: RunOpts,
+) -> Result<HashMap<String, CommitReview>> {
+    if !gh::is_available() {
+        anyhow::bail!("GitHub CLI (gh) not found. Cannot read the review issues.");
+    }
+    let created = parse_age_hours(since)
+        .map(|hours| {
+            (Utc::now() - chrono::Duration::hours(hours))
+                .format("%Y-%m-%d")
+                .to_string()
+        })
+        .ok()
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(since.trim(), "%Y-%m-%d")
+                .ok()
+                .map(|d| d.to_string())
+        });
+    let search = match created {
+        Some(date) => format!("[Review] in:title created:>={}", date),
+        None => "[Review] in:title".to_string(),
+    };
+    let output = gh::run(
+        &[
+            "issue",
+            "list",
+            "--search",
+            &search,
+            "--state",
+            "all",
+            "--json",
+            "title,labels,closedAt,comments",
+            "--limit",
+            "1000",
+        ],
+        opts.for_reads(),
+    )
+    .context("Failed to search for GitHub issues")?;
+    if !output.success {
+        anyhow::bail!("Could not list the review issues: {}", output.stderr.trim());
+    }
+    Ok(github_commit_reviews(&output.stdout, &config.review.labels))
+}
 
-        let _ = Command::new("gh")
-            .args(["issue", "comment", &issue_num_str, "--body", &comment])
-            .output();
+/// Handles `tbdflow review stats`: whether trunk commits since `since` were
+/// reviewed, how long approval took, and who raised and received concerns.
+pub fn handle_review_stats(config: &Config, since: &str, json: bool, opts: RunOpts) -> Result<()> {
+    let reviews: HashMap<String, CommitReview> = match config.review.strategy {
+        ReviewStrategy::GitNotes | ReviewStrategy::InRepo => {
+            let store = ReviewStore::for_config(config);
+            if let Err(e) = store.sync(config, opts)
+                && !json
+            {
+                println!(
+                    "{}",
+                    format!("Warning: could not fetch reviews from origin: {}", e).yellow()
+                );
+            }
+            let required = config.review.required_approvals;
+            store
+                .read_all(config, opts)?
+                .into_iter()
+                .map(|(commit, _, events)| {
+                    (
+                        short_hash(&commit).to_string(),
+                        recorded_commit_review(&events, required),
+                    )
+                })
+                .collect()
+        }
+        ReviewStrategy::GithubIssue | ReviewStrategy::GithubWorkflow => {
+            github_reviews_since(config, since, opts)?
+        }
+        ReviewStrategy::Gitea | ReviewStrategy::Bitbucket | ReviewStrategy::LogOnly => {
+            anyhow::bail!(
+                "Review stats work with the github-issue, github-workflow, git-notes and in-repo strategies."
+            );
+        }
+    };
 
-        // Append checklist item to the issue body
-        append_concern_checklist_item(&issue_num_str, message, opts)?;
+    let remote = format!("origin/{}", config.main_branch_name);
+    let trunk = if git::ref_exists(&format!("refs/remotes/{}", remote), opts.for_reads()) {
+        remote
+    } else {
+        config.main_branch_name.clone()
+    };
+    let log = git::get_trunk_commits(&trunk, &metrics::git_since(since), opts.for_reads())?;
+    let stats = review_stats(&trunk, since, &parse_trunk_commits(&log), &reviews);
 
-        // Set commit status based on config
-        set_commit_status(config, commit_hash, message, opts)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&TbdResponse::ok(stats))?);
+        return Ok(());
+    }
 
+    println!(
+        "{}",
+        format!("--- Review throughput on '{}' (since {}) ---", trunk, since).blue()
+    );
+    if stats.commits == 0 {
         println!(
             "{}",
-            format!(
-                "Concern raised on issue #{} for commit {} (label: {})",
-                issue_num, short, labels.concern
-            )
-            .yellow()
+            "No commits found in the specified time range.".yellow()
         );
+        return Ok(());
+    }
+    println!("Commits:            {}", stats.commits);
+    println!(
+        "Reviewed:           {} ({}%)",
+        stats.reviewed,
+        stats.reviewed * 100 / stats.commits
+    );
+    let unreviewed = stats.unreviewed.to_string();
+    println!(
+        "Unreviewed:         {}",
+        if stats.unreviewed > 0 {
+            unreviewed.yellow()
+        } else {
+            unreviewed.normal()
+        }
+    );
+    println!(
+        "Median to approval: {}",
+        stats
+            .median_seconds_to_approval
+            .map_or("-".to_string(), |s| format_duration(
+                chrono::Duration::seconds(s)
+            ))
+    );
+    for (heading, counts) in [
+        ("Concerns raised per author:", &stats.concerns_by_author),
+        ("Top reviewers:", &stats.top_reviewers),
+    ] {
+        if counts.is_empty() {
+            continue;
+        }
+        println!("\n{}", heading);
+        let width = counts
+            .iter()
+            .map(|c| c.name.chars().count())
+            .max()
+            .unwrap_or(0);
+        for count in counts.iter().take(10) {
+            println!("  {:<width$}  {}", count.name, count.count, width = width);
+        }
+    }
+    Ok(())
+}
+
+/// An open review issue still waiting for a reviewer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingReview {
+    pub number: i64,
+    pub title: String,
+    pub age_hours: i64,
+    pub escalated: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SweepStatus {
+    Fresh,
+    Stale,
+    /// Past the SLA and not yet escalated.
+    Escalate,
+    AlreadyEscalated,
+}
+
+pub fn classify_pending_review(review: &PendingReview, stale_after: u64, sla: u64) -> SweepStatus {
+    let age = review.age_hours.max(0) as u64;
+    if review.escalated {
+        SweepStatus::AlreadyEscalated
+    } else if age >= sla {
+        SweepStatus::Escalate
+    } else if age >= stale_after {
+        SweepStatus::Stale
     } else {
-        println!(
-            "{}",
-            format!("Warning: No open review issue found for commit {}", short).yellow()
-        );
-        println!("   Run 'tbdflow review --trigger' first to create the review issue.");
+        SweepStatus::Fresh
     }
+}
 
-    Ok(())
+/// Parses `gh issue list --json number,title,createdAt,labels` output.
+fn parse_pending_reviews(
+    json: &str,
+    escalated_label: &str,
+    now: DateTime<Utc>,
+) -> Vec<PendingReview> {
+    let parsed: Value = serde_json::from_str(json).unwrap_or(Value::Null);
+    let Some(issues) = parsed.as_array() else {
+        return Vec::new();
+    };
+    issues
+        .iter()
+        .filter_map(|issue| {
+            let created = DateTime::parse_from_rfc3339(issue["createdAt"].as_str()?).ok()?;
+            let escalated = issue["labels"].as_array().is_some_and(|labels| {
+                labels
+                    .iter()
+                    .any(|l| l["name"].as_str() == Some(escalated_label))
+            });
+            Some(PendingReview {
+                number: issue["number"].as_i64()?,
+                title: issue["title"].as_str().unwrap_or_default().to_string(),
+                age_hours: (now - created.with_timezone(&Utc)).num_hours(),
+                escalated,
+            })
+        })
+        .collect()
 }
 
-fn append_concern_checklist_item(
-    issue_num: &str,
-    concern_message: &str,
-    opts: RunOpts,
-) -> Result<()> {
-    // Get current issue body
-    let output = Command::new("gh")
-        .args(["issue", "view", issue_num, "--json", "body"])
-        .output()
-        .context("Failed to get issue body")?;
+/// Team handles need the `@org/team` form to notify; bare users get an `@`.
+fn mention(reviewer: &str) -> String {
+    if reviewer.starts_with('@') {
+        reviewer.to_string()
+    } else {
+        format!("@{}", reviewer)
+    }
+}
 
-    if !output.status.success() {
-        return Ok(());
+fn escalate_review(config: &Config, review: &PendingReview, opts: RunOpts) -> Result<()> {
+    let number = review.number.to_string();
+    // The label is what keeps the next sweep from escalating again, so the
+    // fallback reviewers are only pinged once it is on.
+    let output = gh::run(
+        &[
+            "issue",
+            "edit",
+            &number,
+            "--add-label",
+            &config.review.labels.escalated,
+        ],
+        opts,
+    )
+    .context("Failed to label GitHub issue")?;
+    if !output.success {
+        return Err(anyhow::anyhow!(
+            "Failed to add the '{}' label to issue #{}: {}",
+            config.review.labels.escalated,
+            review.number,
+            output.stderr
+        ));
+    }
+
+    let mut comment = format!(
+        "**Escalated** by `tbdflow review sweep`: this review has been pending for {}h (SLA {}h).",
+        review.age_hours, config.review.sla_hours
+    );
+    if !config.review.fallback_reviewers.is_empty() {
+        let mentions: Vec<String> = config
+            .review
+            .fallback_reviewers
+            .iter()
+            .map(|r| mention(r))
+            .collect();
+        comment.push_str(&format!(
+            "\n\n{}, could you take a look?",
+            mentions.join(" ")
+        ));
     }
 
-    let json_output = String::from_utf8_lossy(&output.stdout);
+    if opts.verbose {
+        println!("{} Escalating issue #{}", "[INFO]".cyan(), review.number);
+    }
+    let output = gh::run(&["issue", "comment", &number, "--body", &comment], opts)
+        .context("Failed to comment on GitHub issue")?;
+    if !output.success {
+        return Err(anyhow::anyhow!(
+            "Failed to escalate issue #{}: {}",
+            review.number,
+            output.stderr
+        ));
+    }
+    Ok(())
+}
 
-    // Extract the body content
-    let current_body = extract_body_from_json(&json_output).unwrap_or_default();
+/// Filters for `tbdflow review list` and `tbdflow review mine`.
+#[derive(Debug, Default, Clone)]
+pub struct ReviewListFilter {
+    /// Only reviews assigned to the authenticated `gh` user.
+    pub mine: bool,
+    /// Only reviews of commits authored by the local git user.
+    pub authored: bool,
+    /// A label role (`concern`, `critical`, ...) or a literal label name.
+    pub label: Option<String>,
+    /// Only reviews at least this old, e.g. `12h`, `3d` or `2w`.
+    pub older_than: Option<String>,
+}
 
-    // Replace the "No concerns raised yet" placeholder or append to concerns section
-    let new_body = if current_body.contains("_No concerns raised yet._") {
-        current_body.replace(
-            "_No concerns raised yet._",
-            &format!("- [ ] {}", concern_message),
+/// An open review as shown by `review list`: an issue, or a review recorded
+/// in git notes or the ledger.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewListing {
+    /// Issue number; `None` for a review recorded in git.
+    pub number: Option<i64>,
+    /// Short hash of the reviewed commit, or `first..last` for a batch.
+    pub commit: String,
+    pub title: String,
+    pub url: String,
+    /// Commit author, from the issue's `**Author:**` line or the commit.
+    pub author: Option<String>,
+    /// Assignees of the issue, or the reviewers the review was requested from.
+    pub reviewers: Vec<String>,
+    /// `pending` or `concern`.
+    pub state: String,
+    pub labels: Vec<String>,
+    pub age_hours: i64,
+}
+
+/// Parses an age such as `12h`, `3d` or `2w` into hours.
+pub fn parse_age_hours(age: &str) -> Result<i64> {
+    let age = age.trim();
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid age '{}'. Use a number followed by h, d or w (e.g. 3d).",
+            age
         )
-    } else if current_body.contains("### Concerns") {
-        // Find the concerns section and append the new item
-        let concerns_marker = "### Concerns\n\n";
-        if let Some(pos) = current_body.find(concerns_marker) {
-            let insert_pos = pos + concerns_marker.len();
-            let (before, after) = current_body.split_at(insert_pos);
-            format!("{}- [ ] {}\n{}", before, concern_message, after)
-        } else {
-            current_body
-        }
-    } else {
-        current_body
     };
-
-    if opts.verbose {
-        println!(
-            "{} Updating issue body with concern checklist item",
-            "[INFO]".cyan()
-        );
+    let unit = age.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = age[..age.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| invalid())?;
+    match unit {
+        'h' => Ok(amount),
+        'd' => Ok(amount * 24),
+        'w' => Ok(amount * 24 * 7),
+        _ => Err(invalid()),
     }
+}
 
-    let _ = Command::new("gh")
-        .args(["issue", "edit", issue_num, "--body", &new_body])
-        .output();
+fn review_author(body: &str) -> Option<String> {
+    body.lines()
+        .find_map(|line| line.trim().strip_prefix("**Author:**"))
+        .map(|author| author.trim().to_string())
+        .filter(|author| !author.is_empty())
+}
 
-    Ok(())
+/// The commit a review issue title ends with: `abc1234`, or `aaa..bbb` for
+/// a batch.
+fn review_title_commit(title: &str) -> String {
+    title
+        .rsplit_once('(')
+        .and_then(|(_, rest)| rest.strip_suffix(')'))
+        .and_then(|inner| inner.rsplit(", ").next())
+        .unwrap_or_default()
+        .to_string()
 }
 
-fn extract_body_from_json(json: &str) -> Option<String> {
-    let parsed: Value = serde_json::from_str(json).ok()?;
-    parsed["body"].as_str().map(|s| s.to_string())
+/// Parses `gh issue list --json number,title,url,body,createdAt,labels,assignees`
+/// output. Issues carrying `concern_label` are in the `concern` state.
+fn parse_review_listings(
+    json: &str,
+    concern_label: &str,
+    now: DateTime<Utc>,
+) -> Vec<ReviewListing> {
+    let parsed: Value = serde_json::from_str(json).unwrap_or(Value::Null);
+    let Some(issues) = parsed.as_array() else {
+        return Vec::new();
+    };
+    issues
+        .iter()
+        .filter_map(|issue| {
+            let created = DateTime::parse_from_rfc3339(issue["createdAt"].as_str()?).ok()?;
+            let title = issue["title"].as_str().unwrap_or_default().to_string();
+            let labels: Vec<String> = issue["labels"]
+                .as_array()
+                .map(|labels| {
+                    labels
+                        .iter()
+                        .filter_map(|l| l["name"].as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let state = if labels.iter().any(|l| l == concern_label) {
+                "concern"
+            } else {
+                "pending"
+            };
+            Some(ReviewListing {
+                number: Some(issue["number"].as_i64()?),
+                commit: review_title_commit(&title),
+                title,
+                url: issue["url"].as_str().unwrap_or_default().to_string(),
+                author: issue["body"].as_str().and_then(review_author),
+                reviewers: issue["assignees"]
+                    .as_array()
+                    .map(|assignees| {
+                        assignees
+                            .iter()
+                            .filter_map(|a| a["login"].as_str().map(str::to_string))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                state: state.to_string(),
+                labels,
+                age_hours: (now - created.with_timezone(&Utc)).num_hours(),
+            })
+        })
+        .collect()
 }
 
-fn set_commit_status(
+/// The reviews recorded in git notes or the ledger that are still open:
+/// pending, or with a concern. Their age counts from the request.
+fn recorded_review_listings(
     config: &Config,
-    commit_hash: &str,
-    message: &str,
+    now: DateTime<Utc>,
     opts: RunOpts,
-) -> Result<()> {
-    if !is_gh_cli_available() {
-        return Ok(());
+) -> Result<Vec<ReviewListing>> {
+    let required = config.review.required_approvals;
+    let mut listings = Vec::new();
+    for (commit, subject, events) in ReviewStore::for_config(config).read_all(config, opts)? {
+        let Some(status) = review_notes::fold(&events, required) else {
+            continue;
+        };
+        if !matches!(status.state, ReviewState::Pending | ReviewState::Concern) {
+            continue;
+        }
+        let requested = events
+            .first()
+            .and_then(|e| DateTime::parse_from_rfc3339(&e.at).ok())
+            .map_or(now, |at| at.with_timezone(&Utc));
+        listings.push(ReviewListing {
+            number: None,
+            commit: short_hash(&commit).to_string(),
+            title: subject,
+            url: String::new(),
+            author: git::get_commit_author(&commit, opts.for_reads()).ok(),
+            reviewers: status.reviewers,
+            state: status.state.as_str().to_string(),
+            labels: Vec::new(),
+            age_hours: (now - requested).num_hours(),
+        });
     }
+    Ok(listings)
+}
 
-    let (state, description) = if config.review.concern_blocks_status {
-        ("failure", format!("Audit Concern: {}", message))
+/// `5h` under two days, `3d` from then on.
+fn format_age(hours: i64) -> String {
+    if hours < 48 {
+        format!("{}h", hours)
     } else {
-        (
-            "pending",
-            format!("Awaiting fix-forward for concern: {}", message),
-        )
-    };
+        format!("{}d", hours / 24)
+    }
+}
 
-    // Get repo owner/name
-    let repo_info = Command::new("gh")
-        .args(["repo", "view", "--json", "owner,name"])
-        .output();
+/// Applies the filters `gh` cannot: commit author and minimum age.
+pub fn filter_review_listings(
+    listings: Vec<ReviewListing>,
+    author: Option<&str>,
+    min_age_hours: Option<i64>,
+) -> Vec<ReviewListing> {
+    listings
+        .into_iter()
+        .filter(|l| author.is_none_or(|a| l.author.as_deref() == Some(a)))
+        .filter(|l| min_age_hours.is_none_or(|min| l.age_hours >= min))
+        .collect()
+}
 
-    let repo = match repo_info {
-        Ok(output) if output.status.success() => {
-            let json = String::from_utf8_lossy(&output.stdout);
-            extract_repo_from_json(&json)
+/// Lists open reviews, narrowed by `filter`: review issues on GitHub, or the
+/// reviews recorded in git notes or the ledger.
+pub fn handle_review_list(
+    config: &Config,
+    filter: &ReviewListFilter,
+    json: bool,
+    opts: RunOpts,
+) -> Result<()> {
+    let min_age_hours = filter
+        .older_than
+        .as_deref()
+        .map(parse_age_hours)
+        .transpose()?;
+    let author = if filter.authored {
+        Some(git::get_user_name(opts)?)
+    } else {
+        None
+    };
+
+    let listings = match config.review.strategy {
+        ReviewStrategy::GitNotes | ReviewStrategy::InRepo => {
+            let store = ReviewStore::for_config(config);
+            if let Err(e) = store.sync(config, opts)
+                && !json
+            {
+                println!(
+                    "{}",
+                    format!("Warning: could not fetch reviews from origin: {}", e).yellow()
+                );
+            }
+            // Reviewers are named as they were requested; match on user.name.
+            let me = if filter.mine {
+                Some(git::get_user_name(opts)?)
+            } else {
+                None
+            };
+            recorded_review_listings(config, Utc::now(), opts)?
+                .into_iter()
+                .filter(|l| {
+                    me.as_deref()
+                        .is_none_or(|me| l.reviewers.iter().any(|r| r.eq_ignore_ascii_case(me)))
+                })
+                .filter(|l| filter.label.as_deref().is_none_or(|label| l.state == label))
+                .collect()
+        }
+        ReviewStrategy::GithubIssue | ReviewStrategy::GithubWorkflow => {
+            list_github_reviews(config, filter, opts)?
+        }
+        ReviewStrategy::Gitea | ReviewStrategy::Bitbucket | ReviewStrategy::LogOnly => {
+            anyhow::bail!(
+                "Listing open reviews works with the github-issue, github-workflow, git-notes and in-repo strategies."
+            );
         }
-        _ => return Ok(()),
     };
+    let mut reviews = filter_review_listings(listings, author.as_deref(), min_age_hours);
+    reviews.sort_by_key(|r| std::cmp::Reverse(r.age_hours));
+
+    if json {
+        let response: Vec<ReviewListResponse> = reviews
+            .into_iter()
+            .map(|r| ReviewListResponse {
+                number: r.number,
+                commit: r.commit,
+                title: r.title,
+                url: r.url,
+                author: r.author,
+                reviewers: r.reviewers,
+                state: r.state,
+                labels: r.labels,
+                age_hours: r.age_hours,
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(response))?
+        );
+        return Ok(());
+    }
 
-    let Some((owner, name)) = repo else {
+    println!("{}", "--- Open reviews ---".blue());
+    if reviews.is_empty() {
+        println!("{}", "No open reviews match.".green());
         return Ok(());
+    }
+    let rows: Vec<[String; 5]> = reviews
+        .iter()
+        .map(|r| {
+            [
+                r.commit.clone(),
+                r.author.clone().unwrap_or_else(|| "-".to_string()),
+                format_age(r.age_hours),
+                if r.reviewers.is_empty() {
+                    "-".to_string()
+                } else {
+                    r.reviewers.join(", ")
+                },
+                r.state.clone(),
+            ]
+        })
+        .collect();
+    let headers = ["COMMIT", "AUTHOR", "AGE", "REVIEWERS", "STATE"];
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain(std::iter::once(headers[i].len()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |cells: [&str; 5]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
     };
+    println!("  {}  {}", line(headers).bold(), "REVIEW".bold());
+    for (review, row) in reviews.iter().zip(&rows) {
+        let cells = line([&row[0], &row[1], &row[2], &row[3], &row[4]]);
+        let cells = if review.state == "concern" {
+            cells.yellow()
+        } else {
+            cells.normal()
+        };
+        let subject = review
+            .title
+            .strip_prefix("[Review] ")
+            .unwrap_or(&review.title);
+        let extra_labels: Vec<&str> = review
+            .labels
+            .iter()
+            .map(String::as_str)
+            .filter(|l| *l != config.review.labels.pending && *l != config.review.labels.concern)
+            .collect();
+        let mut detail = match review.number {
+            Some(number) => format!("#{} {}", number, subject),
+            None => subject.to_string(),
+        };
+        if !extra_labels.is_empty() {
+            detail = format!("{} [{}]", detail, extra_labels.join(", "));
+        }
+        println!("  {}  {}", cells, detail);
+    }
+    println!("\n{} review(s)", reviews.len());
+    Ok(())
+}
 
-    if opts.verbose {
+/// Open review issues on GitHub, narrowed by what `gh` can filter on.
+fn list_github_reviews(
+    config: &Config,
+    filter: &ReviewListFilter,
+    opts: RunOpts,
+) -> Result<Vec<ReviewListing>> {
+    let label = filter.label.as_deref().map(|label| {
+        label_name_for_role(&config.review.labels, label)
+            .unwrap_or(label)
+            .to_string()
+    });
+    gh::GH
+        .require()
+        .context("Listing review issues needs the GitHub CLI")?;
+
+    let mut args = vec![
+        "issue",
+        "list",
+        "--search",
+        "[Review] in:title is:open",
+        "--json",
+        "number,title,url,body,createdAt,labels,assignees",
+        "--limit",
+        "500",
+    ];
+    if filter.mine {
+        args.extend(["--assignee", "@me"]);
+    }
+    if let Some(label) = &label {
+        args.extend(["--label", label.as_str()]);
+    }
+    let output = gh::run(&args, opts).context("Failed to list review issues with 'gh'")?;
+    if !output.success {
+        return Err(anyhow::anyhow!(
+            "Failed to list review issues: {}",
+            output.stderr
+        ));
+    }
+    Ok(parse_review_listings(
+        &output.stdout,
+        &config.review.labels.concern,
+        Utc::now(),
+    ))
+}
+
+/// Reports stale pending reviews and escalates the ones past the SLA.
+/// Meant to run on a schedule in CI; the output is written for a job log.
+pub fn handle_review_sweep(config: &Config, opts: RunOpts) -> Result<()> {
+    println!("{}", "--- Sweeping pending reviews ---".blue());
+    gh::GH
+        .require()
+        .context("The sweep needs the GitHub CLI, authenticated with GH_TOKEN")?;
+
+    let labels = &config.review.labels;
+    let output = gh::run(
+        &[
+            "issue",
+            "list",
+            "--search",
+            "[Review] in:title is:open",
+            "--label",
+            &labels.pending,
+            "--json",
+            "number,title,createdAt,labels",
+            "--limit",
+            "500",
+        ],
+        opts,
+    )
+    .context("Failed to list review issues with 'gh'")?;
+    if !output.success {
+        return Err(anyhow::anyhow!(
+            "Failed to list review issues: {}",
+            output.stderr
+        ));
+    }
+
+    let reviews = parse_pending_reviews(&output.stdout, &labels.escalated, Utc::now());
+    let (stale_after, sla) = (config.review.stale_after_hours, config.review.sla_hours);
+
+    let mut stale = 0;
+    let mut escalated = 0;
+    let mut already = 0;
+    for review in &reviews {
+        let status = classify_pending_review(review, stale_after, sla);
+        let line = format!(
+            "#{} {} ({}h)",
+            review.number, review.title, review.age_hours
+        );
+        match status {
+            SweepStatus::Fresh => continue,
+            SweepStatus::Stale => {
+                stale += 1;
+                println!("  {} {}", "[stale]".yellow(), line);
+            }
+            SweepStatus::AlreadyEscalated => {
+                already += 1;
+                println!("  {} {}", "[escalated]".magenta(), line);
+            }
+            SweepStatus::Escalate => {
+                escalated += 1;
+                if opts.dry_run {
+                    println!("  {} {}", "[DRY RUN] would escalate".yellow(), line);
+                    continue;
+                }
+                if escalated == 1 {
+                    ensure_review_labels_exist(labels, opts);
+                }
+                escalate_review(config, review, opts)?;
+                println!("  {} {}", "[escalate]".red(), line);
+            }
+        }
+    }
+
+    println!(
+        "\nSummary: {} pending, {} stale (>{}h), {} newly escalated (>{}h), {} already escalated.",
+        reviews.len(),
+        stale,
+        stale_after,
+        escalated,
+        sla,
+        already
+    );
+    if escalated > 0 && config.review.fallback_reviewers.is_empty() {
         println!(
-            "{} Setting commit status to '{}' for {}",
-            "[INFO]".cyan(),
-            state,
-            short_hash(commit_hash)
+            "{}",
+            "Hint: set review.fallback_reviewers to ping someone when reviews are escalated."
+                .dimmed()
         );
     }
+    Ok(())
+}
 
-    let api_path = format!("repos/{}/{}/statuses/{}", owner, name, commit_hash);
+/// Review work waiting on the current user, shown by `tbdflow status`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewObligations {
+    /// Open review issues assigned to the user.
+    pub pending_assigned: usize,
+    /// Open review issues with a concern on commits the user authored.
+    pub concerns_on_own_commits: usize,
+}
 
-    let _ = Command::new("gh")
-        .args([
-            "api",
-            &api_path,
-            "-f",
-            &format!("state={}", state),
-            "-f",
-            "context=peer-review",
-            "-f",
-            &format!("description={}", description),
-        ])
-        .output();
+impl ReviewObligations {
+    pub fn summary(&self) -> String {
+        if self.pending_assigned == 0 && self.concerns_on_own_commits == 0 {
+            return "Reviews: nothing waiting on you".to_string();
+        }
+        let concerns = if self.concerns_on_own_commits == 1 {
+            "concern"
+        } else {
+            "concerns"
+        };
+        format!(
+            "Reviews: {} pending assigned to you, {} {} on your commits",
+            self.pending_assigned, self.concerns_on_own_commits, concerns
+        )
+    }
+}
 
-    Ok(())
+#[derive(Serialize, Deserialize)]
+struct CachedObligations {
+    fetched_at: String,
+    obligations: ReviewObligations,
+}
+
+impl State for CachedObligations {
+    const FILE: &'static str = "review-status.json";
+    const LEGACY_FILE: Option<&'static str> = Some("tbdflow-review-status.json");
+}
+
+fn is_cache_fresh(fetched_at: &str, now: DateTime<Utc>, ttl_minutes: u64) -> bool {
+    DateTime::parse_from_rfc3339(fetched_at).is_ok_and(|at| {
+        let age = now.signed_duration_since(at);
+        age >= chrono::Duration::zero() && age.num_minutes() < ttl_minutes as i64
+    })
+}
+
+/// Counts open `[Review]` issues matching the extra `gh issue list` filters.
+fn count_review_issues(filters: &[&str], opts: RunOpts) -> Result<usize> {
+    let mut args = vec![
+        "issue",
+        "list",
+        "--search",
+        "[Review] in:title is:open",
+        "--json",
+        "number",
+        "--limit",
+        "500",
+    ];
+    args.extend_from_slice(filters);
+    let output = gh::run(&args, opts).context("Failed to list review issues with 'gh'")?;
+    if !output.success {
+        return Err(anyhow::anyhow!(
+            "Failed to list review issues: {}",
+            output.stderr
+        ));
+    }
+    let parsed: Value = serde_json::from_str(&output.stdout).unwrap_or(Value::Null);
+    Ok(parsed.as_array().map_or(0, Vec::len))
+}
+
+fn fetch_review_obligations(config: &Config, opts: RunOpts) -> Result<ReviewObligations> {
+    let labels = &config.review.labels;
+    let pending_assigned =
+        count_review_issues(&["--label", &labels.pending, "--assignee", "@me"], opts)?;
+    // Issues opened locally are authored by the committer; the workflow
+    // strategy opens them from CI, so match the author named in the body.
+    let concerns_on_own_commits = match config.review.strategy {
+        ReviewStrategy::GithubWorkflow => {
+            let name = git::get_user_name(opts)?;
+            let search = format!("[Review] in:title is:open \"{}\" in:body", name);
+            count_review_issues(&["--label", &labels.concern, "--search", &search], opts)?
+        }
+        _ => count_review_issues(&["--label", &labels.concern, "--author", "@me"], opts)?,
+    };
+    Ok(ReviewObligations {
+        pending_assigned,
+        concerns_on_own_commits,
+    })
+}
+
+/// Review obligations for `tbdflow status`, served from a short-lived cache
+/// in `.git/` so status stays fast. `None` when reviews are disabled, do
+/// not go to GitHub, or GitHub cannot be reached.
+pub fn review_obligations(
+    config: &Config,
+    git_dir: &Path,
+    opts: RunOpts,
+) -> Option<ReviewObligations> {
+    if !config.review.enabled
+        || matches!(
+            config.review.strategy,
+            ReviewStrategy::LogOnly
+                | ReviewStrategy::Gitea
+                | ReviewStrategy::Bitbucket
+                | ReviewStrategy::GitNotes
+                | ReviewStrategy::InRepo
+        )
+    {
+        return None;
+    }
+    if let Some(cached) = state::load::<CachedObligations>(git_dir).ok().flatten()
+        && is_cache_fresh(
+            &cached.fetched_at,
+            Utc::now(),
+            config.review.status_cache_minutes,
+        )
+    {
+        return Some(cached.obligations);
+    }
+    if !gh::is_available() {
+        return None;
+    }
+    let obligations = fetch_review_obligations(config, opts).ok()?;
+    if !opts.dry_run {
+        let cached = CachedObligations {
+            fetched_at: Utc::now().to_rfc3339(),
+            obligations: obligations.clone(),
+        };
+        let _ = state::save(git_dir, &cached);
+    }
+    Some(obligations)
+}
+
+/// An issue as printed by `gh issue list/view --json`. The number is always
+/// asked for; the other fields are left empty when they were not.
+#[derive(Debug, Deserialize)]
+struct GhIssue {
+    number: u64,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    url: String,
+}
+
+/// `gh repo view --json owner,name`.
+#[derive(Debug, Deserialize)]
+struct GhRepo {
+    name: String,
+    owner: GhOwner,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhOwner {
+    login: String,
+}
+
+/// Parses `gh ... --json` output. Empty output, as under `--dry-run`, is `None`.
+fn parse_gh_json<T: serde::de::DeserializeOwned>(json: &str) -> Result<Option<T>> {
+    if json.trim().is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_str(json)
+        .map(Some)
+        .with_context(|| format!("Unexpected output from gh: {}", json))
 }
 
-fn extract_repo_from_json(json: &str) -> Option<(String, String)> {
-    let parsed: Value = serde_json::from_str(json).ok()?;
-    let owner = parsed["owner"]["login"].as_str()?.to_string();
-    let name = parsed["name"].as_str()?.to_string();
-    Some((owner, name))
+/// The first issue in `gh issue list --json` output.
+fn first_issue(json: &str) -> Result<Option<GhIssue>> {
+    Ok(parse_gh_json::<Vec<GhIssue>>(json)?.and_then(|issues| issues.into_iter().next()))
 }
 
-fn dismiss_github_review_issue(
-    labels: &ReviewLabelsConfig,
-    short_hash: &str,
-    message: &str,
-    opts: RunOpts,
-) -> Result<()> {
-    if !is_gh_cli_available() {
-        println!(
-            "{}",
-            "Warning: GitHub CLI (gh) not found. Cannot dismiss review.".yellow()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digest_groups_commits_like_the_changelog() {
+        let log = "\x1eaaaaaaa1\x1fAda\x1ffix(api): handle timeouts\n\
+                   \x1ebbbbbbb2\x1fBob\x1ffeat!: drop v1 endpoints\n\
+                   \x1eccccccc3\x1fAda\x1ffeat(ui): add search\n\
+                   \x1eddddddd4\x1fCy\x1fUpdate readme\n";
+        let commits = history::parse_log(log);
+        let groups = group_digest(&commits, &ChangelogConfig::default());
+        let summary: Vec<(&str, Vec<&str>)> = groups
+            .iter()
+            .map(|(h, c)| (h.as_str(), c.iter().map(|c| c.short_hash()).collect()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("### ⚠️ BREAKING CHANGES", vec!["bbbbbbb"]),
+                ("### ✨ Features", vec!["ccccccc"]),
+                ("### 🐛 Bug Fixes", vec!["aaaaaaa"]),
+                ("### Miscellaneous", vec!["ddddddd"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn batch_issue_has_a_section_per_commit() {
+        let commits = history::parse_log(
+            "\x1eaaaaaaa111\x1fAda\x1ffeat: add parser\n\nsrc/parser.rs\n\
+             \x1ebbbbbbb222\x1fAda\x1ftest: cover parser\n\ntests/parser.rs\n",
         );
-        return Ok(());
+        let (title, body) = batch_issue("main~2..main", &commits, "Ada", "", "");
+        assert_eq!(title, "[Review] main~2..main (2 commits, aaaaaaa..bbbbbbb)");
+        assert!(body.contains("#### `aaaaaaa111` feat: add parser\n\n- [ ] Reviewed"));
+        assert!(body.contains("#### `bbbbbbb222` test: cover parser"));
+        assert!(body.contains("- `tests/parser.rs`"));
+        assert!(body.contains("tbdflow review --approve bbbbbbb"));
+        assert!(body.find("feat: add parser") < body.find("test: cover parser"));
     }
 
-    // Search for the review issue
-    let search_query = format!("[Review] in:title {} in:title is:open", short_hash);
+    #[test]
+    fn fixes_review_trailers_are_read_back() {
+        let trailers = vec![
+            "Co-authored-by: Ada <ada@example.com>".to_string(),
+            fixes_review_trailer("abc1234567890"),
+        ];
+        assert_eq!(trailers[1], "Fixes-Review: abc1234");
+        assert_eq!(fixed_reviews(&trailers), vec!["abc1234".to_string()]);
+    }
 
-    if opts.verbose {
-        println!("{} Searching for review issue...", "[INFO]".cyan());
+    #[test]
+    fn check_off_concern_ticks_the_matching_item() {
+        let body = "### Concerns\n\n- [ ] Drops writes on retry\n- [ ] Missing timeout on client\n- [x] Typo";
+        let (new_body, item) = check_off_concern(body, "fix(client): add request timeout").unwrap();
+        assert_eq!(item, "Missing timeout on client");
+        assert!(new_body.contains("- [x] Missing timeout on client"));
+        assert!(new_body.contains("- [ ] Drops writes on retry"));
+
+        assert!(check_off_concern(body, "chore: bump deps").is_none());
+
+        let single = "- [ ] Drops writes on retry";
+        let (new_body, _) = check_off_concern(single, "fix: something unrelated").unwrap();
+        assert_eq!(new_body, "- [x] Drops writes on retry");
     }
 
-    let output = Command::new("gh")
-        .args([
-            "issue",
-            "list",
-            "--search",
-            &search_query,
-            "--json",
-            "number",
-            "--limit",
-            "1",
-        ])
-        .output()
-        .context("Failed to search for GitHub issues")?;
+    #[test]
+    fn mentions_include_teams_and_optionally_reviewers() {
+        let reviewers = vec!["ada".to_string(), "@bob".to_string()];
+        let mut review = ReviewConfig::default();
+        assert_eq!(review_mentions(&review, &reviewers), None);
+
+        review.mention_teams = vec!["my-org/backend".to_string()];
+        assert_eq!(
+            review_mentions(&review, &reviewers).as_deref(),
+            Some("**Reviewers:** @my-org/backend\n")
+        );
 
-    if output.status.success() {
-        let json_output = String::from_utf8_lossy(&output.stdout);
+        review.mention_reviewers = true;
+        review.mention_teams.push("@ada".to_string());
+        assert_eq!(
+            review_mentions(&review, &reviewers).as_deref(),
+            Some("**Reviewers:** @ada @bob @my-org/backend\n")
+        );
+    }
 
-        if let Some(issue_num) = extract_issue_number(&json_output) {
-            let issue_num_str = issue_num.to_string();
+    #[test]
+    fn approvers_are_counted_once_per_login() {
+        let json = r#"{"comments": [
+            {"author": {"login": "ada"}, "body": "Approved via `tbdflow review --approve` (1/2)"},
+            {"author": {"login": "bob"}, "body": "Looks risky"},
+            {"author": {"login": "ada"}, "body": "Approved via `tbdflow review --approve` (1/2)"},
+            {"author": {"login": "cy"}, "body": "Approved via `tbdflow review --approve` (2/2)"}
+        ]}"#;
+        assert_eq!(approvers_from_comments(json), vec!["ada", "cy"]);
+        assert!(approvers_from_comments("not json").is_empty());
+    }
 
-            // Update labels: remove pending/concern, add dismissed
-            if opts.verbose {
-                println!(
-                    "{} Updating labels on issue #{}",
-                    "[INFO]".cyan(),
-                    issue_num
-                );
-            }
+    #[test]
+    fn gitea_approvers_and_review_issues_are_read_from_api_answers() {
+        let comments = json!([
+            {"user": {"login": "ada"}, "body": "Approved via `tbdflow review --approve` (1/2)"},
+            {"user": {"login": "bob"}, "body": "Looks risky"},
+            {"user": {"login": "cy"}, "body": "Approved via `tbdflow review --approve` (2/2)"}
+        ]);
+        assert_eq!(
+            approvers_in(&comments, "/body", "/user/login"),
+            vec!["ada", "cy"]
+        );
 
-            let _ = Command::new("gh")
-                .args([
-                    "issue",
-                    "edit",
-                    &issue_num_str,
-                    "--remove-label",
-                    &labels.pending,
-                ])
-                .output();
+        let issues = json!([
+            {"number": 3, "title": "Bump abc1234 in the lockfile"},
+            {"number": 4, "title": "[Review] feat: add login (abc1234)"}
+        ]);
+        assert_eq!(
+            gitea_review_issue(&issues, "abc1234").map(|i| &i["number"]),
+            Some(&json!(4))
+        );
+        assert!(gitea_review_issue(&issues, "def5678").is_none());
+    }
 
-            let _ = Command::new("gh")
-                .args([
-                    "issue",
-                    "edit",
-                    &issue_num_str,
-                    "--remove-label",
-                    &labels.concern,
-                ])
-                .output();
+    #[test]
+    fn bitbucket_review_issues_must_be_open() {
+        let page = json!({"values": [
+            {"id": 7, "state": "resolved", "title": "[Review] feat: add login (abc1234)"},
+            {"id": 9, "state": "new", "title": "[Review] feat: add login (abc1234)"}
+        ]});
+        assert_eq!(
+            bitbucket_review_issue(&page, "abc1234").map(|i| &i["id"]),
+            Some(&json!(9))
+        );
+        assert_eq!(bitbucket_state("failure"), "FAILED");
+        assert_eq!(bitbucket_state("pending"), "INPROGRESS");
+        assert_eq!(
+            commit_url("https://bitbucket.org/team/app", "abc1234"),
+            "https://bitbucket.org/team/app/commits/abc1234"
+        );
+        assert_eq!(
+            commit_url("https://github.com/team/app", "abc1234"),
+            "https://github.com/team/app/commit/abc1234"
+        );
+    }
 
-            let _ = Command::new("gh")
-                .args([
-                    "issue",
-                    "edit",
-                    &issue_num_str,
-                    "--add-label",
-                    &labels.dismissed,
-                ])
-                .output();
+    #[test]
+    fn local_approvers_are_distinct_per_commit() {
+        let approval = |author: &str, commit: &str| {
+            journal::JournalEntry::now(
+                author,
+                journal::JournalEvent::ReviewApproved {
+                    commit: commit.to_string(),
+                },
+            )
+        };
+        let entries = vec![
+            approval("Ada <ada@example.com>", "abc"),
+            approval("Ada <ada@example.com>", "abc"),
+            approval("Bob <bob@example.com>", "def"),
+        ];
+        assert_eq!(local_approvers(&entries, "abc").len(), 1);
+        assert_eq!(local_approvers(&entries, "def").len(), 1);
+        assert!(local_approvers(&entries, "xyz").is_empty());
+    }
 
-            // Close with a comment
-            let comment = format!(
-                "**Dismissed** via `tbdflow review --dismiss`\n\nReason: {}",
-                message
-            );
+    #[test]
+    fn digest_page_slices_newest_first_pages() {
+        let items = [1, 2, 3, 4, 5];
+        assert_eq!(digest_page(&items, None, 1), (&items[..], 1));
+        assert_eq!(digest_page(&items, Some(2), 1), (&items[0..2], 3));
+        assert_eq!(digest_page(&items, Some(2), 3), (&items[4..5], 3));
+        assert!(digest_page(&items, Some(2), 4).0.is_empty());
+        assert_eq!(digest_page::<i32>(&[], Some(2), 1).1, 1);
+    }
 
-            let close_output = Command::new("gh")
-                .args(["issue", "close", &issue_num_str, "--comment", &comment])
-                .output()
-                .context("Failed to close GitHub issue")?;
+    #[test]
+    fn digest_state_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_digest_state(dir.path()).unwrap().is_none());
+        let state = DigestState {
+            last_commit: "abc1234def".to_string(),
+            digested_at: "2026-10-17T09:00:00+00:00".to_string(),
+        };
+        save_digest_state(dir.path(), &state).unwrap();
+        assert_eq!(load_digest_state(dir.path()).unwrap(), Some(state));
+    }
 
-            if close_output.status.success() {
-                println!(
-                    "{}",
-                    format!(
-                        "Review for commit {} dismissed and issue #{} closed (label: {})",
-                        short_hash, issue_num, labels.dismissed
-                    )
-                    .dimmed()
-                );
-            } else {
-                println!(
-                    "{}",
-                    "Review dismissed (issue close failed)".to_string().yellow()
-                );
-            }
-        } else {
-            println!(
-                "{}",
-                format!(
-                    "Review for {} dismissed (no open review issue found)",
-                    short_hash
-                )
-                .dimmed()
-            );
-        }
-    } else {
-        println!(
-            "{}",
-            format!("Review for {} dismissed", short_hash).dimmed()
+    #[test]
+    fn first_issue_reads_number_body_and_url() {
+        let json = r#"[{"number": 42, "body": "b", "url": "https://github.com/o/r/issues/42"}, {"number": 7}]"#;
+        let issue = first_issue(json).unwrap().unwrap();
+        assert_eq!(issue.number, 42);
+        assert_eq!(issue.body, "b");
+        assert_eq!(issue.url, "https://github.com/o/r/issues/42");
+        assert_eq!(
+            first_issue(r#"[{"number":98765}]"#)
+                .unwrap()
+                .unwrap()
+                .number,
+            98765
         );
     }
 
-    Ok(())
-}
+    #[test]
+    fn first_issue_without_a_number_is_an_error() {
+        assert!(first_issue(r#"[{"body": "b"}]"#).is_err());
+    }
 
-fn close_github_review_issue(
-    labels: &ReviewLabelsConfig,
-    short_hash: &str,
-    opts: RunOpts,
-) -> Result<()> {
-    if !is_gh_cli_available() {
-        println!(
-            "{}",
-            "Warning: GitHub CLI (gh) not found. Marking as approved locally only.".yellow()
+    #[test]
+    fn first_issue_is_none_for_no_issues_or_no_output() {
+        assert!(first_issue("[]").unwrap().is_none());
+        // What gh::run returns under --dry-run.
+        assert!(first_issue("").unwrap().is_none());
+    }
+
+    #[test]
+    fn issue_record_reads_concerns_labels_and_comments() {
+        let issue = json!({
+            "url": "https://github.com/o/r/issues/7",
+            "state": "OPEN",
+            "labels": [{ "name": "review-concern" }],
+            "body": "## Non-blocking Review Request\n\n- [ ] Reviewed\n\n### Concerns\n\n\
+                     - [ ] Drops writes on retry\n- [x] Missing timeout\n\n---\n\n- [ ] not a concern",
+            "comments": [
+                { "author": { "login": "ada" }, "createdAt": "2026-10-17T09:00:00Z",
+                  "body": "**Concern Raised** (major)\n\nDrops writes on retry" },
+                { "author": { "login": "bob" }, "createdAt": "2026-10-17T10:00:00Z", "body": " " }
+            ]
+        });
+        let record = issue_record(&issue, &issue["comments"], &GITHUB_ISSUE);
+        assert_eq!(
+            record.issue.as_deref(),
+            Some("https://github.com/o/r/issues/7")
         );
-        println!("{}", format!("Commit {} approved", short_hash).green());
-        return Ok(());
+        assert_eq!(record.state.as_deref(), Some("open"));
+        assert_eq!(record.labels, vec!["review-concern"]);
+        let concerns: Vec<(&str, bool)> = record
+            .concerns
+            .iter()
+            .map(|c| (c.text.as_str(), c.resolved))
+            .collect();
+        assert_eq!(
+            concerns,
+            vec![("Drops writes on retry", false), ("Missing timeout", true)]
+        );
+        assert_eq!(record.comments.len(), 1);
+        assert_eq!(record.comments[0].author, "ada");
     }
 
-    // Search for the review issue
-    let search_query = format!("[Review] in:title {} in:title is:open", short_hash);
+    #[test]
+    fn review_stats_count_reviews_approval_times_and_people() {
+        let at = |rfc: &str| {
+            DateTime::parse_from_rfc3339(rfc)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let commit = |hash: &str, author: &str| TrunkCommit {
+            hash: hash.to_string(),
+            author: author.to_string(),
+            committed_at: at("2026-10-01T09:00:00Z"),
+        };
+        let event = |author: &str, action, when: &str| {
+            let mut event = NoteEvent::now(author.to_string(), action);
+            event.at = when.to_string();
+            event
+        };
+        let approved = recorded_commit_review(
+            &[
+                event("Ada", NoteAction::Requested, "2026-10-01T09:00:00Z"),
+                event("Bob", NoteAction::Approved, "2026-10-01T10:00:00Z"),
+                event("Cy", NoteAction::Approved, "2026-10-01T13:00:00Z"),
+            ],
+            2,
+        );
+        assert_eq!(approved.approved_at, Some(at("2026-10-01T13:00:00Z")));
+
+        let issues = json!([{
+            "title": "[Review] feat: add cache (bbbbbbb)",
+            "labels": [{ "name": "review-accepted" }],
+            "closedAt": "2026-10-01T11:00:00Z",
+            "comments": [
+                { "author": { "login": "bob" }, "body": "**Concern Raised** (minor)\n\nNo tests" },
+                { "author": { "login": "bob" }, "body": "Approved via `tbdflow review --approve`" }
+            ]
+        }]);
+        let mut reviews =
+            github_commit_reviews(&issues.to_string(), &ReviewLabelsConfig::default());
+        assert_eq!(reviews["bbbbbbb"].reviewers, vec!["bob"]);
+        assert_eq!(reviews["bbbbbbb"].concerns, 1);
+        reviews.insert("aaaaaaa".to_string(), approved);
+        reviews.insert(
+            "ccccccc".to_string(),
+            recorded_commit_review(
+                &[event("Ada", NoteAction::Requested, "2026-10-01T09:00:00Z")],
+                1,
+            ),
+        );
 
-    if opts.verbose {
-        println!("{} Searching for review issue...", "[INFO]".cyan());
+        let commits = [
+            commit("aaaaaaa1", "Ada"),
+            commit("bbbbbbb2", "Ada"),
+            commit("ccccccc3", "Ada"),
+            commit("ddddddd4", "Dee"),
+        ];
+        let stats = review_stats("origin/main", "30d", &commits, &reviews);
+        assert_eq!((stats.commits, stats.reviewed, stats.unreviewed), (4, 2, 2));
+        // Approved after 4h and after 2h.
+        assert_eq!(stats.median_seconds_to_approval, Some(3 * 3600));
+        assert_eq!(stats.concerns_by_author.len(), 1);
+        assert_eq!(stats.concerns_by_author[0].name, "Ada");
+        let reviewers: Vec<(&str, usize)> = stats
+            .top_reviewers
+            .iter()
+            .map(|r| (r.name.as_str(), r.count))
+            .collect();
+        assert_eq!(reviewers, vec![("Bob", 1), ("Cy", 1), ("bob", 1)]);
     }
 
-    let output = Command::new("gh")
-        .args([
-            "issue",
-            "list",
-            "--search",
-            &search_query,
-            "--json",
-            "number",
-            "--limit",
-            "1",
-        ])
-        .output()
-        .context("Failed to search for GitHub issues")?;
+    #[test]
+    fn trunk_commits_leave_out_review_ledger_commits() {
+        let log = "aaa\x1fAda\x1f1790000000\x1ffeat: add cache\n\
+                   bbb\x1fAda\x1f1790000100\x1fchore(review): approve aaa";
+        let commits = parse_trunk_commits(log);
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].hash, "aaa");
+    }
 
-    if output.status.success() {
-        let json_output = String::from_utf8_lossy(&output.stdout);
+    #[test]
+    fn recorded_concerns_are_resolved_by_fix_forwards_in_order() {
+        let event = |action, message: &str| {
+            let mut event = NoteEvent::now("Ada".to_string(), action);
+            event.message = Some(message.to_string());
+            event
+        };
+        let events = vec![
+            event(NoteAction::Concern, "Drops writes"),
+            event(NoteAction::Concern, "No timeout"),
+            NoteEvent {
+                commit: Some("fedcba9876".to_string()),
+                ..event(NoteAction::Fix, "fix: retry writes")
+            },
+        ];
+        let record = recorded_review_record(&events, 1).unwrap();
+        assert_eq!(record.state.as_deref(), Some("concern"));
+        assert!(record.concerns[0].resolved);
+        assert!(!record.concerns[1].resolved);
+        assert_eq!(record.comments[0].body, "concern: Drops writes");
+        assert_eq!(
+            record.comments[2].body,
+            "fix-forward fedcba9: fix: retry writes"
+        );
+        assert!(recorded_review_record(&[], 1).is_none());
+    }
 
-        // Simple JSON parsing for issue number
-        if let Some(issue_num) = extract_issue_number(&json_output) {
-            let issue_num_str = issue_num.to_string();
+    #[test]
+    fn unexpected_gh_output_is_an_error() {
+        let err = first_issue("not json").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Unexpected output from gh: not json")
+        );
+        assert!(parse_gh_json::<GhRepo>(r#"{"name": "r"}"#).is_err());
+    }
 
-            // Remove pending/concern labels and add accepted label
-            if opts.verbose {
-                println!(
-                    "{} Updating labels on issue #{}",
-                    "[INFO]".cyan(),
-                    issue_num
-                );
-            }
+    #[test]
+    fn repo_is_read_from_owner_login_and_name() {
+        let repo = parse_gh_json::<GhRepo>(
+            r#"{"name": "tbdflow", "owner": {"id": "x", "login": "cladam"}}"#,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            (repo.owner.login.as_str(), repo.name.as_str()),
+            ("cladam", "tbdflow")
+        );
+    }
 
-            let _ = Command::new("gh")
-                .args([
-                    "issue",
-                    "edit",
-                    &issue_num_str,
-                    "--remove-label",
-                    &labels.pending,
-                ])
-                .output();
+    #[test]
+    fn hex_colors_are_validated() {
+        assert!(is_valid_hex_color("FBCA04"));
+        assert!(is_valid_hex_color("#0e8a16"));
+        assert!(!is_valid_hex_color("red"));
+        assert!(!is_valid_hex_color("FBCA0"));
+        assert!(!is_valid_hex_color("#GGGGGG"));
+    }
 
-            let _ = Command::new("gh")
-                .args([
-                    "issue",
-                    "edit",
-                    &issue_num_str,
-                    "--remove-label",
-                    &labels.concern,
-                ])
-                .output();
+    #[test]
+    fn label_taxonomy_applies_overrides() {
+        let mut labels = ReviewLabelsConfig {
+            concern: "needs-fix".to_string(),
+            ..ReviewLabelsConfig::default()
+        };
+        labels
+            .colors
+            .insert("concern".to_string(), "#b60205".to_string());
+        labels
+            .descriptions
+            .insert("accepted".to_string(), "Looks good".to_string());
+
+        let specs = label_taxonomy(&labels).unwrap();
+        assert_eq!(specs.len(), 8);
+        let concern = specs.iter().find(|s| s.name == "needs-fix").unwrap();
+        assert_eq!(concern.color, "B60205");
+        let accepted = specs.iter().find(|s| s.name == "review-accepted").unwrap();
+        assert_eq!(accepted.description, "Looks good");
+        assert_eq!(accepted.color, "0E8A16");
+    }
 
-            let _ = Command::new("gh")
-                .args([
-                    "issue",
-                    "edit",
-                    &issue_num_str,
-                    "--add-label",
-                    &labels.accepted,
-                ])
-                .output();
+    #[test]
+    fn label_taxonomy_rejects_bad_color_and_unknown_role() {
+        let mut labels = ReviewLabelsConfig::default();
+        labels
+            .colors
+            .insert("pending".to_string(), "yellow".to_string());
+        let err = label_taxonomy(&labels).unwrap_err().to_string();
+        assert!(err.contains("Invalid color 'yellow'"));
+
+        let mut labels = ReviewLabelsConfig::default();
+        labels
+            .descriptions
+            .insert("blocked".to_string(), "x".to_string());
+        let err = label_taxonomy(&labels).unwrap_err().to_string();
+        assert!(err.contains("Unknown review label role 'blocked'"));
+    }
 
-            if opts.verbose {
-                println!("{} Closing issue #{}", "[INFO]".cyan(), issue_num);
-            }
+    #[test]
+    fn plan_label_sync_creates_updates_and_keeps() {
+        let wanted = label_taxonomy(&ReviewLabelsConfig::default()).unwrap();
+        let existing = parse_label_list(
+            r#"[{"name":"review-pending","color":"fbca04","description":"Review pending - awaiting attention"},
+                {"name":"review-concern","color":"000000","description":"old"},
+                {"name":"bug","color":"d73a4a","description":""}]"#,
+        );
+        let plan = plan_label_sync(&wanted, &existing);
+        assert_eq!(
+            plan[0],
+            LabelChange::Unchanged("review-pending".to_string())
+        );
+        assert!(matches!(&plan[1], LabelChange::Update(s) if s.name == "review-concern"));
+        assert!(matches!(&plan[2], LabelChange::Create(s) if s.name == "review-accepted"));
+        assert_eq!(plan.len(), 8);
+    }
 
-            let close_output = Command::new("gh")
-                .args([
-                    "issue",
-                    "close",
-                    &issue_num_str,
-                    "--comment",
-                    "Approved via `tbdflow review --approve`",
-                ])
-                .output()
-                .context("Failed to close GitHub issue")?;
+    #[test]
+    fn critical_concern_fails_status_regardless_of_config() {
+        let mut review = ReviewConfig::default();
+        let (state, template) = concern_commit_status(Some(ConcernSeverity::Critical), &review);
+        assert_eq!(state, "failure");
+        assert_eq!(template, "Critical Concern: {{message}}");
+
+        assert_eq!(
+            concern_commit_status(Some(ConcernSeverity::Minor), &review).0,
+            "pending"
+        );
+        assert_eq!(concern_commit_status(None, &review).0, "pending");
+        review.concern_blocks_status = true;
+        assert_eq!(
+            concern_commit_status(Some(ConcernSeverity::Major), &review).0,
+            "failure"
+        );
+    }
 
-            if close_output.status.success() {
-                println!(
-                    "{}",
-                    format!(
-                        "Commit {} approved and review issue #{} closed (label: {})",
-                        short_hash, issue_num, labels.accepted
-                    )
-                    .green()
-                );
-            } else {
-                println!(
-                    "{}",
-                    format!("Commit {} approved (issue close failed)", short_hash).yellow()
-                );
-            }
-        } else {
-            println!(
-                "{}",
-                format!(
-                    "Commit {} approved (no open review issue found)",
-                    short_hash
-                )
-                .green()
-            );
-        }
-    } else {
-        println!("{}", format!("Commit {} approved", short_hash).green());
+    #[test]
+    fn status_templates_are_rendered() {
+        let vars = StatusVars {
+            message: "data loss",
+            severity: Some(ConcernSeverity::Major),
+            sha: "abc1234def5678",
+            issue_url: "https://github.com/o/r/issues/7",
+        };
+        assert_eq!(
+            render_status_template("[{{severity}}] {{short_sha}}: {{message}}", &vars),
+            "[major] abc1234: data loss"
+        );
+        assert_eq!(
+            render_status_template("https://ci.example.com/review/{{sha}}", &vars),
+            "https://ci.example.com/review/abc1234def5678"
+        );
     }
 
-    Ok(())
-}
+    #[test]
+    fn long_descriptions_are_truncated() {
+        let description = truncate_description("x".repeat(200));
+        assert_eq!(description.chars().count(), MAX_STATUS_DESCRIPTION);
+        assert!(description.ends_with("..."));
+        assert_eq!(truncate_description("short".to_string()), "short");
+    }
 
-fn extract_issue_number(json: &str) -> Option<i64> {
-    let parsed: Value = serde_json::from_str(json).ok()?;
-    parsed.as_array()?.first()?["number"].as_i64()
-}
+    #[test]
+    fn severity_maps_to_configured_label() {
+        let labels = ReviewLabelsConfig {
+            critical: "sev-1".to_string(),
+            ..ReviewLabelsConfig::default()
+        };
+        assert_eq!(ConcernSeverity::Critical.label(&labels), "sev-1");
+        assert_eq!(ConcernSeverity::Minor.label(&labels), "concern-minor");
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn breaking_messages_are_detected() {
+        assert!(is_breaking_message("feat(api)!: drop v1 endpoints"));
+        assert!(is_breaking_message(
+            "refactor: rename config\n\nBREAKING CHANGE: `foo` is now `bar`"
+        ));
+        assert!(!is_breaking_message("fix: handle empty input"));
+        assert!(is_breaking_message(
+            "not conventional\n\nBREAKING CHANGE: still flagged"
+        ));
+    }
+
+    #[test]
+    fn skip_review_trailer_is_detected() {
+        assert!(has_skip_review_trailer("docs: fix typo\n\nReview: skip"));
+        assert!(has_skip_review_trailer("docs: fix typo\n\nreview: SKIP"));
+        assert!(!has_skip_review_trailer("docs: fix typo\n\nReview: later"));
+    }
 
     #[test]
-    fn short_hash_returns_first_seven_chars() {
-        assert_eq!(short_hash("abc1234567890"), "abc1234");
+    fn exemption_respects_allowlist_and_breaking() {
+        let config = Config::default();
+        assert_eq!(review_exemption_refusal(&config, "docs", false), None);
+        assert!(
+            review_exemption_refusal(&config, "feat", false)
+                .unwrap()
+                .contains("'feat' is not in review.exempt_types")
+        );
+        assert!(
+            review_exemption_refusal(&config, "chore", true)
+                .unwrap()
+                .contains("breaking")
+        );
+    }
+
+    fn pending(age_hours: i64, escalated: bool) -> PendingReview {
+        PendingReview {
+            number: 1,
+            title: "[Review] feat: x (abc1234)".to_string(),
+            age_hours,
+            escalated,
+        }
     }
 
     #[test]
-    fn short_hash_handles_exact_seven_chars() {
-        assert_eq!(short_hash("abc1234"), "abc1234");
+    fn sweep_classifies_by_age_and_label() {
+        assert_eq!(
+            classify_pending_review(&pending(3, false), 24, 72),
+            SweepStatus::Fresh
+        );
+        assert_eq!(
+            classify_pending_review(&pending(30, false), 24, 72),
+            SweepStatus::Stale
+        );
+        assert_eq!(
+            classify_pending_review(&pending(72, false), 24, 72),
+            SweepStatus::Escalate
+        );
+        assert_eq!(
+            classify_pending_review(&pending(100, true), 24, 72),
+            SweepStatus::AlreadyEscalated
+        );
     }
 
     #[test]
-    fn short_hash_handles_short_input() {
-        assert_eq!(short_hash("abc"), "abc");
+    fn parse_pending_reviews_computes_age_and_escalation() {
+        let now = DateTime::parse_from_rfc3339("2025-03-04T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let json = r#"[
+            {"number":7,"title":"[Review] fix: a (1111111)","createdAt":"2025-03-01T12:00:00Z",
+             "labels":[{"name":"review-pending"},{"name":"review-escalated"}]},
+            {"number":8,"title":"[Review] feat: b (2222222)","createdAt":"2025-03-04T02:00:00Z","labels":[]}
+        ]"#;
+        let reviews = parse_pending_reviews(json, "review-escalated", now);
+        assert_eq!(reviews.len(), 2);
+        assert_eq!(reviews[0].age_hours, 72);
+        assert!(reviews[0].escalated);
+        assert_eq!(reviews[1].age_hours, 10);
+        assert!(!reviews[1].escalated);
     }
 
     #[test]
-    fn short_hash_handles_empty_input() {
-        assert_eq!(short_hash(""), "");
+    fn ages_are_parsed_into_hours() {
+        assert_eq!(parse_age_hours("12h").unwrap(), 12);
+        assert_eq!(parse_age_hours("3d").unwrap(), 72);
+        assert_eq!(parse_age_hours("2w").unwrap(), 336);
+        assert!(parse_age_hours("3").is_err());
+        assert!(parse_age_hours("d").is_err());
+        assert!(parse_age_hours("3m").is_err());
     }
 
     #[test]
-    fn extract_issue_number_parses_valid_json() {
-        let json = r#"[{"number":123}]"#;
-        assert_eq!(extract_issue_number(json), Some(123));
+    fn review_listings_filter_by_author_and_age() {
+        let now = Utc::now();
+        let created = |hours: i64| (now - chrono::Duration::hours(hours)).to_rfc3339();
+        let json = format!(
+            r#"[
+                {{"number": 1, "title": "[Review] a", "url": "u1", "createdAt": "{}",
+                  "body": "**Commit:** x\n**Author:** Ada\n**Message:** a", "labels": [{{"name": "review-concern"}}]}},
+                {{"number": 2, "title": "[Review] b", "url": "u2", "createdAt": "{}",
+                  "body": "**Author:** Bob", "labels": []}}
+            ]"#,
+            created(80),
+            created(5)
+        );
+        let listings = parse_review_listings(&json, "review-concern", now);
+        assert_eq!(listings[0].author.as_deref(), Some("Ada"));
+        assert_eq!(listings[0].labels, vec!["review-concern"]);
+        assert_eq!(listings[0].state, "concern");
+        assert_eq!(listings[1].state, "pending");
+
+        let ada = filter_review_listings(listings.clone(), Some("Ada"), None);
+        assert_eq!(ada.len(), 1);
+        assert_eq!(ada[0].number, Some(1));
+
+        let old = filter_review_listings(listings, None, Some(72));
+        assert_eq!(
+            old.iter().map(|l| l.number).collect::<Vec<_>>(),
+            vec![Some(1)]
+        );
     }
 
     #[test]
-    fn extract_issue_number_parses_larger_number() {
-        let json = r#"[{"number":98765}]"#;
-        assert_eq!(extract_issue_number(json), Some(98765));
+    fn review_listings_take_commit_and_reviewers_from_the_issue() {
+        let json = r#"[
+            {"number": 3, "title": "[Review] feat: x (abc1234)", "createdAt": "2025-03-01T12:00:00Z",
+             "labels": [], "assignees": [{"login": "ada"}, {"login": "bo"}]},
+            {"number": 4, "title": "[Review] main~2..main (2 commits, aaaaaaa..bbbbbbb)",
+             "createdAt": "2025-03-01T12:00:00Z", "labels": []}
+        ]"#;
+        let now = Utc::now();
+        let listings = parse_review_listings(json, "review-concern", now);
+        assert_eq!(listings[0].commit, "abc1234");
+        assert_eq!(listings[0].reviewers, vec!["ada", "bo"]);
+        assert_eq!(listings[1].commit, "aaaaaaa..bbbbbbb");
+        assert!(listings[1].reviewers.is_empty());
+        assert_eq!(format_age(5), "5h");
+        assert_eq!(format_age(80), "3d");
     }
 
     #[test]
-    fn extract_issue_number_returns_none_for_empty_array() {
-        let json = r#"[]"#;
-        assert_eq!(extract_issue_number(json), None);
+    fn mention_prefixes_handles() {
+        assert_eq!(mention("my-org/leads"), "@my-org/leads");
+        assert_eq!(mention("@ada"), "@ada");
     }
 
     #[test]
-    fn extract_issue_number_returns_none_for_invalid_json() {
-        let json = r#"not json"#;
-        assert_eq!(extract_issue_number(json), None);
+    fn review_obligations_summary() {
+        let none = ReviewObligations {
+            pending_assigned: 0,
+            concerns_on_own_commits: 0,
+        };
+        assert_eq!(none.summary(), "Reviews: nothing waiting on you");
+        let some = ReviewObligations {
+            pending_assigned: 2,
+            concerns_on_own_commits: 1,
+        };
+        assert_eq!(
+            some.summary(),
+            "Reviews: 2 pending assigned to you, 1 concern on your commits"
+        );
     }
 
     #[test]
-    fn extract_issue_number_handles_whitespace() {
-        let json = r#"[{"number": 42}]"#;
-        assert_eq!(extract_issue_number(json), Some(42));
+    fn review_obligations_cache_expires() {
+        let now = Utc::now();
+        let five_minutes_ago = (now - chrono::Duration::minutes(5)).to_rfc3339();
+        assert!(is_cache_fresh(&five_minutes_ago, now, 10));
+        assert!(!is_cache_fresh(&five_minutes_ago, now, 5));
+        assert!(!is_cache_fresh("not a date", now, 10));
     }
 }
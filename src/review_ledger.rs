@@ -0,0 +1,196 @@
+//! Reviews recorded as YAML files under `.tbdflow/reviews/`, one per reviewed
+//! commit, and committed to trunk, for the `in-repo` strategy. Every clone
+//! has them and git history is the audit trail. The events are the same as
+//! the `git-notes` strategy records.
+
+use crate::config::Config;
+use crate::git::{self, RunOpts};
+use crate::review_notes::{NoteAction, NoteEvent};
+use anyhow::{Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+
+/// Where the ledger files live, relative to the repository root.
+pub const LEDGER_DIR: &str = ".tbdflow/reviews";
+
+/// The contents of one `.tbdflow/reviews/<hash>.yml`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ledger {
+    pub commit: String,
+    /// Subject of the reviewed commit, so the file reads on its own.
+    #[serde(default)]
+    pub subject: String,
+    #[serde(default)]
+    pub events: Vec<NoteEvent>,
+}
+
+pub fn ledger_path(commit: &str) -> String {
+    format!("{}/{}.yml", LEDGER_DIR, commit)
+}
+
+/// Subject line of the commit that records `event` about `commit`.
+fn ledger_message(event: &NoteEvent, commit: &str) -> String {
+    let what = match event.action {
+        NoteAction::Requested => "request review of",
+        NoteAction::Approved => "approve",
+        NoteAction::Concern => "raise concern on",
+        NoteAction::Dismissed => "dismiss review of",
+        NoteAction::Fix => "link fix-forward to",
+    };
+    format!("chore(review): {} {}", what, git::short_hash(commit))
+}
+
+fn has_origin(opts: RunOpts) -> bool {
+    git::get_remote_url(opts.for_reads()).is_ok_and(|url| !url.is_empty())
+}
+
+/// The trunk the ledger is read from and committed to: origin's when there
+/// is an origin, else the local branch.
+fn trunk_ref(config: &Config, opts: RunOpts) -> String {
+    let remote = format!("refs/remotes/origin/{}", config.main_branch_name);
+    if has_origin(opts) && git::ref_exists(&remote, opts.for_reads()) {
+        remote
+    } else {
+        format!("refs/heads/{}", config.main_branch_name)
+    }
+}
+
+/// True when origin's trunk can be reached, or there is no origin at all.
+pub fn is_reachable(config: &Config, opts: RunOpts) -> bool {
+    !has_origin(opts)
+        || git::run_git_command(
+            "ls-remote",
+            &["--heads", "origin", &config.main_branch_name],
+            opts.for_reads(),
+        )
+        .is_ok()
+}
+
+/// Fetches origin's trunk, if there is an origin.
+pub fn fetch(config: &Config, opts: RunOpts) -> Result<()> {
+    if has_origin(opts) {
+        git::fetch_branch(&config.main_branch_name, opts)?;
+    }
+    Ok(())
+}
+
+fn read_at(rev: &str, commit: &str, opts: RunOpts) -> Result<Option<Ledger>> {
+    match git::show_file(rev, &ledger_path(commit), opts.for_reads()) {
+        Some(text) => Ok(Some(yaml_serde::from_str(&text)?)),
+        None => Ok(None),
+    }
+}
+
+/// The ledger for `commit` on trunk, as last fetched.
+pub fn read(config: &Config, commit: &str, opts: RunOpts) -> Result<Option<Ledger>> {
+    read_at(&trunk_ref(config, opts), commit, opts)
+}
+
+/// Every ledger on trunk, as last fetched.
+pub fn read_all(config: &Config, opts: RunOpts) -> Result<Vec<Ledger>> {
+    let rev = trunk_ref(config, opts);
+    let mut ledgers = Vec::new();
+    for path in git::list_files_at(&rev, LEDGER_DIR, opts.for_reads()) {
+        if let Some(text) = git::show_file(&rev, &path, opts.for_reads()) {
+            ledgers
+                .push(yaml_serde::from_str(&text).map_err(|e| anyhow!("Invalid {}: {}", path, e))?);
+        }
+    }
+    Ok(ledgers)
+}
+
+/// Adds `event` to `commit`'s ledger in a new commit on trunk and pushes it.
+/// If someone else pushed first, it is redone once on top of theirs.
+pub fn record(
+    config: &Config,
+    commit: &str,
+    subject: &str,
+    event: NoteEvent,
+    opts: RunOpts,
+) -> Result<Ledger> {
+    let trunk = &config.main_branch_name;
+    let path = ledger_path(commit);
+    for attempt in 0..2 {
+        fetch(config, opts)?;
+        let base = git::resolve_commit_hash(&trunk_ref(config, opts), opts.for_reads())?;
+        let mut ledger = read_at(&base, commit, opts)?.unwrap_or_else(|| Ledger {
+            commit: commit.to_string(),
+            subject: subject.to_string(),
+            events: Vec::new(),
+        });
+        ledger.events.push(event.clone());
+        let message = ledger_message(&event, commit);
+        let yaml = yaml_serde::to_string(&ledger)?;
+        let recorded = git::commit_file_onto(&base, &path, &yaml, &message, opts)?;
+
+        if has_origin(opts) {
+            match git::push_commit_to_branch(&recorded, trunk, opts) {
+                Ok(_) => {}
+                Err(e) if attempt == 0 && git::is_non_fast_forward(&e.to_string()) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        advance_local_trunk(config, &base, &recorded, &path, opts)?;
+        return Ok(ledger);
+    }
+    bail!("'{}' kept moving while recording the review.", trunk)
+}
+
+/// Brings the local trunk up to the ledger commit when that loses nothing:
+/// when it was the commit built on, or it is not checked out and behind.
+fn advance_local_trunk(
+    config: &Config,
+    base: &str,
+    recorded: &str,
+    path: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    let trunk = &config.main_branch_name;
+    let local_ref = format!("refs/heads/{}", trunk);
+    let Ok(local) = git::resolve_commit_hash(&local_ref, opts.for_reads()) else {
+        return Ok(());
+    };
+    let checked_out = git::get_current_branch(opts.for_reads()).is_ok_and(|b| b == *trunk);
+    if local == base {
+        git::update_ref(&local_ref, recorded, base, opts)?;
+        if checked_out {
+            // Only the ledger file differs from what is checked out.
+            git::checkout_path_from_head(path, opts)?;
+        }
+    } else if !checked_out
+        && git::run_git_command(
+            "merge-base",
+            &["--is-ancestor", &local, recorded],
+            opts.for_reads(),
+        )
+        .is_ok()
+    {
+        git::update_ref(&local_ref, recorded, &local, opts)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ledger_files_are_yaml_named_after_the_commit() {
+        let mut event = NoteEvent::now("Ada <ada@example.com>".to_string(), NoteAction::Concern);
+        event.at = "2026-10-17T09:00:00+00:00".to_string();
+        event.message = Some("Drops writes".to_string());
+        let ledger = Ledger {
+            commit: "abc1234def".to_string(),
+            subject: "feat: add cache".to_string(),
+            events: vec![event.clone()],
+        };
+
+        let yaml = yaml_serde::to_string(&ledger).unwrap();
+        assert!(yaml.contains("action: concern"));
+        assert_eq!(yaml_serde::from_str::<Ledger>(&yaml).unwrap(), ledger);
+        assert_eq!(ledger_path("abc1234def"), ".tbdflow/reviews/abc1234def.yml");
+        assert_eq!(
+            ledger_message(&event, "abc1234def"),
+            "chore(review): raise concern on abc1234"
+        );
+    }
+}
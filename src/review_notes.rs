@@ -0,0 +1,267 @@
+//! Review state kept in git notes under `refs/notes/tbdflow-reviews`, for the
+//! `git-notes` strategy. A reviewed commit's note holds one JSON line per
+//! review event, so notes written in two clones merge by keeping both sets
+//! of lines.
+
+use crate::git::{self, RunOpts};
+use crate::review::ConcernSeverity;
+use anyhow::Result;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+pub const NOTES_REF: &str = "refs/notes/tbdflow-reviews";
+
+/// Where origin's review notes are fetched to before being merged.
+const FETCHED_REF: &str = "refs/notes/tbdflow-reviews-origin";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoteAction {
+    Requested,
+    Approved,
+    Concern,
+    Dismissed,
+    /// A fix-forward commit named the review in a `Fixes-Review` trailer.
+    Fix,
+}
+
+impl NoteAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NoteAction::Requested => "requested",
+            NoteAction::Approved => "approved",
+            NoteAction::Concern => "concern",
+            NoteAction::Dismissed => "dismissed",
+            NoteAction::Fix => "fix",
+        }
+    }
+}
+
+/// One line of a review note. `at` comes first so that sorting the lines, as
+/// the `cat_sort_uniq` merge does, keeps them in the order they happened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoteEvent {
+    pub at: String,
+    pub author: String,
+    pub action: NoteAction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<ConcernSeverity>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reviewers: Vec<String>,
+    /// The fix-forward commit, for `fix` events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+impl NoteEvent {
+    pub fn now(author: String, action: NoteAction) -> Self {
+        Self {
+            at: Utc::now().to_rfc3339(),
+            author,
+            action,
+            message: None,
+            severity: None,
+            reviewers: Vec::new(),
+            commit: None,
+        }
+    }
+}
+
+/// The events in a note, oldest first. Lines that are not events are skipped.
+pub fn parse(note: &str) -> Vec<NoteEvent> {
+    note.lines()
+        .filter_map(|line| serde_json::from_str(line.trim()).ok())
+        .collect()
+}
+
+fn render(events: &[NoteEvent]) -> Result<String> {
+    let lines = events
+        .iter()
+        .map(serde_json::to_string)
+        .collect::<serde_json::Result<Vec<_>>>()?;
+    Ok(lines.join("\n"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReviewState {
+    Pending,
+    Approved,
+    Concern,
+    Dismissed,
+}
+
+impl ReviewState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReviewState::Pending => "pending",
+            ReviewState::Approved => "approved",
+            ReviewState::Concern => "concern",
+            ReviewState::Dismissed => "dismissed",
+        }
+    }
+}
+
+/// Where a review stands after all of its events.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewStatus {
+    pub state: ReviewState,
+    pub reviewers: Vec<String>,
+    /// Distinct authors of approvals.
+    pub approvers: Vec<String>,
+    /// Concerns not yet answered by a fix-forward.
+    pub open_concerns: usize,
+}
+
+/// Replays `events`; `None` when no review was ever recorded. A review is
+/// approved once `required` different people have approved it.
+pub fn fold(events: &[NoteEvent], required: usize) -> Option<ReviewStatus> {
+    if events.is_empty() {
+        return None;
+    }
+    let mut status = ReviewStatus {
+        state: ReviewState::Pending,
+        reviewers: Vec::new(),
+        approvers: Vec::new(),
+        open_concerns: 0,
+    };
+    for event in events {
+        match event.action {
+            NoteAction::Requested => {
+                status.state = ReviewState::Pending;
+                for reviewer in &event.reviewers {
+                    if !status.reviewers.contains(reviewer) {
+                        status.reviewers.push(reviewer.clone());
+                    }
+                }
+            }
+            NoteAction::Approved => {
+                if !status.approvers.contains(&event.author) {
+                    status.approvers.push(event.author.clone());
+                }
+                if status.approvers.len() >= required.max(1) {
+                    status.state = ReviewState::Approved;
+                    status.open_concerns = 0;
+                }
+            }
+            NoteAction::Concern => {
+                status.state = ReviewState::Concern;
+                status.open_concerns += 1;
+            }
+            NoteAction::Fix => status.open_concerns = status.open_concerns.saturating_sub(1),
+            NoteAction::Dismissed => status.state = ReviewState::Dismissed,
+        }
+    }
+    Some(status)
+}
+
+/// Every review event recorded on `commit`, oldest first.
+pub fn read(commit: &str, opts: RunOpts) -> Vec<NoteEvent> {
+    git::read_note(NOTES_REF, commit, opts)
+        .map(|note| parse(&note))
+        .unwrap_or_default()
+}
+
+/// Every commit with review notes, and its events.
+pub fn read_all(opts: RunOpts) -> Vec<(String, Vec<NoteEvent>)> {
+    git::list_noted_commits(NOTES_REF, opts)
+        .into_iter()
+        .map(|commit| {
+            let events = read(&commit, opts);
+            (commit, events)
+        })
+        .collect()
+}
+
+/// Adds `event` to `commit`'s note and returns all of its events.
+pub fn append(commit: &str, event: NoteEvent, opts: RunOpts) -> Result<Vec<NoteEvent>> {
+    let mut events = read(commit, opts.for_reads());
+    events.push(event);
+    git::write_note(NOTES_REF, commit, &render(&events)?, opts)?;
+    Ok(events)
+}
+
+fn has_origin(opts: RunOpts) -> bool {
+    git::get_remote_url(opts.for_reads()).is_ok_and(|url| !url.is_empty())
+}
+
+/// Merges origin's review notes into ours. Does nothing without an `origin`,
+/// or when origin has no review notes yet.
+pub fn pull(opts: RunOpts) -> Result<()> {
+    if !has_origin(opts) || git::fetch_notes(NOTES_REF, FETCHED_REF, opts).is_err() {
+        return Ok(());
+    }
+    if git::ref_exists(FETCHED_REF, opts.for_reads()) {
+        git::merge_notes(NOTES_REF, FETCHED_REF, opts)?;
+    }
+    Ok(())
+}
+
+/// Pushes our review notes to origin, merging in theirs first if someone
+/// else pushed since we last pulled.
+pub fn push(opts: RunOpts) -> Result<()> {
+    if !has_origin(opts) || !git::ref_exists(NOTES_REF, opts.for_reads()) {
+        return Ok(());
+    }
+    match git::push_ref(NOTES_REF, opts) {
+        Err(e) if git::is_non_fast_forward(&e.to_string()) => {
+            pull(opts)?;
+            git::push_ref(NOTES_REF, opts)?;
+        }
+        result => {
+            result?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(author: &str, action: NoteAction) -> NoteEvent {
+        NoteEvent {
+            at: "2026-10-17T09:00:00+00:00".to_string(),
+            ..NoteEvent::now(author.to_string(), action)
+        }
+    }
+
+    #[test]
+    fn notes_round_trip_and_skip_foreign_lines() {
+        let mut concern = event("Ada <ada@example.com>", NoteAction::Concern);
+        concern.message = Some("Drops writes".to_string());
+        concern.severity = Some(ConcernSeverity::Major);
+        let events = vec![event("Bo <bo@example.com>", NoteAction::Requested), concern];
+
+        let note = render(&events).unwrap();
+        assert!(note.lines().all(|line| line.starts_with("{\"at\":")));
+        assert_eq!(parse(&format!("{}\nnot json", note)), events);
+    }
+
+    #[test]
+    fn state_follows_the_events() {
+        let mut events = vec![event("Bo", NoteAction::Requested)];
+        assert_eq!(fold(&events, 2).unwrap().state, ReviewState::Pending);
+
+        events.push(event("Ada", NoteAction::Concern));
+        events.push(event("Ada", NoteAction::Fix));
+        let status = fold(&events, 2).unwrap();
+        assert_eq!(status.state, ReviewState::Concern);
+        assert_eq!(status.open_concerns, 0);
+
+        // The same person approving twice counts once.
+        events.push(event("Ada", NoteAction::Approved));
+        events.push(event("Ada", NoteAction::Approved));
+        let status = fold(&events, 2).unwrap();
+        assert_eq!(status.state, ReviewState::Concern);
+        assert_eq!(status.approvers, vec!["Ada"]);
+
+        events.push(event("Cy", NoteAction::Approved));
+        assert_eq!(fold(&events, 2).unwrap().state, ReviewState::Approved);
+
+        events.push(event("Bo", NoteAction::Dismissed));
+        assert_eq!(fold(&events, 2).unwrap().state, ReviewState::Dismissed);
+        assert_eq!(fold(&[], 1), None);
+    }
+}
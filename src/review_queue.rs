@@ -0,0 +1,126 @@
+//! Review actions taken while GitHub was unreachable, kept until they can be
+//! replayed.
+
+use crate::git;
+use crate::review::ConcernSeverity;
+use crate::state::{self, State};
+use anyhow::Result;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A review action waiting to be sent to GitHub.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum Action {
+    Approve {
+        commit: String,
+    },
+    Concern {
+        commit: String,
+        message: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        severity: Option<ConcernSeverity>,
+    },
+    Dismiss {
+        commit: String,
+        message: String,
+    },
+}
+
+impl Action {
+    /// Short description, e.g. "approve abc1234".
+    pub fn describe(&self) -> String {
+        let short = |c: &str| git::short_hash(c).to_string();
+        match self {
+            Action::Approve { commit } => format!("approve {}", short(commit)),
+            Action::Concern { commit, .. } => format!("concern on {}", short(commit)),
+            Action::Dismiss { commit, .. } => format!("dismiss {}", short(commit)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedAction {
+    pub queued_at: String,
+    #[serde(flatten)]
+    pub action: Action,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+struct ReviewQueue(Vec<QueuedAction>);
+
+impl State for ReviewQueue {
+    const FILE: &'static str = "review-queue.json";
+    const LEGACY_FILE: Option<&'static str> = Some("tbdflow-review-queue.json");
+}
+
+/// Every queued action, oldest first.
+pub fn load(git_dir: &Path) -> Result<Vec<QueuedAction>> {
+    Ok(state::load::<ReviewQueue>(git_dir)?
+        .map(|queue| queue.0)
+        .unwrap_or_default())
+}
+
+/// Replaces the queue; an empty queue removes the file.
+pub fn save(git_dir: &Path, actions: &[QueuedAction]) -> Result<()> {
+    if actions.is_empty() {
+        state::remove::<ReviewQueue>(git_dir)?;
+        return Ok(());
+    }
+    state::save(git_dir, &ReviewQueue(actions.to_vec()))
+}
+
+/// Adds `action` to the end of the queue and returns the new queue length.
+pub fn push(git_dir: &Path, action: Action) -> Result<usize> {
+    let mut actions = load(git_dir)?;
+    actions.push(QueuedAction {
+        queued_at: Utc::now().to_rfc3339(),
+        action,
+    });
+    save(git_dir, &actions)?;
+    Ok(actions.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_round_trips_and_empties() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load(dir.path()).unwrap().is_empty());
+
+        push(
+            dir.path(),
+            Action::Approve {
+                commit: "abc1234def".to_string(),
+            },
+        )
+        .unwrap();
+        let count = push(
+            dir.path(),
+            Action::Concern {
+                commit: "abc1234def".to_string(),
+                message: "Drops writes".to_string(),
+                severity: Some(ConcernSeverity::Critical),
+            },
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+
+        let actions = load(dir.path()).unwrap();
+        assert_eq!(actions[0].action.describe(), "approve abc1234");
+        assert!(matches!(
+            actions[1].action,
+            Action::Concern {
+                severity: Some(ConcernSeverity::Critical),
+                ..
+            }
+        ));
+
+        save(dir.path(), &[]).unwrap();
+        assert!(!dir.path().join("tbdflow").join(ReviewQueue::FILE).exists());
+    }
+}
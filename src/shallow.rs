@@ -0,0 +1,72 @@
+//! Shallow clones, which CI checks out by default, are missing the history and
+//! tags that changelogs, tag lookups and branch ages are read from.
+
+use crate::git::{self, RunOpts};
+use crate::prompt;
+use anyhow::Result;
+use colored::Colorize;
+
+const UNSHALLOW_HINT: &str =
+    "Run 'git fetch --unshallow --tags' (or 'fetch-depth: 0' in CI) to fix.";
+
+/// True in a shallow clone. Checked under `--dry-run` too.
+pub fn is_shallow(opts: RunOpts) -> bool {
+    git::is_shallow_repository(opts.for_reads()).unwrap_or(false)
+}
+
+/// Call before reading history for `what`. In a shallow clone, offers to fetch
+/// the full history and tags; without a terminal that takes `--yes`. Declining
+/// carries on with a warning. Returns whether the full history is available.
+/// Messages go to stderr, so `--json` output stays parseable.
+pub fn ensure_full_history(what: &str, json: bool, opts: RunOpts) -> Result<bool> {
+    if !is_shallow(opts) {
+        return Ok(true);
+    }
+    eprintln!(
+        "{}",
+        format!(
+            "This is a shallow clone, so {} may be incomplete or wrong.",
+            what
+        )
+        .yellow()
+    );
+    let deepen = if json {
+        opts.assume_yes
+    } else {
+        prompt::confirm(
+            "Fetch the full history and tags now (git fetch --unshallow --tags)?",
+            Some(false),
+            opts,
+        )?
+    };
+    if !deepen {
+        eprintln!(
+            "{}",
+            format!("Continuing with the shallow history. {}", UNSHALLOW_HINT).yellow()
+        );
+        return Ok(false);
+    }
+    git::unshallow(opts)?;
+    eprintln!("{}", "Fetched the full history and tags.".green());
+    Ok(true)
+}
+
+/// Warns that `what` may be incomplete in a shallow clone, without offering
+/// to deepen it: for checks that run as part of other commands.
+pub fn warn_if_shallow(what: &str, opts: RunOpts) {
+    if is_shallow(opts) {
+        eprintln!(
+            "{}",
+            format!(
+                "Warning: this is a shallow clone, so {} may be incomplete. {}",
+                what, UNSHALLOW_HINT
+            )
+            .yellow()
+        );
+    }
+}
+
+/// Explains why no tag was found when the clone is shallow.
+pub fn missing_tag_hint(opts: RunOpts) -> Option<String> {
+    is_shallow(opts).then(|| format!("This is a shallow clone. {}", UNSHALLOW_HINT))
+}
@@ -0,0 +1,194 @@
+//! Searches past trunk commit messages so a recurring change can reuse one.
+
+use crate::commands::{SnippetMatchResponse, SnippetResponse, TbdResponse};
+use crate::commit::{self, CommitParams};
+use crate::config::Config;
+use crate::git::{self, RunOpts};
+use crate::{output, prompt, wizard};
+use anyhow::Result;
+use colored::Colorize;
+use dialoguer::Select;
+
+/// Conventional commits in `git::get_log_messages` output whose message
+/// contains every word of `query` (case-insensitive), newest first. Commits
+/// with the same header and body are listed once, with how often they were used.
+pub fn search_messages(log: &str, query: &str, limit: usize) -> Vec<SnippetMatchResponse> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    let mut matches: Vec<(SnippetMatchResponse, (String, Option<String>))> = Vec::new();
+    for record in log.split('\x1e') {
+        let mut fields = record.trim_start_matches('\n').splitn(4, '\x1f');
+        let (Some(sha), Some(date), Some(author), Some(message)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        let message = message.trim();
+        let lower = message.to_lowercase();
+        if !terms.iter().all(|term| lower.contains(term.as_str())) {
+            continue;
+        }
+        let Ok(parsed) = commit::parse_commit_message(message) else {
+            continue;
+        };
+        let subject = message.lines().next().unwrap_or_default().to_string();
+        let key = (subject.clone(), parsed.body);
+        if let Some((existing, _)) = matches.iter_mut().find(|(_, k)| *k == key) {
+            existing.uses += 1;
+            continue;
+        }
+        matches.push((
+            SnippetMatchResponse {
+                sha: git::short_hash(sha).to_string(),
+                date: date.to_string(),
+                author: author.to_string(),
+                subject,
+                message: message.to_string(),
+                uses: 1,
+            },
+            key,
+        ));
+    }
+    matches.into_iter().take(limit).map(|(m, _)| m).collect()
+}
+
+/// The picked message as wizard answers: trailers, tags and the like
+/// belonged to the old commit and are left out.
+pub fn template_from(message: &str) -> Result<CommitParams> {
+    Ok(CommitParams {
+        tag: None,
+        trailers: Vec::new(),
+        ..commit::parse_commit_message(message)?
+    })
+}
+
+fn uses_suffix(m: &SnippetMatchResponse) -> String {
+    if m.uses > 1 {
+        format!(", used {} times", m.uses)
+    } else {
+        String::new()
+    }
+}
+
+/// Lists trunk commit messages matching `query` and, in a terminal, lets the
+/// user pick one to adapt in the commit wizard and commit.
+pub fn handle_snippet(
+    config: &Config,
+    query: &str,
+    since: Option<&str>,
+    limit: usize,
+    list: bool,
+    json: bool,
+    opts: RunOpts,
+) -> Result<()> {
+    let trunk = format!("origin/{}", config.main_branch_name);
+    let quiet = opts.for_reads();
+    let log = git::get_log_messages(&trunk, since, quiet)?;
+    let matches = search_messages(&log, query, limit);
+
+    if json {
+        let response = SnippetResponse {
+            query: query.to_string(),
+            matches,
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(response))?
+        );
+        return Ok(());
+    }
+
+    if matches.is_empty() {
+        println!(
+            "{}",
+            format!("No commits on '{}' match '{}'.", trunk, query).yellow()
+        );
+        return Ok(());
+    }
+
+    if list || !prompt::is_interactive() {
+        println!(
+            "{}",
+            format!("--- Commits on '{}' matching '{}' ---", trunk, query).blue()
+        );
+        for m in &matches {
+            println!("  {} {}", m.sha.yellow(), m.subject);
+            println!(
+                "{}",
+                format!("          {} by {}{}", m.date, m.author, uses_suffix(m)).dimmed()
+            );
+        }
+        return Ok(());
+    }
+
+    let items: Vec<String> = matches
+        .iter()
+        .map(|m| format!("{} ({} {}{})", m.subject, m.sha, m.date, uses_suffix(m)))
+        .collect();
+    let Some(choice) = Select::with_theme(&*output::theme())
+        .with_prompt("Pick a message to reuse (Esc to cancel)")
+        .items(&items)
+        .default(0)
+        .interact_opt()?
+    else {
+        println!("{}", "Nothing selected.".yellow());
+        return Ok(());
+    };
+
+    let template = template_from(&matches[choice].message)?;
+    let w = wizard::run_commit_wizard_from(config, Some(&template))?;
+    let params = CommitParams {
+        r#type: w.r#type,
+        scope: w.scope,
+        message: w.message,
+        body: w.body,
+        breaking: w.breaking,
+        breaking_description: w.breaking_description,
+        tag: w.tag,
+        issue: w.issue,
+        include_projects: false,
+        no_verify: false,
+        trailers: Vec::new(),
+        preview: false,
+        fix: false,
+    };
+    commit::handle_commit(opts, config, params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log() -> String {
+        [
+            "c3\x1f2026-10-17\x1fAda\x1fchore(deps): bump serde\n\nTbdflow-Version: 0.34.0",
+            "c2\x1f2026-10-10\x1fBob\x1fchore(deps): bump serde",
+            "c1\x1f2026-10-03\x1fAda\x1fchore(deps): bump clap\n\nWeekly Dependabot batch.",
+            "c0\x1f2026-10-01\x1fAda\x1fbump deps by hand",
+        ]
+        .join("\x1e\n")
+    }
+
+    #[test]
+    fn matches_every_term_and_groups_repeats() {
+        let matches = search_messages(&log(), "Deps BUMP", 10);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].sha, "c3");
+        assert_eq!(matches[0].uses, 2);
+        assert_eq!(matches[1].subject, "chore(deps): bump clap");
+
+        // Bodies are searched too; non-conventional messages are skipped.
+        assert_eq!(search_messages(&log(), "dependabot", 10)[0].sha, "c1");
+        assert!(search_messages(&log(), "by hand", 10).is_empty());
+        assert_eq!(search_messages(&log(), "bump", 1).len(), 1);
+    }
+
+    #[test]
+    fn template_drops_trailers_from_the_old_commit() {
+        let template =
+            template_from("chore(deps): bump serde\n\nRefs: PROJ-1\nTbdflow-Version: 0.34.0")
+                .unwrap();
+        assert_eq!(template.scope.as_deref(), Some("deps"));
+        assert_eq!(template.issue.as_deref(), Some("PROJ-1"));
+        assert!(template.trailers.is_empty());
+    }
+}
@@ -0,0 +1,239 @@
+//! Local state of one clone, kept in `.git/tbdflow/` so it never shows up in
+//! `git status` and is never pushed.
+//!
+//! Each typed state file is JSON wrapped in `{"version": N, "state": ...}`.
+//! Files written by a newer tbdflow are refused rather than overwritten; files
+//! that cannot be parsed are moved aside to `<file>.corrupt` and treated as
+//! missing. Files from releases that kept state directly in `.git/` are moved
+//! into the directory the first time they are read.
+//!
+//! `.git/tbdflow-behind` stays where it is: shell prompts read it directly.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory inside `.git/` that holds every state file.
+pub const STATE_DIR: &str = "tbdflow";
+
+/// A typed state file in `.git/tbdflow/`.
+pub trait State: Serialize + DeserializeOwned {
+    /// File name inside the state directory.
+    const FILE: &'static str;
+    /// File name directly under `.git/` used before the state directory
+    /// existed, for state that predates it.
+    const LEGACY_FILE: Option<&'static str> = None;
+    /// Bumped whenever the format changes incompatibly.
+    const VERSION: u32 = 1;
+}
+
+#[derive(Serialize)]
+struct EnvelopeRef<'a, T> {
+    version: u32,
+    state: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    version: u32,
+    state: Value,
+}
+
+/// `.git/tbdflow/`, created if missing.
+pub fn state_dir(git_dir: &Path) -> Result<PathBuf> {
+    let dir = git_dir.join(STATE_DIR);
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    Ok(dir)
+}
+
+/// Path of a raw (unversioned) state file such as the journal, moving the
+/// file out of `.git/` first if an older release left it there.
+pub fn file_path(git_dir: &Path, file: &str, legacy_file: &str) -> Result<PathBuf> {
+    let path = state_dir(git_dir)?.join(file);
+    let legacy = git_dir.join(legacy_file);
+    if !path.exists() && legacy.exists() {
+        fs::rename(&legacy, &path).with_context(|| {
+            format!("Failed to move {} to {}", legacy.display(), path.display())
+        })?;
+    }
+    Ok(path)
+}
+
+/// Moves an unreadable file to `<file>.corrupt` so the next save starts clean.
+fn quarantine(path: &Path, reason: &str) -> Result<()> {
+    let mut aside = path.as_os_str().to_owned();
+    aside.push(".corrupt");
+    let aside = PathBuf::from(aside);
+    fs::rename(path, &aside).with_context(|| format!("Failed to move aside {}", path.display()))?;
+    eprintln!(
+        "{}",
+        format!(
+            "Warning: {} could not be read ({}); moved it to {} and starting afresh.",
+            path.display(),
+            reason,
+            aside.display()
+        )
+        .yellow()
+    );
+    Ok(())
+}
+
+/// Brings a file from the old `.git/` location into the state directory.
+fn migrate_legacy<T: State>(git_dir: &Path, path: &Path) -> Result<()> {
+    let Some(legacy) = T::LEGACY_FILE.map(|file| git_dir.join(file)) else {
+        return Ok(());
+    };
+    if path.exists() || !legacy.exists() {
+        return Ok(());
+    }
+    let content = fs::read_to_string(&legacy)
+        .with_context(|| format!("Failed to read {}", legacy.display()))?;
+    match serde_json::from_str::<T>(&content) {
+        Ok(state) => {
+            write_state(path, &state)?;
+            fs::remove_file(&legacy)
+                .with_context(|| format!("Failed to remove {}", legacy.display()))
+        }
+        Err(e) => quarantine(&legacy, &e.to_string()),
+    }
+}
+
+/// Parses the contents of a state file: `Ok(None)` when a newer tbdflow wrote
+/// it, `Err` with the reason when it is corrupt.
+fn parse_state<T: State>(content: &str) -> Result<Option<T>, String> {
+    let envelope: Envelope = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    if envelope.version > T::VERSION {
+        return Ok(None);
+    }
+    serde_json::from_value(envelope.state)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+/// Loads a state file, or `None` when there is none (or it was corrupt).
+pub fn load<T: State>(git_dir: &Path) -> Result<Option<T>> {
+    let path = state_dir(git_dir)?.join(T::FILE);
+    migrate_legacy::<T>(git_dir, &path)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    match parse_state::<T>(&content) {
+        Ok(Some(state)) => Ok(Some(state)),
+        Ok(None) => anyhow::bail!(
+            "{} was written by a newer version of tbdflow. Upgrade tbdflow, or remove the file to start afresh.",
+            path.display()
+        ),
+        Err(reason) => {
+            quarantine(&path, &reason)?;
+            Ok(None)
+        }
+    }
+}
+
+fn write_state<T: State>(path: &Path, state: &T) -> Result<()> {
+    let json = serde_json::to_string_pretty(&EnvelopeRef {
+        version: T::VERSION,
+        state,
+    })?;
+    // Write next to the file and rename, so a crash never leaves half a file.
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    fs::write(&tmp, json).with_context(|| format!("Failed to write {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Replaces a state file.
+pub fn save<T: State>(git_dir: &Path, state: &T) -> Result<()> {
+    let path = state_dir(git_dir)?.join(T::FILE);
+    write_state(&path, state)
+}
+
+/// Removes a state file, wherever it is kept. Returns whether there was one.
+pub fn remove<T: State>(git_dir: &Path) -> Result<bool> {
+    let mut removed = false;
+    let legacy = T::LEGACY_FILE.map(|file| git_dir.join(file));
+    for path in std::iter::once(git_dir.join(STATE_DIR).join(T::FILE)).chain(legacy) {
+        if path.exists() {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            removed = true;
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Counter {
+        count: u32,
+    }
+
+    impl State for Counter {
+        const FILE: &'static str = "counter.json";
+        const LEGACY_FILE: Option<&'static str> = Some("tbdflow-counter.json");
+    }
+
+    #[test]
+    fn state_round_trips_in_a_versioned_envelope() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(load::<Counter>(dir.path()).unwrap(), None);
+
+        save(dir.path(), &Counter { count: 3 }).unwrap();
+        let raw = fs::read_to_string(dir.path().join("tbdflow/counter.json")).unwrap();
+        assert!(raw.contains("\"version\": 1"));
+        assert_eq!(
+            load::<Counter>(dir.path()).unwrap(),
+            Some(Counter { count: 3 })
+        );
+
+        assert!(remove::<Counter>(dir.path()).unwrap());
+        assert!(!remove::<Counter>(dir.path()).unwrap());
+    }
+
+    #[test]
+    fn legacy_files_are_migrated() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("tbdflow-counter.json"), r#"{"count":7}"#).unwrap();
+
+        assert_eq!(
+            load::<Counter>(dir.path()).unwrap(),
+            Some(Counter { count: 7 })
+        );
+        assert!(!dir.path().join("tbdflow-counter.json").exists());
+        assert!(dir.path().join("tbdflow/counter.json").exists());
+
+        fs::write(dir.path().join("tbdflow-journal.jsonl"), "{}\n").unwrap();
+        let journal = file_path(dir.path(), "journal.jsonl", "tbdflow-journal.jsonl").unwrap();
+        assert_eq!(fs::read_to_string(journal).unwrap(), "{}\n");
+    }
+
+    #[test]
+    fn corrupt_files_are_moved_aside_and_newer_versions_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_dir = state_dir(dir.path()).unwrap();
+
+        fs::write(state_dir.join("counter.json"), "{ not json").unwrap();
+        assert_eq!(load::<Counter>(dir.path()).unwrap(), None);
+        assert!(state_dir.join("counter.json.corrupt").exists());
+        assert!(!state_dir.join("counter.json").exists());
+
+        fs::write(
+            state_dir.join("counter.json"),
+            r#"{"version": 2, "state": {"count": 1}}"#,
+        )
+        .unwrap();
+        let err = load::<Counter>(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("newer version of tbdflow"));
+        assert!(state_dir.join("counter.json").exists());
+    }
+}
@@ -0,0 +1,132 @@
+use crate::config::{Config, UncommittedPolicy};
+use crate::git::{GitError, RunOpts};
+use crate::{git, prompt};
+use anyhow::Result;
+use colored::Colorize;
+
+/// Stash message used to park changes for `branch`, so they can be found
+/// again when the user switches back.
+fn parked_stash_message(branch: &str) -> String {
+    format!("tbdflow-switch: {}", branch)
+}
+
+/// Switches to `target` (main by default), dealing with uncommitted changes
+/// according to `switch.uncommitted` and warning when the target is stale.
+pub fn handle_switch(
+    config: &Config,
+    target: Option<String>,
+    stash: bool,
+    opts: RunOpts,
+) -> Result<()> {
+    let main_branch = config.main_branch_name.as_str();
+    let target = target.unwrap_or_else(|| main_branch.to_string());
+    println!("{}", format!("--- Switching to '{}' ---", target).blue());
+
+    let current = git::get_current_branch(opts)?;
+    if current == target {
+        println!("{}", format!("Already on '{}'.", target).green());
+        return Ok(());
+    }
+
+    if git::fetch_origin(opts).is_err() {
+        println!(
+            "{}",
+            "Warning: could not reach origin; comparing against the last fetch.".yellow()
+        );
+    }
+    if git::branch_exists_locally(&target, opts).is_err() {
+        git::remote_branch_exists(&target, opts)
+            .map_err(|_| GitError::BranchNotFound(target.clone()))?;
+        git::create_tracking_branch(&target, opts)?;
+    }
+
+    if git::is_working_directory_dirty(opts)? {
+        let policy = if stash {
+            UncommittedPolicy::Stash
+        } else {
+            config.switch.uncommitted
+        };
+        match policy {
+            UncommittedPolicy::Refuse => {
+                return Err(GitError::DirectoryNotClean(format!(
+                    "You have uncommitted changes on '{}'. Commit them, or pass --stash (or set switch.uncommitted: stash) to park them.",
+                    current
+                ))
+                .into());
+            }
+            UncommittedPolicy::Stash => {
+                git::stash_push(&parked_stash_message(&current), opts)?;
+                println!(
+                    "{}",
+                    format!(
+                        "Parked uncommitted changes from '{}'; they come back when you switch to it again.",
+                        current
+                    )
+                    .dimmed()
+                );
+            }
+        }
+    }
+
+    git::checkout_branch(&target, opts)?;
+    if let Some(reference) = git::find_stash(&parked_stash_message(&target), opts)? {
+        git::stash_pop(&reference, opts)?;
+        println!(
+            "{}",
+            format!("Restored the changes parked on '{}'.", target).dimmed()
+        );
+    }
+    println!("{}", format!("Switched to '{}'.", target).green());
+
+    if target != main_branch {
+        warn_if_stale(config, &target, opts)?;
+    }
+    Ok(())
+}
+
+/// Warns when `branch` lags behind trunk or has gone quiet, and offers to
+/// rebase it onto `origin/main`.
+fn warn_if_stale(config: &Config, branch: &str, opts: RunOpts) -> Result<()> {
+    let main_branch = config.main_branch_name.as_str();
+    if let Some((_, days)) =
+        git::get_stale_branches(opts, main_branch, config.stale_branch_threshold_days)?
+            .into_iter()
+            .find(|(name, _)| name == branch)
+    {
+        println!(
+            "{}",
+            format!(
+                "Warning: '{}' has had no commits for {} days. Short-lived branches should be merged or deleted.",
+                branch, days
+            )
+            .yellow()
+        );
+    }
+
+    let behind: u64 = git::get_branch_count_behind_main(branch, main_branch, opts)?
+        .parse()
+        .unwrap_or(0);
+    if behind == 0 {
+        return Ok(());
+    }
+    println!(
+        "{}",
+        format!(
+            "Warning: '{}' is {} commit(s) behind 'origin/{}'.",
+            branch, behind, main_branch
+        )
+        .yellow()
+    );
+    if prompt::confirm(
+        &format!("Rebase '{}' onto 'origin/{}' now?", branch, main_branch),
+        Some(false),
+        opts,
+    )? {
+        git::rebase_onto_main(main_branch, opts)?;
+        println!(
+            "{}",
+            format!("'{}' is up to date with 'origin/{}'.", branch, main_branch).green()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use crate::git::{self, RunOpts};
+use crate::state::{self, State};
+use anyhow::Result;
+use chrono::Utc;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A branch temporarily treated as trunk, e.g. a release branch during stabilisation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrunkTarget {
+    pub branch: String,
+    /// Timestamp when the target was set.
+    pub set_at: String,
+}
+
+impl State for TrunkTarget {
+    const FILE: &'static str = "target.json";
+    const LEGACY_FILE: Option<&'static str> = Some("tbdflow-target.json");
+}
+
+/// Loads the trunk target, or returns `None` when the configured main branch is trunk.
+pub fn load_target(git_dir: &Path) -> Result<Option<TrunkTarget>> {
+    state::load(git_dir)
+}
+
+/// The trunk target of the current repository, if one is set. Outside a
+/// repository, or when the state file cannot be read, there is none.
+pub fn active_target(opts: RunOpts) -> Option<TrunkTarget> {
+    let quiet = opts.for_reads();
+    let git_dir = PathBuf::from(git::get_git_dir(quiet).ok()?);
+    load_target(&git_dir).ok().flatten()
+}
+
+/// Makes `branch` the trunk for `commit`, `sync` and `branch`
+/// until `tbdflow target reset`.
+pub fn handle_target_set(
+    git_dir: &Path,
+    branch: &str,
+    configured_main: &str,
+    opts: RunOpts,
+) -> Result<()> {
+    if branch == configured_main {
+        return handle_target_reset(git_dir, configured_main, opts);
+    }
+    let quiet = opts.for_reads();
+    if git::branch_exists_locally(branch, quiet).is_err()
+        && git::remote_branch_exists(branch, quiet).is_err()
+    {
+        anyhow::bail!("Branch '{}' does not exist locally or on origin.", branch);
+    }
+    if opts.dry_run {
+        println!(
+            "{}",
+            format!("[DRY RUN] Would make '{}' the trunk target", branch).yellow()
+        );
+        return Ok(());
+    }
+
+    let target = TrunkTarget {
+        branch: branch.to_string(),
+        set_at: Utc::now().to_rfc3339(),
+    };
+    state::save(git_dir, &target)?;
+
+    println!(
+        "{}",
+        format!("Trunk target set to '{}'.", target.branch).green()
+    );
+    println!(
+        "{}",
+        format!(
+            "commit, sync and branch now treat '{}' as main. Run 'tbdflow target reset' to go back to '{}'.",
+            target.branch, configured_main
+        )
+        .dimmed()
+    );
+    Ok(())
+}
+
+/// Goes back to the configured main branch.
+pub fn handle_target_reset(git_dir: &Path, configured_main: &str, opts: RunOpts) -> Result<()> {
+    if load_target(git_dir)?.is_none() {
+        println!(
+            "{}",
+            format!("No trunk target set; '{}' is trunk.", configured_main).yellow()
+        );
+        return Ok(());
+    }
+    if opts.dry_run {
+        println!(
+            "{}",
+            format!(
+                "[DRY RUN] Would reset the trunk target to '{}'",
+                configured_main
+            )
+            .yellow()
+        );
+        return Ok(());
+    }
+    state::remove::<TrunkTarget>(git_dir)?;
+    println!(
+        "{}",
+        format!("Trunk target reset; '{}' is trunk again.", configured_main).green()
+    );
+    Ok(())
+}
+
+/// Prints which branch is trunk.
+pub fn handle_target_show(git_dir: &Path, configured_main: &str) -> Result<()> {
+    match load_target(git_dir)? {
+        Some(target) => {
+            println!("{}", format!("Trunk target: '{}'", target.branch).bold());
+            println!(
+                "{}",
+                format!(
+                    "Set at {}; the configured main branch is '{}'.",
+                    target.set_at, configured_main
+                )
+                .dimmed()
+            );
+        }
+        None => println!(
+            "{}",
+            format!("No trunk target set; '{}' is trunk.", configured_main).green()
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn target_round_trips_and_resets() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(load_target(dir.path()).unwrap(), None);
+
+        let target = TrunkTarget {
+            branch: "release_2.0".to_string(),
+            set_at: "2026-10-17T09:00:00+00:00".to_string(),
+        };
+        state::save(dir.path(), &target).unwrap();
+        assert_eq!(load_target(dir.path()).unwrap(), Some(target));
+
+        handle_target_reset(dir.path(), "main", RunOpts::new(false, false)).unwrap();
+        assert_eq!(load_target(dir.path()).unwrap(), None);
+    }
+}
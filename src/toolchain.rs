@@ -0,0 +1,219 @@
+//! Detects commit tooling that already lives in the repository (husky hooks,
+//! commitlint) so tbdflow can avoid linting every message twice.
+
+use crate::hooks::{self, InstalledHook};
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+const HUSKY_DIR: &str = ".husky";
+const COMMIT_MSG_HOOK: &str = "commit-msg";
+/// The commit-msg hook line that hands message linting to tbdflow.
+pub const TBDFLOW_COMMIT_MSG_LINE: &str = "tbdflow verify-commit-msg \"$1\"";
+
+/// True when `hook` already hands messages to tbdflow, including hooks
+/// written before `verify-commit-msg` existed.
+pub fn hook_runs_tbdflow(hook: &str) -> bool {
+    hook.contains("tbdflow verify-commit-msg") || hook.contains("tbdflow parse")
+}
+const COMMITLINT_CONFIGS: &[&str] = &[
+    ".commitlintrc",
+    ".commitlintrc.json",
+    ".commitlintrc.yaml",
+    ".commitlintrc.yml",
+    ".commitlintrc.js",
+    ".commitlintrc.cjs",
+    ".commitlintrc.mjs",
+    ".commitlintrc.ts",
+    "commitlint.config.js",
+    "commitlint.config.cjs",
+    "commitlint.config.mjs",
+    "commitlint.config.ts",
+];
+
+/// Commit hooks and linters found at the repository root.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CommitToolchain {
+    /// Hook names under `.husky/`, or from a `husky` key in `package.json`.
+    pub husky_hooks: Vec<String>,
+    /// Where commitlint is configured: a config file or `package.json`.
+    pub commitlint_config: Option<String>,
+    /// Contents of `.husky/commit-msg`, if present.
+    pub commit_msg_hook: Option<String>,
+}
+
+impl CommitToolchain {
+    pub fn detect(root: &Path) -> Self {
+        let mut toolchain = CommitToolchain::default();
+        let package_json = fs::read_to_string(root.join("package.json"))
+            .ok()
+            .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok());
+
+        let husky_dir = root.join(HUSKY_DIR);
+        if let Ok(entries) = fs::read_dir(&husky_dir) {
+            let mut hooks: Vec<String> = entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_file())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|name| !name.starts_with('.') && !name.starts_with('_'))
+                .collect();
+            hooks.sort();
+            toolchain.husky_hooks = hooks;
+            toolchain.commit_msg_hook = fs::read_to_string(husky_dir.join(COMMIT_MSG_HOOK)).ok();
+        } else if let Some(hooks) = package_json
+            .as_ref()
+            .and_then(|p| p.pointer("/husky/hooks"))
+            .and_then(|h| h.as_object())
+        {
+            // husky v4 keeps hooks in package.json.
+            toolchain.husky_hooks = hooks.keys().cloned().collect();
+            toolchain.commit_msg_hook = hooks
+                .get(COMMIT_MSG_HOOK)
+                .and_then(|v| v.as_str())
+                .map(str::to_string);
+        }
+
+        toolchain.commitlint_config = COMMITLINT_CONFIGS
+            .iter()
+            .find(|name| root.join(name).is_file())
+            .map(|name| name.to_string())
+            .or_else(|| {
+                package_json
+                    .as_ref()
+                    .and_then(|p| p.get("commitlint"))
+                    .map(|_| "package.json".to_string())
+            });
+        toolchain
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.husky_hooks.is_empty() && self.commitlint_config.is_none()
+    }
+
+    /// True when the commit-msg hook already runs tbdflow.
+    pub fn commit_msg_runs_tbdflow(&self) -> bool {
+        self.commit_msg_hook
+            .as_deref()
+            .is_some_and(hook_runs_tbdflow)
+    }
+
+    /// True when something other than tbdflow checks commit messages.
+    pub fn lints_messages(&self) -> bool {
+        let hook_runs_commitlint = self
+            .commit_msg_hook
+            .as_deref()
+            .is_some_and(|hook| hook.contains("commitlint"));
+        !self.commit_msg_runs_tbdflow()
+            && (self.commitlint_config.is_some() || hook_runs_commitlint)
+    }
+
+    /// One-line summary, e.g. "commitlint (commitlint.config.js), husky hooks: commit-msg, pre-commit".
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(config) = &self.commitlint_config {
+            parts.push(format!("commitlint ({})", config));
+        }
+        if !self.husky_hooks.is_empty() {
+            parts.push(format!("husky hooks: {}", self.husky_hooks.join(", ")));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Replaces commitlint in `hook` with `tbdflow verify-commit-msg`, keeping everything else
+/// (such as husky's loader line). Appends the call when commitlint is absent.
+pub fn rewrite_commit_msg_hook(hook: &str) -> String {
+    let mut replaced = false;
+    let mut lines: Vec<String> = hook
+        .lines()
+        .filter_map(|line| {
+            if !line.contains("commitlint") {
+                return Some(line.to_string());
+            }
+            if replaced {
+                return None;
+            }
+            replaced = true;
+            Some(TBDFLOW_COMMIT_MSG_LINE.to_string())
+        })
+        .collect();
+    if !replaced && !hook_runs_tbdflow(hook) {
+        lines.push(TBDFLOW_COMMIT_MSG_LINE.to_string());
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Points `.husky/commit-msg` at `tbdflow verify-commit-msg` so tbdflow's lint rules are
+/// the only ones applied to commit messages. Returns what the hook was changed
+/// from, for `tbdflow hooks uninstall`.
+pub fn defer_commit_msg_to_tbdflow(root: &Path) -> Result<InstalledHook> {
+    let husky_dir = root.join(HUSKY_DIR);
+    fs::create_dir_all(&husky_dir).context("Failed to create .husky directory")?;
+    let hook_path = husky_dir.join(COMMIT_MSG_HOOK);
+    let current = fs::read_to_string(&hook_path).ok();
+    let rewritten = match &current {
+        Some(current) => rewrite_commit_msg_hook(current),
+        None => hooks::render_hook(None),
+    };
+    fs::write(&hook_path, rewritten)
+        .with_context(|| format!("Failed to write {}", hook_path.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&hook_path, fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(InstalledHook {
+        path: hook_path.to_string_lossy().to_string(),
+        backup: None,
+        original: current,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_husky_and_commitlint() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".husky/_")).unwrap();
+        fs::write(
+            dir.path().join(".husky/commit-msg"),
+            "npx --no -- commitlint --edit $1\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join(".husky/pre-commit"), "npm test\n").unwrap();
+        fs::write(dir.path().join("commitlint.config.js"), "").unwrap();
+
+        let toolchain = CommitToolchain::detect(dir.path());
+        assert_eq!(toolchain.husky_hooks, vec!["commit-msg", "pre-commit"]);
+        assert_eq!(
+            toolchain.commitlint_config.as_deref(),
+            Some("commitlint.config.js")
+        );
+        assert!(toolchain.lints_messages());
+    }
+
+    #[test]
+    fn commitlint_in_package_json_is_detected() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"commitlint": {"extends": ["@commitlint/config-conventional"]}}"#,
+        )
+        .unwrap();
+        let toolchain = CommitToolchain::detect(dir.path());
+        assert_eq!(toolchain.commitlint_config.as_deref(), Some("package.json"));
+    }
+
+    #[test]
+    fn rewrite_swaps_commitlint_for_tbdflow() {
+        let hook = "#!/usr/bin/env sh\n. \"$(dirname -- \"$0\")/_/husky.sh\"\n\nnpx --no -- commitlint --edit $1\n";
+        let rewritten = rewrite_commit_msg_hook(hook);
+        assert!(rewritten.contains("husky.sh"));
+        assert!(rewritten.ends_with("tbdflow verify-commit-msg \"$1\"\n"));
+        assert!(!rewritten.contains("commitlint"));
+        assert_eq!(rewrite_commit_msg_hook(&rewritten), rewritten);
+    }
+}
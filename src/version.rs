@@ -0,0 +1,387 @@
+//! Semantic versions read from release tags, and the next version implied by
+//! the Conventional Commits since the last release.
+
+use crate::commands::{NextVersionResponse, TbdResponse};
+use crate::config::{Config, TagSort};
+use crate::git::{self, RunOpts};
+use crate::history::{self, ParsedCommit};
+use anyhow::Result;
+use colored::Colorize;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A semver version: `1.2.3`, `2.0.0-rc.1` or `1.0.0+build.5`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Pre-release identifiers, e.g. `["rc", "1"]`. Empty for a release.
+    pub pre: Vec<String>,
+    /// Build metadata after `+`. Ignored when comparing versions.
+    pub build: Option<String>,
+}
+
+impl Version {
+    /// Parses `1.2.3[-pre][+build]`. A `v` or other prefix must be stripped first.
+    pub fn parse(text: &str) -> Option<Version> {
+        let (rest, build) = match text.split_once('+') {
+            Some((rest, build)) if !build.is_empty() => (rest, Some(build.to_string())),
+            Some(_) => return None,
+            None => (text, None),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => {
+                let pre: Vec<String> = pre.split('.').map(str::to_string).collect();
+                if pre.iter().any(String::is_empty) {
+                    return None;
+                }
+                (core, pre)
+            }
+            None => (rest, Vec::new()),
+        };
+        let mut numbers = core.split('.').map(|n| n.parse::<u64>().ok());
+        let version = Version {
+            major: numbers.next()??,
+            minor: numbers.next()??,
+            patch: numbers.next()??,
+            pre,
+            build,
+        };
+        numbers.next().is_none().then_some(version)
+    }
+
+    /// Parses a tag such as `v2.0.0-rc.1` with the configured release prefix.
+    pub fn from_tag(tag: &str, prefix: &str) -> Option<Version> {
+        Version::parse(tag.strip_prefix(prefix)?)
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    /// The release this version leads up to: `2.0.0` for `2.0.0-rc.1+b5`.
+    pub fn release(&self) -> Version {
+        Version {
+            pre: Vec::new(),
+            build: None,
+            ..self.clone()
+        }
+    }
+
+    /// The next release after this one for `bump`.
+    pub fn bumped(&self, bump: Bump) -> Version {
+        let (major, minor, patch) = match bump {
+            Bump::Major => (self.major + 1, 0, 0),
+            Bump::Minor => (self.major, self.minor + 1, 0),
+            Bump::Patch => (self.major, self.minor, self.patch + 1),
+        };
+        Version {
+            major,
+            minor,
+            patch,
+            pre: Vec::new(),
+            build: None,
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if self.is_prerelease() {
+            write!(f, "-{}", self.pre.join("."))?;
+        }
+        if let Some(build) = &self.build {
+            write!(f, "+{}", build)?;
+        }
+        Ok(())
+    }
+}
+
+/// Semver precedence: a pre-release sorts before its release, and build
+/// metadata is ignored.
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.is_prerelease(), other.is_prerelease()) {
+                (false, false) => Ordering::Equal,
+                (false, true) => Ordering::Greater,
+                (true, false) => Ordering::Less,
+                (true, true) => compare_pre(&self.pre, &other.pre),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Numeric identifiers compare as numbers and sort before alphanumeric ones;
+/// a shorter list sorts first when all shared identifiers are equal.
+fn compare_pre(a: &[String], b: &[String]) -> Ordering {
+    for (x, y) in a.iter().zip(b) {
+        let order = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => x.cmp(y),
+        };
+        if order != Ordering::Equal {
+            return order;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Bump::Patch => "patch",
+            Bump::Minor => "minor",
+            Bump::Major => "major",
+        })
+    }
+}
+
+/// The bump the commits call for: major for a breaking change, minor for a
+/// `feat`, patch for a `fix` or `perf`. `None` when nothing needs releasing.
+pub fn bump_for(commits: &[ParsedCommit]) -> Option<Bump> {
+    commits
+        .iter()
+        .filter_map(|c| match c.r#type.as_deref()? {
+            _ if c.breaking => Some(Bump::Major),
+            "feat" => Some(Bump::Minor),
+            "fix" | "perf" => Some(Bump::Patch),
+            _ => None,
+        })
+        .max()
+}
+
+/// The newest version among `tags` with the release prefix, optionally
+/// skipping pre-releases. Tags that are not semver are ignored.
+pub fn latest<'a>(
+    tags: &'a [String],
+    prefix: &str,
+    include_prereleases: bool,
+) -> Option<(&'a str, Version)> {
+    tags.iter()
+        .filter_map(|tag| Version::from_tag(tag, prefix).map(|v| (tag.as_str(), v)))
+        .filter(|(_, v)| include_prereleases || !v.is_prerelease())
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+}
+
+/// The version after `release` for `bump`. With `pre` (e.g. `rc`), a
+/// pre-release of it instead: `-rc.1`, or one past the highest `-rc.N`
+/// already in `tags`.
+pub fn next_version(
+    release: &Version,
+    bump: Bump,
+    pre: Option<&str>,
+    tags: &[String],
+    prefix: &str,
+) -> Version {
+    let target = release.bumped(bump);
+    let Some(id) = pre else {
+        return target;
+    };
+    let number = tags
+        .iter()
+        .filter_map(|tag| Version::from_tag(tag, prefix))
+        .filter(|v| v.release() == target && v.pre.first().map(String::as_str) == Some(id))
+        .filter_map(|v| v.pre.get(1)?.parse::<u64>().ok())
+        .max()
+        .map_or(1, |n| n + 1);
+    Version {
+        pre: vec![id.to_string(), number.to_string()],
+        ..target
+    }
+}
+
+/// The latest tag reachable from HEAD, as `automatic_tags.tag_sort` says:
+/// the highest semver tag with the release prefix, or the nearest tag.
+/// Without `include_prereleases` only release versions count, whatever the
+/// sort. When no tag is a version, falls back to the nearest tag.
+pub fn latest_tag(config: &Config, include_prereleases: bool, opts: RunOpts) -> Result<String> {
+    if config.automatic_tags.tag_sort == TagSort::Nearest && include_prereleases {
+        return git::get_latest_tag(opts);
+    }
+    let tags = git::list_merged_tags(opts)?;
+    match latest(
+        &tags,
+        &config.automatic_tags.release_prefix,
+        include_prereleases,
+    ) {
+        Some((tag, _)) => Ok(tag.to_string()),
+        None if include_prereleases => git::get_latest_tag(opts),
+        None => Err(anyhow::anyhow!(
+            "No release tag found (pre-releases skipped)."
+        )),
+    }
+}
+
+/// Prints the version the next release (or pre-release) should get.
+pub fn handle_next_version(
+    config: &Config,
+    pre: Option<&str>,
+    json: bool,
+    opts: RunOpts,
+) -> Result<()> {
+    let prefix = config.automatic_tags.release_prefix.as_str();
+    let tags = git::list_merged_tags(opts)?;
+    let last = latest(&tags, prefix, false);
+    let current = last.as_ref().map(|(tag, _)| tag.to_string());
+    let range = current
+        .as_ref()
+        .map_or("HEAD".to_string(), |tag| format!("{}..HEAD", tag));
+    let commits = history::commits_in_range(&range, opts)?;
+    let since = current.as_deref().unwrap_or("the first commit");
+    let Some(bump) = bump_for(&commits) else {
+        return Err(anyhow::anyhow!(
+            "Nothing to release since {}: no feat, fix, perf or breaking commits.",
+            since
+        ));
+    };
+    let release = last.map(|(_, v)| v).unwrap_or(Version {
+        major: 0,
+        minor: 0,
+        patch: 0,
+        pre: Vec::new(),
+        build: None,
+    });
+    let next = format!(
+        "{}{}",
+        prefix,
+        next_version(&release, bump, pre, &tags, prefix)
+    );
+
+    if json {
+        let response = NextVersionResponse {
+            current: current.clone(),
+            next,
+            bump: bump.to_string(),
+            commits: commits.len(),
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&TbdResponse::ok(response))?
+        );
+        return Ok(());
+    }
+    println!("{}", next);
+    eprintln!(
+        "{}",
+        format!("{} bump from {} ({} commits).", bump, since, commits.len()).dimmed()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(text: &str) -> Version {
+        Version::parse(text).unwrap()
+    }
+
+    fn tags(names: &[&str]) -> Vec<String> {
+        names.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn versions_parse_and_round_trip() {
+        for text in [
+            "1.2.3",
+            "2.0.0-rc.1",
+            "1.0.0-alpha.beta+exp.sha.5114f85",
+            "0.1.0+20261017",
+        ] {
+            assert_eq!(v(text).to_string(), text);
+        }
+        for bad in [
+            "1.2",
+            "1.2.3.4",
+            "v1.2.3",
+            "1.2.3-",
+            "1.2.3+",
+            "1.2.x",
+            "1.2.3-rc..1",
+        ] {
+            assert_eq!(Version::parse(bad), None, "{}", bad);
+        }
+        assert_eq!(
+            Version::from_tag("release-1.0.0", "release-"),
+            Some(v("1.0.0"))
+        );
+    }
+
+    #[test]
+    fn precedence_follows_semver() {
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+        assert_eq!(v("1.0.0+a").cmp(&v("1.0.0+b")), Ordering::Equal);
+    }
+
+    #[test]
+    fn latest_can_skip_prereleases() {
+        let all = tags(&["v1.9.0", "v2.0.0-rc.1", "v1.10.0", "nightly", "v2.0.0-rc.2"]);
+        assert_eq!(latest(&all, "v", true).unwrap().0, "v2.0.0-rc.2");
+        assert_eq!(latest(&all, "v", false).unwrap().0, "v1.10.0");
+    }
+
+    #[test]
+    fn next_version_counts_up_prereleases_of_the_target() {
+        let all = tags(&["v1.4.0", "v2.0.0-rc.1", "v2.0.0-rc.2", "v2.0.0-beta.1"]);
+        let release = v("1.4.0");
+        assert_eq!(
+            next_version(&release, Bump::Major, Some("rc"), &all, "v").to_string(),
+            "2.0.0-rc.3"
+        );
+        assert_eq!(
+            next_version(&release, Bump::Major, Some("alpha"), &all, "v").to_string(),
+            "2.0.0-alpha.1"
+        );
+        assert_eq!(
+            next_version(&release, Bump::Minor, Some("rc"), &all, "v").to_string(),
+            "1.5.0-rc.1"
+        );
+        assert_eq!(
+            next_version(&release, Bump::Major, None, &all, "v").to_string(),
+            "2.0.0"
+        );
+    }
+
+    #[test]
+    fn bump_is_the_largest_the_commits_call_for() {
+        let log = "\x1ea1\x1fAda\x1ffix: handle empty list\n\
+                   \x1ea2\x1fAda\x1ffeat: add paging\n\
+                   \x1ea3\x1fAda\x1fchore: tidy\n";
+        assert_eq!(bump_for(&history::parse_log(log)), Some(Bump::Minor));
+        let breaking = "\x1ea1\x1fAda\x1ffix!: drop v1 endpoint\n";
+        assert_eq!(bump_for(&history::parse_log(breaking)), Some(Bump::Major));
+        let chores = "\x1ea1\x1fAda\x1fchore: tidy\n\x1ea2\x1fAda\x1fTidy up\n";
+        assert_eq!(bump_for(&history::parse_log(chores)), None);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::commands::{SyncWatchResponse, TbdResponse};
+use crate::config::Config;
+use crate::git;
+use crate::git::RunOpts;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Written inside `.git/` while the branch is behind trunk, for shell prompts to read.
+const PROMPT_STATE_FILE: &str = "tbdflow-behind";
+
+/// Message shown when trunk has moved ahead of the local branch.
+pub fn describe_behind(behind: u64, main_branch: &str) -> String {
+    format!(
+        "'{}' has moved {} commit(s) ahead of you. Run 'tbdflow sync' to integrate.",
+        main_branch, behind
+    )
+}
+
+/// Keeps `.git/tbdflow-behind` in step with the behind count; removed when up to date.
+fn write_prompt_state(git_dir: &Path, behind: u64) -> Result<()> {
+    let path = git_dir.join(PROMPT_STATE_FILE);
+    if behind == 0 {
+        if path.exists() {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        return Ok(());
+    }
+    fs::write(&path, behind.to_string())
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Best-effort desktop notification; silently does nothing if no notifier is installed.
+fn send_desktop_notification(message: &str) {
+    let mut command = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification \"{}\" with title \"tbdflow\"",
+            message.replace('"', "'")
+        );
+        let mut c = Command::new("osascript");
+        c.args(["-e", &script]);
+        c
+    } else if cfg!(windows) {
+        let mut c = Command::new("msg");
+        c.args(["*", message]);
+        c
+    } else {
+        let mut c = Command::new("notify-send");
+        c.args(["tbdflow", message]);
+        c
+    };
+    let _ = command.stdout(Stdio::null()).stderr(Stdio::null()).status();
+}
+
+/// Periodically fetches origin and nudges the developer when trunk moves
+/// ahead of the local branch. Runs until interrupted, or once under
+/// `--dry-run`. With `json`, prints one JSON line per fetch instead.
+pub fn handle_sync_watch(
+    opts: RunOpts,
+    config: &Config,
+    interval_secs: u64,
+    notify: bool,
+    json: bool,
+) -> Result<()> {
+    let main_branch = &config.main_branch_name;
+    if !json {
+        println!(
+            "{}",
+            format!(
+                "--- Watching '{}' on origin every {}s (Ctrl+C to stop) ---",
+                main_branch, interval_secs
+            )
+            .blue()
+        );
+    }
+    let git_dir = PathBuf::from(git::get_git_dir(opts)?);
+    let mut last_behind = 0;
+
+    loop {
+        let behind = match git::fetch_origin(opts) {
+            Ok(_) => Ok(git::get_commit_count_behind_main(main_branch, opts)?
+                .parse::<u64>()
+                .unwrap_or(0)),
+            Err(e) => Err(e),
+        };
+        if let Ok(behind) = behind
+            && !opts.dry_run
+        {
+            write_prompt_state(&git_dir, behind)?;
+        }
+
+        if json {
+            let event = SyncWatchResponse {
+                main_branch: main_branch.clone(),
+                behind: behind.as_ref().ok().copied(),
+                fetch_error: behind.as_ref().err().map(|e| e.to_string()),
+            };
+            println!("{}", serde_json::to_string(&TbdResponse::ok(event))?);
+        }
+        match behind {
+            Err(e) if !json => {
+                println!(
+                    "{}",
+                    format!("Fetch failed, retrying later: {}", e).yellow()
+                );
+            }
+            Err(_) => {}
+            Ok(behind) => {
+                if behind > last_behind {
+                    let message = describe_behind(behind, main_branch);
+                    if !json {
+                        // The terminal bell gets attention even when the window is in the background.
+                        println!("\x07{}", message.bold().yellow());
+                    }
+                    if notify {
+                        send_desktop_notification(&message);
+                    }
+                } else if behind == 0 && last_behind > 0 && !json {
+                    println!("{}", format!("Up to date with '{}'.", main_branch).green());
+                }
+                last_behind = behind;
+            }
+        }
+
+        if opts.dry_run {
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_secs(interval_secs));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompt_state_is_written_and_cleared() {
+        let dir = tempfile::tempdir().unwrap();
+        write_prompt_state(dir.path(), 3).unwrap();
+        let path = dir.path().join(PROMPT_STATE_FILE);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "3");
+
+        write_prompt_state(dir.path(), 0).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn describe_behind_names_trunk_and_count() {
+        let message = describe_behind(2, "main");
+        assert!(message.contains("'main' has moved 2 commit(s)"));
+        assert!(message.contains("tbdflow sync"));
+    }
+}
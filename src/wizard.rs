@@ -1,6 +1,8 @@
+use crate::commit::{self, CommitParams};
 use crate::config::Config;
+use crate::{output, prompt};
 use anyhow::Result;
-use dialoguer::{Confirm, Input, Select, theme::ColorfulTheme};
+use dialoguer::{Confirm, Input, Select};
 
 #[derive(Debug, Clone)]
 pub struct CommitWizardResult {
@@ -20,6 +22,7 @@ pub struct BranchWizardResult {
     pub name: String,
     pub issue: Option<String>,
     pub from_commit: Option<String>,
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -36,7 +39,17 @@ pub struct ChangeLogWizardResult {
 }
 
 pub fn run_commit_wizard(config: &Config) -> Result<CommitWizardResult> {
-    let theme = ColorfulTheme::default();
+    run_commit_wizard_from(config, None)
+}
+
+/// The commit wizard with every answer pre-filled from `template`, e.g. a
+/// previous commit picked with `tbdflow snippet`, ready to be adapted.
+pub fn run_commit_wizard_from(
+    config: &Config,
+    template: Option<&CommitParams>,
+) -> Result<CommitWizardResult> {
+    prompt::require_interactive("Pass --type and --message to commit without the wizard.")?;
+    let theme = output::theme();
 
     // Load commit types from config or use defaults
     let allowed_types = config
@@ -62,10 +75,13 @@ pub fn run_commit_wizard(config: &Config) -> Result<CommitWizardResult> {
             ]
         });
 
-    let type_selection = Select::with_theme(&theme)
+    let default_type = template
+        .and_then(|t| allowed_types.iter().position(|a| *a == t.r#type))
+        .unwrap_or(0);
+    let type_selection = Select::with_theme(&*theme)
         .with_prompt("Select the type of change")
         .items(&allowed_types)
-        .default(0)
+        .default(default_type)
         .interact()?;
     let r#type = allowed_types[type_selection].clone();
 
@@ -74,33 +90,64 @@ pub fn run_commit_wizard(config: &Config) -> Result<CommitWizardResult> {
         if s.is_empty() { None } else { Some(s) }
     }
 
+    // Pre-filled answers are editable initial text rather than defaults, so they can be
+    // cleared. The prompts are single-line, so a multi-line body is joined with spaces.
+    let initial = |field: Option<&String>| {
+        field
+            .map(|f| f.lines().map(str::trim).collect::<Vec<_>>().join(" "))
+            .unwrap_or_default()
+    };
+
     let scope: Option<String> = to_option(
-        Input::<String>::with_theme(&theme)
+        Input::<String>::with_theme(&*theme)
             .with_prompt("Enter the scope of this change (optional)")
+            .with_initial_text(initial(template.and_then(|t| t.scope.as_ref())))
             .allow_empty(true)
             .interact_text()?,
     );
 
-    let message: String = Input::with_theme(&theme)
+    let message: String = Input::with_theme(&*theme)
         .with_prompt("Write a short, imperative tense description of the change")
+        .with_initial_text(initial(template.map(|t| &t.message)))
         .interact_text()?;
 
-    let body: Option<String> = to_option(
-        Input::<String>::with_theme(&theme)
-            .with_prompt("Provide a longer description of the change (optional)")
-            .allow_empty(true)
-            .interact_text()?,
-    );
+    // A body template for the type is asked line by line, unless a body is pre-filled.
+    let body_template = commit::body_template(config, &r#type)
+        .filter(|_| template.and_then(|t| t.body.as_ref()).is_none());
+    let body: Option<String> = match body_template {
+        Some(body_template) => {
+            let mut answers = Vec::new();
+            for line in body_template.lines().filter(|l| !l.trim().is_empty()) {
+                answers.push(
+                    Input::<String>::with_theme(&*theme)
+                        .with_prompt(format!("{} (optional)", line.trim().trim_end_matches(':')))
+                        .allow_empty(true)
+                        .interact_text()?,
+                );
+            }
+            commit::fill_body_template(body_template, &answers)
+        }
+        None => to_option(
+            Input::<String>::with_theme(&*theme)
+                .with_prompt("Provide a longer description of the change (optional)")
+                .with_initial_text(initial(template.and_then(|t| t.body.as_ref())))
+                .allow_empty(true)
+                .interact_text()?,
+        ),
+    };
 
-    let breaking = Confirm::with_theme(&theme)
+    let breaking = Confirm::with_theme(&*theme)
         .with_prompt("Is this a breaking change?")
-        .default(false)
+        .default(template.is_some_and(|t| t.breaking))
         .interact()?;
 
     let breaking_description: Option<String> = if breaking {
         Some(
-            Input::<String>::with_theme(&theme)
+            Input::<String>::with_theme(&*theme)
                 .with_prompt("Describe the breaking change")
+                .with_initial_text(initial(
+                    template.and_then(|t| t.breaking_description.as_ref()),
+                ))
                 .interact_text()?,
         )
     } else {
@@ -108,14 +155,15 @@ pub fn run_commit_wizard(config: &Config) -> Result<CommitWizardResult> {
     };
 
     let issue: Option<String> = to_option(
-        Input::<String>::with_theme(&theme)
+        Input::<String>::with_theme(&*theme)
             .with_prompt("Enter an issue reference (e.g., PROJ-123) (optional)")
+            .with_initial_text(initial(template.and_then(|t| t.issue.as_ref())))
             .allow_empty(true)
             .interact_text()?,
     );
 
     let tag: Option<String> = to_option(
-        Input::<String>::with_theme(&theme)
+        Input::<String>::with_theme(&*theme)
             .with_prompt("Enter a tag for this commit (optional)")
             .allow_empty(true)
             .interact_text()?,
@@ -134,62 +182,85 @@ pub fn run_commit_wizard(config: &Config) -> Result<CommitWizardResult> {
 }
 
 pub fn run_branch_wizard(config: &Config) -> Result<BranchWizardResult> {
-    let theme = ColorfulTheme::default();
+    run_branch_wizard_with(config, true)
+}
+
+/// The branch wizard for a branch created where HEAD is, so it does not ask
+/// for a commit on main to start from.
+pub fn run_branch_wizard_at_head(config: &Config) -> Result<BranchWizardResult> {
+    run_branch_wizard_with(config, false)
+}
+
+fn run_branch_wizard_with(config: &Config, ask_from_commit: bool) -> Result<BranchWizardResult> {
+    prompt::require_interactive("Pass --type and --name to create a branch without the wizard.")?;
+    let theme = output::theme();
 
     // Load branch types from config
     let mut allowed_types: Vec<String> = config.branch_types.keys().cloned().collect();
     allowed_types.sort(); // Sort for consistent order
 
-    let type_selection = Select::with_theme(&theme)
+    let type_selection = Select::with_theme(&*theme)
         .with_prompt("Select the type of branch")
         .items(&allowed_types)
         .default(0)
         .interact()?;
     let branch_type = allowed_types[type_selection].clone();
 
-    let name: String = Input::with_theme(&theme)
+    let name: String = Input::with_theme(&*theme)
         .with_prompt("Enter a short, descriptive name for the branch (use hyphens)")
         .interact_text()?;
 
     let issue: Option<String> = {
-        let input: String = Input::<String>::with_theme(&theme)
+        let input: String = Input::<String>::with_theme(&*theme)
             .with_prompt("Enter an issue reference to include in the branch name (optional)")
             .allow_empty(true)
             .interact_text()?;
         if input.is_empty() { None } else { Some(input) }
     };
 
-    let from_commit: Option<String> = {
-        let input: String = Input::<String>::with_theme(&theme)
+    let from_commit: Option<String> = if !ask_from_commit {
+        None
+    } else {
+        let input: String = Input::<String>::with_theme(&*theme)
             .with_prompt("Enter a commit hash on 'main' to branch from (optional)")
             .allow_empty(true)
             .interact_text()?;
         if input.is_empty() { None } else { Some(input) }
     };
 
+    let description: Option<String> = {
+        let input: String = Input::<String>::with_theme(&*theme)
+            .with_prompt("Describe what this branch is for (optional)")
+            .allow_empty(true)
+            .interact_text()?;
+        if input.is_empty() { None } else { Some(input) }
+    };
+
     Ok(BranchWizardResult {
         branch_type,
         name,
         issue,
         from_commit,
+        description,
     })
 }
 
 pub fn run_complete_wizard(config: &Config) -> Result<CompleteWizardResult> {
-    let theme = ColorfulTheme::default();
+    prompt::require_interactive("Pass --type and --name to complete a branch without the wizard.")?;
+    let theme = output::theme();
 
     // Load branch types from config
     let mut allowed_types: Vec<String> = config.branch_types.keys().cloned().collect();
     allowed_types.sort(); // Sort for consistent order
 
-    let type_selection = Select::with_theme(&theme)
+    let type_selection = Select::with_theme(&*theme)
         .with_prompt("Select the type of branch to complete")
         .items(&allowed_types)
         .default(0)
         .interact()?;
     let branch_type = allowed_types[type_selection].clone();
 
-    let name: String = Input::with_theme(&theme)
+    let name: String = Input::with_theme(&*theme)
         .with_prompt("Enter the name of the branch to complete")
         .interact_text()?;
 
@@ -197,14 +268,17 @@ pub fn run_complete_wizard(config: &Config) -> Result<CompleteWizardResult> {
 }
 
 pub fn run_changelog_wizard() -> Result<ChangeLogWizardResult> {
-    let theme = ColorfulTheme::default();
+    prompt::require_interactive(
+        "Pass --from/--to or --unreleased to generate a changelog without the wizard.",
+    )?;
+    let theme = output::theme();
 
     let options = &[
         "Generate for unreleased changes (since the latest tag)",
         "Generate for a specific range of tags",
     ];
 
-    let selection = Select::with_theme(&theme)
+    let selection = Select::with_theme(&*theme)
         .with_prompt("What changelog would you like to generate?")
         .items(options)
         .default(0)
@@ -217,10 +291,10 @@ pub fn run_changelog_wizard() -> Result<ChangeLogWizardResult> {
             unreleased: true,
         }),
         1 => {
-            let from: String = Input::with_theme(&theme)
+            let from: String = Input::with_theme(&*theme)
                 .with_prompt("Enter the 'from' tag (e.g., v0.12.0)")
                 .interact_text()?;
-            let to: String = Input::with_theme(&theme)
+            let to: String = Input::with_theme(&*theme)
                 .with_prompt("Enter the 'to' tag (e.g., v0.13.0, optional)")
                 .allow_empty(true)
                 .interact_text()?;
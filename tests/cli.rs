@@ -1,11 +1,12 @@
 use assert_cmd::Command;
 use chrono::{Duration, Utc};
+use predicates::prelude::PredicateBooleanExt;
 use predicates::str::contains;
 use predicates::str::is_match;
 use serial_test::serial;
 
 mod util;
-use util::setup_temp_git_repo;
+use util::{setup_temp_git_repo, tbdflow};
 
 /// Tests that the status command outputs the expected status message.
 #[test]
@@ -15,7 +16,7 @@ fn test_status_command() {
     let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
     std::env::set_current_dir(&repo_path).unwrap();
 
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("--verbose").arg("status");
     cmd.assert().success().stdout(contains("Checking status"));
 }
@@ -26,7 +27,7 @@ fn test_status_command() {
 fn test_current_branch_command() {
     let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
     std::env::set_current_dir(&repo_path).unwrap();
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("current-branch");
     cmd.assert()
         .success()
@@ -61,18 +62,18 @@ automatic_tags:
 
     // Commit the config file to clean the working directory
     std::process::Command::new("git")
-        .args(&["add", ".tbdflow.yml"])
+        .args(["add", ".tbdflow.yml"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     std::process::Command::new("git")
-        .args(&["commit", "-m", "Add tbdflow config"])
+        .args(["commit", "-m", "Add tbdflow config"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
 
     // Test creating a branch WITH an issue ID
-    let mut cmd_with_issue = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd_with_issue = tbdflow();
     cmd_with_issue
         .arg("branch")
         .arg("--type")
@@ -111,18 +112,18 @@ automatic_tags:
 
     // Commit the config file to clean the working directory
     std::process::Command::new("git")
-        .args(&["add", ".tbdflow.yml"])
+        .args(["add", ".tbdflow.yml"])
         .current_dir(&repo_path2)
         .output()
         .unwrap();
     std::process::Command::new("git")
-        .args(&["commit", "-m", "Add tbdflow config"])
+        .args(["commit", "-m", "Add tbdflow config"])
         .current_dir(&repo_path2)
         .output()
         .unwrap();
 
     // Test creating a branch WITHOUT an issue ID in the name
-    let mut cmd_without_issue = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd_without_issue = tbdflow();
     cmd_without_issue
         .arg("branch")
         .arg("--type")
@@ -138,13 +139,272 @@ automatic_tags:
         .stdout(contains("Success! Switched to new branch: 'fix/login-bug'"));
 }
 
+/// Tests that --cwd runs from a subdirectory, where commit and status still cover the whole repo.
+#[test]
+#[serial]
+fn test_subdirectory_commands_cover_the_whole_repo() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    std::fs::create_dir(repo_path.join("docs")).unwrap();
+    std::fs::write(repo_path.join("docs/guide.md"), "guide").unwrap();
+    std::fs::write(repo_path.join("ROOT.md"), "root").unwrap();
+
+    tbdflow()
+        .args(["--cwd", "docs", "status"])
+        .assert()
+        .success()
+        .stdout(contains("?? ROOT.md"))
+        .stdout(contains("?? docs/"));
+
+    tbdflow()
+        .args(["--cwd", "docs", "commit", "-t", "docs", "-m", "add guide"])
+        .assert()
+        .success();
+    let status = std::process::Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    assert!(
+        status.stdout.is_empty(),
+        "Expected a clean tree, got: {}",
+        String::from_utf8_lossy(&status.stdout)
+    );
+
+    tbdflow()
+        .args(["--cwd", "no-such-dir", "status"])
+        .assert()
+        .failure()
+        .stderr(contains("Cannot change to directory 'no-such-dir'"));
+}
+
+/// Tests that --version reports the build and --version --json returns it as data.
+#[test]
+fn test_version_reports_build_details() {
+    tbdflow()
+        .arg("--version")
+        .assert()
+        .success()
+        .stdout(contains(format!("tbdflow {}", env!("CARGO_PKG_VERSION"))))
+        .stdout(contains("review strategies:"));
+
+    tbdflow()
+        .args(["--version", "--json"])
+        .assert()
+        .success()
+        .stdout(contains(r#""build_date":"#))
+        .stdout(contains(r#""git-notes""#));
+}
+
+/// Tests that commits to main warn while a release branch exists, and can go to it instead.
+#[test]
+#[serial]
+fn test_release_stabilization_warns_and_offers_the_release_branch() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .output()
+            .unwrap()
+    };
+    git(&["push", "-q", "origin", "main:release_1.0.0"]);
+    git(&["fetch", "-q", "origin"]);
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+  release: "release_"
+automatic_tags:
+  release_prefix: "v"
+commit:
+  release_stabilization:
+    policy: warn
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    git(&["add", ".tbdflow.yml"]);
+    git(&["commit", "-q", "-m", "chore: add config"]);
+    git(&["push", "-q", "origin", "main"]);
+
+    std::fs::write(repo_path.join("a.txt"), "a").unwrap();
+    tbdflow()
+        .args(["commit", "-t", "feat", "-m", "add a", "--no-verify"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "'release_1.0.0' is being stabilised. This 'feat' commit goes to 'main' and stays out of that release.",
+        ));
+
+    std::fs::write(
+        repo_path.join(".tbdflow.yml"),
+        config_content.replace("policy: warn", "policy: offer"),
+    )
+    .unwrap();
+    git(&["commit", "-q", "-am", "chore: offer"]);
+    git(&["push", "-q", "origin", "main"]);
+
+    // New work is never offered the release branch.
+    std::fs::write(repo_path.join("c.txt"), "c").unwrap();
+    tbdflow()
+        .args([
+            "--yes",
+            "commit",
+            "-t",
+            "feat",
+            "-m",
+            "add c",
+            "--no-verify",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("stays out of that release"))
+        .stdout(contains("instead?").not());
+    let on_main = git(&["log", "-1", "--format=%s", "origin/main"]);
+    assert_eq!(
+        String::from_utf8_lossy(&on_main.stdout).trim(),
+        "feat: add c"
+    );
+
+    std::fs::write(repo_path.join("b.txt"), "b").unwrap();
+    tbdflow()
+        .args(["--yes", "commit", "-t", "fix", "-m", "fix b", "--no-verify"])
+        .assert()
+        .success()
+        .stdout(contains("Commit to 'release_1.0.0' instead? yes"))
+        .stdout(contains("Back on 'main'"));
+
+    let on_release = git(&["log", "-1", "--format=%s", "origin/release_1.0.0"]);
+    assert_eq!(
+        String::from_utf8_lossy(&on_release.stdout).trim(),
+        "fix: fix b"
+    );
+    let on_main = git(&["log", "-1", "--format=%s", "main"]);
+    assert_eq!(
+        String::from_utf8_lossy(&on_main.stdout).trim(),
+        "feat: add c"
+    );
+    let branch = git(&["branch", "--show-current"]);
+    assert_eq!(String::from_utf8_lossy(&branch.stdout).trim(), "main");
+}
+
+/// Tests that a commit whose push failed can be finished with `tbdflow retry`.
+#[test]
+#[serial]
+fn test_retry_finishes_a_commit_whose_push_failed() {
+    let (_dir, bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap()
+    };
+    let bare = bare_dir.path().to_str().unwrap();
+    std::fs::write(repo_path.join("README.md"), "retry me").unwrap();
+    // Fetching still works; only pushing fails.
+    git(&[
+        "remote",
+        "set-url",
+        "--push",
+        "origin",
+        "/nonexistent/origin.git",
+    ]);
+
+    tbdflow()
+        .args([
+            "commit",
+            "-t",
+            "docs",
+            "-m",
+            "explain retries",
+            "--no-verify",
+        ])
+        .assert()
+        .failure()
+        .stdout(contains(
+            "Run 'tbdflow retry' to continue from the push step",
+        ));
+    assert!(repo_path.join(".git/tbdflow/retry.json").exists());
+
+    git(&["remote", "set-url", "--push", "origin", bare]);
+    tbdflow().arg("retry").assert().success().stdout(contains(
+        "Retrying 'docs: explain retries' from the push step",
+    ));
+    let pushed = git(&["--git-dir", bare, "log", "-1", "--format=%s", "main"]);
+    assert_eq!(
+        String::from_utf8_lossy(&pushed.stdout).trim(),
+        "docs: explain retries"
+    );
+    assert!(!repo_path.join(".git/tbdflow/retry.json").exists());
+
+    tbdflow()
+        .arg("retry")
+        .assert()
+        .success()
+        .stdout(contains("Nothing to retry."));
+}
+
+/// Tests that a commit to main points out changed files it left behind in its scope.
+#[test]
+#[serial]
+fn test_commit_hints_at_forgotten_files() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+    };
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+monorepo:
+  enabled: true
+  project_dirs:
+    - frontend
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    std::fs::create_dir(repo_path.join("frontend")).unwrap();
+    std::fs::write(repo_path.join("frontend/app.js"), "v1").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-m", "chore: add frontend"]);
+    git(&["push", "origin", "main"]);
+
+    // The root commit leaves the project directory alone.
+    std::fs::write(repo_path.join("README.md"), "frontend notes").unwrap();
+    std::fs::write(repo_path.join("frontend/app.js"), "v2").unwrap();
+    tbdflow()
+        .args([
+            "commit",
+            "-t",
+            "docs",
+            "-s",
+            "frontend",
+            "-m",
+            "describe the app",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Did you forget to include frontend/app.js?"))
+        .stdout(contains("git commit --amend --no-edit"));
+}
+
 /// Tests that creating a new feature branch called "new-feature" works correctly.
 #[test]
 #[serial]
 fn test_create_feature_branch_command() {
     let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
     std::env::set_current_dir(&repo_path).unwrap();
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("branch")
         .arg("--type")
         .arg("feature")
@@ -161,7 +421,7 @@ fn test_create_feature_branch_command() {
 fn test_create_release_branch_command() {
     let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
     std::env::set_current_dir(&repo_path).unwrap();
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("branch")
         .arg("--type")
         .arg("release")
@@ -172,6 +432,34 @@ fn test_create_release_branch_command() {
         .stdout(contains("Success! Switched to new branch: 'release_1.0.0'"));
 }
 
+/// Tests that a near-duplicate branch name offers the existing branch instead.
+#[test]
+#[serial]
+fn test_branch_detects_similar_existing_branch() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    tbdflow()
+        .args(["branch", "-t", "feature", "-n", "user-profile-page"])
+        .assert()
+        .success();
+    std::process::Command::new("git")
+        .args(["checkout", "main"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+
+    tbdflow()
+        .args(["--yes", "branch", "-t", "feature", "-n", "user-profil-page"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "A branch with a similar name already exists: 'feature_user-profile-page'",
+        ))
+        .stdout(contains(
+            "Success! Switched to existing branch: 'feature_user-profile-page'",
+        ));
+}
+
 /// Tests that adding a new file and committing it with the commit command works correctly.
 /// Skipping .dod.yml verification for simplicity in this test.
 #[test]
@@ -194,7 +482,7 @@ fn test_commit_command() {
     let mut retries = 5;
     while retries > 0 {
         let status = Command::new("git")
-            .args(&["status", "--porcelain"])
+            .args(["status", "--porcelain"])
             .current_dir(&repo_path)
             .output()
             .unwrap();
@@ -205,7 +493,7 @@ fn test_commit_command() {
         retries -= 1;
     }
 
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     // Run the commit command with a feature type, scope, message, and breaking change flag
     cmd.arg("commit")
         .arg("--type")
@@ -254,7 +542,7 @@ fn test_complete_feature_branch_command() {
     std::env::set_current_dir(&repo_path).unwrap();
 
     // Create the feature branch first
-    let mut create_cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut create_cmd = tbdflow();
     create_cmd
         .arg("branch")
         .arg("--type")
@@ -263,7 +551,7 @@ fn test_complete_feature_branch_command() {
         .arg("new-feature");
     create_cmd.assert().success();
 
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("complete")
         .arg("--type")
         .arg("feature")
@@ -282,7 +570,7 @@ fn test_complete_release_branch_command() {
     std::env::set_current_dir(&repo_path).unwrap();
 
     // Create the release branch first
-    let mut create_cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut create_cmd = tbdflow();
     create_cmd
         .arg("branch")
         .arg("--type")
@@ -293,7 +581,7 @@ fn test_complete_release_branch_command() {
 
     // Ensure the branch exists locally
     let output = std::process::Command::new("git")
-        .args(&["branch", "--list"])
+        .args(["branch", "--list"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
@@ -304,7 +592,7 @@ fn test_complete_release_branch_command() {
         branches
     );
 
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("--verbose")
         .arg("complete")
         .arg("--type")
@@ -327,6 +615,42 @@ fn test_complete_release_branch_command() {
         "Expected tag v1.0.0 not found. Tags: {}",
         tags
     );
+
+    // The tag and the merge commit reach the remote together
+    let remote = std::process::Command::new("git")
+        .args(["ls-remote", "origin"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    let remote_refs = String::from_utf8_lossy(&remote.stdout);
+    assert!(remote_refs.contains("refs/tags/v1.0.0"), "{}", remote_refs);
+}
+
+/// Tests that `sync --watch` stops after one fetch under --dry-run and prints JSON lines with --json.
+#[test]
+#[serial]
+fn test_sync_watch_dry_run_runs_once() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    tbdflow()
+        .args(["--dry-run", "sync", "--watch", "--interval", "3600"])
+        .timeout(std::time::Duration::from_secs(30))
+        .assert()
+        .success()
+        .stdout(contains("Watching 'main'"))
+        .stdout(contains("git fetch origin"));
+    assert!(!repo_path.join(".git/tbdflow-behind").exists());
+
+    tbdflow()
+        .args(["--dry-run", "--json", "sync", "--watch"])
+        .timeout(std::time::Duration::from_secs(30))
+        .assert()
+        .success()
+        .stdout(contains(
+            r#"{"success":true,"data":{"main_branch":"main","behind":0,"fetch_error":null}}"#,
+        ))
+        .stdout(contains("Watching").not());
 }
 
 /// Testing the synch command to ensure it pulls changes from the remote repository
@@ -340,7 +664,7 @@ fn test_sync_command() {
     let remote_repo_url = bare_dir.path().to_str().unwrap();
     let second_clone_dir = tempfile::tempdir().unwrap();
     std::process::Command::new("git")
-        .args(&["clone", remote_repo_url, "."])
+        .args(["clone", remote_repo_url, "."])
         .current_dir(second_clone_dir.path())
         .output()
         .unwrap();
@@ -348,35 +672,35 @@ fn test_sync_command() {
     std::fs::write(second_clone_dir.path().join("REMOTE.md"), "remote change").unwrap();
 
     std::process::Command::new("git")
-        .args(&["config", "user.email", "teammate@example.com"])
+        .args(["config", "user.email", "teammate@example.com"])
         .current_dir(second_clone_dir.path())
         .output()
         .unwrap();
     std::process::Command::new("git")
-        .args(&["config", "user.name", "Teammate"])
+        .args(["config", "user.name", "Teammate"])
         .current_dir(second_clone_dir.path())
         .output()
         .unwrap();
 
     std::process::Command::new("git")
-        .args(&["add", "."])
+        .args(["add", "."])
         .current_dir(second_clone_dir.path())
         .output()
         .unwrap();
     std::process::Command::new("git")
-        .args(&["commit", "-m", "feat: add remote file"])
+        .args(["commit", "-m", "feat: add remote file"])
         .current_dir(second_clone_dir.path())
         .output()
         .unwrap();
     std::process::Command::new("git")
-        .args(&["push"])
+        .args(["push"])
         .current_dir(second_clone_dir.path())
         .output()
         .unwrap();
 
     // Now run the sync command
     std::env::set_current_dir(&repo_path).unwrap();
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("sync");
     cmd.assert()
         .success()
@@ -393,27 +717,23 @@ fn test_undo_command() {
     // Create a file and commit it so we have something to revert
     std::fs::write(repo_path.join("BAD_CHANGE.md"), "this breaks the build").unwrap();
     std::process::Command::new("git")
-        .args(&["add", "."])
+        .args(["add", "."])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     std::process::Command::new("git")
-        .args(&["commit", "-m", "feat: add bad change"])
+        .args(["commit", "-m", "feat: add bad change"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     std::process::Command::new("git")
-        .args(&["push"])
+        .args(["push"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
 
     // Grab the SHA of the commit we just made using tbdflow head-sha
-    let sha_output = Command::cargo_bin("tbdflow")
-        .unwrap()
-        .arg("head-sha")
-        .output()
-        .unwrap();
+    let sha_output = tbdflow().arg("head-sha").output().unwrap();
     let sha = String::from_utf8_lossy(&sha_output.stdout)
         .trim()
         .to_string();
@@ -422,12 +742,12 @@ fn test_undo_command() {
     assert!(repo_path.join("BAD_CHANGE.md").exists());
 
     // Run the undo command
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("undo").arg(&sha);
     cmd.assert()
         .success()
         .stdout(contains("Undo: The Panic Button"))
-        .stdout(contains(&format!("Commit '{}' has been reverted", &sha)));
+        .stdout(contains(format!("Commit '{}' has been reverted", &sha)));
 
     // The file should be gone after the revert
     assert!(
@@ -445,31 +765,27 @@ fn test_undo_no_push_command() {
 
     std::fs::write(repo_path.join("OOPS.md"), "oops").unwrap();
     std::process::Command::new("git")
-        .args(&["add", "."])
+        .args(["add", "."])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     std::process::Command::new("git")
-        .args(&["commit", "-m", "feat: oops"])
+        .args(["commit", "-m", "feat: oops"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     std::process::Command::new("git")
-        .args(&["push"])
+        .args(["push"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
 
-    let sha_output = Command::cargo_bin("tbdflow")
-        .unwrap()
-        .arg("head-sha")
-        .output()
-        .unwrap();
+    let sha_output = tbdflow().arg("head-sha").output().unwrap();
     let sha = String::from_utf8_lossy(&sha_output.stdout)
         .trim()
         .to_string();
 
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("undo").arg(&sha).arg("--no-push");
     cmd.assert()
         .success()
@@ -485,7 +801,7 @@ fn test_undo_nonexistent_sha() {
     let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
     std::env::set_current_dir(&repo_path).unwrap();
 
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("undo").arg("deadbeefdeadbeef");
     cmd.assert().failure();
 }
@@ -498,7 +814,7 @@ fn test_check_branches_command() {
     std::env::set_current_dir(&repo_path).unwrap();
 
     // Create a feature branch that is stale
-    let mut create_cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut create_cmd = tbdflow();
     create_cmd
         .arg("branch")
         .arg("--type")
@@ -510,19 +826,19 @@ fn test_check_branches_command() {
     let old_date = (Utc::now() - Duration::hours(25)).to_rfc3339();
 
     std::process::Command::new("git")
-        .args(&["commit", "--allow-empty", "-m", "stale commit"])
+        .args(["commit", "--allow-empty", "-m", "stale commit"])
         .env("GIT_COMMITTER_DATE", &old_date) // Set the committer date
         .current_dir(&repo_path)
         .output()
         .unwrap();
     // Switch to main branch to ensure the stale branch is not the current one
     std::process::Command::new("git")
-        .args(&["checkout", "main"])
+        .args(["checkout", "main"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
 
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("check-branches");
     cmd.assert()
         .success()
@@ -547,7 +863,7 @@ automatic_tags:
 "#;
     std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
 
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("radar");
     cmd.assert().success().stdout(contains("Radar is disabled"));
 }
@@ -573,17 +889,17 @@ automatic_tags:
     std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
     // Commit the config so working directory is clean for radar
     std::process::Command::new("git")
-        .args(&["add", ".tbdflow.yml"])
+        .args(["add", ".tbdflow.yml"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     std::process::Command::new("git")
-        .args(&["commit", "-m", "chore: add config"])
+        .args(["commit", "-m", "chore: add config"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     std::process::Command::new("git")
-        .args(&["push"])
+        .args(["push"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
@@ -591,7 +907,7 @@ automatic_tags:
     // Modify an existing tracked file so radar has local changes to scan
     std::fs::write(repo_path.join("README.md"), "modified locally").unwrap();
 
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("radar");
     cmd.assert()
         .success()
@@ -619,53 +935,53 @@ automatic_tags:
     std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
     // Commit the config so it's part of the repo
     std::process::Command::new("git")
-        .args(&["add", ".tbdflow.yml"])
+        .args(["add", ".tbdflow.yml"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     std::process::Command::new("git")
-        .args(&["commit", "-m", "chore: add config"])
+        .args(["commit", "-m", "chore: add config"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     std::process::Command::new("git")
-        .args(&["push"])
+        .args(["push"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
 
     // Create a feature branch, modify README.md, push it
     std::process::Command::new("git")
-        .args(&["checkout", "-b", "feat/other-work"])
+        .args(["checkout", "-b", "feat/other-work"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     std::fs::write(repo_path.join("README.md"), "changed by teammate").unwrap();
     std::process::Command::new("git")
-        .args(&["add", "."])
+        .args(["add", "."])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     std::process::Command::new("git")
-        .args(&["commit", "-m", "feat: teammate changes"])
+        .args(["commit", "-m", "feat: teammate changes"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     std::process::Command::new("git")
-        .args(&["push", "-u", "origin", "feat/other-work"])
+        .args(["push", "-u", "origin", "feat/other-work"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
 
     // Switch back to main and make a local change to the same file
     std::process::Command::new("git")
-        .args(&["checkout", "main"])
+        .args(["checkout", "main"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     std::fs::write(repo_path.join("README.md"), "my local change").unwrap();
 
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("radar");
     cmd.assert()
         .success()
@@ -688,7 +1004,7 @@ fn test_commit_with_message_file() {
     let msg_file = repo_path.join("commit-msg.txt");
     std::fs::write(&msg_file, "add feature file\n").unwrap();
 
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("commit")
         .arg("--type")
         .arg("feat")
@@ -719,7 +1035,7 @@ fn test_commit_with_body_file() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("commit")
         .arg("--type")
         .arg("docs")
@@ -734,6 +1050,76 @@ fn test_commit_with_body_file() {
         .stdout(is_match(r"Successfully (?:committed and )?pushed changes").unwrap());
 }
 
+/// Tests that commit --edit opens the editor on the type's body template.
+#[test]
+#[serial]
+fn test_commit_edit_starts_from_the_type_template() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+commit:
+  templates:
+    perf: |
+      Before:
+      After:
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+
+    // An editor that answers the template's lines.
+    let editor = repo_path.join(".git/fill-template.sh");
+    std::fs::write(
+        &editor,
+        "#!/bin/sh\nsed -i -e 's/^Before:$/Before: 120ms/' -e 's/^After:$/After: 40ms/' \"$1\"\n",
+    )
+    .unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&editor, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    tbdflow()
+        .args([
+            "commit",
+            "--edit",
+            "-t",
+            "perf",
+            "-m",
+            "cache lookups",
+            "--no-verify",
+        ])
+        .env("EDITOR", &editor)
+        .assert()
+        .success()
+        .stdout(is_match(r"Successfully (?:committed and )?pushed changes").unwrap());
+
+    let log = std::process::Command::new("git")
+        .args(["log", "-1", "--format=%B"])
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&log.stdout).trim(),
+        "perf: cache lookups\n\nBefore: 120ms\nAfter: 40ms"
+    );
+
+    // Saving an empty message aborts.
+    std::fs::write(repo_path.join("b.txt"), "b").unwrap();
+    std::fs::write(&editor, "#!/bin/sh\n: > \"$1\"\n").unwrap();
+    tbdflow()
+        .args(["commit", "--edit", "-t", "perf", "--no-verify"])
+        .env("EDITOR", &editor)
+        .assert()
+        .failure()
+        .stderr(contains("empty commit message"));
+}
+
 /// Tests that --message-file and --message conflict.
 #[test]
 #[serial]
@@ -744,7 +1130,7 @@ fn test_commit_message_file_conflicts_with_message() {
     let msg_file = repo_path.join("msg.txt");
     std::fs::write(&msg_file, "subject").unwrap();
 
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("commit")
         .arg("--type")
         .arg("feat")
@@ -767,7 +1153,7 @@ fn test_commit_body_file_conflicts_with_body() {
     let body_file = repo_path.join("body.txt");
     std::fs::write(&body_file, "file body").unwrap();
 
-    let mut cmd = Command::cargo_bin("tbdflow").unwrap();
+    let mut cmd = tbdflow();
     cmd.arg("commit")
         .arg("--type")
         .arg("feat")
@@ -781,3 +1167,3627 @@ fn test_commit_body_file_conflicts_with_body() {
         .failure()
         .stderr(contains("cannot be used with"));
 }
+
+/// Tests that the commit wizard fails fast instead of hanging when there is no TTY.
+#[test]
+#[serial]
+fn test_commit_wizard_fails_fast_without_tty() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let mut cmd = tbdflow();
+    cmd.arg("commit");
+    cmd.assert()
+        .failure()
+        .stderr(contains("non-interactive session"))
+        .stderr(contains("--type and --message"));
+}
+
+/// Tests that a full conventional message piped via --stdin is parsed and committed.
+#[test]
+#[serial]
+fn test_commit_full_message_from_stdin() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    std::fs::write(repo_path.join("API.md"), "endpoints").unwrap();
+
+    let mut cmd = tbdflow();
+    cmd.arg("commit")
+        .arg("--stdin")
+        .arg("--no-verify")
+        .write_stdin("docs(api): describe endpoints\n\nLists every route.\n\nRefs: PROJ-7\n");
+    cmd.assert()
+        .success()
+        .stdout(contains("docs(api): describe endpoints"))
+        .stdout(contains("Refs: PROJ-7"));
+
+    let log = std::process::Command::new("git")
+        .args(["log", "-1", "--format=%B"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    let body = String::from_utf8_lossy(&log.stdout);
+    assert!(body.contains("Lists every route."), "Body: {}", body);
+}
+
+/// Tests that a non-conventional message from -F is rejected by the linter.
+#[test]
+#[serial]
+fn test_commit_full_message_file_rejects_invalid_header() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let msg_file = repo_path.join("msg.txt");
+    std::fs::write(&msg_file, "Updated some things").unwrap();
+
+    let mut cmd = tbdflow();
+    cmd.arg("commit").arg("-F").arg(msg_file.to_str().unwrap());
+    cmd.assert()
+        .failure()
+        .stderr(contains("not a valid Conventional Commit"));
+}
+
+/// Tests that `parse` reports rule names and exits non-zero on violations.
+#[test]
+#[serial]
+fn test_parse_reports_violations() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let mut cmd = tbdflow();
+    cmd.arg("parse").arg("yolo: Add thing.");
+    cmd.assert()
+        .failure()
+        .stdout(contains("conventional_commit_type"))
+        .stdout(contains("subject_line_rules"));
+
+    let mut ok = tbdflow();
+    ok.arg("parse").write_stdin("fix(ui): align button\n");
+    ok.assert()
+        .success()
+        .stdout(contains("Commit message is valid."));
+}
+
+/// Tests that `commit --tag` pushes only the new tag, not other local tags.
+#[test]
+#[serial]
+fn test_commit_tag_pushes_only_created_tag() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    std::process::Command::new("git")
+        .args(["tag", "private-experiment"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    std::fs::write(repo_path.join("NOTES.md"), "release notes").unwrap();
+
+    let mut cmd = tbdflow();
+    cmd.arg("commit")
+        .arg("-t")
+        .arg("chore")
+        .arg("-m")
+        .arg("prepare release")
+        .arg("--tag")
+        .arg("v0.1.0")
+        .arg("--no-verify");
+    cmd.assert().success();
+
+    let output = std::process::Command::new("git")
+        .args(["ls-remote", "--tags", "origin"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    let remote_tags = String::from_utf8_lossy(&output.stdout);
+    assert!(remote_tags.contains("refs/tags/v0.1.0"), "{}", remote_tags);
+    assert!(
+        !remote_tags.contains("private-experiment"),
+        "{}",
+        remote_tags
+    );
+}
+
+/// Tests that `complete` refuses to merge a branch that is behind its remote,
+/// and fast-forwards it first when confirmed with --yes.
+#[test]
+#[serial]
+fn test_complete_checks_branch_is_current_with_remote() {
+    let (_dir, bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let mut create_cmd = tbdflow();
+    create_cmd
+        .arg("branch")
+        .arg("--type")
+        .arg("feat")
+        .arg("--name")
+        .arg("shared-work");
+    create_cmd.assert().success();
+
+    // A teammate pushes to the same branch from another clone
+    let teammate = tempfile::tempdir().unwrap();
+    let git = |args: &[&str], dir: &std::path::Path| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+    };
+    git(
+        &[
+            "clone",
+            "-b",
+            "feat/shared-work",
+            bare_dir.path().to_str().unwrap(),
+            ".",
+        ],
+        teammate.path(),
+    );
+    git(
+        &["config", "user.email", "mate@example.com"],
+        teammate.path(),
+    );
+    git(&["config", "user.name", "Mate"], teammate.path());
+    std::fs::write(teammate.path().join("MATE.md"), "teammate work").unwrap();
+    git(&["add", "."], teammate.path());
+    git(&["commit", "-m", "feat: teammate work"], teammate.path());
+    git(&["push"], teammate.path());
+
+    let mut cmd = tbdflow();
+    cmd.arg("complete")
+        .arg("--type")
+        .arg("feat")
+        .arg("--name")
+        .arg("shared-work");
+    cmd.assert().failure().stdout(contains("behind its remote"));
+
+    let mut cmd = tbdflow();
+    cmd.arg("--yes")
+        .arg("complete")
+        .arg("--type")
+        .arg("feat")
+        .arg("--name")
+        .arg("shared-work");
+    cmd.assert().success();
+    assert!(repo_path.join("MATE.md").exists());
+}
+
+/// Tests that an active pair session adds a Co-authored-by trailer until stopped.
+#[test]
+#[serial]
+fn test_pair_session_adds_co_author_trailer() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    tbdflow()
+        .args(["pair", "start", "Ada Lovelace <ada@example.com>"])
+        .assert()
+        .success()
+        .stdout(contains("Pairing with: Ada Lovelace <ada@example.com>"));
+
+    tbdflow()
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(contains("Pairing with: Ada Lovelace <ada@example.com>"));
+
+    std::fs::write(repo_path.join("PAIR.md"), "paired").unwrap();
+    tbdflow()
+        .args(["commit", "-t", "docs", "-m", "paired change", "--no-verify"])
+        .assert()
+        .success();
+    let log = std::process::Command::new("git")
+        .args(["log", "-1", "--format=%B"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    let body = String::from_utf8_lossy(&log.stdout);
+    assert!(
+        body.contains("Co-authored-by: Ada Lovelace <ada@example.com>"),
+        "Body: {}",
+        body
+    );
+
+    tbdflow()
+        .args(["pair", "stop"])
+        .assert()
+        .success()
+        .stdout(contains("Pair session ended."));
+
+    std::fs::write(repo_path.join("SOLO.md"), "solo").unwrap();
+    tbdflow()
+        .args(["commit", "-t", "docs", "-m", "solo change", "--no-verify"])
+        .assert()
+        .success();
+    let log = std::process::Command::new("git")
+        .args(["log", "-1", "--format=%B"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    let body = String::from_utf8_lossy(&log.stdout);
+    assert!(!body.contains("Co-authored-by"), "Body: {}", body);
+}
+
+/// Tests that `mob next` hands off WIP and `mob done` squashes it, crediting every driver.
+#[test]
+#[serial]
+fn test_mob_next_and_done() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    tbdflow()
+        .args(["branch", "-t", "feature", "-n", "mob-session"])
+        .assert()
+        .success();
+
+    std::fs::write(repo_path.join("MOB1.md"), "first driver").unwrap();
+    tbdflow()
+        .args(["mob", "next"])
+        .env("GIT_AUTHOR_NAME", "Bob Berg")
+        .env("GIT_AUTHOR_EMAIL", "bob@example.com")
+        .assert()
+        .success()
+        .stdout(contains("Committed WIP: wip: mob handoff"))
+        .stdout(contains("git checkout feature_mob-session"));
+
+    std::fs::write(repo_path.join("MOB2.md"), "second driver").unwrap();
+    tbdflow().args(["mob", "next"]).assert().success();
+
+    tbdflow()
+        .args(["mob", "done", "-t", "feat", "-m", "add mob feature"])
+        .assert()
+        .success()
+        .stdout(contains("Squashed 2 WIP commit(s)"));
+
+    let log = std::process::Command::new("git")
+        .args([
+            "log",
+            "--format=%B",
+            "origin/main..origin/feature_mob-session",
+        ])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    let body = String::from_utf8_lossy(&log.stdout);
+    assert!(body.starts_with("feat: add mob feature"), "Log: {}", body);
+    assert!(
+        body.contains("Co-authored-by: Bob Berg <bob@example.com>"),
+        "Log: {}",
+        body
+    );
+    assert!(!body.contains("wip:"), "Log: {}", body);
+}
+
+/// Tests that --preview prints a diffstat of the staged changes before committing.
+#[test]
+#[serial]
+fn test_commit_preview_shows_diffstat() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    std::fs::write(repo_path.join("PREVIEW.md"), "preview me").unwrap();
+    tbdflow()
+        .args([
+            "commit",
+            "-t",
+            "docs",
+            "-m",
+            "add preview notes",
+            "--no-verify",
+            "--preview",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("--- Changes to be committed ---"))
+        .stdout(contains("PREVIEW.md"))
+        .stdout(contains("1 file changed"))
+        .stdout(contains(
+            "Successfully committed and pushed changes to main.",
+        ));
+}
+
+/// Tests that doctor reports its checks as JSON in a healthy repository.
+#[test]
+#[serial]
+fn test_doctor_json_reports_checks() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    tbdflow()
+        .args(["--json", "doctor"])
+        .assert()
+        .success()
+        .stdout(contains("\"healthy\": true"))
+        .stdout(contains("\"name\": \"identity\""))
+        .stdout(contains("Test <test@example.com>"));
+}
+
+/// Tests that doctor --json still exits non-zero when a check fails.
+#[test]
+#[serial]
+fn test_doctor_json_fails_when_a_check_fails() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    std::process::Command::new("git")
+        .args(["config", "--unset", "user.name"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+
+    tbdflow()
+        .env("GIT_CONFIG_GLOBAL", "/dev/null")
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .args(["--json", "doctor"])
+        .assert()
+        .code(1)
+        .stdout(contains("\"healthy\": false"));
+}
+
+/// Tests that TBDFLOW_ASSUME_YES confirms prompts like the --yes flag.
+#[test]
+#[serial]
+fn test_assume_yes_from_environment() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    tbdflow()
+        .args(["branch", "-t", "feature", "-n", "search-page"])
+        .assert()
+        .success();
+    std::process::Command::new("git")
+        .args(["checkout", "main"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+
+    tbdflow()
+        .args(["branch", "-t", "feature", "-n", "serch-page"])
+        .env("TBDFLOW_ASSUME_YES", "1")
+        .assert()
+        .success()
+        .stdout(contains("yes (--yes)"))
+        .stdout(contains(
+            "Success! Switched to existing branch: 'feature_search-page'",
+        ));
+}
+
+/// Tests that breaking and oversized commits trigger a review without any matching rule.
+#[test]
+#[serial]
+fn test_review_auto_triggers_on_breaking_and_large_commits() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+review:
+  enabled: true
+  strategy: log-only
+  large_commit_lines: 5
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+
+    // The config itself is over the threshold.
+    tbdflow()
+        .args(["commit", "-t", "chore", "-m", "add config", "--no-verify"])
+        .assert()
+        .success()
+        .stdout(contains("--- Triggering Non-blocking Review ---"));
+
+    std::fs::write(repo_path.join("README.md"), "small change").unwrap();
+    tbdflow()
+        .args(["commit", "-t", "docs", "-m", "tweak readme", "--no-verify"])
+        .assert()
+        .success()
+        .stdout(contains("Triggering Non-blocking Review").not());
+
+    std::fs::write(repo_path.join("README.md"), "breaking change").unwrap();
+    tbdflow()
+        .args([
+            "commit",
+            "-t",
+            "feat",
+            "-m",
+            "drop legacy flag",
+            "--breaking",
+            "--no-verify",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("--- Triggering Non-blocking Review ---"))
+        .stdout(contains("Review logged"));
+}
+
+/// Tests that `review --approve` waits for `required_approvals` distinct approvers.
+#[test]
+#[serial]
+fn test_review_approve_waits_for_required_approvals() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+review:
+  enabled: true
+  strategy: log-only
+  required_approvals: 2
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+
+    let approve = || {
+        tbdflow()
+            .args(["review", "--approve", "HEAD"])
+            .assert()
+            .success()
+    };
+    approve().stdout(contains("(1/2). Waiting for 1 more."));
+    // The same reviewer approving again does not count twice.
+    approve().stdout(contains("(1/2). Waiting for 1 more."));
+
+    std::process::Command::new("git")
+        .args(["config", "user.name", "Second Reviewer"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    approve().stdout(contains("marked as approved"));
+
+    let journal = std::fs::read_to_string(repo_path.join(".git/tbdflow/journal.jsonl")).unwrap();
+    assert!(journal.contains("\"event\":\"review-approved\""));
+}
+
+/// Tests that --batch requests one review covering a range of commits.
+#[test]
+#[serial]
+fn test_review_batch_covers_a_range_of_commits() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+review:
+  enabled: true
+  strategy: log-only
+  large_commit_lines: 0
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    for (file, message) in [("parser.rs", "add parser"), ("lexer.rs", "add lexer")] {
+        std::fs::write(repo_path.join(file), message).unwrap();
+        tbdflow()
+            .args(["commit", "-t", "feat", "-m", message, "--no-verify"])
+            .assert()
+            .success();
+    }
+
+    tbdflow()
+        .args(["review", "--trigger", "--batch", "HEAD~2..HEAD"])
+        .assert()
+        .success()
+        .stdout(contains("--- Triggering Non-blocking Batch Review ---"))
+        .stdout(contains("HEAD~2..HEAD (2 commits)"))
+        .stdout(contains("feat: add parser"))
+        .stdout(contains("feat: add lexer"))
+        .stdout(contains("Review logged"));
+
+    tbdflow()
+        .args(["review", "--trigger", "--batch", "HEAD..HEAD"])
+        .assert()
+        .failure()
+        .stderr(contains("No commits in range 'HEAD..HEAD'."));
+}
+
+/// Tests that the review digest pages, filters by path and resumes from saved state.
+#[test]
+#[serial]
+fn test_review_digest_pages_filters_and_resumes() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    std::fs::create_dir(repo_path.join("src")).unwrap();
+    for (file, kind, message) in [
+        ("src/parser.rs", "feat", "add parser"),
+        ("src/lexer.rs", "fix", "handle empty input"),
+        ("notes.md", "docs", "write notes"),
+    ] {
+        std::fs::write(repo_path.join(file), message).unwrap();
+        tbdflow()
+            .args(["commit", "-t", kind, "-m", message, "--no-verify"])
+            .assert()
+            .success();
+    }
+
+    tbdflow()
+        .args(["review", "--digest", "--path", "src", "--limit", "1"])
+        .assert()
+        .success()
+        .stdout(contains("Page 1/2 (1 of 2 commits)"))
+        .stdout(contains("handle empty input"))
+        .stdout(contains("add parser").not())
+        .stdout(contains("write notes").not())
+        .stdout(contains("--page 2"));
+
+    tbdflow()
+        .args([
+            "review", "--digest", "--path", "src", "--limit", "1", "--page", "3",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("Page 3 is past the end of the digest"));
+
+    tbdflow()
+        .args(["review", "--digest", "--resume"])
+        .assert()
+        .success()
+        .stdout(contains("write notes"))
+        .stdout(contains("Digest position saved"));
+
+    std::fs::write(repo_path.join("src/emitter.rs"), "emit").unwrap();
+    tbdflow()
+        .args(["commit", "-t", "feat", "-m", "add emitter", "--no-verify"])
+        .assert()
+        .success();
+
+    tbdflow()
+        .args(["review", "--digest", "--resume"])
+        .assert()
+        .success()
+        .stdout(contains("Since last digest at"))
+        .stdout(contains("add emitter"))
+        .stdout(contains("write notes").not());
+}
+
+/// Tests that `--json review` prints the digest as JSON and rejects review writes.
+#[test]
+#[serial]
+fn test_review_digest_json() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    for (file, kind, message) in [
+        ("a.rs", "feat", "add parser"),
+        ("b.rs", "fix", "handle empty input"),
+    ] {
+        std::fs::write(repo_path.join(file), message).unwrap();
+        tbdflow()
+            .args(["commit", "-t", kind, "-m", message, "--no-verify"])
+            .assert()
+            .success();
+    }
+
+    tbdflow()
+        .args(["--json", "review", "--digest", "--limit", "1"])
+        .assert()
+        .success()
+        .stdout(contains("\"pages\": 3"))
+        .stdout(contains("\"next_page\": 2"))
+        .stdout(contains("Bug Fixes\""))
+        .stdout(contains("\"description\": \"handle empty input\""))
+        .stdout(contains("add parser").not())
+        .stdout(contains("Trunk Evolution Digest").not());
+
+    tbdflow()
+        .args(["--json", "review"])
+        .assert()
+        .success()
+        .stdout(contains("\"sections\""));
+
+    tbdflow()
+        .args(["--json", "review", "--approve", "HEAD"])
+        .assert()
+        .failure()
+        .stderr(contains("--json is only supported"));
+}
+
+/// Tests that `complete` requests a batch review of the merged commits.
+#[test]
+#[serial]
+fn test_complete_triggers_review_of_merged_commits() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+review:
+  enabled: true
+  strategy: log-only
+  large_commit_lines: 0
+  on_complete: batch
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    tbdflow()
+        .args(["commit", "-t", "chore", "-m", "add config", "--no-verify"])
+        .assert()
+        .success();
+
+    tbdflow()
+        .args(["branch", "-t", "feat", "-n", "parser"])
+        .assert()
+        .success();
+    for (file, message) in [("parser.rs", "add parser"), ("lexer.rs", "add lexer")] {
+        std::fs::write(repo_path.join(file), message).unwrap();
+        tbdflow()
+            .args(["commit", "-t", "feat", "-m", message, "--no-verify"])
+            .assert()
+            .success()
+            .stdout(contains("Triggering Non-blocking").not());
+    }
+
+    tbdflow()
+        .args(["complete", "-t", "feat", "-n", "parser"])
+        .assert()
+        .success()
+        .stdout(contains("--- Triggering Non-blocking Batch Review ---"))
+        .stdout(contains(" (2 commits)"))
+        .stdout(contains("feat: add parser"))
+        .stdout(contains("feat: add lexer"))
+        .stdout(contains("chore: add config").not());
+}
+
+/// Tests that review actions are queued while GitHub is unreachable.
+#[test]
+#[serial]
+fn test_review_actions_are_queued_when_offline() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+review:
+  enabled: true
+  strategy: github-issue
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+
+    // Without gh on PATH, GitHub is never reachable.
+    let offline = || {
+        let mut cmd = tbdflow();
+        cmd.env("PATH", "/usr/bin:/bin");
+        cmd
+    };
+    offline()
+        .args(["review", "--approve", "abc1234"])
+        .assert()
+        .success()
+        .stdout(contains("Queued 'approve abc1234' (1 action(s) waiting)"));
+    offline()
+        .args([
+            "review",
+            "--concern",
+            "abc1234",
+            "-m",
+            "Drops writes",
+            "--severity",
+            "critical",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("(2 action(s) waiting)"));
+
+    let queue = std::fs::read_to_string(repo_path.join(".git/tbdflow/review-queue.json")).unwrap();
+    assert!(queue.contains("\"action\": \"concern\""));
+    assert!(queue.contains("\"severity\": \"critical\""));
+
+    offline()
+        .args(["review", "flush"])
+        .assert()
+        .success()
+        .stdout(contains("2 review action(s) remain queued"));
+}
+
+/// Tests that the gitea strategy queues review actions while its server is down.
+#[test]
+#[serial]
+fn test_gitea_review_actions_are_queued_when_server_is_down() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+review:
+  enabled: true
+  strategy: gitea
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    // Nothing listens on port 9, so the server is never reachable.
+    std::process::Command::new("git")
+        .args([
+            "remote",
+            "set-url",
+            "origin",
+            "http://127.0.0.1:9/team/app.git",
+        ])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+
+    tbdflow()
+        .args(["review", "--dismiss", "abc1234", "-m", "Generated code"])
+        .env("GITEA_TOKEN", "secret")
+        .assert()
+        .success()
+        .stdout(contains("Gitea is unreachable. Queued 'dismiss abc1234'"));
+}
+
+/// Tests that the bitbucket strategy queues review actions until it has credentials.
+#[test]
+#[serial]
+fn test_bitbucket_review_actions_wait_for_credentials() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+review:
+  enabled: true
+  strategy: bitbucket
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    std::process::Command::new("git")
+        .args([
+            "remote",
+            "set-url",
+            "origin",
+            "https://bitbucket.org/team/app.git",
+        ])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+
+    tbdflow()
+        .args(["review", "--approve", "abc1234"])
+        .env_remove("BITBUCKET_USERNAME")
+        .env_remove("BITBUCKET_APP_PASSWORD")
+        .assert()
+        .success()
+        .stdout(contains(
+            "Bitbucket is unreachable. Queued 'approve abc1234'",
+        ));
+}
+
+/// Tests that the git-notes strategy records the review lifecycle in notes pushed to origin.
+#[test]
+#[serial]
+fn test_git_notes_review_lifecycle() {
+    let (_dir, bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+review:
+  enabled: true
+  strategy: git-notes
+  required_approvals: 2
+  default_reviewers:
+    - ada
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+
+    tbdflow()
+        .args(["review", "--trigger"])
+        .assert()
+        .success()
+        .stdout(contains("Review recorded in git notes"));
+    tbdflow()
+        .args(["review", "--concern", "HEAD", "-m", "Missing tests"])
+        .assert()
+        .success()
+        .stdout(contains("CONCERN on"));
+    tbdflow()
+        .args(["review", "--approve", "HEAD"])
+        .assert()
+        .success()
+        .stdout(contains("1/2"));
+
+    tbdflow()
+        .args(["review", "status"])
+        .assert()
+        .success()
+        .stdout(contains("State:     concern (1/2 approvals)"))
+        .stdout(contains("Reviewers: ada"))
+        .stdout(contains("Missing tests"));
+
+    // A second reviewer approves from their own identity.
+    tbdflow()
+        .args(["review", "--approve", "HEAD"])
+        .env("GIT_CONFIG_COUNT", "1")
+        .env("GIT_CONFIG_KEY_0", "user.name")
+        .env("GIT_CONFIG_VALUE_0", "Bo")
+        .assert()
+        .success()
+        .stdout(contains("approved"));
+    tbdflow()
+        .args(["--json", "review", "status", "HEAD"])
+        .assert()
+        .success()
+        .stdout(contains(r#""state": "approved""#))
+        .stdout(contains(r#""approvals": 2"#));
+
+    let remote = std::process::Command::new("git")
+        .args([
+            "ls-remote",
+            bare_dir.path().to_str().unwrap(),
+            "refs/notes/*",
+        ])
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&remote.stdout).contains("refs/notes/tbdflow-reviews"));
+}
+
+/// Tests that `review list` shows open reviews recorded in git notes as a table.
+#[test]
+#[serial]
+fn test_review_list_shows_reviews_recorded_in_git_notes() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+review:
+  enabled: true
+  strategy: git-notes
+  default_reviewers:
+    - test
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    tbdflow().args(["review", "--trigger"]).assert().success();
+
+    tbdflow()
+        .args(["review", "list"])
+        .assert()
+        .success()
+        .stdout(contains("COMMIT"))
+        .stdout(contains("REVIEWERS"))
+        .stdout(contains("pending"))
+        .stdout(contains("init"))
+        .stdout(contains("1 review(s)"));
+    tbdflow()
+        .args(["review", "mine", "--label", "concern"])
+        .assert()
+        .success()
+        .stdout(contains("No open reviews match."));
+
+    tbdflow()
+        .args(["review", "--concern", "HEAD", "-m", "Missing tests"])
+        .assert()
+        .success();
+    tbdflow()
+        .args(["--json", "review", "mine"])
+        .assert()
+        .success()
+        .stdout(contains(r#""state": "concern""#))
+        .stdout(contains(r#""author": "Test""#))
+        .stdout(contains(r#""number""#).not());
+
+    tbdflow()
+        .args(["review", "--approve", "HEAD"])
+        .assert()
+        .success();
+    tbdflow()
+        .args(["review", "list"])
+        .assert()
+        .success()
+        .stdout(contains("No open reviews match."));
+}
+
+/// Tests that review show prints the concerns, comments and diffstat of a review in git notes.
+#[test]
+#[serial]
+fn test_review_show_prints_the_full_review_record() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+review:
+  enabled: true
+  strategy: git-notes
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    tbdflow()
+        .args(["review", "show"])
+        .assert()
+        .success()
+        .stdout(contains("No review found for"))
+        .stdout(contains("Changes:"))
+        .stdout(contains("changed"));
+
+    tbdflow().args(["review", "--trigger"]).assert().success();
+    tbdflow()
+        .args(["review", "--concern", "HEAD", "-m", "Missing tests"])
+        .assert()
+        .success();
+
+    tbdflow()
+        .args(["review", "show", "HEAD"])
+        .assert()
+        .success()
+        .stdout(contains("State:    concern"))
+        .stdout(contains("[ ] Missing tests"))
+        .stdout(contains("concern: Missing tests"))
+        .stdout(contains("Changes:"));
+    tbdflow()
+        .args(["--json", "review", "show"])
+        .assert()
+        .success()
+        .stdout(contains(r#""text": "Missing tests""#))
+        .stdout(contains(r#""resolved": false"#))
+        .stdout(contains(r#""diffstat""#));
+}
+
+/// Tests that review stats counts reviewed and unreviewed trunk commits from git notes.
+#[test]
+#[serial]
+fn test_review_stats_counts_reviews_recorded_in_git_notes() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+review:
+  enabled: true
+  strategy: git-notes
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    tbdflow()
+        .args(["commit", "-t", "chore", "-m", "add config", "--no-verify"])
+        .assert()
+        .success();
+    tbdflow()
+        .args(["review", "--concern", "HEAD", "-m", "Missing tests"])
+        .assert()
+        .success();
+    tbdflow()
+        .args(["review", "--approve", "HEAD"])
+        .assert()
+        .success();
+
+    tbdflow()
+        .args(["review", "stats", "--since", "30d"])
+        .assert()
+        .success()
+        .stdout(contains("Review throughput on 'origin/main'"))
+        .stdout(contains("Commits:            2"))
+        .stdout(contains("Reviewed:           1 (50%)"))
+        .stdout(contains("Concerns raised per author:"))
+        .stdout(contains("Top reviewers:"));
+    tbdflow()
+        .args(["--json", "review", "stats"])
+        .assert()
+        .success()
+        .stdout(contains(r#""unreviewed": 1"#))
+        .stdout(contains(r#""median_seconds_to_approval": "#))
+        .stdout(contains(r#""name": "Test""#));
+}
+
+/// Tests that the in-repo strategy commits the review ledger to trunk and pushes it.
+#[test]
+#[serial]
+fn test_in_repo_review_ledger_is_committed_to_trunk() {
+    let (_dir, bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+review:
+  enabled: true
+  strategy: in-repo
+  default_reviewers:
+    - ada
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    let head = std::process::Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .unwrap();
+    let reviewed = String::from_utf8_lossy(&head.stdout).trim().to_string();
+
+    tbdflow()
+        .args(["review", "--trigger"])
+        .assert()
+        .success()
+        .stdout(contains("Review recorded in .tbdflow/reviews"));
+    tbdflow()
+        .args(["review", "--approve", &reviewed])
+        .assert()
+        .success()
+        .stdout(contains("approved"));
+    tbdflow()
+        .args(["review", "status", &reviewed])
+        .assert()
+        .success()
+        .stdout(contains("State:     approved (1/1 approvals)"))
+        .stdout(contains("Reviewers: ada"));
+
+    // The ledger is on origin's trunk, and the local checkout has it too.
+    let ledger = format!("main:.tbdflow/reviews/{}.yml", reviewed);
+    let remote = std::process::Command::new("git")
+        .args([
+            "--git-dir",
+            bare_dir.path().to_str().unwrap(),
+            "show",
+            &ledger,
+        ])
+        .output()
+        .unwrap();
+    let remote = String::from_utf8_lossy(&remote.stdout).to_string();
+    assert!(remote.contains("action: requested"));
+    assert!(remote.contains("action: approved"));
+    let local =
+        std::fs::read_to_string(repo_path.join(format!(".tbdflow/reviews/{}.yml", reviewed)))
+            .unwrap();
+    assert_eq!(local, remote);
+    let log = std::process::Command::new("git")
+        .args(["log", "--format=%s", "-2"])
+        .output()
+        .unwrap();
+    assert!(
+        String::from_utf8_lossy(&log.stdout)
+            .contains(&format!("chore(review): approve {}", &reviewed[..7]))
+    );
+}
+
+/// Tests that --skip-review exempts allowlisted types, logs to the journal, and is ignored otherwise.
+#[test]
+#[serial]
+fn test_skip_review_is_journaled_for_exempt_types() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+review:
+  enabled: true
+  strategy: log-only
+  large_commit_lines: 1
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    tbdflow()
+        .args([
+            "commit",
+            "-t",
+            "docs",
+            "-m",
+            "add config",
+            "--no-verify",
+            "--skip-review",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Review: skip"))
+        .stdout(contains("Review skipped for this 'docs' commit"))
+        .stdout(contains("Triggering Non-blocking Review").not());
+
+    let journal = std::fs::read_to_string(repo_path.join(".git/tbdflow/journal.jsonl")).unwrap();
+    assert!(journal.contains("\"event\":\"review-skipped\""));
+    assert!(journal.contains("\"commit_type\":\"docs\""));
+
+    std::fs::write(repo_path.join("README.md"), "new feature\nmore").unwrap();
+    tbdflow()
+        .args([
+            "commit",
+            "-t",
+            "feat",
+            "-m",
+            "add feature",
+            "--no-verify",
+            "--skip-review",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("'feat' is not in review.exempt_types"))
+        .stdout(contains("--- Triggering Non-blocking Review ---"));
+}
+
+/// Tests that `complete` works for a branch that only exists on origin.
+#[test]
+#[serial]
+fn test_complete_remote_only_branch() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap()
+    };
+    // A teammate pushed the branch; only the remote ref is left locally.
+    git(&["checkout", "-b", "feature_vacation-work"]);
+    std::fs::write(repo_path.join("vacation.txt"), "done before leaving").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-m", "feat: vacation work"]);
+    git(&["push", "-u", "origin", "feature_vacation-work"]);
+    git(&["checkout", "main"]);
+    git(&["branch", "-D", "feature_vacation-work"]);
+
+    tbdflow()
+        .args([
+            "--verbose",
+            "complete",
+            "--type",
+            "feature",
+            "--name",
+            "vacation-work",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Branch to complete: feature_vacation-work"))
+        .stdout(contains("only existed on origin"))
+        .stdout(contains("git fetch origin").count(1))
+        .stdout(contains("was merged into main and deleted"));
+
+    assert!(repo_path.join("vacation.txt").exists());
+}
+
+/// Tests that a tag which only exists on origin is detected before committing.
+#[test]
+#[serial]
+fn test_commit_tag_already_on_remote_is_rejected() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap()
+    };
+    git(&["tag", "v1.0.0"]);
+    git(&["push", "origin", "v1.0.0"]);
+    git(&["tag", "-d", "v1.0.0"]);
+
+    std::fs::write(repo_path.join("README.md"), "release notes").unwrap();
+    tbdflow()
+        .args([
+            "commit",
+            "-t",
+            "chore",
+            "-m",
+            "release 1.0.0",
+            "--tag",
+            "v1.0.0",
+            "--no-verify",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("Tag 'v1.0.0' already exists on origin"));
+
+    let log = git(&["log", "-1", "--format=%s"]);
+    assert_eq!(String::from_utf8_lossy(&log.stdout).trim(), "init");
+}
+
+/// Tests that a push rejected because trunk moved mid-commit explains how to recover.
+#[test]
+#[serial]
+fn test_commit_on_moved_trunk_gives_recovery_guidance() {
+    let (_dir, bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    // A pre-commit hook that lets a teammate push to main right after our pull.
+    let other = tempfile::tempdir().unwrap();
+    let hook = format!(
+        "#!/bin/sh\n\
+         unset GIT_DIR GIT_INDEX_FILE GIT_WORK_TREE\n\
+         git clone -q -b main {bare} {other}/clone\n\
+         cd {other}/clone\n\
+         git -c user.name=Mate -c user.email=mate@example.com commit -q --allow-empty -m 'feat: teammate'\n\
+         git push -q origin main\n",
+        bare = bare_dir.path().display(),
+        other = other.path().display()
+    );
+    let hook_path = repo_path.join(".git/hooks/pre-commit");
+    std::fs::write(&hook_path, hook).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&hook_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    std::fs::write(repo_path.join("README.md"), "racing").unwrap();
+    tbdflow()
+        .args([
+            "commit",
+            "-t",
+            "fix",
+            "-m",
+            "race the teammate",
+            "--no-verify",
+        ])
+        .assert()
+        .failure()
+        .stdout(contains("moved while you were committing"))
+        .stdout(contains("tbdflow sync"))
+        .stderr(contains("non-fast-forward"));
+}
+
+/// Tests that --from-commit must point at trunk unless --allow-non-main is given.
+#[test]
+#[serial]
+fn test_branch_from_non_main_commit_requires_opt_in() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap()
+    };
+    git(&["checkout", "-b", "unmerged-work"]);
+    std::fs::write(repo_path.join("wip.txt"), "not on main").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-m", "wip"]);
+    git(&["checkout", "main"]);
+
+    tbdflow()
+        .args([
+            "branch",
+            "-t",
+            "feat",
+            "-n",
+            "forked",
+            "-f",
+            "unmerged-work",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("is not on 'origin/main'"))
+        .stderr(contains("--allow-non-main"));
+
+    tbdflow()
+        .args(["branch", "-t", "feat", "-n", "from-trunk", "-f", "main"])
+        .assert()
+        .success();
+
+    git(&["checkout", "main"]);
+    tbdflow()
+        .args([
+            "branch",
+            "-t",
+            "feat",
+            "-n",
+            "forked",
+            "-f",
+            "unmerged-work",
+            "--allow-non-main",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Warning: branching from 'unmerged-work'"));
+}
+
+/// Tests that branch reports its base commit and that --no-sync skips the pull.
+#[test]
+#[serial]
+fn test_branch_reports_base_commit_and_honours_no_sync() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    tbdflow()
+        .args(["branch", "-t", "feat", "-n", "synced"])
+        .assert()
+        .success()
+        .stdout(contains("(main, synced with origin)"));
+
+    std::process::Command::new("git")
+        .args(["checkout", "main"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    tbdflow()
+        .args([
+            "--verbose",
+            "branch",
+            "-t",
+            "feat",
+            "-n",
+            "offline",
+            "--no-sync",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("(local main, not synced)"))
+        .stdout(contains("git pull").not());
+}
+
+/// Tests that branch metadata is recorded and shown by `branch list` and `complete`.
+#[test]
+#[serial]
+fn test_branch_metadata_is_recorded_and_listed() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    tbdflow()
+        .args([
+            "branch",
+            "-t",
+            "feat",
+            "-n",
+            "dashboard",
+            "--issue",
+            "PROJ-7",
+            "--description",
+            "Usage dashboard",
+        ])
+        .assert()
+        .success();
+
+    let description = std::process::Command::new("git")
+        .args(["config", "branch.feat/PROJ-7-dashboard.description"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&description.stdout).trim(),
+        "Usage dashboard"
+    );
+
+    tbdflow()
+        .args(["branch", "list"])
+        .assert()
+        .success()
+        .stdout(contains("feat/PROJ-7-dashboard"))
+        .stdout(contains("feat, PROJ-7 by"))
+        .stdout(contains("\"Usage dashboard\""));
+
+    tbdflow()
+        .args(["--json", "branch", "list"])
+        .assert()
+        .success()
+        .stdout(contains("\"type\": \"feat\""));
+
+    std::process::Command::new("git")
+        .args(["commit", "--allow-empty", "-m", "feat: add dashboard"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    std::process::Command::new("git")
+        .args(["push"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    tbdflow()
+        .args(["complete", "-t", "feat", "-n", "dashboard"])
+        .assert()
+        .success()
+        .stdout(contains("\"Usage dashboard\""));
+
+    let merge_message = std::process::Command::new("git")
+        .args(["log", "-1", "--format=%B"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&merge_message.stdout).trim(),
+        "Merge branch 'feat/PROJ-7-dashboard'\n\nUsage dashboard"
+    );
+}
+
+/// Tests that switch refuses uncommitted changes, parks them with --stash and
+/// restores them when switching back.
+#[test]
+#[serial]
+fn test_switch_parks_and_restores_uncommitted_changes() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    tbdflow()
+        .args(["branch", "-t", "feat", "-n", "parking"])
+        .assert()
+        .success();
+    std::fs::write(repo_path.join("wip.txt"), "half done").unwrap();
+
+    tbdflow()
+        .args(["switch", "main"])
+        .assert()
+        .failure()
+        .stderr(contains("uncommitted changes on 'feat/parking'"))
+        .stderr(contains("--stash"));
+
+    tbdflow()
+        .args(["switch", "--stash"])
+        .assert()
+        .success()
+        .stdout(contains("Parked uncommitted changes from 'feat/parking'"))
+        .stdout(contains("Switched to 'main'."));
+    assert!(!repo_path.join("wip.txt").exists());
+
+    tbdflow()
+        .args(["switch", "feat/parking"])
+        .assert()
+        .success()
+        .stdout(contains("Restored the changes parked on 'feat/parking'"));
+    assert_eq!(
+        std::fs::read_to_string(repo_path.join("wip.txt")).unwrap(),
+        "half done"
+    );
+}
+
+/// Tests that switch warns when the target branch is behind main and rebases it with --yes.
+#[test]
+#[serial]
+fn test_switch_warns_and_offers_sync_when_behind_main() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap()
+    };
+    tbdflow()
+        .args(["branch", "-t", "feat", "-n", "lagging"])
+        .assert()
+        .success();
+    git(&["checkout", "main"]);
+    std::fs::write(repo_path.join("trunk.txt"), "moved on").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-m", "trunk moves"]);
+    git(&["push", "origin", "main"]);
+
+    tbdflow()
+        .args(["switch", "feat/lagging"])
+        .assert()
+        .success()
+        .stdout(contains("is 1 commit(s) behind 'origin/main'"))
+        .stdout(contains("no (non-interactive default)"));
+    assert!(!repo_path.join("trunk.txt").exists());
+
+    git(&["checkout", "main"]);
+    tbdflow()
+        .args(["--yes", "switch", "feat/lagging"])
+        .assert()
+        .success()
+        .stdout(contains("'feat/lagging' is up to date with 'origin/main'"));
+    assert!(repo_path.join("trunk.txt").exists());
+}
+
+/// Tests that --experiment adds a trailer and `experiments` lists the commit.
+#[test]
+#[serial]
+fn test_experiment_trailer_and_listing() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    std::fs::write(repo_path.join("checkout.txt"), "one click").unwrap();
+    tbdflow()
+        .args([
+            "commit",
+            "-t",
+            "feat",
+            "-m",
+            "add one-click checkout",
+            "--no-verify",
+            "--experiment",
+            "EXP-42",
+        ])
+        .assert()
+        .success();
+
+    let body = std::process::Command::new("git")
+        .args(["log", "-1", "--format=%B"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&body.stdout).contains("Experiment: EXP-42"));
+
+    tbdflow()
+        .arg("experiments")
+        .assert()
+        .success()
+        .stdout(contains("EXP-42"))
+        .stdout(contains("feat: add one-click checkout"));
+
+    tbdflow()
+        .args(["experiments", "EXP-7"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "No commits on 'origin/main' for experiment 'EXP-7'.",
+        ));
+}
+
+/// Tests that messages follow `locale:` in config and TBDFLOW_LANG, and only
+/// follow the system locale when asked to.
+#[test]
+#[serial]
+fn test_messages_are_localised() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    tbdflow()
+        .env_remove("TBDFLOW_LANG")
+        .env("LANG", "sv_SE.UTF-8")
+        .args(["commit", "-t", "fix", "-m", "nothing", "--no-verify"])
+        .assert()
+        .success()
+        .stdout(contains("No changes added to commit."));
+    tbdflow()
+        .env("TBDFLOW_LANG", "system")
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env("LANG", "sv_SE.UTF-8")
+        .args(["commit", "-t", "fix", "-m", "nothing", "--no-verify"])
+        .assert()
+        .success()
+        .stdout(contains("Inga ändringar att committa."));
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+locale: sv
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    for args in [vec!["add", "."], vec!["commit", "-qm", "chore: set locale"]] {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+    }
+
+    tbdflow()
+        .env_remove("TBDFLOW_LANG")
+        .args(["commit", "-t", "fix", "-m", "nothing", "--no-verify"])
+        .assert()
+        .success()
+        .stdout(contains("Inga ändringar att committa."));
+    tbdflow()
+        .env("TBDFLOW_LANG", "en")
+        .args(["commit", "-t", "fix", "-m", "nothing", "--no-verify"])
+        .assert()
+        .success()
+        .stdout(contains("No changes added to commit."));
+}
+
+/// Tests that --ascii and TBDFLOW_ASCII strip emojis from output, but never
+/// from commit text.
+#[test]
+#[serial]
+fn test_ascii_output_strips_emojis() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    std::fs::write(repo_path.join("parser.rs"), "parser").unwrap();
+    tbdflow()
+        .args(["commit", "-t", "feat", "-m", "add parser", "--no-verify"])
+        .assert()
+        .success();
+    std::fs::write(repo_path.join("menu.rs"), "menu").unwrap();
+    tbdflow()
+        .args([
+            "commit",
+            "-t",
+            "feat",
+            "-m",
+            "show Grüße and 日本語 on the menu 🎉",
+            "--no-verify",
+        ])
+        .assert()
+        .success();
+
+    tbdflow()
+        .args(["changelog", "--from", "HEAD~2"])
+        .assert()
+        .success()
+        .stdout(contains("### ✨ Features"));
+    tbdflow()
+        .args(["--ascii", "changelog", "--from", "HEAD~2"])
+        .assert()
+        .success()
+        .stdout(contains("### Features"))
+        .stdout(contains("✨").not())
+        .stdout(contains("show Grüße and 日本語 on the menu 🎉"));
+    tbdflow()
+        .env("TBDFLOW_ASCII", "1")
+        .args(["changelog", "--from", "HEAD~2"])
+        .assert()
+        .success()
+        .stdout(contains("✨").not());
+}
+
+/// Tests that --fixes-review adds a trailer pointing at the reviewed commit.
+#[test]
+#[serial]
+fn test_fixes_review_trailer_links_fix_to_review() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let head = std::process::Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    let reviewed = String::from_utf8_lossy(&head.stdout).trim().to_string();
+
+    std::fs::write(repo_path.join("token.txt"), "handled").unwrap();
+    tbdflow()
+        .args([
+            "commit",
+            "-t",
+            "fix",
+            "-m",
+            "handle empty token",
+            "--no-verify",
+            "--fixes-review",
+            &reviewed,
+        ])
+        .assert()
+        .success()
+        .stdout(contains("recorded in the commit trailer"));
+
+    let body = std::process::Command::new("git")
+        .args(["log", "-1", "--format=%B"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    assert!(
+        String::from_utf8_lossy(&body.stdout)
+            .contains(&format!("Fixes-Review: {}", &reviewed[..7]))
+    );
+
+    tbdflow()
+        .args([
+            "commit",
+            "-t",
+            "fix",
+            "-m",
+            "nothing",
+            "--no-verify",
+            "--fixes-review",
+            "deadbeef",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("commit 'deadbeef' not found"));
+}
+
+/// Tests that DoD items with a command are checked off by running it.
+#[test]
+#[serial]
+fn test_dod_command_items_are_checked_automatically() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    std::fs::write(
+        repo_path.join(".dod.yml"),
+        "checklist:\n  - item: \"Build passes\"\n    command: \"exit 0\"\n",
+    )
+    .unwrap();
+    tbdflow()
+        .args(["commit", "-t", "chore", "-m", "add dod"])
+        .assert()
+        .success()
+        .stdout(contains("Checking 'Build passes' (exit 0)... passed"))
+        .stdout(contains("TODO:").not());
+
+    std::fs::write(
+        repo_path.join(".dod.yml"),
+        "checklist:\n  - item: \"Build passes\"\n    command: \"echo boom && exit 1\"\n",
+    )
+    .unwrap();
+    tbdflow()
+        .args(["--yes", "commit", "-t", "chore", "-m", "break the build"])
+        .assert()
+        .success()
+        .stdout(contains("failed"))
+        .stdout(contains("boom"))
+        .stdout(contains("- [ ] Build passes"));
+}
+
+/// Tests that --no-verify with a DoD checklist is journaled and reported.
+#[test]
+#[serial]
+fn test_dod_skips_are_journaled_and_reported() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    tbdflow()
+        .args(["dod", "report"])
+        .assert()
+        .success()
+        .stdout(contains("Nobody skipped the DoD checklist."));
+
+    std::fs::write(
+        repo_path.join(".dod.yml"),
+        "checklist:\n  - \"Tests pass\"\n",
+    )
+    .unwrap();
+    tbdflow()
+        .args(["commit", "-t", "chore", "-m", "add dod", "--no-verify"])
+        .assert()
+        .success();
+
+    let journal = std::fs::read_to_string(repo_path.join(".git/tbdflow/journal.jsonl")).unwrap();
+    assert!(journal.contains("\"event\":\"dod-skipped\""));
+    assert!(journal.contains("\"branch\":\"main\""));
+
+    tbdflow()
+        .args(["dod", "report", "--days", "7"])
+        .assert()
+        .success()
+        .stdout(contains("DoD skips (last 7 days)"))
+        .stdout(contains("Test <test@example.com>"));
+}
+
+/// Tests that status shows review obligations, served from the cache in .git/.
+#[test]
+#[serial]
+fn test_status_shows_cached_review_obligations() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+review:
+  enabled: true
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    let cache = format!(
+        r#"{{"fetched_at":"{}","obligations":{{"pending_assigned":2,"concerns_on_own_commits":1}}}}"#,
+        chrono::Utc::now().to_rfc3339()
+    );
+    // Written where older releases kept the cache, so it is also migrated.
+    std::fs::write(repo_path.join(".git/tbdflow-review-status.json"), cache).unwrap();
+
+    tbdflow().arg("status").assert().success().stdout(contains(
+        "Reviews: 2 pending assigned to you, 1 concern on your commits",
+    ));
+
+    tbdflow()
+        .args(["--json", "status"])
+        .assert()
+        .success()
+        .stdout(contains("\"pending_assigned\": 2"));
+}
+
+/// Tests that --profile and TBDFLOW_PROFILE apply a named profile over the base config.
+#[test]
+#[serial]
+fn test_config_profiles_are_selected_per_invocation() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+profiles:
+  solo:
+    commit:
+      dod: false
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    std::fs::write(repo_path.join(".dod.yml"), "checklist:\n  - Tests pass\n").unwrap();
+    std::fs::write(repo_path.join("a.txt"), "a").unwrap();
+
+    // The base config runs the checklist, which cannot be answered here.
+    tbdflow()
+        .args(["commit", "-t", "feat", "-m", "add a"])
+        .assert()
+        .failure();
+
+    tbdflow()
+        .args(["--profile", "solo", "commit", "-t", "feat", "-m", "add a"])
+        .assert()
+        .success();
+
+    tbdflow()
+        .arg("info")
+        .env("TBDFLOW_PROFILE", "solo")
+        .assert()
+        .success()
+        .stdout(contains("Profile: solo"));
+
+    tbdflow()
+        .args(["--profile", "team", "info"])
+        .assert()
+        .failure()
+        .stderr(contains("Unknown profile 'team' (available: solo)."));
+}
+
+/// Tests that `extends:` merges a shared base config pinned by .tbdflow.lock.
+#[test]
+#[serial]
+fn test_extends_pins_shared_config_in_lock_file() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    std::fs::create_dir_all(repo_path.join("shared")).unwrap();
+    let base = r#"
+main_branch_name: main
+stale_branch_threshold_days: 5
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+"#;
+    std::fs::write(repo_path.join("shared/base.yml"), base).unwrap();
+    std::fs::write(
+        repo_path.join(".tbdflow.yml"),
+        "extends: shared/base.yml\nlog_display_count: 3\n",
+    )
+    .unwrap();
+
+    // Reading the config never writes the lock into the work tree.
+    tbdflow()
+        .args(["--json", "info"])
+        .assert()
+        .success()
+        .stderr(contains("shared config shared/base.yml is not pinned"))
+        .stdout(contains("\"stale_branch_threshold_days\": 5"))
+        .stdout(contains("\"log_display_count\": 3"));
+    assert!(!repo_path.join(".tbdflow.lock").exists());
+    tbdflow()
+        .args(["--dry-run", "info", "--update-lock"])
+        .assert()
+        .success()
+        .stdout(contains("[DRY RUN] Would lock shared/base.yml"));
+    assert!(!repo_path.join(".tbdflow.lock").exists());
+
+    tbdflow()
+        .args(["info", "--update-lock"])
+        .assert()
+        .success()
+        .stdout(contains("Updated .tbdflow.lock"));
+    assert!(repo_path.join(".tbdflow.lock").exists());
+
+    std::fs::write(
+        repo_path.join("shared/base.yml"),
+        base.replace("days: 5", "days: 9"),
+    )
+    .unwrap();
+    tbdflow()
+        .arg("info")
+        .assert()
+        .failure()
+        .stderr(contains("has changed upstream"));
+
+    tbdflow()
+        .args(["info", "--update-lock"])
+        .assert()
+        .success()
+        .stdout(contains("Updated .tbdflow.lock"));
+
+    tbdflow()
+        .args(["--json", "info"])
+        .assert()
+        .success()
+        .stdout(contains("\"stale_branch_threshold_days\": 9"));
+}
+
+/// Tests that lint.branches relaxes rules on feature branches but not on main.
+#[test]
+#[serial]
+fn test_lint_overrides_per_branch_type() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+lint:
+  conventional_commit_type:
+    enabled: true
+    allowed_types: [ "feat", "fix" ]
+  branches:
+    feat:
+      conventional_commit_type:
+        allowed_types: [ "feat", "fix", "wip" ]
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    std::fs::write(repo_path.join("a.txt"), "a").unwrap();
+
+    tbdflow()
+        .args(["commit", "-t", "wip", "-m", "half done"])
+        .assert()
+        .failure()
+        .stdout(contains("'wip' is not a valid Conventional Commit type."));
+
+    std::process::Command::new("git")
+        .args(["checkout", "-b", "feat/search"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    tbdflow()
+        .args(["commit", "-t", "wip", "-m", "half done"])
+        .assert()
+        .success();
+}
+
+/// Tests that init on an existing repo proposes a config matching its history.
+#[test]
+#[serial]
+fn test_init_adopts_existing_conventions() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+    };
+
+    git(&["branch", "-m", "main", "master"]);
+    for (i, subject) in [
+        "feat(API): Add users endpoint",
+        "feat(API): Add paging",
+        "fix(UI): Handle empty list",
+        "fix: Correct typo",
+    ]
+    .iter()
+    .enumerate()
+    {
+        std::fs::write(repo_path.join(format!("f{}.txt", i)), subject).unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", subject]);
+    }
+    git(&["tag", "release-1.0.0"]);
+
+    tbdflow()
+        .args(["--yes", "init"])
+        .assert()
+        .success()
+        .stdout(contains("Detected conventions from the last 5 commits:"))
+        .stdout(contains("Main branch: master"))
+        .stdout(contains("Tag prefix: release-"))
+        .stdout(contains(
+            "Created .tbdflow.yml matching the repository's conventions.",
+        ));
+
+    let config = std::fs::read_to_string(repo_path.join(".tbdflow.yml")).unwrap();
+    assert!(config.contains("main_branch_name: master"));
+    assert!(config.contains("release_prefix: release-"));
+    assert!(config.contains("enforce_lowercase: false"));
+    let parsed: tbdflow::config::Config = yaml_serde::from_str(&config).unwrap();
+    let allowed = parsed
+        .lint
+        .and_then(|l| l.conventional_commit_type)
+        .and_then(|c| c.allowed_types)
+        .unwrap();
+    assert_eq!(allowed, vec!["feat".to_string(), "fix".to_string()]);
+
+    let gitignore = std::fs::read_to_string(repo_path.join(".gitignore")).unwrap();
+    assert!(gitignore.contains(".tbdflow.local.yml"), "{}", gitignore);
+}
+
+/// Tests that init and doctor notice commitlint running alongside tbdflow's linting.
+#[test]
+#[serial]
+fn test_commitlint_double_linting_is_detected() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    std::fs::create_dir_all(repo_path.join(".husky")).unwrap();
+    std::fs::write(
+        repo_path.join(".husky/commit-msg"),
+        "npx --no -- commitlint --edit $1\n",
+    )
+    .unwrap();
+    std::fs::write(repo_path.join("commitlint.config.js"), "").unwrap();
+
+    tbdflow()
+        .args(["--yes", "init"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "Found commitlint (commitlint.config.js), husky hooks: commit-msg.",
+        ))
+        .stdout(contains("Keeping both linters."));
+
+    tbdflow()
+        .arg("doctor")
+        .assert()
+        .stdout(contains(
+            "[warn] commit hooks: commitlint (commitlint.config.js), husky hooks: commit-msg and tbdflow both lint commit messages",
+        ));
+}
+
+/// Tests that `postmortem` writes a timeline for a revert and rejects other commits.
+#[test]
+#[serial]
+fn test_postmortem_summarises_a_revert() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    std::fs::write(repo_path.join("cache.rs"), "cache").unwrap();
+    tbdflow()
+        .args(["commit", "-t", "feat", "-m", "add cache", "--no-verify"])
+        .assert()
+        .success();
+    std::process::Command::new("git")
+        .args(["revert", "--no-edit", "HEAD"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+
+    let offline = || {
+        let mut cmd = tbdflow();
+        cmd.env("PATH", "/usr/bin:/bin");
+        cmd
+    };
+    offline()
+        .args(["postmortem", "HEAD"])
+        .assert()
+        .success()
+        .stdout(contains("# Incident summary: feat: add cache"))
+        .stdout(contains("(revert) |"))
+        .stdout(contains("| Review | Unknown (GitHub not available) |"))
+        .stdout(contains("Original change"))
+        .stdout(contains("Resolved by revert"));
+
+    offline()
+        .args(["postmortem", "HEAD", "-o", "incident.md"])
+        .assert()
+        .success()
+        .stdout(contains("Postmortem written to incident.md"));
+    let written = std::fs::read_to_string(repo_path.join("incident.md")).unwrap();
+    assert!(written.contains("## Timeline"));
+
+    offline()
+        .args(["postmortem", "HEAD~1"])
+        .assert()
+        .failure()
+        .stderr(contains("is neither a revert nor a fix-forward"));
+}
+
+/// Tests that `audit` flags trunk commits made with raw git.
+#[test]
+#[serial]
+fn test_audit_flags_commits_that_bypassed_tbdflow() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    std::fs::write(repo_path.join("cache.rs"), "cache").unwrap();
+    tbdflow()
+        .args(["note", "trying an lru cache"])
+        .assert()
+        .success();
+    tbdflow()
+        .args(["commit", "-t", "feat", "-m", "add cache", "--no-verify"])
+        .assert()
+        .success();
+
+    std::fs::write(repo_path.join("hack.rs"), "hack").unwrap();
+    for args in [
+        vec!["add", "hack.rs"],
+        vec!["commit", "-m", "quick hack"],
+        vec!["push", "origin", "main"],
+    ] {
+        std::process::Command::new("git")
+            .args(&args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+    }
+
+    tbdflow()
+        .args(["audit"])
+        .assert()
+        .success()
+        .stdout(contains("--- Trunk audit for 'origin/main'"))
+        .stdout(contains("Commits that bypassed tbdflow:"))
+        .stdout(contains("quick hack"))
+        .stdout(is_match(r"\d+ commit\(s\): 1 made with tbdflow").unwrap());
+
+    tbdflow()
+        .args(["--json", "audit"])
+        .assert()
+        .success()
+        .stdout(contains("\"adoption_percent\""))
+        .stdout(contains("\"provenance\": \"manual\""));
+}
+
+/// Tests that `commit.version_trailer` stamps commits and `audit` counts them as tool-made.
+#[test]
+#[serial]
+fn test_version_trailer_marks_tool_made_commits() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+commit:
+  version_trailer: true
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    tbdflow()
+        .args([
+            "commit",
+            "-t",
+            "chore",
+            "-m",
+            "stamp commits",
+            "--no-verify",
+        ])
+        .assert()
+        .success()
+        .stdout(contains(format!(
+            "Tbdflow-Version: {}",
+            env!("CARGO_PKG_VERSION")
+        )));
+
+    let body = std::process::Command::new("git")
+        .args([
+            "log",
+            "-1",
+            "--format=%(trailers:key=Tbdflow-Version,valueonly)",
+        ])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&body.stdout).trim(),
+        env!("CARGO_PKG_VERSION")
+    );
+
+    tbdflow()
+        .args(["audit"])
+        .assert()
+        .success()
+        .stdout(is_match(r"\d+ commit\(s\): 1 made with tbdflow").unwrap());
+}
+
+/// Tests that `target set` makes commit treat a release branch as trunk until reset,
+/// and leaves other commands on the configured main.
+#[test]
+#[serial]
+fn test_target_set_treats_release_branch_as_trunk() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    for args in [
+        vec!["checkout", "-b", "release_2.0"],
+        vec!["push", "-u", "origin", "release_2.0"],
+    ] {
+        std::process::Command::new("git")
+            .args(&args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+    }
+
+    tbdflow()
+        .args(["target", "set", "release_9.9"])
+        .assert()
+        .failure()
+        .stderr(contains("Branch 'release_9.9' does not exist"));
+    tbdflow()
+        .args(["target", "set", "release_2.0"])
+        .assert()
+        .success()
+        .stdout(contains("Trunk target set to 'release_2.0'."));
+
+    std::fs::write(repo_path.join("stabilise.rs"), "fix").unwrap();
+    tbdflow()
+        .args([
+            "commit",
+            "-t",
+            "fix",
+            "-m",
+            "stabilise release",
+            "--no-verify",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Trunk target: 'release_2.0' (instead of 'main'"))
+        .stdout(contains("Committing directly to main branch"));
+    let remote_log = std::process::Command::new("git")
+        .args(["log", "-1", "--format=%s", "origin/release_2.0"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&remote_log.stdout).trim(),
+        "fix: stabilise release"
+    );
+    // Commands other than commit, sync and branch keep the configured main.
+    tbdflow()
+        .args(["status", "--json"])
+        .assert()
+        .success()
+        .stdout(contains("\"is_main\": false"))
+        .stdout(contains("Trunk target").not());
+
+    tbdflow()
+        .args(["target", "show"])
+        .assert()
+        .success()
+        .stdout(contains("Trunk target: 'release_2.0'"));
+    tbdflow()
+        .args(["target", "reset"])
+        .assert()
+        .success()
+        .stdout(contains("'main' is trunk again"));
+    tbdflow()
+        .args(["target", "show"])
+        .assert()
+        .success()
+        .stdout(contains("No trunk target set; 'main' is trunk."));
+}
+
+/// Tests that `completion install` writes the script where the shell loads it
+/// and only touches rc files after consent.
+#[test]
+#[serial]
+fn test_completion_install_writes_script_and_asks_before_rc_edits() {
+    let home = tempfile::tempdir().unwrap();
+
+    tbdflow()
+        .env("HOME", home.path())
+        .env_remove("XDG_DATA_HOME")
+        .args(["completion", "install", "bash"])
+        .assert()
+        .success()
+        .stdout(contains("Wrote completions to"));
+    let script = home
+        .path()
+        .join(".local/share/bash-completion/completions/tbdflow");
+    assert!(
+        std::fs::read_to_string(&script)
+            .unwrap()
+            .contains("_tbdflow")
+    );
+
+    // Without a terminal the rc file is left alone and the line is printed.
+    tbdflow()
+        .env("HOME", home.path())
+        .env_remove("ZDOTDIR")
+        .args(["completion", "install", "zsh"])
+        .assert()
+        .success()
+        .stdout(contains("To load completions, add:"));
+    assert!(home.path().join(".zfunc/_tbdflow").exists());
+    assert!(!home.path().join(".zshrc").exists());
+
+    tbdflow()
+        .env("HOME", home.path())
+        .env_remove("ZDOTDIR")
+        .args(["--yes", "completion", "install", "zsh"])
+        .assert()
+        .success();
+    let zshrc = std::fs::read_to_string(home.path().join(".zshrc")).unwrap();
+    assert!(zshrc.contains("fpath=(~/.zfunc $fpath)"));
+
+    tbdflow()
+        .args(["completion", "fish"])
+        .assert()
+        .success()
+        .stdout(contains("complete -c tbdflow"));
+}
+
+/// Tests that `hooks install` honours core.hooksPath, chains to the existing
+/// hook, and that `hooks uninstall` restores it.
+#[test]
+#[serial]
+fn test_hooks_install_chains_and_uninstall_restores() {
+    let (_dir, _remote, repo_path) = setup_temp_git_repo();
+    let hooks_dir = repo_path.join("team-hooks");
+    std::fs::create_dir_all(&hooks_dir).unwrap();
+    let previous = "#!/bin/sh\necho previous hook\n";
+    std::fs::write(hooks_dir.join("commit-msg"), previous).unwrap();
+    std::process::Command::new("git")
+        .args(["config", "core.hooksPath", "team-hooks"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["hooks", "install"])
+        .assert()
+        .success()
+        .stdout(contains("core.hooksPath is set"))
+        .stdout(contains("commit-msg.pre-tbdflow"));
+    let hook = std::fs::read_to_string(hooks_dir.join("commit-msg")).unwrap();
+    assert!(hook.contains("tbdflow verify-commit-msg"));
+    assert!(hook.contains("commit-msg.pre-tbdflow"));
+    assert_eq!(
+        std::fs::read_to_string(hooks_dir.join("commit-msg.pre-tbdflow")).unwrap(),
+        previous
+    );
+    assert!(repo_path.join(".git/tbdflow/hooks.json").exists());
+
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["hooks", "uninstall"])
+        .assert()
+        .success()
+        .stdout(contains("Restored"));
+    assert_eq!(
+        std::fs::read_to_string(hooks_dir.join("commit-msg")).unwrap(),
+        previous
+    );
+    assert!(!hooks_dir.join("commit-msg.pre-tbdflow").exists());
+    assert!(!repo_path.join(".git/tbdflow/hooks.json").exists());
+}
+
+/// Tests the documented exit codes of `verify-commit-msg`.
+#[test]
+#[serial]
+fn test_verify_commit_msg_exit_codes() {
+    let (_dir, _remote, repo_path) = setup_temp_git_repo();
+    let message = repo_path.join("COMMIT_EDITMSG");
+
+    std::fs::write(
+        &message,
+        "feat: add cache\n\n# Please enter the commit message\n",
+    )
+    .unwrap();
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["verify-commit-msg", "COMMIT_EDITMSG"])
+        .assert()
+        .code(0)
+        .stderr("");
+
+    std::fs::write(&message, "Merge branch 'main' into feat/x\n").unwrap();
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["verify-commit-msg", "COMMIT_EDITMSG"])
+        .assert()
+        .code(0);
+
+    std::fs::write(&message, "quick fix\n").unwrap();
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["verify-commit-msg", "COMMIT_EDITMSG"])
+        .assert()
+        .code(1)
+        .stderr(contains("tbdflow: [conventional_format]"));
+
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["verify-commit-msg", "missing-file"])
+        .assert()
+        .code(2)
+        .stderr(contains("cannot read commit message file"));
+
+    std::fs::write(repo_path.join(".tbdflow.yml"), "lint: [unclosed\n").unwrap();
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["verify-commit-msg", "COMMIT_EDITMSG"])
+        .assert()
+        .code(3)
+        .stderr(contains("invalid .tbdflow.yml"));
+}
+
+/// Tests that lint failures carry positions and fixes, and that `--fix`
+/// applies them in `verify-commit-msg` and `commit`.
+#[test]
+#[serial]
+fn test_lint_fix_corrects_subject() {
+    let (_dir, _remote, repo_path) = setup_temp_git_repo();
+    let message = repo_path.join("COMMIT_EDITMSG");
+
+    std::fs::write(&message, "feat(UI): Align button.\n\n# comment\n").unwrap();
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["verify-commit-msg", "COMMIT_EDITMSG"])
+        .assert()
+        .code(1)
+        .stderr(contains(
+            "COMMIT_EDITMSG:1:11: tbdflow: [subject_line_rules] Subject line must not start with a capital letter. field=subject text=\"Align button.\" fix=\"align button\"",
+        ))
+        .stderr(contains("verify-commit-msg --fix"));
+
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["verify-commit-msg", "--fix", "COMMIT_EDITMSG"])
+        .assert()
+        .code(0)
+        .stderr(contains("tbdflow: fixed [scope]"));
+    assert_eq!(
+        std::fs::read_to_string(&message).unwrap(),
+        "feat(ui): align button\n\n# comment\n"
+    );
+
+    std::fs::write(repo_path.join("button.css"), "button {}").unwrap();
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["commit", "-t", "fix", "-m", "Align button.", "--no-verify"])
+        .assert()
+        .failure()
+        .stdout(contains(
+            "Run again with --fix to commit it as 'align button'",
+        ));
+    tbdflow()
+        .current_dir(&repo_path)
+        .args([
+            "commit",
+            "-t",
+            "fix",
+            "-m",
+            "Align button.",
+            "--no-verify",
+            "--fix",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Fixed subject: 'Align button.' -> 'align button'"));
+    let subject = std::process::Command::new("git")
+        .args(["log", "-1", "--format=%s"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&subject.stdout).trim(),
+        "fix: align button"
+    );
+}
+
+/// Tests that `complete` renders the `complete.merge_message` template.
+#[test]
+#[serial]
+fn test_complete_uses_merge_message_template() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+complete:
+  merge_message: |
+    {{type}}: merge {{name}} ({{commit_count}} commits)
+
+    {{description}}
+
+    {{subjects}}
+
+    Refs: {{issue}}
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    for args in [
+        vec!["add", ".tbdflow.yml"],
+        vec!["commit", "-m", "chore: add tbdflow config"],
+        vec!["push"],
+    ] {
+        std::process::Command::new("git")
+            .args(&args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+    }
+
+    tbdflow()
+        .args([
+            "branch",
+            "-t",
+            "feat",
+            "-n",
+            "charts",
+            "--description",
+            "Usage charts",
+        ])
+        .assert()
+        .success();
+    for subject in ["feat: add chart", "fix: axis labels"] {
+        std::process::Command::new("git")
+            .args(["commit", "--allow-empty", "-m", subject])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+    }
+    std::process::Command::new("git")
+        .args(["push"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+
+    tbdflow()
+        .args(["complete", "-t", "feat", "-n", "charts"])
+        .assert()
+        .success();
+
+    let merge_message = std::process::Command::new("git")
+        .args(["log", "-1", "--format=%B"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&merge_message.stdout).trim(),
+        "feat: merge charts (2 commits)\n\nUsage charts\n\n- feat: add chart\n- fix: axis labels"
+    );
+}
+
+/// Tests that `complete` rejects an unknown --type up front, suggesting the
+/// closest type and the type an existing branch with that name uses.
+#[test]
+#[serial]
+fn test_complete_validates_type_before_looking_for_branch() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    std::process::Command::new("git")
+        .args(["branch", "feat/login"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+
+    tbdflow()
+        .args(["complete", "--type", "fet", "--name", "login"])
+        .assert()
+        .failure()
+        .stderr(contains("Invalid branch type 'fet'. Allowed types are: chore, ci, docs"))
+        .stderr(contains("Did you mean 'feat'?"))
+        .stderr(contains(
+            "A branch named 'login' exists with type feat. Run 'tbdflow complete --type feat --name login'.",
+        ));
+}
+
+#[test]
+#[serial]
+fn test_complete_asks_before_merging_open_todos() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    tbdflow()
+        .args(["branch", "-t", "feat", "-n", "todos"])
+        .assert()
+        .success();
+    for args in [
+        vec![
+            "commit",
+            "--allow-empty",
+            "-m",
+            "feat: half done\n\nTODO:\n- [ ] Tests\n- [x] Docs",
+        ],
+        vec!["push"],
+    ] {
+        std::process::Command::new("git")
+            .args(&args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+    }
+
+    // Without a terminal the prompt defaults to no.
+    tbdflow()
+        .args(["complete", "-t", "feat", "-n", "todos"])
+        .assert()
+        .failure()
+        .stdout(contains("1 open Definition of Done item(s)"))
+        .stdout(contains("- [ ] Tests"))
+        .stdout(contains("- [ ] Docs").not())
+        .stderr(contains("--accept-todos"));
+
+    let branch = std::process::Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&branch.stdout).trim(), "feat/todos");
+
+    tbdflow()
+        .args(["complete", "-t", "feat", "-n", "todos", "--accept-todos"])
+        .assert()
+        .success()
+        .stdout(contains("Merging anyway"));
+}
+
+#[test]
+#[serial]
+fn test_sync_predicts_conflicts_before_rebasing() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap()
+    };
+
+    std::fs::write(repo_path.join("shared.txt"), "base\n").unwrap();
+    git(&["add", "shared.txt"]);
+    git(&["commit", "-m", "chore: add shared file"]);
+    git(&["push"]);
+
+    git(&["checkout", "-b", "feat/mine"]);
+    std::fs::write(repo_path.join("shared.txt"), "mine\n").unwrap();
+    git(&["commit", "-am", "feat: my change"]);
+
+    git(&["checkout", "main"]);
+    std::fs::write(repo_path.join("shared.txt"), "theirs\n").unwrap();
+    git(&["commit", "-am", "feat: their change"]);
+    git(&["push"]);
+    git(&["checkout", "feat/mine"]);
+
+    // Without a terminal the prompt defaults to no, so no rebase is started.
+    tbdflow()
+        .arg("sync")
+        .assert()
+        .success()
+        .stdout(contains("1 file(s) will conflict: shared.txt"))
+        .stdout(contains("Sync aborted."));
+    assert!(!repo_path.join(".git/rebase-merge").exists());
+
+    tbdflow()
+        .args(["--json", "sync"])
+        .assert()
+        .success()
+        .stdout(contains("\"code\": \"conflicts_predicted\""));
+}
+
+#[test]
+#[serial]
+fn test_metrics_integration_reports_per_author() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let now = Utc::now();
+    for (author, days_ago) in [("Ada", 3), ("Ada", 1), ("Bob", 2)] {
+        let date = (now - Duration::days(days_ago)).to_rfc3339();
+        std::process::Command::new("git")
+            .args([
+                "commit",
+                "--allow-empty",
+                "-m",
+                "chore: integrate",
+                "--author",
+                &format!("{} <{}@example.com>", author, author.to_lowercase()),
+            ])
+            .env("GIT_AUTHOR_DATE", &date)
+            .env("GIT_COMMITTER_DATE", &date)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+    }
+    std::process::Command::new("git")
+        .args(["push"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+
+    tbdflow()
+        .args(["metrics", "integration", "--since", "30d"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "Integration frequency on 'origin/main' (since 30d)",
+        ))
+        .stdout(is_match(r"Ada\s+2\s+2d 0h\s+2d 0h").unwrap());
+
+    tbdflow()
+        .args(["--json", "metrics", "integration", "--since", "30d"])
+        .assert()
+        .success()
+        .stdout(contains("\"author\": \"Bob\""))
+        .stdout(contains("\"longest_gap_seconds\": 172800"));
+}
+
+#[test]
+#[serial]
+fn test_snippet_lists_matching_trunk_messages() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    for message in [
+        "chore(deps): bump serde",
+        "feat: add charts",
+        "chore(deps): bump serde",
+        "chore(deps): bump clap\n\nWeekly dependency batch.",
+    ] {
+        std::process::Command::new("git")
+            .args(["commit", "--allow-empty", "-m", message])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+    }
+    std::process::Command::new("git")
+        .args(["push"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+
+    // Without a terminal the matches are listed instead of opening the wizard.
+    tbdflow()
+        .args(["snippet", "deps", "bump"])
+        .assert()
+        .success()
+        .stdout(contains("chore(deps): bump clap"))
+        .stdout(contains("used 2 times"))
+        .stdout(contains("add charts").not());
+
+    tbdflow()
+        .args(["--json", "snippet", "dependency", "batch"])
+        .assert()
+        .success()
+        .stdout(contains("\"subject\": \"chore(deps): bump clap\""))
+        .stdout(contains("\"uses\": 1"));
+
+    tbdflow()
+        .args(["snippet", "nothing-like-this"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "No commits on 'origin/main' match 'nothing-like-this'.",
+        ));
+}
+
+/// Tests that `git.backend: gix` falls back to the git binary, with a warning, in a default build.
+#[cfg(not(feature = "gix"))]
+#[test]
+#[serial]
+fn test_gix_backend_falls_back_without_the_feature() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+git:
+  backend: gix
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+
+    tbdflow()
+        .arg("status")
+        .assert()
+        .success()
+        .stderr(contains("needs tbdflow built with '--features gix'"));
+}
+
+/// Tests that current-branch, check-branches and changelog emit JSON with --json.
+#[test]
+#[serial]
+fn test_json_output_for_branch_checks_and_changelog() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    std::fs::write(repo_path.join("parser.rs"), "parser").unwrap();
+    tbdflow()
+        .args([
+            "commit",
+            "-t",
+            "feat",
+            "-s",
+            "api",
+            "-m",
+            "add parser",
+            "--no-verify",
+        ])
+        .assert()
+        .success();
+
+    tbdflow()
+        .args(["--json", "current-branch"])
+        .assert()
+        .success()
+        .stdout(contains("\"branch\": \"main\""))
+        .stdout(contains("\"is_main\": true"));
+
+    tbdflow()
+        .args(["--json", "check-branches"])
+        .assert()
+        .success()
+        .stdout(contains("\"stale_branches\": []"));
+
+    tbdflow()
+        .args(["--json", "changelog", "--from", "HEAD~1"])
+        .assert()
+        .success()
+        .stdout(contains("\"header\": \"### ✨ Features\""))
+        .stdout(contains("\"type\": \"feat\""))
+        .stdout(contains("\"scope\": \"api\""))
+        .stdout(contains("\"description\": \"add parser\""));
+
+    tbdflow()
+        .args(["--json", "changelog"])
+        .assert()
+        .success()
+        .stdout(contains("\"code\": \"missing_args\""));
+
+    std::process::Command::new("git")
+        .args(["checkout", "-q", "-b", "feat/x"])
+        .output()
+        .unwrap();
+    tbdflow()
+        .args(["--json", "check-branches"])
+        .assert()
+        .success()
+        .stdout(contains("\"code\": \"not_on_main\""));
+}
+
+/// Tests that commit and sync explain a detached HEAD instead of failing obscurely.
+#[test]
+#[serial]
+fn test_detached_head_is_explained_before_commit_and_sync() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+
+    std::process::Command::new("git")
+        .args(["checkout", "-q", "--detach"])
+        .output()
+        .unwrap();
+    std::fs::write(repo_path.join("a.txt"), "a").unwrap();
+
+    tbdflow()
+        .args(["commit", "-t", "feat", "-m", "add a"])
+        .assert()
+        .failure()
+        .stdout(contains("HEAD is detached at"))
+        .stderr(contains("'tbdflow branch'"));
+
+    tbdflow()
+        .args(["--json", "sync"])
+        .assert()
+        .failure()
+        .stdout(contains("\"code\": \"detached_head\""));
+}
+
+/// Tests that a shallow clone is detected and only deepened with consent.
+#[test]
+#[serial]
+fn test_shallow_clone_is_deepened_only_with_consent() {
+    let (_dir, bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    std::fs::write(repo_path.join("a.txt"), "a").unwrap();
+    tbdflow()
+        .args(["commit", "-t", "feat", "-m", "add a", "--no-verify"])
+        .assert()
+        .success();
+
+    let shallow_dir = tempfile::tempdir().unwrap();
+    let clone_path = shallow_dir.path().join("clone");
+    let status = std::process::Command::new("git")
+        .args(["clone", "-q", "--depth", "1", "--branch", "main"])
+        .arg(format!("file://{}", bare_dir.path().display()))
+        .arg(&clone_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    std::env::set_current_dir(&clone_path).unwrap();
+
+    // Without a terminal the default is to stay shallow, with a warning.
+    tbdflow()
+        .args(["changelog", "--unreleased"])
+        .assert()
+        .failure()
+        .stderr(contains(
+            "This is a shallow clone, so the changelog may be incomplete",
+        ))
+        .stderr(contains("git fetch --unshallow --tags"));
+
+    tbdflow()
+        .args(["--yes", "changelog", "--from", "HEAD~1"])
+        .assert()
+        .success()
+        .stderr(contains("Fetched the full history and tags."))
+        .stdout(contains("add a"));
+
+    tbdflow()
+        .args(["changelog", "--from", "HEAD~1"])
+        .assert()
+        .success()
+        .stderr(contains("shallow").not());
+}
+
+/// Tests that `config set`, `get` and `unset` edit .tbdflow.yml and keep its comments.
+#[test]
+#[serial]
+fn test_config_set_get_unset_keeps_comments() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    let config_content = r#"# Team settings
+main_branch_name: main
+stale_branch_threshold_days: 1
+lint:
+  subject_line_rules:
+    max_length: 72 # agreed in the retro
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+
+    tbdflow()
+        .args(["config", "set", "lint.subject_line_rules.max_length", "50"])
+        .assert()
+        .success();
+    let written = std::fs::read_to_string(repo_path.join(".tbdflow.yml")).unwrap();
+    assert!(written.starts_with("# Team settings\n"));
+    assert!(written.contains("max_length: 50 # agreed in the retro"));
+
+    tbdflow()
+        .args(["config", "get", "lint.subject_line_rules.max_length"])
+        .assert()
+        .success()
+        .stdout("50\n");
+
+    tbdflow()
+        .args(["config", "set", "lint.subject_line_rules.max_lenght", "50"])
+        .assert()
+        .failure()
+        .stderr(contains(
+            "Unknown setting 'lint.subject_line_rules.max_lenght'",
+        ));
+
+    tbdflow()
+        .args(["config", "unset", "lint.subject_line_rules.max_length"])
+        .assert()
+        .success();
+    let written = std::fs::read_to_string(repo_path.join(".tbdflow.yml")).unwrap();
+    assert!(!written.contains("lint:"));
+    assert!(written.contains("# Team settings"));
+
+    tbdflow()
+        .args(["config", "list"])
+        .assert()
+        .success()
+        .stdout(contains("branch_types.feat = feat/"))
+        .stdout(contains("main_branch_name = main"));
+}
+
+/// Tests that `config list` and `config get` never print credentials.
+#[test]
+#[serial]
+fn test_config_output_redacts_credentials() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+review:
+  gitea:
+    url: https://git.example.com
+    token: gitea-s3cret
+  bitbucket:
+    username: alex
+    app_password: hunter2
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+
+    tbdflow()
+        .args(["config", "list"])
+        .assert()
+        .success()
+        .stdout(contains("review.gitea.token = '***'"))
+        .stdout(contains("review.gitea.url = https://git.example.com"))
+        .stdout(contains("review.bitbucket.app_password = '***'"))
+        .stdout(contains("s3cret").not())
+        .stdout(contains("hunter2").not());
+    tbdflow()
+        .args(["--json", "config", "list"])
+        .assert()
+        .success()
+        .stdout(contains("s3cret").not())
+        .stdout(contains("hunter2").not());
+    tbdflow()
+        .args(["config", "get", "review.bitbucket.app_password"])
+        .assert()
+        .success()
+        .stdout("***\n");
+    tbdflow()
+        .args(["config", "get", "review.gitea.token"])
+        .assert()
+        .success()
+        .stdout("***\n");
+    tbdflow()
+        .args(["config", "get", "review.gitea"])
+        .assert()
+        .success()
+        .stdout(contains("s3cret").not());
+}
+
+/// Tests that `changelog --repo` reads a checkout or bare repository elsewhere.
+#[test]
+#[serial]
+fn test_changelog_for_another_repository() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    let config_content = r#"
+main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+changelog:
+  sections:
+    feat: "Shiny things"
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+    std::fs::write(repo_path.join("a.txt"), "a").unwrap();
+    tbdflow()
+        .args(["commit", "-t", "feat", "-m", "add a", "--no-verify"])
+        .assert()
+        .success();
+
+    let elsewhere = tempfile::tempdir().unwrap();
+    let bare_path = elsewhere.path().join("mirror.git");
+    let status = std::process::Command::new("git")
+        .args(["clone", "-q", "--bare"])
+        .arg(&repo_path)
+        .arg(&bare_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    std::env::set_current_dir(elsewhere.path()).unwrap();
+
+    tbdflow()
+        .args(["changelog", "--from", "HEAD~1", "--repo"])
+        .arg(&repo_path)
+        .assert()
+        .success()
+        .stdout(contains("Shiny things"))
+        .stdout(contains("add a"));
+
+    // A bare repository uses the .tbdflow.yml committed on HEAD.
+    tbdflow()
+        .args(["changelog", "--from", "HEAD~1", "--git-dir"])
+        .arg(&bare_path)
+        .assert()
+        .success()
+        .stdout(contains("Shiny things"))
+        .stdout(contains("add a"));
+}
+
+/// Tests that `config validate` reports typos and wrong types with line numbers.
+#[test]
+#[serial]
+fn test_config_validate_reports_problems_with_line_numbers() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    let config_content = r#"main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+
+    tbdflow()
+        .args(["config", "validate"])
+        .assert()
+        .success()
+        .stdout(contains(".tbdflow.yml is valid."));
+
+    let broken = format!(
+        "{}lint:\n  subject_line_rules:\n    max_lenght: 50\nstale_branch_threshold_days_x: 2\n",
+        config_content
+    );
+    std::fs::write(repo_path.join(".tbdflow.yml"), broken).unwrap();
+    tbdflow()
+        .args(["config", "validate"])
+        .assert()
+        .failure()
+        .stdout(contains(
+            ".tbdflow.yml:9: 'lint.subject_line_rules.max_lenght' is not a known setting (did you mean 'max_length'?)",
+        ))
+        .stdout(contains("2 problem(s) found"));
+
+    tbdflow()
+        .args(["--json", "config", "validate"])
+        .assert()
+        .failure()
+        .stdout(contains("\"valid\": false"))
+        .stdout(contains("\"line\": 9"));
+
+    tbdflow()
+        .args(["config", "validate", "--schema"])
+        .assert()
+        .success()
+        .stdout(contains("\"$schema\""))
+        .stdout(contains("\"max_length\""));
+}
+
+/// Tests that `defaults:` become flag defaults and that `config validate` checks them.
+#[test]
+#[serial]
+fn test_config_defaults_become_flag_defaults() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    let config_content = r#"main_branch_name: main
+stale_branch_threshold_days: 1
+branch_types:
+  feat: "feat/"
+automatic_tags:
+  release_prefix: "v"
+defaults:
+  commit:
+    no_verify: true
+"#;
+    std::fs::write(repo_path.join(".tbdflow.yml"), config_content).unwrap();
+
+    tbdflow()
+        .args(["commit", "--help"])
+        .assert()
+        .success()
+        .stdout(contains("[default: true]"));
+    std::fs::write(
+        repo_path.join(".dod.yml"),
+        "checklist:\n  - \"Tests pass\"\n",
+    )
+    .unwrap();
+    std::fs::write(repo_path.join("a.txt"), "a").unwrap();
+    tbdflow()
+        .args(["commit", "-t", "feat", "-m", "add a"])
+        .assert()
+        .success();
+    tbdflow()
+        .args(["dod", "report"])
+        .assert()
+        .success()
+        .stdout(contains("Nobody skipped").not());
+
+    let broken = format!("{}    frobnicate: true\n", config_content);
+    std::fs::write(repo_path.join(".tbdflow.yml"), broken).unwrap();
+    tbdflow()
+        .args(["config", "validate"])
+        .assert()
+        .failure()
+        .stdout(contains(
+            ".tbdflow.yml:10: 'defaults.commit.frobnicate' is not a flag of 'tbdflow commit'",
+        ));
+    tbdflow()
+        .args(["head-sha"])
+        .assert()
+        .success()
+        .stderr(contains("ignoring it"));
+}
+
+/// Tests that `whoami` shows the identity, pair session and forge host, and never a token.
+#[test]
+#[serial]
+fn test_whoami_summarises_identity_and_session() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    std::process::Command::new("git")
+        .args([
+            "remote",
+            "set-url",
+            "origin",
+            "git@github.com:acme/widgets.git",
+        ])
+        .output()
+        .unwrap();
+    tbdflow()
+        .args(["pair", "start", "Bob <bob@example.com>"])
+        .assert()
+        .success();
+
+    tbdflow()
+        .arg("whoami")
+        .env("GH_TOKEN", "s3cret-token")
+        .assert()
+        .success()
+        .stdout(contains("Test <test@example.com>"))
+        .stdout(contains("with Bob <bob@example.com>"))
+        .stdout(contains("github.com"))
+        .stdout(contains("GH_TOKEN set in the environment"))
+        .stdout(contains("s3cret-token").not());
+
+    tbdflow()
+        .args(["--json", "whoami"])
+        .assert()
+        .success()
+        .stdout(contains("\"forge_host\": \"github.com\""))
+        .stdout(contains("\"signing\""));
+}
+
+/// Tests the config layers: `.tbdflow.local.yml` > `.tbdflow.yml` > user
+/// config > defaults.
+#[test]
+#[serial]
+fn test_user_and_local_config_layers() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    let config_home = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(config_home.path().join("tbdflow")).unwrap();
+    std::fs::write(
+        config_home.path().join("tbdflow/config.yml"),
+        "stale_branch_threshold_days: 9\nlog_display_count: 3\nlocale: sv\n",
+    )
+    .unwrap();
+    std::fs::write(
+        repo_path.join(".tbdflow.yml"),
+        "main_branch_name: main\nstale_branch_threshold_days: 1\nbranch_types:\n  feat: \"feat/\"\nautomatic_tags:\n  release_prefix: \"v\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        repo_path.join(".tbdflow.local.yml"),
+        "log_display_count: 4\ncommit:\n  dod: false\n",
+    )
+    .unwrap();
+
+    let get = |key: &str| {
+        let output = tbdflow()
+            .current_dir(&repo_path)
+            .env("XDG_CONFIG_HOME", config_home.path())
+            .args(["config", "get", key])
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    assert_eq!(get("stale_branch_threshold_days"), "1");
+    assert_eq!(get("log_display_count"), "4");
+    assert_eq!(get("locale"), "sv");
+    assert_eq!(get("commit.dod"), "false");
+
+    tbdflow()
+        .current_dir(&repo_path)
+        .env("XDG_CONFIG_HOME", config_home.path())
+        .arg("info")
+        .assert()
+        .success()
+        .stdout(contains("User config:"))
+        .stdout(contains("Local overrides:"));
+}
+
+/// Tests that a commit reverted in the same range is listed once, under the
+/// reverted header, and its revert is left out.
+#[test]
+#[serial]
+fn test_changelog_lists_reverted_commits_once() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    let git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    let base = git(&["rev-parse", "HEAD"]);
+    std::fs::write(repo_path.join("cache.rs"), "cache").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "feat: add cache"]);
+    let feat = git(&["rev-parse", "HEAD"]);
+    std::fs::write(repo_path.join("upload.rs"), "upload").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "fix: retry uploads"]);
+    git(&["revert", "--no-edit", &feat]);
+
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["changelog", "--from", &base])
+        .assert()
+        .success()
+        .stdout(contains("Reverted"))
+        .stdout(contains(format!("- add cache [`{}`]", &feat[..7])))
+        .stdout(contains("(reverted in `"))
+        .stdout(contains("retry uploads"))
+        .stdout(contains("Features").not());
+
+    std::fs::write(
+        repo_path.join(".tbdflow.yml"),
+        "main_branch_name: main\nstale_branch_threshold_days: 1\nbranch_types:\n  feat: \"feat/\"\nautomatic_tags:\n  release_prefix: \"v\"\nchangelog:\n  reverted_header: \"\"\n",
+    )
+    .unwrap();
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["changelog", "--from", &base])
+        .assert()
+        .success()
+        .stdout(contains("add cache").not())
+        .stdout(contains("retry uploads"));
+}
+
+/// Tests that `next-version` counts pre-releases up and that
+/// `changelog --unreleased --skip-prereleases` starts from the last release.
+#[test]
+#[serial]
+fn test_next_version_and_prerelease_aware_changelog() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    let git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+    };
+    let commit = |file: &str, subject: &str| {
+        std::fs::write(repo_path.join(file), subject).unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", subject]);
+    };
+    git(&["tag", "v1.0.0"]);
+    commit("paging.rs", "feat: add paging");
+    git(&["tag", "v1.1.0-rc.1"]);
+    commit("list.rs", "fix: handle empty list");
+
+    let next_version = |args: &[&str]| {
+        let output = tbdflow()
+            .current_dir(&repo_path)
+            .arg("next-version")
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    assert_eq!(next_version(&["--pre", "rc"]), "v1.1.0-rc.2");
+    assert_eq!(next_version(&[]), "v1.1.0");
+
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["changelog", "--unreleased"])
+        .assert()
+        .success()
+        .stdout(contains("handle empty list"))
+        .stdout(contains("add paging").not());
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["changelog", "--unreleased", "--skip-prereleases"])
+        .assert()
+        .success()
+        .stdout(contains("handle empty list"))
+        .stdout(contains("add paging"));
+
+    git(&["tag", "v1.1.0"]);
+    tbdflow()
+        .current_dir(&repo_path)
+        .arg("next-version")
+        .assert()
+        .failure()
+        .stderr(contains("Nothing to release since v1.1.0"));
+}
+
+#[test]
+#[serial]
+fn test_latest_tag_is_the_highest_version() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    let git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+    };
+    let commit = |file: &str, subject: &str| {
+        std::fs::write(repo_path.join(file), subject).unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", subject]);
+    };
+    commit("cache.rs", "feat: add cache");
+    git(&["tag", "v1.10.0"]);
+    commit("paging.rs", "feat: add paging");
+    // Nearer to HEAD, but neither is the highest version.
+    git(&["tag", "v1.9.0"]);
+    commit("list.rs", "fix: handle empty list");
+    git(&["tag", "deploy-42"]);
+
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["changelog", "--unreleased"])
+        .assert()
+        .success()
+        .stdout(contains("add paging"))
+        .stdout(contains("handle empty list"))
+        .stdout(contains("add cache").not());
+
+    std::fs::write(
+        repo_path.join(".tbdflow.local.yml"),
+        "automatic_tags:\n  release_prefix: \"v\"\n  tag_sort: nearest\n",
+    )
+    .unwrap();
+    commit("cli.rs", "fix: parse flags");
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["changelog", "--unreleased"])
+        .assert()
+        .success()
+        .stdout(contains("parse flags"))
+        .stdout(contains("handle empty list").not());
+}
+
+/// Tests that `config import` writes commitlint rules to the lint section.
+#[test]
+#[serial]
+fn test_config_import_from_commitlint() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    std::env::set_current_dir(&repo_path).unwrap();
+    std::fs::write(
+        repo_path.join(".tbdflow.yml"),
+        "# Team settings\nmain_branch_name: main\nstale_branch_threshold_days: 1\nbranch_types:\n  feat: \"feat/\"\nautomatic_tags:\n  release_prefix: \"v\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        repo_path.join(".commitlintrc.json"),
+        r#"{"rules": {"type-enum": [2, "always", ["feat", "fix"]], "header-max-length": [2, "always", 60], "signed-off-by": [2, "always"]}}"#,
+    )
+    .unwrap();
+
+    tbdflow()
+        .args(["config", "import", "--from", "commitlint"])
+        .assert()
+        .success()
+        .stdout(contains("Imported 3 lint settings from .commitlintrc.json"))
+        .stdout(contains("Not imported: signed-off-by"));
+    let written = std::fs::read_to_string(repo_path.join(".tbdflow.yml")).unwrap();
+    assert!(written.starts_with("# Team settings\n"));
+
+    tbdflow()
+        .args(["config", "get", "lint.subject_line_rules.max_length"])
+        .assert()
+        .success()
+        .stdout("60\n");
+    tbdflow()
+        .args(["commit", "-t", "chore", "-m", "tidy up", "--no-verify"])
+        .assert()
+        .failure()
+        .stderr(contains("Invalid commit type"));
+
+    tbdflow()
+        .args(["config", "import", "--from", "commitizen"])
+        .assert()
+        .failure()
+        .stderr(contains("No commitizen config found"));
+}
+
+/// Tests that `clean` lists leftovers first and removes them only once confirmed.
+#[test]
+#[serial]
+fn test_clean_lists_then_removes_leftovers() {
+    let (_dir, _bare_dir, repo_path) = setup_temp_git_repo();
+    let head = String::from_utf8(
+        std::process::Command::new("git")
+            .args(["rev-parse", "--short", "HEAD"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap()
+            .stdout,
+    )
+    .unwrap();
+    let state_dir = repo_path.join(".git/tbdflow");
+    std::fs::create_dir_all(&state_dir).unwrap();
+    let journal_path = state_dir.join("journal.jsonl");
+    std::fs::write(
+        &journal_path,
+        format!(
+            "{{\"timestamp\":\"t\",\"author\":\"a\",\"event\":\"review-approved\",\"commit\":\"{}\"}}\n\
+             {{\"timestamp\":\"t\",\"author\":\"a\",\"event\":\"review-approved\",\"commit\":\"deadbeef\"}}\n",
+            head.trim()
+        ),
+    )
+    .unwrap();
+    std::fs::write(state_dir.join("pair.json.corrupt"), "{").unwrap();
+    std::fs::write(
+        repo_path.join(".tbdflow-intent.json"),
+        r#"{"branch": "feat/gone", "started_at": "t", "notes": []}"#,
+    )
+    .unwrap();
+
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["--dry-run", "clean"])
+        .assert()
+        .success()
+        .stdout(contains("branch 'feat/gone' no longer exists"))
+        .stdout(contains("review-approved deadbeef"))
+        .stdout(contains("pair.json.corrupt"))
+        .stdout(contains(head.trim()).not())
+        .stdout(contains("Would remove 3 item(s)."));
+    tbdflow()
+        .current_dir(&repo_path)
+        .arg("clean")
+        .assert()
+        .success()
+        .stdout(contains("Nothing removed."));
+    assert!(repo_path.join(".tbdflow-intent.json").exists());
+
+    tbdflow()
+        .current_dir(&repo_path)
+        .args(["--yes", "clean"])
+        .assert()
+        .success()
+        .stdout(contains("Removed 3 item(s)."));
+    assert!(!repo_path.join(".tbdflow-intent.json").exists());
+    assert!(!state_dir.join("pair.json.corrupt").exists());
+    let journal = std::fs::read_to_string(&journal_path).unwrap();
+    assert!(journal.contains(head.trim()));
+    assert!(!journal.contains("deadbeef"));
+
+    tbdflow()
+        .current_dir(&repo_path)
+        .arg("clean")
+        .assert()
+        .success()
+        .stdout(contains("Nothing to clean."));
+}
@@ -2,6 +2,15 @@ use std::fs::write;
 use std::process::Command;
 use tempfile::{TempDir, tempdir};
 
+/// The tbdflow binary, with messages pinned to English whatever the
+/// machine's locale.
+#[allow(dead_code)]
+pub fn tbdflow() -> assert_cmd::Command {
+    let mut cmd = assert_cmd::Command::cargo_bin("tbdflow").unwrap();
+    cmd.env("TBDFLOW_LANG", "en");
+    cmd
+}
+
 /// Sets up a temporary Git repository for testing purposes.
 pub fn setup_temp_git_repo() -> (TempDir, TempDir, std::path::PathBuf) {
     let dir = tempdir().expect("create temp dir");
@@ -26,49 +35,49 @@ pub fn setup_temp_git_repo() -> (TempDir, TempDir, std::path::PathBuf) {
         .output()
         .unwrap();
     Command::new("git")
-        .args(&["config", "user.email", "test@example.com"])
+        .args(["config", "user.email", "test@example.com"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     Command::new("git")
-        .args(&["config", "user.name", "Test"])
+        .args(["config", "user.name", "Test"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     Command::new("git")
-        .args(&["config", "push.autoSetupRemote", "true"])
+        .args(["config", "push.autoSetupRemote", "true"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     let file_path = repo_path.join("README.md");
     write(&file_path, "test").unwrap();
     Command::new("git")
-        .args(&["add", "."])
+        .args(["add", "."])
         .current_dir(&repo_path)
         .output()
         .unwrap();
     Command::new("git")
-        .args(&["commit", "-m", "init"])
+        .args(["commit", "-m", "init"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
 
     // Add local bare repo as remote
     Command::new("git")
-        .args(&["remote", "add", "origin", bare_repo_path.to_str().unwrap()])
+        .args(["remote", "add", "origin", bare_repo_path.to_str().unwrap()])
         .current_dir(&repo_path)
         .output()
         .unwrap();
 
     // Push main to origin to set up tracking
     Command::new("git")
-        .args(&["push", "-u", "origin", "main"])
+        .args(["push", "-u", "origin", "main"])
         .current_dir(&repo_path)
         .output()
         .unwrap();
 
     let status = Command::new("git")
-        .args(&["status", "--porcelain"])
+        .args(["status", "--porcelain"])
         .current_dir(&repo_path)
         .output()
         .unwrap();